@@ -328,10 +328,168 @@ pub fn load_text_map(path: &Path) -> Result<Vec<(String, String)>> {
     Ok(pairs)
 }
 
+// ─── Glyph rasterization cache ───────────────────────────────────────────────
+
+/// A rasterized glyph's coverage buffer and layout metrics, relative to a
+/// pen position of `(0, 0)` so the same entry is reusable at any cursor
+/// position or baseline.
+struct CachedGlyph {
+    /// Glyph origin offset from the pen position, in pixels.
+    bounds_min: (i32, i32),
+    width: usize,
+    height: usize,
+    /// One coverage byte (0-255) per pixel, row-major, `width * height` long.
+    coverage: Vec<u8>,
+    h_advance: f32,
+}
+
+/// Cache of rasterized glyphs, keyed by `(font-stack index, char, quantized
+/// font height)`.
+///
+/// Bulk-anonymizing many screenshots redraws the same handful of characters
+/// (digits, "Player") over and over, and `modify_screenshot_pixels`'s
+/// shrink-to-fit loop calls `measure_text_width` repeatedly for the same
+/// text at several candidate heights -- so without a cache, `outline_glyph`
+/// reruns for glyphs it has already rasterized. Font height is quantized to
+/// the nearest 0.5px to bound the key space while still treating visually
+/// identical requests as cache hits. The font-stack index is part of the key
+/// because two fonts in a `FontStack` can map the same `char` to differently
+/// shaped glyphs.
+#[derive(Default)]
+pub struct GlyphCache {
+    entries: HashMap<(usize, char, u32), CachedGlyph>,
+}
+
+impl GlyphCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn quantize_height(font_height: f32) -> u32 {
+        (font_height * 2.0).round() as u32
+    }
+
+    /// Look up or rasterize the glyph for `ch` at `font_height`, resolving
+    /// it against the first font in `fonts` that actually has the glyph.
+    fn get_or_insert(&mut self, fonts: &FontStack, ch: char, font_height: f32) -> &CachedGlyph {
+        let (font_idx, font) = fonts.resolve(ch);
+        let key = (font_idx, ch, Self::quantize_height(font_height));
+        self.entries.entry(key).or_insert_with(|| {
+            let scale = ab_glyph::PxScale::from(font_height);
+            let scaled_font = font.as_scaled(scale);
+            let glyph_id = scaled_font.glyph_id(ch);
+            let h_advance = scaled_font.h_advance(glyph_id);
+            let glyph = glyph_id.with_scale_and_position(scale, ab_glyph::point(0.0, 0.0));
+
+            match font.outline_glyph(glyph) {
+                Some(outlined) => {
+                    let bounds = outlined.px_bounds();
+                    let width = (bounds.max.x - bounds.min.x) as usize;
+                    let height = (bounds.max.y - bounds.min.y) as usize;
+                    let mut coverage = vec![0u8; width * height];
+                    outlined.draw(|gx, gy, c| {
+                        coverage[gy as usize * width + gx as usize] = (c * 255.0) as u8;
+                    });
+                    CachedGlyph {
+                        bounds_min: (bounds.min.x as i32, bounds.min.y as i32),
+                        width,
+                        height,
+                        coverage,
+                        h_advance,
+                    }
+                }
+                None => CachedGlyph {
+                    bounds_min: (0, 0),
+                    width: 0,
+                    height: 0,
+                    coverage: Vec::new(),
+                    h_advance,
+                },
+            }
+        })
+    }
+}
+
 // ─── TrueType text rendering ─────────────────────────────────────────────────
 
-/// Load a system sans-serif font, trying several common paths.
-pub fn load_system_font() -> Result<FontVec> {
+/// Parse `data` as a font, coping with the same legacy-vs-Unicode name-table
+/// quirk `load_system_font` has always worked around: some `.ttc`/`.ttf`
+/// files only expose a usable cmap under font index 0 (the historical
+/// MacRoman-era layout), while single-font files need the plain
+/// `try_from_vec` path. Try index 0 first, then fall back.
+fn parse_font_data(data: &[u8]) -> std::result::Result<FontVec, ()> {
+    FontVec::try_from_vec_and_index(data.to_vec(), 0)
+        .or_else(|_| FontVec::try_from_vec(data.to_vec()))
+        .map_err(|_| ())
+}
+
+/// An ordered list of fonts consulted in turn for each character. A single
+/// font's cmap often can't cover every script a BBO username might use
+/// (accented Latin, Cyrillic, CJK); probing `glyph_id` and falling through
+/// to the next font on a `.notdef` (glyph id 0) result lets a fallback font
+/// fill in glyphs the primary font lacks, instead of silently rendering
+/// nothing for that character.
+pub struct FontStack {
+    fonts: Vec<FontVec>,
+    /// Raw font bytes parallel to `fonts`. `ab_glyph` doesn't expose the
+    /// bytes it parsed back out, and the optional `shaping` feature
+    /// (rustybuzz, which parses its own `Face`) needs the primary font's
+    /// bytes to build one -- so every font keeps its source bytes around,
+    /// not just the one that'll end up shaped.
+    font_data: Vec<Vec<u8>>,
+}
+
+impl FontStack {
+    /// A stack containing just the font parsed from `data`, with no
+    /// fallbacks yet.
+    pub fn new(font: FontVec, data: Vec<u8>) -> Self {
+        FontStack { fonts: vec![font], font_data: vec![data] }
+    }
+
+    /// Load `path` and append it to the stack as a fallback, tried after
+    /// every font already in the stack.
+    pub fn push_fallback_path(&mut self, path: &Path) -> Result<()> {
+        let data = std::fs::read(path)
+            .with_context(|| format!("Failed to read fallback font {}", path.display()))?;
+        let font = parse_font_data(&data)
+            .map_err(|_| anyhow::anyhow!("Failed to parse fallback font {}", path.display()))?;
+        self.fonts.push(font);
+        self.font_data.push(data);
+        Ok(())
+    }
+
+    /// The font (and its index in the stack) to use for `ch`: the first
+    /// font whose cmap maps it to something other than `.notdef`, or the
+    /// primary font if none of them have it (so callers still get *a*
+    /// glyph -- typically `.notdef`'s empty box -- rather than a panic).
+    fn resolve(&self, ch: char) -> (usize, &FontVec) {
+        for (idx, font) in self.fonts.iter().enumerate() {
+            if font.glyph_id(ch).0 != 0 {
+                return (idx, font);
+            }
+        }
+        (0, &self.fonts[0])
+    }
+
+    /// The stack's primary (first-loaded) font, used for metrics like
+    /// ascent that should stay stable across a run of mixed-script text
+    /// rather than jumping around per character.
+    fn primary(&self) -> &FontVec {
+        &self.fonts[0]
+    }
+
+    /// The primary font's raw bytes, for the `shaping` feature's rustybuzz
+    /// `Face`, which needs its own parse of the font data.
+    #[cfg(feature = "shaping")]
+    fn primary_data(&self) -> &[u8] {
+        &self.font_data[0]
+    }
+}
+
+/// Load a system sans-serif font, trying several common paths, as the
+/// primary entry of a `FontStack`. Use `FontStack::push_fallback_path` to
+/// add fonts covering scripts the primary one doesn't.
+pub fn load_system_font() -> Result<FontStack> {
     let candidates = [
         "/System/Library/Fonts/Helvetica.ttc",
         "/System/Library/Fonts/SFNSText.ttf",
@@ -341,80 +499,207 @@ pub fn load_system_font() -> Result<FontVec> {
     ];
     for path in &candidates {
         if let Ok(data) = std::fs::read(path) {
-            if let Ok(font) = FontVec::try_from_vec_and_index(data.clone(), 0) {
-                println!("  Loaded font: {}", path);
-                return Ok(font);
-            }
-            if let Ok(font) = FontVec::try_from_vec(data) {
+            if let Ok(font) = parse_font_data(&data) {
                 println!("  Loaded font: {}", path);
-                return Ok(font);
+                return Ok(FontStack::new(font, data));
             }
         }
     }
     anyhow::bail!("No system font found. Tried: {}", candidates.join(", "))
 }
 
+/// Decode an sRGB-encoded channel byte (0-255) to linear light (0.0-1.0),
+/// via the exact piecewise sRGB transfer function. `draw_text` looks this up
+/// in `srgb_decode_lut` (one entry per possible byte value) instead of
+/// calling it directly, so blending a glyph over thousands of pixels
+/// doesn't repeat the branch-and-`powf` per pixel.
+fn srgb_to_linear(c: u8) -> f32 {
+    let c = c as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// The 256-entry sRGB→linear decode table, built once and shared by every
+/// `draw_text` call.
+fn srgb_decode_lut() -> &'static [f32; 256] {
+    lazy_static::lazy_static! {
+        static ref LUT: [f32; 256] = {
+            let mut lut = [0f32; 256];
+            for (i, entry) in lut.iter_mut().enumerate() {
+                *entry = srgb_to_linear(i as u8);
+            }
+            lut
+        };
+    }
+    &LUT
+}
+
+/// Encode a linear-light channel value (0.0-1.0) back to an sRGB byte.
+fn linear_to_srgb(c: f32) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    let encoded = if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (encoded * 255.0).round() as u8
+}
+
 /// Draw anti-aliased text onto raw pixel data using a TrueType font.
 ///
 /// `channels` is the number of bytes per pixel (3 for RGB, 4 for RGBA).
 /// `font_height` is the desired pixel height of the rendered text.
-/// Text is alpha-blended over the existing background.
+/// Text is alpha-blended over the existing background. `cache` is consulted
+/// before rasterizing each glyph; pass the same `GlyphCache` across calls
+/// (e.g. across a bulk CSV run) to avoid re-rasterizing repeated characters.
+/// The cursor advances by each glyph's `h_advance` plus the font's kerning
+/// adjustment between it and the previous glyph (skipped across a
+/// fallback-font switch, since kerning pairs don't cross font files).
 #[allow(clippy::too_many_arguments)]
 pub fn draw_text(
     pixels: &mut [u8],
     img_w: usize,
     img_h: usize,
     channels: usize,
-    font: &FontVec,
+    fonts: &FontStack,
     text: &str,
     start_x: f32,
     start_y: f32,
     font_height: f32,
     fg: (u8, u8, u8),
+    cache: &mut GlyphCache,
 ) {
     let scale = ab_glyph::PxScale::from(font_height);
-    let scaled_font = font.as_scaled(scale);
+    let baseline_y = start_y + fonts.primary().as_scaled(scale).ascent();
+    let lut = srgb_decode_lut();
+    let fg_linear = (srgb_to_linear(fg.0), srgb_to_linear(fg.1), srgb_to_linear(fg.2));
 
     let mut cursor_x = start_x;
+    let mut prev_glyph: Option<(usize, ab_glyph::GlyphId)> = None;
 
     for ch in text.chars() {
-        let glyph_id = scaled_font.glyph_id(ch);
-        let glyph = glyph_id.with_scale_and_position(
-            scale,
-            ab_glyph::point(cursor_x, start_y + scaled_font.ascent()),
-        );
+        let (font_idx, font) = fonts.resolve(ch);
+        let glyph_id = font.as_scaled(scale).glyph_id(ch);
+        if let Some((prev_idx, prev_id)) = prev_glyph {
+            // Kerning pairs are per-font; a fallback-font switch between
+            // two characters has no shared pair table to consult.
+            if prev_idx == font_idx {
+                cursor_x += font.as_scaled(scale).kern(prev_id, glyph_id);
+            }
+        }
+        prev_glyph = Some((font_idx, glyph_id));
+
+        let glyph = cache.get_or_insert(fonts, ch, font_height);
+        let origin_x = cursor_x as i32 + glyph.bounds_min.0;
+        let origin_y = baseline_y as i32 + glyph.bounds_min.1;
 
-        if let Some(outlined) = font.outline_glyph(glyph) {
-            let bounds = outlined.px_bounds();
-            outlined.draw(|gx, gy, coverage| {
-                let px = gx as usize + bounds.min.x as usize;
-                let py = gy as usize + bounds.min.y as usize;
-                if px >= img_w || py >= img_h {
-                    return;
+        for gy in 0..glyph.height {
+            for gx in 0..glyph.width {
+                let coverage = glyph.coverage[gy * glyph.width + gx];
+                if coverage == 0 {
+                    continue;
                 }
-                let idx = (py * img_w + px) * channels;
+                let px = origin_x + gx as i32;
+                let py = origin_y + gy as i32;
+                if px < 0 || py < 0 || px as usize >= img_w || py as usize >= img_h {
+                    continue;
+                }
+                let idx = (py as usize * img_w + px as usize) * channels;
                 if idx + 2 >= pixels.len() {
-                    return;
+                    continue;
                 }
-                let alpha = coverage;
+                // Blend in linear light, not directly on the sRGB bytes:
+                // compositing gamma-encoded values darkens anti-aliased
+                // edges (mid-coverage pixels end up too dark), which is
+                // especially visible against the light-on-dark case below.
+                let alpha = coverage as f32 / 255.0;
                 let inv = 1.0 - alpha;
-                pixels[idx] = (fg.0 as f32 * alpha + pixels[idx] as f32 * inv) as u8;
-                pixels[idx + 1] = (fg.1 as f32 * alpha + pixels[idx + 1] as f32 * inv) as u8;
-                pixels[idx + 2] = (fg.2 as f32 * alpha + pixels[idx + 2] as f32 * inv) as u8;
-            });
+                pixels[idx] = linear_to_srgb(fg_linear.0 * alpha + lut[pixels[idx] as usize] * inv);
+                pixels[idx + 1] =
+                    linear_to_srgb(fg_linear.1 * alpha + lut[pixels[idx + 1] as usize] * inv);
+                pixels[idx + 2] =
+                    linear_to_srgb(fg_linear.2 * alpha + lut[pixels[idx + 2] as usize] * inv);
+            }
         }
 
-        cursor_x += scaled_font.h_advance(glyph_id);
+        cursor_x += glyph.h_advance;
     }
 }
 
-/// Measure the width of a string at a given font height (in pixels).
-pub fn measure_text_width(font: &FontVec, text: &str, font_height: f32) -> f32 {
+/// Measure the width of a string at a given font height (in pixels),
+/// including kerning between successive glyphs -- the same advance
+/// computation `draw_text` uses, so the shrink-to-fit loop in
+/// `modify_screenshot_pixels` sizes text to what will actually be drawn.
+/// Shares `cache` with `draw_text` so it doesn't re-rasterize a glyph
+/// that's about to be drawn.
+pub fn measure_text_width(fonts: &FontStack, text: &str, font_height: f32, cache: &mut GlyphCache) -> f32 {
     let scale = ab_glyph::PxScale::from(font_height);
-    let scaled = font.as_scaled(scale);
-    text.chars()
-        .map(|ch| scaled.h_advance(scaled.glyph_id(ch)))
-        .sum()
+    let mut width = 0.0f32;
+    let mut prev_glyph: Option<(usize, ab_glyph::GlyphId)> = None;
+
+    for ch in text.chars() {
+        let (font_idx, font) = fonts.resolve(ch);
+        let scaled_font = font.as_scaled(scale);
+        let glyph_id = scaled_font.glyph_id(ch);
+        if let Some((prev_idx, prev_id)) = prev_glyph {
+            if prev_idx == font_idx {
+                width += scaled_font.kern(prev_id, glyph_id);
+            }
+        }
+        prev_glyph = Some((font_idx, glyph_id));
+        width += cache.get_or_insert(fonts, ch, font_height).h_advance;
+    }
+    width
+}
+
+/// One glyph of a `shape_text` run: its id in the primary font and its pen
+/// position (in the same pixel units as `font_height`), relative to the
+/// run's start.
+#[cfg(feature = "shaping")]
+pub struct ShapedGlyph {
+    pub glyph_id: ab_glyph::GlyphId,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Shape `text` against the stack's primary font with rustybuzz, returning
+/// positioned glyph ids.
+///
+/// The naive per-char advance-plus-kern loop `draw_text`/`measure_text_width`
+/// use is enough for Latin text, but the accented/Cyrillic/CJK usernames
+/// `FontStack` exists to render can need real shaping -- combining marks,
+/// ligatures, and GPOS-driven positioning a simple cursor walk can't
+/// reproduce. This is gated behind the `shaping` feature rather than always
+/// on, so an ASCII-only build doesn't pull in rustybuzz.
+#[cfg(feature = "shaping")]
+pub fn shape_text(fonts: &FontStack, text: &str, font_height: f32) -> Vec<ShapedGlyph> {
+    let face = match rustybuzz::Face::from_slice(fonts.primary_data(), 0) {
+        Some(f) => f,
+        None => return Vec::new(),
+    };
+    let scale = font_height / face.units_per_em() as f32;
+
+    let mut buffer = rustybuzz::UnicodeBuffer::new();
+    buffer.push_str(text);
+    buffer.guess_segment_properties();
+    let shaped = rustybuzz::shape(&face, &[], buffer);
+
+    let mut cursor_x = 0.0f32;
+    let mut cursor_y = 0.0f32;
+    let mut glyphs = Vec::with_capacity(shaped.len());
+    for (info, pos) in shaped.glyph_infos().iter().zip(shaped.glyph_positions()) {
+        glyphs.push(ShapedGlyph {
+            glyph_id: ab_glyph::GlyphId(info.glyph_id as u16),
+            x: cursor_x + pos.x_offset as f32 * scale,
+            y: cursor_y + pos.y_offset as f32 * scale,
+        });
+        cursor_x += pos.x_advance as f32 * scale;
+        cursor_y += pos.y_advance as f32 * scale;
+    }
+    glyphs
 }
 
 /// Sample the dominant background colour from the rightmost column of a
@@ -467,8 +752,9 @@ pub fn modify_screenshot_pixels(
     img_w: usize,
     img_h: usize,
     names: &[String; 4],
-    font: &FontVec,
+    fonts: &FontStack,
     channels: usize,
+    cache: &mut GlyphCache,
 ) {
     let rects = bbo_name_rects(); // [N, S, W, E]
                                   // Map rect index → names index: N→names[2], S→names[0], W→names[1], E→names[3]
@@ -511,7 +797,7 @@ pub fn modify_screenshot_pixels(
         let mut font_h = rect_h * 0.66;
         let padding = font_h * 0.3;
         loop {
-            let text_w = measure_text_width(font, name, font_h);
+            let text_w = measure_text_width(fonts, name, font_h, cache);
             if text_w + padding <= rect_w || font_h <= 8.0 {
                 break;
             }
@@ -523,7 +809,7 @@ pub fn modify_screenshot_pixels(
         let text_y = y1 as f32 + (rect_h - font_h) * 0.5;
 
         draw_text(
-            pixels, img_w, img_h, channels, font, name, text_x, text_y, font_h, fg,
+            pixels, img_w, img_h, channels, fonts, name, text_x, text_y, font_h, fg, cache,
         );
     }
 }
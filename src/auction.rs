@@ -0,0 +1,264 @@
+//! Structured auction type.
+//!
+//! `bbo_csv`'s `extract_contract_from_lin` and `extract_declarer_from_auction`
+//! used to re-walk a LIN auction's bid strings inline with brittle uppercase
+//! matching, and could hand back the wrong declarer on artificial auctions
+//! (the strain named first by a convention call, then abandoned, still has
+//! to be tracked separately from the strain the final contract actually
+//! ends in). `Call` and `Auction` parse and validate the sequence once, the
+//! same way [`crate::contract::Contract`] replaced ad-hoc contract-string
+//! parsing.
+//!
+//! Seats here are plain `0..=3` in dealing-rotation order (dealer, then
+//! clockwise) -- North/East/South/West by convention -- not the
+//! `bridge_solver` seat constants used elsewhere for cardplay, since the
+//! auction only ever rotates N-E-S-W regardless of how the solver numbers
+//! its seats.
+
+use std::str::FromStr;
+
+use crate::contract::{Contract, Strain};
+use crate::scoring::Doubled;
+
+/// One call in an auction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Call {
+    Pass,
+    Double,
+    Redouble,
+    Bid { level: u8, strain: Strain },
+}
+
+impl FromStr for Call {
+    type Err = String;
+
+    /// Normalizes the LIN/PBN spellings for a call: `"P"`/`"PASS"`,
+    /// `"X"`/`"D"`/`"DBL"`, `"XX"`/`"R"`/`"RDBL"`, and bids like `"3NT"`
+    /// (strain without a doubling suffix -- a `Call::Bid` is never doubled
+    /// itself, [`Auction`] tracks doubling as a separate call).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_uppercase();
+
+        match s.as_str() {
+            "P" | "PASS" => return Ok(Call::Pass),
+            "X" | "D" | "DBL" => return Ok(Call::Double),
+            "XX" | "R" | "RDBL" => return Ok(Call::Redouble),
+            _ => {}
+        }
+
+        let mut chars = s.chars();
+        let level = chars
+            .next()
+            .and_then(|c| c.to_digit(10))
+            .map(|d| d as u8)
+            .filter(|level| (1..=7).contains(level))
+            .ok_or_else(|| format!("Could not parse call: {}", s))?;
+
+        let strain = match chars.as_str() {
+            "NT" | "N" => Strain::NoTrump,
+            "S" => Strain::Spades,
+            "H" => Strain::Hearts,
+            "D" => Strain::Diamonds,
+            "C" => Strain::Clubs,
+            _ => return Err(format!("Could not parse strain from call: {}", s)),
+        };
+
+        Ok(Call::Bid { level, strain })
+    }
+}
+
+/// A validated bidding sequence, plus the dealer seat (`0..=3`, N/E/S/W)
+/// the first call in `calls` belongs to.
+#[derive(Debug, Clone)]
+pub struct Auction {
+    dealer: usize,
+    calls: Vec<Call>,
+}
+
+/// The contract and declarer an [`Auction`] settled on.
+#[derive(Debug, Clone, Copy)]
+pub struct AuctionResult {
+    pub contract: Contract,
+    /// Seat (`0..=3`, N/E/S/W) of the declaring hand.
+    pub declarer: usize,
+}
+
+impl Auction {
+    /// Validates `calls` as a legal bidding sequence starting at `dealer`
+    /// (insufficient bids, a double with no live opposing contract to
+    /// double, a redouble without a prior double) and wraps it up.
+    pub fn new(dealer: usize, calls: Vec<Call>) -> Result<Self, String> {
+        let mut highest: Option<(u8, Strain)> = None;
+        let mut highest_bidder: Option<usize> = None;
+        let mut doubled = false;
+        let mut redoubled = false;
+
+        for (i, call) in calls.iter().enumerate() {
+            let seat = (dealer + i) % 4;
+            match call {
+                Call::Pass => {}
+                Call::Bid { level, strain } => {
+                    if let Some((hi_level, hi_strain)) = highest {
+                        if (*level, strain.rank()) <= (hi_level, hi_strain.rank()) {
+                            return Err(format!(
+                                "Insufficient bid at seat {}: {}{}",
+                                seat, level, strain
+                            ));
+                        }
+                    }
+                    highest = Some((*level, *strain));
+                    highest_bidder = Some(seat);
+                    doubled = false;
+                    redoubled = false;
+                }
+                Call::Double => {
+                    let Some(bidder) = highest_bidder else {
+                        return Err(format!("Double with no contract on the table (seat {})", seat));
+                    };
+                    if doubled || redoubled {
+                        return Err(format!("Double of an already-doubled contract (seat {})", seat));
+                    }
+                    if bidder % 2 == seat % 2 {
+                        return Err(format!("Double of partner's own contract (seat {})", seat));
+                    }
+                    doubled = true;
+                }
+                Call::Redouble => {
+                    let Some(bidder) = highest_bidder else {
+                        return Err(format!("Redouble with no contract on the table (seat {})", seat));
+                    };
+                    if !doubled {
+                        return Err(format!("Redouble without a prior double (seat {})", seat));
+                    }
+                    if bidder % 2 != seat % 2 {
+                        return Err(format!(
+                            "Redouble of an opponent's double by the wrong side (seat {})",
+                            seat
+                        ));
+                    }
+                    doubled = false;
+                    redoubled = true;
+                }
+            }
+        }
+
+        Ok(Auction { dealer, calls })
+    }
+
+    /// The final contract and declarer, or `None` if the auction passed out
+    /// (no bid was ever made). Declarer is the first member of the
+    /// declaring partnership to have named the final strain, which may be
+    /// an earlier, lower bid than the final one if partner raised it.
+    pub fn result(&self) -> Option<AuctionResult> {
+        let mut level = 0u8;
+        let mut strain = None;
+        let mut doubling = Doubled::Undoubled;
+        let mut final_bidder = None;
+        // First seat of each partnership (0 = N/S, 1 = E/W) to name each strain.
+        let mut first_namer: [[Option<usize>; 5]; 2] = [[None; 5]; 2];
+
+        for (i, call) in self.calls.iter().enumerate() {
+            let seat = (self.dealer + i) % 4;
+            match call {
+                Call::Pass => {}
+                Call::Double => doubling = Doubled::Doubled,
+                Call::Redouble => doubling = Doubled::Redoubled,
+                Call::Bid { level: l, strain: s } => {
+                    level = *l;
+                    strain = Some(*s);
+                    doubling = Doubled::Undoubled;
+                    final_bidder = Some(seat);
+                    let slot = &mut first_namer[seat % 2][s.rank() as usize];
+                    if slot.is_none() {
+                        *slot = Some(seat);
+                    }
+                }
+            }
+        }
+
+        let strain = strain?;
+        let final_bidder = final_bidder?;
+        let declarer = first_namer[final_bidder % 2][strain.rank() as usize]?;
+
+        Some(AuctionResult { contract: Contract { level, strain, doubling }, declarer })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calls(strs: &[&str]) -> Vec<Call> {
+        strs.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_call_from_str() {
+        assert_eq!("P".parse::<Call>(), Ok(Call::Pass));
+        assert_eq!("pass".parse::<Call>(), Ok(Call::Pass));
+        assert_eq!("X".parse::<Call>(), Ok(Call::Double));
+        assert_eq!("dbl".parse::<Call>(), Ok(Call::Double));
+        assert_eq!("XX".parse::<Call>(), Ok(Call::Redouble));
+        assert_eq!("rdbl".parse::<Call>(), Ok(Call::Redouble));
+        assert_eq!("3NT".parse::<Call>(), Ok(Call::Bid { level: 3, strain: Strain::NoTrump }));
+        assert_eq!("1c".parse::<Call>(), Ok(Call::Bid { level: 1, strain: Strain::Clubs }));
+        assert!("8H".parse::<Call>().is_err());
+        assert!("XYZ".parse::<Call>().is_err());
+    }
+
+    #[test]
+    fn test_auction_result_simple_contract() {
+        // Dealer North passes, East opens 1S, South passes, West passes.
+        let auction = Auction::new(0, calls(&["P", "1S", "P", "P"])).unwrap();
+        let result = auction.result().unwrap();
+        assert_eq!(result.contract.level, 1);
+        assert_eq!(result.contract.strain, Strain::Spades);
+        assert_eq!(result.contract.doubling, Doubled::Undoubled);
+        assert_eq!(result.declarer, 1);
+    }
+
+    #[test]
+    fn test_auction_result_declarer_is_first_to_name_strain() {
+        // North opens 1S, East passes, South raises to 4S: North named
+        // spades first, so North declares even though South bid the game.
+        let auction = Auction::new(0, calls(&["1S", "P", "4S", "P", "P", "P"])).unwrap();
+        let result = auction.result().unwrap();
+        assert_eq!(result.contract.level, 4);
+        assert_eq!(result.contract.strain, Strain::Spades);
+        assert_eq!(result.declarer, 0);
+    }
+
+    #[test]
+    fn test_auction_result_doubled_and_redoubled() {
+        let auction = Auction::new(0, calls(&["1NT", "P", "P", "X", "XX", "P", "P", "P"])).unwrap();
+        let result = auction.result().unwrap();
+        assert_eq!(result.contract.doubling, Doubled::Redoubled);
+    }
+
+    #[test]
+    fn test_auction_result_passed_out() {
+        let auction = Auction::new(0, calls(&["P", "P", "P", "P"])).unwrap();
+        assert!(auction.result().is_none());
+    }
+
+    #[test]
+    fn test_auction_rejects_insufficient_bid() {
+        assert!(Auction::new(0, calls(&["1S", "1H"])).is_err());
+    }
+
+    #[test]
+    fn test_auction_rejects_double_with_no_contract() {
+        assert!(Auction::new(0, calls(&["P", "X"])).is_err());
+    }
+
+    #[test]
+    fn test_auction_rejects_double_of_own_side() {
+        // North bids 1S, South (North's partner) tries to double it.
+        assert!(Auction::new(0, calls(&["1S", "P", "X"])).is_err());
+    }
+
+    #[test]
+    fn test_auction_rejects_redouble_without_double() {
+        assert!(Auction::new(0, calls(&["1S", "P", "P", "XX"])).is_err());
+    }
+}
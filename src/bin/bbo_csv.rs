@@ -5,10 +5,20 @@
 //! double-dummy analysis.
 
 use anyhow::{Context, Result};
-use edgar_defense_toolkit::dd_analysis::compute_dd_costs;
+use edgar_defense_toolkit::dd_analysis::{self, compute_dd_costs};
+use edgar_defense_toolkit::dd_table;
+use edgar_defense_toolkit::rate_limit::{bucket_for, url_host, TokenBucket};
+use edgar_defense_toolkit::scoring::{points_to_imps, score_contract};
+use edgar_defense_toolkit::stats::{
+    bootstrap_def_minus_decl, decay_weight, diff_se_weighted, replacement_defending_rate,
+    two_proportion_z_test, weighted_error_stats, wilson_ci_f64, write_stats_export,
+    z_test_diff_vs_baseline, z_test_diff_vs_baseline_weighted, DealObservation,
+    PlayRole, PlayerStats, MIN_DEALS_FOR_SUSPICIOUS,
+};
 use bridge_parsers::lin::parse_lin_from_url;
 use bridge_parsers::tinyurl::UrlResolver;
-use bridge_solver::{NORTH, EAST, SOUTH, WEST, SPADE, HEART, DIAMOND, CLUB};
+use bridge_parsers::Vulnerability;
+use bridge_solver::{Hands, NORTH, EAST, SOUTH, WEST, SPADE, HEART, DIAMOND, CLUB};
 // Card, Rank, Suit only used in #[cfg(test)] functions
 #[cfg(test)]
 use bridge_parsers::{Card, Rank, Suit};
@@ -18,14 +28,20 @@ use bridge_solver::NOTRUMP;
 use bridge_solver::cards::card_of;
 use clap::{Parser, Subcommand};
 use csv::{Reader, ReaderBuilder, Writer, StringRecord};
+use rand::Rng;
 use rayon::prelude::*;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::{HashSet, HashMap};
 use std::fs::File;
-use std::io::{BufRead, BufReader, Write as IoWrite};
-use std::path::PathBuf;
+use std::hash::{Hash, Hasher};
+use indicatif::{ProgressBar, ProgressStyle};
+use std::fmt::Write as FmtWrite;
+use std::io::{BufRead, BufReader, IsTerminal, Write as IoWrite};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 // ============================================================================
 // BBO CSV Preprocessing - Fix malformed quoted fields
@@ -71,10 +87,36 @@ fn fix_bbo_csv_line(line: &str) -> String {
     line.to_string()
 }
 
-/// Read a BBO CSV file and preprocess to fix malformed lines
-fn read_bbo_csv_fixed(path: &PathBuf) -> Result<String> {
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+
+/// Open `path` for reading, transparently decompressing it if its leading
+/// magic bytes identify it as zstd or gzip. Large BBO exports with full
+/// double-dummy columns are often shipped compressed.
+fn open_maybe_compressed(path: &PathBuf) -> Result<Box<dyn BufRead>> {
     let file = File::open(path).context("Failed to open input file")?;
-    let reader = BufReader::new(file);
+    let mut reader = BufReader::new(file);
+
+    let mut magic = [0u8; 4];
+    let peeked = reader.fill_buf().context("Failed to read input file")?;
+    let n = peeked.len().min(4);
+    magic[..n].copy_from_slice(&peeked[..n]);
+
+    if n >= 4 && magic == ZSTD_MAGIC {
+        let decoder = zstd::stream::read::Decoder::new(reader).context("Failed to open zstd stream")?;
+        Ok(Box::new(BufReader::new(decoder)))
+    } else if n >= 2 && magic[..2] == GZIP_MAGIC {
+        let decoder = flate2::read::GzDecoder::new(reader);
+        Ok(Box::new(BufReader::new(decoder)))
+    } else {
+        Ok(Box::new(reader))
+    }
+}
+
+/// Read a (possibly zstd/gzip-compressed) BBO CSV file and preprocess to fix
+/// malformed lines.
+fn read_bbo_csv_fixed(path: &PathBuf) -> Result<String> {
+    let reader = open_maybe_compressed(path)?;
     let mut output = String::new();
 
     for line in reader.lines() {
@@ -87,6 +129,20 @@ fn read_bbo_csv_fixed(path: &PathBuf) -> Result<String> {
     Ok(output)
 }
 
+/// Open `path` for writing, wrapping it in a zstd encoder when the path ends
+/// in `.zst` so anonymized corpora can be re-archived without a separate step.
+fn create_maybe_compressed(path: &PathBuf) -> Result<Box<dyn IoWrite>> {
+    let file = File::create(path).with_context(|| format!("Failed to create output file {:?}", path))?;
+    if path.extension().and_then(|e| e.to_str()) == Some("zst") {
+        let encoder = zstd::stream::write::Encoder::new(file, 0)
+            .context("Failed to create zstd encoder")?
+            .auto_finish();
+        Ok(Box::new(encoder))
+    } else {
+        Ok(Box::new(file))
+    }
+}
+
 #[derive(Parser)]
 #[command(name = "bbo-csv")]
 #[command(about = "Extract cardplay data from BBO hand records in CSV files")]
@@ -123,9 +179,28 @@ enum Commands {
         #[arg(long, default_value = "2000")]
         batch_delay_ms: u64,
 
+        /// Retries for a transient error (rate limit, timeout, 5xx) before
+        /// giving up and writing ERROR: into the row
+        #[arg(long, default_value = "4")]
+        max_retries: u32,
+
         /// Resume from previous run (skip rows with existing cardplay data)
         #[arg(long)]
         resume: bool,
+
+        /// Persistent content-addressed cache directory for resolved LIN
+        /// payloads. A URL whose payload is already cached is served from
+        /// disk (integrity-checked against its digest) instead of re-fetched
+        /// from BBO.
+        #[arg(long)]
+        cache_dir: Option<PathBuf>,
+
+        /// Treat `input`/`output` as directories: recurse into `input`
+        /// (depth-first, skipping dotfile entries), run this subcommand on
+        /// every `*.csv` found, and mirror each result under `output` at
+        /// the same relative path.
+        #[arg(long)]
+        recursive: bool,
     },
 
     /// Analyze double-dummy cost for each card played
@@ -149,13 +224,56 @@ enum Commands {
         /// Save progress every N rows
         #[arg(long, default_value = "100")]
         checkpoint_interval: usize,
+
+        /// Row-selection predicate, repeatable (combined with AND semantics).
+        /// Supports `column=value` (exact match), `column~text` (case-insensitive
+        /// substring), and `column=min..max` (inclusive numeric range).
+        /// Example: --filter "Con~NT" --filter "Max DD=8..11"
+        #[arg(long = "filter")]
+        filters: Vec<String>,
+
+        /// Measure sustained DD throughput instead of analyzing: repeatedly
+        /// process a fixed sample of boards until --bench-duration-secs
+        /// elapses, then report boards/sec, card-evaluations/sec, p50/p95
+        /// per-board latency, and thread utilization at --threads.
+        #[arg(long)]
+        bench: bool,
+
+        /// Number of boards to sample from the input for --bench
+        #[arg(long, default_value = "50")]
+        bench_sample_size: usize,
+
+        /// Target wall-clock duration for --bench, in seconds
+        #[arg(long, default_value = "10")]
+        bench_duration_secs: u64,
+
+        /// Treat `input`/`output` as directories: recurse into `input`
+        /// (depth-first, skipping dotfile entries), run this subcommand on
+        /// every `*.csv` found, and mirror each result under `output` at
+        /// the same relative path. Not combined with --bench.
+        #[arg(long)]
+        recursive: bool,
+
+        /// Also compute the single-dummy-vs-double-dummy gap check: flag
+        /// defending tricks that were genuinely ambiguous under restricted
+        /// (single-dummy) information but resolved to the double-dummy-optimal
+        /// line anyway, via `DD_<seat>_SDAmbiguous`/`DD_<seat>_SDExcessMatch`.
+        /// Off by default -- a constrained redeal sample per trick is far more
+        /// expensive than the rest of this pipeline.
+        #[arg(long)]
+        single_dummy_gap: bool,
+
+        /// Constrained-redeal sample count per trick for --single-dummy-gap.
+        #[arg(long, default_value = "30")]
+        single_dummy_samples: usize,
     },
 
     /// Anonymize usernames in CSV file.
     ///
-    /// Replaces player names with anonymized versions using keyed hashing for
-    /// reproducibility. The same name always maps to the same anonymized value
-    /// when using the same key. Also processes player names in LIN_URL column.
+    /// Replaces player names with anonymized versions using a salted,
+    /// HMAC-SHA256-keyed MAC for reproducibility: the same name maps to the
+    /// same anonymized value across every file that shares a `--key` and
+    /// `--map-file`. Also processes player names in LIN_URL column.
     Anonymize {
         /// Input CSV file
         #[arg(short, long)]
@@ -180,6 +298,53 @@ enum Commands {
         /// LIN_URL column is also processed automatically (pn| tag).
         #[arg(long, default_value = "N,S,E,W,Ob name,Dec name,Leader", value_delimiter = ',')]
         columns: Vec<String>,
+
+        /// Self-describing JSON sidecar (e.g. `output.map.json`) recording the
+        /// real-name <-> pseudonym table, plus the salt and MAC algorithm
+        /// that produced it. If it exists and matches `key`, prior mappings
+        /// and the salt are reused so pseudonyms stay consistent across
+        /// files; the (possibly extended) table is written back afterward.
+        /// Required to later reverse the run with `deanonymize`.
+        #[arg(long)]
+        map_file: Option<PathBuf>,
+
+        /// Treat `input`/`output` as directories: recurse into `input`
+        /// (depth-first, skipping dotfile entries), anonymize every
+        /// `*.csv` found, and mirror each result under `output` at the
+        /// same relative path. `map_file`, if set, is shared and reused
+        /// across every file so pseudonyms stay consistent.
+        #[arg(long)]
+        recursive: bool,
+
+        /// Suppress the progress bar (always off when stderr isn't a
+        /// terminal, e.g. when output is redirected to a log file)
+        #[arg(long)]
+        quiet: bool,
+    },
+
+    /// Reverse a previous `anonymize` run using its saved mapping sidecar
+    DeAnonymize {
+        /// Anonymized input CSV file
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Output CSV file with real names restored
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// JSON mapping sidecar saved by a previous `anonymize --map-file` run
+        #[arg(long)]
+        map_file: PathBuf,
+
+        /// Secret key used for the original `anonymize` run. Must match the
+        /// sidecar's stored key fingerprint, or the reversal is refused.
+        #[arg(short, long, env = "BBO_ANON_KEY")]
+        key: String,
+
+        /// Columns containing pseudonyms to reverse.
+        /// LIN_URL column is also processed automatically (pn| tag).
+        #[arg(long, default_value = "N,S,E,W,Ob name,Dec name,Leader", value_delimiter = ',')]
+        columns: Vec<String>,
     },
 
     /// Analyze DD error statistics by player and role (declaring vs defending)
@@ -195,6 +360,52 @@ enum Commands {
         /// Output detailed CSV with per-player stats
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Treat `input` as a directory and recurse into it (depth-first,
+        /// skipping dotfile entries), merging the per-player counters from
+        /// every `*.csv` found into one combined report -- a whole season
+        /// of tournament exports analyzed as a single field.
+        #[arg(long)]
+        recursive: bool,
+
+        /// Bootstrap iterations for the Def-Decl confidence interval and
+        /// p-value of the top two subjects, resampling each one's per-deal
+        /// observations with replacement instead of relying on
+        /// `z_test_diff_vs_baseline`'s normal approximation (which returns
+        /// NaN below 30 plays and is unreliable for small, correlated
+        /// per-deal samples). Set to 0 to skip and print only the z-test.
+        #[arg(long, default_value = "10000")]
+        bootstrap: u64,
+
+        /// Steepness of the logistic `p_loss` curve used for `Decl Acc%` /
+        /// `Def Acc%`: `p_loss = 1 / (1 + exp(-k * (cost - accuracy-c0)))`,
+        /// so a play costing exactly `accuracy-c0` tricks scores 0.5.
+        #[arg(long, default_value = "3.0")]
+        accuracy_k: f64,
+
+        /// Cost, in tricks, centered by the logistic `p_loss` curve -- the
+        /// default puts a clean play (cost 0) at low loss probability and a
+        /// 1-trick blunder at high loss probability.
+        #[arg(long, default_value = "0.5")]
+        accuracy_c0: f64,
+
+        /// Exponential recency-decay half-life, in deals, for an additional
+        /// recency-weighted error-rate section. Disabled by default (`None`).
+        /// Weights each deal by `0.5^(age/half_life)`, where age is measured
+        /// against the player's own most recent deal using the deal's row
+        /// position in the input CSV as a proxy clock (these CSVs don't
+        /// reliably carry a date column). The effective sample size behind
+        /// the weighted rate is the Kish ESS, `(sum w)^2 / sum(w^2)`.
+        #[arg(long)]
+        half_life: Option<f64>,
+
+        /// Write every player's stats (plus the FIELD baseline) as
+        /// structured JSON to this path, with raw `(count/total)` fractions
+        /// alongside each percentage and a short description per field --
+        /// for feeding a dashboard or report generator instead of parsing
+        /// `--output`'s formatted CSV or the prose table on stdout.
+        #[arg(long)]
+        export: Option<PathBuf>,
     },
 
     /// Display a single hand with DD analysis for spot-checking
@@ -206,6 +417,75 @@ enum Commands {
         /// Row number to display (1-indexed, not counting header)
         #[arg(short = 'n', long)]
         row: usize,
+
+        /// Last row to include (1-indexed, inclusive) when rendering a
+        /// `--format html` or `svg` page, stacking every board from `row`
+        /// through this one into a single document. Ignored for `ascii`,
+        /// which always shows exactly one hand.
+        #[arg(long)]
+        end_row: Option<usize>,
+
+        /// Output format: `ascii` (the 80-column terminal layout, the
+        /// default), `html` (a styled compass layout with a shaded
+        /// trick-by-trick DD-cost table), or `svg` (just the compass hand
+        /// diagram, for dropping into slides/teaching material)
+        #[arg(long, default_value = "ascii")]
+        format: String,
+
+        /// Write `html`/`svg` output to this file instead of stdout
+        #[arg(short = 'o', long = "out")]
+        out: Option<PathBuf>,
+    },
+
+    /// Export a hand's cardplay as a GraphViz DOT graph, colored by DD
+    /// cost, for rendering trick-by-trick error diagrams with `dot -Tsvg`
+    ExportDot {
+        /// Input CSV file (must have Cardplay and DD_Analysis columns)
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Row number to export (1-indexed, not counting header)
+        #[arg(short = 'n', long)]
+        row: usize,
+
+        /// Output .dot file (prints to stdout if omitted)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Import a standard PBN file into the same CSV shape `analyze-dd` reads,
+    /// so non-BBO data can run through the DD pipeline unchanged
+    ImportPbn {
+        /// Input .pbn file
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Output CSV file
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Export a processed CSV (hands, Contract, Dec, Result, Cardplay) to PBN
+    ExportPbn {
+        /// Input CSV file
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Output .pbn file
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Compare two analyzed CSVs and report regressions in contract, result,
+    /// and per-seat DD cost totals between matching boards.
+    Diff {
+        /// Baseline analyzed CSV (the "known good" run)
+        #[arg(long)]
+        baseline: PathBuf,
+
+        /// Candidate analyzed CSV to compare against the baseline
+        #[arg(long)]
+        candidate: PathBuf,
     },
 }
 
@@ -221,17 +501,38 @@ fn main() -> Result<()> {
             delay_ms,
             batch_size,
             batch_delay_ms,
+            max_retries,
             resume,
+            cache_dir,
+            recursive,
         } => {
-            fetch_cardplay(
-                &input,
-                &output,
-                &url_column,
-                delay_ms,
-                batch_size,
-                batch_delay_ms,
-                resume,
-            )?;
+            if recursive {
+                run_recursive(&input, &output, |in_path, out_path| {
+                    fetch_cardplay(
+                        in_path,
+                        out_path,
+                        &url_column,
+                        delay_ms,
+                        batch_size,
+                        batch_delay_ms,
+                        max_retries,
+                        resume,
+                        cache_dir.as_ref(),
+                    )
+                })?;
+            } else {
+                fetch_cardplay(
+                    &input,
+                    &output,
+                    &url_column,
+                    delay_ms,
+                    batch_size,
+                    batch_delay_ms,
+                    max_retries,
+                    resume,
+                    cache_dir.as_ref(),
+                )?;
+            }
         }
         Commands::AnalyzeDd {
             input,
@@ -239,8 +540,29 @@ fn main() -> Result<()> {
             threads,
             resume,
             checkpoint_interval,
+            filters,
+            bench,
+            bench_sample_size,
+            bench_duration_secs,
+            recursive,
+            single_dummy_gap,
+            single_dummy_samples,
         } => {
-            analyze_dd(&input, &output, threads, resume, checkpoint_interval)?;
+            if bench {
+                bench_dd(&input, threads, bench_sample_size, bench_duration_secs)?;
+            } else {
+                let filters: Vec<RowFilter> = filters
+                    .iter()
+                    .map(|spec| RowFilter::parse(spec))
+                    .collect::<Result<_>>()?;
+                if recursive {
+                    run_recursive(&input, &output, |in_path, out_path| {
+                        analyze_dd(in_path, out_path, threads, resume, checkpoint_interval, &filters, single_dummy_gap, single_dummy_samples)
+                    })?;
+                } else {
+                    analyze_dd(&input, &output, threads, resume, checkpoint_interval, &filters, single_dummy_gap, single_dummy_samples)?;
+                }
+            }
         }
         Commands::Anonymize {
             input,
@@ -248,24 +570,143 @@ fn main() -> Result<()> {
             key,
             map,
             columns,
+            map_file,
+            recursive,
+            quiet,
+        } => {
+            if recursive {
+                run_recursive(&input, &output, |in_path, out_path| {
+                    anonymize_csv(in_path, out_path, &key, &map, &columns, map_file.as_ref(), quiet)
+                })?;
+            } else {
+                anonymize_csv(&input, &output, &key, &map, &columns, map_file.as_ref(), quiet)?;
+            }
+        }
+        Commands::DeAnonymize {
+            input,
+            output,
+            map_file,
+            key,
+            columns,
         } => {
-            anonymize_csv(&input, &output, &key, &map, &columns)?;
+            de_anonymize(&input, &output, &map_file, &key, &columns)?;
         }
         Commands::Stats {
             input,
             top_n,
             output,
+            recursive,
+            bootstrap,
+            accuracy_k,
+            accuracy_c0,
+            half_life,
+            export,
         } => {
-            compute_stats(&input, top_n, output.as_ref())?;
+            if recursive {
+                let files = discover_csv_files(&input);
+                compute_stats_aggregate(&files, top_n, output.as_ref(), bootstrap, accuracy_k, accuracy_c0, half_life, export.as_ref())?;
+            } else {
+                compute_stats(&input, top_n, output.as_ref(), bootstrap, accuracy_k, accuracy_c0, half_life, export.as_ref())?;
+            }
+        }
+        Commands::DisplayHand { input, row, end_row, format, out } => {
+            if format == "ascii" {
+                if end_row.is_some() || out.is_some() {
+                    return Err(anyhow::anyhow!(
+                        "--end-row and --out only apply to --format html or svg"
+                    ));
+                }
+                display_hand(&input, row)?;
+            } else {
+                display_hand_export(&input, row, end_row.unwrap_or(row), &format, out.as_ref())?;
+            }
+        }
+        Commands::ExportDot { input, row, output } => {
+            export_dot(&input, row, output.as_ref())?;
+        }
+        Commands::ImportPbn { input, output } => {
+            import_pbn(&input, &output)?;
+        }
+        Commands::ExportPbn { input, output } => {
+            export_pbn(&input, &output)?;
+        }
+        Commands::Diff { baseline, candidate } => {
+            compute_diff(&baseline, &candidate)?;
         }
-        Commands::DisplayHand { input, row } => {
-            display_hand(&input, row)?;
+    }
+
+    Ok(())
+}
+
+/// Collect every `*.csv` under `root`, recursing depth-first and skipping
+/// entries whose filename starts with `.`, for `--recursive` mode. Matches
+/// `edgar_ui.rs`'s `scan_dir_recursive` convention (plain `std::fs::read_dir`,
+/// no extra crate). Sorted for deterministic processing order.
+fn discover_csv_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    collect_csv_files(root, &mut files);
+    files.sort();
+    files
+}
+
+fn collect_csv_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = match path.file_name().and_then(|n| n.to_str()) {
+            Some(name) => name,
+            None => continue,
+        };
+        if name.starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            collect_csv_files(&path, files);
+        } else if name.to_lowercase().ends_with(".csv") {
+            files.push(path);
+        }
+    }
+}
+
+/// Drive `per_file` over every CSV discovered under `input_root`, writing
+/// each result to the same relative path under `output_root` (creating
+/// parent directories as needed) -- the shared `--recursive` loop for
+/// `fetch-cardplay`, `analyze-dd`, and `anonymize`.
+fn run_recursive(
+    input_root: &Path,
+    output_root: &Path,
+    mut per_file: impl FnMut(&PathBuf, &PathBuf) -> Result<()>,
+) -> Result<()> {
+    let files = discover_csv_files(input_root);
+    if files.is_empty() {
+        eprintln!("No CSV files found under {}", input_root.display());
+        return Ok(());
+    }
+
+    for input in &files {
+        let rel = input.strip_prefix(input_root).unwrap_or(input);
+        let output = output_root.join(rel);
+        if let Some(parent) = output.parent() {
+            std::fs::create_dir_all(parent)?;
         }
+        eprintln!("=== {} ===", input.display());
+        per_file(input, &output)?;
     }
 
     Ok(())
 }
 
+/// A single fetched row, keyed by its 0-based position in the input CSV.
+struct FetchResult {
+    cardplay: String,
+    lin_url: String,
+    src_hash: String,
+}
+
 fn fetch_cardplay(
     input: &PathBuf,
     output: &PathBuf,
@@ -273,8 +714,11 @@ fn fetch_cardplay(
     delay_ms: u64,
     batch_size: usize,
     batch_delay_ms: u64,
+    max_retries: u32,
     resume: bool,
+    cache_dir: Option<&PathBuf>,
 ) -> Result<()> {
+    let cache = cache_dir.map(|dir| LinCache::open(dir)).transpose()?;
     // Read and preprocess input CSV to fix BBO's malformed quoting
     let csv_data = read_bbo_csv_fixed(input)?;
     let mut reader = ReaderBuilder::new()
@@ -291,12 +735,13 @@ fn fetch_cardplay(
     // Find the Ref # column for tracking progress
     let ref_col_idx = headers.iter().position(|h| h == "Ref #");
 
-    // Check if input already has Cardplay/LIN_URL columns
+    // Check if input already has Cardplay/LIN_URL/Src_Hash columns
     let cardplay_col_idx = headers.iter().position(|h| h == "Cardplay");
     let lin_url_col_idx = headers.iter().position(|h| h == "LIN_URL");
+    let src_hash_col_idx = headers.iter().position(|h| h == "Src_Hash");
 
-    // If resume mode and output exists, load existing data (ref -> (lin_url, cardplay))
-    let existing_data: HashMap<String, (String, String)> = if resume && output.exists() {
+    // If resume mode and output exists, load existing data (ref -> row)
+    let existing_data: HashMap<String, ExistingRow> = if resume && output.exists() {
         load_existing_cardplay_data(output)?
     } else {
         HashMap::new()
@@ -308,78 +753,150 @@ fn fetch_cardplay(
         output_headers.push_field("Cardplay");
         output_headers.push_field("LIN_URL");
     }
-
-    // Create URL resolver
-    let mut resolver = UrlResolver::with_config(delay_ms, batch_size, batch_delay_ms);
+    if src_hash_col_idx.is_none() {
+        output_headers.push_field("Src_Hash");
+    }
 
     // Count total rows for progress
     let total_rows = count_csv_rows(input)?;
 
-    // Open output file with flexible field count to handle malformed input rows
-    let mut writer = csv::WriterBuilder::new()
-        .flexible(true)
-        .from_path(output)
-        .context("Failed to create output CSV")?;
-    writer.write_record(&output_headers)?;
-
-    let mut processed = 0;
-    let mut skipped = 0;
-    let mut errors = 0;
+    // Split rows into those already resolved (resume/skip) and those that
+    // still need a network fetch, keeping the original row index so results
+    // can be written back out in order. A row is only reused when its
+    // existing lookup entry is present, valid, and its source URL hash
+    // still matches — otherwise the URL changed since the last run and it
+    // must be re-fetched.
+    let mut all_records: Vec<StringRecord> = Vec::new();
+    let mut to_fetch: Vec<usize> = Vec::new();
+    let mut resolved: HashMap<usize, (String, String, String)> = HashMap::new();
+    let mut skipped = 0usize;
 
-    for (row_num, result) in reader.records().enumerate() {
+    for (row_idx, result) in reader.records().enumerate() {
         let record = result.context("Failed to read CSV row")?;
-        processed += 1;
-
-        // Check if we have existing data for this row (resume mode)
         let ref_id = ref_col_idx.and_then(|i| record.get(i)).unwrap_or("").to_string();
-        let existing = existing_data.get(&ref_id);
-
-        // Progress indicator
-        eprint!(
-            "\r[{}/{}] Processing... ({} errors, {} skipped)    ",
-            processed, total_rows, errors, skipped
-        );
-        std::io::stderr().flush().ok();
-
-        // Use existing data if available and valid, otherwise fetch
-        let (cardplay, lin_url) = if let Some((existing_lin, existing_cardplay)) = existing {
-            if !existing_cardplay.is_empty() && !existing_cardplay.starts_with("ERROR:") {
+        let url = record.get(url_col_idx).unwrap_or("").trim();
+        let src_hash = hash_source_url(url);
+        all_records.push(record);
+
+        match existing_data.get(&ref_id) {
+            Some(existing)
+                if !existing.cardplay.is_empty()
+                    && !existing.cardplay.starts_with("ERROR:")
+                    && (existing.src_hash.is_empty() || existing.src_hash == src_hash) =>
+            {
                 skipped += 1;
-                (existing_cardplay.clone(), existing_lin.clone())
-            } else {
-                // Re-fetch if previous attempt was an error
-                fetch_cardplay_for_url(&mut resolver, &record, url_col_idx, row_num, &mut errors)
+                resolved.insert(row_idx, (existing.cardplay.clone(), existing.lin_url.clone(), src_hash));
             }
-        } else {
-            fetch_cardplay_for_url(&mut resolver, &record, url_col_idx, row_num, &mut errors)
-        };
+            _ => to_fetch.push(row_idx),
+        }
+    }
+
+    // Per-host rate limiters: each host gets its own bucket of `batch_size`
+    // tokens, refilled at a rate derived from `delay_ms`, so a slow or
+    // rate-limiting host doesn't throttle fetches from every other host.
+    // `batch_delay_ms` becomes the cool-down between AIMD recovery nudges.
+    let buckets: Mutex<HashMap<String, Arc<TokenBucket>>> = Mutex::new(HashMap::new());
+    let processed_count = AtomicUsize::new(skipped);
+    let errors = AtomicUsize::new(0);
+    let results: Mutex<HashMap<usize, FetchResult>> = Mutex::new(HashMap::new());
+
+    // Write whatever's in `resolved` + `results` so far to `output`, atomically
+    // (temp file + rename) so a crash mid-write never leaves a partial file
+    // that a later `--resume` run would mistake for completed rows.
+    let flush_output = |results: &HashMap<usize, FetchResult>| -> Result<()> {
+        write_atomic(output, |tmp_path| {
+            let mut writer = csv::WriterBuilder::new()
+                .flexible(true)
+                .from_path(tmp_path)
+                .context("Failed to create output CSV")?;
+            writer.write_record(&output_headers)?;
+
+            for (row_idx, record) in all_records.iter().enumerate() {
+                let (cardplay, lin_url, src_hash) = if let Some((cp, lu, h)) = resolved.get(&row_idx) {
+                    (cp.clone(), lu.clone(), h.clone())
+                } else if let Some(r) = results.get(&row_idx) {
+                    (r.cardplay.clone(), r.lin_url.clone(), r.src_hash.clone())
+                } else {
+                    (String::new(), String::new(), String::new())
+                };
 
-        // Write the row with cardplay/lin_url data
-        let mut output_record: Vec<String> = record.iter().map(|s| s.to_string()).collect();
+                let mut output_record: Vec<String> = record.iter().map(|s| s.to_string()).collect();
 
-        if let (Some(cp_idx), Some(lu_idx)) = (cardplay_col_idx, lin_url_col_idx) {
-            // Update existing columns
-            if cp_idx < output_record.len() {
-                output_record[cp_idx] = cardplay;
-            }
-            if lu_idx < output_record.len() {
-                output_record[lu_idx] = lin_url;
+                if let (Some(cp_idx), Some(lu_idx)) = (cardplay_col_idx, lin_url_col_idx) {
+                    if cp_idx < output_record.len() {
+                        output_record[cp_idx] = cardplay;
+                    }
+                    if lu_idx < output_record.len() {
+                        output_record[lu_idx] = lin_url;
+                    }
+                } else {
+                    output_record.push(cardplay);
+                    output_record.push(lin_url);
+                }
+
+                if let Some(sh_idx) = src_hash_col_idx {
+                    if sh_idx < output_record.len() {
+                        output_record[sh_idx] = src_hash;
+                    }
+                } else {
+                    output_record.push(src_hash);
+                }
+
+                writer.write_record(&output_record)?;
             }
-        } else {
-            // Add new columns
-            output_record.push(cardplay);
-            output_record.push(lin_url);
-        }
-        writer.write_record(&output_record)?;
 
-        // Flush periodically for crash recovery
-        if processed % 100 == 0 {
             writer.flush()?;
+            Ok(())
+        })
+    };
+
+    // Process in chunks of `batch_size`, flushing `output` and letting the
+    // limiters recover between chunks, same as the old sequential batching:
+    // killing the process between chunks loses at most one chunk's worth of
+    // rows instead of the whole run. `chunks()` panics on a zero size, so
+    // guard the same way `TokenBucket::new` already does for a
+    // `--batch-size 0` mis-entry.
+    for chunk in to_fetch.chunks(batch_size.max(1)) {
+        chunk.par_iter().for_each(|&row_idx| {
+            let record = &all_records[row_idx];
+
+            let (cardplay, lin_url) = fetch_with_retry(
+                record,
+                url_col_idx,
+                row_idx,
+                &errors,
+                &buckets,
+                batch_size,
+                delay_ms,
+                max_retries,
+                cache.as_ref(),
+            );
+
+            let src_hash = hash_source_url(record.get(url_col_idx).unwrap_or("").trim());
+            results.lock().unwrap().insert(row_idx, FetchResult { cardplay, lin_url, src_hash });
+
+            let count = processed_count.fetch_add(1, Ordering::Relaxed) + 1;
+            eprint!(
+                "\r[{}/{}] Processing... ({} errors, {} skipped)    ",
+                count, total_rows, errors.load(Ordering::Relaxed), skipped
+            );
+            std::io::stderr().flush().ok();
+        });
+
+        // Let the rate stabilize a bit before the next chunk.
+        std::thread::sleep(std::time::Duration::from_millis(batch_delay_ms));
+        for bucket in buckets.lock().unwrap().values() {
+            bucket.recover();
         }
+
+        flush_output(&results.lock().unwrap())?;
     }
 
-    writer.flush()?;
-    eprintln!("\nDone! Processed {} rows ({} errors)", processed, errors);
+    eprintln!(
+        "\nDone! Processed {} rows ({} errors)",
+        all_records.len(),
+        errors.load(Ordering::Relaxed)
+    );
 
     Ok(())
 }
@@ -401,13 +918,176 @@ fn process_url(resolver: &mut UrlResolver, url: &str) -> Result<(String, String)
     Ok((cardplay, resolved_url))
 }
 
-/// Helper to fetch cardplay for a URL, handling errors
+/// Ceiling on a single retry's backoff sleep, so a long run of retries
+/// doesn't back off past a reasonable wait.
+const RETRY_BACKOFF_CAP_MS: u64 = 30_000;
+
+/// Fetch one row, retrying a transient failure (rate limit, timeout, 5xx)
+/// up to `max_retries` times with exponential backoff (`200ms * 2^attempt`,
+/// capped, plus up to 50% jitter) before giving up. A non-transient error
+/// (a malformed URL, unparseable LIN data) returns immediately since
+/// retrying it would just fail the same way again; only a final, exhausted
+/// transient failure or a non-transient one ends up as the `ERROR:` string
+/// written to the row.
+#[allow(clippy::too_many_arguments)]
+fn fetch_with_retry(
+    record: &StringRecord,
+    url_col_idx: usize,
+    row_idx: usize,
+    errors: &AtomicUsize,
+    buckets: &Mutex<HashMap<String, Arc<TokenBucket>>>,
+    batch_size: usize,
+    delay_ms: u64,
+    max_retries: u32,
+    cache: Option<&LinCache>,
+) -> (String, String) {
+    let source_url = record.get(url_col_idx).unwrap_or("").trim().to_string();
+
+    if let Some(cache) = cache {
+        if let Some(hit) = cache.get(&source_url) {
+            return hit;
+        }
+    }
+
+    let bucket = bucket_for(buckets, url_host(&source_url), batch_size, delay_ms);
+
+    let mut attempt = 0u32;
+    loop {
+        bucket.acquire();
+        let mut resolver = UrlResolver::with_config(0, batch_size, 0);
+        let (cardplay, lin_url) = fetch_cardplay_for_url(&mut resolver, record, url_col_idx, row_idx, errors);
+
+        if !cardplay.starts_with("ERROR:") || !is_transient_error(&cardplay) || attempt >= max_retries {
+            if !cardplay.starts_with("ERROR:") {
+                if let Some(cache) = cache {
+                    if let Err(e) = cache.put(&source_url, &lin_url, &cardplay) {
+                        log::warn!("Row {}: failed to write LIN cache entry: {}", row_idx + 1, e);
+                    }
+                }
+            }
+            return (cardplay, lin_url);
+        }
+
+        if cardplay.contains("Rate limited") {
+            bucket.throttle();
+        }
+        // This attempt is going to be retried, not counted as the row's
+        // final outcome -- fetch_cardplay_for_url already incremented
+        // `errors` for it, so undo that until we know whether a retry
+        // eventually succeeds.
+        errors.fetch_sub(1, Ordering::Relaxed);
+
+        attempt += 1;
+        let backoff_ms = (200u64.saturating_mul(1u64 << attempt.min(10))).min(RETRY_BACKOFF_CAP_MS);
+        let jitter_ms = rand::thread_rng().gen_range(0..=(backoff_ms / 2).max(1));
+        std::thread::sleep(std::time::Duration::from_millis(backoff_ms + jitter_ms));
+    }
+}
+
+/// Content-addressed on-disk cache for resolved LIN payloads, shared by
+/// `--cache-dir`. Layout: `<dir>/objects/<sha256>` holds one cached
+/// payload (`resolved_url\ncardplay`), and `<dir>/index` is an append-only
+/// `source_url\tdigest` table mapping the original (pre-resolution) URL to
+/// the object holding its last-known-good result.
+///
+/// The actual HTTP fetch happens inside `bridge_parsers::lin::parse_lin_from_url`,
+/// outside this crate, so the digest can't be computed mid-download the way
+/// `pipeline::sha256_file` streams a local file; instead it's computed over
+/// the formatted payload in the same pass that builds it, with no
+/// write-then-reread round trip.
+struct LinCache {
+    dir: PathBuf,
+    index: Mutex<HashMap<String, String>>,
+}
+
+impl LinCache {
+    fn open(dir: &PathBuf) -> Result<Self> {
+        std::fs::create_dir_all(dir.join("objects"))
+            .with_context(|| format!("Failed to create cache directory {}", dir.display()))?;
+
+        let mut index = HashMap::new();
+        if let Ok(contents) = std::fs::read_to_string(dir.join("index")) {
+            for line in contents.lines() {
+                if let Some((url, digest)) = line.split_once('\t') {
+                    index.insert(url.to_string(), digest.to_string());
+                }
+            }
+        }
+
+        Ok(LinCache { dir: dir.clone(), index: Mutex::new(index) })
+    }
+
+    /// Look up `source_url`, re-verifying the cached payload's digest
+    /// before trusting it so on-disk corruption or truncation shows up as
+    /// a cache miss rather than bad data silently flowing back out.
+    fn get(&self, source_url: &str) -> Option<(String, String)> {
+        let digest = self.index.lock().unwrap().get(source_url).cloned()?;
+        let bytes = std::fs::read(self.dir.join("objects").join(&digest)).ok()?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(&bytes);
+        if format!("{:x}", hasher.finalize()) != digest {
+            log::warn!("LIN cache object {} failed integrity check, re-fetching", digest);
+            return None;
+        }
+
+        let text = String::from_utf8(bytes).ok()?;
+        let (resolved_url, cardplay) = text.split_once('\n')?;
+        Some((cardplay.to_string(), resolved_url.to_string()))
+    }
+
+    /// Hash and store a freshly-resolved payload, then record `source_url`
+    /// -> digest in the index so a later run can find it again.
+    fn put(&self, source_url: &str, resolved_url: &str, cardplay: &str) -> Result<()> {
+        let payload = format!("{}\n{}", resolved_url, cardplay);
+        let mut hasher = Sha256::new();
+        hasher.update(payload.as_bytes());
+        let digest = format!("{:x}", hasher.finalize());
+
+        let object_path = self.dir.join("objects").join(&digest);
+        if !object_path.exists() {
+            std::fs::write(&object_path, payload.as_bytes())?;
+        }
+
+        let mut index = self.index.lock().unwrap();
+        if index.get(source_url) != Some(&digest) {
+            index.insert(source_url.to_string(), digest.clone());
+            let mut index_file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.dir.join("index"))?;
+            writeln!(index_file, "{}\t{}", source_url, digest)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether an `ERROR: ...` string from `fetch_cardplay_for_url` looks like
+/// a transient condition worth retrying, rather than a permanent one.
+fn is_transient_error(error: &str) -> bool {
+    let lower = error.to_lowercase();
+    lower.contains("rate limited")
+        || lower.contains("timeout")
+        || lower.contains("timed out")
+        || lower.contains("connection")
+        || lower.contains("500")
+        || lower.contains("502")
+        || lower.contains("503")
+        || lower.contains("504")
+}
+
+/// Helper to fetch cardplay for a URL, handling errors.
+///
+/// Rate limiting is handled by the caller's shared `TokenBucket` rather than
+/// by sleeping this single thread, so a 429 here just gets reported back as
+/// an `ERROR: ... Rate limited ...` string for the caller to act on.
 fn fetch_cardplay_for_url(
     resolver: &mut UrlResolver,
     record: &StringRecord,
     url_col_idx: usize,
     row_num: usize,
-    errors: &mut usize,
+    errors: &AtomicUsize,
 ) -> (String, String) {
     let url = record.get(url_col_idx).unwrap_or("").trim();
 
@@ -419,22 +1099,29 @@ fn fetch_cardplay_for_url(
         Ok((cp, lu)) => (cp, lu),
         Err(e) => {
             log::warn!("Row {}: Error processing URL '{}': {}", row_num + 1, url, e);
-            *errors += 1;
-
-            // Check if rate limited and need to pause
-            if e.to_string().contains("Rate limited") {
-                eprintln!("\nRate limited - pausing for 60 seconds...");
-                std::thread::sleep(std::time::Duration::from_secs(60));
-                resolver.reset_batch();
-            }
-
+            errors.fetch_add(1, Ordering::Relaxed);
             (format!("ERROR: {}", e), String::new())
         }
     }
 }
 
-/// Load existing cardplay data from output file for resume
-fn load_existing_cardplay_data(output: &PathBuf) -> Result<HashMap<String, (String, String)>> {
+/// Short, non-cryptographic hash of a source URL, used only to detect when a
+/// row's input has changed across runs — not a security primitive.
+fn hash_source_url(url: &str) -> String {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    url.trim().hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Existing data for a previously-fetched row: the source URL hash it was
+/// fetched from, plus the resolved LIN URL and cardplay.
+struct ExistingRow {
+    src_hash: String,
+    lin_url: String,
+    cardplay: String,
+}
+
+fn load_existing_cardplay_data(output: &PathBuf) -> Result<HashMap<String, ExistingRow>> {
     let mut data = HashMap::new();
     let mut reader = ReaderBuilder::new()
         .flexible(true)
@@ -444,6 +1131,7 @@ fn load_existing_cardplay_data(output: &PathBuf) -> Result<HashMap<String, (Stri
     let ref_idx = headers.iter().position(|h| h == "Ref #");
     let lin_url_idx = headers.iter().position(|h| h == "LIN_URL");
     let cardplay_idx = headers.iter().position(|h| h == "Cardplay");
+    let src_hash_idx = headers.iter().position(|h| h == "Src_Hash");
 
     if ref_idx.is_none() || cardplay_idx.is_none() {
         return Ok(data);
@@ -460,9 +1148,15 @@ fn load_existing_cardplay_data(output: &PathBuf) -> Result<HashMap<String, (Stri
             .unwrap_or("")
             .to_string();
         let cardplay = record.get(cardplay_idx).unwrap_or("").to_string();
+        // Missing Src_Hash column means this is data from before hash-aware
+        // resume existed; treat as matching so a rerun doesn't refetch it.
+        let src_hash = src_hash_idx
+            .and_then(|i| record.get(i))
+            .unwrap_or("")
+            .to_string();
 
         if !ref_id.is_empty() {
-            data.insert(ref_id, (lin_url, cardplay));
+            data.insert(ref_id, ExistingRow { src_hash, lin_url, cardplay });
         }
     }
 
@@ -500,28 +1194,98 @@ fn load_existing_refs(output: &PathBuf, column: &str) -> Result<HashSet<String>>
     Ok(refs)
 }
 
+/// Count data rows via a real CSV parse (not a line count), so fields with
+/// embedded newlines don't inflate the total.
 fn count_csv_rows(path: &PathBuf) -> Result<usize> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
-    // Subtract 1 for header row
-    Ok(reader.lines().count().saturating_sub(1))
+    let mut reader = ReaderBuilder::new().flexible(true).from_path(path)?;
+    Ok(reader.records().count())
 }
 
-// ============================================================================
-// DD Analysis Implementation
-// ============================================================================
+/// Shared progress-bar wrapper for long row-iterating commands, replacing
+/// each one's own `\r[{}/{}]`-to-stderr hand rolling. Driven by bytes
+/// consumed rather than a row count, so the caller doesn't need a
+/// `count_csv_rows` pre-pass (a full second parse of the file) just to get
+/// a denominator -- the input's byte length is already known once it's been
+/// read into memory (see `read_bbo_csv_fixed`), and `csv::Reader::position`
+/// reports how far into it the parser has gotten after each record.
+///
+/// Produces a no-op reporter when `quiet` is set or stderr isn't a
+/// terminal, so piped output and background/cron runs don't fill logs with
+/// bar-redraw frames.
+struct ProgressReporter {
+    bar: Option<ProgressBar>,
+}
 
-/// Represents a row to be processed for DD analysis
-#[derive(Clone)]
-struct DdWorkItem {
-    row_idx: usize,
-    #[allow(dead_code)]
-    ref_id: String,
+impl ProgressReporter {
+    fn new(total_bytes: u64, quiet: bool, label: &str) -> ProgressReporter {
+        if quiet || !std::io::stderr().is_terminal() {
+            return ProgressReporter { bar: None };
+        }
+
+        let bar = ProgressBar::new(total_bytes);
+        let style = ProgressStyle::with_template(
+            "{prefix}: [{elapsed_precise}] [{bar:40.cyan/blue}] {percent}% ({bytes}/{total_bytes}, {bytes_per_sec}, ETA {eta})",
+        )
+        .unwrap_or_else(|_| ProgressStyle::default_bar())
+        .progress_chars("=>-");
+        bar.set_style(style);
+        bar.set_prefix(label.to_string());
+        ProgressReporter { bar: Some(bar) }
+    }
+
+    /// Advances the bar to `byte_offset` bytes consumed so far (an absolute
+    /// position, not a delta) -- what `csv::Reader::position().byte()`
+    /// reports, so the bar tracks actual parse progress.
+    fn set_position(&self, byte_offset: u64) {
+        if let Some(bar) = &self.bar {
+            bar.set_position(byte_offset);
+        }
+    }
+
+    fn finish(&self, message: String) {
+        if let Some(bar) = &self.bar {
+            bar.finish_with_message(message);
+        }
+    }
+}
+
+/// Write `contents` to a temp file next to `path` and atomically rename it
+/// into place, so a crash mid-write never leaves a partial file that a later
+/// resume would mistake for a complete one.
+fn write_atomic(path: &PathBuf, contents: impl FnOnce(&PathBuf) -> Result<()>) -> Result<()> {
+    let tmp_path = PathBuf::from(format!("{}.tmp", path.display()));
+    contents(&tmp_path)?;
+    std::fs::rename(&tmp_path, path)
+        .with_context(|| format!("Failed to atomically rename {:?} -> {:?}", tmp_path, path))?;
+    Ok(())
+}
+
+// ============================================================================
+// DD Analysis Implementation
+// ============================================================================
+
+/// Represents a row to be processed for DD analysis
+#[derive(Clone)]
+struct DdWorkItem {
+    row_idx: usize,
+    #[allow(dead_code)]
+    ref_id: String,
     deal_pbn: String,
     cardplay: String,
     contract: String,
     declarer: String,
     max_dd: Option<i8>, // From input file, -1 means incomplete hand
+    /// Vulnerability from the input `Vul` column (e.g. "None", "NS", "EW", "All")
+    vul: Option<String>,
+    /// Whether a LIN-derived contract/declarer came from an auction that
+    /// parsed and validated cleanly (see [`RowData::auction_valid`])
+    auction_valid: bool,
+    /// Redeal-sample count for the single-dummy-vs-double-dummy gap check
+    /// (`--single-dummy-gap`/`--single-dummy-samples`), or `None` to skip it
+    /// entirely -- it's far more expensive than the rest of this pipeline
+    /// (a constrained redeal sample per trick, via
+    /// `single_dummy::compute_single_dummy_costs`), so it's opt-in.
+    sd_gap_samples: Option<usize>,
 }
 
 /// Result stored for each processed row
@@ -541,6 +1305,117 @@ struct DdResultEntry {
     errors_s: u8,
     errors_e: u8,
     errors_w: u8,
+    /// Per-seat total DD trick cost (N, S, E, W)
+    cost_sum_n: u32,
+    cost_sum_s: u32,
+    cost_sum_e: u32,
+    cost_sum_w: u32,
+    /// Per-seat revoke counts (N, S, E, W)
+    revokes_n: u8,
+    revokes_s: u8,
+    revokes_e: u8,
+    revokes_w: u8,
+    /// Set when a played card wasn't in the player's holding at all (a
+    /// data-quality signal, not a genuine revoke)
+    illegal: bool,
+    /// Per-seat severity tallies from the play-rule engine (N, S, E, W)
+    severity_n: dd_analysis::SeatSeverityCounts,
+    severity_s: dd_analysis::SeatSeverityCounts,
+    severity_e: dd_analysis::SeatSeverityCounts,
+    severity_w: dd_analysis::SeatSeverityCounts,
+    /// Cost string with flagged cards tagged `rule_id:severity`
+    diagnostics: String,
+    /// Per-seat rule-id tallies, e.g. `N:opening-lead=1;E:declarer-line=1`
+    categories: String,
+    vulnerable: bool,
+    score: i32,
+    dd_par_score: i32,
+    score_imps: i32,
+    voids_n: String,
+    voids_s: String,
+    voids_e: String,
+    voids_w: String,
+    /// Whether this row's contract/declarer came from a cleanly-parsed LIN
+    /// auction (see [`RowData::auction_valid`])
+    auction_valid: bool,
+    par_contract: String,
+    par_score: i32,
+    par_delta: i32,
+    /// See [`DdAnalysisOutput::sd_ambiguous_n`] / `sd_excess_n`.
+    sd_ambiguous_n: u8,
+    sd_ambiguous_s: u8,
+    sd_ambiguous_e: u8,
+    sd_ambiguous_w: u8,
+    sd_excess_n: u8,
+    sd_excess_s: u8,
+    sd_excess_e: u8,
+    sd_excess_w: u8,
+}
+
+/// Comparison applied by a [`RowFilter`] against a column's raw string value.
+#[derive(Debug, Clone)]
+enum FilterOp {
+    Eq(String),
+    Contains(String),
+    Range(f64, f64),
+}
+
+/// A `{column, op, value}` row-selection predicate parsed from a `--filter`
+/// flag. Predicates are combined with AND semantics: a row must satisfy every
+/// filter to be analyzed.
+#[derive(Debug, Clone)]
+struct RowFilter {
+    column: String,
+    op: FilterOp,
+}
+
+impl RowFilter {
+    /// Parse `column=value`, `column~substring`, or `column=min..max`.
+    fn parse(spec: &str) -> Result<Self> {
+        let (column, op) = if let Some(idx) = spec.find('~') {
+            (&spec[..idx], FilterOp::Contains(spec[idx + 1..].to_string()))
+        } else if let Some(idx) = spec.find('=') {
+            let value = &spec[idx + 1..];
+            if let Some((min, max)) = value.split_once("..") {
+                let min: f64 = min.trim().parse().with_context(|| {
+                    format!("Invalid range start in filter '{}'", spec)
+                })?;
+                let max: f64 = max.trim().parse().with_context(|| {
+                    format!("Invalid range end in filter '{}'", spec)
+                })?;
+                (&spec[..idx], FilterOp::Range(min, max))
+            } else {
+                (&spec[..idx], FilterOp::Eq(value.to_string()))
+            }
+        } else {
+            return Err(anyhow::anyhow!(
+                "Invalid filter '{}': expected 'column=value', 'column~substring', or 'column=min..max'",
+                spec
+            ));
+        };
+
+        Ok(RowFilter { column: column.trim().to_string(), op })
+    }
+
+    /// Whether `record` satisfies this predicate. A missing column, or a
+    /// non-numeric value compared against a range, counts as no match.
+    fn matches(&self, record: &StringRecord, headers: &StringRecord) -> bool {
+        let value = headers
+            .iter()
+            .position(|h| h == self.column)
+            .and_then(|idx| record.get(idx))
+            .unwrap_or("");
+
+        match &self.op {
+            FilterOp::Eq(want) => value.eq_ignore_ascii_case(want),
+            FilterOp::Contains(want) => value.to_uppercase().contains(&want.to_uppercase()),
+            FilterOp::Range(min, max) => value
+                .trim()
+                .parse::<f64>()
+                .map(|v| v >= *min && v <= *max)
+                .unwrap_or(false),
+        }
+    }
 }
 
 fn analyze_dd(
@@ -549,6 +1424,9 @@ fn analyze_dd(
     threads: Option<usize>,
     resume: bool,
     checkpoint_interval: usize,
+    filters: &[RowFilter],
+    single_dummy_gap: bool,
+    single_dummy_samples: usize,
 ) -> Result<()> {
     // Configure thread pool
     if let Some(n) = threads {
@@ -592,6 +1470,49 @@ fn analyze_dd(
         output_headers.push_field("DD_S_Errors");
         output_headers.push_field("DD_E_Errors");
         output_headers.push_field("DD_W_Errors");
+        output_headers.push_field("DD_N_CostSum");
+        output_headers.push_field("DD_S_CostSum");
+        output_headers.push_field("DD_E_CostSum");
+        output_headers.push_field("DD_W_CostSum");
+        output_headers.push_field("DD_N_Revokes");
+        output_headers.push_field("DD_S_Revokes");
+        output_headers.push_field("DD_E_Revokes");
+        output_headers.push_field("DD_W_Revokes");
+        output_headers.push_field("DD_Illegal");
+        output_headers.push_field("DD_N_Minors");
+        output_headers.push_field("DD_N_Majors");
+        output_headers.push_field("DD_N_Blunders");
+        output_headers.push_field("DD_S_Minors");
+        output_headers.push_field("DD_S_Majors");
+        output_headers.push_field("DD_S_Blunders");
+        output_headers.push_field("DD_E_Minors");
+        output_headers.push_field("DD_E_Majors");
+        output_headers.push_field("DD_E_Blunders");
+        output_headers.push_field("DD_W_Minors");
+        output_headers.push_field("DD_W_Majors");
+        output_headers.push_field("DD_W_Blunders");
+        output_headers.push_field("DD_Diagnostics");
+        output_headers.push_field("DD_Error_Categories");
+        output_headers.push_field("Vulnerable");
+        output_headers.push_field("Score");
+        output_headers.push_field("DD_Par_Score");
+        output_headers.push_field("DD_Score_IMPs");
+        output_headers.push_field("DD_Voids_N");
+        output_headers.push_field("DD_Voids_S");
+        output_headers.push_field("DD_Voids_E");
+        output_headers.push_field("DD_Voids_W");
+        output_headers.push_field("Auction_Valid");
+        output_headers.push_field("Par_Contract");
+        output_headers.push_field("Par_Score");
+        output_headers.push_field("Par_Delta");
+        output_headers.push_field("DD_N_SDAmbiguous");
+        output_headers.push_field("DD_S_SDAmbiguous");
+        output_headers.push_field("DD_E_SDAmbiguous");
+        output_headers.push_field("DD_W_SDAmbiguous");
+        output_headers.push_field("DD_N_SDExcessMatch");
+        output_headers.push_field("DD_S_SDExcessMatch");
+        output_headers.push_field("DD_E_SDExcessMatch");
+        output_headers.push_field("DD_W_SDExcessMatch");
         output_headers.push_field("DD_Analysis");
     }
 
@@ -600,6 +1521,7 @@ fn analyze_dd(
     let mut work_items: Vec<DdWorkItem> = Vec::new();
     let mut skipped_incomplete = 0usize;
     let mut skipped_passout = 0usize;
+    let mut skipped_filtered = 0usize;
 
     for (row_idx, result) in reader.records().enumerate() {
         let record = result.context("Failed to read CSV row")?;
@@ -607,6 +1529,13 @@ fn analyze_dd(
 
         let ref_id = record.get(col_indices.ref_col).unwrap_or("").to_string();
 
+        // Rows that don't satisfy every --filter predicate are passed through
+        // to the output unchanged and don't count toward any other skip category.
+        if !filters.is_empty() && !filters.iter().all(|f| f.matches(&record, &headers)) {
+            skipped_filtered += 1;
+            continue;
+        }
+
         // Skip if already processed (resume mode)
         if resume && existing_refs.contains(&ref_id) {
             continue;
@@ -639,6 +1568,8 @@ fn analyze_dd(
                 continue;
             }
 
+            let vul = col_indices.vul_col.and_then(|col| record.get(col)).map(|s| s.to_string());
+
             work_items.push(DdWorkItem {
                 row_idx,
                 ref_id,
@@ -647,6 +1578,9 @@ fn analyze_dd(
                 contract: row_data.contract,
                 declarer: row_data.declarer,
                 max_dd,
+                vul,
+                auction_valid: row_data.auction_valid,
+                sd_gap_samples: if single_dummy_gap { Some(single_dummy_samples) } else { None },
             });
         }
     }
@@ -655,12 +1589,13 @@ fn analyze_dd(
     let to_process = work_items.len();
 
     eprintln!(
-        "Found {} rows, {} need DD analysis ({} already done, {} incomplete, {} passout)",
+        "Found {} rows, {} need DD analysis ({} already done, {} incomplete, {} passout, {} filtered out)",
         total_rows,
         to_process,
-        total_rows - to_process - skipped_incomplete - skipped_passout,
+        total_rows - to_process - skipped_incomplete - skipped_passout - skipped_filtered,
         skipped_incomplete,
-        skipped_passout
+        skipped_passout,
+        skipped_filtered
     );
 
     if to_process == 0 {
@@ -671,13 +1606,35 @@ fn analyze_dd(
     // Progress tracking
     let processed_count = AtomicUsize::new(0);
     let error_count = AtomicUsize::new(0);
+    let cache_hit_count = AtomicUsize::new(0);
 
     // Store results in a thread-safe map (includes computed DD for validation)
     let results: Mutex<HashMap<usize, DdResultEntry>> = Mutex::new(HashMap::new());
 
+    // Memoizes compute_dd_analysis by board/contract/cardplay identity
+    // (see zobrist_hash) so repeated rows -- replays and duplicate boards
+    // across tables are common in real exports -- skip the DD solve entirely.
+    let dd_cache: Mutex<HashMap<u64, DdAnalysisOutput>> = Mutex::new(HashMap::new());
+
     // Process work items in parallel
     work_items.par_iter().for_each(|item| {
-        let entry = match compute_dd_analysis(item) {
+        let cache_key = zobrist_hash(item);
+        let cached = dd_cache.lock().unwrap().get(&cache_key).cloned();
+        let computed = match cached {
+            Some(output) => {
+                cache_hit_count.fetch_add(1, Ordering::Relaxed);
+                Ok(output)
+            }
+            None => {
+                let result = compute_dd_analysis(item);
+                if let Ok(output) = &result {
+                    dd_cache.lock().unwrap().insert(cache_key, output.clone());
+                }
+                result
+            }
+        };
+
+        let entry = match computed {
             Ok(output) => DdResultEntry {
                 analysis: output.analysis,
                 computed_dd: Some(output.initial_dd),
@@ -691,10 +1648,46 @@ fn analyze_dd(
                 errors_s: output.errors_s,
                 errors_e: output.errors_e,
                 errors_w: output.errors_w,
+                cost_sum_n: output.cost_sum_n,
+                cost_sum_s: output.cost_sum_s,
+                cost_sum_e: output.cost_sum_e,
+                cost_sum_w: output.cost_sum_w,
+                revokes_n: output.revokes_n,
+                revokes_s: output.revokes_s,
+                revokes_e: output.revokes_e,
+                revokes_w: output.revokes_w,
+                illegal: output.illegal,
+                severity_n: output.severity_n,
+                severity_s: output.severity_s,
+                severity_e: output.severity_e,
+                severity_w: output.severity_w,
+                diagnostics: output.diagnostics,
+                categories: output.categories,
+                vulnerable: output.vulnerable,
+                score: output.score,
+                dd_par_score: output.dd_par_score,
+                score_imps: output.score_imps,
+                voids_n: output.voids_n,
+                voids_s: output.voids_s,
+                voids_e: output.voids_e,
+                voids_w: output.voids_w,
+                auction_valid: item.auction_valid,
+                par_contract: output.par_contract,
+                par_score: output.par_score,
+                par_delta: output.par_delta,
+                sd_ambiguous_n: output.sd_ambiguous_n,
+                sd_ambiguous_s: output.sd_ambiguous_s,
+                sd_ambiguous_e: output.sd_ambiguous_e,
+                sd_ambiguous_w: output.sd_ambiguous_w,
+                sd_excess_n: output.sd_excess_n,
+                sd_excess_s: output.sd_excess_s,
+                sd_excess_e: output.sd_excess_e,
+                sd_excess_w: output.sd_excess_w,
             },
             Err(e) => {
                 error_count.fetch_add(1, Ordering::Relaxed);
                 log::warn!("Row {}: DD analysis error: {}", item.row_idx + 1, e);
+                let (par_contract, par_score, par_delta) = par_summary_for_item(item, 0);
                 DdResultEntry {
                     analysis: format!("ERROR: {}", e),
                     computed_dd: None,
@@ -702,6 +1695,27 @@ fn analyze_dd(
                     ol_error: 0,
                     plays_n: 0, plays_s: 0, plays_e: 0, plays_w: 0,
                     errors_n: 0, errors_s: 0, errors_e: 0, errors_w: 0,
+                    cost_sum_n: 0, cost_sum_s: 0, cost_sum_e: 0, cost_sum_w: 0,
+                    revokes_n: 0, revokes_s: 0, revokes_e: 0, revokes_w: 0,
+                    illegal: false,
+                    severity_n: Default::default(), severity_s: Default::default(),
+                    severity_e: Default::default(), severity_w: Default::default(),
+                    diagnostics: String::new(),
+                    categories: String::new(),
+                    vulnerable: is_declarer_vulnerable(item.vul.as_deref(), &item.declarer),
+                    score: 0,
+                    dd_par_score: 0,
+                    score_imps: 0,
+                    par_contract,
+                    par_score,
+                    par_delta,
+                    voids_n: String::new(),
+                    voids_s: String::new(),
+                    voids_e: String::new(),
+                    voids_w: String::new(),
+                    auction_valid: item.auction_valid,
+                    sd_ambiguous_n: 0, sd_ambiguous_s: 0, sd_ambiguous_e: 0, sd_ambiguous_w: 0,
+                    sd_excess_n: 0, sd_excess_s: 0, sd_excess_e: 0, sd_excess_w: 0,
                 }
             }
         };
@@ -755,10 +1769,53 @@ fn analyze_dd(
                 output_record.push_field(&entry.errors_s.to_string());
                 output_record.push_field(&entry.errors_e.to_string());
                 output_record.push_field(&entry.errors_w.to_string());
+                output_record.push_field(&entry.cost_sum_n.to_string());
+                output_record.push_field(&entry.cost_sum_s.to_string());
+                output_record.push_field(&entry.cost_sum_e.to_string());
+                output_record.push_field(&entry.cost_sum_w.to_string());
+                output_record.push_field(&entry.revokes_n.to_string());
+                output_record.push_field(&entry.revokes_s.to_string());
+                output_record.push_field(&entry.revokes_e.to_string());
+                output_record.push_field(&entry.revokes_w.to_string());
+                output_record.push_field(&entry.illegal.to_string());
+                output_record.push_field(&entry.severity_n.minors.to_string());
+                output_record.push_field(&entry.severity_n.majors.to_string());
+                output_record.push_field(&entry.severity_n.blunders.to_string());
+                output_record.push_field(&entry.severity_s.minors.to_string());
+                output_record.push_field(&entry.severity_s.majors.to_string());
+                output_record.push_field(&entry.severity_s.blunders.to_string());
+                output_record.push_field(&entry.severity_e.minors.to_string());
+                output_record.push_field(&entry.severity_e.majors.to_string());
+                output_record.push_field(&entry.severity_e.blunders.to_string());
+                output_record.push_field(&entry.severity_w.minors.to_string());
+                output_record.push_field(&entry.severity_w.majors.to_string());
+                output_record.push_field(&entry.severity_w.blunders.to_string());
+                output_record.push_field(&entry.diagnostics);
+                output_record.push_field(&entry.categories);
+                output_record.push_field(&entry.vulnerable.to_string());
+                output_record.push_field(&entry.score.to_string());
+                output_record.push_field(&entry.dd_par_score.to_string());
+                output_record.push_field(&entry.score_imps.to_string());
+                output_record.push_field(&entry.voids_n);
+                output_record.push_field(&entry.voids_s);
+                output_record.push_field(&entry.voids_e);
+                output_record.push_field(&entry.voids_w);
+                output_record.push_field(&entry.auction_valid.to_string());
+                output_record.push_field(&entry.par_contract);
+                output_record.push_field(&entry.par_score.to_string());
+                output_record.push_field(&entry.par_delta.to_string());
+                output_record.push_field(&entry.sd_ambiguous_n.to_string());
+                output_record.push_field(&entry.sd_ambiguous_s.to_string());
+                output_record.push_field(&entry.sd_ambiguous_e.to_string());
+                output_record.push_field(&entry.sd_ambiguous_w.to_string());
+                output_record.push_field(&entry.sd_excess_n.to_string());
+                output_record.push_field(&entry.sd_excess_s.to_string());
+                output_record.push_field(&entry.sd_excess_e.to_string());
+                output_record.push_field(&entry.sd_excess_w.to_string());
                 output_record.push_field(&entry.analysis);
             } else {
-                // Empty values for rows we didn't process (12 columns now)
-                for _ in 0..12 {
+                // Empty values for rows we didn't process (55 columns now)
+                for _ in 0..55 {
                     output_record.push_field("");
                 }
             }
@@ -790,9 +1847,10 @@ fn analyze_dd(
     writer.flush()?;
 
     let errors = error_count.load(Ordering::Relaxed);
+    let cache_hits = cache_hit_count.load(Ordering::Relaxed);
     eprintln!(
-        "Done! Analyzed {} rows ({} errors)",
-        to_process, errors
+        "Done! Analyzed {} rows ({} errors, {} cache hits)",
+        to_process, errors, cache_hits
     );
 
     // Report DD validation statistics
@@ -817,6 +1875,159 @@ fn analyze_dd(
     Ok(())
 }
 
+/// Measure sustained DD-analysis throughput on a fixed sample of boards
+/// from `input`, repeating the workload until `duration_secs` has elapsed.
+///
+/// Unlike `analyze_dd`, nothing is written back to disk: this exists to let
+/// an operator empirically tune `--threads` (and to replace the Fetch tab's
+/// hardcoded network-overhead estimate with a measured rate) rather than to
+/// process a file.
+fn bench_dd(
+    input: &PathBuf,
+    threads: Option<usize>,
+    sample_size: usize,
+    duration_secs: u64,
+) -> Result<()> {
+    if let Some(n) = threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(n)
+            .build_global()
+            .ok();
+    }
+    let threads_used = rayon::current_num_threads();
+
+    let mut reader = ReaderBuilder::new()
+        .flexible(true)
+        .from_path(input)
+        .context("Failed to open input CSV")?;
+    let headers = reader.headers()?.clone();
+    let col_indices = find_required_columns(&headers)?;
+
+    let mut sample: Vec<DdWorkItem> = Vec::new();
+    for (row_idx, result) in reader.records().enumerate() {
+        if sample.len() >= sample_size {
+            break;
+        }
+        let record = result.context("Failed to read CSV row")?;
+        let ref_id = record.get(col_indices.ref_col).unwrap_or("").to_string();
+        let max_dd: Option<i8> = col_indices
+            .max_dd_col
+            .and_then(|col| record.get(col))
+            .and_then(|s| s.parse::<i8>().ok());
+        if max_dd == Some(-1) {
+            continue;
+        }
+        let cardplay = record.get(col_indices.cardplay_col).unwrap_or("").to_string();
+        if cardplay.is_empty() || cardplay.starts_with("ERROR:") {
+            continue;
+        }
+        if let Some(row_data) = extract_row_data(&record, &col_indices) {
+            let contract_upper = row_data.contract.to_uppercase();
+            if contract_upper.starts_with("0") || contract_upper == "P" || contract_upper == "PASS" {
+                continue;
+            }
+            let vul = col_indices.vul_col.and_then(|col| record.get(col)).map(|s| s.to_string());
+
+            sample.push(DdWorkItem {
+                row_idx,
+                ref_id,
+                deal_pbn: row_data.deal_pbn,
+                cardplay,
+                contract: row_data.contract,
+                declarer: row_data.declarer,
+                max_dd,
+                vul,
+                auction_valid: row_data.auction_valid,
+                sd_gap_samples: None,
+            });
+        }
+    }
+
+    if sample.is_empty() {
+        return Err(anyhow::anyhow!(
+            "No usable boards found in {:?} to benchmark (need a non-passout Cardplay row)",
+            input
+        ));
+    }
+    if sample.len() < sample_size {
+        eprintln!(
+            "Only found {} usable boards (requested sample of {})",
+            sample.len(),
+            sample_size
+        );
+    }
+
+    eprintln!(
+        "Benchmarking DD analysis: {} boards, {} threads, {}s target duration",
+        sample.len(),
+        threads_used,
+        duration_secs
+    );
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(duration_secs);
+    let mut latencies_ms: Vec<f64> = Vec::new();
+    let mut boards_processed: u64 = 0;
+    let mut card_evaluations: u64 = 0;
+    let mut busy_secs: f64 = 0.0;
+    let start = std::time::Instant::now();
+
+    while std::time::Instant::now() < deadline {
+        let pass_latencies: Mutex<Vec<f64>> = Mutex::new(Vec::with_capacity(sample.len()));
+        let pass_evaluations = AtomicUsize::new(0);
+
+        sample.par_iter().for_each(|item| {
+            let item_start = std::time::Instant::now();
+            let evaluations = match compute_dd_analysis(item) {
+                Ok(output) => {
+                    (output.plays_n as usize
+                        + output.plays_s as usize
+                        + output.plays_e as usize
+                        + output.plays_w as usize)
+                }
+                Err(_) => 0,
+            };
+            let elapsed_ms = item_start.elapsed().as_secs_f64() * 1000.0;
+            pass_evaluations.fetch_add(evaluations, Ordering::Relaxed);
+            pass_latencies.lock().unwrap().push(elapsed_ms);
+        });
+
+        let pass_latencies = pass_latencies.into_inner().unwrap();
+        busy_secs += pass_latencies.iter().map(|ms| ms / 1000.0).sum::<f64>();
+        boards_processed += pass_latencies.len() as u64;
+        card_evaluations += pass_evaluations.load(Ordering::Relaxed) as u64;
+        latencies_ms.extend(pass_latencies);
+    }
+
+    let elapsed = start.elapsed().as_secs_f64();
+    latencies_ms.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let percentile = |p: f64| -> f64 {
+        if latencies_ms.is_empty() {
+            return 0.0;
+        }
+        let idx = ((latencies_ms.len() as f64 - 1.0) * p).round() as usize;
+        latencies_ms[idx]
+    };
+    let boards_per_sec = boards_processed as f64 / elapsed;
+    let evals_per_sec = card_evaluations as f64 / elapsed;
+    let utilization_pct = (busy_secs / (elapsed * threads_used as f64) * 100.0).min(100.0);
+
+    eprintln!();
+    eprintln!("=== DD Benchmark ===");
+    eprintln!("Boards processed: {} in {:.1}s", boards_processed, elapsed);
+    eprintln!("Throughput: {:.1} boards/sec, {:.0} card-evaluations/sec", boards_per_sec, evals_per_sec);
+    eprintln!(
+        "Per-board latency: p50 {:.2}ms, p95 {:.2}ms",
+        percentile(0.50),
+        percentile(0.95)
+    );
+    eprintln!(
+        "Average thread utilization: {:.0}% at --threads={}",
+        utilization_pct, threads_used
+    );
+
+    Ok(())
+}
+
 /// Column indices for required fields
 struct ColumnIndices {
     ref_col: usize,
@@ -825,6 +2036,7 @@ struct ColumnIndices {
     declarer_col: Option<usize>,
     lin_url_col: Option<usize>,
     max_dd_col: Option<usize>,
+    vul_col: Option<usize>,
     // Hand columns (actual PBN-style hand data, not player names)
     north_col: Option<usize>,
     south_col: Option<usize>,
@@ -862,6 +2074,7 @@ fn find_required_columns(headers: &StringRecord) -> Result<ColumnIndices> {
         declarer_col,
         lin_url_col,
         max_dd_col: find_optional("Max DD"),
+        vul_col: find_optional("Vul"),
         // Look for hand columns (might be PBN-style hands or player names)
         north_col: find_optional("North").or_else(|| find_optional("N_Hand")),
         south_col: find_optional("South").or_else(|| find_optional("S_Hand")),
@@ -875,6 +2088,13 @@ struct RowData {
     deal_pbn: String,
     contract: String,
     declarer: String,
+    /// On the LIN path: whether the LIN auction parsed and validated cleanly
+    /// as a legal bidding sequence and its declarer agrees with the
+    /// opening-lead heuristic (or there was no lead to check against). This
+    /// holds even when explicit `Con`/`Dec` columns supplied the contract and
+    /// declarer. Always `true` when the deal, contract and declarer all came
+    /// straight from explicit CSV columns, since no auction is consulted.
+    auction_valid: bool,
 }
 
 /// Extract deal, contract, and declarer from a CSV row
@@ -895,6 +2115,7 @@ fn extract_row_data(record: &StringRecord, cols: &ColumnIndices) -> Option<RowDa
                     deal_pbn,
                     contract,
                     declarer,
+                    auction_valid: true,
                 });
             }
         }
@@ -907,19 +2128,34 @@ fn extract_row_data(record: &StringRecord, cols: &ColumnIndices) -> Option<RowDa
                 if let Ok(lin_data) = parse_lin_from_url(url) {
                     let deal_pbn = lin_data.deal.to_pbn(bridge_parsers::Direction::North);
 
-                    // Use explicit columns if available, otherwise extract from LIN
-                    let contract = contract_from_col
-                        .filter(|s| !s.is_empty())
-                        .unwrap_or_else(|| extract_contract_from_lin(&lin_data));
-                    let declarer = declarer_from_col
-                        .filter(|s| !s.is_empty())
-                        .unwrap_or_else(|| extract_declarer_from_lin(&lin_data));
+                    // The parsed Auction is the primary source for contract
+                    // and declarer; the opening-lead heuristic only serves
+                    // as a cross-check now, since it can't see conventional
+                    // (artificial) bids that named a suit other than the
+                    // one eventually led.
+                    let auction_result = auction_from_lin(&lin_data).ok().and_then(|a| a.result());
+                    let lead_card_declarer = extract_declarer_from_lin(&lin_data);
+
+                    let (auction_contract, auction_declarer, auction_valid) = match auction_result {
+                        Some(result) => {
+                            let declarer = auction_seat_letter(result.declarer);
+                            let agrees_with_lead = lead_card_declarer.is_empty()
+                                || lead_card_declarer.eq_ignore_ascii_case(declarer);
+                            (result.contract.to_string(), declarer.to_string(), agrees_with_lead)
+                        }
+                        None => (extract_contract_from_lin(&lin_data), lead_card_declarer, false),
+                    };
+
+                    // Explicit columns still win over anything derived from LIN.
+                    let contract = contract_from_col.filter(|s| !s.is_empty()).unwrap_or(auction_contract);
+                    let declarer = declarer_from_col.filter(|s| !s.is_empty()).unwrap_or(auction_declarer);
 
                     if !contract.is_empty() && !declarer.is_empty() {
                         return Some(RowData {
                             deal_pbn,
                             contract,
                             declarer,
+                            auction_valid,
                         });
                     }
                 }
@@ -930,6 +2166,30 @@ fn extract_row_data(record: &StringRecord, cols: &ColumnIndices) -> Option<RowDa
     None
 }
 
+/// Parse a LIN auction's bid strings into a validated [`Auction`], using
+/// the LIN dealer as the seat of the first call.
+fn auction_from_lin(
+    lin_data: &bridge_parsers::lin::LinData,
+) -> Result<edgar_defense_toolkit::auction::Auction, String> {
+    let dealer = lin_data.dealer as usize;
+    let calls = lin_data
+        .auction
+        .iter()
+        .map(|bid| bid.bid.parse::<edgar_defense_toolkit::auction::Call>())
+        .collect::<Result<Vec<_>, _>>()?;
+    edgar_defense_toolkit::auction::Auction::new(dealer, calls)
+}
+
+/// Seat letter for an [`Auction`] seat index (`0..=3`, N/E/S/W).
+fn auction_seat_letter(seat: usize) -> &'static str {
+    match seat % 4 {
+        0 => "N",
+        1 => "E",
+        2 => "S",
+        _ => "W",
+    }
+}
+
 /// Try to build a PBN deal from hand columns
 /// Returns None if columns don't exist or don't contain valid hand data
 fn build_deal_from_hand_cols(record: &StringRecord, cols: &ColumnIndices) -> Option<String> {
@@ -1092,6 +2352,7 @@ fn extract_declarer_from_auction(lin_data: &bridge_parsers::lin::LinData) -> Str
 }
 
 /// Result from DD analysis including validation info
+#[derive(Clone)]
 struct DdAnalysisOutput {
     analysis: String,
     initial_dd: u8,
@@ -1107,8 +2368,210 @@ struct DdAnalysisOutput {
     errors_s: u8,
     errors_e: u8,
     errors_w: u8,
+    /// Per-seat total DD trick cost (N, S, E, W) -- the sum of each play's
+    /// cost, not just a count of plays with cost > 0, so downstream stats
+    /// (Defense Above Replacement) can be expressed in actual tricks
+    cost_sum_n: u32,
+    cost_sum_s: u32,
+    cost_sum_e: u32,
+    cost_sum_w: u32,
+    /// Per-seat revoke counts (N, S, E, W) -- a seat had the led suit but
+    /// didn't follow it
+    revokes_n: u8,
+    revokes_s: u8,
+    revokes_e: u8,
+    revokes_w: u8,
+    /// Set when some card in the cardplay wasn't in the player's holding at
+    /// all -- a data-quality signal that the row's deal/cardplay are
+    /// mis-parsed, distinct from a genuine revoke
+    illegal: bool,
+    /// Per-seat severity tallies from the play-rule engine (N, S, E, W)
+    severity_n: dd_analysis::SeatSeverityCounts,
+    severity_s: dd_analysis::SeatSeverityCounts,
+    severity_e: dd_analysis::SeatSeverityCounts,
+    severity_w: dd_analysis::SeatSeverityCounts,
+    /// Cost string with flagged cards tagged `rule_id:severity`
+    diagnostics: String,
+    /// Per-seat rule-id tallies from the play-rule engine, e.g.
+    /// `N:opening-lead=1;E:defensive-carding=2,declarer-line=1`
+    categories: String,
+    /// Whether declarer's side was vulnerable, from the input `Vul` column
+    vulnerable: bool,
+    /// Duplicate score for the actual result, from declarer's perspective
+    /// (negative when declarer went down)
+    score: i32,
+    /// Duplicate score for the same contract had declarer taken the
+    /// DD-optimal number of tricks instead of what was actually taken
+    dd_par_score: i32,
+    /// `score - dd_par_score` converted to IMPs -- how many IMPs declarer's
+    /// actual play cost (negative) or gained (positive, e.g. from a
+    /// defensive error) relative to the DD-optimal result in this contract
+    score_imps: i32,
+    /// Per-seat discovered voids, e.g. `S@3,H@7` (void in spades from trick
+    /// 3, hearts from trick 7)
+    voids_n: String,
+    voids_s: String,
+    voids_e: String,
+    voids_w: String,
+    /// Par contract for the board, e.g. `"4S-N"`, from solving all 20
+    /// declarer/strain combinations (`"Passed"` if neither side can make
+    /// anything)
+    par_contract: String,
+    /// Par score, from the actual declarer's side's perspective (so it's
+    /// directly comparable to `score`)
+    par_score: i32,
+    /// `score - par_score`: how far the actual result fell from double-dummy
+    /// optimal bidding and play by both sides, independent of `dd_par_score`
+    /// (which only asks whether *this* contract was played optimally)
+    par_delta: i32,
+    /// Per-seat count of defending tricks where restricted (single-dummy)
+    /// information left real doubt about the best play -- only populated
+    /// when `--single-dummy-gap` is passed, since it costs a constrained
+    /// redeal per trick. See [`DdWorkItem::sd_gap_samples`].
+    sd_ambiguous_n: u8,
+    sd_ambiguous_s: u8,
+    sd_ambiguous_e: u8,
+    sd_ambiguous_w: u8,
+    /// Of `sd_ambiguous_*`, how many the defender nonetheless resolved to
+    /// the double-dummy-optimal card -- a player whose excess rate
+    /// (`sd_excess / sd_ambiguous`) is unusually high is hitting the
+    /// omniscient line exactly where single-dummy knowledge alone
+    /// shouldn't have made it obvious.
+    sd_excess_n: u8,
+    sd_excess_s: u8,
+    sd_excess_e: u8,
+    sd_excess_w: u8,
+}
+
+/// Parse a `Vul` column value (`"None"`, `"NS"`, `"EW"`, or `"All"`/`"Both"`)
+/// into the `Vulnerability` type `dd_table::compute_dd_table_and_par` takes.
+fn parse_vulnerability(vul: Option<&str>) -> Vulnerability {
+    match vul.unwrap_or("").trim().to_uppercase().as_str() {
+        "ALL" | "BOTH" => Vulnerability::Both,
+        "NS" => Vulnerability::NorthSouth,
+        "EW" => Vulnerability::EastWest,
+        _ => Vulnerability::None,
+    }
+}
+
+/// Whether declarer's side is vulnerable, from a `Vul` column value
+/// (`"None"`, `"NS"`, `"EW"`, or `"All"`/`"Both"`).
+fn is_declarer_vulnerable(vul: Option<&str>, declarer: &str) -> bool {
+    let vul = vul.unwrap_or("").trim().to_uppercase();
+    let declarer_is_ns = matches!(declarer.trim().to_uppercase().chars().next(), Some('N') | Some('S'));
+    match vul.as_str() {
+        "ALL" | "BOTH" => true,
+        "NS" => declarer_is_ns,
+        "EW" => !declarer_is_ns,
+        _ => false,
+    }
+}
+
+/// Splitmix64 finalizer, used to turn a small integer feature index into a
+/// well-mixed pseudo-random 64-bit value for [`zobrist_hash`].
+fn splitmix64(x: u64) -> u64 {
+    let x = x.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = x;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// FNV-1a over a string, used to fold the non-deal fields into the
+/// [`zobrist_hash`].
+fn string_hash(s: &str) -> u64 {
+    let mut h = 0xcbf29ce484222325u64;
+    for b in s.bytes() {
+        h ^= b as u64;
+        h = h.wrapping_mul(0x100000001b3);
+    }
+    h
+}
+
+/// Zobrist-style hash identifying a distinct [`DdWorkItem`] for DD-analysis
+/// memoization: XORs a pseudo-random value per (hand position, suit, rank)
+/// card feature parsed out of `deal_pbn`'s 52 cards with values folded in
+/// from `contract`, `declarer`, `cardplay`, and `vul`. Large exports often
+/// contain many rows for the same board (replays, duplicate boards across
+/// tables), and `compute_dd_analysis`'s DD solve dominates runtime, so
+/// collapsing those to one solve per distinct key is worth the extra hash.
+/// `cardplay` and `vul` have to be part of the key alongside the deal and
+/// contract -- the per-card costs and the vulnerability-dependent scoring
+/// fields aren't determined by the board alone, only by the full row.
+fn zobrist_hash(item: &DdWorkItem) -> u64 {
+    let mut hash = 0u64;
+
+    if let Some((_, hands_str)) = item.deal_pbn.split_once(':') {
+        for (hand_idx, hand_str) in hands_str.split_whitespace().enumerate() {
+            for (suit_idx, ranks) in hand_str.split('.').enumerate() {
+                for rank_char in ranks.chars() {
+                    let feature = ((hand_idx as u64) * 4 + suit_idx as u64) * 128 + rank_char as u64;
+                    hash ^= splitmix64(feature);
+                }
+            }
+        }
+    }
+
+    for (field_idx, field) in
+        [item.contract.as_str(), item.declarer.as_str(), item.cardplay.as_str(), item.vul.as_deref().unwrap_or("")]
+            .iter()
+            .enumerate()
+    {
+        hash ^= splitmix64(1_000_000 + field_idx as u64) ^ string_hash(field);
+    }
+
+    hash
+}
+
+/// Compute the board's double-dummy par contract and score (solving all 20
+/// declarer/strain combinations), and `actual_score - par_score` -- how far
+/// the actual result fell from the equilibrium both sides would reach under
+/// optimal bidding and play, including sacrifices. `actual_score` must
+/// already be from the actual declarer's side's perspective, as `score` is;
+/// `par_score` is converted to that same perspective so the two subtract
+/// cleanly regardless of which side ends up declaring at par.
+fn par_summary_for_item(item: &DdWorkItem, actual_score: i32) -> (String, i32, i32) {
+    let Ok(hands) = Hands::from_pbn(&item.deal_pbn) else {
+        return (String::new(), 0, 0);
+    };
+    let vulnerability = parse_vulnerability(item.vul.as_deref());
+    let par = dd_table::compute_par(&dd_table::compute_dd_table(&hands), &vulnerability);
+
+    let Some(level) = par.level else {
+        return ("Passed".to_string(), 0, actual_score);
+    };
+
+    let declarer_is_ns = matches!(item.declarer.trim().to_uppercase().chars().next(), Some('N') | Some('S'));
+    let par_is_ns = par.declaring_side == dd_table::Side::NorthSouth;
+    let par_score = if par_is_ns == declarer_is_ns { par.score } else { -par.score };
+
+    let strain_letter = match par.strain {
+        SPADE => "S",
+        HEART => "H",
+        DIAMOND => "D",
+        CLUB => "C",
+        _ => "NT",
+    };
+    let seat_letter = match par.declarer_seat {
+        NORTH => 'N',
+        SOUTH => 'S',
+        EAST => 'E',
+        WEST => 'W',
+        _ => '?',
+    };
+    let par_contract = format!("{}{}-{}", level, strain_letter, seat_letter);
+
+    (par_contract, par_score, actual_score - par_score)
 }
 
+/// Minimum single-dummy expected-tricks swing (see
+/// `single_dummy::compute_single_dummy_costs`) for a trick to count as
+/// "ambiguous" under restricted information in the `--single-dummy-gap`
+/// check -- below this, the trick's outcome was close to a foregone
+/// conclusion even without double-dummy knowledge, so matching the DD line
+/// there isn't informative.
+const SD_AMBIGUOUS_THRESHOLD: f64 = 0.5;
+
 /// Compute DD analysis for a single work item
 ///
 /// For each card played, computes the DD cost of the actual play vs optimal.
@@ -1116,27 +2579,57 @@ struct DdAnalysisOutput {
 /// Output format: T1:c1,c2,c3,c4|T2:c1,c2,c3,c4|... where each c is the cost for that card
 fn compute_dd_analysis(item: &DdWorkItem) -> Result<DdAnalysisOutput> {
     // Use the shared library function for DD computation
+    let contract: edgar_defense_toolkit::contract::Contract =
+        item.contract.parse().map_err(|e: String| anyhow::anyhow!("{}", e))?;
     let result = compute_dd_costs(
         &item.deal_pbn,
         &item.cardplay,
-        &item.contract,
+        &contract,
         &item.declarer,
         false, // no debug output
+        false, // no best-card enumeration
+        false, // no alternative-baseline cost attribution
     ).map_err(|e| anyhow::anyhow!("{}", e))?;
 
     if result.costs.is_empty() {
+        let (par_contract, par_score, par_delta) = par_summary_for_item(item, 0);
         return Ok(DdAnalysisOutput {
             analysis: String::new(),
             initial_dd: result.initial_dd,
             ol_error: 0,
             plays_n: 0, plays_s: 0, plays_e: 0, plays_w: 0,
             errors_n: 0, errors_s: 0, errors_e: 0, errors_w: 0,
+            cost_sum_n: 0, cost_sum_s: 0, cost_sum_e: 0, cost_sum_w: 0,
+            revokes_n: 0, revokes_s: 0, revokes_e: 0, revokes_w: 0,
+            illegal: false,
+            severity_n: Default::default(), severity_s: Default::default(),
+            severity_e: Default::default(), severity_w: Default::default(),
+            diagnostics: String::new(),
+            categories: String::new(),
+            vulnerable: is_declarer_vulnerable(item.vul.as_deref(), &item.declarer),
+            score: 0,
+            dd_par_score: 0,
+            score_imps: 0,
+            voids_n: String::new(),
+            voids_s: String::new(),
+            voids_e: String::new(),
+            voids_w: String::new(),
+            par_contract,
+            par_score,
+            par_delta,
+            sd_ambiguous_n: 0, sd_ambiguous_s: 0, sd_ambiguous_e: 0, sd_ambiguous_w: 0,
+            sd_excess_n: 0, sd_excess_s: 0, sd_excess_e: 0, sd_excess_w: 0,
         });
     }
 
     // Track per-seat plays and errors
     let mut plays = [0u8; 4];  // indexed by seat constant (NORTH, EAST, SOUTH, WEST)
     let mut errors = [0u8; 4];
+    let mut cost_sums = [0u32; 4];
+    let mut declarer_side_tricks = 0u8;
+    // Suits a seat is discovered to be void in, and the trick at which the
+    // void became apparent: `(suit_char, trick_num)`, in discovery order.
+    let mut voids: [Vec<(char, usize)>; 4] = Default::default();
 
     // Opening lead error: check if the first card of trick 1 cost a trick
     let ol_error = if !result.costs.is_empty() && !result.costs[0].is_empty() {
@@ -1164,26 +2657,57 @@ fn compute_dd_analysis(item: &DdWorkItem) -> Result<DdAnalysisOutput> {
             if cost > 0 {
                 errors[seat] += 1;
             }
+            cost_sums[seat] += cost as u32;
             seat = (seat + 1) % 4;
         }
 
         // Determine trick winner for next trick's leader
         // We need to parse the cards to determine the winner
         if trick_idx < tricks.len() && tricks[trick_idx].len() == 4 {
+            // A seat that follows with a different suit than the one led,
+            // despite not having been dealt a stopper earlier, is now known
+            // void in the led suit -- stamp it the first time it happens.
+            let trick_cards = &tricks[trick_idx];
+            if let Some(led_suit) = trick_cards[0].chars().next().map(|c| c.to_ascii_uppercase()) {
+                let mut seat = current_leader;
+                for card in trick_cards.iter().skip(1) {
+                    seat = (seat + 1) % 4;
+                    if let Some(suit) = card.chars().next().map(|c| c.to_ascii_uppercase()) {
+                        if suit != led_suit && !voids[seat].iter().any(|(s, _)| *s == led_suit) {
+                            voids[seat].push((led_suit, trick_idx + 1));
+                        }
+                    }
+                }
+            }
+
             // For simplicity, we'll track winners using the cardplay
-            // Parse trump from contract
-            let trump = parse_trump_for_winner(&item.contract);
+            let trump = edgar_defense_toolkit::cards::trump_from_contract(&item.contract);
             if let Some(winner) = determine_trick_winner_from_cards(
                 &tricks[trick_idx],
                 trump,
                 current_leader,
             ) {
                 current_leader = winner;
+                if winner == result.declarer_seat || winner == (result.declarer_seat + 2) % 4 {
+                    declarer_side_tricks += 1;
+                }
             }
             // If we can't determine the winner, keep current_leader unchanged
         }
     }
 
+    let format_voids = |seat_voids: &[(char, usize)]| -> String {
+        seat_voids
+            .iter()
+            .map(|(suit, trick_num)| format!("{}@{}", suit, trick_num))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let voids_n = format_voids(&voids[NORTH]);
+    let voids_s = format_voids(&voids[SOUTH]);
+    let voids_e = format_voids(&voids[EAST]);
+    let voids_w = format_voids(&voids[WEST]);
+
     // Format the costs as T1:c1,c2,c3,c4|T2:c1,c2,c3,c4|...
     let trick_results: Vec<String> = result
         .costs
@@ -1199,6 +2723,98 @@ fn compute_dd_analysis(item: &DdWorkItem) -> Result<DdAnalysisOutput> {
         })
         .collect();
 
+    let (revokes_n, revokes_s, revokes_e, revokes_w, illegal) = dd_analysis::detect_revokes(
+        &item.deal_pbn,
+        &item.cardplay,
+        &item.contract,
+        &item.declarer,
+    )
+    .map(|report| {
+        let mut by_seat = [0u8; 4];
+        for event in &report.revokes {
+            by_seat[event.seat] = by_seat[event.seat].saturating_add(1);
+        }
+        (by_seat[NORTH], by_seat[SOUTH], by_seat[EAST], by_seat[WEST], report.illegal)
+    })
+    .unwrap_or_default();
+
+    let (severity_n, severity_s, severity_e, severity_w, diagnostics, categories) =
+        dd_analysis::compute_dd_diagnostics(
+            &item.deal_pbn,
+            &item.cardplay,
+            &item.contract,
+            &item.declarer,
+            &dd_analysis::SeverityThresholds::default(),
+            &dd_analysis::default_rules(),
+        )
+        .map(|d| {
+            (
+                d.seat_counts.get(&NORTH).copied().unwrap_or_default(),
+                d.seat_counts.get(&SOUTH).copied().unwrap_or_default(),
+                d.seat_counts.get(&EAST).copied().unwrap_or_default(),
+                d.seat_counts.get(&WEST).copied().unwrap_or_default(),
+                d.tagged_analysis.clone(),
+                format_error_categories(&d.diagnostics),
+            )
+        })
+        .unwrap_or_default();
+
+    let vulnerable = is_declarer_vulnerable(item.vul.as_deref(), &item.declarer);
+    let score = score_contract(
+        contract.level,
+        contract.trump(),
+        contract.doubling,
+        declarer_side_tricks,
+        vulnerable,
+    );
+    let dd_par_score = score_contract(
+        contract.level,
+        contract.trump(),
+        contract.doubling,
+        result.initial_dd,
+        vulnerable,
+    );
+    let score_imps = points_to_imps(score - dd_par_score);
+    let (par_contract, par_score, par_delta) = par_summary_for_item(item, score);
+
+    // Single-dummy-vs-double-dummy gap check (opt-in, see
+    // `DdWorkItem::sd_gap_samples`): at each trick a defender led, compare
+    // the restricted-information expected-tricks swing (trick-boundary
+    // granularity, the same as `single_dummy::compute_single_dummy_costs`
+    // itself) to the true DD cost of that trick's cards to find tricks the
+    // defender resolved to the DD-optimal line despite real single-dummy
+    // ambiguity.
+    let mut sd_ambiguous = [0u8; 4];
+    let mut sd_excess = [0u8; 4];
+    if let Some(samples) = item.sd_gap_samples {
+        let config = edgar_defense_toolkit::single_dummy::SingleDummyConfig { samples };
+        if let Ok(sd_result) = edgar_defense_toolkit::single_dummy::compute_single_dummy_costs(
+            &item.deal_pbn,
+            &item.cardplay,
+            &item.contract,
+            &item.declarer,
+            &config,
+        ) {
+            let trump = edgar_defense_toolkit::cards::trump_from_contract(&item.contract);
+            let mut leader = initial_leader;
+            for (trick_idx, &sd_swing) in sd_result.costs.iter().enumerate() {
+                let is_defender = leader != result.declarer_seat && leader != (result.declarer_seat + 2) % 4;
+                if is_defender && sd_swing >= SD_AMBIGUOUS_THRESHOLD {
+                    sd_ambiguous[leader] = sd_ambiguous[leader].saturating_add(1);
+                    let trick_dd_cost: u32 = result.costs.get(trick_idx).map(|c| c.iter().map(|&x| x as u32).sum()).unwrap_or(0);
+                    if trick_dd_cost == 0 {
+                        sd_excess[leader] = sd_excess[leader].saturating_add(1);
+                    }
+                }
+                if trick_idx < tricks.len() && tricks[trick_idx].len() == 4 {
+                    if let Some(winner) = determine_trick_winner_from_cards(&tricks[trick_idx], trump, leader) {
+                        leader = winner;
+                    }
+                }
+            }
+        }
+    }
+
     Ok(DdAnalysisOutput {
         analysis: trick_results.join("|"),
         initial_dd: result.initial_dd,
@@ -1211,112 +2827,94 @@ fn compute_dd_analysis(item: &DdWorkItem) -> Result<DdAnalysisOutput> {
         errors_s: errors[SOUTH],
         errors_e: errors[EAST],
         errors_w: errors[WEST],
+        cost_sum_n: cost_sums[NORTH],
+        cost_sum_s: cost_sums[SOUTH],
+        cost_sum_e: cost_sums[EAST],
+        cost_sum_w: cost_sums[WEST],
+        revokes_n,
+        revokes_s,
+        revokes_e,
+        revokes_w,
+        illegal,
+        severity_n,
+        severity_s,
+        severity_e,
+        severity_w,
+        diagnostics,
+        categories,
+        vulnerable,
+        score,
+        dd_par_score,
+        score_imps,
+        voids_n,
+        voids_s,
+        voids_e,
+        voids_w,
+        par_contract,
+        par_score,
+        par_delta,
+        sd_ambiguous_n: sd_ambiguous[NORTH],
+        sd_ambiguous_s: sd_ambiguous[SOUTH],
+        sd_ambiguous_e: sd_ambiguous[EAST],
+        sd_ambiguous_w: sd_ambiguous[WEST],
+        sd_excess_n: sd_excess[NORTH],
+        sd_excess_s: sd_excess[SOUTH],
+        sd_excess_e: sd_excess[EAST],
+        sd_excess_w: sd_excess[WEST],
     })
 }
 
-/// Parse trump suit from contract for trick winner determination
-fn parse_trump_for_winner(contract: &str) -> Option<usize> {
-    let contract = contract.trim().to_uppercase();
-    if contract.contains("NT") {
-        return None; // No trump
+/// Tally each flagged card's rule id by seat, for the `DD_Error_Categories`
+/// column -- a coarser, per-player-attributable summary of the same
+/// `Diagnostic`s that `tagged_analysis` already spells out per card, so
+/// `compute_stats` can break a player's errors down by category without
+/// re-parsing the tagged cost string.
+fn format_error_categories(diagnostics: &[dd_analysis::Diagnostic]) -> String {
+    let mut by_seat: HashMap<usize, HashMap<&'static str, u32>> = HashMap::new();
+    for diag in diagnostics {
+        *by_seat.entry(diag.seat).or_default().entry(diag.rule_id).or_insert(0) += 1;
     }
-    for c in contract.chars() {
-        match c {
-            'S' => return Some(SPADE),
-            'H' => return Some(HEART),
-            'D' => return Some(DIAMOND),
-            'C' => return Some(CLUB),
-            _ => continue,
-        }
-    }
-    None
+
+    [NORTH, EAST, SOUTH, WEST]
+        .into_iter()
+        .filter_map(|seat| {
+            let counts = by_seat.get(&seat)?;
+            let mut categories: Vec<(&str, u32)> = counts.iter().map(|(&id, &n)| (id, n)).collect();
+            categories.sort_by_key(|(id, _)| *id);
+            let cat_str = categories
+                .iter()
+                .map(|(id, n)| format!("{}={}", id, n))
+                .collect::<Vec<_>>()
+                .join(",");
+            let seat_char = match seat {
+                NORTH => 'N',
+                SOUTH => 'S',
+                EAST => 'E',
+                WEST => 'W',
+                _ => '?',
+            };
+            Some(format!("{}:{}", seat_char, cat_str))
+        })
+        .collect::<Vec<_>>()
+        .join(";")
 }
 
-/// Determine trick winner from card strings
+/// Determine trick winner from card strings, via `cards::trick_winner`.
 fn determine_trick_winner_from_cards(
     cards: &[&str],
-    trump: Option<usize>,
+    trump: edgar_defense_toolkit::cards::Suit,
     leader: usize,
 ) -> Option<usize> {
     if cards.len() != 4 {
         return None;
     }
 
-    // Parse cards to (suit, rank) where higher rank = better
-    let parsed: Vec<Option<(usize, u8)>> = cards
-        .iter()
-        .map(|s| {
-            if s.len() < 2 {
-                return None;
-            }
-            let suit = match s.chars().next()? {
-                'S' | 's' => SPADE,
-                'H' | 'h' => HEART,
-                'D' | 'd' => DIAMOND,
-                'C' | 'c' => CLUB,
-                _ => return None,
-            };
-            let rank_char = s.chars().nth(1)?;
-            let rank = match rank_char {
-                'A' | 'a' => 14,
-                'K' | 'k' => 13,
-                'Q' | 'q' => 12,
-                'J' | 'j' => 11,
-                'T' | 't' | '1' => 10,
-                '9' => 9, '8' => 8, '7' => 7, '6' => 6,
-                '5' => 5, '4' => 4, '3' => 3, '2' => 2,
-                _ => return None,
-            };
-            Some((suit, rank))
-        })
-        .collect();
-
-    // All cards must parse
-    let cards_parsed: Vec<(usize, u8)> = parsed.into_iter().collect::<Option<Vec<_>>>()?;
-
-    let led_suit = cards_parsed[0].0;
-    let mut winner_idx = 0;
-    let mut winner_card = cards_parsed[0];
-
-    for (i, &(suit, rank)) in cards_parsed.iter().enumerate().skip(1) {
-        let dominated = if let Some(trump_suit) = trump {
-            if suit == trump_suit && winner_card.0 != trump_suit {
-                // This card is trump, winner is not
-                true
-            } else if suit == trump_suit && winner_card.0 == trump_suit {
-                // Both trump, higher wins
-                rank > winner_card.1
-            } else if winner_card.0 == trump_suit {
-                // Winner is trump, this is not
-                false
-            } else if suit == led_suit && winner_card.0 == led_suit {
-                // Both follow suit, higher wins
-                rank > winner_card.1
-            } else if suit == led_suit {
-                // This follows suit, winner doesn't
-                true
-            } else {
-                // Neither trump nor following suit
-                false
-            }
-        } else {
-            // No trump
-            if suit == led_suit && winner_card.0 == led_suit {
-                rank > winner_card.1
-            } else if suit == led_suit {
-                true
-            } else {
-                false
-            }
-        };
-
-        if dominated {
-            winner_idx = i;
-            winner_card = (suit, rank);
-        }
-    }
+    let c0 = edgar_defense_toolkit::cards::Card::parse(cards[0])?;
+    let c1 = edgar_defense_toolkit::cards::Card::parse(cards[1])?;
+    let c2 = edgar_defense_toolkit::cards::Card::parse(cards[2])?;
+    let c3 = edgar_defense_toolkit::cards::Card::parse(cards[3])?;
 
-    Some((leader + winner_idx) % 4)
+    Some(edgar_defense_toolkit::cards::trick_winner([c0, c1, c2, c3], leader, trump))
 }
 
 // Functions below are used by tests only
@@ -1527,9 +3125,71 @@ const SURNAMES: &[&str] = &[
     "Watson", "West", "White", "Williams", "Wilson", "Wood", "Wright", "Young",
 ];
 
-/// Anonymizer that maps usernames to fake names using keyed hashing
+/// Hand-rolled HMAC-SHA256 (RFC 2104), since this tree doesn't carry the
+/// `hmac` crate -- only `sha2` is already a dependency (the LIN cache uses it
+/// for plain content digests above).
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    const BLOCK_SIZE: usize = 64;
+
+    let mut block_key = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        let digest = Sha256::digest(key);
+        block_key[..digest.len()].copy_from_slice(&digest);
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+
+    let mut ipad = [0x36u8; BLOCK_SIZE];
+    let mut opad = [0x5cu8; BLOCK_SIZE];
+    for i in 0..BLOCK_SIZE {
+        ipad[i] ^= block_key[i];
+        opad[i] ^= block_key[i];
+    }
+
+    let mut inner = Sha256::new();
+    inner.update(ipad);
+    inner.update(message);
+    let inner_digest = inner.finalize();
+
+    let mut outer = Sha256::new();
+    outer.update(opad);
+    outer.update(inner_digest);
+    outer.finalize().into()
+}
+
+/// Short fingerprint of `(salt, key)` recorded in the mapping sidecar, so a
+/// later run can confirm it was handed the same key without the sidecar
+/// having to store the key itself.
+fn key_fingerprint(key: &str, salt: &[u8; 16]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(key.as_bytes());
+    hex_encode(&hasher.finalize()[..8])
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn hex_decode(s: &str) -> Result<Vec<u8>, String> {
+    if s.len() % 2 != 0 {
+        return Err(format!("Odd-length hex string: {}", s));
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(|e| e.to_string()))
+        .collect()
+}
+
+/// Anonymizer that maps usernames to fake names using a salted, keyed MAC.
+///
+/// `salt` is freshly random for a brand-new mapping table, then pinned to
+/// whatever `load_mapping_file` finds on disk -- the salt has to stay fixed
+/// across runs sharing a `--map-file`, or the same username would hash to a
+/// different fake name every invocation.
 struct Anonymizer {
     key: String,
+    salt: [u8; 16],
     explicit_maps: HashMap<String, String>,
     generated_maps: HashMap<String, String>,
     used_names: HashSet<String>,
@@ -1555,8 +3215,12 @@ impl Anonymizer {
             }
         }
 
+        let mut salt = [0u8; 16];
+        rand::thread_rng().fill(&mut salt);
+
         Anonymizer {
             key: key.to_string(),
+            salt,
             explicit_maps,
             generated_maps: HashMap::new(),
             used_names,
@@ -1584,19 +3248,20 @@ impl Anonymizer {
         new_name
     }
 
-    /// Generate a unique name using keyed hash
+    /// Generate a unique name using the keyed MAC
     fn generate_name(&mut self, username: &str) -> String {
-        // Simple keyed hash: combine key + username, then hash
-        let combined = format!("{}:{}", self.key, username);
-        let hash = self.simple_hash(&combined);
+        let seed = self.keyed_seed(username);
 
-        // Use hash to pick first name and surname
-        let first_idx = (hash % FIRST_NAMES.len() as u64) as usize;
-        let surname_idx = ((hash / FIRST_NAMES.len() as u64) % SURNAMES.len() as u64) as usize;
+        // Use the seed to pick first name and surname
+        let first_idx = (seed % FIRST_NAMES.len() as u64) as usize;
+        let surname_idx = ((seed / FIRST_NAMES.len() as u64) % SURNAMES.len() as u64) as usize;
 
         let mut candidate = format!("{}_{}", FIRST_NAMES[first_idx], SURNAMES[surname_idx]);
 
-        // If name is already used (collision or explicit), add a number
+        // If name is already used (collision or explicit), add a number. This
+        // suffix is order-dependent (it depends on what's already in
+        // `used_names`), so it can't be recomputed from the key alone and
+        // has to be recorded verbatim in the saved mapping file.
         let mut suffix = 2;
         while self.used_names.contains(&candidate) {
             candidate = format!("{}_{}_{}", FIRST_NAMES[first_idx], SURNAMES[surname_idx], suffix);
@@ -1608,14 +3273,37 @@ impl Anonymizer {
         candidate
     }
 
-    /// Simple hash function (FNV-1a inspired)
-    fn simple_hash(&self, s: &str) -> u64 {
-        let mut hash: u64 = 0xcbf29ce484222325;
-        for byte in s.bytes() {
-            hash ^= byte as u64;
-            hash = hash.wrapping_mul(0x100000001b3);
-        }
-        hash
+    /// Derive an unpredictable-without-the-key seed for `username`.
+    ///
+    /// The old `simple_hash` was an unkeyed FNV-1a variant over `"key:username"`
+    /// -- anyone who suspected a username could hash it themselves and check
+    /// it against a leaked fake name to confirm the guess, which defeats the
+    /// point of anonymizing in the first place. This instead runs HMAC-SHA256
+    /// (hand-rolled from `sha2::Sha256`, since this tree has no `hmac` crate
+    /// to pull in) with a key derived from `self.key` and `self.salt`, and
+    /// takes the first 8 bytes of the MAC as the `u64` seed.
+    fn keyed_seed(&self, username: &str) -> u64 {
+        let mac = hmac_sha256(&self.derived_key(), username.as_bytes());
+        u64::from_be_bytes(mac[..8].try_into().unwrap())
+    }
+
+    /// The HMAC key for this table: `SHA-256(salt || key)`, so the salt
+    /// (rather than the raw key) is what actually seeds every name, and two
+    /// tables sharing a key but not a salt hash usernames completely
+    /// differently.
+    fn derived_key(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.salt);
+        hasher.update(self.key.as_bytes());
+        hasher.finalize().into()
+    }
+
+    /// Short fingerprint of `(salt, key)`, stored in the mapping sidecar so a
+    /// later run (or `deanonymize`) can tell whether the `--key` it was given
+    /// is the one that produced this table, without the sidecar needing to
+    /// hold the key itself.
+    fn key_fingerprint(&self) -> String {
+        key_fingerprint(&self.key, &self.salt)
     }
 
     /// Print summary of mappings
@@ -1625,15 +3313,156 @@ impl Anonymizer {
         eprintln!("  Generated names: {}", self.generated_maps.len());
         eprintln!("  Total unique names: {}", self.used_names.len());
     }
-}
 
-fn anonymize_csv(
-    input: &PathBuf,
-    output: &PathBuf,
-    key: &str,
-    map: &str,
-    columns: &[String],
-) -> Result<()> {
+    /// Load a previously-saved mapping sidecar, so repeated runs reuse the
+    /// same salt and assignments instead of drifting with encounter order.
+    /// A sidecar whose `key_fingerprint` doesn't match this run's `--key` is
+    /// left untouched -- it was generated under a different key and doesn't
+    /// belong to this run.
+    fn load_mapping_file(&mut self, path: &PathBuf) -> Result<()> {
+        let Some(sidecar) = MappingSidecar::load(path)? else {
+            return Ok(());
+        };
+
+        if sidecar.key_fingerprint != key_fingerprint(&self.key, &sidecar.salt()?) {
+            log::warn!(
+                "Mapping file {} was generated under a different key, ignoring it",
+                path.display()
+            );
+            return Ok(());
+        }
+
+        self.salt = sidecar.salt()?;
+        for entry in &sidecar.entries {
+            self.used_names.insert(entry.pseudonym.clone());
+            match entry.source.as_str() {
+                "explicit" => {
+                    self.explicit_maps.insert(entry.real.clone(), entry.pseudonym.clone());
+                }
+                _ => {
+                    self.generated_maps.insert(entry.real.clone(), entry.pseudonym.clone());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Save the full explicit + generated mapping table, plus the salt and
+    /// MAC algorithm that produced it, to a self-describing JSON sidecar so a
+    /// trusted analyst holding both the sidecar and the key can de-anonymize
+    /// results later (or a later `anonymize` run can pick up where this one
+    /// left off).
+    fn save_mapping_file(&self, path: &PathBuf) -> Result<()> {
+        let mut entries: Vec<MappingEntry> = Vec::with_capacity(
+            self.explicit_maps.len() + self.generated_maps.len(),
+        );
+        for (real, pseudonym) in &self.explicit_maps {
+            entries.push(MappingEntry {
+                real: real.clone(),
+                pseudonym: pseudonym.clone(),
+                source: "explicit".to_string(),
+            });
+        }
+        for (real, pseudonym) in &self.generated_maps {
+            entries.push(MappingEntry {
+                real: real.clone(),
+                pseudonym: pseudonym.clone(),
+                source: "generated".to_string(),
+            });
+        }
+        entries.sort_by(|a, b| a.real.cmp(&b.real));
+
+        let sidecar = MappingSidecar {
+            algorithm: "hmac-sha256".to_string(),
+            salt_hex: hex_encode(&self.salt),
+            key_fingerprint: self.key_fingerprint(),
+            entry_count: entries.len(),
+            entries,
+        };
+        sidecar.save(path)
+    }
+}
+
+/// Self-describing JSON mapping sidecar written by `anonymize` and read back
+/// by `anonymize` (to resume) and `deanonymize` (to reverse). Storing the MAC
+/// algorithm, salt, and entry count alongside the entries means a reader can
+/// tell whether it's looking at a sidecar it knows how to decode before it
+/// tries, instead of silently producing garbage names on a format or salt
+/// mismatch.
+#[derive(Serialize, Deserialize)]
+struct MappingSidecar {
+    algorithm: String,
+    salt_hex: String,
+    key_fingerprint: String,
+    entry_count: usize,
+    entries: Vec<MappingEntry>,
+}
+
+/// One real-name -> pseudonym mapping. `source` is `"explicit"` (from
+/// `--map`) or `"generated"` (from the keyed MAC); a collision-suffixed
+/// pseudonym like `Alice_Smith_2` is order-dependent and can't be
+/// recomputed from the key alone, so it's always recorded verbatim here.
+#[derive(Serialize, Deserialize)]
+struct MappingEntry {
+    real: String,
+    pseudonym: String,
+    source: String,
+}
+
+impl MappingSidecar {
+    fn salt(&self) -> Result<[u8; 16]> {
+        let bytes = hex_decode(&self.salt_hex).map_err(|e| anyhow::anyhow!(e))?;
+        bytes
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Mapping sidecar salt is not 16 bytes"))
+    }
+
+    /// Load a sidecar from disk, or `None` if `path` doesn't exist yet.
+    fn load(path: &PathBuf) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let text = std::fs::read_to_string(path)
+            .context("Failed to read anonymization mapping file")?;
+        let sidecar: MappingSidecar = serde_json::from_str(&text)
+            .context("Failed to parse anonymization mapping file as JSON")?;
+        if sidecar.entries.len() != sidecar.entry_count {
+            return Err(anyhow::anyhow!(
+                "Mapping file {} is corrupt: claims {} entries but has {}",
+                path.display(),
+                sidecar.entry_count,
+                sidecar.entries.len()
+            ));
+        }
+        if sidecar.algorithm != "hmac-sha256" {
+            return Err(anyhow::anyhow!(
+                "Mapping file {} uses unsupported algorithm {:?}",
+                path.display(),
+                sidecar.algorithm
+            ));
+        }
+        Ok(Some(sidecar))
+    }
+
+    fn save(&self, path: &PathBuf) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .context("Failed to serialize anonymization mapping file")?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+}
+
+fn anonymize_csv(
+    input: &PathBuf,
+    output: &PathBuf,
+    key: &str,
+    map: &str,
+    columns: &[String],
+    map_file: Option<&PathBuf>,
+    quiet: bool,
+) -> Result<()> {
     if key.is_empty() {
         return Err(anyhow::anyhow!(
             "Anonymization key is required. Set BBO_ANON_KEY env var or use --key"
@@ -1672,14 +3501,19 @@ fn anonymize_csv(
         if lin_url_idx.is_some() { " + LIN_URL (embedded names)" } else { "" }
     );
 
-    // Create anonymizer
+    // Create anonymizer, reusing any previously-saved mappings for this key
     let mut anonymizer = Anonymizer::new(key, map);
+    if let Some(path) = map_file {
+        anonymizer.load_mapping_file(path)?;
+    }
 
-    // Count rows for progress
-    let total_rows = count_csv_rows(input)?;
+    // Drive the progress bar off bytes consumed from the in-memory buffer
+    // rather than a separate `count_csv_rows` pass over the file.
+    let progress = ProgressReporter::new(csv_data.len() as u64, quiet, "Anonymizing");
 
-    // Open output
-    let mut writer = Writer::from_path(output).context("Failed to create output CSV")?;
+    // Open output, transparently compressing if the path ends in .zst
+    let out_handle = create_maybe_compressed(output)?;
+    let mut writer = Writer::from_writer(out_handle);
     writer.write_record(&headers)?;
 
     let mut processed = 0;
@@ -1687,10 +3521,8 @@ fn anonymize_csv(
     for result in reader.records() {
         let record = result.context("Failed to read CSV row")?;
         processed += 1;
-
-        if processed % 1000 == 0 {
-            eprint!("\r[{}/{}] Anonymizing...    ", processed, total_rows);
-            std::io::stderr().flush().ok();
+        if let Some(pos) = record.position() {
+            progress.set_position(pos.byte());
         }
 
         // Build output record with anonymized columns
@@ -1711,9 +3543,14 @@ fn anonymize_csv(
     }
 
     writer.flush()?;
-    eprint!("\r[{}/{}] Anonymizing...    ", processed, total_rows);
+    progress.finish(format!("{} rows anonymized", processed));
     anonymizer.print_summary();
 
+    if let Some(path) = map_file {
+        anonymizer.save_mapping_file(path)?;
+        eprintln!("  Mapping saved to: {}", path.display());
+    }
+
     Ok(())
 }
 
@@ -1766,10 +3603,190 @@ fn anonymize_lin_url(url: &str, anonymizer: &mut Anonymizer) -> String {
     result.to_string()
 }
 
+/// Reverse `anonymize_lin_url`: replace pseudonyms embedded in `pn|...|` and
+/// `pn%7C...%7C` segments with the real names from `reverse_map` (pseudonym,
+/// lowercased -> real name). Names with no entry in the map are left as-is.
+fn de_anonymize_lin_url(url: &str, reverse_map: &HashMap<String, String>) -> String {
+    lazy_static::lazy_static! {
+        static ref PN_ENCODED: Regex = Regex::new(r"(?i)pn%7C([^%]+(?:%2C[^%]+)*)%7C").unwrap();
+        static ref PN_LITERAL: Regex = Regex::new(r"pn\|([^|]+)\|").unwrap();
+    }
+
+    let result = PN_ENCODED.replace(url, |caps: &regex::Captures| {
+        let names_str = &caps[1];
+        let real_names: Vec<String> = names_str
+            .split("%2C")
+            .map(|name| {
+                let name = name.trim();
+                reverse_map
+                    .get(&name.to_lowercase())
+                    .cloned()
+                    .unwrap_or_else(|| name.to_string())
+            })
+            .collect();
+        format!("pn%7C{}%7C", real_names.join("%2C"))
+    });
+
+    let result = PN_LITERAL.replace(&result, |caps: &regex::Captures| {
+        let names = &caps[1];
+        let real_names: Vec<String> = names
+            .split(',')
+            .map(|name| {
+                let name = name.trim();
+                reverse_map
+                    .get(&name.to_lowercase())
+                    .cloned()
+                    .unwrap_or_else(|| name.to_string())
+            })
+            .collect();
+        format!("pn|{}|", real_names.join(","))
+    });
+
+    result.to_string()
+}
+
+/// Reverse an anonymized CSV back to real names using the JSON mapping
+/// sidecar saved by a previous `anonymize` run. `key` must match the one that
+/// produced the sidecar (checked against its stored `key_fingerprint`) --
+/// only the analyst holding both the sidecar and the original key can
+/// meaningfully perform this step.
+fn de_anonymize(
+    input: &PathBuf,
+    output: &PathBuf,
+    map_file: &PathBuf,
+    key: &str,
+    columns: &[String],
+) -> Result<()> {
+    let sidecar = MappingSidecar::load(map_file)?
+        .ok_or_else(|| anyhow::anyhow!("Mapping file {} not found", map_file.display()))?;
+    if sidecar.key_fingerprint != key_fingerprint(key, &sidecar.salt()?) {
+        return Err(anyhow::anyhow!(
+            "--key does not match the key that produced {}",
+            map_file.display()
+        ));
+    }
+
+    // pseudonym (lowercased) -> real name
+    let mut reverse_map: HashMap<String, String> = HashMap::new();
+    for entry in &sidecar.entries {
+        if entry.real.is_empty() || entry.pseudonym.is_empty() {
+            continue;
+        }
+        reverse_map.insert(entry.pseudonym.to_lowercase(), entry.real.clone());
+    }
+
+    let csv_data = read_bbo_csv_fixed(input)?;
+    let mut reader = ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(csv_data.as_bytes());
+    let headers = reader.headers()?.clone();
+
+    let col_indices: Vec<usize> = columns
+        .iter()
+        .filter_map(|col| headers.iter().position(|h| h == col))
+        .collect();
+
+    let lin_url_idx = headers.iter().position(|h| h == "LIN_URL");
+
+    let mut writer = Writer::from_path(output).context("Failed to create output CSV")?;
+    writer.write_record(&headers)?;
+
+    let mut processed = 0;
+    for result in reader.records() {
+        let record = result.context("Failed to read CSV row")?;
+        processed += 1;
+
+        let mut output_fields: Vec<String> = Vec::with_capacity(record.len());
+        for (i, field) in record.iter().enumerate() {
+            if col_indices.contains(&i) && !field.is_empty() {
+                let real = reverse_map
+                    .get(&field.to_lowercase())
+                    .cloned()
+                    .unwrap_or_else(|| field.to_string());
+                output_fields.push(real);
+            } else if Some(i) == lin_url_idx && !field.is_empty() {
+                output_fields.push(de_anonymize_lin_url(field, &reverse_map));
+            } else {
+                output_fields.push(field.to_string());
+            }
+        }
+
+        writer.write_record(&output_fields)?;
+    }
+
+    writer.flush()?;
+    eprintln!("De-anonymized {} rows using {}", processed, map_file.display());
+
+    Ok(())
+}
+
 // ============================================================================
 // Display Hand Implementation
 // ============================================================================
 
+/// One suit's holding from a hand string like `"S:AKQ H:JT9 D:876 C:5432"`
+/// (the cards after the matching `"S:"`/`"s:"` token), or `""` for a suit
+/// that's void or whose token is missing entirely.
+fn suit_holding(hand: &str, suit_char: char) -> &str {
+    let lower_suit = suit_char.to_ascii_lowercase();
+    for part in hand.split_whitespace() {
+        if part.starts_with(suit_char) || part.starts_with(lower_suit) {
+            if let Some(cards) = part.get(2..) {
+                return cards;
+            }
+        }
+    }
+    ""
+}
+
+/// Milton-Work point-count evaluation for one hand.
+struct HandEvaluation {
+    /// High-card points: A=4, K=3, Q=2, J=1.
+    hcp: u32,
+    /// Short-suit distribution points: void=3, singleton=2, doubleton=1.
+    dist_points: u32,
+    /// Honor count plus length per suit (S, H, D, C) -- a quick suit-quality
+    /// signal beyond raw HCP, e.g. a four-card suit headed by AK (6)
+    /// outranks a four-card suit with no honors (4).
+    suit_quality: [u32; 4],
+}
+
+fn evaluate_hand(hand: &str) -> HandEvaluation {
+    let mut hcp = 0u32;
+    let mut dist_points = 0u32;
+    let mut suit_quality = [0u32; 4];
+
+    for (i, &suit_char) in ['S', 'H', 'D', 'C'].iter().enumerate() {
+        let holding = suit_holding(hand, suit_char);
+        let length = holding.chars().count();
+        let honors = holding
+            .chars()
+            .filter(|c| matches!(c.to_ascii_uppercase(), 'A' | 'K' | 'Q' | 'J'))
+            .count();
+
+        for c in holding.chars() {
+            hcp += match c.to_ascii_uppercase() {
+                'A' => 4,
+                'K' => 3,
+                'Q' => 2,
+                'J' => 1,
+                _ => 0,
+            };
+        }
+
+        dist_points += match length {
+            0 => 3,
+            1 => 2,
+            2 => 1,
+            _ => 0,
+        };
+
+        suit_quality[i] = honors as u32 + length as u32;
+    }
+
+    HandEvaluation { hcp, dist_points, suit_quality }
+}
+
 fn display_hand(input: &PathBuf, row_num: usize) -> Result<()> {
     if row_num == 0 {
         return Err(anyhow::anyhow!("Row number must be 1 or greater"));
@@ -1883,6 +3900,58 @@ fn display_hand(input: &PathBuf, row_num: usize) -> Result<()> {
         println!("{:^40}", line);
     }
 
+    // Print hand evaluation (HCP, distribution points, suit quality), so a
+    // reader can judge the bidding against the combined point count before
+    // looking at how the cards were actually played.
+    println!("\n{:=^80}", " HAND EVALUATION ");
+    println!(
+        "{:<6} {:>5} {:>5} {:>6} | {:>3} {:>3} {:>3} {:>3} | {:>10}",
+        "Seat", "HCP", "Dist", "Total", "S", "H", "D", "C", "Role"
+    );
+    println!("{:-<80}", "");
+
+    let declaring_seats: [char; 2] = match declarer.chars().next() {
+        Some('N') | Some('S') => ['N', 'S'],
+        Some('E') | Some('W') => ['E', 'W'],
+        _ => ['?', '?'],
+    };
+
+    let mut ns_hcp = 0u32;
+    let mut ns_total = 0u32;
+    let mut ew_hcp = 0u32;
+    let mut ew_total = 0u32;
+
+    for seat in ['N', 'E', 'S', 'W'] {
+        let hand = match seat {
+            'N' => north_hand,
+            'S' => south_hand,
+            'E' => east_hand,
+            _ => west_hand,
+        };
+        let eval = evaluate_hand(hand);
+        let total = eval.hcp + eval.dist_points;
+        let role = if declaring_seats.contains(&seat) { "Declaring" } else { "Defending" };
+
+        println!(
+            "{:<6} {:>5} {:>5} {:>6} | {:>3} {:>3} {:>3} {:>3} | {:>10}",
+            seat, eval.hcp, eval.dist_points, total,
+            eval.suit_quality[0], eval.suit_quality[1], eval.suit_quality[2], eval.suit_quality[3],
+            role
+        );
+
+        if seat == 'N' || seat == 'S' {
+            ns_hcp += eval.hcp;
+            ns_total += total;
+        } else {
+            ew_hcp += eval.hcp;
+            ew_total += total;
+        }
+    }
+
+    println!("{:-<80}", "");
+    println!("N-S combined: {} HCP, {} total points", ns_hcp, ns_total);
+    println!("E-W combined: {} HCP, {} total points", ew_hcp, ew_total);
+
     // Print cardplay
     println!("\n{:=^80}", " CARDPLAY ");
 
@@ -1998,52 +4067,687 @@ fn display_hand(input: &PathBuf, row_num: usize) -> Result<()> {
                     .filter_map(|s| s.trim().parse().ok())
                     .collect();
 
-                if costs.len() == 4 {
-                    let seats = get_seat_order(current_leader);
-                    for (i, &cost) in costs.iter().enumerate() {
-                        let seat = seats[i];
-                        *seat_costs.entry(seat).or_insert(0) += cost as u64;
-                        *seat_plays.entry(seat).or_insert(0) += 1;
-                        if cost > 0 {
-                            *seat_errors.entry(seat).or_insert(0) += 1;
-                        }
-                    }
+                if costs.len() == 4 {
+                    let seats = get_seat_order(current_leader);
+                    for (i, &cost) in costs.iter().enumerate() {
+                        let seat = seats[i];
+                        *seat_costs.entry(seat).or_insert(0) += cost as u64;
+                        *seat_plays.entry(seat).or_insert(0) += 1;
+                        if cost > 0 {
+                            *seat_errors.entry(seat).or_insert(0) += 1;
+                        }
+                    }
+
+                    // Determine next leader
+                    if trick_idx < tricks.len() {
+                        let cards: Vec<&str> = tricks[trick_idx].split_whitespace().collect();
+                        if let Some(winner) = determine_trick_winner_for_display(&cards, current_leader, contract) {
+                            current_leader = winner;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Determine declaring side
+        let declaring_seats: [char; 2] = match declarer.chars().next() {
+            Some('N') | Some('S') => ['N', 'S'],
+            Some('E') | Some('W') => ['E', 'W'],
+            _ => ['?', '?'],
+        };
+
+        println!("\n{:<10} {:>10} {:>10} {:>12} {:>10}", "Seat", "Plays", "Errors", "Total Cost", "Role");
+        println!("{:-<60}", "");
+
+        for seat in ['N', 'E', 'S', 'W'] {
+            let plays = seat_plays.get(&seat).unwrap_or(&0);
+            let errors = seat_errors.get(&seat).unwrap_or(&0);
+            let cost = seat_costs.get(&seat).unwrap_or(&0);
+            let role = if declaring_seats.contains(&seat) { "Declaring" } else { "Defending" };
+
+            println!("{:<10} {:>10} {:>10} {:>12} {:>10}", seat, plays, errors, cost, role);
+        }
+    } else if dd_analysis.starts_with("ERROR") {
+        println!("\n{:=^80}", " DD ANALYSIS ");
+        println!("Error: {}", dd_analysis);
+    }
+
+    println!("\n{:=^80}", "");
+
+    Ok(())
+}
+
+/// One hand's data as parsed for the `html`/`svg` renderers below -- the
+/// same row/column extraction `display_hand` does for its ASCII output, but
+/// returned as data instead of printed directly, since a `--format html`
+/// page needs to lay out several boards' worth at once. Kept as its own
+/// pass over the CSV rather than threading a shared struct back through the
+/// older `display_hand`, matching this file's existing tolerance for small
+/// parallel structures doing near-identical parsing (see `cards.rs`'s note
+/// on the `DdWorkItem`/`Anonymizer` duplication).
+struct HandRecord {
+    row_num: usize,
+    ref_num: String,
+    contract: String,
+    declarer: String,
+    result: String,
+    /// Player names in seat order: N, E, S, W.
+    players: [String; 4],
+    /// Suit holdings in seat order (N, E, S, W), each `[spades, hearts, diamonds, clubs]`.
+    hand_lines: [[String; 4]; 4],
+    tricks: Vec<TrickRecord>,
+    lin_url: Option<String>,
+}
+
+struct TrickRecord {
+    trick_num: usize,
+    /// Seat that played each of `cards`, in play order.
+    seats: [char; 4],
+    cards: [String; 4],
+    /// Per-seat DD cost for `cards`, aligned the same way, if DD analysis
+    /// was available for this trick.
+    dd_costs: Option<[u8; 4]>,
+}
+
+/// Parses row `row_num` of `input` into a [`HandRecord`] -- the same parsing
+/// `display_hand` does inline for its ASCII rendering, as a separate pass
+/// used by `display_hand_export` for the `html`/`svg` formats.
+fn read_hand_record(input: &PathBuf, row_num: usize) -> Result<HandRecord> {
+    if row_num == 0 {
+        return Err(anyhow::anyhow!("Row number must be 1 or greater"));
+    }
+
+    let mut reader = ReaderBuilder::new()
+        .flexible(true)
+        .from_path(input)
+        .context("Failed to open input CSV")?;
+    let headers = reader.headers()?.clone();
+    let find_col = |name: &str| headers.iter().position(|h| h == name);
+
+    let north_col = find_col("North hand").or_else(|| find_col("N hand"));
+    let south_col = find_col("South hand").or_else(|| find_col("S hand"));
+    let east_col = find_col("East hand").or_else(|| find_col("E hand"));
+    let west_col = find_col("West hand").or_else(|| find_col("W hand"));
+    let contract_col = find_col("Contract");
+    let declarer_col = find_col("Dec");
+    let result_col = find_col("Result");
+    let cardplay_col = find_col("Cardplay");
+    let dd_col = find_col("DD_Analysis");
+    let n_col = find_col("N");
+    let s_col = find_col("S");
+    let e_col = find_col("E");
+    let w_col = find_col("W");
+    let ref_col = find_col("Ref #");
+    let lin_url_col = find_col("LIN_URL");
+
+    let record = reader
+        .records()
+        .nth(row_num - 1)
+        .ok_or_else(|| anyhow::anyhow!("Row {} not found in file", row_num))?
+        .context("Failed to read CSV row")?;
+
+    let get = |col: Option<usize>| col.and_then(|i| record.get(i)).unwrap_or("").to_string();
+
+    let north_hand = get(north_col);
+    let south_hand = get(south_col);
+    let east_hand = get(east_col);
+    let west_hand = get(west_col);
+    let contract = get(contract_col);
+    let declarer = get(declarer_col);
+    let result = get(result_col);
+    let cardplay = get(cardplay_col);
+    let dd_analysis = get(dd_col);
+    let ref_num = get(ref_col);
+    let lin_url = lin_url_col
+        .and_then(|i| record.get(i))
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string());
+
+    let format_suit = |hand: &str, suit_char: char| -> String {
+        for part in hand.split_whitespace() {
+            let lower_suit = suit_char.to_ascii_lowercase();
+            if part.starts_with(suit_char) || part.starts_with(lower_suit) {
+                if let Some(cards) = part.get(2..) {
+                    return cards.to_string();
+                }
+            }
+        }
+        "-".to_string()
+    };
+    let format_hand_lines = |hand: &str| -> [String; 4] {
+        [
+            format_suit(hand, 'S'),
+            format_suit(hand, 'H'),
+            format_suit(hand, 'D'),
+            format_suit(hand, 'C'),
+        ]
+    };
+
+    let hand_lines = [
+        format_hand_lines(&north_hand),
+        format_hand_lines(&east_hand),
+        format_hand_lines(&south_hand),
+        format_hand_lines(&west_hand),
+    ];
+
+    let mut dd_costs: HashMap<usize, [u8; 4]> = HashMap::new();
+    if !dd_analysis.is_empty() && !dd_analysis.starts_with("ERROR") {
+        for trick_str in dd_analysis.split('|') {
+            if let Some(colon_idx) = trick_str.find(':') {
+                let trick_num_str = &trick_str[1..colon_idx];
+                if let Ok(trick_num) = trick_num_str.parse::<usize>() {
+                    let costs: Vec<u8> = trick_str[colon_idx + 1..]
+                        .split(',')
+                        .filter_map(|s| s.trim().parse().ok())
+                        .collect();
+                    if costs.len() == 4 {
+                        dd_costs.insert(trick_num, [costs[0], costs[1], costs[2], costs[3]]);
+                    }
+                }
+            }
+        }
+    }
+
+    let initial_leader = match declarer.chars().next() {
+        Some('N') => 'E',
+        Some('E') => 'S',
+        Some('S') => 'W',
+        Some('W') => 'N',
+        _ => '?',
+    };
+
+    let mut tricks = Vec::new();
+    let mut current_leader = initial_leader;
+    for (trick_idx, trick_str) in cardplay.split('|').enumerate() {
+        if trick_str.is_empty() {
+            continue;
+        }
+        let cards: Vec<&str> = trick_str.split_whitespace().collect();
+        if cards.len() != 4 {
+            continue;
+        }
+
+        let trick_num = trick_idx + 1;
+        let seats = get_seat_order(current_leader);
+        tricks.push(TrickRecord {
+            trick_num,
+            seats,
+            cards: [cards[0].to_string(), cards[1].to_string(), cards[2].to_string(), cards[3].to_string()],
+            dd_costs: dd_costs.get(&trick_num).copied(),
+        });
+
+        if let Some(winner) = determine_trick_winner_for_display(&cards, current_leader, &contract) {
+            current_leader = winner;
+        }
+    }
+
+    Ok(HandRecord {
+        row_num,
+        ref_num,
+        contract,
+        declarer,
+        result,
+        players: [get(n_col), get(e_col), get(s_col), get(w_col)],
+        hand_lines,
+        tricks,
+        lin_url,
+    })
+}
+
+/// HTML-escapes text for safe inclusion in the generated hand-diagram
+/// document. Duplicates `pipeline.rs`'s private `html_escape` of the same
+/// name rather than exporting that one across a binary/library boundary for
+/// a three-line helper.
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Background shade for a cardplay-table cell, redder as the DD cost of
+/// that card rises above zero.
+fn dd_cost_shade(cost: u8) -> &'static str {
+    match cost {
+        0 => "#f4f4f4",
+        1 => "#ffe0b2",
+        2 => "#ffab91",
+        3 => "#ff7043",
+        _ => "#d32f2f",
+    }
+}
+
+/// Renders `records` as a single self-contained HTML document: one
+/// `<section>` per board, each with a compass hand diagram (suits in
+/// colored spans) and a trick-by-trick cardplay table shaded by DD cost.
+fn render_hand_html(records: &[HandRecord]) -> String {
+    let mut doc = String::new();
+    doc.push_str("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">\n");
+    doc.push_str("<title>Hand Display</title>\n<style>\n");
+    doc.push_str("body { font-family: Helvetica, Arial, sans-serif; }\n");
+    doc.push_str(".board { border-bottom: 2px solid #333; padding: 1em 0; margin-bottom: 1em; }\n");
+    doc.push_str(".compass { display: grid; grid-template-columns: 1fr 1fr 1fr; max-width: 30em; margin: 1em auto; text-align: center; }\n");
+    doc.push_str(".seat { grid-column: 2; }\n.seat.north { grid-row: 1; }\n.seat.south { grid-row: 3; }\n");
+    doc.push_str(".seat.west { grid-column: 1; grid-row: 2; }\n.seat.east { grid-column: 3; grid-row: 2; }\n");
+    doc.push_str(".suit-s, .suit-c { color: black; }\n.suit-h, .suit-d { color: #c00; }\n");
+    doc.push_str("table.cardplay { border-collapse: collapse; margin: 1em auto; }\n");
+    doc.push_str("table.cardplay td, table.cardplay th { border: 1px solid #999; padding: 0.2em 0.5em; text-align: center; }\n");
+    doc.push_str("</style></head><body>\n");
+
+    let seat_names = ["North", "East", "South", "West"];
+    let seat_classes = ["north", "east", "south", "west"];
+    let suit_chars = ['S', 'H', 'D', 'C'];
+
+    for rec in records {
+        let _ = writeln!(doc, "<section class=\"board\" id=\"board-{}\">", rec.row_num);
+        let _ = writeln!(doc, "<h2>Hand #{} (Ref: {})</h2>", rec.row_num, html_escape(&rec.ref_num));
+        let _ = writeln!(
+            doc,
+            "<p>Contract: <strong>{}</strong> by {} &mdash; Result: {}</p>",
+            html_escape(&rec.contract), html_escape(&rec.declarer), html_escape(&rec.result)
+        );
+
+        let player_line = format!(
+            "N={} E={} S={} W={}",
+            rec.players[0], rec.players[1], rec.players[2], rec.players[3]
+        );
+        match &rec.lin_url {
+            Some(url) => {
+                let _ = writeln!(
+                    doc, "<p>Players: <a href=\"{}\">{}</a></p>",
+                    html_escape(url), html_escape(&player_line)
+                );
+            }
+            None => {
+                let _ = writeln!(doc, "<p>Players: {}</p>", html_escape(&player_line));
+            }
+        }
+
+        doc.push_str("<div class=\"compass\">\n");
+        for (i, lines) in rec.hand_lines.iter().enumerate() {
+            let _ = writeln!(doc, "<div class=\"seat {}\"><strong>{}</strong><br>", seat_classes[i], seat_names[i]);
+            for (j, holding) in lines.iter().enumerate() {
+                let _ = writeln!(
+                    doc, "<span class=\"suit-{}\">{}: {}</span><br>",
+                    suit_chars[j].to_ascii_lowercase(), suit_chars[j], html_escape(holding)
+                );
+            }
+            doc.push_str("</div>\n");
+        }
+        doc.push_str("</div>\n");
+
+        if rec.tricks.is_empty() {
+            doc.push_str("<p>(No cardplay recorded)</p>\n");
+        } else {
+            doc.push_str("<table class=\"cardplay\">\n<tr><th>Trick</th><th>Leader</th><th>2nd</th><th>3rd</th><th>4th</th><th>DD Cost (L/2/3/4)</th></tr>\n");
+            for trick in &rec.tricks {
+                doc.push_str("<tr>");
+                let _ = write!(doc, "<td>{}</td>", trick.trick_num);
+                for (i, card) in trick.cards.iter().enumerate() {
+                    let cost = trick.dd_costs.map(|c| c[i]);
+                    let style = match cost {
+                        Some(c) => format!(" style=\"background:{}\"", dd_cost_shade(c)),
+                        None => String::new(),
+                    };
+                    let _ = write!(doc, "<td{}>{}:{}</td>", style, trick.seats[i], html_escape(card));
+                }
+                let cost_str = match trick.dd_costs {
+                    Some(c) => format!("{},{},{},{}", c[0], c[1], c[2], c[3]),
+                    None => "-".to_string(),
+                };
+                let _ = writeln!(doc, "<td>{}</td></tr>", cost_str);
+            }
+            doc.push_str("</table>\n");
+        }
+
+        doc.push_str("</section>\n");
+    }
+
+    doc.push_str("</body></html>\n");
+    doc
+}
+
+/// Renders `records` as one raw SVG document stacking each board's compass
+/// hand diagram vertically -- no cardplay table, just the deal, for pasting
+/// into slides or a teaching handout.
+fn render_hand_svg(records: &[HandRecord]) -> String {
+    const BOARD_HEIGHT: usize = 220;
+    let total_height = BOARD_HEIGHT * records.len().max(1);
+
+    let mut doc = String::new();
+    let _ = writeln!(
+        doc,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"400\" height=\"{}\" font-family=\"Helvetica, Arial, sans-serif\">",
+        total_height
+    );
+
+    // (x, y offset within the board, text-anchor) for N, E, S, W.
+    let seat_layout = [(200, 20, "middle"), (330, 110, "start"), (200, 200, "middle"), (70, 110, "end")];
+    let suit_chars = ['S', 'H', 'D', 'C'];
+
+    for (idx, rec) in records.iter().enumerate() {
+        let y0 = idx * BOARD_HEIGHT;
+        let _ = writeln!(
+            doc,
+            "<text x=\"200\" y=\"{}\" font-size=\"14\" text-anchor=\"middle\">{}</text>",
+            y0 + 15,
+            html_escape(&format!("Hand #{} -- {} by {}", rec.row_num, rec.contract, rec.declarer))
+        );
+
+        for (seat_idx, lines) in rec.hand_lines.iter().enumerate() {
+            let (x, y_offset, anchor) = seat_layout[seat_idx];
+            let y = y0 + y_offset;
+            for (suit_idx, holding) in lines.iter().enumerate() {
+                let color = if suit_idx == 1 || suit_idx == 2 { "#c00" } else { "black" };
+                let _ = writeln!(
+                    doc,
+                    "<text x=\"{}\" y=\"{}\" font-size=\"13\" text-anchor=\"{}\" fill=\"{}\">{}: {}</text>",
+                    x, y + suit_idx * 16, anchor, color, suit_chars[suit_idx], html_escape(holding)
+                );
+            }
+        }
+    }
+
+    doc.push_str("</svg>\n");
+    doc
+}
+
+/// Renders hand(s) `start_row..=end_row` of `input` in `html` or `svg`
+/// format, writing the result to `out` or stdout. The ASCII format stays on
+/// `display_hand`'s original single-row, stdout-only path.
+fn display_hand_export(
+    input: &PathBuf,
+    start_row: usize,
+    end_row: usize,
+    format: &str,
+    out: Option<&PathBuf>,
+) -> Result<()> {
+    if end_row < start_row {
+        return Err(anyhow::anyhow!(
+            "--end-row ({}) must be >= the starting row ({})",
+            end_row, start_row
+        ));
+    }
+
+    let records: Vec<HandRecord> = (start_row..=end_row)
+        .map(|row| read_hand_record(input, row))
+        .collect::<Result<Vec<_>>>()?;
+
+    let doc = match format {
+        "html" => render_hand_html(&records),
+        "svg" => render_hand_svg(&records),
+        other => return Err(anyhow::anyhow!("Unknown --format '{}': expected ascii, html, or svg", other)),
+    };
+
+    match out {
+        Some(path) => std::fs::write(path, doc).context("Failed to write hand-display output")?,
+        None => print!("{}", doc),
+    }
+
+    Ok(())
+}
+
+/// Export one hand's cardplay as a GraphViz `digraph`: one node per card
+/// played (grouped into a same-rank cluster per trick), edges connecting
+/// consecutive plays in play order, and each node colored by the DD cost
+/// `display_hand` already prints -- green where the card was optimal,
+/// yellow for a one-trick loss, red for a larger one, gray where no DD
+/// analysis is available for that trick. Render with `dot -Tsvg`.
+fn export_dot(input: &PathBuf, row_num: usize, output: Option<&PathBuf>) -> Result<()> {
+    if row_num == 0 {
+        return Err(anyhow::anyhow!("Row number must be 1 or greater"));
+    }
+
+    let mut reader = ReaderBuilder::new()
+        .flexible(true)
+        .from_path(input)
+        .context("Failed to open input CSV")?;
+    let headers = reader.headers()?.clone();
+    let find_col = |name: &str| headers.iter().position(|h| h == name);
+
+    let contract_col = find_col("Contract");
+    let declarer_col = find_col("Dec");
+    let cardplay_col = find_col("Cardplay");
+    let dd_col = find_col("DD_Analysis");
+    let ref_col = find_col("Ref #");
+
+    let record = reader
+        .records()
+        .nth(row_num - 1)
+        .ok_or_else(|| anyhow::anyhow!("Row {} not found in file", row_num))?
+        .context("Failed to read CSV row")?;
+
+    let get = |col: Option<usize>| col.and_then(|i| record.get(i)).unwrap_or("");
+    let contract = get(contract_col);
+    let declarer = get(declarer_col);
+    let cardplay = get(cardplay_col);
+    let dd_analysis = get(dd_col);
+    let ref_num = get(ref_col);
+
+    // Parse DD analysis into a map: trick_num -> per-seat costs, same format
+    // `display_hand` parses (T1:c1,c2,c3,c4|T2:...).
+    let mut dd_costs: HashMap<usize, Vec<u8>> = HashMap::new();
+    if !dd_analysis.is_empty() && !dd_analysis.starts_with("ERROR") {
+        for trick_str in dd_analysis.split('|') {
+            if let Some(colon_idx) = trick_str.find(':') {
+                let trick_num_str = &trick_str[1..colon_idx];
+                if let Ok(trick_num) = trick_num_str.parse::<usize>() {
+                    let costs: Vec<u8> = trick_str[colon_idx + 1..]
+                        .split(',')
+                        .filter_map(|s| s.trim().parse().ok())
+                        .collect();
+                    if costs.len() == 4 {
+                        dd_costs.insert(trick_num, costs);
+                    }
+                }
+            }
+        }
+    }
+
+    let initial_leader = match declarer.chars().next() {
+        Some('N') => 'E',
+        Some('E') => 'S',
+        Some('S') => 'W',
+        Some('W') => 'N',
+        _ => '?',
+    };
+
+    let mut dot = String::new();
+    dot.push_str("digraph G {\n");
+    dot.push_str(&format!(
+        "    label=\"Hand #{} (Ref: {}) -- {} by {}\";\n",
+        row_num, ref_num, contract, declarer
+    ));
+    dot.push_str("    rankdir=TB;\n");
+    dot.push_str("    node [shape=box, style=filled, fontname=\"Helvetica\"];\n\n");
+
+    let mut current_leader = initial_leader;
+    let mut prev_node: Option<String> = None;
+
+    for (trick_idx, trick_str) in cardplay.split('|').enumerate() {
+        if trick_str.is_empty() {
+            continue;
+        }
+        let cards: Vec<&str> = trick_str.split_whitespace().collect();
+        if cards.len() != 4 {
+            continue;
+        }
+
+        let trick_num = trick_idx + 1;
+        let seats = get_seat_order(current_leader);
+        let costs = dd_costs.get(&trick_num);
+
+        dot.push_str(&format!("    subgraph cluster_t{} {{\n", trick_num));
+        dot.push_str(&format!("        label=\"Trick {}\";\n", trick_num));
+        let mut rank_nodes = Vec::with_capacity(4);
+        for (i, card) in cards.iter().enumerate() {
+            let seat = seats[i];
+            let cost = costs.and_then(|c| c.get(i)).copied();
+            let color = match cost {
+                None => "lightgray",
+                Some(0) => "darkseagreen2",
+                Some(1) => "gold",
+                Some(_) => "firebrick2",
+            };
+            let node_id = format!("t{}_{}", trick_num, i);
+            dot.push_str(&format!(
+                "        {} [label=\"{}: {}\", fillcolor={}];\n",
+                node_id, seat, card, color
+            ));
+            rank_nodes.push(node_id);
+        }
+        dot.push_str("        { rank=same; ");
+        for node_id in &rank_nodes {
+            dot.push_str(node_id);
+            dot.push_str("; ");
+        }
+        dot.push_str("}\n");
+        dot.push_str("    }\n\n");
+
+        for node_id in &rank_nodes {
+            if let Some(prev) = &prev_node {
+                dot.push_str(&format!("    {} -> {};\n", prev, node_id));
+            }
+            prev_node = Some(node_id.clone());
+        }
+
+        if let Some(winner) = determine_trick_winner_for_display(&cards, current_leader, contract) {
+            current_leader = winner;
+        }
+    }
+
+    dot.push_str("}\n");
+
+    match output {
+        Some(path) => std::fs::write(path, dot).context("Failed to write DOT file")?,
+        None => print!("{}", dot),
+    }
+
+    Ok(())
+}
+
+/// Standard duplicate vulnerability rotation, indexed by `(board - 1) % 16`.
+const VUL_CYCLE: [&str; 16] = [
+    "None", "NS", "EW", "All", "NS", "EW", "All", "None",
+    "EW", "All", "None", "NS", "All", "None", "NS", "EW",
+];
+
+/// Export a processed CSV (hands, Contract, Dec, Result, Cardplay) to
+/// Portable Bridge Notation. Each row becomes one `[Event ...]`-less PBN
+/// block with a reconstructed `[Play]` section, giving a path out of the
+/// BBO/LIN world into any PBN-consuming analysis tool. `[Dealer]` and
+/// `[Vulnerable]` follow the standard rotation for the board number; player
+/// names are not written.
+fn export_pbn(input: &PathBuf, output: &PathBuf) -> Result<()> {
+    let mut reader = ReaderBuilder::new()
+        .flexible(true)
+        .from_path(input)
+        .context("Failed to open input CSV")?;
+    let headers = reader.headers()?.clone();
+    let find_col = |name: &str| headers.iter().position(|h| h == name);
 
-                    // Determine next leader
-                    if trick_idx < tricks.len() {
-                        let cards: Vec<&str> = tricks[trick_idx].split_whitespace().collect();
-                        if let Some(winner) = determine_trick_winner_for_display(&cards, current_leader, contract) {
-                            current_leader = winner;
-                        }
-                    }
+    let north_col = find_col("North hand").or_else(|| find_col("N hand"));
+    let south_col = find_col("South hand").or_else(|| find_col("S hand"));
+    let east_col = find_col("East hand").or_else(|| find_col("E hand"));
+    let west_col = find_col("West hand").or_else(|| find_col("W hand"));
+    let board_col = find_col("Board").or_else(|| find_col("Ref #"));
+    let contract_col = find_col("Contract");
+    let declarer_col = find_col("Dec");
+    let result_col = find_col("Result");
+    let cardplay_col = find_col("Cardplay");
+
+    let format_suit = |hand: &str, suit_char: char| -> String {
+        for part in hand.split_whitespace() {
+            let lower_suit = suit_char.to_ascii_lowercase();
+            if part.starts_with(suit_char) || part.starts_with(lower_suit) {
+                if let Some(cards) = part.get(2..) {
+                    return cards.to_string();
                 }
             }
         }
+        String::new()
+    };
 
-        // Determine declaring side
-        let declaring_seats: [char; 2] = match declarer.chars().next() {
-            Some('N') | Some('S') => ['N', 'S'],
-            Some('E') | Some('W') => ['E', 'W'],
-            _ => ['?', '?'],
-        };
+    let mut out = String::new();
+    let mut boards_written = 0usize;
 
-        println!("\n{:<10} {:>10} {:>10} {:>12} {:>10}", "Seat", "Plays", "Errors", "Total Cost", "Role");
-        println!("{:-<60}", "");
+    for (row_idx, result) in reader.records().enumerate() {
+        let record = result.context("Failed to read CSV row")?;
+        let get = |col: Option<usize>| col.and_then(|i| record.get(i)).unwrap_or("");
+
+        let board_num = get(board_col).parse::<usize>().unwrap_or(row_idx + 1);
+        let dealer = ["N", "E", "S", "W"][(board_num - 1) % 4];
+        let vulnerable = VUL_CYCLE[(board_num - 1) % 16];
+
+        let deal = format!(
+            "N:{}.{}.{}.{} {}.{}.{}.{} {}.{}.{}.{} {}.{}.{}.{}",
+            format_suit(get(north_col), 'S'), format_suit(get(north_col), 'H'),
+            format_suit(get(north_col), 'D'), format_suit(get(north_col), 'C'),
+            format_suit(get(east_col), 'S'), format_suit(get(east_col), 'H'),
+            format_suit(get(east_col), 'D'), format_suit(get(east_col), 'C'),
+            format_suit(get(south_col), 'S'), format_suit(get(south_col), 'H'),
+            format_suit(get(south_col), 'D'), format_suit(get(south_col), 'C'),
+            format_suit(get(west_col), 'S'), format_suit(get(west_col), 'H'),
+            format_suit(get(west_col), 'D'), format_suit(get(west_col), 'C'),
+        );
 
-        for seat in ['N', 'E', 'S', 'W'] {
-            let plays = seat_plays.get(&seat).unwrap_or(&0);
-            let errors = seat_errors.get(&seat).unwrap_or(&0);
-            let cost = seat_costs.get(&seat).unwrap_or(&0);
-            let role = if declaring_seats.contains(&seat) { "Declaring" } else { "Defending" };
+        let declarer = get(declarer_col);
+        let contract = get(contract_col);
+        let cardplay = get(cardplay_col);
+
+        out.push_str(&format!("[Board \"{}\"]\n", board_num));
+        out.push_str(&format!("[Dealer \"{}\"]\n", dealer));
+        out.push_str(&format!("[Vulnerable \"{}\"]\n", vulnerable));
+        out.push_str(&format!("[Deal \"{}\"]\n", deal));
+        out.push_str(&format!("[Declarer \"{}\"]\n", declarer));
+        out.push_str(&format!("[Contract \"{}\"]\n", contract));
+        out.push_str(&format!("[Result \"{}\"]\n", get(result_col)));
+
+        if !cardplay.is_empty() {
+            let initial_leader = match declarer.chars().next() {
+                Some('N') => 'E',
+                Some('E') => 'S',
+                Some('S') => 'W',
+                Some('W') => 'N',
+                _ => 'N',
+            };
+            out.push_str(&format!("[Play \"{}\"]\n", initial_leader));
 
-            println!("{:<10} {:>10} {:>10} {:>12} {:>10}", seat, plays, errors, cost, role);
+            let mut current_leader = initial_leader;
+            for trick_str in cardplay.split('|') {
+                if trick_str.is_empty() {
+                    continue;
+                }
+                let cards: Vec<&str> = trick_str.split_whitespace().collect();
+                if cards.len() != 4 {
+                    continue;
+                }
+
+                let seats = get_seat_order(current_leader);
+                // PBN lists the four cards in seat order N E S W regardless
+                // of who led; columns for seats that didn't play yet use "-".
+                let mut by_seat: HashMap<char, &str> = HashMap::new();
+                for (i, &card) in cards.iter().enumerate() {
+                    by_seat.insert(seats[i], card);
+                }
+                out.push_str(&format!(
+                    "{} {} {} {}\n",
+                    by_seat.get(&'N').copied().unwrap_or("-"),
+                    by_seat.get(&'E').copied().unwrap_or("-"),
+                    by_seat.get(&'S').copied().unwrap_or("-"),
+                    by_seat.get(&'W').copied().unwrap_or("-"),
+                ));
+
+                if let Some(winner) = determine_trick_winner_for_display(&cards, current_leader, contract) {
+                    current_leader = winner;
+                }
+            }
+            out.push_str("*\n");
         }
-    } else if dd_analysis.starts_with("ERROR") {
-        println!("\n{:=^80}", " DD ANALYSIS ");
-        println!("Error: {}", dd_analysis);
+
+        out.push('\n');
+        boards_written += 1;
     }
 
-    println!("\n{:=^80}", "");
+    std::fs::write(output, out).context("Failed to write PBN output")?;
+    eprintln!("Wrote {} boards to {:?}", boards_written, output);
 
     Ok(())
 }
@@ -2054,231 +4758,447 @@ fn determine_trick_winner_for_display(cards: &[&str], leader: char, contract: &s
         return None;
     }
 
-    // Parse trump suit from contract
-    let trump = if contract.contains('N') {
-        None // NT
-    } else if contract.contains('S') {
-        Some('S')
-    } else if contract.contains('H') {
-        Some('H')
-    } else if contract.contains('D') {
-        Some('D')
-    } else if contract.contains('C') {
-        Some('C')
-    } else {
-        None
-    };
+    let trump = edgar_defense_toolkit::cards::trump_from_contract(contract);
+    let c0 = edgar_defense_toolkit::cards::Card::parse(cards[0])?;
+    let c1 = edgar_defense_toolkit::cards::Card::parse(cards[1])?;
+    let c2 = edgar_defense_toolkit::cards::Card::parse(cards[2])?;
+    let c3 = edgar_defense_toolkit::cards::Card::parse(cards[3])?;
 
-    // Parse cards
-    let parse_card = |s: &str| -> Option<(char, u8)> {
-        let s = s.trim();
-        if s.len() < 2 {
-            return None;
+    let leader_idx = "NESW".find(leader)?;
+    let winner_idx = edgar_defense_toolkit::cards::trick_winner([c0, c1, c2, c3], leader_idx, trump);
+    "NESW".chars().nth(winner_idx)
+}
+
+/// Split a PBN file into per-board text blocks (blank-line separated, the
+/// convention `export_pbn` itself writes) and parse each block's
+/// `[Tag "value"]` pairs.
+fn parse_pbn_boards(pbn: &str) -> Vec<(String, HashMap<String, String>)> {
+    lazy_static::lazy_static! {
+        static ref TAG: Regex = Regex::new(r#"(?m)^\[(\w+)\s+"([^"]*)"\]\s*$"#).unwrap();
+    }
+
+    let mut boards = Vec::new();
+    for block in pbn.split("\n\n") {
+        if block.trim().is_empty() {
+            continue;
         }
-        let suit = s.chars().next()?;
-        let rank_char = s.chars().nth(1)?;
-        let rank = match rank_char {
-            'A' => 14,
-            'K' => 13,
-            'Q' => 12,
-            'J' => 11,
-            'T' | '1' => 10,
-            '9' => 9,
-            '8' => 8,
-            '7' => 7,
-            '6' => 6,
-            '5' => 5,
-            '4' => 4,
-            '3' => 3,
-            '2' => 2,
-            _ => return None,
-        };
-        Some((suit, rank))
+
+        let mut tags = HashMap::new();
+        for cap in TAG.captures_iter(block) {
+            tags.insert(cap[1].to_string(), cap[2].to_string());
+        }
+        if !tags.contains_key("Deal") {
+            continue;
+        }
+
+        boards.push((block.to_string(), tags));
+    }
+
+    boards
+}
+
+/// Parse a PBN `[Play "<leader>"]` section (the lines immediately following
+/// the tag, up to a terminating `*`) back into the internal Cardplay format:
+/// pipe-separated tricks, cards in play order starting from the leader.
+fn parse_pbn_play(pbn: &str, contract: &str) -> String {
+    lazy_static::lazy_static! {
+        static ref PLAY_TAG: Regex = Regex::new(r#"(?m)^\[Play\s+"([NESW])"\]\s*$"#).unwrap();
+    }
+
+    let Some(caps) = PLAY_TAG.captures(pbn) else {
+        return String::new();
     };
+    let mut current_leader = caps[1].chars().next().unwrap();
+    let section_start = caps.get(0).unwrap().end();
+
+    let mut tricks = Vec::new();
+    for line in pbn[section_start..].lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('*') || line.starts_with('[') {
+            break;
+        }
 
-    let parsed: Vec<Option<(char, u8)>> = cards.iter().map(|c| parse_card(c)).collect();
+        let cols: Vec<&str> = line.split_whitespace().collect();
+        if cols.len() != 4 {
+            break;
+        }
 
-    // Lead suit
-    let lead_suit = parsed[0].map(|(s, _)| s)?;
+        let seats = get_seat_order(current_leader);
+        let by_col = ['N', 'E', 'S', 'W'];
+        let mut by_seat: HashMap<char, &str> = HashMap::new();
+        for (&seat, &card) in by_col.iter().zip(cols.iter()) {
+            by_seat.insert(seat, card);
+        }
 
-    // Find winner
-    let mut winner_idx = 0;
-    let mut winning_card = parsed[0]?;
-
-    for (i, card_opt) in parsed.iter().enumerate().skip(1) {
-        if let Some((suit, rank)) = card_opt {
-            let dominated = if let Some(t) = trump {
-                // Trump beats non-trump
-                if *suit == t && winning_card.0 != t {
-                    true
-                } else if *suit == t && winning_card.0 == t {
-                    *rank > winning_card.1
-                } else if winning_card.0 == t {
-                    false
-                } else if *suit == lead_suit {
-                    *rank > winning_card.1
-                } else {
-                    false
-                }
-            } else {
-                // No trump: must follow suit
-                *suit == lead_suit && *rank > winning_card.1
-            };
+        let trick_cards: Vec<&str> = seats
+            .iter()
+            .map(|seat| by_seat.get(seat).copied().unwrap_or("-"))
+            .take_while(|&c| c != "-")
+            .collect();
+        if trick_cards.is_empty() {
+            break;
+        }
+        tricks.push(trick_cards.join(" "));
 
-            if dominated {
-                winner_idx = i;
-                winning_card = (*suit, *rank);
+        if trick_cards.len() == 4 {
+            if let Some(winner) = determine_trick_winner_for_display(&trick_cards, current_leader, contract) {
+                current_leader = winner;
             }
         }
     }
 
-    // Map winner index to seat
-    let seats = get_seat_order(leader);
-    Some(seats[winner_idx])
+    tricks.join("|")
+}
+
+/// Import a standard PBN file, normalizing each board into the same CSV
+/// shape (hand columns, Con/Dec, Cardplay) that `analyze-dd` and
+/// `write_lookup_row` already consume, so PBN data runs through the
+/// unmodified DD pipeline alongside BBO exports.
+fn import_pbn(input: &PathBuf, output: &PathBuf) -> Result<()> {
+    let pbn = std::fs::read_to_string(input).context("Failed to read PBN file")?;
+    let boards = parse_pbn_boards(&pbn);
+
+    let mut writer = Writer::from_path(output).context("Failed to create output CSV")?;
+    writer.write_record([
+        "Ref #", "Board", "Dealer", "Vul", "North", "South", "East", "West",
+        "Con", "Dec", "Result", "Cardplay",
+    ])?;
+
+    let mut boards_written = 0usize;
+    for (idx, (block, tags)) in boards.iter().enumerate() {
+        let get = |name: &str| tags.get(name).map(|s| s.as_str()).unwrap_or("");
+
+        let deal = get("Deal");
+        let Some((first_seat_str, hands_str)) = deal.split_once(':') else {
+            continue;
+        };
+        let hands: Vec<&str> = hands_str.split_whitespace().collect();
+        if hands.len() != 4 {
+            continue;
+        }
+        // Rotate the four dot-separated hands (in PBN's Deal order, starting
+        // at `first_seat_str`) into fixed North/East/South/West columns.
+        let seat_cycle = ['N', 'E', 'S', 'W'];
+        let first_idx = seat_cycle
+            .iter()
+            .position(|&s| s.to_string() == first_seat_str.trim().to_uppercase())
+            .unwrap_or(0);
+        let mut hand_by_seat: HashMap<char, &str> = HashMap::new();
+        for (i, hand) in hands.iter().enumerate() {
+            hand_by_seat.insert(seat_cycle[(first_idx + i) % 4], hand);
+        }
+
+        let contract = get("Contract");
+        let declarer = get("Declarer");
+        let cardplay = parse_pbn_play(block, contract);
+
+        writer.write_record([
+            (idx + 1).to_string(),
+            get("Board").to_string(),
+            get("Dealer").to_string(),
+            get("Vulnerable").to_string(),
+            hand_by_seat.get(&'N').copied().unwrap_or("").to_string(),
+            hand_by_seat.get(&'S').copied().unwrap_or("").to_string(),
+            hand_by_seat.get(&'E').copied().unwrap_or("").to_string(),
+            hand_by_seat.get(&'W').copied().unwrap_or("").to_string(),
+            contract.to_string(),
+            declarer.to_string(),
+            get("Result").to_string(),
+            cardplay,
+        ])?;
+        boards_written += 1;
+    }
+
+    writer.flush()?;
+    eprintln!("Imported {} boards from {:?}", boards_written, input);
+
+    Ok(())
 }
 
 // ============================================================================
 // Stats Implementation
 // ============================================================================
+//
+// The PlayerStats engine itself (wilson_ci, two_proportion_z_test,
+// bootstrap_def_minus_decl, the JSON export types, etc.) lives in
+// edgar_defense_toolkit::stats, shared with pipeline::compute_stats so the
+// GUI and this CLI report the same numbers from the same code.
+
+/// Per-board snapshot pulled out of an analyzed CSV, used by `compute_diff`.
+struct BoardSnapshot {
+    contract: String,
+    result: String,
+    declaring_cost: u64,
+    defending_cost: u64,
+}
 
-/// Statistics for a player
-#[derive(Default, Clone)]
-struct PlayerStats {
-    name: String,
-    // Total deals where this player participated (including as dummy)
-    total_deals: u64,
-    // Declaring stats
-    declaring_plays: u64,
-    declaring_errors: u64,
-    declaring_deals: u64,
-    // Defending stats
-    defending_plays: u64,
-    defending_errors: u64,
-    defending_deals: u64,
-}
-
-impl PlayerStats {
-    fn new(name: &str) -> Self {
-        PlayerStats {
-            name: name.to_string(),
-            ..Default::default()
-        }
-    }
-
-    fn declaring_error_rate(&self) -> f64 {
-        if self.declaring_plays == 0 {
-            0.0
-        } else {
-            self.declaring_errors as f64 / self.declaring_plays as f64 * 100.0
-        }
-    }
+/// How a board's analysis changed between two runs of the same pipeline.
+enum BoardChange {
+    Added,
+    Removed,
+    Changed { baseline: BoardSnapshot, candidate: BoardSnapshot },
+}
 
-    fn defending_error_rate(&self) -> f64 {
-        if self.defending_plays == 0 {
-            0.0
-        } else {
-            self.defending_errors as f64 / self.defending_plays as f64 * 100.0
+fn load_board_snapshots(path: &PathBuf) -> Result<HashMap<String, BoardSnapshot>> {
+    let mut reader = ReaderBuilder::new()
+        .flexible(true)
+        .from_path(path)
+        .with_context(|| format!("Failed to open {:?}", path))?;
+    let headers = reader.headers()?.clone();
+    let find_col = |name: &str| headers.iter().position(|h| h == name);
+
+    let ref_col = find_col("Board_ID").or_else(|| find_col("Ref #"))
+        .ok_or_else(|| anyhow::anyhow!("Neither 'Board_ID' nor 'Ref #' column found in {:?}", path))?;
+    let contract_col = find_col("Contract");
+    let result_col = find_col("Result");
+    let dec_col = find_col("Dec");
+    let n_errors_col = find_col("DD_N_Errors");
+    let s_errors_col = find_col("DD_S_Errors");
+    let e_errors_col = find_col("DD_E_Errors");
+    let w_errors_col = find_col("DD_W_Errors");
+
+    let mut snapshots = HashMap::new();
+    for result in reader.records() {
+        let record = result.context("Failed to read CSV row")?;
+        let get = |col: Option<usize>| col.and_then(|i| record.get(i)).unwrap_or("");
+        let board_id = get(Some(ref_col)).to_string();
+        if board_id.is_empty() {
+            continue;
         }
-    }
 
-    fn total_deals(&self) -> u64 {
-        self.total_deals
-    }
+        let n_errors: u64 = n_errors_col.and_then(|i| record.get(i)).and_then(|s| s.parse().ok()).unwrap_or(0);
+        let s_errors: u64 = s_errors_col.and_then(|i| record.get(i)).and_then(|s| s.parse().ok()).unwrap_or(0);
+        let e_errors: u64 = e_errors_col.and_then(|i| record.get(i)).and_then(|s| s.parse().ok()).unwrap_or(0);
+        let w_errors: u64 = w_errors_col.and_then(|i| record.get(i)).and_then(|s| s.parse().ok()).unwrap_or(0);
 
-    /// Merge another player's stats into this one (for "Field" aggregation)
-    fn merge(&mut self, other: &PlayerStats) {
-        self.total_deals += other.total_deals;
-        self.declaring_plays += other.declaring_plays;
-        self.declaring_errors += other.declaring_errors;
-        self.declaring_deals += other.declaring_deals;
-        self.defending_plays += other.defending_plays;
-        self.defending_errors += other.defending_errors;
-        self.defending_deals += other.defending_deals;
-    }
+        let declarer = get(dec_col).trim().to_uppercase();
+        let (declaring_cost, defending_cost) = match declarer.chars().next() {
+            Some('N') | Some('S') => (n_errors + s_errors, e_errors + w_errors),
+            Some('E') | Some('W') => (e_errors + w_errors, n_errors + s_errors),
+            _ => (0, 0),
+        };
 
-    /// 95% confidence interval half-width for error rate (using normal approximation)
-    fn declaring_ci(&self) -> f64 {
-        if self.declaring_plays < 30 {
-            return f64::NAN;
-        }
-        let p = self.declaring_errors as f64 / self.declaring_plays as f64;
-        let n = self.declaring_plays as f64;
-        1.96 * (p * (1.0 - p) / n).sqrt() * 100.0
+        snapshots.insert(board_id, BoardSnapshot {
+            contract: get(contract_col).to_string(),
+            result: get(result_col).to_string(),
+            declaring_cost,
+            defending_cost,
+        });
     }
 
-    fn defending_ci(&self) -> f64 {
-        if self.defending_plays < 30 {
-            return f64::NAN;
+    Ok(snapshots)
+}
+
+/// Compare two analyzed CSVs (aligned by `Board_ID`/`Ref #`) and report
+/// regressions in contract, result, and per-side DD cost totals. Lets users
+/// verify that re-running `analyze-dd` after a solver change didn't silently
+/// alter results, the same way golden-file comparison catches format drift.
+fn compute_diff(baseline: &PathBuf, candidate: &PathBuf) -> Result<()> {
+    let baseline_boards = load_board_snapshots(baseline)?;
+    let candidate_boards = load_board_snapshots(candidate)?;
+
+    let mut all_ids: Vec<&String> = baseline_boards.keys().chain(candidate_boards.keys()).collect();
+    all_ids.sort();
+    all_ids.dedup();
+
+    let mut changes: Vec<(String, BoardChange)> = Vec::new();
+    for board_id in all_ids {
+        match (baseline_boards.get(board_id), candidate_boards.get(board_id)) {
+            (None, Some(_)) => changes.push((board_id.clone(), BoardChange::Added)),
+            (Some(_), None) => changes.push((board_id.clone(), BoardChange::Removed)),
+            (Some(b), Some(c)) => {
+                if b.contract != c.contract || b.result != c.result
+                    || b.declaring_cost != c.declaring_cost || b.defending_cost != c.defending_cost
+                {
+                    changes.push((board_id.clone(), BoardChange::Changed {
+                        baseline: BoardSnapshot {
+                            contract: b.contract.clone(),
+                            result: b.result.clone(),
+                            declaring_cost: b.declaring_cost,
+                            defending_cost: b.defending_cost,
+                        },
+                        candidate: BoardSnapshot {
+                            contract: c.contract.clone(),
+                            result: c.result.clone(),
+                            declaring_cost: c.declaring_cost,
+                            defending_cost: c.defending_cost,
+                        },
+                    }));
+                }
+            }
+            (None, None) => {}
         }
-        let p = self.defending_errors as f64 / self.defending_plays as f64;
-        let n = self.defending_plays as f64;
-        1.96 * (p * (1.0 - p) / n).sqrt() * 100.0
     }
 
-    /// Calculate the Def - Decl difference (expected to be positive for honest players)
-    fn def_minus_decl(&self) -> f64 {
-        self.defending_error_rate() - self.declaring_error_rate()
-    }
+    let added = changes.iter().filter(|(_, c)| matches!(c, BoardChange::Added)).count();
+    let removed = changes.iter().filter(|(_, c)| matches!(c, BoardChange::Removed)).count();
+    let changed = changes.iter().filter(|(_, c)| matches!(c, BoardChange::Changed { .. })).count();
+    let mut declaring_cost_delta: i64 = 0;
+    let mut defending_cost_delta: i64 = 0;
 
-    /// Standard error for the Def - Decl difference
-    fn diff_se(&self) -> f64 {
-        if self.declaring_plays < 30 || self.defending_plays < 30 {
-            return f64::NAN;
+    println!("{:=^80}", " REGRESSION DIFF ");
+    println!("{:<12} {:<10} {:<12} {:<12} {:<12}", "Board", "Change", "Contract", "Result", "Cost (D/F)");
+    println!("{:-<80}", "");
+
+    for (board_id, change) in &changes {
+        match change {
+            BoardChange::Added => println!("{:<12} {:<10}", board_id, "added"),
+            BoardChange::Removed => println!("{:<12} {:<10}", board_id, "removed"),
+            BoardChange::Changed { baseline, candidate } => {
+                declaring_cost_delta += candidate.declaring_cost as i64 - baseline.declaring_cost as i64;
+                defending_cost_delta += candidate.defending_cost as i64 - baseline.defending_cost as i64;
+                println!(
+                    "{:<12} {:<10} {:<12} {:<12} {}/{} -> {}/{}",
+                    board_id, "changed",
+                    format!("{}->{}", baseline.contract, candidate.contract),
+                    format!("{}->{}", baseline.result, candidate.result),
+                    baseline.declaring_cost, baseline.defending_cost,
+                    candidate.declaring_cost, candidate.defending_cost,
+                );
+            }
         }
-        let p1 = self.declaring_errors as f64 / self.declaring_plays as f64;
-        let n1 = self.declaring_plays as f64;
-        let p2 = self.defending_errors as f64 / self.defending_plays as f64;
-        let n2 = self.defending_plays as f64;
-        // SE of difference of two proportions
-        ((p1 * (1.0 - p1) / n1) + (p2 * (1.0 - p2) / n2)).sqrt() * 100.0
     }
-}
 
-/// Z-test comparing two players' Def-Decl differences
-/// Returns (z-score, p-value) for one-tailed test
-fn z_test_diff_vs_baseline(subject: &PlayerStats, baseline: &PlayerStats) -> (f64, f64) {
-    let diff_subj = subject.def_minus_decl();
-    let diff_base = baseline.def_minus_decl();
+    println!("{:-<80}", "");
+    println!(
+        "{} added, {} removed, {} changed ({} boards in baseline, {} in candidate)",
+        added, removed, changed, baseline_boards.len(), candidate_boards.len()
+    );
+    println!(
+        "Aggregate DD cost delta: declaring {:+}, defending {:+}",
+        declaring_cost_delta, defending_cost_delta
+    );
+
+    Ok(())
+}
 
-    let se_subj = subject.diff_se();
-    let se_base = baseline.diff_se();
+/// Parse a `DD_Error_Categories` cell (`N:opening-lead=1;E:declarer-line=2,defensive-carding=1`,
+/// as written by `format_error_categories`) into per-seat rule-id -> count maps.
+fn parse_error_categories(raw: &str) -> HashMap<char, HashMap<String, u64>> {
+    let mut by_seat = HashMap::new();
+    for seat_part in raw.split(';') {
+        let seat_part = seat_part.trim();
+        if seat_part.is_empty() {
+            continue;
+        }
+        let Some((seat_str, cats_str)) = seat_part.split_once(':') else {
+            continue;
+        };
+        let Some(seat_char) = seat_str.trim().chars().next() else {
+            continue;
+        };
 
-    if se_subj.is_nan() || se_base.is_nan() {
-        return (f64::NAN, f64::NAN);
+        let mut categories = HashMap::new();
+        for cat in cats_str.split(',') {
+            if let Some((id, count_str)) = cat.split_once('=') {
+                if let Ok(count) = count_str.trim().parse::<u64>() {
+                    categories.insert(id.trim().to_string(), count);
+                }
+            }
+        }
+        by_seat.insert(seat_char, categories);
     }
+    by_seat
+}
 
-    // Combined SE for comparing two differences
-    let se_combined = (se_subj.powi(2) + se_base.powi(2)).sqrt();
+/// Logistic win-probability-style weight for a single play's DD cost, the
+/// way a chess engine maps centipawns to win chance: a cost of `c0` maps to
+/// 0.5, and `k` controls how sharply probability rises around it. Unlike a
+/// flat "cost > 0 is an error" threshold, this lets a one-trick blunder
+/// count far more than a borderline half-point inaccuracy would if DD ever
+/// reported fractional costs, and keeps every play's contribution bounded
+/// in `(0, 1)` regardless of how costly the alternative line of play was.
+fn p_loss(cost: f64, k: f64, c0: f64) -> f64 {
+    1.0 / (1.0 + (-k * (cost - c0)).exp())
+}
 
-    // Z-score: how many SEs is subject's diff below baseline's diff?
-    let z = (diff_subj - diff_base) / se_combined;
+/// Walks one row's `DD_Analysis` cost string trick by trick (the same
+/// seat-rotation walk as `display_hand`'s "DD ANALYSIS SUMMARY" section),
+/// adding each play's [`p_loss`] to the attributed player's declaring or
+/// defending loss sum in `player_stats`. `accuracy_seats` maps a seat letter
+/// to the player name its plays are attributed to -- the declaring side's
+/// plays (declarer and dummy) are both attributed to the declarer, matching
+/// `accumulate_stats_from_csv`'s existing plays/errors convention.
+fn accumulate_play_accuracy(
+    dd_analysis: &str,
+    cardplay: &str,
+    contract: &str,
+    declarer: &str,
+    accuracy_seats: &[(char, String); 4],
+    declaring_seats: [char; 2],
+    k: f64,
+    c0: f64,
+    player_stats: &mut HashMap<String, PlayerStats>,
+) {
+    let initial_leader = match declarer.chars().next() {
+        Some('N') => 'E',
+        Some('E') => 'S',
+        Some('S') => 'W',
+        Some('W') => 'N',
+        _ => return,
+    };
 
-    // One-tailed p-value (testing if subject's diff is significantly LOWER than baseline)
-    // P(Z <= z) where z is negative when subject has smaller gap than baseline
-    // This gives the probability of seeing a gap this small or smaller by chance
-    let p = 0.5 * (1.0 + erf(z / std::f64::consts::SQRT_2));
+    let tricks: Vec<&str> = cardplay.split('|').collect();
+    let mut current_leader = initial_leader;
 
-    (z, p)
-}
+    for (trick_idx, trick_str) in dd_analysis.split('|').enumerate() {
+        if let Some(colon_idx) = trick_str.find(':') {
+            let costs: Vec<u8> = trick_str[colon_idx + 1..]
+                .split(',')
+                .filter_map(|s| s.trim().parse().ok())
+                .collect();
 
-/// Error function approximation (for p-value calculation)
-fn erf(x: f64) -> f64 {
-    // Horner form coefficients for erf approximation
-    let a1 =  0.254829592;
-    let a2 = -0.284496736;
-    let a3 =  1.421413741;
-    let a4 = -1.453152027;
-    let a5 =  1.061405429;
-    let p  =  0.3275911;
+            if costs.len() == 4 {
+                let seats = get_seat_order(current_leader);
+                for (i, &cost) in costs.iter().enumerate() {
+                    let seat = seats[i];
+                    let Some((_, player)) = accuracy_seats.iter().find(|(s, _)| *s == seat) else {
+                        continue;
+                    };
+                    if player.is_empty() {
+                        continue;
+                    }
+                    let loss = p_loss(cost as f64, k, c0);
+                    let stats = player_stats
+                        .entry(player.clone())
+                        .or_insert_with(|| PlayerStats::new(player));
+                    if declaring_seats.contains(&seat) {
+                        stats.declaring_ploss_sum += loss;
+                        stats.declaring_ploss_count += 1;
+                    } else {
+                        stats.defending_ploss_sum += loss;
+                        stats.defending_ploss_count += 1;
+                    }
+                }
+            }
 
-    let sign = if x < 0.0 { -1.0 } else { 1.0 };
-    let x = x.abs();
-    let t = 1.0 / (1.0 + p * x);
-    let y = 1.0 - (((((a5 * t + a4) * t) + a3) * t + a2) * t + a1) * t * (-x * x).exp();
-    sign * y
+            if trick_idx < tricks.len() {
+                let cards: Vec<&str> = tricks[trick_idx].split_whitespace().collect();
+                if let Some(winner) = determine_trick_winner_for_display(&cards, current_leader, contract) {
+                    current_leader = winner;
+                }
+            }
+        }
+    }
 }
 
-fn compute_stats(input: &PathBuf, top_n: usize, output: Option<&PathBuf>) -> Result<()> {
+/// Read one CSV and accumulate its per-player DD error stats and partnership
+/// deal counts into `player_stats`/`partnership_counts`, so `compute_stats`
+/// (single file) and `compute_stats_aggregate` (many files, e.g. a season of
+/// tournament exports via `--recursive`) can share the same accumulation and
+/// differ only in how many times they call this before reporting. Returns
+/// this file's `(processed, skipped)` deal counts.
+fn accumulate_stats_from_csv(
+    input: &PathBuf,
+    player_stats: &mut HashMap<String, PlayerStats>,
+    partnership_counts: &mut HashMap<(String, String), u64>,
+    accuracy_k: f64,
+    accuracy_c0: f64,
+    seq_offset: u64,
+) -> Result<(usize, usize)> {
     // Read input CSV
     let mut reader = ReaderBuilder::new()
         .flexible(true)
@@ -2315,18 +5235,30 @@ fn compute_stats(input: &PathBuf, top_n: usize, output: Option<&PathBuf>) -> Res
         .ok_or_else(|| anyhow::anyhow!("Column 'DD_E_Errors' not found"))?;
     let dd_w_errors_col = headers.iter().position(|h| h == "DD_W_Errors")
         .ok_or_else(|| anyhow::anyhow!("Column 'DD_W_Errors' not found"))?;
+    // Optional: only present on output from an `analyze-dd` run new enough
+    // to have the play-rule engine's category tally.
+    let dd_error_categories_col = headers.iter().position(|h| h == "DD_Error_Categories");
+    // Optional: only present on output from an `analyze-dd` run new enough
+    // to emit per-seat total DD trick cost, for Defense Above Replacement.
+    let dd_n_costsum_col = headers.iter().position(|h| h == "DD_N_CostSum");
+    let dd_s_costsum_col = headers.iter().position(|h| h == "DD_S_CostSum");
+    let dd_e_costsum_col = headers.iter().position(|h| h == "DD_E_CostSum");
+    let dd_w_costsum_col = headers.iter().position(|h| h == "DD_W_CostSum");
+    // Optional: only present when the input still has the per-trick cost
+    // string, needed to compute per-play logistic "accuracy" (the seat
+    // totals above aren't enough -- accuracy weights each play's own cost).
+    let cardplay_col = headers.iter().position(|h| h == "Cardplay");
+    let contract_col = headers.iter().position(|h| h == "Contract");
+    let dd_analysis_col = headers.iter().position(|h| h == "DD_Analysis");
 
-    // Collect stats per player
-    let mut player_stats: HashMap<String, PlayerStats> = HashMap::new();
-    // Track partnership deal counts: (player1, player2) -> deal_count
-    // Normalized so player1 < player2 alphabetically
-    let mut partnership_counts: HashMap<(String, String), u64> = HashMap::new();
     let mut processed = 0;
     let mut skipped = 0;
+    let mut deal_seq = seq_offset;
 
     for result in reader.records() {
         let record = result.context("Failed to read CSV row")?;
         processed += 1;
+        deal_seq += 1;
 
         // Get player names (lowercase for consistency)
         let north = record.get(n_col).unwrap_or("").to_lowercase();
@@ -2369,6 +5301,11 @@ fn compute_stats(input: &PathBuf, top_n: usize, output: Option<&PathBuf>) -> Res
         let e_errors: u64 = record.get(dd_e_errors_col).and_then(|s| s.parse().ok()).unwrap_or(0);
         let w_errors: u64 = record.get(dd_w_errors_col).and_then(|s| s.parse().ok()).unwrap_or(0);
 
+        let n_cost: u64 = dd_n_costsum_col.and_then(|i| record.get(i)).and_then(|s| s.parse().ok()).unwrap_or(0);
+        let s_cost: u64 = dd_s_costsum_col.and_then(|i| record.get(i)).and_then(|s| s.parse().ok()).unwrap_or(0);
+        let e_cost: u64 = dd_e_costsum_col.and_then(|i| record.get(i)).and_then(|s| s.parse().ok()).unwrap_or(0);
+        let w_cost: u64 = dd_w_costsum_col.and_then(|i| record.get(i)).and_then(|s| s.parse().ok()).unwrap_or(0);
+
         // Skip rows with no DD data (all plays are 0 means no cardplay analyzed)
         if n_plays == 0 && s_plays == 0 && e_plays == 0 && w_plays == 0 {
             skipped += 1;
@@ -2387,17 +5324,62 @@ fn compute_stats(input: &PathBuf, top_n: usize, output: Option<&PathBuf>) -> Res
             }
         };
 
+        let categories_by_seat = dd_error_categories_col
+            .and_then(|i| record.get(i))
+            .map(parse_error_categories)
+            .unwrap_or_default();
+
+        // Win-probability-weighted accuracy needs each play's own cost, not
+        // just the seat totals above, so walk the per-trick cost string
+        // directly when the row still has it.
+        if let (Some(cp_i), Some(con_i), Some(dda_i)) = (cardplay_col, contract_col, dd_analysis_col) {
+            let cardplay = record.get(cp_i).unwrap_or("");
+            let contract_str = record.get(con_i).unwrap_or("");
+            let dd_analysis = record.get(dda_i).unwrap_or("");
+            if !dd_analysis.is_empty() && !dd_analysis.starts_with("ERROR") {
+                let declaring_seat_chars: [char; 2] = match declarer.chars().next() {
+                    Some('N') | Some('S') => ['N', 'S'],
+                    Some('E') | Some('W') => ['E', 'W'],
+                    _ => ['?', '?'],
+                };
+                let attribute = |name: &str| -> String {
+                    if name == declarer_name || name == dummy_name {
+                        declarer_name.clone()
+                    } else {
+                        name.to_string()
+                    }
+                };
+                let accuracy_seats: [(char, String); 4] = [
+                    ('N', attribute(&north)),
+                    ('S', attribute(&south)),
+                    ('E', attribute(&east)),
+                    ('W', attribute(&west)),
+                ];
+                accumulate_play_accuracy(
+                    dd_analysis,
+                    cardplay,
+                    contract_str,
+                    &declarer,
+                    &accuracy_seats,
+                    declaring_seat_chars,
+                    accuracy_k,
+                    accuracy_c0,
+                    player_stats,
+                );
+            }
+        }
+
         // Map seat plays/errors to player names and roles
         // Declarer side: declarer + dummy plays/errors go to declarer's declaring stats
         // Defense side: each defender's plays/errors go to their own defending stats
         let seat_data = [
-            (&north, 'N', n_plays, n_errors),
-            (&south, 'S', s_plays, s_errors),
-            (&east, 'E', e_plays, e_errors),
-            (&west, 'W', w_plays, w_errors),
+            (&north, 'N', n_plays, n_errors, n_cost),
+            (&south, 'S', s_plays, s_errors, s_cost),
+            (&east, 'E', e_plays, e_errors, e_cost),
+            (&west, 'W', w_plays, w_errors, w_cost),
         ];
 
-        for (player_name, _seat, plays, errors) in &seat_data {
+        for (player_name, seat, plays, errors, cost) in &seat_data {
             if player_name.is_empty() {
                 continue;
             }
@@ -2406,13 +5388,21 @@ fn compute_stats(input: &PathBuf, top_n: usize, output: Option<&PathBuf>) -> Res
             let is_dummy = *player_name == dummy_name;
             let is_declaring_side = is_declarer || is_dummy;
 
-            if is_declaring_side {
+            let stats = if is_declaring_side {
                 // Declaring side plays/errors go to DECLARER's stats (not dummy)
                 let stats = player_stats
                     .entry(declarer_name.clone())
                     .or_insert_with(|| PlayerStats::new(declarer_name));
                 stats.declaring_plays += plays;
                 stats.declaring_errors += errors;
+                stats.declaring_cost_sum += cost;
+                stats.observations.push(DealObservation {
+                    role: PlayRole::Declaring,
+                    plays: *plays,
+                    errors: *errors,
+                    seq: deal_seq,
+                });
+                stats
             } else {
                 // Defender's plays/errors go to their own stats
                 let stats = player_stats
@@ -2420,11 +5410,25 @@ fn compute_stats(input: &PathBuf, top_n: usize, output: Option<&PathBuf>) -> Res
                     .or_insert_with(|| PlayerStats::new(player_name));
                 stats.defending_plays += plays;
                 stats.defending_errors += errors;
+                stats.defending_cost_sum += cost;
+                stats.observations.push(DealObservation {
+                    role: PlayRole::Defending,
+                    plays: *plays,
+                    errors: *errors,
+                    seq: deal_seq,
+                });
+                stats
+            };
+
+            if let Some(categories) = categories_by_seat.get(seat) {
+                for (category, count) in categories {
+                    *stats.error_categories.entry(category.clone()).or_insert(0) += count;
+                }
             }
         }
 
         // Track deals per player
-        for (player_name, _seat, _, _) in &seat_data {
+        for (player_name, _seat, _, _, _) in &seat_data {
             if player_name.is_empty() {
                 continue;
             }
@@ -2445,6 +5449,25 @@ fn compute_stats(input: &PathBuf, top_n: usize, output: Option<&PathBuf>) -> Res
         }
     }
 
+    Ok((processed, skipped))
+}
+
+/// Print the DD error-rate report and (optionally) write the detailed CSV,
+/// given stats already accumulated by one or more
+/// `accumulate_stats_from_csv` calls.
+fn report_player_stats(
+    player_stats: &HashMap<String, PlayerStats>,
+    partnership_counts: &HashMap<(String, String), u64>,
+    processed: usize,
+    skipped: usize,
+    top_n: usize,
+    output: Option<&PathBuf>,
+    bootstrap_iterations: u64,
+    accuracy_k: f64,
+    accuracy_c0: f64,
+    half_life: Option<f64>,
+    export: Option<&PathBuf>,
+) -> Result<()> {
     eprintln!("Processed {} deals ({} skipped)", processed, skipped);
     eprintln!("Found {} unique players\n", player_stats.len());
 
@@ -2477,8 +5500,8 @@ fn compute_stats(input: &PathBuf, top_n: usize, output: Option<&PathBuf>) -> Res
         // Relative percent: how much better/worse is defense vs declaring
         // Negative means defense is better (fewer errors), positive means worse
         let rel_pct = if decl_rate > 0.0 { -diff / decl_rate * 100.0 } else { 0.0 };
-        let decl_ci = player.declaring_ci();
-        let def_ci = player.defending_ci();
+        let (decl_ci_lo, decl_ci_hi) = player.declaring_ci();
+        let (def_ci_lo, def_ci_hi) = player.defending_ci();
 
         println!("{:<20} {:>8} {:>6} {:>6} {:>12} {:>9.2}% {:>12} {:>9.2}% {:>+9.2}% {:>+7.1}%",
             truncate_name(&player.name, 20),
@@ -2493,21 +5516,53 @@ fn compute_stats(input: &PathBuf, top_n: usize, output: Option<&PathBuf>) -> Res
             rel_pct
         );
 
-        // Print confidence intervals on separate line if enough data
-        if !decl_ci.is_nan() || !def_ci.is_nan() {
+        // Print Wilson score confidence intervals on a separate line
+        if player.declaring_plays > 0 || player.defending_plays > 0 {
             println!("{:<20} {:>8} {:>6} {:>6} {:>12} {:>10} {:>12} {:>10}",
                 "",
                 "",
                 "",
                 "",
-                format!("({:.2}%)", decl_ci),
+                format!("[{:.2}-{:.2}%]", decl_ci_lo, decl_ci_hi),
                 "",
-                format!("({:.2}%)", def_ci),
+                format!("[{:.2}-{:.2}%]", def_ci_lo, def_ci_hi),
                 ""
             );
         }
     }
 
+    // Error Category Breakdown: top-N players' errors by rule id, if the
+    // input had a `DD_Error_Categories` column for us to tally.
+    if players.iter().take(top_n).any(|p| !p.error_categories.is_empty()) {
+        println!("\n{:=^80}", " Error Categories ");
+        for player in players.iter().take(top_n) {
+            let top = player.top_categories(5);
+            if top.is_empty() {
+                continue;
+            }
+            let breakdown = top
+                .iter()
+                .map(|(id, count)| format!("{}={}", id, count))
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!("  {:<20} {}", truncate_name(&player.name, 20), breakdown);
+        }
+    }
+
+    // Win-probability-weighted accuracy: a separate table rather than wedged
+    // into the fixed-width error-rate table above, and only printed at all
+    // when at least one top-N player has per-play cost data to compute it.
+    if players.iter().take(top_n).any(|p| p.declaring_accuracy().is_some() || p.defending_accuracy().is_some()) {
+        println!("\n{:=^60}", format!(" Accuracy (logistic k={}, c0={}) ", accuracy_k, accuracy_c0));
+        println!("{:<20} {:>10} {:>10}", "Player", "Decl Acc%", "Def Acc%");
+        println!("{:-<42}", "");
+        for player in players.iter().take(top_n) {
+            let decl_acc = player.declaring_accuracy().map(|a| format!("{:.2}%", a)).unwrap_or_else(|| "-".to_string());
+            let def_acc = player.defending_accuracy().map(|a| format!("{:.2}%", a)).unwrap_or_else(|| "-".to_string());
+            println!("{:<20} {:>10} {:>10}", truncate_name(&player.name, 20), decl_acc, def_acc);
+        }
+    }
+
     // Print Field aggregate
     println!("{:-<126}", "");
     let decl_rate = field_stats.declaring_error_rate();
@@ -2527,14 +5582,16 @@ fn compute_stats(input: &PathBuf, top_n: usize, output: Option<&PathBuf>) -> Res
         diff,
         rel_pct
     );
+    let (field_decl_ci_lo, field_decl_ci_hi) = field_stats.declaring_ci();
+    let (field_def_ci_lo, field_def_ci_hi) = field_stats.defending_ci();
     println!("{:<20} {:>8} {:>6} {:>6} {:>12} {:>10} {:>12} {:>10}",
         "",
         "",
         "",
         "",
-        format!("({:.2}%)", field_stats.declaring_ci()),
+        format!("[{:.2}-{:.2}%]", field_decl_ci_lo, field_decl_ci_hi),
         "",
-        format!("({:.2}%)", field_stats.defending_ci()),
+        format!("[{:.2}-{:.2}%]", field_def_ci_lo, field_def_ci_hi),
         ""
     );
 
@@ -2586,6 +5643,8 @@ fn compute_stats(input: &PathBuf, top_n: usize, output: Option<&PathBuf>) -> Res
         // Statistical Test Section
         println!("\n{:=^100}", " Statistical Analysis ");
 
+        let replacement_rate = replacement_defending_rate(&players);
+
         // Compare each subject to Field baseline
         for subj in [subj_a, subj_b] {
             let subj_diff = subj.def_minus_decl();
@@ -2618,6 +5677,78 @@ fn compute_stats(input: &PathBuf, top_n: usize, output: Option<&PathBuf>) -> Res
             } else {
                 println!("    (Insufficient data for statistical test)");
             }
+
+            if bootstrap_iterations > 0 {
+                match bootstrap_def_minus_decl(&subj.observations, field_diff, bootstrap_iterations) {
+                    Some(boot) => {
+                        println!(
+                            "    Bootstrap 95% CI ({} resamples): [{:+.2}%, {:+.2}%]",
+                            bootstrap_iterations, boot.ci_lo, boot.ci_hi
+                        );
+                        println!("    Bootstrap P-value: {:.4} (fraction of resamples >= FIELD's diff)", boot.p_value);
+                    }
+                    None => println!("    (No per-deal observations recorded for bootstrap)"),
+                }
+            }
+
+            if subj.defending_plays > 0 {
+                let dar = subj.defense_above_replacement(replacement_rate);
+                println!(
+                    "    Defense Above Replacement: {:+.2} tricks over {} defending plays (replacement rate {:.3} tricks/play)",
+                    dar, subj.defending_plays, replacement_rate
+                );
+                if dar > 1.0 {
+                    println!("      Large positive DAR -- {} is saving far more tricks on defense than a replacement-level defender would", subj.name);
+                }
+            } else {
+                println!("    (No DD cost data for DAR -- re-run analyze-dd to populate DD_<seat>_CostSum)");
+            }
+
+            if let Some(hl) = half_life {
+                match (
+                    weighted_error_stats(&subj.observations, PlayRole::Declaring, hl),
+                    weighted_error_stats(&subj.observations, PlayRole::Defending, hl),
+                ) {
+                    (Some((decl_rate, decl_ess)), Some((def_rate, def_ess))) => {
+                        let (decl_ci_lo, decl_ci_hi) = wilson_ci_f64(decl_rate, decl_ess);
+                        let (def_ci_lo, def_ci_hi) = wilson_ci_f64(def_rate, def_ess);
+                        println!(
+                            "    Recency-weighted (half-life={} deals): Decl {:.2}% [{:.2}-{:.2}%] (ESS {:.1}), Def {:.2}% [{:.2}-{:.2}%] (ESS {:.1}), Diff {:+.2}%",
+                            hl, decl_rate, decl_ci_lo, decl_ci_hi, decl_ess, def_rate, def_ci_lo, def_ci_hi, def_ess, def_rate - decl_rate
+                        );
+                        if let (Some((fdr, fde)), Some((ffr, ffe))) = (
+                            weighted_error_stats(&field_stats.observations, PlayRole::Declaring, hl),
+                            weighted_error_stats(&field_stats.observations, PlayRole::Defending, hl),
+                        ) {
+                            let (wz, wp) =
+                                z_test_diff_vs_baseline_weighted(decl_rate, decl_ess, def_rate, def_ess, fdr, fde, ffr, ffe);
+                            if !wz.is_nan() {
+                                println!("      Recency-weighted Z-score vs FIELD: {:.2} (P-value {:.4})", wz, wp);
+                            }
+                        }
+                    }
+                    _ => println!("    (Insufficient recency-weighted data)"),
+                }
+            }
+        }
+
+        // Direct two-proportion comparison between the two subjects
+        println!("\n  {} vs {} (declaring error rate):", subj_a.name, subj_b.name);
+        let (decl_z, decl_p) = subj_a.declaring_vs(subj_b);
+        if !decl_z.is_nan() {
+            println!("    Z-score: {:.2}, P-value: {:.4}{}", decl_z, decl_p,
+                if decl_p < 0.05 { " (significant at 5%)" } else { " (not significant)" });
+        } else {
+            println!("    (Insufficient data for statistical test)");
+        }
+
+        println!("  {} vs {} (defending error rate):", subj_a.name, subj_b.name);
+        let (def_z, def_p) = subj_a.defending_vs(subj_b);
+        if !def_z.is_nan() {
+            println!("    Z-score: {:.2}, P-value: {:.4}{}", def_z, def_p,
+                if def_p < 0.05 { " (significant at 5%)" } else { " (not significant)" });
+        } else {
+            println!("    (Insufficient data for statistical test)");
         }
     }
 
@@ -2644,7 +5775,6 @@ fn compute_stats(input: &PathBuf, top_n: usize, output: Option<&PathBuf>) -> Res
 
     // Suspicious Players Table: Def-Decl > 0.05% (defense better than declaring) and p < 0.20
     // Require minimum 50 deals for statistical reliability
-    const MIN_DEALS_FOR_SUSPICIOUS: u64 = 50;
     let mut suspicious: Vec<_> = players.iter()
         .filter_map(|p| {
             // Skip players with insufficient data
@@ -2785,12 +5915,14 @@ fn compute_stats(input: &PathBuf, top_n: usize, output: Option<&PathBuf>) -> Res
 
         writer.write_record(&[
             "Player", "Total_Deals", "Decl_Deals", "Def_Deals",
-            "Decl_Plays", "Decl_Errors", "Decl_Err_Pct", "Decl_CI",
-            "Def_Plays", "Def_Errors", "Def_Err_Pct", "Def_CI",
+            "Decl_Plays", "Decl_Errors", "Decl_Err_Pct", "Decl_CI_Lo", "Decl_CI_Hi",
+            "Def_Plays", "Def_Errors", "Def_Err_Pct", "Def_CI_Lo", "Def_CI_Hi",
             "Diff_Pct"
         ])?;
 
         for player in &players {
+            let (decl_ci_lo, decl_ci_hi) = player.declaring_ci();
+            let (def_ci_lo, def_ci_hi) = player.defending_ci();
             writer.write_record(&[
                 &player.name,
                 &player.total_deals().to_string(),
@@ -2799,16 +5931,20 @@ fn compute_stats(input: &PathBuf, top_n: usize, output: Option<&PathBuf>) -> Res
                 &player.declaring_plays.to_string(),
                 &player.declaring_errors.to_string(),
                 &format!("{:.4}", player.declaring_error_rate()),
-                &format!("{:.4}", player.declaring_ci()),
+                &format!("{:.4}", decl_ci_lo),
+                &format!("{:.4}", decl_ci_hi),
                 &player.defending_plays.to_string(),
                 &player.defending_errors.to_string(),
                 &format!("{:.4}", player.defending_error_rate()),
-                &format!("{:.4}", player.defending_ci()),
+                &format!("{:.4}", def_ci_lo),
+                &format!("{:.4}", def_ci_hi),
                 &format!("{:.4}", player.declaring_error_rate() - player.defending_error_rate()),
             ])?;
         }
 
         // Add Field row
+        let (field_decl_ci_lo, field_decl_ci_hi) = field_stats.declaring_ci();
+        let (field_def_ci_lo, field_def_ci_hi) = field_stats.defending_ci();
         writer.write_record(&[
             "FIELD",
             &field_stats.total_deals().to_string(),
@@ -2817,11 +5953,13 @@ fn compute_stats(input: &PathBuf, top_n: usize, output: Option<&PathBuf>) -> Res
             &field_stats.declaring_plays.to_string(),
             &field_stats.declaring_errors.to_string(),
             &format!("{:.4}", field_stats.declaring_error_rate()),
-            &format!("{:.4}", field_stats.declaring_ci()),
+            &format!("{:.4}", field_decl_ci_lo),
+            &format!("{:.4}", field_decl_ci_hi),
             &field_stats.defending_plays.to_string(),
             &field_stats.defending_errors.to_string(),
             &format!("{:.4}", field_stats.defending_error_rate()),
-            &format!("{:.4}", field_stats.defending_ci()),
+            &format!("{:.4}", field_def_ci_lo),
+            &format!("{:.4}", field_def_ci_hi),
             &format!("{:.4}", field_stats.declaring_error_rate() - field_stats.defending_error_rate()),
         ])?;
 
@@ -2829,9 +5967,59 @@ fn compute_stats(input: &PathBuf, top_n: usize, output: Option<&PathBuf>) -> Res
         eprintln!("\nDetailed stats written to: {}", output_path.display());
     }
 
+    if let Some(export_path) = export {
+        write_stats_export(export_path, &players, &field_stats)?;
+        eprintln!("Exported player stats JSON to: {}", export_path.display());
+    }
+
     Ok(())
 }
 
+fn compute_stats(
+    input: &PathBuf,
+    top_n: usize,
+    output: Option<&PathBuf>,
+    bootstrap: u64,
+    accuracy_k: f64,
+    accuracy_c0: f64,
+    half_life: Option<f64>,
+    export: Option<&PathBuf>,
+) -> Result<()> {
+    let mut player_stats: HashMap<String, PlayerStats> = HashMap::new();
+    let mut partnership_counts: HashMap<(String, String), u64> = HashMap::new();
+    let (processed, skipped) =
+        accumulate_stats_from_csv(input, &mut player_stats, &mut partnership_counts, accuracy_k, accuracy_c0, 0)?;
+    report_player_stats(&player_stats, &partnership_counts, processed, skipped, top_n, output, bootstrap, accuracy_k, accuracy_c0, half_life, export)
+}
+
+/// Merge DD error stats across every CSV in `inputs` into one combined
+/// report -- the `--recursive` aggregation step for `stats`, so a whole
+/// season of tournament exports can be analyzed as one field instead of
+/// per-file in isolation.
+fn compute_stats_aggregate(
+    inputs: &[PathBuf],
+    top_n: usize,
+    output: Option<&PathBuf>,
+    bootstrap: u64,
+    accuracy_k: f64,
+    accuracy_c0: f64,
+    half_life: Option<f64>,
+    export: Option<&PathBuf>,
+) -> Result<()> {
+    let mut player_stats: HashMap<String, PlayerStats> = HashMap::new();
+    let mut partnership_counts: HashMap<(String, String), u64> = HashMap::new();
+    let mut processed = 0usize;
+    let mut skipped = 0usize;
+    let mut seq_offset = 0u64;
+    for input in inputs {
+        let (p, s) = accumulate_stats_from_csv(input, &mut player_stats, &mut partnership_counts, accuracy_k, accuracy_c0, seq_offset)?;
+        processed += p;
+        skipped += s;
+        seq_offset += p as u64;
+    }
+    report_player_stats(&player_stats, &partnership_counts, processed, skipped, top_n, output, bootstrap, accuracy_k, accuracy_c0, half_life, export)
+}
+
 /// Get seat order starting from leader going clockwise
 fn get_seat_order(leader: char) -> [char; 4] {
     match leader {
@@ -0,0 +1,838 @@
+//! DD Batch - batch double-dummy analysis over many boards at once
+//!
+//! `dd-debug` analyzes a single hand's cardplay from one TinyURL. This
+//! companion tool generalizes that to many boards at once: give it a file
+//! of TinyURLs (or raw LIN strings), one per line, or a multi-board `.pbn`
+//! file, and it runs the card-by-card DD analysis over every board in
+//! parallel, then reports per-player totals and the single biggest
+//! blunder -- optionally as a machine-readable JSON report (`--json`)
+//! instead of the console summary.
+//!
+//! `compute_dd_costs` already allocates its own solver caches per call, so
+//! handing boards to it from a `rayon` parallel iterator is solve-safe
+//! without any extra bookkeeping here.
+
+use anyhow::{Context, Result};
+use bridge_parsers::lin::parse_lin_from_url;
+use bridge_parsers::tinyurl::UrlResolver;
+use bridge_parsers::{Card, Rank, Suit};
+use bridge_solver::cards::card_of;
+use bridge_solver::{CLUB, DIAMOND, EAST, HEART, NORTH, NOTRUMP, SOUTH, SPADE, WEST};
+use clap::Parser;
+use edgar_defense_toolkit::dd_analysis::compute_dd_costs;
+use rayon::prelude::*;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "dd-batch")]
+#[command(about = "Batch DD analysis over many boards, with per-player blunder totals")]
+struct Cli {
+    /// Input file: one TinyURL/LIN string per line, or a multi-board .pbn file
+    input: PathBuf,
+
+    /// Emit a machine-readable JSON report instead of the console summary
+    #[arg(long)]
+    json: bool,
+
+    /// Sort the player table by errors-per-board (worst first) instead of
+    /// alphabetically by name
+    #[arg(long)]
+    rank: bool,
+
+    /// Number of parallel threads (default: number of CPU cores)
+    #[arg(short, long)]
+    threads: Option<usize>,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    if let Some(threads) = cli.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .ok();
+    }
+
+    let boards = load_boards(&cli.input)?;
+    if boards.is_empty() {
+        anyhow::bail!("No boards found in {:?}", cli.input);
+    }
+
+    let reports: Vec<BoardReport> = boards
+        .par_iter()
+        .filter_map(|board| analyze_batch_board(board))
+        .collect();
+
+    let mut player_stats: HashMap<String, PlayerBatchStats> = HashMap::new();
+    for report in &reports {
+        accumulate(&mut player_stats, report);
+    }
+
+    if cli.json {
+        println!("{}", render_json(&reports, &player_stats));
+    } else {
+        print_summary(&boards.len(), &reports, &player_stats, cli.rank);
+    }
+
+    Ok(())
+}
+
+/// One board's raw inputs to the DD analysis, normalized from whichever
+/// source format it came from.
+struct BatchBoard {
+    board_num: Option<usize>,
+    deal_pbn: String,
+    cardplay: String,
+    contract: String,
+    declarer: String,
+    seat_to_label: HashMap<usize, String>,
+}
+
+/// Load boards from `path`: a multi-board `.pbn` file if the extension or
+/// contents say so, otherwise a newline-separated list of TinyURLs/LIN
+/// strings (blank lines and `#`-comments ignored).
+fn load_boards(path: &PathBuf) -> Result<Vec<BatchBoard>> {
+    let contents = std::fs::read_to_string(path).context("Failed to read input file")?;
+
+    let looks_like_pbn = path.extension().and_then(|e| e.to_str()) == Some("pbn")
+        || contents.contains("[Deal ");
+
+    if looks_like_pbn {
+        Ok(load_pbn_boards(&contents))
+    } else {
+        load_url_boards(&contents)
+    }
+}
+
+fn load_url_boards(contents: &str) -> Result<Vec<BatchBoard>> {
+    let mut resolver = UrlResolver::with_config(0, 1, 0);
+    let mut boards = Vec::new();
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let resolved = if line.contains("tinyurl.com") || line.contains("bit.ly") {
+            resolver.resolve(line)?
+        } else {
+            line.to_string()
+        };
+
+        let lin_data = parse_lin_from_url(&resolved)
+            .with_context(|| format!("Failed to parse LIN source: {}", line))?;
+
+        let pbn = lin_data.deal.to_pbn(bridge_parsers::Direction::North);
+        let cardplay = lin_data.format_cardplay_by_trick();
+        let contract = extract_contract(&lin_data);
+        let declarer = extract_declarer(&lin_data);
+        let board_num = lin_data
+            .board_header
+            .as_ref()
+            .and_then(|h| h.split_whitespace().last())
+            .and_then(|n| n.parse().ok());
+
+        let seat_to_label: HashMap<usize, String> = [
+            (SOUTH, lin_data.player_names[0].clone()),
+            (WEST, lin_data.player_names[1].clone()),
+            (NORTH, lin_data.player_names[2].clone()),
+            (EAST, lin_data.player_names[3].clone()),
+        ]
+        .into_iter()
+        .collect();
+
+        boards.push(BatchBoard {
+            board_num,
+            deal_pbn: pbn,
+            cardplay,
+            contract,
+            declarer,
+            seat_to_label,
+        });
+    }
+
+    Ok(boards)
+}
+
+fn load_pbn_boards(contents: &str) -> Vec<BatchBoard> {
+    lazy_static::lazy_static! {
+        static ref TAG: Regex = Regex::new(r#"(?m)^\[(\w+)\s+"([^"]*)"\]\s*$"#).unwrap();
+        static ref PLAY_TAG: Regex = Regex::new(r#"(?m)^\[Play\s+"([NESW])"\]\s*$"#).unwrap();
+    }
+
+    let seat_to_label: HashMap<usize, String> = [
+        (NORTH, "North".to_string()),
+        (EAST, "East".to_string()),
+        (SOUTH, "South".to_string()),
+        (WEST, "West".to_string()),
+    ]
+    .into_iter()
+    .collect();
+
+    let mut boards = Vec::new();
+    for block in contents.split("\n\n") {
+        if block.trim().is_empty() {
+            continue;
+        }
+
+        let mut tags = HashMap::new();
+        for cap in TAG.captures_iter(block) {
+            tags.insert(cap[1].to_string(), cap[2].to_string());
+        }
+        let Some(deal) = tags.get("Deal") else {
+            continue;
+        };
+
+        let contract = tags.get("Contract").cloned().unwrap_or_default();
+        let declarer = tags.get("Declarer").cloned().unwrap_or_default();
+        let board_num = tags.get("Board").and_then(|b| b.parse().ok());
+        let cardplay = parse_pbn_play(block, &contract, &PLAY_TAG);
+
+        boards.push(BatchBoard {
+            board_num,
+            deal_pbn: deal.clone(),
+            cardplay,
+            contract,
+            declarer,
+            seat_to_label: seat_to_label.clone(),
+        });
+    }
+
+    boards
+}
+
+/// Parse a PBN `[Play "<leader>"]` section into the internal pipe-separated
+/// cardplay format `dd_analysis` expects.
+fn parse_pbn_play(pbn: &str, contract: &str, play_tag: &Regex) -> String {
+    let Some(caps) = play_tag.captures(pbn) else {
+        return String::new();
+    };
+    let leader_char = caps[1].chars().next().unwrap();
+    let mut current_leader = match leader_char {
+        'N' => NORTH,
+        'E' => EAST,
+        'S' => SOUTH,
+        _ => WEST,
+    };
+    let section_start = caps.get(0).unwrap().end();
+    let trump = parse_trump(contract).unwrap_or(NOTRUMP);
+
+    let mut tricks = Vec::new();
+    for line in pbn[section_start..].lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('*') || line.starts_with('[') {
+            break;
+        }
+
+        let cols: Vec<&str> = line.split_whitespace().collect();
+        if cols.len() != 4 {
+            break;
+        }
+
+        let seat_order = [NORTH, EAST, SOUTH, WEST];
+        let mut by_seat: HashMap<usize, &str> = HashMap::new();
+        for (&seat, &card) in seat_order.iter().zip(cols.iter()) {
+            by_seat.insert(seat, card);
+        }
+
+        let seats_in_play_order: Vec<usize> = (0..4).map(|i| (current_leader + i) % 4).collect();
+        let trick_cards: Vec<&str> = seats_in_play_order
+            .iter()
+            .map(|s| by_seat.get(s).copied().unwrap_or("-"))
+            .take_while(|&c| c != "-")
+            .collect();
+        if trick_cards.is_empty() {
+            break;
+        }
+        tricks.push(trick_cards.join(" "));
+
+        if trick_cards.len() == 4 {
+            let solver_cards: Vec<(usize, usize)> = seats_in_play_order
+                .iter()
+                .zip(trick_cards.iter())
+                .filter_map(|(&seat, &card_str)| {
+                    let card = parse_card_str(card_str).ok()?;
+                    bridge_card_to_solver(card).ok().map(|sc| (seat, sc))
+                })
+                .collect();
+            if solver_cards.len() == 4 {
+                current_leader = determine_trick_winner(&solver_cards, trump, current_leader);
+            }
+        }
+    }
+
+    tricks.join("|")
+}
+
+/// A single DD error, tagged with whether it fell on the declaring side.
+struct BatchError {
+    player: String,
+    is_declarer_side: bool,
+    trick_num: usize,
+    card_str: String,
+    cost: u8,
+}
+
+/// Per-board DD report.
+struct BoardReport {
+    board_num: Option<usize>,
+    contract: String,
+    declarer_label: String,
+    defender_labels: Vec<String>,
+    initial_dd: u8,
+    final_result: u8,
+    errors: Vec<BatchError>,
+}
+
+fn analyze_batch_board(board: &BatchBoard) -> Option<BoardReport> {
+    let contract: edgar_defense_toolkit::contract::Contract = board.contract.parse().ok()?;
+    let dd_result = compute_dd_costs(
+        &board.deal_pbn,
+        &board.cardplay,
+        &contract,
+        &board.declarer,
+        false,
+        false,
+        false,
+    )
+    .ok()?;
+    let trump = contract.trump();
+    let tricks = parse_cardplay(&board.cardplay).ok()?;
+
+    let mut errors = Vec::new();
+    let mut declarer_tricks_won: u8 = 0;
+    let initial_leader = (dd_result.declarer_seat + 1) % 4;
+    let mut current_leader = initial_leader;
+
+    for (trick_idx, (trick_costs, trick_cards)) in
+        dd_result.costs.iter().zip(tricks.iter()).enumerate()
+    {
+        let mut seat = current_leader;
+        let mut cards_in_trick: Vec<(usize, usize)> = Vec::new();
+
+        for (&cost, &card) in trick_costs.iter().zip(trick_cards.iter()) {
+            if let Ok(solver_card) = bridge_card_to_solver(card) {
+                cards_in_trick.push((seat, solver_card));
+            }
+
+            if cost > 0 {
+                let player_is_declarer_side = if dd_result.declarer_is_ns {
+                    seat == NORTH || seat == SOUTH
+                } else {
+                    seat == EAST || seat == WEST
+                };
+                // For dummy's cards, attribute to declarer.
+                let error_seat = if player_is_declarer_side {
+                    dd_result.declarer_seat
+                } else {
+                    seat
+                };
+
+                if let Some(label) = board.seat_to_label.get(&error_seat) {
+                    errors.push(BatchError {
+                        player: label.clone(),
+                        is_declarer_side: player_is_declarer_side,
+                        trick_num: trick_idx + 1,
+                        card_str: format!("{}{}", card.suit.to_char(), card.rank.to_char()),
+                        cost,
+                    });
+                }
+            }
+            seat = (seat + 1) % 4;
+        }
+
+        if cards_in_trick.len() == 4 {
+            let winner = determine_trick_winner(&cards_in_trick, trump, current_leader);
+            let declarer_won = if dd_result.declarer_is_ns {
+                winner == NORTH || winner == SOUTH
+            } else {
+                winner == EAST || winner == WEST
+            };
+            if declarer_won {
+                declarer_tricks_won += 1;
+            }
+            current_leader = winner;
+        }
+    }
+
+    let declarer_label = board
+        .seat_to_label
+        .get(&dd_result.declarer_seat)
+        .cloned()
+        .unwrap_or_else(|| board.declarer.clone());
+    let defender_labels: Vec<String> = (0..4)
+        .filter(|&s| ((s == NORTH || s == SOUTH) != dd_result.declarer_is_ns))
+        .filter_map(|s| board.seat_to_label.get(&s).cloned())
+        .collect();
+
+    Some(BoardReport {
+        board_num: board.board_num,
+        contract: board.contract.clone(),
+        declarer_label,
+        defender_labels,
+        initial_dd: dd_result.initial_dd,
+        final_result: declarer_tricks_won,
+        errors,
+    })
+}
+
+/// Totals for one player across every board they appeared in.
+#[derive(Default, Clone)]
+struct PlayerBatchStats {
+    name: String,
+    boards_as_declarer: u32,
+    declarer_tricks_lost: u32,
+    boards_as_defender: u32,
+    defender_tricks_gifted: u32,
+    biggest_blunder: Option<BlunderRef>,
+    error_count: u32,
+    /// Errors bucketed by cost, same thresholds as
+    /// `dd_analysis::SeverityThresholds::default` (minor, major, blunder) --
+    /// duplicated here rather than reused since that classifier is private
+    /// to `dd_analysis`.
+    severity_counts: [u32; 3],
+}
+
+const SEVERITY_MAJOR: u8 = 2;
+const SEVERITY_BLUNDER: u8 = 3;
+
+/// Index into `PlayerBatchStats::severity_counts` for a given DD cost.
+fn severity_bucket(cost: u8) -> usize {
+    if cost >= SEVERITY_BLUNDER {
+        2
+    } else if cost >= SEVERITY_MAJOR {
+        1
+    } else {
+        0
+    }
+}
+
+#[derive(Clone)]
+struct BlunderRef {
+    board_num: Option<usize>,
+    trick_num: usize,
+    card: String,
+    cost: u8,
+}
+
+impl PlayerBatchStats {
+    fn new(name: &str) -> Self {
+        PlayerBatchStats {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn boards_played(&self) -> u32 {
+        self.boards_as_declarer + self.boards_as_defender
+    }
+
+    fn average_cost_per_board(&self) -> f64 {
+        let boards = self.boards_played();
+        if boards == 0 {
+            0.0
+        } else {
+            (self.declarer_tricks_lost + self.defender_tricks_gifted) as f64 / boards as f64
+        }
+    }
+
+    /// Errors made per board played, regardless of trick cost -- a rate
+    /// that, unlike `average_cost_per_board`, doesn't let one blunder hide
+    /// many small slips or vice versa.
+    fn error_rate_per_board(&self) -> f64 {
+        let boards = self.boards_played();
+        if boards == 0 {
+            0.0
+        } else {
+            self.error_count as f64 / boards as f64
+        }
+    }
+}
+
+fn accumulate(stats: &mut HashMap<String, PlayerBatchStats>, report: &BoardReport) {
+    stats
+        .entry(report.declarer_label.clone())
+        .or_insert_with(|| PlayerBatchStats::new(&report.declarer_label))
+        .boards_as_declarer += 1;
+
+    for label in &report.defender_labels {
+        stats
+            .entry(label.clone())
+            .or_insert_with(|| PlayerBatchStats::new(label))
+            .boards_as_defender += 1;
+    }
+
+    for error in &report.errors {
+        let entry = stats
+            .entry(error.player.clone())
+            .or_insert_with(|| PlayerBatchStats::new(&error.player));
+
+        if error.is_declarer_side {
+            entry.declarer_tricks_lost += error.cost as u32;
+        } else {
+            entry.defender_tricks_gifted += error.cost as u32;
+        }
+        entry.error_count += 1;
+        entry.severity_counts[severity_bucket(error.cost)] += 1;
+
+        let candidate = BlunderRef {
+            board_num: report.board_num,
+            trick_num: error.trick_num,
+            card: error.card_str.clone(),
+            cost: error.cost,
+        };
+        let replace = match &entry.biggest_blunder {
+            Some(existing) => candidate.cost > existing.cost,
+            None => true,
+        };
+        if replace {
+            entry.biggest_blunder = Some(candidate);
+        }
+    }
+}
+
+fn print_summary(
+    board_count: &usize,
+    reports: &[BoardReport],
+    player_stats: &HashMap<String, PlayerBatchStats>,
+    rank: bool,
+) {
+    println!(
+        "Analyzed {} of {} boards",
+        reports.len(),
+        board_count
+    );
+    println!();
+
+    for report in reports {
+        let board_label = report
+            .board_num
+            .map(|n| format!("Board {}", n))
+            .unwrap_or_else(|| "Board ?".to_string());
+        println!(
+            "{}: {} by {} -- DD {}, made {}, {} error(s)",
+            board_label,
+            report.contract,
+            report.declarer_label,
+            report.initial_dd,
+            report.final_result,
+            report.errors.len()
+        );
+    }
+    println!();
+
+    println!(
+        "{:<20} {:>9} {:>14} {:>9} {:>14} {:>10} {:>9} {:>16} {:>10}",
+        "Player", "Declarer", "Tricks Lost", "Defender", "Tricks Given", "Avg Cost", "Err/Bd",
+        "Minor/Major/Blndr", "Blunder"
+    );
+    let mut names: Vec<&String> = player_stats.keys().collect();
+    if rank {
+        names.sort_by(|a, b| {
+            player_stats[*b]
+                .error_rate_per_board()
+                .partial_cmp(&player_stats[*a].error_rate_per_board())
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.cmp(b))
+        });
+    } else {
+        names.sort();
+    }
+    for name in names {
+        let s = &player_stats[name];
+        let blunder = s
+            .biggest_blunder
+            .as_ref()
+            .map(|b| format!("{} (T{})", b.card, b.trick_num))
+            .unwrap_or_else(|| "-".to_string());
+        let histogram = format!(
+            "{}/{}/{}",
+            s.severity_counts[0], s.severity_counts[1], s.severity_counts[2]
+        );
+        println!(
+            "{:<20} {:>9} {:>14} {:>9} {:>14} {:>10.2} {:>9.2} {:>16} {:>10}",
+            s.name,
+            s.boards_as_declarer,
+            s.declarer_tricks_lost,
+            s.boards_as_defender,
+            s.defender_tricks_gifted,
+            s.average_cost_per_board(),
+            s.error_rate_per_board(),
+            histogram,
+            blunder
+        );
+    }
+}
+
+fn render_json(reports: &[BoardReport], player_stats: &HashMap<String, PlayerBatchStats>) -> String {
+    let mut out = String::from("{\n  \"boards\": [\n");
+    for (i, report) in reports.iter().enumerate() {
+        out.push_str("    {\n");
+        out.push_str(&format!(
+            "      \"board_num\": {},\n",
+            report
+                .board_num
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| "null".to_string())
+        ));
+        out.push_str(&format!(
+            "      \"contract\": \"{}\",\n",
+            json_escape(&report.contract)
+        ));
+        out.push_str(&format!(
+            "      \"declarer\": \"{}\",\n",
+            json_escape(&report.declarer_label)
+        ));
+        out.push_str(&format!("      \"initial_dd\": {},\n", report.initial_dd));
+        out.push_str(&format!("      \"final_result\": {},\n", report.final_result));
+        out.push_str("      \"errors\": [\n");
+        for (j, error) in report.errors.iter().enumerate() {
+            out.push_str("        {\n");
+            out.push_str(&format!(
+                "          \"player\": \"{}\",\n",
+                json_escape(&error.player)
+            ));
+            out.push_str(&format!(
+                "          \"role\": \"{}\",\n",
+                if error.is_declarer_side { "declarer" } else { "defender" }
+            ));
+            out.push_str(&format!("          \"trick_num\": {},\n", error.trick_num));
+            out.push_str(&format!(
+                "          \"card\": \"{}\",\n",
+                json_escape(&error.card_str)
+            ));
+            out.push_str(&format!("          \"cost\": {}\n", error.cost));
+            out.push_str(if j + 1 == report.errors.len() {
+                "        }\n"
+            } else {
+                "        },\n"
+            });
+        }
+        out.push_str("      ]\n");
+        out.push_str(if i + 1 == reports.len() { "    }\n" } else { "    },\n" });
+    }
+    out.push_str("  ],\n");
+
+    out.push_str("  \"players\": [\n");
+    let mut names: Vec<&String> = player_stats.keys().collect();
+    names.sort();
+    for (i, name) in names.iter().enumerate() {
+        let s = &player_stats[*name];
+        out.push_str("    {\n");
+        out.push_str(&format!("      \"name\": \"{}\",\n", json_escape(&s.name)));
+        out.push_str(&format!("      \"boards_as_declarer\": {},\n", s.boards_as_declarer));
+        out.push_str(&format!("      \"declarer_tricks_lost\": {},\n", s.declarer_tricks_lost));
+        out.push_str(&format!("      \"boards_as_defender\": {},\n", s.boards_as_defender));
+        out.push_str(&format!(
+            "      \"defender_tricks_gifted\": {},\n",
+            s.defender_tricks_gifted
+        ));
+        out.push_str(&format!(
+            "      \"average_cost_per_board\": {:.3},\n",
+            s.average_cost_per_board()
+        ));
+        out.push_str(&format!(
+            "      \"error_rate_per_board\": {:.3},\n",
+            s.error_rate_per_board()
+        ));
+        out.push_str(&format!(
+            "      \"severity_histogram\": {{\"minor\": {}, \"major\": {}, \"blunder\": {}}},\n",
+            s.severity_counts[0], s.severity_counts[1], s.severity_counts[2]
+        ));
+        match &s.biggest_blunder {
+            Some(b) => {
+                out.push_str("      \"biggest_blunder\": {\n");
+                out.push_str(&format!(
+                    "        \"board_num\": {},\n",
+                    b.board_num.map(|n| n.to_string()).unwrap_or_else(|| "null".to_string())
+                ));
+                out.push_str(&format!("        \"trick_num\": {},\n", b.trick_num));
+                out.push_str(&format!("        \"card\": \"{}\",\n", json_escape(&b.card)));
+                out.push_str(&format!("        \"cost\": {}\n", b.cost));
+                out.push_str("      }\n");
+            }
+            None => out.push_str("      \"biggest_blunder\": null\n"),
+        }
+        out.push_str(if i + 1 == names.len() { "    }\n" } else { "    },\n" });
+    }
+    out.push_str("  ]\n}");
+
+    out
+}
+
+fn json_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// Helper functions, duplicated in the same shape as dd_analysis.rs's
+// private helpers since the library only exposes the raw-string-based
+// compute_dd_costs, not these parsing internals.
+
+fn extract_contract(lin_data: &bridge_parsers::lin::LinData) -> String {
+    let mut level = 0u8;
+    let mut suit = String::new();
+    let mut doubled = false;
+    let mut redoubled = false;
+
+    for bid in &lin_data.auction {
+        let bid_str = bid.bid.to_uppercase();
+        if bid_str == "P" || bid_str == "PASS" {
+            continue;
+        } else if bid_str == "D" || bid_str == "X" || bid_str == "DBL" {
+            doubled = true;
+            redoubled = false;
+        } else if bid_str == "R" || bid_str == "XX" || bid_str == "RDBL" {
+            redoubled = true;
+        } else if let Some(c) = bid_str.chars().next() {
+            if c.is_ascii_digit() {
+                level = c.to_digit(10).unwrap_or(0) as u8;
+                suit = bid_str[1..].to_string();
+                doubled = false;
+                redoubled = false;
+            }
+        }
+    }
+
+    if level == 0 {
+        return "Passed Out".to_string();
+    }
+
+    let mut contract = format!("{}{}", level, suit);
+    if redoubled {
+        contract.push_str("XX");
+    } else if doubled {
+        contract.push_str("X");
+    }
+    contract
+}
+
+fn extract_declarer(lin_data: &bridge_parsers::lin::LinData) -> String {
+    if !lin_data.play.is_empty() {
+        let opening_lead = &lin_data.play[0];
+        for dir in bridge_parsers::Direction::ALL {
+            let hand = lin_data.deal.hand(dir);
+            if hand.has_card(*opening_lead) {
+                return match dir {
+                    bridge_parsers::Direction::North => "West".to_string(),
+                    bridge_parsers::Direction::East => "North".to_string(),
+                    bridge_parsers::Direction::South => "East".to_string(),
+                    bridge_parsers::Direction::West => "South".to_string(),
+                };
+            }
+        }
+    }
+    "Unknown".to_string()
+}
+
+fn parse_trump(contract: &str) -> Result<usize, String> {
+    let contract = contract.trim().to_uppercase();
+    if contract.contains("NT") || (contract.contains('N') && !contract.contains('S')) {
+        return Ok(NOTRUMP);
+    }
+    for c in contract.chars() {
+        match c {
+            'S' => return Ok(SPADE),
+            'H' => return Ok(HEART),
+            'D' => return Ok(DIAMOND),
+            'C' => return Ok(CLUB),
+            _ => continue,
+        }
+    }
+    Err(format!("Could not parse trump from: {}", contract))
+}
+
+fn parse_cardplay(cardplay: &str) -> Result<Vec<Vec<Card>>, String> {
+    let mut tricks = Vec::new();
+    for trick_str in cardplay.split('|') {
+        if trick_str.is_empty() {
+            continue;
+        }
+        let mut trick = Vec::new();
+        for card_str in trick_str.split_whitespace() {
+            trick.push(parse_card_str(card_str)?);
+        }
+        if !trick.is_empty() {
+            tricks.push(trick);
+        }
+    }
+    Ok(tricks)
+}
+
+fn parse_card_str(s: &str) -> Result<Card, String> {
+    let s = s.trim();
+    if s.len() < 2 {
+        return Err(format!("Invalid card: {}", s));
+    }
+    let mut chars = s.chars();
+    let suit_char = chars.next().unwrap();
+    let rank_char = chars.next().unwrap();
+
+    let suit = match suit_char.to_ascii_uppercase() {
+        'S' => Suit::Spades,
+        'H' => Suit::Hearts,
+        'D' => Suit::Diamonds,
+        'C' => Suit::Clubs,
+        _ => return Err(format!("Invalid suit: {}", suit_char)),
+    };
+
+    let rank = Rank::from_char(rank_char).ok_or_else(|| format!("Invalid rank: {}", rank_char))?;
+
+    Ok(Card::new(suit, rank))
+}
+
+fn bridge_card_to_solver(card: Card) -> Result<usize, String> {
+    let suit = match card.suit {
+        Suit::Spades => SPADE,
+        Suit::Hearts => HEART,
+        Suit::Diamonds => DIAMOND,
+        Suit::Clubs => CLUB,
+    };
+
+    let rank = match card.rank {
+        Rank::Ace => 12,
+        Rank::King => 11,
+        Rank::Queen => 10,
+        Rank::Jack => 9,
+        Rank::Ten => 8,
+        Rank::Nine => 7,
+        Rank::Eight => 6,
+        Rank::Seven => 5,
+        Rank::Six => 4,
+        Rank::Five => 3,
+        Rank::Four => 2,
+        Rank::Three => 1,
+        Rank::Two => 0,
+    };
+
+    Ok(card_of(suit, rank))
+}
+
+fn determine_trick_winner(cards: &[(usize, usize)], trump: usize, leader: usize) -> usize {
+    let mut winner_idx = 0;
+    let mut winning_card = cards[0].1;
+
+    for (i, (_seat, card)) in cards.iter().enumerate().skip(1) {
+        let card_suit = bridge_solver::cards::suit_of(*card);
+        let beats = if card_suit == bridge_solver::cards::suit_of(winning_card) {
+            *card < winning_card
+        } else if card_suit == trump && trump < NOTRUMP {
+            bridge_solver::cards::suit_of(winning_card) != trump
+        } else {
+            false
+        };
+
+        if beats {
+            winner_idx = i;
+            winning_card = *card;
+        }
+    }
+
+    (leader + winner_idx) % 4
+}
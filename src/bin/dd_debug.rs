@@ -10,36 +10,71 @@
 
 use anyhow::{Context, Result};
 use bridge_parsers::lin::parse_lin_from_url;
-use bridge_parsers::{Card, Direction, Rank, Suit};
+use bridge_parsers::{Card, Direction, Rank, Suit, Vulnerability};
 use bridge_parsers::tinyurl::UrlResolver;
 use bridge_solver::cards::{card_of, suit_of};
 use bridge_solver::{CutoffCache, Hands, PartialTrick, PatternCache, Solver};
 use bridge_solver::{CLUB, DIAMOND, EAST, HEART, NOTRUMP, NORTH, SOUTH, SPADE, WEST};
+use edgar_defense_toolkit::dd_table::{compute_dd_table_and_par, format_dd_table, format_par_result};
+use edgar_defense_toolkit::play_state::{PlayState, Side};
+use edgar_defense_toolkit::scoring::{points_to_imps, score_contract, Doubled};
+use edgar_defense_toolkit::single_dummy::{compute_single_dummy_costs, SingleDummyConfig};
 
 fn main() -> Result<()> {
     let args: Vec<String> = std::env::args().collect();
 
     // Parse args
     let mut mid_trick_mode = false;
+    let mut par_mode = false;
+    let mut suggest_mode = false;
+    let mut single_dummy_mode = false;
+    let mut samples: usize = 50;
     let mut url_arg = None;
 
-    for arg in &args[1..] {
+    let mut i = 1;
+    while i < args.len() {
+        let arg = &args[i];
         if arg == "--mid-trick" {
             mid_trick_mode = true;
+        } else if arg == "--par" {
+            par_mode = true;
+        } else if arg == "--suggest" {
+            suggest_mode = true;
+        } else if arg == "--single-dummy" {
+            single_dummy_mode = true;
+        } else if arg == "--samples" {
+            i += 1;
+            samples = args
+                .get(i)
+                .and_then(|s| s.parse().ok())
+                .context("--samples requires a numeric argument")?;
         } else if !arg.starts_with('-') {
             url_arg = Some(arg.clone());
         }
+        i += 1;
+    }
+
+    // Suggesting the best card only makes sense per-card, so it implies --mid-trick.
+    if suggest_mode {
+        mid_trick_mode = true;
     }
 
     let url = match url_arg {
         Some(u) => u,
         None => {
-            eprintln!("Usage: {} [--mid-trick] <tinyurl>", args[0]);
+            eprintln!(
+                "Usage: {} [--mid-trick] [--par] [--suggest] [--single-dummy] [--samples N] <tinyurl>",
+                args[0]
+            );
             eprintln!("Example: {} http://tinyurl.com/27g7hbuc", args[0]);
             eprintln!("");
             eprintln!("Options:");
             eprintln!("  --mid-trick    Compute DD after every card (slower, may differ from BBO)");
             eprintln!("                 Default: compute DD at trick boundaries only");
+            eprintln!("  --par          Print the full double-dummy table and par result first");
+            eprintln!("  --suggest      Show the best available card at every decision point (implies --mid-trick)");
+            eprintln!("  --single-dummy Also report restricted-information (single-dummy) trick costs");
+            eprintln!("  --samples N    Redeals averaged per single-dummy decision point (default 50)");
             std::process::exit(1);
         }
     };
@@ -81,9 +116,12 @@ fn main() -> Result<()> {
 
     // Parse trump and declarer seat
     let trump = parse_trump(&contract)?;
+    let level = parse_level(&contract)?;
+    let doubled = parse_doubled(&contract);
     let declarer_seat = parse_declarer_seat(&declarer)?;
     let initial_leader = (declarer_seat + 1) % 4;
     let declarer_is_ns = declarer_seat == NORTH || declarer_seat == SOUTH;
+    let declarer_vulnerable = declarer_side_vulnerable(&lin_data.vulnerability, declarer_is_ns);
 
     println!(
         "Trump: {}",
@@ -110,6 +148,13 @@ fn main() -> Result<()> {
     let pbn = lin_data.deal.to_pbn(Direction::North);
     let hands = Hands::from_pbn(&pbn).context("Failed to parse deal for solver")?;
 
+    if par_mode {
+        println!("\n=== Double-Dummy Table ===");
+        let (table, par) = compute_dd_table_and_par(&hands, &lin_data.vulnerability);
+        println!("{}", format_dd_table(&table));
+        println!("{}", format_par_result(&par));
+    }
+
     // Create caches for solver (reuse across all solves)
     let mut cutoff_cache = CutoffCache::new(16);
     let mut pattern_cache = PatternCache::new(16);
@@ -133,37 +178,50 @@ fn main() -> Result<()> {
     } else {
         println!("\n=== DD Analysis at Trick Boundaries ===");
     }
-    println!(
-        "{:^6} | {:^4} | {:^6} | {:^6} | {:^10} | {:^10} | {:^6}",
-        "Trick", "Card", "Player", "Played", "DD Before", "DD After", "Cost"
-    );
-    println!("{}", "-".repeat(72));
+    if suggest_mode {
+        println!(
+            "{:^6} | {:^4} | {:^6} | {:^6} | {:^10} | {:^10} | {:^6} | {:^4} | {:^10}",
+            "Trick", "Card", "Player", "Played", "DD Before", "DD After", "Cost", "IMP", "Best"
+        );
+        println!("{}", "-".repeat(95));
+    } else {
+        println!(
+            "{:^6} | {:^4} | {:^6} | {:^6} | {:^10} | {:^10} | {:^6} | {:^4}",
+            "Trick", "Card", "Player", "Played", "DD Before", "DD After", "Cost", "IMP"
+        );
+        println!("{}", "-".repeat(82));
+    }
 
-    let mut current_hands = hands;
-    let mut current_leader = initial_leader;
     let tricks = parse_cardplay(&cardplay)?;
-    let mut declarer_tricks_won: u8 = 0;
+    let mut state = PlayState::new(&pbn, trump, initial_leader).map_err(|e| anyhow::anyhow!(e))?;
 
     if mid_trick_mode {
         // Mid-trick mode: compute DD before and after every card
         for (trick_num, trick) in tricks.iter().enumerate() {
-            let mut seat = current_leader;
-            let mut partial_trick = PartialTrick::new();
-            let mut cards_in_trick: Vec<(usize, usize)> = Vec::new();
-
             for (card_idx, card) in trick.iter().enumerate() {
-                let solver_card = bridge_card_to_solver(*card)?;
+                let seat = state.to_play();
+                let declarer_tricks_won = if declarer_is_ns {
+                    state.tricks_won(Side::NorthSouth)
+                } else {
+                    state.tricks_won(Side::EastWest)
+                };
+
+                let player_is_declarer_side = if declarer_is_ns {
+                    seat == NORTH || seat == SOUTH
+                } else {
+                    seat == EAST || seat == WEST
+                };
 
                 // Compute DD BEFORE this card is played
-                let dd_before = if partial_trick.is_empty() {
-                    let ns = solve_position(&current_hands, trump, current_leader, &mut cutoff_cache, &mut pattern_cache);
+                let dd_before = if state.partial_trick().is_empty() {
+                    let ns = solve_position(state.hands(), trump, state.leader(), &mut cutoff_cache, &mut pattern_cache);
                     if declarer_is_ns {
                         declarer_tricks_won + ns
                     } else {
-                        declarer_tricks_won + (current_hands.num_tricks() as u8).saturating_sub(ns)
+                        declarer_tricks_won + (state.hands().num_tricks() as u8).saturating_sub(ns)
                     }
                 } else {
-                    let (ns, remaining) = solve_mid_trick(&current_hands, trump, &partial_trick, &mut cutoff_cache, &mut pattern_cache);
+                    let (ns, remaining) = solve_mid_trick(state.hands(), trump, state.partial_trick(), &mut cutoff_cache, &mut pattern_cache);
                     if declarer_is_ns {
                         declarer_tricks_won + ns
                     } else {
@@ -171,14 +229,54 @@ fn main() -> Result<()> {
                     }
                 };
 
+                // Enumerate legal alternatives and find the best one, before the actual card is played
+                let best_cards = if suggest_mode {
+                    let legal = state.legal_plays(seat);
+                    let mut best_value: Option<u8> = None;
+                    let mut best: Vec<Card> = Vec::new();
+                    for candidate in legal {
+                        let candidate_solver_card = bridge_card_to_solver(candidate)?;
+                        let value = tricks_after_play(
+                            state.hands(),
+                            trump,
+                            state.leader(),
+                            state.cards_in_trick(),
+                            seat,
+                            candidate_solver_card,
+                            card_idx,
+                            declarer_tricks_won,
+                            declarer_is_ns,
+                            &mut cutoff_cache,
+                            &mut pattern_cache,
+                        );
+                        let better = match best_value {
+                            None => true,
+                            Some(bv) => {
+                                if player_is_declarer_side {
+                                    value > bv
+                                } else {
+                                    value < bv
+                                }
+                            }
+                        };
+                        if better {
+                            best_value = Some(value);
+                            best = vec![candidate];
+                        } else if Some(value) == best_value {
+                            best.push(candidate);
+                        }
+                    }
+                    best
+                } else {
+                    Vec::new()
+                };
+
                 // Play the card
-                current_hands[seat].remove(solver_card);
-                partial_trick.add(solver_card, seat);
-                cards_in_trick.push((seat, solver_card));
+                state.play(*card).map_err(|e| anyhow::anyhow!(e))?;
 
                 // Compute DD AFTER this card is played
                 let dd_after = if card_idx == 3 {
-                    let winner = determine_trick_winner(&cards_in_trick, trump, current_leader);
+                    let winner = determine_trick_winner(state.cards_in_trick(), trump, state.leader());
                     let declarer_won = if declarer_is_ns {
                         winner == NORTH || winner == SOUTH
                     } else {
@@ -186,19 +284,19 @@ fn main() -> Result<()> {
                     };
                     let tricks_from_this = if declarer_won { 1u8 } else { 0u8 };
 
-                    if current_hands.num_tricks() == 0 {
+                    if state.hands().num_tricks() == 0 {
                         declarer_tricks_won + tricks_from_this
                     } else {
-                        let ns = solve_position(&current_hands, trump, winner, &mut cutoff_cache, &mut pattern_cache);
+                        let ns = solve_position(state.hands(), trump, winner, &mut cutoff_cache, &mut pattern_cache);
                         if declarer_is_ns {
                             declarer_tricks_won + tricks_from_this + ns
                         } else {
-                            let remaining = current_hands.num_tricks() as u8;
+                            let remaining = state.hands().num_tricks() as u8;
                             declarer_tricks_won + tricks_from_this + remaining.saturating_sub(ns)
                         }
                     }
                 } else {
-                    let (ns, remaining) = solve_mid_trick(&current_hands, trump, &partial_trick, &mut cutoff_cache, &mut pattern_cache);
+                    let (ns, remaining) = solve_mid_trick(state.hands(), trump, state.partial_trick(), &mut cutoff_cache, &mut pattern_cache);
                     if declarer_is_ns {
                         declarer_tricks_won + ns
                     } else {
@@ -207,67 +305,77 @@ fn main() -> Result<()> {
                 };
 
                 // Cost calculation
-                let player_is_declarer_side = if declarer_is_ns {
-                    seat == NORTH || seat == SOUTH
-                } else {
-                    seat == EAST || seat == WEST
-                };
-
+                let points_before = score_contract(level, trump, doubled, dd_before, declarer_vulnerable);
+                let points_after = score_contract(level, trump, doubled, dd_after, declarer_vulnerable);
                 let cost = if player_is_declarer_side {
-                    if dd_after < dd_before { dd_before - dd_after } else { 0 }
+                    if points_after < points_before { points_before - points_after } else { 0 }
                 } else {
-                    if dd_after > dd_before { dd_after - dd_before } else { 0 }
+                    if points_after > points_before { points_after - points_before } else { 0 }
                 };
+                let cost_imps = points_to_imps(cost);
 
                 let card_str = format!("{}{}", card.suit.to_char(), card.rank.to_char());
                 let position = match card_idx {
                     0 => "Lead", 1 => "2nd", 2 => "3rd", 3 => "4th", _ => "?",
                 };
 
-                println!(
-                    "{:^6} | {:^4} | {:^6} | {:^6} | {:^10} | {:^10} | {:^6}",
-                    if card_idx == 0 { format!("{}", trick_num + 1) } else { "".to_string() },
-                    position, seat_name(seat), card_str, dd_before, dd_after,
-                    if cost > 0 { format!("{}", cost) } else { "-".to_string() }
-                );
-
-                seat = (seat + 1) % 4;
-            }
-
-            // Update state after trick
-            if cards_in_trick.len() == 4 {
-                let winner = determine_trick_winner(&cards_in_trick, trump, current_leader);
-                let declarer_won = if declarer_is_ns {
-                    winner == NORTH || winner == SOUTH
+                if suggest_mode {
+                    let suggestion = if best_cards.iter().any(|c| c.suit == card.suit && c.rank == card.rank) {
+                        "-".to_string()
+                    } else {
+                        best_cards
+                            .iter()
+                            .map(|c| format!("{}{}", c.suit.to_char(), c.rank.to_char()))
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    };
+                    println!(
+                        "{:^6} | {:^4} | {:^6} | {:^6} | {:^10} | {:^10} | {:^6} | {:^4} | {:^10}",
+                        if card_idx == 0 { format!("{}", trick_num + 1) } else { "".to_string() },
+                        position, seat_name(seat), card_str, dd_before, dd_after,
+                        if cost > 0 { format!("{}", cost) } else { "-".to_string() },
+                        if cost_imps > 0 { format!("{}", cost_imps) } else { "-".to_string() },
+                        suggestion
+                    );
                 } else {
-                    winner == EAST || winner == WEST
-                };
-                if declarer_won { declarer_tricks_won += 1; }
-                current_leader = winner;
-                println!("{}", "-".repeat(72));
+                    println!(
+                        "{:^6} | {:^4} | {:^6} | {:^6} | {:^10} | {:^10} | {:^6} | {:^4}",
+                        if card_idx == 0 { format!("{}", trick_num + 1) } else { "".to_string() },
+                        position, seat_name(seat), card_str, dd_before, dd_after,
+                        if cost > 0 { format!("{}", cost) } else { "-".to_string() },
+                        if cost_imps > 0 { format!("{}", cost_imps) } else { "-".to_string() }
+                    );
+                }
+
+                if card_idx == 3 {
+                    state.trick_complete();
+                    println!("{}", "-".repeat(82));
+                }
             }
         }
     } else {
         // Trick-boundary mode: compute DD only at start and end of each trick
         for (trick_num, trick) in tricks.iter().enumerate() {
-            let mut seat = current_leader;
-            let mut cards_in_trick: Vec<(usize, usize)> = Vec::new();
+            let declarer_tricks_won = if declarer_is_ns {
+                state.tricks_won(Side::NorthSouth)
+            } else {
+                state.tricks_won(Side::EastWest)
+            };
 
             // DD at start of trick (before any card played)
             let dd_start = {
-                let ns = solve_position(&current_hands, trump, current_leader, &mut cutoff_cache, &mut pattern_cache);
+                let ns = solve_position(state.hands(), trump, state.leader(), &mut cutoff_cache, &mut pattern_cache);
                 if declarer_is_ns {
                     declarer_tricks_won + ns
                 } else {
-                    declarer_tricks_won + (current_hands.num_tricks() as u8).saturating_sub(ns)
+                    declarer_tricks_won + (state.hands().num_tricks() as u8).saturating_sub(ns)
                 }
             };
 
             // Play all cards in the trick
             for (card_idx, card) in trick.iter().enumerate() {
-                let solver_card = bridge_card_to_solver(*card)?;
-                current_hands[seat].remove(solver_card);
-                cards_in_trick.push((seat, solver_card));
+                let seat = state.to_play();
+                state.play(*card).map_err(|e| anyhow::anyhow!(e))?;
 
                 let card_str = format!("{}{}", card.suit.to_char(), card.rank.to_char());
                 let position = match card_idx {
@@ -277,12 +385,12 @@ fn main() -> Result<()> {
                 // Only show DD values for first and last card of trick
                 if card_idx == 0 {
                     println!(
-                        "{:^6} | {:^4} | {:^6} | {:^6} | {:^10} |            |       ",
+                        "{:^6} | {:^4} | {:^6} | {:^6} | {:^10} |            |       |     ",
                         trick_num + 1, position, seat_name(seat), card_str, dd_start
                     );
                 } else if card_idx == 3 {
                     // Compute DD at end of trick
-                    let winner = determine_trick_winner(&cards_in_trick, trump, current_leader);
+                    let winner = determine_trick_winner(state.cards_in_trick(), trump, state.leader());
                     let declarer_won = if declarer_is_ns {
                         winner == NORTH || winner == SOUTH
                     } else {
@@ -290,49 +398,76 @@ fn main() -> Result<()> {
                     };
                     let tricks_from_this = if declarer_won { 1u8 } else { 0u8 };
 
-                    let dd_end = if current_hands.num_tricks() == 0 {
+                    let dd_end = if state.hands().num_tricks() == 0 {
                         declarer_tricks_won + tricks_from_this
                     } else {
-                        let ns = solve_position(&current_hands, trump, winner, &mut cutoff_cache, &mut pattern_cache);
+                        let ns = solve_position(state.hands(), trump, winner, &mut cutoff_cache, &mut pattern_cache);
                         if declarer_is_ns {
                             declarer_tricks_won + tricks_from_this + ns
                         } else {
-                            let remaining = current_hands.num_tricks() as u8;
+                            let remaining = state.hands().num_tricks() as u8;
                             declarer_tricks_won + tricks_from_this + remaining.saturating_sub(ns)
                         }
                     };
 
-                    // Cost = any change in DD during this trick
-                    let cost = if dd_end < dd_start {
-                        dd_start - dd_end
+                    // Cost = drop in declarer's score during this trick, in points (a rise counts as 0)
+                    let points_start = score_contract(level, trump, doubled, dd_start, declarer_vulnerable);
+                    let points_end = score_contract(level, trump, doubled, dd_end, declarer_vulnerable);
+                    let cost = if points_end < points_start {
+                        points_start - points_end
                     } else {
                         0
                     };
+                    let cost_imps = points_to_imps(cost);
 
                     println!(
-                        "{:^6} | {:^4} | {:^6} | {:^6} |            | {:^10} | {:^6}",
+                        "{:^6} | {:^4} | {:^6} | {:^6} |            | {:^10} | {:^6} | {:^4}",
                         "", position, seat_name(seat), card_str, dd_end,
-                        if cost > 0 { format!("{}", cost) } else { "-".to_string() }
+                        if cost > 0 { format!("{}", cost) } else { "-".to_string() },
+                        if cost_imps > 0 { format!("{}", cost_imps) } else { "-".to_string() }
                     );
 
-                    // Update state
-                    if declarer_won { declarer_tricks_won += 1; }
-                    current_leader = winner;
+                    state.trick_complete();
                 } else {
                     println!(
-                        "{:^6} | {:^4} | {:^6} | {:^6} |            |            |       ",
+                        "{:^6} | {:^4} | {:^6} | {:^6} |            |            |       |     ",
                         "", position, seat_name(seat), card_str
                     );
                 }
-
-                seat = (seat + 1) % 4;
             }
 
-            println!("{}", "-".repeat(72));
+            println!("{}", "-".repeat(82));
         }
     }
 
-    println!("Final result: Declarer made {} tricks", declarer_tricks_won);
+    let final_declarer_tricks_won = if declarer_is_ns {
+        state.tricks_won(Side::NorthSouth)
+    } else {
+        state.tricks_won(Side::EastWest)
+    };
+    println!("Final result: Declarer made {} tricks", final_declarer_tricks_won);
+
+    if single_dummy_mode {
+        println!("\n=== Single-Dummy Analysis ({} samples/trick) ===", samples);
+        let config = SingleDummyConfig { samples };
+        match compute_single_dummy_costs(&pbn, &cardplay, &contract, &declarer, &config) {
+            Ok(result) => {
+                println!(
+                    "Expected declarer tricks at opening lead: {:.2}",
+                    result.initial_dd
+                );
+                println!("{}", "-".repeat(40));
+                println!("{:^6} | {:^22}", "Trick", "Expected-tricks swing");
+                println!("{}", "-".repeat(40));
+                for (i, cost) in result.costs.iter().enumerate() {
+                    println!("{:^6} | {:^22.2}", i + 1, cost);
+                }
+            }
+            Err(e) => {
+                eprintln!("Single-dummy analysis failed: {}", e);
+            }
+        }
+    }
 
     // Print BBO link for verification
     println!("\n=== Verification Link ===");
@@ -394,6 +529,60 @@ fn solve_mid_trick(
     }
 }
 
+/// Evaluate declarer's trick value if `candidate` were played by `seat` at
+/// this point in the trick, without mutating the real walk-through state.
+/// Used by `--suggest` to compare the played card against its alternatives.
+fn tricks_after_play(
+    hands: &Hands,
+    trump: usize,
+    leader: usize,
+    cards_in_trick: &[(usize, usize)],
+    seat: usize,
+    candidate: usize,
+    card_idx: usize,
+    declarer_tricks_won: u8,
+    declarer_is_ns: bool,
+    cutoff_cache: &mut CutoffCache,
+    pattern_cache: &mut PatternCache,
+) -> u8 {
+    let mut hyp_hands = *hands;
+    hyp_hands[seat].remove(candidate);
+    let mut hyp_cards = cards_in_trick.to_vec();
+    hyp_cards.push((seat, candidate));
+
+    if card_idx == 3 {
+        let winner = determine_trick_winner(&hyp_cards, trump, leader);
+        let declarer_won = if declarer_is_ns {
+            winner == NORTH || winner == SOUTH
+        } else {
+            winner == EAST || winner == WEST
+        };
+        let tricks_from_this = if declarer_won { 1u8 } else { 0u8 };
+        if hyp_hands.num_tricks() == 0 {
+            declarer_tricks_won + tricks_from_this
+        } else {
+            let ns = solve_position(&hyp_hands, trump, winner, cutoff_cache, pattern_cache);
+            if declarer_is_ns {
+                declarer_tricks_won + tricks_from_this + ns
+            } else {
+                let remaining = hyp_hands.num_tricks() as u8;
+                declarer_tricks_won + tricks_from_this + remaining.saturating_sub(ns)
+            }
+        }
+    } else {
+        let mut hyp_partial = PartialTrick::new();
+        for (s, c) in &hyp_cards {
+            hyp_partial.add(*c, *s);
+        }
+        let (ns, remaining) = solve_mid_trick(&hyp_hands, trump, &hyp_partial, cutoff_cache, pattern_cache);
+        if declarer_is_ns {
+            declarer_tricks_won + ns
+        } else {
+            declarer_tricks_won + remaining.saturating_sub(ns)
+        }
+    }
+}
+
 fn extract_contract(lin_data: &bridge_parsers::lin::LinData) -> String {
     let mut level = 0u8;
     let mut suit = String::new();
@@ -467,6 +656,36 @@ fn parse_trump(contract: &str) -> Result<usize> {
     Err(anyhow::anyhow!("Could not parse trump from: {}", contract))
 }
 
+fn parse_level(contract: &str) -> Result<u8> {
+    contract
+        .trim()
+        .chars()
+        .next()
+        .and_then(|c| c.to_digit(10))
+        .map(|d| d as u8)
+        .ok_or_else(|| anyhow::anyhow!("Could not parse level from: {}", contract))
+}
+
+fn parse_doubled(contract: &str) -> Doubled {
+    let contract = contract.trim().to_uppercase();
+    if contract.ends_with("XX") {
+        Doubled::Redoubled
+    } else if contract.ends_with('X') {
+        Doubled::Doubled
+    } else {
+        Doubled::Undoubled
+    }
+}
+
+fn declarer_side_vulnerable(vulnerability: &Vulnerability, declarer_is_ns: bool) -> bool {
+    match vulnerability {
+        Vulnerability::Both => true,
+        Vulnerability::NorthSouth => declarer_is_ns,
+        Vulnerability::EastWest => !declarer_is_ns,
+        Vulnerability::None => false,
+    }
+}
+
 fn parse_declarer_seat(declarer: &str) -> Result<usize> {
     match declarer.trim().to_uppercase().chars().next() {
         Some('N') => Ok(NORTH),
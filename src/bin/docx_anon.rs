@@ -6,10 +6,13 @@
 //!   docx-anon --docx input.docx --lookup lookup.csv --anon anon.csv \
 //!     [--extra-map extra.csv] [--text-map map.txt] -o output.docx
 
-use ab_glyph::FontVec;
 use anyhow::{Context, Result};
 use clap::Parser;
 use edgar_defense_toolkit::anon_common::*;
+use quick_xml::escape::escape;
+use quick_xml::events::Event;
+use quick_xml::reader::Reader;
+use rayon::prelude::*;
 use regex::Regex;
 use std::collections::HashMap;
 use std::io::{Cursor, Read, Write};
@@ -73,6 +76,88 @@ struct Cli {
     /// Replacement text for redacted paragraphs (shown in first redacted paragraph)
     #[arg(long)]
     redact_replacement: Option<String>,
+
+    /// Comma-separated w:pStyle ids to redact regardless of text (e.g. "Heading2,BodyPII")
+    #[arg(long)]
+    redact_style: Option<String>,
+
+    /// Comma-separated w:pStyle ids to always preserve, redacting every other paragraph
+    /// (e.g. "TOC1,TOC2"). Mutually exclusive with --redact-style.
+    #[arg(long)]
+    keep_style: Option<String>,
+
+    /// Dry run: perform every detection pass but write no DOCX. Instead
+    /// writes a full plaintext extraction to "<output>.txt" and a change
+    /// manifest (URL remaps, text-replacement counts, redacted paragraph
+    /// ranges, which candidate images actually qualify as BBO screenshots
+    /// under the ≥1000×1000 rule, and which are slated for blanking/CC-name
+    /// redaction) to "<output>.manifest.txt" so an operator can audit the
+    /// result before trusting it. Also available as `--dry-run`.
+    #[arg(long, alias = "dry-run")]
+    report: bool,
+
+    /// Write a structured JSON audit manifest of every redaction actually
+    /// performed in this run (URL mappings applied, per-part replacement
+    /// counts, redacted paragraph ranges, every image modified/blanked/
+    /// CC-redacted) to this path, alongside the output DOCX.
+    #[arg(long)]
+    audit_json: Option<PathBuf>,
+}
+
+/// One URL mapping applied during the run, for `--audit-json`.
+#[derive(serde::Serialize)]
+struct UrlMappingRecord {
+    original: String,
+    anonymized: String,
+}
+
+/// A per-part replacement count, for `--audit-json`.
+#[derive(serde::Serialize)]
+struct PartCount {
+    part: String,
+    count: u32,
+}
+
+/// A redacted paragraph's byte range within its part, for `--audit-json`.
+#[derive(serde::Serialize)]
+struct RedactedRange {
+    part: String,
+    start: usize,
+    end: usize,
+}
+
+/// One BBO screenshot anonymized in place, for `--audit-json`.
+#[derive(serde::Serialize)]
+struct ImageModifiedRecord {
+    media_path: String,
+    width: u32,
+    height: u32,
+    north: String,
+    south: String,
+    east: String,
+    west: String,
+}
+
+/// Convention-card name redaction performed, for `--audit-json`.
+#[derive(serde::Serialize)]
+struct CcRedactionRecord {
+    media_path: String,
+    replacement: String,
+}
+
+/// Everything `run()` actually did this invocation, built up incrementally
+/// as each step executes and serialized at the end. A real-run counterpart
+/// to `write_report`'s dry-run manifest: this records what *was* changed,
+/// not what *would be*.
+#[derive(Default, serde::Serialize)]
+struct AuditManifest {
+    url_mappings: Vec<UrlMappingRecord>,
+    hyperlink_replacements: Vec<PartCount>,
+    text_replacements: Vec<PartCount>,
+    redacted_paragraphs: Vec<RedactedRange>,
+    images_modified: Vec<ImageModifiedRecord>,
+    images_blanked: Vec<String>,
+    cc_redaction: Option<CcRedactionRecord>,
 }
 
 // ─── DOCX zip I/O ───────────────────────────────────────────────────────────
@@ -153,9 +238,6 @@ fn replace_document_text(xml: &str, replacements: &[(String, String)]) -> (Strin
     let mut result = xml.to_string();
     let mut total = 0usize;
 
-    // Regex for <w:t> content within a paragraph
-    let wt_re = Regex::new(r#"<w:t(?: [^>]*)?>([^<]*)</w:t>"#).expect("invalid regex");
-
     // Find all paragraph byte ranges in the original XML (before any modifications)
     // Using a simple stack-based approach for nested safety
     let paragraphs = find_paragraphs(&result);
@@ -164,18 +246,10 @@ fn replace_document_text(xml: &str, replacements: &[(String, String)]) -> (Strin
     for (p_start, p_end) in paragraphs.into_iter().rev() {
         let para_xml = result[p_start..p_end].to_string();
 
-        // Find all <w:t> elements in this paragraph (offsets relative to paragraph)
-        let wt_matches: Vec<(usize, usize, String)> = wt_re
-            .captures_iter(&para_xml)
-            .map(|caps| {
-                let text_match = caps.get(1).expect("no group 1");
-                (
-                    text_match.start(),
-                    text_match.end(),
-                    text_match.as_str().to_string(),
-                )
-            })
-            .collect();
+        // Find all <w:t> runs in this paragraph with quick-xml instead of a
+        // flat regex, so entities (`&amp;`, `&#8217;`) come back decoded and
+        // match the text an operator actually typed into the --text-map.
+        let wt_matches = find_text_runs(&para_xml);
 
         if wt_matches.is_empty() {
             continue;
@@ -185,8 +259,8 @@ fn replace_document_text(xml: &str, replacements: &[(String, String)]) -> (Strin
         // For each char in virtual text: (wt_index, char_offset_within_wt)
         let mut virtual_text = String::new();
         let mut char_map: Vec<(usize, usize)> = Vec::new();
-        for (wt_idx, (_, _, text)) in wt_matches.iter().enumerate() {
-            for (char_offset, ch) in text.char_indices() {
+        for (wt_idx, run) in wt_matches.iter().enumerate() {
+            for (char_offset, ch) in run.text.char_indices() {
                 char_map.push((wt_idx, char_offset));
                 virtual_text.push(ch);
             }
@@ -233,7 +307,7 @@ fn replace_document_text(xml: &str, replacements: &[(String, String)]) -> (Strin
         }
 
         // Build new text for each <w:t> element by applying modifications
-        let mut new_wt_texts: Vec<String> = wt_matches.iter().map(|(_, _, t)| t.clone()).collect();
+        let mut new_wt_texts: Vec<String> = wt_matches.iter().map(|run| run.text.clone()).collect();
 
         // Apply modifications to new_wt_texts
         // Sort by start position for easier processing
@@ -279,10 +353,14 @@ fn replace_document_text(xml: &str, replacements: &[(String, String)]) -> (Strin
             *text = new_text;
         }
 
-        // Now replace the <w:t> contents in the paragraph XML (reverse order for offset safety)
+        // Now replace each whole <w:t>...</w:t> element in the paragraph XML
+        // (reverse order for offset safety), not just its inner content --
+        // a replacement with leading/trailing whitespace needs
+        // xml:space="preserve" added even if the original run didn't have it.
         let mut new_para = para_xml.clone();
-        for (wt_idx, &(content_start, content_end, _)) in wt_matches.iter().enumerate().rev() {
-            new_para.replace_range(content_start..content_end, &new_wt_texts[wt_idx]);
+        for (wt_idx, run) in wt_matches.iter().enumerate().rev() {
+            let rendered = render_text_run(&new_wt_texts[wt_idx]);
+            new_para.replace_range(run.elem_start..run.elem_end, &rendered);
         }
 
         // Replace the paragraph in the full result
@@ -292,6 +370,87 @@ fn replace_document_text(xml: &str, replacements: &[(String, String)]) -> (Strin
     (result, total)
 }
 
+/// One `<w:t>...</w:t>` (or self-closing `<w:t/>`) run found while walking a
+/// paragraph: its fully decoded text, and the byte range of the whole
+/// element in the paragraph's original XML so it can be rewritten in place.
+struct TextRun {
+    text: String,
+    elem_start: usize,
+    elem_end: usize,
+}
+
+/// Walk a paragraph's XML with a streaming parser and collect every `<w:t>`
+/// run in document order, decoding entities (`&amp;`, `&#8217;`, ...) along
+/// the way.
+///
+/// Replaces the old `<w:t(?: [^>]*)?>([^<]*)</w:t>` regex, which matched
+/// entity text literally instead of decoding it -- a run containing
+/// `"Smith &amp; Jones"` would never match a `--text-map` rule written
+/// against the decoded `"Smith & Jones"`, and a mid-entity split (`&amp` at
+/// a chunk boundary) could corrupt the replacement.
+fn find_text_runs(para_xml: &str) -> Vec<TextRun> {
+    let mut reader = Reader::from_str(para_xml);
+    reader.config_mut().trim_text(false);
+
+    let mut runs = Vec::new();
+    let mut open_start: Option<usize> = None;
+    let mut text = String::new();
+
+    loop {
+        let pos = reader.buffer_position() as usize;
+        match reader.read_event() {
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"t" => {
+                open_start = Some(pos);
+                text.clear();
+            }
+            Ok(Event::Text(t)) if open_start.is_some() => {
+                text.push_str(
+                    &t.unescape()
+                        .map(|s| s.into_owned())
+                        .unwrap_or_else(|_| String::from_utf8_lossy(t.as_ref()).into_owned()),
+                );
+            }
+            Ok(Event::End(e)) if e.local_name().as_ref() == b"t" => {
+                if let Some(elem_start) = open_start.take() {
+                    runs.push(TextRun {
+                        text: std::mem::take(&mut text),
+                        elem_start,
+                        elem_end: reader.buffer_position() as usize,
+                    });
+                }
+            }
+            Ok(Event::Empty(e)) if e.local_name().as_ref() == b"t" => {
+                // Self-closing <w:t/> -- an empty run, still a valid boundary.
+                runs.push(TextRun {
+                    text: String::new(),
+                    elem_start: pos,
+                    elem_end: reader.buffer_position() as usize,
+                });
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+
+    runs
+}
+
+/// Render a `<w:t>` run for `text`, XML-escaping it and marking
+/// `xml:space="preserve"` whenever the text has leading/trailing whitespace
+/// that Word would otherwise collapse.
+fn render_text_run(text: &str) -> String {
+    if text.is_empty() {
+        return "<w:t/>".to_string();
+    }
+    let preserve = text.starts_with(char::is_whitespace) || text.ends_with(char::is_whitespace);
+    if preserve {
+        format!(r#"<w:t xml:space="preserve">{}</w:t>"#, escape(text))
+    } else {
+        format!("<w:t>{}</w:t>", escape(text))
+    }
+}
+
 /// Find all `<w:p ...>...</w:p>` paragraph byte ranges in the XML.
 fn find_paragraphs(xml: &str) -> Vec<(usize, usize)> {
     let p_re = Regex::new(r"<w:p[ >]").expect("invalid regex");
@@ -309,6 +468,49 @@ fn find_paragraphs(xml: &str) -> Vec<(usize, usize)> {
     paragraphs
 }
 
+// ─── Plaintext extraction (for --report) ───────────────────────────────────
+
+/// Flatten one paragraph's runs to plain text, the same way a text-extraction
+/// transform over WordprocessingML does: entities decoded via the same
+/// streaming walk as `find_text_runs`, with `<w:br/>`/`<w:cr/>` rendered as a
+/// newline and `<w:tab/>` as a tab.
+fn paragraph_plaintext(para_xml: &str) -> String {
+    let mut reader = Reader::from_str(para_xml);
+    reader.config_mut().trim_text(false);
+
+    let mut out = String::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Text(t)) => {
+                out.push_str(
+                    &t.unescape()
+                        .map(|s| s.into_owned())
+                        .unwrap_or_else(|_| String::from_utf8_lossy(t.as_ref()).into_owned()),
+                );
+            }
+            Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                b"br" | b"cr" => out.push('\n'),
+                b"tab" => out.push('\t'),
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+    out
+}
+
+/// Concatenate every paragraph's plaintext in document order, one paragraph
+/// per line, mirroring how Word's own "Save as plain text" flattens the body.
+fn extract_document_plaintext(xml: &str) -> String {
+    find_paragraphs(xml)
+        .into_iter()
+        .map(|(start, end)| paragraph_plaintext(&xml[start..end]))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 // ─── Paragraph redaction ────────────────────────────────────────────────────
 
 /// Redact paragraph text between start and end marker texts.
@@ -322,19 +524,27 @@ fn redact_paragraphs(
     end_marker: &str,
     replacement: Option<&str>,
 ) -> (String, usize) {
-    let wt_re = Regex::new(r#"<w:t(?: [^>]*)?>([^<]*)</w:t>"#).expect("invalid regex");
+    let to_redact = paragraphs_to_redact_by_marker(xml, start_marker, end_marker);
+    apply_paragraph_redaction(xml, &to_redact, replacement)
+}
+
+/// Identify paragraph byte ranges between `start_marker` and `end_marker`
+/// (exclusive of both marker paragraphs themselves), without redacting
+/// anything -- shared by `redact_paragraphs` and `--report`'s manifest, which
+/// needs the same ranges without writing them.
+fn paragraphs_to_redact_by_marker(
+    xml: &str,
+    start_marker: &str,
+    end_marker: &str,
+) -> Vec<(usize, usize)> {
     let paragraphs = find_paragraphs(xml);
 
-    // First pass (forward): identify paragraphs in the redaction range
     let mut in_range = false;
     let mut to_redact: Vec<(usize, usize)> = Vec::new();
 
     for &(p_start, p_end) in &paragraphs {
         let para = &xml[p_start..p_end];
-        let vtext: String = wt_re
-            .captures_iter(para)
-            .filter_map(|c| c.get(1).map(|m| m.as_str().to_string()))
-            .collect();
+        let vtext: String = find_text_runs(para).into_iter().map(|run| run.text).collect();
 
         if vtext.contains(start_marker) {
             in_range = true;
@@ -349,50 +559,91 @@ fn redact_paragraphs(
         }
     }
 
-    // Second pass (reverse): clear <w:t> content using byte positions.
-    // The replacement text goes into the first paragraph that actually has text.
+    to_redact
+}
+
+/// How `redact_paragraphs_by_style` selects which paragraphs to redact.
+enum StyleSelector<'a> {
+    /// Redact only paragraphs whose `w:pStyle` matches one of these ids.
+    Redact(&'a [String]),
+    /// Redact every paragraph whose `w:pStyle` does NOT match one of these
+    /// ids (i.e. these styles are always preserved).
+    Keep(&'a [String]),
+}
+
+/// The style id a paragraph's `<w:pPr><w:pStyle w:val="..."/>` declares, if any.
+fn paragraph_style(para: &str) -> Option<String> {
+    let re = Regex::new(r#"<w:pStyle\s+w:val="([^"]+)""#).expect("invalid regex");
+    re.captures(para).map(|c| c[1].to_string())
+}
+
+/// Redact paragraphs by `w:pStyle` id instead of by marker text — robust
+/// against wording changes across regenerated documents, at the cost of
+/// needing the style ids (e.g. `Heading2`, `PlayerInfo`) to stay consistent.
+/// Shares the same `<w:t>`-clearing second pass as `redact_paragraphs`,
+/// including putting `replacement` into the first redacted paragraph that
+/// actually has text.
+fn redact_paragraphs_by_style(
+    xml: &str,
+    selector: &StyleSelector<'_>,
+    replacement: Option<&str>,
+) -> (String, usize) {
+    let to_redact: Vec<(usize, usize)> = find_paragraphs(xml)
+        .into_iter()
+        .filter(|&(p_start, p_end)| {
+            let style = paragraph_style(&xml[p_start..p_end]);
+            match selector {
+                StyleSelector::Redact(styles) => {
+                    style.is_some_and(|s| styles.iter().any(|want| *want == s))
+                }
+                StyleSelector::Keep(styles) => {
+                    !style.is_some_and(|s| styles.iter().any(|want| *want == s))
+                }
+            }
+        })
+        .collect();
+
+    apply_paragraph_redaction(xml, &to_redact, replacement)
+}
+
+/// Clear the `<w:t>` content of every paragraph in `to_redact` (byte ranges
+/// into `xml`), putting `replacement` (or emptying, if `None`) into the
+/// first `<w:t>` of the first paragraph that actually has text.
+fn apply_paragraph_redaction(
+    xml: &str,
+    to_redact: &[(usize, usize)],
+    replacement: Option<&str>,
+) -> (String, usize) {
     let mut result = xml.to_string();
     let count = to_redact.len();
 
     // Find the first paragraph with non-empty <w:t> content (for replacement text)
     let first_text_idx = to_redact
         .iter()
-        .find(|&&(ps, pe)| {
-            let para = &xml[ps..pe];
-            wt_re
-                .captures_iter(para)
-                .any(|c| c.get(1).is_some_and(|m| !m.as_str().is_empty()))
-        })
+        .find(|&&(ps, pe)| find_text_runs(&xml[ps..pe]).iter().any(|run| !run.text.is_empty()))
         .map(|&(s, _)| s);
 
     for &(p_start, p_end) in to_redact.iter().rev() {
         let para = result[p_start..p_end].to_string();
         let mut new_para = para.clone();
 
-        let wt_contents: Vec<(usize, usize)> = wt_re
-            .captures_iter(&para)
-            .filter_map(|c| {
-                let content = c.get(1).unwrap();
-                if content.as_str().is_empty() {
-                    None
-                } else {
-                    Some((content.start(), content.end()))
-                }
-            })
-            .collect();
+        let wt_runs = find_text_runs(&para)
+            .into_iter()
+            .filter(|run| !run.text.is_empty())
+            .collect::<Vec<_>>();
 
         // Put replacement text in the first <w:t> of the first text-bearing paragraph
         let is_replacement_para = Some(p_start) == first_text_idx;
-        for (i, (start, end)) in wt_contents.iter().enumerate().rev() {
-            if is_replacement_para && i == 0 {
-                if let Some(rep) = replacement {
-                    new_para.replace_range(*start..*end, rep);
-                } else {
-                    new_para.replace_range(*start..*end, "");
+        for (i, run) in wt_runs.iter().enumerate().rev() {
+            let rendered = if is_replacement_para && i == 0 {
+                match replacement {
+                    Some(rep) => render_text_run(rep),
+                    None => render_text_run(""),
                 }
             } else {
-                new_para.replace_range(*start..*end, "");
-            }
+                render_text_run("")
+            };
+            new_para.replace_range(run.elem_start..run.elem_end, &rendered);
         }
         result.replace_range(p_start..p_end, &new_para);
     }
@@ -402,48 +653,52 @@ fn redact_paragraphs(
 
 // ─── Image blanking/redaction ───────────────────────────────────────────────
 
-/// Create a solid-colored PNG of the same dimensions/format as the source image.
-fn create_blank_png(png_data: &[u8]) -> Result<Vec<u8>> {
-    let decoder = png::Decoder::new(Cursor::new(png_data));
-    let reader = decoder.read_info().context("Failed to decode PNG")?;
-    let info = reader.info().clone();
-
-    // All-zero pixels = solid black (works for RGB, RGBA, Grayscale)
-    let channels = match info.color_type {
-        png::ColorType::Rgba => 4,
-        png::ColorType::Rgb => 3,
-        _ => 3,
-    };
-    let pixels = vec![0u8; info.width as usize * info.height as usize * channels];
+/// Detect the codec an embedded image part is stored in. Real DOCX files
+/// don't only use PNG for `word/media/*` -- JPEG is common for scanned or
+/// photographed material -- so every image part goes through this instead
+/// of assuming PNG. Sniffs the magic bytes first (authoritative) and falls
+/// back to the zip entry's extension for anything the sniff can't place.
+fn detect_image_format(media_path: &str, data: &[u8]) -> Result<image::ImageFormat> {
+    image::guess_format(data)
+        .or_else(|_| image::ImageFormat::from_path(media_path))
+        .with_context(|| format!("Could not determine image format for {}", media_path))
+}
 
-    let mut output = Vec::new();
-    {
-        let mut encoder = png::Encoder::new(&mut output, info.width, info.height);
-        encoder.set_color(info.color_type);
-        encoder.set_depth(info.bit_depth);
-        let mut writer = encoder.write_header()?;
-        writer.write_image_data(&pixels)?;
-    }
-    Ok(output)
+/// Decode an embedded image of any format the `image` crate supports (PNG,
+/// JPEG, WebP, ...) into a common RGBA buffer, alongside the format it was
+/// decoded from so the caller can re-encode in the same format afterward.
+fn decode_image(media_path: &str, data: &[u8]) -> Result<(image::RgbaImage, image::ImageFormat)> {
+    let format = detect_image_format(media_path, data)?;
+    let img = image::load_from_memory_with_format(data, format)
+        .with_context(|| format!("Failed to decode {}", media_path))?;
+    Ok((img.to_rgba8(), format))
+}
+
+/// Re-encode an RGBA buffer back into `format` (the format it was originally
+/// decoded from), so e.g. a JPEG screenshot comes back out as a JPEG.
+fn encode_image(img: &image::RgbaImage, format: image::ImageFormat) -> Result<Vec<u8>> {
+    let mut output = Cursor::new(Vec::new());
+    image::DynamicImage::ImageRgba8(img.clone())
+        .write_to(&mut output, format)
+        .context("Failed to re-encode image")?;
+    Ok(output.into_inner())
+}
+
+/// Create a solid-colored image of the same dimensions/format as the source image.
+fn create_blank_image(media_path: &str, image_data: &[u8]) -> Result<Vec<u8>> {
+    let (img, format) = decode_image(media_path, image_data)?;
+    let blank = image::RgbaImage::from_pixel(img.width(), img.height(), image::Rgba([0, 0, 0, 255]));
+    encode_image(&blank, format)
 }
 
-/// Redact the player names line from a convention card PNG image.
+/// Redact the player names line from a convention card image.
 /// Paints over the top "NAMES ..." area with white and draws replacement text.
-fn redact_cc_names(png_data: &[u8], replacement: &str, font: &FontVec) -> Result<Vec<u8>> {
-    let decoder = png::Decoder::new(Cursor::new(png_data));
-    let mut reader = decoder.read_info().context("Failed to decode PNG")?;
-    let info = reader.info().clone();
-
-    let mut buf = vec![0u8; reader.output_buffer_size()];
-    reader.next_frame(&mut buf)?;
-
-    let w = info.width as usize;
-    let h = info.height as usize;
-    let channels = match info.color_type {
-        png::ColorType::Rgba => 4,
-        png::ColorType::Rgb => 3,
-        _ => anyhow::bail!("Unsupported color type: {:?}", info.color_type),
-    };
+fn redact_cc_names(media_path: &str, image_data: &[u8], replacement: &str, font: &FontStack) -> Result<Vec<u8>> {
+    let (mut img, format) = decode_image(media_path, image_data)?;
+    let w = img.width() as usize;
+    let h = img.height() as usize;
+    let channels = 4;
+    let buf: &mut [u8] = &mut img;
 
     // Paint white rectangle over the names area (after "NAMES" label).
     // "NAMES" label ends at ~8% of width; names extend to ~85% of width.
@@ -466,8 +721,9 @@ fn redact_cc_names(png_data: &[u8], replacement: &str, font: &FontVec) -> Result
     // Draw replacement text in black
     let font_height = y_end as f32 * 0.75;
     let text_y = (y_end as f32 - font_height) * 0.3;
+    let mut cache = GlyphCache::new();
     draw_text(
-        &mut buf,
+        buf,
         w,
         h,
         channels,
@@ -477,22 +733,203 @@ fn redact_cc_names(png_data: &[u8], replacement: &str, font: &FontVec) -> Result
         text_y,
         font_height,
         (0, 0, 0),
+        &mut cache,
     );
 
-    // Re-encode
-    let mut output = Vec::new();
-    {
-        let mut encoder = png::Encoder::new(&mut output, info.width, info.height);
-        encoder.set_color(info.color_type);
-        encoder.set_depth(info.bit_depth);
-        let mut writer = encoder.write_header()?;
-        writer.write_image_data(&buf)?;
+    encode_image(&img, format)
+}
+
+/// Result of trying to anonymize one candidate BBO screenshot, returned by
+/// `anonymize_one_image` so the decode/pixel-edit/encode work (CPU-bound,
+/// independent per image) can run on a rayon parallel iterator while
+/// `entries` is only touched afterward, back on the main thread.
+enum ImageAnonOutcome {
+    Modified { media_path: String, width: u32, height: u32, names: [String; 4], image_data: Vec<u8> },
+    /// Below the 1000×1000 BBO-screenshot size heuristic -- left untouched.
+    NotScreenshot,
+    Skipped { reason: String },
+}
+
+/// Decode `image_data` (any format `detect_image_format` recognizes), and if
+/// it's large enough to be a BBO screenshot, redact the four player names
+/// into it and re-encode in its original format. Pure function (no I/O, no
+/// shared mutable state) so it's safe to call from any thread.
+fn anonymize_one_image(
+    media_path: &str,
+    anon_url: &str,
+    image_data: &[u8],
+    font: &FontStack,
+) -> ImageAnonOutcome {
+    let names = match extract_player_names(anon_url) {
+        Some(n) => n,
+        None => {
+            return ImageAnonOutcome::Skipped {
+                reason: format!("could not parse player names from URL for {}", media_path),
+            }
+        }
+    };
+
+    let (rgba, format) = match decode_image(media_path, image_data) {
+        Ok(decoded) => decoded,
+        Err(e) => {
+            return ImageAnonOutcome::Skipped {
+                reason: format!("failed to decode {}: {}", media_path, e),
+            }
+        }
+    };
+    let width = rgba.width();
+    let height = rgba.height();
+
+    if width < 1000 || height < 1000 {
+        return ImageAnonOutcome::NotScreenshot;
+    }
+
+    match anonymize_image(rgba, format, &names, font) {
+        Ok(image_data) => ImageAnonOutcome::Modified {
+            media_path: media_path.to_string(),
+            width,
+            height,
+            names,
+            image_data,
+        },
+        Err(e) => ImageAnonOutcome::Skipped {
+            reason: format!("failed to anonymize {}: {}", media_path, e),
+        },
+    }
+}
+
+/// One tinyurl hyperlink found in the document, in whichever OOXML form it
+/// takes. `position` is the byte offset used to order hyperlinks across all
+/// three forms so "Hand N" labels come out sequential in document order.
+enum FieldHyperlink {
+    /// `<w:hyperlink r:id="...">...</w:hyperlink>` — URL lives in the rels
+    /// Target, already rewritten by `replace_rels_urls`; only the display
+    /// `<w:t>` within `[start, end)` needs relabeling.
+    Relationship { start: usize, end: usize },
+    /// `<w:fldSimple w:instr=' HYPERLINK "url" '>...</w:fldSimple>` — the
+    /// URL lives in the `w:instr` attribute itself.
+    Simple { elem_start: usize, elem_end: usize, instr_start: usize, instr_end: usize, new_instr: String },
+    /// A complex field: `<w:fldChar w:fldCharType="begin"/>`, one or more
+    /// `<w:instrText>` runs whose concatenation is ` HYPERLINK "url" `, a
+    /// `<w:fldChar w:fldCharType="separate"/>`, the display runs, then
+    /// `<w:fldChar w:fldCharType="end"/>`. `instr_runs` are the `<w:instrText>`
+    /// content ranges between `begin` and `separate`; the new instr text goes
+    /// into the first one and the rest are cleared, the same way
+    /// `replace_document_text` handles a replacement spanning multiple runs.
+    Complex { instr_runs: Vec<(usize, usize)>, new_instr: String, display_start: usize, display_end: usize },
+}
+
+fn field_hyperlink_position(h: &FieldHyperlink) -> usize {
+    match h {
+        FieldHyperlink::Relationship { start, .. } => *start,
+        FieldHyperlink::Simple { elem_start, .. } => *elem_start,
+        FieldHyperlink::Complex { instr_runs, .. } => {
+            instr_runs.first().map(|&(s, _)| s).unwrap_or(0)
+        }
+    }
+}
+
+/// Extract the quoted URL from a field instruction like ` HYPERLINK "url" `.
+fn extract_hyperlink_field_url(instr: &str) -> Option<String> {
+    let re = Regex::new(r#"HYPERLINK\s+"([^"]+)""#).expect("invalid regex");
+    re.captures(instr).map(|c| c[1].to_string())
+}
+
+/// Find every `<w:fldSimple w:instr=' HYPERLINK "..." '>` field whose URL is
+/// a mapped tinyurl.
+fn find_simple_field_hyperlinks(xml: &str, url_map: &HashMap<String, String>) -> Vec<FieldHyperlink> {
+    let re = Regex::new(r#"(?s)<w:fldSimple\s+w:instr="([^"]*)"[^>]*>.*?</w:fldSimple>"#)
+        .expect("invalid regex");
+    let instr_re = Regex::new(r#"w:instr="([^"]*)""#).expect("invalid regex");
+
+    let mut found = Vec::new();
+    for caps in re.captures_iter(xml) {
+        let whole = caps.get(0).expect("no match");
+        let instr = &caps[1];
+        let Some(url) = extract_hyperlink_field_url(instr) else {
+            continue;
+        };
+        let Some(anon_url) = url_map.get(&normalize_tinyurl(&url)) else {
+            continue;
+        };
+        let instr_match = instr_re.captures(whole.as_str()).expect("instr found above");
+        let instr_group = instr_match.get(1).expect("no group 1");
+        let new_instr = instr.replacen(&url, anon_url, 1);
+        found.push(FieldHyperlink::Simple {
+            elem_start: whole.start(),
+            elem_end: whole.end(),
+            instr_start: whole.start() + instr_group.start(),
+            instr_end: whole.start() + instr_group.end(),
+            new_instr,
+        });
+    }
+    found
+}
+
+/// Find every complex-field `begin`/`instrText...`/`separate`/`end` sequence
+/// whose concatenated instruction is a mapped tinyurl HYPERLINK field.
+fn find_complex_field_hyperlinks(xml: &str, url_map: &HashMap<String, String>) -> Vec<FieldHyperlink> {
+    let marker_re =
+        Regex::new(r#"<w:fldChar[^>]*w:fldCharType="(begin|separate|end)"[^>]*/>"#).expect("invalid regex");
+    let instr_text_re =
+        Regex::new(r#"<w:instrText(?: [^>]*)?>([^<]*)</w:instrText>"#).expect("invalid regex");
+
+    let markers: Vec<(usize, usize, &str)> = marker_re
+        .captures_iter(xml)
+        .map(|c| {
+            let whole = c.get(0).expect("no match");
+            (whole.start(), whole.end(), c.get(1).expect("no group 1").as_str())
+        })
+        .collect();
+
+    let mut found = Vec::new();
+    let mut i = 0;
+    while i < markers.len() {
+        if markers[i].2 != "begin" {
+            i += 1;
+            continue;
+        }
+        let Some(j) = (i + 1..markers.len()).find(|&k| markers[k].2 == "separate") else {
+            i += 1;
+            continue;
+        };
+        let Some(k) = (j + 1..markers.len()).find(|&m| markers[m].2 == "end") else {
+            i += 1;
+            continue;
+        };
+
+        let instr_region = &xml[markers[i].1..markers[j].0];
+        let instr_runs: Vec<(usize, usize)> = instr_text_re
+            .captures_iter(instr_region)
+            .map(|c| {
+                let m = c.get(1).expect("no group 1");
+                (markers[i].1 + m.start(), markers[i].1 + m.end())
+            })
+            .collect();
+        let concatenated: String = instr_runs.iter().map(|&(s, e)| &xml[s..e]).collect();
+
+        if let Some(url) = extract_hyperlink_field_url(&concatenated) {
+            if let Some(anon_url) = url_map.get(&normalize_tinyurl(&url)) {
+                let new_instr = concatenated.replacen(&url, anon_url, 1);
+                found.push(FieldHyperlink::Complex {
+                    instr_runs,
+                    new_instr,
+                    display_start: markers[j].1,
+                    display_end: markers[k].0,
+                });
+            }
+        }
+
+        i = k + 1;
     }
-    Ok(output)
+    found
 }
 
-/// Update visible hyperlink text in the document — replace displayed tinyurl text
-/// with a short sequential label ("Hand 1", "Hand 2", …).
+/// Update visible hyperlink text in the document — replace displayed tinyurl
+/// text with a short sequential label ("Hand 1", "Hand 2", …), and rewrite
+/// the underlying URL for field-code hyperlinks (relationship-based
+/// hyperlinks have their URL rewritten separately, in the rels file, by
+/// `replace_rels_urls`).
 fn replace_hyperlink_text(
     xml: &str,
     url_map: &HashMap<String, String>,
@@ -503,50 +940,104 @@ fn replace_hyperlink_text(
 
     let hl_re = Regex::new(r#"<w:hyperlink[^>]*r:id="([^"]+)"[^>]*>"#).expect("invalid regex");
     let hl_end = "</w:hyperlink>";
+    // Unlike replace_document_text/redact_paragraphs, this only locates the
+    // first display run to overwrite wholesale with an ASCII "Hand N" label --
+    // it never needs the original text's decoded content, so the flat regex
+    // (which still finds the right span; it just mis-decodes entities within
+    // it) is fine to keep here.
     let wt_re = Regex::new(r#"<w:t(?: [^>]*)?>([^<]*)</w:t>"#).expect("invalid regex");
 
-    // Collect tinyurl hyperlinks in document order, assigning sequential hand numbers
-    let mut hyperlinks: Vec<(usize, usize, String, u32)> = Vec::new();
-    let mut hand_num = 0u32;
+    // Collect every tinyurl hyperlink, in whichever form, in document order.
+    let mut hyperlinks: Vec<FieldHyperlink> = Vec::new();
 
     for caps in hl_re.captures_iter(&result) {
         let full = caps.get(0).expect("no match");
         let rid = caps[1].to_string();
         let hl_start = full.start();
 
-        // Only process tinyurl hyperlinks that have a mapping
         let is_mapped_tinyurl = rels_map
             .get(&rid)
             .filter(|url| url.contains("tinyurl.com"))
             .and_then(|url| url_map.get(&normalize_tinyurl(url)))
             .is_some();
-
         if !is_mapped_tinyurl {
             continue;
         }
 
         if let Some(end_pos) = result[hl_start..].find(hl_end) {
-            hand_num += 1;
-            hyperlinks.push((hl_start, hl_start + end_pos + hl_end.len(), rid, hand_num));
+            hyperlinks.push(FieldHyperlink::Relationship {
+                start: hl_start,
+                end: hl_start + end_pos + hl_end.len(),
+            });
         }
     }
 
-    // Process in reverse to preserve offsets
-    for (hl_start, hl_end_pos, _rid, num) in hyperlinks.iter().rev() {
-        let hl_xml = result[*hl_start..*hl_end_pos].to_string();
-        if let Some(caps) = wt_re.captures(&hl_xml) {
-            let text_match = caps.get(1).expect("no group 1");
-            let abs_start = hl_start + text_match.start();
-            let abs_end = hl_start + text_match.end();
-            let label = format!("Hand {}", num);
-            result.replace_range(abs_start..abs_end, &label);
-            count += 1;
+    hyperlinks.extend(find_simple_field_hyperlinks(&result, url_map));
+    hyperlinks.extend(find_complex_field_hyperlinks(&result, url_map));
+    hyperlinks.sort_by_key(field_hyperlink_position);
+
+    // Process in reverse document order so earlier offsets aren't shifted.
+    for (num, hyperlink) in hyperlinks.iter().enumerate().rev() {
+        let num = num as u32 + 1;
+        match hyperlink {
+            FieldHyperlink::Relationship { start, end } => {
+                let hl_xml = result[*start..*end].to_string();
+                if let Some(caps) = wt_re.captures(&hl_xml) {
+                    let text_match = caps.get(1).expect("no group 1");
+                    let abs_start = start + text_match.start();
+                    let abs_end = start + text_match.end();
+                    result.replace_range(abs_start..abs_end, &format!("Hand {}", num));
+                    count += 1;
+                }
+            }
+            FieldHyperlink::Simple { elem_start, elem_end, instr_start, instr_end, new_instr } => {
+                let elem_xml = result[*elem_start..*elem_end].to_string();
+                if let Some(caps) = wt_re.captures(&elem_xml) {
+                    let text_match = caps.get(1).expect("no group 1");
+                    let abs_start = elem_start + text_match.start();
+                    let abs_end = elem_start + text_match.end();
+                    result.replace_range(abs_start..abs_end, &format!("Hand {}", num));
+                }
+                result.replace_range(*instr_start..*instr_end, new_instr);
+                count += 1;
+            }
+            FieldHyperlink::Complex { instr_runs, new_instr, display_start, display_end } => {
+                let display_xml = result[*display_start..*display_end].to_string();
+                if let Some(caps) = wt_re.captures(&display_xml) {
+                    let text_match = caps.get(1).expect("no group 1");
+                    let abs_start = display_start + text_match.start();
+                    let abs_end = display_start + text_match.end();
+                    result.replace_range(abs_start..abs_end, &format!("Hand {}", num));
+                }
+                for &(start, end) in instr_runs.iter().rev().take(instr_runs.len().saturating_sub(1)) {
+                    result.replace_range(start..end, "");
+                }
+                if let Some(&(start, end)) = instr_runs.first() {
+                    result.replace_range(start..end, new_instr);
+                }
+                count += 1;
+            }
         }
     }
 
     (result, count)
 }
 
+/// The `_rels` part for a given content part, per the OOXML convention that
+/// it lives in a `_rels` subfolder *alongside* the part, not alongside the
+/// package root -- `word/document.xml` -> `word/_rels/document.xml.rels`,
+/// but `word/glossary/document.xml` -> `word/glossary/_rels/document.xml.rels`,
+/// not `word/_rels/glossary/document.xml.rels`. The previous single-part
+/// scope never hit this because `word/document.xml` has no subdirectory, so
+/// the bug was latent until the per-part loop started walking nested parts
+/// like the glossary document.
+fn rels_path_for_part(part_name: &str) -> String {
+    match part_name.rsplit_once('/') {
+        Some((dir, filename)) => format!("{}/_rels/{}.rels", dir, filename),
+        None => format!("_rels/{}.rels", part_name),
+    }
+}
+
 // ─── Image-URL association ──────────────────────────────────────────────────
 
 /// Parse rels XML into rId -> target URL/path mapping.
@@ -667,43 +1158,184 @@ fn build_image_url_pairs(
     pairs
 }
 
-// ─── PNG anonymization ──────────────────────────────────────────────────────
+// ─── Image anonymization ────────────────────────────────────────────────────
 
-/// Decode a PNG, anonymize BBO screenshot player names, re-encode.
-fn anonymize_png(png_data: &[u8], names: &[String; 4], font: &FontVec) -> Result<Vec<u8>> {
-    let decoder = png::Decoder::new(Cursor::new(png_data));
-    let mut reader = decoder.read_info().context("Failed to decode PNG")?;
-    let info = reader.info().clone();
+thread_local! {
+    // One glyph cache per rayon worker thread. anonymize_one_image runs on a
+    // parallel iterator, so a plain shared cache would need a lock around
+    // every glyph lookup; this instead lets each worker accumulate its own
+    // cache across the images it happens to process, with no contention.
+    static GLYPH_CACHE: std::cell::RefCell<GlyphCache> = std::cell::RefCell::new(GlyphCache::new());
+}
 
-    let mut buf = vec![0u8; reader.output_buffer_size()];
-    reader.next_frame(&mut buf)?;
+/// Anonymize BBO screenshot player names into an already-decoded RGBA
+/// buffer, then re-encode in `format` (the format it was originally decoded
+/// from, so e.g. a JPEG screenshot stays a JPEG).
+fn anonymize_image(
+    mut rgba: image::RgbaImage,
+    format: image::ImageFormat,
+    names: &[String; 4],
+    font: &FontStack,
+) -> Result<Vec<u8>> {
+    let (width, height) = (rgba.width() as usize, rgba.height() as usize);
+    let buf: &mut [u8] = &mut rgba;
+    GLYPH_CACHE.with(|cache| {
+        modify_screenshot_pixels(buf, width, height, names, font, 4, &mut cache.borrow_mut());
+    });
+    encode_image(&rgba, format)
+}
 
-    let channels = match info.color_type {
-        png::ColorType::Rgba => 4,
-        png::ColorType::Rgb => 3,
-        _ => anyhow::bail!("Unsupported PNG color type: {:?}", info.color_type),
-    };
+// ─── --report (dry run) ─────────────────────────────────────────────────────
 
-    modify_screenshot_pixels(
-        &mut buf,
-        info.width as usize,
-        info.height as usize,
-        names,
-        font,
-        channels,
-    );
+/// `--report` mode: run every detection pass read-only and write a plaintext
+/// extraction plus a change manifest instead of an anonymized DOCX. Reuses
+/// `find_paragraphs`, `parse_rels`, and `build_image_url_pairs` exactly as
+/// the real run does; it just never assigns the results back into `entries`.
+fn write_report(
+    cli: &Cli,
+    url_map: &HashMap<String, String>,
+    entries: &[(String, Vec<u8>)],
+    part_names: &[String],
+    text_replacements: &[(String, String)],
+    redact_styles: &[String],
+    keep_styles: &[String],
+) -> Result<()> {
+    let mut plaintext = String::new();
+    let mut url_section = String::new();
+    let mut text_section = String::new();
+    let mut redact_section = String::new();
+
+    let mut urls: Vec<(&String, &String)> = url_map.iter().collect();
+    urls.sort();
+    for (orig, anon) in urls {
+        url_section.push_str(&format!("{} -> {}\n", orig, anon));
+    }
+
+    let mut image_url_pairs: Vec<(String, String)> = Vec::new();
+
+    for part_name in part_names {
+        let part_xml = entries
+            .iter()
+            .find(|(name, _)| name == part_name)
+            .map(|(_, data)| String::from_utf8_lossy(data).to_string())
+            .expect("part_name came from entries");
+
+        plaintext.push_str(&format!("--- {} ---\n", part_name));
+        plaintext.push_str(&extract_document_plaintext(&part_xml));
+        plaintext.push('\n');
+
+        let rels_key = rels_path_for_part(part_name);
+        let rels_xml = entries
+            .iter()
+            .find(|(name, _)| *name == rels_key)
+            .map(|(_, data)| String::from_utf8_lossy(data).to_string())
+            .unwrap_or_default();
+        let rels_map = parse_rels(&rels_xml);
+        if !cli.no_images {
+            image_url_pairs.extend(build_image_url_pairs(&part_xml, &rels_map, url_map));
+        }
+
+        if !text_replacements.is_empty() {
+            let (_, text_count) = replace_document_text(&part_xml, text_replacements);
+            if text_count > 0 {
+                text_section.push_str(&format!(
+                    "{}: {} text replacement matches\n",
+                    part_name, text_count
+                ));
+            }
+        }
 
-    // Re-encode
-    let mut output = Vec::new();
-    {
-        let mut encoder = png::Encoder::new(&mut output, info.width, info.height);
-        encoder.set_color(info.color_type);
-        encoder.set_depth(info.bit_depth);
-        let mut writer = encoder.write_header()?;
-        writer.write_image_data(&buf)?;
+        if let (Some(start), Some(end)) = (&cli.redact_start, &cli.redact_end) {
+            for (range_start, range_end) in paragraphs_to_redact_by_marker(&part_xml, start, end) {
+                redact_section.push_str(&format!(
+                    "{}: paragraph bytes [{}, {})\n",
+                    part_name, range_start, range_end
+                ));
+            }
+        }
+
+        if !redact_styles.is_empty() || !keep_styles.is_empty() {
+            let selector = if !redact_styles.is_empty() {
+                StyleSelector::Redact(redact_styles)
+            } else {
+                StyleSelector::Keep(keep_styles)
+            };
+            let to_redact = find_paragraphs(&part_xml).into_iter().filter(|&(ps, pe)| {
+                let style = paragraph_style(&part_xml[ps..pe]);
+                match &selector {
+                    StyleSelector::Redact(styles) => {
+                        style.is_some_and(|s| styles.iter().any(|want| *want == s))
+                    }
+                    StyleSelector::Keep(styles) => {
+                        !style.is_some_and(|s| styles.iter().any(|want| *want == s))
+                    }
+                }
+            });
+            for (range_start, range_end) in to_redact {
+                redact_section.push_str(&format!(
+                    "{}: style-matched paragraph bytes [{}, {})\n",
+                    part_name, range_start, range_end
+                ));
+            }
+        }
     }
 
-    Ok(output)
+    let mut image_section = String::new();
+    for (media_path, anon_url) in &image_url_pairs {
+        let zip_path = format!("word/{}", media_path);
+        let dims = entries
+            .iter()
+            .find(|(name, _)| *name == zip_path)
+            .and_then(|(_, data)| decode_image(&zip_path, data).ok())
+            .map(|(rgba, _)| (rgba.width(), rgba.height()));
+
+        match dims {
+            Some((w, h)) if w >= 1000 && h >= 1000 => {
+                image_section.push_str(&format!(
+                    "would anonymize {} ({}x{}, qualifies as BBO screenshot) -> {}\n",
+                    zip_path, w, h, anon_url
+                ));
+            }
+            Some((w, h)) => {
+                image_section.push_str(&format!(
+                    "skipping {} ({}x{}, below the 1000x1000 screenshot threshold)\n",
+                    zip_path, w, h
+                ));
+            }
+            None => {
+                image_section.push_str(&format!("skipping {} (not a decodable image)\n", zip_path));
+            }
+        }
+    }
+    if let Some(ref blank_list) = cli.blank_images {
+        for img_name in blank_list.split(',').map(str::trim) {
+            image_section.push_str(&format!("would blank word/media/{}\n", img_name));
+        }
+    }
+    if let Some(ref cc_name) = cli.cc_redact {
+        let replacement = cli.cc_names.as_deref().unwrap_or("Bob & Sally");
+        image_section.push_str(&format!(
+            "would redact convention card names in word/media/{} -> \"{}\"\n",
+            cc_name, replacement
+        ));
+    }
+
+    let manifest = format!(
+        "=== URL remaps ===\n{}\n=== Text replacements ===\n{}\n=== Redacted paragraph ranges ===\n{}\n=== Images ===\n{}",
+        url_section, text_section, redact_section, image_section
+    );
+
+    let plaintext_path = PathBuf::from(format!("{}.txt", cli.output.display()));
+    let manifest_path = PathBuf::from(format!("{}.manifest.txt", cli.output.display()));
+    std::fs::write(&plaintext_path, plaintext)
+        .with_context(|| format!("Failed to write {}", plaintext_path.display()))?;
+    std::fs::write(&manifest_path, manifest)
+        .with_context(|| format!("Failed to write {}", manifest_path.display()))?;
+
+    println!("\nReport only (no DOCX written):");
+    println!("  Plaintext extraction: {}", plaintext_path.display());
+    println!("  Change manifest:      {}", manifest_path.display());
+    Ok(())
 }
 
 // ─── Main ───────────────────────────────────────────────────────────────────
@@ -738,197 +1370,311 @@ fn run(cli: &Cli) -> Result<()> {
         Vec::new()
     };
 
+    let redact_styles: Vec<String> = cli
+        .redact_style
+        .as_deref()
+        .map(|s| s.split(',').map(|s| s.trim().to_string()).collect())
+        .unwrap_or_default();
+    let keep_styles: Vec<String> = cli
+        .keep_style
+        .as_deref()
+        .map(|s| s.split(',').map(|s| s.trim().to_string()).collect())
+        .unwrap_or_default();
+    if !redact_styles.is_empty() && !keep_styles.is_empty() {
+        anyhow::bail!("--redact-style and --keep-style are mutually exclusive");
+    }
+
+    let mut audit = AuditManifest::default();
+    if cli.audit_json.is_some() {
+        let mut mappings: Vec<(&String, &String)> = url_map.iter().collect();
+        mappings.sort();
+        audit.url_mappings = mappings
+            .into_iter()
+            .map(|(original, anonymized)| UrlMappingRecord {
+                original: original.clone(),
+                anonymized: anonymized.clone(),
+            })
+            .collect();
+    }
+
     // 4. Read DOCX
     println!("\nOpening DOCX: {}", cli.docx.display());
     let mut entries = read_docx(&cli.docx)?;
     println!("  {} zip entries", entries.len());
 
-    // 5. Process rels — replace tinyurl targets
-    let rels_key = "word/_rels/document.xml.rels";
-    let rels_xml = entries
+    // 5. Process every word/*.xml content part (document, headers, footers,
+    // footnotes, endnotes, comments, ...), each paired with its own
+    // `_rels/<part>.rels` (see `rels_path_for_part`) if it has one. Names/disclosures leak just as
+    // easily through a header or a comment as through the document body, so
+    // every part gets the full rels/text/hyperlink pass, not just
+    // word/document.xml.
+    let part_names: Vec<String> = entries
         .iter()
-        .find(|(name, _)| name == rels_key)
-        .map(|(_, data)| String::from_utf8_lossy(data).to_string())
-        .context("No word/_rels/document.xml.rels found")?;
-
-    let rels_map_before = parse_rels(&rels_xml);
-    // Build rId -> URL map for hyperlink text replacement
-    let rid_to_url: HashMap<String, String> = rels_map_before
-        .iter()
-        .filter(|(_, (t, _))| t.contains("hyperlink"))
-        .map(|(id, (_, target))| (id.clone(), target.clone()))
+        .map(|(name, _)| name.clone())
+        .filter(|name| name.starts_with("word/") && name.ends_with(".xml"))
         .collect();
 
-    let (new_rels, rels_count) = replace_rels_urls(&rels_xml, &url_map);
-    println!("\nReplaced {} tinyurl targets in rels", rels_count);
-
-    // Update rels entry
-    if let Some((_, data)) = entries.iter_mut().find(|(name, _)| name == rels_key) {
-        *data = new_rels.into_bytes();
+    if cli.report {
+        return write_report(
+            cli,
+            &url_map,
+            &entries,
+            &part_names,
+            &text_replacements,
+            &redact_styles,
+            &keep_styles,
+        );
     }
 
-    // 6. Process document.xml — text replacement + hyperlink text
-    let doc_key = "word/document.xml";
-    let doc_xml = entries
-        .iter()
-        .find(|(name, _)| name == doc_key)
-        .map(|(_, data)| String::from_utf8_lossy(data).to_string())
-        .context("No word/document.xml found")?;
-
-    // Build image-URL pairs before modifying the XML
-    let image_url_pairs = if !cli.no_images {
-        let rels_parsed = parse_rels(&rels_xml);
-        build_image_url_pairs(&doc_xml, &rels_parsed, &url_map)
-    } else {
-        Vec::new()
-    };
+    let mut image_url_pairs: Vec<(String, String)> = Vec::new();
+
+    for part_name in &part_names {
+        let rels_key = rels_path_for_part(part_name);
+
+        let rels_xml = entries
+            .iter()
+            .find(|(name, _)| name == &rels_key)
+            .map(|(_, data)| String::from_utf8_lossy(data).to_string())
+            .unwrap_or_default();
+        let rels_map_before = parse_rels(&rels_xml);
+        let rid_to_url: HashMap<String, String> = rels_map_before
+            .iter()
+            .filter(|(_, (t, _))| t.contains("hyperlink"))
+            .map(|(id, (_, target))| (id.clone(), target.clone()))
+            .collect();
 
-    let mut new_doc = doc_xml.clone();
+        if !rels_xml.is_empty() {
+            let (new_rels, rels_count) = replace_rels_urls(&rels_xml, &url_map);
+            if rels_count > 0 {
+                println!("Replaced {} tinyurl targets in {}", rels_count, rels_key);
+            }
+            if let Some((_, data)) = entries.iter_mut().find(|(name, _)| name == &rels_key) {
+                *data = new_rels.into_bytes();
+            }
+        }
 
-    // Replace visible hyperlink text (tinyurl text -> anonymized URL)
-    let (updated_doc, hl_count) = replace_hyperlink_text(&new_doc, &url_map, &rid_to_url);
-    new_doc = updated_doc;
-    println!("Replaced {} hyperlink display texts", hl_count);
+        let part_xml = entries
+            .iter()
+            .find(|(name, _)| name == part_name)
+            .map(|(_, data)| String::from_utf8_lossy(data).to_string())
+            .expect("part_name came from entries");
 
-    // Replace document text using text map
-    if !text_replacements.is_empty() {
-        let (updated_doc, text_count) = replace_document_text(&new_doc, &text_replacements);
-        new_doc = updated_doc;
-        println!(
-            "Replaced {} text occurrences ({} rules)",
-            text_count,
-            text_replacements.len()
-        );
-    }
+        if !cli.no_images {
+            image_url_pairs.extend(build_image_url_pairs(&part_xml, &rels_map_before, &url_map));
+        }
 
-    // Redact paragraph range if requested
-    if let (Some(ref start), Some(ref end)) = (&cli.redact_start, &cli.redact_end) {
-        let rep = cli.redact_replacement.as_deref();
-        let (updated_doc, redact_count) = redact_paragraphs(&new_doc, start, end, rep);
-        new_doc = updated_doc;
-        println!(
-            "Redacted {} paragraphs (\"{}\" → \"{}\")",
-            redact_count, start, end
-        );
-    }
+        let mut new_part = part_xml.clone();
 
-    // Update document.xml entry
-    if let Some((_, data)) = entries.iter_mut().find(|(name, _)| name == doc_key) {
-        *data = new_doc.into_bytes();
-    }
+        let (updated, hl_count) = replace_hyperlink_text(&new_part, &url_map, &rid_to_url);
+        new_part = updated;
+        if hl_count > 0 {
+            println!("Replaced {} hyperlink display texts in {}", hl_count, part_name);
+            if cli.audit_json.is_some() {
+                audit.hyperlink_replacements.push(PartCount { part: part_name.clone(), count: hl_count });
+            }
+        }
 
-    // 7. Anonymize BBO screenshot images
-    if !cli.no_images && !image_url_pairs.is_empty() {
-        println!("\nAnonymizing BBO screenshot images...");
-        let font = load_system_font()?;
-        let mut modified = 0u32;
+        if !text_replacements.is_empty() {
+            let (updated, text_count) = replace_document_text(&new_part, &text_replacements);
+            new_part = updated;
+            if text_count > 0 {
+                println!("Replaced {} text occurrences in {}", text_count, part_name);
+                if cli.audit_json.is_some() {
+                    audit.text_replacements.push(PartCount {
+                        part: part_name.clone(),
+                        count: text_count as u32,
+                    });
+                }
+            }
+        }
 
-        for (media_path, anon_url) in &image_url_pairs {
-            let names = match extract_player_names(anon_url) {
-                Some(n) => n,
-                None => {
-                    eprintln!(
-                        "  Warning: could not parse player names from URL for {}",
-                        media_path
-                    );
-                    continue;
+        if let (Some(ref start), Some(ref end)) = (&cli.redact_start, &cli.redact_end) {
+            let rep = cli.redact_replacement.as_deref();
+            if cli.audit_json.is_some() {
+                for (range_start, range_end) in paragraphs_to_redact_by_marker(&new_part, start, end) {
+                    audit.redacted_paragraphs.push(RedactedRange {
+                        part: part_name.clone(),
+                        start: range_start,
+                        end: range_end,
+                    });
                 }
+            }
+            let (updated, redact_count) = redact_paragraphs(&new_part, start, end, rep);
+            new_part = updated;
+            if redact_count > 0 {
+                println!("Redacted {} paragraphs in {}", redact_count, part_name);
+            }
+        }
+
+        if !redact_styles.is_empty() || !keep_styles.is_empty() {
+            let rep = cli.redact_replacement.as_deref();
+            let selector = if !redact_styles.is_empty() {
+                StyleSelector::Redact(&redact_styles)
+            } else {
+                StyleSelector::Keep(&keep_styles)
             };
+            if cli.audit_json.is_some() {
+                for (range_start, range_end) in find_paragraphs(&new_part).into_iter().filter(|&(ps, pe)| {
+                    let style = paragraph_style(&new_part[ps..pe]);
+                    match &selector {
+                        StyleSelector::Redact(styles) => {
+                            style.is_some_and(|s| styles.iter().any(|want| *want == s))
+                        }
+                        StyleSelector::Keep(styles) => {
+                            !style.is_some_and(|s| styles.iter().any(|want| *want == s))
+                        }
+                    }
+                }) {
+                    audit.redacted_paragraphs.push(RedactedRange {
+                        part: part_name.clone(),
+                        start: range_start,
+                        end: range_end,
+                    });
+                }
+            }
+            let (updated, redact_count) = redact_paragraphs_by_style(&new_part, &selector, rep);
+            new_part = updated;
+            if redact_count > 0 {
+                println!("Redacted {} style-matched paragraphs in {}", redact_count, part_name);
+            }
+        }
 
-            // The media_path from rels is like "media/image5.png"
-            let zip_path = format!("word/{}", media_path);
+        if new_part != part_xml {
+            if let Some((_, data)) = entries.iter_mut().find(|(name, _)| name == part_name) {
+                *data = new_part.into_bytes();
+            }
+        }
+    }
 
-            let png_data = match entries.iter().find(|(name, _)| *name == zip_path) {
-                Some((_, data)) => data.clone(),
-                None => {
-                    eprintln!("  Warning: {} not found in DOCX", zip_path);
-                    continue;
-                }
-            };
+    // 6. Anonymize BBO screenshot images. Decode/pixel-edit/re-encode is
+    // CPU-bound and each image is independent, so the actual work runs
+    // through anonymize_one_image on a rayon parallel iterator; only
+    // collecting the image bytes beforehand and writing results back into
+    // entries afterward stay sequential. par_iter().collect() preserves
+    // input order, so the final zip entries come out the same regardless
+    // of which image finishes first.
+    if !cli.no_images && !image_url_pairs.is_empty() {
+        println!("\nAnonymizing BBO screenshot images...");
+        let font = load_system_font()?;
 
-            // Check dimensions — only modify large images (BBO screenshots)
-            let decoder = png::Decoder::new(Cursor::new(&png_data));
-            let reader = match decoder.read_info() {
-                Ok(r) => r,
-                Err(e) => {
-                    eprintln!("  Warning: failed to decode {}: {}", zip_path, e);
-                    continue;
+        let jobs: Vec<(String, String, Vec<u8>)> = image_url_pairs
+            .iter()
+            .filter_map(|(media_path, anon_url)| {
+                let zip_path = format!("word/{}", media_path);
+                match entries.iter().find(|(name, _)| *name == zip_path) {
+                    Some((_, data)) => Some((media_path.clone(), anon_url.clone(), data.clone())),
+                    None => {
+                        eprintln!("  Warning: {} not found in DOCX", zip_path);
+                        None
+                    }
                 }
-            };
-            let info = reader.info();
-            let w = info.width as usize;
-            let h = info.height as usize;
+            })
+            .collect();
 
-            if w < 1000 || h < 1000 {
-                continue; // Not a BBO screenshot
-            }
+        let outcomes: Vec<ImageAnonOutcome> = jobs
+            .par_iter()
+            .map(|(media_path, anon_url, image_data)| {
+                anonymize_one_image(media_path, anon_url, image_data, &font)
+            })
+            .collect();
 
-            match anonymize_png(&png_data, &names, &font) {
-                Ok(new_png) => {
+        let mut modified = 0u32;
+        for outcome in outcomes {
+            match outcome {
+                ImageAnonOutcome::Modified { media_path, width, height, names, image_data } => {
+                    let zip_path = format!("word/{}", media_path);
                     if let Some((_, data)) = entries.iter_mut().find(|(name, _)| *name == zip_path)
                     {
-                        *data = new_png;
+                        *data = image_data;
                     }
                     println!(
                         "  {} ({}x{}): [N]{} [S]{} [W]{} [E]{}",
-                        media_path, w, h, names[2], names[0], names[1], names[3]
+                        media_path, width, height, names[2], names[0], names[1], names[3]
                     );
+                    if cli.audit_json.is_some() {
+                        audit.images_modified.push(ImageModifiedRecord {
+                            media_path,
+                            width,
+                            height,
+                            north: names[2].clone(),
+                            south: names[0].clone(),
+                            west: names[1].clone(),
+                            east: names[3].clone(),
+                        });
+                    }
                     modified += 1;
                 }
-                Err(e) => {
-                    eprintln!("  Warning: failed to anonymize {}: {}", zip_path, e);
-                }
+                ImageAnonOutcome::NotScreenshot => {}
+                ImageAnonOutcome::Skipped { reason } => eprintln!("  Warning: {}", reason),
             }
         }
         println!("Modified {} BBO screenshot images", modified);
     }
 
-    // 8. Blank specified images (replace with solid black)
+    // 7. Blank specified images (replace with solid black)
     if let Some(ref blank_list) = cli.blank_images {
         let names: Vec<&str> = blank_list.split(',').map(str::trim).collect();
         for img_name in &names {
             let zip_path = format!("word/media/{}", img_name);
-            let png_data = match entries.iter().find(|(n, _)| *n == zip_path) {
+            let image_data = match entries.iter().find(|(n, _)| *n == zip_path) {
                 Some((_, data)) => data.clone(),
                 None => {
                     eprintln!("  Warning: {} not found in DOCX", zip_path);
                     continue;
                 }
             };
-            match create_blank_png(&png_data) {
+            match create_blank_image(&zip_path, &image_data) {
                 Ok(blank) => {
                     if let Some((_, data)) = entries.iter_mut().find(|(n, _)| *n == zip_path) {
                         *data = blank;
                     }
                     println!("Blanked image: {}", img_name);
+                    if cli.audit_json.is_some() {
+                        audit.images_blanked.push(img_name.to_string());
+                    }
                 }
                 Err(e) => eprintln!("  Warning: failed to blank {}: {}", img_name, e),
             }
         }
     }
 
-    // 9. Redact convention card player names
+    // 8. Redact convention card player names
     if let Some(ref cc_name) = cli.cc_redact {
         let replacement = cli.cc_names.as_deref().unwrap_or("Bob & Sally");
         let zip_path = format!("word/media/{}", cc_name);
-        let png_data = match entries.iter().find(|(n, _)| *n == zip_path) {
+        let image_data = match entries.iter().find(|(n, _)| *n == zip_path) {
             Some((_, data)) => data.clone(),
             None => anyhow::bail!("Convention card image {} not found", zip_path),
         };
         let font = load_system_font()?;
-        let new_png = redact_cc_names(&png_data, replacement, &font)?;
+        let new_image = redact_cc_names(&zip_path, &image_data, replacement, &font)?;
         if let Some((_, data)) = entries.iter_mut().find(|(n, _)| *n == zip_path) {
-            *data = new_png;
+            *data = new_image;
         }
         println!(
             "Redacted convention card names in {} → \"{}\"",
             cc_name, replacement
         );
+        if cli.audit_json.is_some() {
+            audit.cc_redaction = Some(CcRedactionRecord {
+                media_path: zip_path,
+                replacement: replacement.to_string(),
+            });
+        }
     }
 
-    // 10. Write output DOCX
+    // 9. Write output DOCX
     write_docx(&cli.output, &entries)?;
     println!("\nSaved to: {}", cli.output.display());
+
+    if let Some(ref audit_path) = cli.audit_json {
+        let json = serde_json::to_string_pretty(&audit)
+            .context("Failed to serialize audit manifest")?;
+        std::fs::write(audit_path, json)
+            .with_context(|| format!("Failed to write {}", audit_path.display()))?;
+        println!("Audit manifest: {}", audit_path.display());
+    }
+
     Ok(())
 }
 
@@ -936,3 +1682,49 @@ fn main() -> Result<()> {
     let cli = Cli::parse();
     run(&cli)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_document_text_redacts_text_box_content() {
+        // Text boxes are just another `<w:p>` nested inside `<w:pict>`/
+        // `<w:txbxContent>` in the same document.xml -- find_paragraphs
+        // matches `<w:p>` generically, so the same pass that rewrites body
+        // paragraphs should reach into a text box too.
+        let xml = r#"<w:document><w:body>
+            <w:p><w:r><w:t>Cover page</w:t></w:r></w:p>
+            <w:pict><v:shape><v:textbox><w:txbxContent>
+                <w:p><w:r><w:t>Reviewer: Jane Smith</w:t></w:r></w:p>
+            </w:txbxContent></v:textbox></v:shape></w:pict>
+        </w:body></w:document>"#;
+
+        let replacements = vec![("Jane Smith".to_string(), "REDACTED".to_string())];
+        let (updated, count) = replace_document_text(xml, &replacements);
+
+        assert_eq!(count, 1);
+        assert!(updated.contains("REDACTED"));
+        assert!(!updated.contains("Jane Smith"));
+        assert!(updated.contains("Cover page"));
+    }
+
+    #[test]
+    fn test_part_names_filter_includes_nested_word_subfolders() {
+        let names = [
+            "word/document.xml".to_string(),
+            "word/header1.xml".to_string(),
+            "word/glossary/document.xml".to_string(),
+            "word/_rels/document.xml.rels".to_string(),
+            "[Content_Types].xml".to_string(),
+        ];
+        let parts: Vec<&String> = names
+            .iter()
+            .filter(|name| name.starts_with("word/") && name.ends_with(".xml"))
+            .collect();
+        assert!(parts.contains(&&"word/document.xml".to_string()));
+        assert!(parts.contains(&&"word/header1.xml".to_string()));
+        assert!(parts.contains(&&"word/glossary/document.xml".to_string()));
+        assert!(!parts.contains(&&"word/_rels/document.xml.rels".to_string()));
+    }
+}
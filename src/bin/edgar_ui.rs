@@ -5,21 +5,51 @@
 
 use edgar_defense_toolkit::pipeline;
 use iced::widget::{
-    button, checkbox, column, container, progress_bar, row, rule, scrollable, text, text_input,
+    button, checkbox, column, container, pick_list, progress_bar, row, rule, scrollable, stack,
+    text, text_input,
 };
 use iced::{Center, Element, Fill, Task, Theme};
+use notify::Watcher;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::os::unix::ffi::OsStrExt;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::Instant;
 
+/// `--session <dir>` override for the external control pipe, read once in
+/// `main` and picked up by `App::new` (iced's `application` builder doesn't
+/// thread CLI args through to the state constructor).
+static SESSION_OVERRIDE: OnceLock<Option<PathBuf>> = OnceLock::new();
+
 fn main() -> iced::Result {
+    let mut args = std::env::args().skip(1);
+    let mut session_override = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--session" => session_override = args.next().map(PathBuf::from),
+            "--print-default-theme" => {
+                print!("{}", default_theme_toml());
+                return Ok(());
+            }
+            _ => {}
+        }
+    }
+    let _ = SESSION_OVERRIDE.set(session_override);
+
     iced::application(App::new, App::update, App::view)
         .theme(App::theme)
+        .subscription(App::subscription)
         .centered()
         .run()
 }
 
+/// Key chord that opens the command palette, e.g. `ctrl+k`. Not itself a
+/// keymap binding, so it can't be shadowed by a custom keymap file.
+const PALETTE_CHORD: &str = "ctrl+k";
+
 // ============================================================================
 // App State
 // ============================================================================
@@ -33,6 +63,116 @@ enum TabId {
     Stats,
     Display,
     Package,
+    Batch,
+    History,
+}
+
+/// Display label for a tab, used both for the tab bar and to tag history
+/// entries in the persisted activity log.
+fn tab_label(tab: TabId) -> &'static str {
+    match tab {
+        TabId::Welcome => "Welcome",
+        TabId::Fetch => "Fetch Cardplay",
+        TabId::Anonymize => "Anonymize",
+        TabId::Analyze => "Analyze DD",
+        TabId::Stats => "Statistics",
+        TabId::Display => "Display Hand",
+        TabId::Package => "Package",
+        TabId::Batch => "Batch",
+        TabId::History => "History",
+    }
+}
+
+/// Reverse of `tab_label`, used when reloading the persisted activity log.
+fn tab_from_label(s: &str) -> Option<TabId> {
+    match s {
+        "Welcome" => Some(TabId::Welcome),
+        "Fetch Cardplay" => Some(TabId::Fetch),
+        "Anonymize" => Some(TabId::Anonymize),
+        "Analyze DD" => Some(TabId::Analyze),
+        "Statistics" => Some(TabId::Stats),
+        "Display Hand" => Some(TabId::Display),
+        "Package" => Some(TabId::Package),
+        "Batch" => Some(TabId::Batch),
+        "History" => Some(TabId::History),
+        _ => None,
+    }
+}
+
+/// Severity of a history entry, used for the History panel's filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HistorySeverity {
+    Info,
+    Error,
+}
+
+/// Cap on how many entries `load_history`/`load_run_ledger` keep in memory
+/// (and thus show in the History tab), so a long-running case doesn't grow
+/// the panel without bound. The log files on disk are untouched.
+const MAX_HISTORY_ENTRIES: usize = 200;
+
+/// A single timestamped, tab-tagged entry in the durable activity history.
+/// Distinct from the transient per-run `log_lines`: this record survives
+/// across runs and app restarts, persisted to the case's activity log.
+#[derive(Debug, Clone)]
+struct HistoryEntry {
+    timestamp: String,
+    tab: Option<TabId>,
+    severity: HistorySeverity,
+    message: String,
+}
+
+/// How a finished background run ended, recorded in the run ledger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RunStatus {
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+impl RunStatus {
+    fn label(self) -> &'static str {
+        match self {
+            RunStatus::Completed => "Completed",
+            RunStatus::Cancelled => "Cancelled",
+            RunStatus::Failed => "Failed",
+        }
+    }
+
+    fn from_label(s: &str) -> Option<RunStatus> {
+        match s {
+            "Completed" => Some(RunStatus::Completed),
+            "Cancelled" => Some(RunStatus::Cancelled),
+            "Failed" => Some(RunStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// One row of the durable run ledger (the History tab's Run Log section):
+/// what stage ran, when, how long it took, and what it processed. Distinct
+/// from `HistoryEntry` (a free-text activity feed) — structured enough to
+/// total up boards analyzed and time spent for a case report.
+#[derive(Debug, Clone)]
+struct RunRecord {
+    tab: Option<TabId>,
+    started_at: String,
+    duration_secs: f64,
+    status: RunStatus,
+    boards_processed: usize,
+    errors: usize,
+    skipped: usize,
+    input_path: String,
+    output_path: String,
+}
+
+/// One case subfolder discovered under a batch root by `scan_batch_root`,
+/// and, once the batch pipeline reaches it, how it finished.
+#[derive(Debug, Clone)]
+struct BatchCase {
+    folder: PathBuf,
+    case_files: CaseFiles,
+    status: Option<RunStatus>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -56,6 +196,11 @@ struct App {
     case_folder: String,
     case_files: CaseFiles,
     case_usernames: Vec<String>,
+    /// Set when the filesystem watcher re-scans the case folder after a
+    /// change; shown on the Welcome tab until the next rescan or folder pick.
+    case_files_note: String,
+    /// Most-recently-used case folders, newest first, from `Config`.
+    recent_folders: Vec<String>,
     deal_limit_enabled: bool,
     deal_limit_count: String,
 
@@ -91,14 +236,27 @@ struct App {
     // Display Hand tab
     display_input: String,
     display_row: String,
-    display_result: String,
+    display_deal: Option<pipeline::DealDisplay>,
+    display_error: String,
+    display_loading: bool,
 
     // Package (Welcome tab)
     package_output: String,
     package_status: String,
 
+    // Batch tab: run Fetch -> Anonymize -> Analyze -> Package over every
+    // case subfolder of `batch_root`.
+    batch_root: String,
+    batch_cases: Vec<BatchCase>,
+    /// Index into `batch_cases` of the case currently running, if any.
+    batch_current: Option<usize>,
+    batch_running: bool,
+
     // Task state
-    fetch_cancel: Arc<AtomicBool>,
+    cancel_flag: Arc<AtomicBool>,
+    run_id: u64,
+    active_run_id: u64,
+    run_started_at: String,
     is_running: bool,
     running_tab: Option<TabId>,
     progress: f32,
@@ -109,63 +267,187 @@ struct App {
     fetch_start_time: Option<Instant>,
     status_text: String,
     log_lines: Vec<String>,
+
+    // Shared EMA-based throughput/ETA estimator (Fetch, Analyze, Package)
+    progress_rate_ema: f64,
+    progress_samples: usize,
+    progress_last_sample: Option<(Instant, usize)>,
+
+    // External control pipe (headless/automated runs)
+    external: Option<ExternalSession>,
+
+    // User-configurable theme
+    theme: Theme,
+    theme_name: String,
+    colors: AppColors,
+
+    // Durable activity history (History tab)
+    history: Vec<HistoryEntry>,
+    history_filter: Option<HistorySeverity>,
+
+    // Durable run ledger (History tab's Run Log section): one structured
+    // entry per finished or cancelled background run, most-recent-first.
+    run_ledger: Vec<RunRecord>,
+
+    // Keyboard command palette
+    keymap: HashMap<String, PaletteAction>,
+    palette_open: bool,
+    palette_query: String,
 }
 
 impl App {
     fn theme(&self) -> Theme {
-        Theme::Dark
+        self.theme.clone()
+    }
+
+    fn subscription(&self) -> iced::Subscription<Message> {
+        let keyboard = iced::keyboard::on_key_press(|key, modifiers| {
+            Some(Message::KeyPressed(key_to_chord(&key, modifiers)))
+        });
+
+        if self.case_folder.is_empty() {
+            return keyboard;
+        }
+
+        let watch = iced::Subscription::run_with_id(
+            self.case_folder.clone(),
+            watch_case_folder_stream(PathBuf::from(&self.case_folder)),
+        );
+
+        iced::Subscription::batch([keyboard, watch])
     }
 
     fn new() -> (Self, Task<Message>) {
-        let (deal_limit_enabled, deal_limit_count) = load_config();
-        (
-            App {
-                active_tab: TabId::Welcome,
-                case_folder: String::new(),
-                case_files: CaseFiles::default(),
-                case_usernames: Vec::new(),
-                deal_limit_enabled,
-                deal_limit_count,
-                fetch_input: String::new(),
-                fetch_output: String::new(),
-                fetch_delay: "20".to_string(),
-                fetch_batch_size: "100".to_string(),
-                fetch_batch_delay: "500".to_string(),
-                fetch_resume: false,
-                fetch_advanced_open: false,
-                fetch_row_count: None,
-                anon_input: String::new(),
-                anon_output: String::new(),
-                anon_map: String::new(),
-                analyze_input: String::new(),
-                analyze_output: String::new(),
-                analyze_threads: String::new(),
-                analyze_checkpoint: "100".to_string(),
-                analyze_resume: false,
-                analyze_advanced_open: false,
-                stats_input: String::new(),
-                stats_output: String::new(),
-                stats_top_n: "10".to_string(),
-                stats_result: String::new(),
-                display_input: String::new(),
-                display_row: "1".to_string(),
-                display_result: String::new(),
-                package_output: String::new(),
-                package_status: String::new(),
-                fetch_cancel: Arc::new(AtomicBool::new(false)),
-                is_running: false,
-                running_tab: None,
-                progress: 0.0,
-                progress_total: 0,
-                progress_completed: 0,
-                progress_errors: 0,
-                progress_skipped: 0,
-                fetch_start_time: None,
-                status_text: String::new(),
-                log_lines: Vec::new(),
+        let config = load_config();
+        let session = load_session_state();
+        let theme_name = if session.theme_name.is_empty() {
+            "dark".to_string()
+        } else {
+            session.theme_name.clone()
+        };
+        let (theme, colors) = load_named_theme(&theme_name);
+
+        let session_dir = SESSION_OVERRIDE
+            .get()
+            .cloned()
+            .flatten()
+            .unwrap_or_else(default_session_dir);
+        let (external, external_task) = match ExternalSession::start(session_dir) {
+            Ok((session, stream)) => (Some(session), Task::run(stream, |msg| msg)),
+            Err(e) => {
+                eprintln!("external control pipe disabled: {}", e);
+                (None, Task::none())
+            }
+        };
+
+        let mut app = App {
+            active_tab: TabId::Welcome,
+            case_folder: session.case_folder,
+            case_files: CaseFiles::default(),
+            case_usernames: Vec::new(),
+            case_files_note: String::new(),
+            recent_folders: config.recent_folders,
+            deal_limit_enabled: config.deal_limit_enabled,
+            deal_limit_count: config.deal_limit_count,
+            fetch_input: session.fetch_input,
+            fetch_output: session.fetch_output,
+            fetch_delay: if session.fetch_delay.is_empty() {
+                "20".to_string()
+            } else {
+                session.fetch_delay
             },
-            Task::none(),
-        )
+            fetch_batch_size: if session.fetch_batch_size.is_empty() {
+                "100".to_string()
+            } else {
+                session.fetch_batch_size
+            },
+            fetch_batch_delay: if session.fetch_batch_delay.is_empty() {
+                "500".to_string()
+            } else {
+                session.fetch_batch_delay
+            },
+            fetch_resume: session.fetch_resume,
+            fetch_advanced_open: session.fetch_advanced_open,
+            fetch_row_count: None,
+            anon_input: session.anon_input,
+            anon_output: session.anon_output,
+            anon_map: session.anon_map,
+            analyze_input: session.analyze_input,
+            analyze_output: session.analyze_output,
+            analyze_threads: session.analyze_threads,
+            analyze_checkpoint: if session.analyze_checkpoint.is_empty() {
+                "100".to_string()
+            } else {
+                session.analyze_checkpoint
+            },
+            analyze_resume: session.analyze_resume,
+            analyze_advanced_open: session.analyze_advanced_open,
+            stats_input: session.stats_input,
+            stats_output: session.stats_output,
+            stats_top_n: if session.stats_top_n.is_empty() {
+                "10".to_string()
+            } else {
+                session.stats_top_n
+            },
+            stats_result: String::new(),
+            display_input: session.display_input,
+            display_row: "1".to_string(),
+            display_deal: None,
+            display_error: String::new(),
+            display_loading: false,
+            package_output: session.package_output,
+            package_status: String::new(),
+            batch_root: String::new(),
+            batch_cases: Vec::new(),
+            batch_current: None,
+            batch_running: false,
+            cancel_flag: Arc::new(AtomicBool::new(false)),
+            run_id: 0,
+            active_run_id: 0,
+            run_started_at: String::new(),
+            is_running: false,
+            running_tab: None,
+            progress: 0.0,
+            progress_total: 0,
+            progress_completed: 0,
+            progress_errors: 0,
+            progress_skipped: 0,
+            fetch_start_time: None,
+            status_text: String::new(),
+            log_lines: Vec::new(),
+            progress_rate_ema: 0.0,
+            progress_samples: 0,
+            progress_last_sample: None,
+            external,
+            theme,
+            theme_name,
+            colors,
+            history: Vec::new(),
+            history_filter: None,
+            run_ledger: Vec::new(),
+            keymap: load_keymap(),
+            palette_open: false,
+            palette_query: String::new(),
+        };
+
+        // Re-derive the case-folder-scanned state (detected files, subject
+        // usernames, activity log) the restored case_folder implies, same
+        // as a fresh FolderSelected would, without touching the operator's
+        // restored paths and anonymize mapping.
+        if !app.case_folder.is_empty() {
+            let folder = PathBuf::from(&app.case_folder);
+            app.case_files = scan_case_folder(&folder);
+            app.case_usernames = app
+                .case_files
+                .concise_file
+                .as_deref()
+                .map(parse_concise_usernames)
+                .unwrap_or_default();
+            app.load_history();
+            app.load_run_ledger();
+        }
+
+        (app, external_task)
     }
 
     /// Shorten an absolute path to show from the case folder's parent on down.
@@ -199,6 +481,73 @@ impl App {
         }
     }
 
+    /// Arm a new background run: clear the cooperative-cancellation flag
+    /// and mint a fresh run id, returning it so the caller can tag the
+    /// background stream with it. `ProgressUpdate`/`TaskFinished`/
+    /// `PackageCompleted` messages carry the run id they were produced
+    /// for, and are ignored once a newer run has started — this is what
+    /// keeps a cancelled run's late-arriving result from clobbering the
+    /// run that replaced it.
+    fn start_run(&mut self) -> u64 {
+        self.cancel_flag.store(false, Ordering::Relaxed);
+        self.run_id += 1;
+        self.active_run_id = self.run_id;
+        self.run_started_at = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        self.run_id
+    }
+
+    /// Clear the throughput/ETA estimator. Called whenever a new
+    /// long-running task (Fetch, Analyze, Package) starts.
+    fn reset_progress_estimate(&mut self) {
+        self.progress_rate_ema = 0.0;
+        self.progress_samples = 0;
+        self.progress_last_sample = None;
+    }
+
+    /// Fold a new `completed`/`total` reading into the EMA-based rate
+    /// estimate. Blends the instantaneous rate since the last sample with
+    /// the running average at alpha=0.3, so a burst of slow or fast rows
+    /// doesn't swing the ETA wildly.
+    fn sample_progress_estimate(&mut self, completed: usize) {
+        const ALPHA: f64 = 0.3;
+        const MIN_RATE: f64 = 1e-6;
+
+        let now = Instant::now();
+        if let Some((last_instant, last_completed)) = self.progress_last_sample {
+            let dt = now.duration_since(last_instant).as_secs_f64();
+            if dt > 0.0 {
+                let instant_rate =
+                    (completed.saturating_sub(last_completed) as f64 / dt).max(MIN_RATE);
+                self.progress_rate_ema = if self.progress_samples == 0 {
+                    instant_rate
+                } else {
+                    ALPHA * instant_rate + (1.0 - ALPHA) * self.progress_rate_ema
+                };
+                self.progress_samples += 1;
+            }
+        }
+        self.progress_last_sample = Some((now, completed));
+    }
+
+    /// Render the shared progress line: "N/M • R boards/s • ETA mm:ss".
+    /// Falls back to "estimating..." until at least two samples exist.
+    fn progress_estimate_text(&self, completed: usize, total: usize) -> String {
+        if self.progress_samples < 2 || self.progress_rate_ema <= 0.0 {
+            return format!("{}/{} • estimating...", completed, total);
+        }
+
+        let rate = self.progress_rate_ema;
+        let remaining = total.saturating_sub(completed) as f64;
+        let eta_secs = (remaining / rate).max(0.0);
+        let eta_mins = (eta_secs / 60.0) as u64;
+        let eta_secs_part = (eta_secs as u64) % 60;
+
+        format!(
+            "{}/{} • {:.1} boards/s • ETA {:02}:{:02}",
+            completed, total, rate, eta_mins, eta_secs_part
+        )
+    }
+
     /// Count rows in the fetch input CSV and store the result.
     fn update_fetch_row_count(&mut self) {
         if self.fetch_input.is_empty() {
@@ -249,12 +598,335 @@ impl App {
         }
     }
 
+    /// Switch to case folder `p`: rescan it, wire up the dependent tab
+    /// inputs, and remember it in the recent-folders list. Shared by the
+    /// folder-browse dialog and the Welcome tab's recent-folders picker.
+    fn select_case_folder(&mut self, p: PathBuf) {
+        self.case_folder = p.display().to_string();
+        self.case_files = scan_case_folder(&p);
+
+        // Create EDGAR Defense subfolder
+        let edgar_dir = p.join("EDGAR Defense");
+        let _ = std::fs::create_dir_all(&edgar_dir);
+
+        // Load this case's durable activity history
+        self.load_history();
+        self.load_run_ledger();
+
+        // Parse subject usernames from concise report
+        self.case_usernames = if let Some(concise) = &self.case_files.concise_file {
+            parse_concise_usernames(concise)
+        } else {
+            Vec::new()
+        };
+
+        // Wire found CSV to Fetch input, output to EDGAR Defense folder
+        if let Some(csv) = &self.case_files.csv_file {
+            self.fetch_input = csv.display().to_string();
+            self.update_fetch_row_count();
+            self.update_fetch_output();
+            // Pre-populate anonymize input from fetch output
+            self.anon_input = self.fetch_output.clone();
+            self.update_anon_output();
+        }
+
+        // Default mappings: first subject = Bob, second = Sally
+        let default_names = ["Bob", "Sally"];
+        let map_parts: Vec<String> = self
+            .case_usernames
+            .iter()
+            .zip(default_names.iter())
+            .map(|(user, alias)| format!("{}={}", user, alias))
+            .collect();
+        self.anon_map = map_parts.join(",");
+
+        // Auto-derive package output path
+        let subject = self
+            .case_files
+            .concise_file
+            .as_deref()
+            .and_then(extract_concise_subject)
+            .unwrap_or_else(|| "Report".to_string());
+        self.package_output = format!(
+            "{}/EDGAR Defense/EDGAR Defense {}.xlsx",
+            p.display(),
+            subject
+        );
+        self.package_status.clear();
+        self.case_files_note.clear();
+
+        let folder = self.case_folder.clone();
+        self.recent_folders.retain(|f| f != &folder);
+        self.recent_folders.insert(0, folder);
+        self.recent_folders.truncate(MAX_RECENT_FOLDERS);
+        self.save_config();
+
+        self.persist_session_state();
+    }
+
+    /// Snapshot the deal-limit and recent-folders fields and write them to
+    /// `config_path()`. Called after every change to one of those fields.
+    fn save_config(&self) {
+        save_config(&Config {
+            deal_limit_enabled: self.deal_limit_enabled,
+            deal_limit_count: self.deal_limit_count.clone(),
+            recent_folders: self.recent_folders.clone(),
+        });
+    }
+
     /// Recompute analyze output from analyze input.
     fn update_analyze_output(&mut self) {
         if !self.analyze_input.is_empty() {
             self.analyze_output = derive_analyze_output(&self.analyze_input);
         }
     }
+
+    /// Input/output paths for the given tab, for the run ledger. Package
+    /// has no single "input" field, so its source is the case folder.
+    fn tab_io_paths(&self, tab: Option<TabId>) -> (String, String) {
+        match tab {
+            Some(TabId::Fetch) => (self.fetch_input.clone(), self.fetch_output.clone()),
+            Some(TabId::Anonymize) => (self.anon_input.clone(), self.anon_output.clone()),
+            Some(TabId::Analyze) => (self.analyze_input.clone(), self.analyze_output.clone()),
+            Some(TabId::Stats) => (self.stats_input.clone(), self.stats_output.clone()),
+            Some(TabId::Package) => (self.case_folder.clone(), self.package_output.clone()),
+            _ => (String::new(), String::new()),
+        }
+    }
+
+    /// Snapshot the restorable workspace fields and write them to
+    /// `session_state_path()`. Called after every change to one of those
+    /// fields, so the next launch reopens with the same paths and mappings
+    /// instead of forcing the operator to retype them.
+    fn persist_session_state(&self) {
+        save_session_state(&SessionState {
+            case_folder: self.case_folder.clone(),
+            fetch_input: self.fetch_input.clone(),
+            fetch_output: self.fetch_output.clone(),
+            fetch_delay: self.fetch_delay.clone(),
+            fetch_batch_size: self.fetch_batch_size.clone(),
+            fetch_batch_delay: self.fetch_batch_delay.clone(),
+            fetch_resume: self.fetch_resume,
+            fetch_advanced_open: self.fetch_advanced_open,
+            anon_input: self.anon_input.clone(),
+            anon_output: self.anon_output.clone(),
+            anon_map: self.anon_map.clone(),
+            analyze_input: self.analyze_input.clone(),
+            analyze_output: self.analyze_output.clone(),
+            analyze_threads: self.analyze_threads.clone(),
+            analyze_checkpoint: self.analyze_checkpoint.clone(),
+            analyze_resume: self.analyze_resume,
+            analyze_advanced_open: self.analyze_advanced_open,
+            stats_input: self.stats_input.clone(),
+            stats_output: self.stats_output.clone(),
+            stats_top_n: self.stats_top_n.clone(),
+            display_input: self.display_input.clone(),
+            package_output: self.package_output.clone(),
+            theme_name: self.theme_name.clone(),
+        });
+    }
+
+    /// Path to the durable activity log in the active case's EDGAR Defense folder.
+    fn history_log_path(&self) -> Option<PathBuf> {
+        if self.case_folder.is_empty() {
+            return None;
+        }
+        Some(
+            Path::new(&self.case_folder)
+                .join("EDGAR Defense")
+                .join("activity.log"),
+        )
+    }
+
+    /// Append a timestamped entry to the durable activity history: both
+    /// in-memory (for the History panel) and to the case's activity log, so
+    /// the trail survives across runs and app restarts. Kept separate from
+    /// the transient per-run `log_lines`.
+    fn record_history(&mut self, tab: Option<TabId>, severity: HistorySeverity, message: impl Into<String>) {
+        let message = message.into();
+        let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+
+        if let Some(log_path) = self.history_log_path() {
+            let severity_tag = match severity {
+                HistorySeverity::Info => "INFO",
+                HistorySeverity::Error => "ERROR",
+            };
+            let tab_tag = tab.map(tab_label).unwrap_or("General");
+            let line = format!("{} [{}] {}: {}\n", timestamp, tab_tag, severity_tag, message);
+            if let Some(parent) = log_path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            if let Ok(mut file) = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&log_path)
+            {
+                let _ = file.write_all(line.as_bytes());
+            }
+        }
+
+        self.history.push(HistoryEntry {
+            timestamp,
+            tab,
+            severity,
+            message,
+        });
+        if self.history.len() > MAX_HISTORY_ENTRIES {
+            self.history.remove(0);
+        }
+    }
+
+    /// Reload the durable activity history from the active case's log file.
+    fn load_history(&mut self) {
+        self.history = self
+            .history_log_path()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .map(|content| content.lines().filter_map(parse_history_line).collect())
+            .unwrap_or_default();
+        let keep_from = self.history.len().saturating_sub(MAX_HISTORY_ENTRIES);
+        self.history.drain(..keep_from);
+    }
+
+    /// Path to the durable run ledger in the active case's EDGAR Defense folder.
+    fn run_ledger_path(&self) -> Option<PathBuf> {
+        if self.case_folder.is_empty() {
+            return None;
+        }
+        Some(
+            Path::new(&self.case_folder)
+                .join("EDGAR Defense")
+                .join("run-ledger.log"),
+        )
+    }
+
+    /// Append one finished (or cancelled) run to the durable run ledger:
+    /// both in-memory (for the History tab's Run Log section) and to the
+    /// case's ledger file, so it survives across runs and app restarts.
+    /// `duration_secs` is measured from `fetch_start_time`, which every
+    /// `*Start` handler stamps regardless of which tab it belongs to.
+    fn record_run(
+        &mut self,
+        tab: Option<TabId>,
+        status: RunStatus,
+        boards_processed: usize,
+        errors: usize,
+        skipped: usize,
+        input_path: String,
+        output_path: String,
+    ) {
+        let duration_secs = self
+            .fetch_start_time
+            .map(|t| t.elapsed().as_secs_f64())
+            .unwrap_or(0.0);
+        let record = RunRecord {
+            tab,
+            started_at: self.run_started_at.clone(),
+            duration_secs,
+            status,
+            boards_processed,
+            errors,
+            skipped,
+            input_path,
+            output_path,
+        };
+
+        if let Some(path) = self.run_ledger_path() {
+            let line = format_run_record(&record);
+            if let Some(parent) = path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            if let Ok(mut file) = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+            {
+                let _ = file.write_all(line.as_bytes());
+            }
+        }
+
+        self.run_ledger.push(record);
+        if self.run_ledger.len() > MAX_HISTORY_ENTRIES {
+            self.run_ledger.remove(0);
+        }
+    }
+
+    /// Reload the durable run ledger from the active case's ledger file.
+    fn load_run_ledger(&mut self) {
+        self.run_ledger = self
+            .run_ledger_path()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .map(|content| content.lines().filter_map(parse_run_record).collect())
+            .unwrap_or_default();
+        let keep_from = self.run_ledger.len().saturating_sub(MAX_HISTORY_ENTRIES);
+        self.run_ledger.drain(..keep_from);
+    }
+}
+
+/// Render one `RunRecord` as a pipe-delimited line for the run ledger file.
+/// Paths are written last so a stray `|` inside one doesn't break earlier
+/// fields when reparsed with `parse_run_record`'s fixed split count.
+fn format_run_record(r: &RunRecord) -> String {
+    format!(
+        "{}|{}|{}|{:.3}|{}|{}|{}|{}|{}\n",
+        r.started_at,
+        r.tab.map(tab_label).unwrap_or("General"),
+        r.status.label(),
+        r.duration_secs,
+        r.boards_processed,
+        r.errors,
+        r.skipped,
+        r.input_path,
+        r.output_path,
+    )
+}
+
+/// Parse one line previously written by `format_run_record`.
+fn parse_run_record(line: &str) -> Option<RunRecord> {
+    let mut parts = line.splitn(9, '|');
+    let started_at = parts.next()?.to_string();
+    let tab = tab_from_label(parts.next()?);
+    let status = RunStatus::from_label(parts.next()?)?;
+    let duration_secs = parts.next()?.parse().ok()?;
+    let boards_processed = parts.next()?.parse().ok()?;
+    let errors = parts.next()?.parse().ok()?;
+    let skipped = parts.next()?.parse().ok()?;
+    let input_path = parts.next()?.to_string();
+    let output_path = parts.next().unwrap_or("").to_string();
+    Some(RunRecord {
+        tab,
+        started_at,
+        duration_secs,
+        status,
+        boards_processed,
+        errors,
+        skipped,
+        input_path,
+        output_path,
+    })
+}
+
+/// Parse one line previously written by `App::record_history`, e.g.
+/// `2026-07-30 12:34:56 [Fetch Cardplay] INFO: Starting fetch...`.
+fn parse_history_line(line: &str) -> Option<HistoryEntry> {
+    if line.len() < 19 {
+        return None;
+    }
+    let (timestamp, rest) = line.split_at(19);
+    let rest = rest.trim_start();
+    let rest = rest.strip_prefix('[')?;
+    let (tab_part, rest) = rest.split_once(']')?;
+    let rest = rest.trim_start();
+    let (severity_part, message) = rest.split_once(':')?;
+    let severity = match severity_part.trim() {
+        "ERROR" => HistorySeverity::Error,
+        _ => HistorySeverity::Info,
+    };
+    Some(HistoryEntry {
+        timestamp: timestamp.to_string(),
+        tab: tab_from_label(tab_part.trim()),
+        severity,
+        message: message.trim().to_string(),
+    })
 }
 
 // ============================================================================
@@ -273,17 +945,37 @@ enum Message {
     // Welcome / case folder
     BrowseFolder,
     FolderSelected(Option<PathBuf>),
+    RecentFolderSelected(String),
     DealLimitToggled(bool),
     DealLimitChanged(String),
+    CaseFolderChanged(CaseFiles, Vec<String>),
+    ThemeSelected(String),
 
     // Package (Welcome tab)
     PackageOutputChanged(String),
     BrowsePackageOutput,
     PackageOutputSelected(Option<PathBuf>),
     PackageStart,
-    PackageCompleted(Result<String, String>),
+    PackageCompleted(u64, Result<String, String>),
     OpenPackage,
 
+    // Batch tab
+    BatchBrowseRoot,
+    BatchRootSelected(Option<PathBuf>),
+    BatchStart,
+    BatchCancel,
+    BatchCaseStarted(u64, usize),
+    BatchCaseProgress {
+        run_id: u64,
+        index: usize,
+        completed: usize,
+        total: usize,
+        errors: usize,
+        skipped: usize,
+    },
+    BatchCaseCompleted(u64, usize, Result<String, String>),
+    BatchAllDone(u64),
+
     // Fetch tab
     FetchInputChanged(String),
     FetchOutputChanged(String),
@@ -299,7 +991,7 @@ enum Message {
     AnonInputChanged(String),
     AnonMapChanged(String),
     AnonStart,
-    AnonCompleted(Result<String, String>),
+    AnonCancel,
 
     // Analyze tab
     AnalyzeInputChanged(String),
@@ -316,22 +1008,41 @@ enum Message {
     StatsOutputChanged(String),
     StatsTopNChanged(String),
     StatsRun,
-    StatsCompleted(Result<String, String>),
+    StatsCancel,
 
     // Display Hand tab
     DisplayInputChanged(String),
     DisplayRowChanged(String),
     DisplayShow,
-    DisplayCompleted(Result<String, String>),
+    DisplayCompleted(Result<pipeline::DealDisplay, String>),
 
-    // Background task progress
+    // Background task progress. `run_id` is the id minted by `start_run`
+    // when the task was launched; stale messages from a cancelled run that
+    // raced with a newer one are dropped rather than applied.
     ProgressUpdate {
+        run_id: u64,
         completed: usize,
         total: usize,
         errors: usize,
         skipped: usize,
     },
-    TaskFinished(Result<String, String>),
+    TaskFinished(u64, Result<String, String>),
+
+    // External control pipe
+    ExternalCommand(ExternalMsg),
+    ExternalCommandError(String),
+
+    // History tab
+    HistoryFilterChanged(Option<HistorySeverity>),
+
+    // Command palette / keymap
+    KeyPressed(String),
+    PaletteQueryChanged(String),
+    PaletteExecute(PaletteAction),
+
+    // DD benchmark (hidden/advanced, reached via the command palette only)
+    BenchmarkStart,
+    BenchmarkCompleted(u64, Result<String, String>),
 }
 
 // ============================================================================
@@ -361,54 +1072,26 @@ impl App {
 
             Message::FolderSelected(path) => {
                 if let Some(p) = path {
-                    self.case_folder = p.display().to_string();
-                    self.case_files = scan_case_folder(&p);
-
-                    // Create EDGAR Defense subfolder
-                    let edgar_dir = p.join("EDGAR Defense");
-                    let _ = std::fs::create_dir_all(&edgar_dir);
-
-                    // Parse subject usernames from concise report
-                    self.case_usernames = if let Some(concise) = &self.case_files.concise_file {
-                        parse_concise_usernames(concise)
-                    } else {
-                        Vec::new()
-                    };
+                    self.select_case_folder(p);
+                }
+                Task::none()
+            }
 
-                    // Wire found CSV to Fetch input, output to EDGAR Defense folder
-                    if let Some(csv) = &self.case_files.csv_file {
-                        self.fetch_input = csv.display().to_string();
-                        self.update_fetch_row_count();
-                        self.update_fetch_output();
-                        // Pre-populate anonymize input from fetch output
-                        self.anon_input = self.fetch_output.clone();
-                        self.update_anon_output();
-                    }
+            Message::RecentFolderSelected(folder) => {
+                self.select_case_folder(PathBuf::from(folder));
+                Task::none()
+            }
 
-                    // Default mappings: first subject = Bob, second = Sally
-                    let default_names = ["Bob", "Sally"];
-                    let map_parts: Vec<String> = self
-                        .case_usernames
-                        .iter()
-                        .zip(default_names.iter())
-                        .map(|(user, alias)| format!("{}={}", user, alias))
-                        .collect();
-                    self.anon_map = map_parts.join(",");
-
-                    // Auto-derive package output path
-                    let subject = self
-                        .case_files
-                        .concise_file
-                        .as_deref()
-                        .and_then(extract_concise_subject)
-                        .unwrap_or_else(|| "Report".to_string());
-                    self.package_output = format!(
-                        "{}/EDGAR Defense/EDGAR Defense {}.xlsx",
-                        p.display(),
-                        subject
-                    );
-                    self.package_status.clear();
-                }
+            // A debounced filesystem event fired for the active case folder:
+            // just refresh the detected files and subject players, leaving
+            // the operator's other inputs (fetch/anon paths, mappings) alone.
+            Message::CaseFolderChanged(case_files, case_usernames) => {
+                self.case_files = case_files;
+                self.case_usernames = case_usernames;
+                self.case_files_note = format!(
+                    "Case folder files changed, refreshed at {}",
+                    chrono::Local::now().format("%H:%M:%S")
+                );
                 Task::none()
             }
 
@@ -452,6 +1135,7 @@ impl App {
                         (TabId::Display, FileKind::Input) => self.display_input = path_str,
                         _ => {}
                     }
+                    self.persist_session_state();
                 }
                 Task::none()
             }
@@ -459,20 +1143,29 @@ impl App {
             // -- Deal limit --
             Message::DealLimitToggled(v) => {
                 self.deal_limit_enabled = v;
-                save_config(self.deal_limit_enabled, &self.deal_limit_count);
+                self.save_config();
                 self.update_fetch_output();
                 Task::none()
             }
             Message::DealLimitChanged(v) => {
                 self.deal_limit_count = v;
-                save_config(self.deal_limit_enabled, &self.deal_limit_count);
+                self.save_config();
                 self.update_fetch_output();
                 Task::none()
             }
+            Message::ThemeSelected(name) => {
+                let (theme, colors) = load_named_theme(&name);
+                self.theme = theme;
+                self.colors = colors;
+                self.theme_name = name;
+                self.persist_session_state();
+                Task::none()
+            }
 
             // -- Package --
             Message::PackageOutputChanged(v) => {
                 self.package_output = self.expand_path(&v);
+                self.persist_session_state();
                 Task::none()
             }
             Message::BrowsePackageOutput => Task::perform(
@@ -521,18 +1214,57 @@ impl App {
                     subject_players: self.case_usernames.clone(),
                     deal_limit: self.deal_limit(),
                     cardplay_file,
+                    is_anon: false,
+                    classifier_corpus: None,
+                    output_format: pipeline::OutputFormat::Xlsx,
+                    hyperlink_dialect: pipeline::HyperlinkDialect::Excel,
+                    flat_export: None,
+                    category_palette: None,
+                    category_color_overrides: std::collections::HashMap::new(),
                 };
                 self.is_running = true;
                 self.running_tab = Some(TabId::Package);
                 self.package_status = "Creating workbook...".to_string();
-                Task::perform(
-                    async move { pipeline::package_workbook(&config).map_err(|e| e.to_string()) },
-                    Message::PackageCompleted,
-                )
+                self.record_history(Some(TabId::Package), HistorySeverity::Info, "Creating workbook...");
+                self.progress = 0.0;
+                self.progress_completed = 0;
+                self.progress_total = 0;
+                self.progress_errors = 0;
+                self.progress_skipped = 0;
+                self.fetch_start_time = Some(Instant::now());
+                self.reset_progress_estimate();
+                let run_id = self.start_run();
+
+                Task::run(package_workbook_stream(config, run_id), |msg| msg)
             }
-            Message::PackageCompleted(result) => {
+            Message::PackageCompleted(run_id, result) => {
+                if run_id != self.active_run_id {
+                    return Task::none();
+                }
                 self.is_running = false;
                 self.running_tab = None;
+                if let Some(ext) = &self.external {
+                    match &result {
+                        Ok(_) => ext.send_result("ok"),
+                        Err(e) => ext.send_result(&format!("error: {}", e)),
+                    }
+                }
+                match &result {
+                    Ok(s) => self.record_history(Some(TabId::Package), HistorySeverity::Info, s.clone()),
+                    Err(e) => self.record_history(
+                        Some(TabId::Package),
+                        HistorySeverity::Error,
+                        format!("Error: {}", e),
+                    ),
+                }
+                let run_status = if result.is_ok() {
+                    RunStatus::Completed
+                } else {
+                    RunStatus::Failed
+                };
+                let (input_path, output_path) = self.tab_io_paths(Some(TabId::Package));
+                self.record_run(Some(TabId::Package), run_status, 0, 0, 0, input_path, output_path);
+                self.fetch_start_time = None;
                 match result {
                     Ok(s) => self.package_status = s,
                     Err(e) => self.package_status = format!("Error: {}", e),
@@ -547,81 +1279,253 @@ impl App {
                 Task::none()
             }
 
-            // -- Fetch tab --
-            Message::FetchInputChanged(v) => {
-                self.fetch_input = self.expand_path(&v);
-                self.update_fetch_row_count();
-                self.update_fetch_output();
-                Task::none()
-            }
-            Message::FetchOutputChanged(v) => {
-                self.fetch_output = self.expand_path(&v);
-                Task::none()
-            }
-            Message::FetchDelayChanged(v) => {
-                self.fetch_delay = v;
-                Task::none()
-            }
-            Message::FetchBatchSizeChanged(v) => {
-                self.fetch_batch_size = v;
-                Task::none()
-            }
-            Message::FetchBatchDelayChanged(v) => {
-                self.fetch_batch_delay = v;
-                Task::none()
-            }
-            Message::FetchResumeToggled(v) => {
-                self.fetch_resume = v;
-                Task::none()
-            }
-            Message::ToggleFetchAdvanced => {
-                self.fetch_advanced_open = !self.fetch_advanced_open;
+            // -- Batch tab --
+            Message::BatchBrowseRoot => Task::perform(
+                async {
+                    let folder = rfd::AsyncFileDialog::new()
+                        .set_title("Select parent folder containing case subfolders")
+                        .pick_folder()
+                        .await;
+                    folder.map(|f| f.path().to_path_buf())
+                },
+                Message::BatchRootSelected,
+            ),
+            Message::BatchRootSelected(path) => {
+                if let Some(p) = path {
+                    self.batch_root = p.display().to_string();
+                    self.batch_cases = scan_batch_root(&p);
+                    self.batch_current = None;
+                }
                 Task::none()
             }
-            Message::FetchStart => {
+            Message::BatchStart => {
+                if self.batch_cases.is_empty() {
+                    return Task::none();
+                }
+                self.batch_running = true;
+                self.running_tab = Some(TabId::Batch);
                 self.is_running = true;
-                self.running_tab = Some(TabId::Fetch);
-                self.status_text = "Starting fetch...".to_string();
-                self.log_lines.clear();
+                for case in &mut self.batch_cases {
+                    case.status = None;
+                }
+                self.status_text = format!("Batch: processing {} cases...", self.batch_cases.len());
+                self.record_history(
+                    Some(TabId::Batch),
+                    HistorySeverity::Info,
+                    format!("Starting batch over {} cases", self.batch_cases.len()),
+                );
                 self.progress = 0.0;
                 self.progress_completed = 0;
                 self.progress_total = 0;
                 self.progress_errors = 0;
                 self.progress_skipped = 0;
                 self.fetch_start_time = Some(Instant::now());
-                self.fetch_cancel.store(false, Ordering::Relaxed);
-
-                let input_path = self.fetch_input.clone();
+                self.reset_progress_estimate();
+                let run_id = self.start_run();
+                let cancel = self.cancel_flag.clone();
                 let deal_limit = self.deal_limit();
-                let cancel = self.fetch_cancel.clone();
-
-                let config = pipeline::FetchCardplayConfig {
-                    input: PathBuf::from(&input_path),
-                    output: PathBuf::from(&self.fetch_output),
-                    url_column: "BBO".to_string(),
-                    delay_ms: self.fetch_delay.parse().unwrap_or(20),
-                    batch_size: self.fetch_batch_size.parse().unwrap_or(100),
-                    batch_delay_ms: self.fetch_batch_delay.parse().unwrap_or(500),
-                    resume: self.fetch_resume,
-                };
+                let cases = self.batch_cases.clone();
 
-                Task::run(fetch_cardplay_stream(config, deal_limit, cancel), |msg| msg)
+                Task::run(batch_pipeline_stream(cases, deal_limit, cancel, run_id), |msg| msg)
             }
-            Message::FetchCancel => {
-                self.fetch_cancel.store(true, Ordering::Relaxed);
-                // Don't immediately reset state — let TaskFinished handle cleanup
-                self.status_text = "Cancelling...".to_string();
+            Message::BatchCancel => {
+                self.cancel_flag.store(true, Ordering::Relaxed);
+                self.status_text = "Cancelling batch...".to_string();
+                self.record_history(Some(TabId::Batch), HistorySeverity::Info, "Cancelling batch...");
                 Task::none()
             }
-
-            // -- Anonymize tab --
+            Message::BatchCaseStarted(run_id, index) => {
+                if run_id != self.active_run_id {
+                    return Task::none();
+                }
+                self.batch_current = Some(index);
+                self.progress_completed = 0;
+                self.progress_total = 0;
+                self.progress_errors = 0;
+                self.progress_skipped = 0;
+                if let Some(case) = self.batch_cases.get(index) {
+                    self.status_text = format!(
+                        "Batch: case {}/{} - {}",
+                        index + 1,
+                        self.batch_cases.len(),
+                        case.folder.display()
+                    );
+                }
+                Task::none()
+            }
+            Message::BatchCaseProgress {
+                run_id,
+                index,
+                completed,
+                total,
+                errors,
+                skipped,
+            } => {
+                if run_id != self.active_run_id || self.batch_current != Some(index) {
+                    return Task::none();
+                }
+                self.progress_completed = completed;
+                self.progress_total = total;
+                self.progress_errors = errors;
+                self.progress_skipped = skipped;
+                self.progress = if total > 0 {
+                    completed as f32 / total as f32
+                } else {
+                    0.0
+                };
+                Task::none()
+            }
+            Message::BatchCaseCompleted(run_id, index, result) => {
+                if run_id != self.active_run_id {
+                    return Task::none();
+                }
+                let status = match &result {
+                    Ok(_) => RunStatus::Completed,
+                    Err(e) if e == "cancelled" => RunStatus::Cancelled,
+                    Err(_) => RunStatus::Failed,
+                };
+                if let Some(case) = self.batch_cases.get_mut(index) {
+                    case.status = Some(status);
+                    let folder = case.folder.display().to_string();
+                    match &result {
+                        Ok(_) => self.record_history(
+                            Some(TabId::Batch),
+                            HistorySeverity::Info,
+                            format!("Batch case completed: {}", folder),
+                        ),
+                        Err(e) if e == "cancelled" => self.record_history(
+                            Some(TabId::Batch),
+                            HistorySeverity::Info,
+                            format!("Batch case cancelled: {}", folder),
+                        ),
+                        Err(e) => self.record_history(
+                            Some(TabId::Batch),
+                            HistorySeverity::Error,
+                            format!("Batch case failed: {} ({})", folder, e),
+                        ),
+                    }
+                    self.record_run(Some(TabId::Batch), status, 0, 0, 0, folder, String::new());
+                }
+                Task::none()
+            }
+            Message::BatchAllDone(run_id) => {
+                if run_id != self.active_run_id {
+                    return Task::none();
+                }
+                self.batch_running = false;
+                self.is_running = false;
+                self.running_tab = None;
+                self.batch_current = None;
+                let completed = self
+                    .batch_cases
+                    .iter()
+                    .filter(|c| c.status == Some(RunStatus::Completed))
+                    .count();
+                let failed = self
+                    .batch_cases
+                    .iter()
+                    .filter(|c| c.status == Some(RunStatus::Failed))
+                    .count();
+                self.status_text = format!(
+                    "Batch finished: {} completed, {} failed, {} total",
+                    completed,
+                    failed,
+                    self.batch_cases.len()
+                );
+                self.fetch_start_time = None;
+                Task::none()
+            }
+
+            // -- Fetch tab --
+            Message::FetchInputChanged(v) => {
+                self.fetch_input = self.expand_path(&v);
+                self.update_fetch_row_count();
+                self.update_fetch_output();
+                self.persist_session_state();
+                Task::none()
+            }
+            Message::FetchOutputChanged(v) => {
+                self.fetch_output = self.expand_path(&v);
+                self.persist_session_state();
+                Task::none()
+            }
+            Message::FetchDelayChanged(v) => {
+                self.fetch_delay = v;
+                self.persist_session_state();
+                Task::none()
+            }
+            Message::FetchBatchSizeChanged(v) => {
+                self.fetch_batch_size = v;
+                self.persist_session_state();
+                Task::none()
+            }
+            Message::FetchBatchDelayChanged(v) => {
+                self.fetch_batch_delay = v;
+                self.persist_session_state();
+                Task::none()
+            }
+            Message::FetchResumeToggled(v) => {
+                self.fetch_resume = v;
+                self.persist_session_state();
+                Task::none()
+            }
+            Message::ToggleFetchAdvanced => {
+                self.fetch_advanced_open = !self.fetch_advanced_open;
+                self.persist_session_state();
+                Task::none()
+            }
+            Message::FetchStart => {
+                self.is_running = true;
+                self.running_tab = Some(TabId::Fetch);
+                self.status_text = "Starting fetch...".to_string();
+                self.record_history(Some(TabId::Fetch), HistorySeverity::Info, "Starting fetch...");
+                self.log_lines.clear();
+                self.progress = 0.0;
+                self.progress_completed = 0;
+                self.progress_total = 0;
+                self.progress_errors = 0;
+                self.progress_skipped = 0;
+                self.fetch_start_time = Some(Instant::now());
+                self.reset_progress_estimate();
+                let run_id = self.start_run();
+
+                let input_path = self.fetch_input.clone();
+                let deal_limit = self.deal_limit();
+                let cancel = self.cancel_flag.clone();
+
+                let config = pipeline::FetchCardplayConfig {
+                    input: PathBuf::from(&input_path),
+                    output: PathBuf::from(&self.fetch_output),
+                    url_column: "BBO".to_string(),
+                    delay_ms: self.fetch_delay.parse().unwrap_or(20),
+                    batch_size: self.fetch_batch_size.parse().unwrap_or(100),
+                    batch_delay_ms: self.fetch_batch_delay.parse().unwrap_or(500),
+                    resume: self.fetch_resume,
+                };
+
+                Task::run(
+                    fetch_cardplay_stream(config, deal_limit, cancel, run_id),
+                    |msg| msg,
+                )
+            }
+            Message::FetchCancel => {
+                self.cancel_flag.store(true, Ordering::Relaxed);
+                // Don't immediately reset state — let TaskFinished handle cleanup
+                self.status_text = "Cancelling...".to_string();
+                self.record_history(Some(TabId::Fetch), HistorySeverity::Info, "Cancelling fetch...");
+                Task::none()
+            }
+
+            // -- Anonymize tab --
             Message::AnonInputChanged(v) => {
                 self.anon_input = self.expand_path(&v);
                 self.update_anon_output();
+                self.persist_session_state();
                 Task::none()
             }
             Message::AnonMapChanged(v) => {
                 self.anon_map = v;
+                self.persist_session_state();
                 Task::none()
             }
             Message::AnonStart => {
@@ -664,29 +1568,32 @@ impl App {
                     concise_output,
                     hotspot_input,
                     hotspot_output,
+                    subject_players: self.case_usernames.clone(),
+                    live_resolve_urls: false,
+                    url_cache_path: None,
+                    url_cache_ttl: None,
+                    url_rules_path: None,
                 };
                 self.is_running = true;
                 self.running_tab = Some(TabId::Anonymize);
                 self.status_text = "Anonymizing...".to_string();
-                Task::perform(
-                    async move { pipeline::anonymize_all(&config).map_err(|e| e.to_string()) },
-                    Message::AnonCompleted,
-                )
+                self.record_history(Some(TabId::Anonymize), HistorySeverity::Info, "Anonymizing...");
+                self.progress = 0.0;
+                self.progress_completed = 0;
+                self.progress_total = 0;
+                self.progress_errors = 0;
+                self.progress_skipped = 0;
+                self.fetch_start_time = Some(Instant::now());
+                self.reset_progress_estimate();
+                let run_id = self.start_run();
+                let cancel = self.cancel_flag.clone();
+
+                Task::run(anon_stream(config, cancel, run_id), |msg| msg)
             }
-            Message::AnonCompleted(result) => {
-                self.is_running = false;
-                self.running_tab = None;
-                match &result {
-                    Ok(s) => {
-                        self.status_text = s.clone();
-                        // Chain: set analyze input to anon output
-                        self.analyze_input = self.anon_output.clone();
-                        self.update_analyze_output();
-                    }
-                    Err(e) => {
-                        self.status_text = format!("Error: {}", e);
-                    }
-                }
+            Message::AnonCancel => {
+                self.cancel_flag.store(true, Ordering::Relaxed);
+                self.status_text = "Cancelling...".to_string();
+                self.record_history(Some(TabId::Anonymize), HistorySeverity::Info, "Cancelling anonymize...");
                 Task::none()
             }
 
@@ -694,108 +1601,187 @@ impl App {
             Message::AnalyzeInputChanged(v) => {
                 self.analyze_input = self.expand_path(&v);
                 self.update_analyze_output();
+                self.persist_session_state();
                 Task::none()
             }
             Message::AnalyzeOutputChanged(v) => {
                 self.analyze_output = self.expand_path(&v);
+                self.persist_session_state();
                 Task::none()
             }
             Message::AnalyzeThreadsChanged(v) => {
                 self.analyze_threads = v;
+                self.persist_session_state();
                 Task::none()
             }
             Message::AnalyzeCheckpointChanged(v) => {
                 self.analyze_checkpoint = v;
+                self.persist_session_state();
                 Task::none()
             }
             Message::AnalyzeResumeToggled(v) => {
                 self.analyze_resume = v;
+                self.persist_session_state();
                 Task::none()
             }
             Message::ToggleAnalyzeAdvanced => {
                 self.analyze_advanced_open = !self.analyze_advanced_open;
+                self.persist_session_state();
                 Task::none()
             }
             Message::AnalyzeStart => {
                 self.is_running = true;
                 self.running_tab = Some(TabId::Analyze);
                 self.status_text = "Running DD analysis...".to_string();
+                self.record_history(Some(TabId::Analyze), HistorySeverity::Info, "Running DD analysis...");
                 self.log_lines.clear();
+                self.progress = 0.0;
+                self.progress_completed = 0;
+                self.progress_total = 0;
+                self.progress_errors = 0;
+                self.progress_skipped = 0;
+                self.fetch_start_time = Some(Instant::now());
+                self.reset_progress_estimate();
+                let run_id = self.start_run();
+                let cancel = self.cancel_flag.clone();
+
+                let config = pipeline::AnalyzeDdConfig {
+                    input: PathBuf::from(&self.analyze_input),
+                    output: PathBuf::from(&self.analyze_output),
+                    threads: self.analyze_threads.parse().ok(),
+                    resume: self.analyze_resume,
+                    checkpoint_interval: self.analyze_checkpoint.parse().unwrap_or(100),
+                    error_mode: pipeline::DdErrorMode::default(),
+                };
 
-                let input_path = self.analyze_input.clone();
-                let output = self.analyze_output.clone();
-                let threads = self.analyze_threads.clone();
-                let checkpoint = self.analyze_checkpoint.clone();
-                let resume = self.analyze_resume;
+                Task::run(analyze_dd_stream(config, cancel, run_id), |msg| msg)
+            }
+            Message::AnalyzeCancel => {
+                self.cancel_flag.store(true, Ordering::Relaxed);
+                // Don't immediately reset state — let TaskFinished handle cleanup,
+                // consistent with FetchCancel, so the checkpointed partial output
+                // from analyze_dd's own cancellation handling isn't discarded.
+                self.status_text = "Cancelling...".to_string();
+                self.record_history(Some(TabId::Analyze), HistorySeverity::Info, "Cancelling analysis...");
+                Task::none()
+            }
 
-                Task::perform(
-                    async move {
-                        let mut args = vec![
-                            "analyze-dd".to_string(),
-                            "--input".to_string(),
-                            input_path,
-                            "--output".to_string(),
-                            output,
-                        ];
-                        if !threads.is_empty() {
-                            args.push("--threads".to_string());
-                            args.push(threads);
-                        }
-                        args.push("--checkpoint-interval".to_string());
-                        args.push(checkpoint);
-                        if resume {
-                            args.push("--resume".to_string());
-                        }
+            // -- DD benchmark (hidden/advanced) --
+            Message::BenchmarkStart => {
+                if self.analyze_input.trim().is_empty() {
+                    return Task::none();
+                }
+                self.is_running = true;
+                self.running_tab = Some(TabId::Analyze);
+                self.status_text = "Benchmarking DD throughput...".to_string();
+                self.record_history(Some(TabId::Analyze), HistorySeverity::Info, "Benchmarking DD throughput...");
+                self.log_lines.clear();
+                self.progress = 0.0;
+                self.fetch_start_time = Some(Instant::now());
+                let run_id = self.start_run();
+
+                let mut args = vec![
+                    "analyze-dd".to_string(),
+                    "--input".to_string(),
+                    self.analyze_input.clone(),
+                    "--output".to_string(),
+                    "/dev/null".to_string(),
+                    "--bench".to_string(),
+                ];
+                if let Ok(threads) = self.analyze_threads.parse::<usize>() {
+                    args.push("--threads".to_string());
+                    args.push(threads.to_string());
+                }
 
-                        run_bbo_csv(args)
-                    },
-                    Message::TaskFinished,
+                Task::perform(
+                    async move { run_bbo_csv(args) },
+                    move |result| Message::BenchmarkCompleted(run_id, result),
                 )
             }
-            Message::AnalyzeCancel => {
+            Message::BenchmarkCompleted(run_id, result) => {
+                if run_id != self.active_run_id {
+                    return Task::none();
+                }
                 self.is_running = false;
                 self.running_tab = None;
-                self.status_text = "Cancelled.".to_string();
+                match &result {
+                    Ok(s) => {
+                        self.status_text = "Benchmark complete.".to_string();
+                        for line in s.lines() {
+                            self.log_lines.push(line.to_string());
+                        }
+                        self.record_history(Some(TabId::Analyze), HistorySeverity::Info, "Benchmark complete.");
+                    }
+                    Err(e) => {
+                        self.status_text = format!("Error: {}", e);
+                        self.log_lines.push(format!("ERROR: {}", e));
+                        self.record_history(Some(TabId::Analyze), HistorySeverity::Error, format!("Error: {}", e));
+                    }
+                }
+                let run_status = if result.is_ok() { RunStatus::Completed } else { RunStatus::Failed };
+                self.record_run(
+                    Some(TabId::Analyze),
+                    run_status,
+                    0,
+                    0,
+                    0,
+                    self.analyze_input.clone(),
+                    String::new(),
+                );
+                self.fetch_start_time = None;
                 Task::none()
             }
 
             // -- Stats tab --
             Message::StatsInputChanged(v) => {
                 self.stats_input = self.expand_path(&v);
+                self.persist_session_state();
                 Task::none()
             }
             Message::StatsOutputChanged(v) => {
                 self.stats_output = self.expand_path(&v);
+                self.persist_session_state();
                 Task::none()
             }
             Message::StatsTopNChanged(v) => {
                 self.stats_top_n = v;
+                self.persist_session_state();
                 Task::none()
             }
             Message::StatsRun => {
                 let input = self.stats_input.clone();
-                let top_n = self.stats_top_n.clone();
-                self.stats_result = "Computing statistics...".to_string();
-                Task::perform(
-                    async move {
-                        let top_n: usize = top_n.parse().unwrap_or(10);
-                        pipeline::compute_stats(std::path::Path::new(&input), top_n)
-                            .map_err(|e| e.to_string())
-                    },
-                    Message::StatsCompleted,
+                let top_n: usize = self.stats_top_n.parse().unwrap_or(10);
+                self.is_running = true;
+                self.running_tab = Some(TabId::Stats);
+                self.status_text = "Computing statistics...".to_string();
+                self.stats_result.clear();
+                self.record_history(Some(TabId::Stats), HistorySeverity::Info, "Computing statistics...");
+                self.progress = 0.0;
+                self.progress_completed = 0;
+                self.progress_total = 0;
+                self.progress_errors = 0;
+                self.progress_skipped = 0;
+                self.fetch_start_time = Some(Instant::now());
+                self.reset_progress_estimate();
+                let run_id = self.start_run();
+                let cancel = self.cancel_flag.clone();
+
+                Task::run(
+                    stats_stream(PathBuf::from(input), top_n, cancel, run_id),
+                    |msg| msg,
                 )
             }
-            Message::StatsCompleted(result) => {
-                self.stats_result = match result {
-                    Ok(s) => s,
-                    Err(e) => format!("Error: {}", e),
-                };
+            Message::StatsCancel => {
+                self.cancel_flag.store(true, Ordering::Relaxed);
+                self.status_text = "Cancelling...".to_string();
+                self.record_history(Some(TabId::Stats), HistorySeverity::Info, "Cancelling stats...");
                 Task::none()
             }
 
             // -- Display Hand tab --
             Message::DisplayInputChanged(v) => {
                 self.display_input = self.expand_path(&v);
+                self.persist_session_state();
                 Task::none()
             }
             Message::DisplayRowChanged(v) => {
@@ -805,47 +1791,77 @@ impl App {
             Message::DisplayShow => {
                 let input = self.display_input.clone();
                 let row = self.display_row.clone();
-                self.display_result = "Loading...".to_string();
+                self.display_loading = true;
+                self.display_error.clear();
+                self.record_history(Some(TabId::Display), HistorySeverity::Info, "Loading hand...");
                 Task::perform(
                     async move {
                         let row_num: usize = row
                             .parse()
                             .map_err(|_| format!("Invalid row number: {}", row))?;
-                        pipeline::display_hand(std::path::Path::new(&input), row_num)
+                        pipeline::display_hand_structured(std::path::Path::new(&input), row_num)
                             .map_err(|e| e.to_string())
                     },
                     Message::DisplayCompleted,
                 )
             }
             Message::DisplayCompleted(result) => {
-                self.display_result = match result {
-                    Ok(s) => s,
-                    Err(e) => format!("Error: {}", e),
-                };
+                self.display_loading = false;
+                match &result {
+                    Ok(_) => self.record_history(Some(TabId::Display), HistorySeverity::Info, "Hand loaded."),
+                    Err(e) => self.record_history(
+                        Some(TabId::Display),
+                        HistorySeverity::Error,
+                        format!("Error: {}", e),
+                    ),
+                }
+                match result {
+                    Ok(deal) => {
+                        self.display_deal = Some(deal);
+                        self.display_error.clear();
+                    }
+                    Err(e) => {
+                        self.display_deal = None;
+                        self.display_error = format!("Error: {}", e);
+                    }
+                }
                 Task::none()
             }
 
             // -- Background task progress --
             Message::ProgressUpdate {
+                run_id,
                 completed,
                 total,
                 errors,
                 skipped,
             } => {
+                if run_id != self.active_run_id {
+                    return Task::none();
+                }
                 self.progress_completed = completed;
                 self.progress_total = total;
                 self.progress_errors = errors;
                 self.progress_skipped = skipped;
+                self.sample_progress_estimate(completed.saturating_sub(skipped));
                 self.progress = if total > 0 {
                     completed as f32 / total as f32
                 } else {
                     0.0
                 };
+                if let Some(ext) = &self.external {
+                    ext.send_progress(&format!(
+                        "completed={} total={} errors={} skipped={}",
+                        completed, total, errors, skipped
+                    ));
+                }
                 Task::none()
             }
-            Message::TaskFinished(result) => {
+            Message::TaskFinished(run_id, result) => {
+                if run_id != self.active_run_id {
+                    return Task::none();
+                }
                 self.is_running = false;
-                self.fetch_start_time = None;
                 let finished_tab = self.running_tab.take();
                 match &result {
                     Ok(s) => {
@@ -853,12 +1869,37 @@ impl App {
                         for line in s.lines() {
                             self.log_lines.push(line.to_string());
                         }
+                        self.record_history(finished_tab, HistorySeverity::Info, "Completed successfully.");
+                        if self.progress_skipped > 0 {
+                            self.record_history(
+                                finished_tab,
+                                HistorySeverity::Info,
+                                format!("Skipped {} rows", self.progress_skipped),
+                            );
+                        }
                     }
                     Err(e) => {
                         self.status_text = format!("Error: {}", e);
                         self.log_lines.push(format!("ERROR: {}", e));
+                        self.record_history(finished_tab, HistorySeverity::Error, format!("Error: {}", e));
                     }
                 }
+                let run_status = match &result {
+                    Ok(_) => RunStatus::Completed,
+                    Err(e) if e.starts_with("Cancelled after") => RunStatus::Cancelled,
+                    Err(_) => RunStatus::Failed,
+                };
+                let (input_path, output_path) = self.tab_io_paths(finished_tab);
+                self.record_run(
+                    finished_tab,
+                    run_status,
+                    self.progress_completed,
+                    self.progress_errors,
+                    self.progress_skipped,
+                    input_path,
+                    output_path,
+                );
+                self.fetch_start_time = None;
                 // Chain outputs to next stage inputs
                 if result.is_ok() {
                     match finished_tab {
@@ -866,6 +1907,10 @@ impl App {
                             self.anon_input = self.fetch_output.clone();
                             self.update_anon_output();
                         }
+                        Some(TabId::Anonymize) => {
+                            self.analyze_input = self.anon_output.clone();
+                            self.update_analyze_output();
+                        }
                         Some(TabId::Analyze) => {
                             self.stats_input = self.analyze_output.clone();
                             self.display_input = self.analyze_output.clone();
@@ -880,6 +1925,144 @@ impl App {
                     }
                 }
                 self.progress = 0.0;
+                if let Some(ext) = &self.external {
+                    match &result {
+                        Ok(_) => ext.send_result("ok"),
+                        Err(e) => ext.send_result(&format!("error: {}", e)),
+                    }
+                    for line in &self.log_lines {
+                        ext.send_log(line);
+                    }
+                }
+                Task::none()
+            }
+
+            // -- External control pipe --
+            Message::ExternalCommand(cmd) => self.handle_external_command(cmd),
+            Message::ExternalCommandError(e) => {
+                if let Some(ext) = &self.external {
+                    ext.send_result(&format!("error: {}", e));
+                }
+                Task::none()
+            }
+
+            // -- History tab --
+            Message::HistoryFilterChanged(filter) => {
+                self.history_filter = filter;
+                Task::none()
+            }
+
+            // -- Command palette / keymap --
+            Message::KeyPressed(chord) => self.handle_key_chord(&chord),
+            Message::PaletteQueryChanged(query) => {
+                self.palette_query = query;
+                Task::none()
+            }
+            Message::PaletteExecute(action) => {
+                self.palette_open = false;
+                self.palette_query.clear();
+                if action.requires_idle() && self.is_running {
+                    return Task::none();
+                }
+                self.update(action.message())
+            }
+        }
+    }
+
+    /// Dispatch a raw key chord, either toward the palette (when open) or
+    /// toward the configured keymap (when closed).
+    fn handle_key_chord(&mut self, chord: &str) -> Task<Message> {
+        if self.palette_open {
+            if chord == "escape" {
+                self.palette_open = false;
+                self.palette_query.clear();
+            }
+            return Task::none();
+        }
+
+        if chord == PALETTE_CHORD {
+            self.palette_open = true;
+            self.palette_query.clear();
+            return Task::none();
+        }
+
+        if chord == "enter" && !self.is_running {
+            if let Some(message) = self.active_tab_primary_action() {
+                return self.update(message);
+            }
+        }
+
+        if chord == "escape" && self.is_running {
+            if let Some(message) = self.running_tab_cancel_action() {
+                return self.update(message);
+            }
+        }
+
+        if let Some(action) = self.keymap.get(chord).copied() {
+            if action.requires_idle() && self.is_running {
+                return Task::none();
+            }
+            return self.update(action.message());
+        }
+
+        Task::none()
+    }
+
+    /// The message Enter should send for the tab currently in view, i.e.
+    /// that tab's "primary action" button press. Tabs with no single
+    /// primary action (Welcome, History, Batch) return `None`.
+    fn active_tab_primary_action(&self) -> Option<Message> {
+        match self.active_tab {
+            TabId::Fetch => Some(Message::FetchStart),
+            TabId::Anonymize => Some(Message::AnonStart),
+            TabId::Analyze => Some(Message::AnalyzeStart),
+            TabId::Stats => Some(Message::StatsRun),
+            TabId::Display => Some(Message::DisplayShow),
+            TabId::Package => Some(Message::PackageStart),
+            TabId::Welcome | TabId::Batch | TabId::History => None,
+        }
+    }
+
+    /// The message Esc should send to cancel whichever tab is currently
+    /// running. Package has no cancel action, so `None` is returned there.
+    fn running_tab_cancel_action(&self) -> Option<Message> {
+        match self.running_tab {
+            Some(TabId::Fetch) => Some(Message::FetchCancel),
+            Some(TabId::Anonymize) => Some(Message::AnonCancel),
+            Some(TabId::Analyze) => Some(Message::AnalyzeCancel),
+            Some(TabId::Stats) => Some(Message::StatsCancel),
+            Some(TabId::Batch) => Some(Message::BatchCancel),
+            Some(TabId::Package) | Some(TabId::Welcome) | Some(TabId::History) | None => None,
+        }
+    }
+
+    /// Apply a command received over `msg_in` by forwarding it to the same
+    /// `Message` variants the UI buttons send, honoring the `Start*`-while-
+    /// running and cancel invariants the external pipe promises callers.
+    fn handle_external_command(&mut self, cmd: ExternalMsg) -> Task<Message> {
+        let is_start = matches!(
+            cmd,
+            ExternalMsg::StartFetch | ExternalMsg::StartAnalyze | ExternalMsg::StartPackage
+        );
+        if is_start && self.is_running {
+            if let Some(ext) = &self.external {
+                ext.send_result("error: a task is already running");
+            }
+            return Task::none();
+        }
+
+        match cmd {
+            ExternalMsg::SetCaseFolder(path) => self.update(Message::FolderSelected(Some(path))),
+            ExternalMsg::SetDealLimit(n) => {
+                let _ = self.update(Message::DealLimitToggled(true));
+                self.update(Message::DealLimitChanged(n.to_string()))
+            }
+            ExternalMsg::StartFetch => self.update(Message::FetchStart),
+            ExternalMsg::StartAnalyze => self.update(Message::AnalyzeStart),
+            ExternalMsg::StartPackage => self.update(Message::PackageStart),
+            ExternalMsg::Cancel => {
+                self.cancel_flag.store(true, Ordering::Relaxed);
+                self.status_text = "Cancelling...".to_string();
                 Task::none()
             }
         }
@@ -900,6 +2083,8 @@ impl App {
             tab_button("Statistics", TabId::Stats, self.active_tab),
             tab_button("Display Hand", TabId::Display, self.active_tab),
             tab_button("Package", TabId::Package, self.active_tab),
+            tab_button("Batch", TabId::Batch, self.active_tab),
+            tab_button("History", TabId::History, self.active_tab),
         ]
         .spacing(4);
 
@@ -911,16 +2096,24 @@ impl App {
             TabId::Stats => self.view_stats_tab(),
             TabId::Display => self.view_display_tab(),
             TabId::Package => self.view_package_tab(),
+            TabId::Batch => self.view_batch_tab(),
+            TabId::History => self.view_history_tab(),
         };
 
         let body = container(content).padding(20).width(Fill).height(Fill);
 
-        column![
+        let base: Element<'_, Message> = column![
             container(tab_bar).padding([10, 20]),
             rule::horizontal(1),
             body,
         ]
-        .into()
+        .into();
+
+        if self.palette_open {
+            stack![base, self.view_palette_overlay()].into()
+        } else {
+            base
+        }
     }
 
     // -- Welcome tab --
@@ -929,11 +2122,24 @@ impl App {
         let subtitle = text("Error Detection for Game Analysis and Review").size(14);
 
         // Case folder picker
-        let folder_section = column![
-            text("Case Folder").size(16),
+        let recent_row: Element<'_, Message> = if self.recent_folders.is_empty() {
+            column![].into()
+        } else {
             row![
-                text_input(
-                    "Select a folder containing EDGAR report files...",
+                text("Recent:").size(13),
+                pick_list(self.recent_folders.clone(), None::<String>, Message::RecentFolderSelected)
+                    .placeholder("Choose a recent folder...")
+                    .width(Fill),
+            ]
+            .spacing(10)
+            .align_y(Center)
+            .into()
+        };
+        let folder_section = column![
+            text("Case Folder").size(16),
+            row![
+                text_input(
+                    "Select a folder containing EDGAR report files...",
                     &self.case_folder
                 )
                 .width(Fill),
@@ -941,14 +2147,15 @@ impl App {
             ]
             .spacing(10)
             .align_y(Center),
+            recent_row,
         ]
         .spacing(8);
 
         // File table and subject usernames side by side
         let case_info: Element<'_, Message> = if !self.case_folder.is_empty() {
-            let csv_row = file_status_row("Hand Records (CSV)", &self.case_files.csv_file);
-            let concise_row = file_status_row("Concise Report", &self.case_files.concise_file);
-            let hotspot_row = file_status_row("Hotspot Report", &self.case_files.hotspot_file);
+            let csv_row = file_status_row("Hand Records (CSV)", &self.case_files.csv_file, &self.colors);
+            let concise_row = file_status_row("Concise Report", &self.case_files.concise_file, &self.colors);
+            let hotspot_row = file_status_row("Hotspot Report", &self.case_files.hotspot_file, &self.colors);
 
             let file_table = column![
                 row![
@@ -971,7 +2178,7 @@ impl App {
                 username_rows.push(
                     text("-- none detected --")
                         .size(13)
-                        .color(iced::Color::from_rgb(0.6, 0.6, 0.6))
+                        .color(self.colors.muted)
                         .into(),
                 );
             } else {
@@ -979,7 +2186,7 @@ impl App {
                     username_rows.push(
                         text(name)
                             .size(13)
-                            .color(iced::Color::from_rgb(0.4, 0.9, 0.4))
+                            .color(self.colors.success)
                             .into(),
                     );
                 }
@@ -991,6 +2198,15 @@ impl App {
             column![].into()
         };
 
+        let case_files_note: Element<'_, Message> = if self.case_files_note.is_empty() {
+            column![].into()
+        } else {
+            text(&self.case_files_note)
+                .size(12)
+                .color(self.colors.success)
+                .into()
+        };
+
         // Workflow summary
         let workflow = column![
             rule::horizontal(1),
@@ -1026,13 +2242,32 @@ impl App {
         ]
         .spacing(10);
 
+        let theme_section = column![
+            rule::horizontal(1),
+            text("Appearance").size(16),
+            row![
+                text("Theme:").width(130),
+                pick_list(
+                    list_theme_names(),
+                    Some(self.theme_name.clone()),
+                    Message::ThemeSelected,
+                )
+                .width(200),
+            ]
+            .spacing(10)
+            .align_y(Center),
+        ]
+        .spacing(10);
+
         column![
             title,
             subtitle,
             folder_section,
             case_info,
+            case_files_note,
             workflow,
             deal_limit_section,
+            theme_section,
         ]
         .spacing(16)
         .into()
@@ -1174,63 +2409,13 @@ impl App {
             items.push(progress_bar(0.0..=1.0, self.progress).into());
 
             let progress_text = format!(
-                "{}/{} ({} errors, {} skipped)",
-                self.progress_completed,
-                self.progress_total,
+                "{} ({} errors, {} skipped)",
+                self.progress_estimate_text(self.progress_completed, self.progress_total),
                 self.progress_errors,
                 self.progress_skipped
             );
             items.push(text(progress_text).size(13).into());
 
-            // ETA calculation — exclude skipped rows from rate so resume doesn't
-            // inflate the speed estimate with instantly-processed cached rows.
-            if let Some(start) = self.fetch_start_time {
-                let fetched = self
-                    .progress_completed
-                    .saturating_sub(self.progress_skipped);
-                let remaining_items = self.progress_total.saturating_sub(self.progress_completed);
-
-                if fetched > 0 && self.progress_total > 0 {
-                    let elapsed = start.elapsed();
-                    let rate = fetched as f64 / elapsed.as_secs_f64();
-                    let remaining_secs = if rate > 0.0 {
-                        remaining_items as f64 / rate
-                    } else {
-                        0.0
-                    };
-
-                    let remaining_dur = std::time::Duration::from_secs_f64(remaining_secs);
-                    let remaining_mins = remaining_dur.as_secs() / 60;
-                    let remaining_secs_part = remaining_dur.as_secs() % 60;
-
-                    let eta =
-                        chrono::Local::now() + chrono::Duration::seconds(remaining_secs as i64);
-                    let eta_str = eta.format("%l:%M %p").to_string();
-
-                    let time_text = format!(
-                        "~{:>3} min {:>2} sec remaining  |  ETA: {:>8}",
-                        remaining_mins,
-                        remaining_secs_part,
-                        eta_str.trim()
-                    );
-                    items.push(
-                        container(text(time_text).size(13).font(iced::Font::MONOSPACE))
-                            .width(Fill)
-                            .into(),
-                    );
-                } else if self.progress_completed > 0 {
-                    // Still skipping cached rows — show that we're resuming
-                    items.push(
-                        text(format!(
-                            "Resuming... ({} skipped so far)",
-                            self.progress_skipped
-                        ))
-                        .size(13)
-                        .into(),
-                    );
-                }
-            }
-
             column(items).spacing(4)
         } else {
             column![]
@@ -1337,7 +2522,7 @@ impl App {
                 text("  Concise:   (not found)")
                     .size(12)
                     .font(iced::Font::MONOSPACE)
-                    .color(iced::Color::from_rgb(0.6, 0.6, 0.6))
+                    .color(self.colors.muted)
                     .into(),
             );
         }
@@ -1365,32 +2550,55 @@ impl App {
                 text("  Hotspot:   (not found)")
                     .size(12)
                     .font(iced::Font::MONOSPACE)
-                    .color(iced::Color::from_rgb(0.6, 0.6, 0.6))
+                    .color(self.colors.muted)
                     .into(),
             );
         }
 
         let manifest_section = column(manifest).spacing(4);
 
-        let mut anon_btn = button(text("Anonymize"));
-        if !disabled && !self.anon_input.is_empty() {
-            anon_btn = anon_btn.on_press(Message::AnonStart);
-        }
+        let buttons = if self.is_running && self.running_tab == Some(TabId::Anonymize) {
+            row![
+                button(text("Cancel")).on_press(Message::AnonCancel),
+                text(&self.status_text),
+            ]
+            .spacing(10)
+            .align_y(Center)
+        } else {
+            let mut anon_btn = button(text("Anonymize"));
+            if !disabled && !self.anon_input.is_empty() {
+                anon_btn = anon_btn.on_press(Message::AnonStart);
+            }
+            row![anon_btn].spacing(10)
+        };
 
         let status = if !self.status_text.is_empty()
-            && (self.running_tab == Some(TabId::Anonymize) || !self.is_running)
+            && self.running_tab != Some(TabId::Anonymize)
+            && !self.is_running
         {
             column![text(&self.status_text).size(13)]
         } else {
             column![]
         };
 
+        let progress_section = if self.is_running && self.running_tab == Some(TabId::Anonymize) {
+            column![
+                progress_bar(0.0..=1.0, self.progress),
+                text(self.progress_estimate_text(self.progress_completed, self.progress_total))
+                    .size(13),
+            ]
+            .spacing(4)
+        } else {
+            column![]
+        };
+
         column![
             form,
             rule::horizontal(1),
             manifest_section,
-            row![anon_btn],
-            status
+            buttons,
+            status,
+            progress_section
         ]
         .spacing(12)
         .into()
@@ -1489,8 +2697,9 @@ impl App {
             column![
                 progress_bar(0.0..=1.0, self.progress),
                 text(format!(
-                    "{}/{} ({} errors)",
-                    self.progress_completed, self.progress_total, self.progress_errors
+                    "{} ({} errors)",
+                    self.progress_estimate_text(self.progress_completed, self.progress_total),
+                    self.progress_errors
                 ))
                 .size(13),
             ]
@@ -1547,10 +2756,31 @@ impl App {
         ]
         .spacing(12);
 
-        let mut run_btn = button(text("Compute Stats"));
-        if !disabled {
-            run_btn = run_btn.on_press(Message::StatsRun);
-        }
+        let buttons = if self.is_running && self.running_tab == Some(TabId::Stats) {
+            row![
+                button(text("Cancel")).on_press(Message::StatsCancel),
+                text(&self.status_text),
+            ]
+            .spacing(10)
+            .align_y(Center)
+        } else {
+            let mut run_btn = button(text("Compute Stats"));
+            if !disabled {
+                run_btn = run_btn.on_press(Message::StatsRun);
+            }
+            row![run_btn].spacing(10)
+        };
+
+        let progress_section = if self.is_running && self.running_tab == Some(TabId::Stats) {
+            column![
+                progress_bar(0.0..=1.0, self.progress),
+                text(self.progress_estimate_text(self.progress_completed, self.progress_total))
+                    .size(13),
+            ]
+            .spacing(4)
+        } else {
+            column![]
+        };
 
         let results = if !self.stats_result.is_empty() {
             column![
@@ -1570,7 +2800,9 @@ impl App {
             column![]
         };
 
-        column![form, row![run_btn], results].spacing(16).into()
+        column![form, buttons, progress_section, results]
+            .spacing(16)
+            .into()
     }
 
     // -- Display Hand tab --
@@ -1607,24 +2839,109 @@ impl App {
             show_btn = show_btn.on_press(Message::DisplayShow);
         }
 
-        let results = if !self.display_result.is_empty() {
-            column![scrollable(
-                container(
-                    text(&self.display_result)
-                        .size(13)
-                        .font(iced::Font::MONOSPACE)
-                )
-                .padding(8)
-            )
-            .height(400),]
-            .spacing(4)
+        let results: Element<'_, Message> = if self.display_loading {
+            text("Loading...").size(13).into()
+        } else if !self.display_error.is_empty() {
+            text(&self.display_error).size(13).into()
+        } else if let Some(deal) = &self.display_deal {
+            self.view_deal_diagram(deal)
         } else {
-            column![]
+            column![].into()
         };
 
         column![form, row![show_btn], results].spacing(16).into()
     }
 
+    /// Render a deal as a four-hand compass diagram: suit symbols with
+    /// red/black coloring, cards grouped by suit per seat, and the
+    /// contract/result annotated above. The trick-by-trick cardplay is shown
+    /// below as a grid of card cells colored by DD cost, followed by the DD
+    /// narrative as plain text.
+    fn view_deal_diagram<'a>(&'a self, deal: &'a pipeline::DealDisplay) -> Element<'a, Message> {
+        let header = column![
+            text(format!(
+                "Hand #{}  •  Contract: {} by {}  •  Result: {}",
+                deal.ref_num, deal.contract, deal.declarer, deal.result
+            ))
+            .size(15),
+            text(format!(
+                "Players: N={} S={} E={} W={}",
+                deal.north_player, deal.south_player, deal.east_player, deal.west_player
+            ))
+            .size(13),
+        ]
+        .spacing(4);
+
+        let seat_hand = |label: &str, holdings: &pipeline::SuitHoldings| -> Element<'static, Message> {
+            column![
+                text(label.to_string()).size(14),
+                suit_line('♠', &holdings.spades, false),
+                suit_line('♥', &holdings.hearts, true),
+                suit_line('♦', &holdings.diamonds, true),
+                suit_line('♣', &holdings.clubs, false),
+            ]
+            .spacing(2)
+            .align_x(Center)
+            .into()
+        };
+
+        let compass = column![
+            container(seat_hand("North", &deal.north))
+                .width(Fill)
+                .align_x(Center),
+            row![
+                container(seat_hand("West", &deal.west)).width(Fill),
+                container(seat_hand("East", &deal.east)).width(Fill),
+            ],
+            container(seat_hand("South", &deal.south))
+                .width(Fill)
+                .align_x(Center),
+        ]
+        .spacing(12);
+
+        let trick_header = row![
+            text("Trick").size(12).width(40),
+            container(text("N").size(12)).width(Fill).align_x(Center),
+            container(text("E").size(12)).width(Fill).align_x(Center),
+            container(text("S").size(12)).width(Fill).align_x(Center),
+            container(text("W").size(12)).width(Fill).align_x(Center),
+        ]
+        .spacing(8);
+
+        let trick_rows: Element<'_, Message> = if deal.tricks.is_empty() {
+            text("(No cardplay recorded)").size(13).into()
+        } else {
+            column(
+                deal.tricks
+                    .iter()
+                    .map(trick_row)
+                    .collect::<Vec<Element<'_, Message>>>(),
+            )
+            .spacing(4)
+            .into()
+        };
+
+        let cardplay = column![
+            text("Cardplay — rank color grades DD cost vs. par (green = none, amber = 1 trick, red = 2+)")
+                .size(13),
+            trick_header,
+            scrollable(container(trick_rows).padding(8)).height(220),
+        ]
+        .spacing(6);
+
+        let narrative = scrollable(
+            container(
+                text(&deal.narrative)
+                    .size(13)
+                    .font(iced::Font::MONOSPACE),
+            )
+            .padding(8),
+        )
+        .height(180);
+
+        column![header, compass, cardplay, narrative].spacing(16).into()
+    }
+
     // -- Package tab --
     fn view_package_tab(&self) -> Element<'_, Message> {
         let disabled = self.is_running;
@@ -1677,6 +2994,15 @@ impl App {
         }
         items.push(row![pkg_btn].into());
 
+        if self.is_running && self.running_tab == Some(TabId::Package) {
+            items.push(progress_bar(0.0..=1.0, self.progress).into());
+            items.push(
+                text(self.progress_estimate_text(self.progress_completed, self.progress_total))
+                    .size(13)
+                    .into(),
+            );
+        }
+
         if !self.package_status.is_empty() {
             items.push(text(&self.package_status).size(13).into());
         }
@@ -1690,6 +3016,296 @@ impl App {
 
         column(items).spacing(10).into()
     }
+
+    // -- Batch tab --
+    fn view_batch_tab(&self) -> Element<'_, Message> {
+        let disabled = self.is_running;
+
+        let root_section = column![
+            text("Run the full pipeline over every case subfolder of a parent directory.").size(14),
+            row![
+                text_input(
+                    "Select a parent folder containing case subfolders...",
+                    &self.batch_root
+                )
+                .width(Fill),
+                button(text("Browse").size(13)).on_press_maybe(if disabled {
+                    None
+                } else {
+                    Some(Message::BatchBrowseRoot)
+                }),
+            ]
+            .spacing(10)
+            .align_y(Center),
+        ]
+        .spacing(8);
+
+        if self.batch_cases.is_empty() {
+            return column![
+                text("Batch").size(20),
+                root_section,
+                text("No case subfolders with a hand-records CSV were found.").size(13),
+            ]
+            .spacing(16)
+            .into();
+        }
+
+        let completed = self
+            .batch_cases
+            .iter()
+            .filter(|c| c.status == Some(RunStatus::Completed))
+            .count();
+        let failed = self
+            .batch_cases
+            .iter()
+            .filter(|c| c.status == Some(RunStatus::Failed))
+            .count();
+
+        let mut controls: Vec<Element<'_, Message>> = Vec::new();
+        if !disabled {
+            controls.push(button(text("Start Batch")).on_press(Message::BatchStart).into());
+        } else if self.running_tab == Some(TabId::Batch) {
+            controls.push(button(text("Cancel Batch")).on_press(Message::BatchCancel).into());
+        }
+        let controls_row: Element<'_, Message> = row(controls).spacing(10).into();
+
+        let mut progress_items: Vec<Element<'_, Message>> = Vec::new();
+        if self.is_running && self.running_tab == Some(TabId::Batch) {
+            let aggregate = if self.batch_cases.is_empty() {
+                0.0
+            } else {
+                (completed + failed) as f32 / self.batch_cases.len() as f32
+            };
+            progress_items.push(
+                text(format!(
+                    "Cases completed: {}/{}",
+                    completed + failed,
+                    self.batch_cases.len()
+                ))
+                .size(13)
+                .into(),
+            );
+            progress_items.push(progress_bar(0.0..=1.0, aggregate).into());
+            if let Some(index) = self.batch_current {
+                if let Some(case) = self.batch_cases.get(index) {
+                    progress_items.push(
+                        text(format!("Current case: {}", case.folder.display()))
+                            .size(13)
+                            .into(),
+                    );
+                }
+            }
+            progress_items.push(progress_bar(0.0..=1.0, self.progress).into());
+            progress_items.push(
+                text(self.progress_estimate_text(self.progress_completed, self.progress_total))
+                    .size(13)
+                    .into(),
+            );
+        }
+
+        let summary_rows: Vec<Element<'_, Message>> = self
+            .batch_cases
+            .iter()
+            .map(|case| {
+                let (label, color) = match case.status {
+                    None => ("Pending", self.colors.muted),
+                    Some(RunStatus::Completed) => ("Completed", self.colors.success),
+                    Some(RunStatus::Failed) => ("Failed", self.colors.error),
+                    Some(RunStatus::Cancelled) => ("Cancelled", self.colors.muted),
+                };
+                row![
+                    text(case.folder.display().to_string()).size(13).width(Fill),
+                    text(label).size(13).color(color).width(100),
+                ]
+                .spacing(10)
+                .into()
+            })
+            .collect();
+
+        column![
+            text("Batch").size(20),
+            root_section,
+            text(format!(
+                "{} cases found • {} completed • {} failed",
+                self.batch_cases.len(),
+                completed,
+                failed
+            ))
+            .size(13),
+            controls_row,
+            column(progress_items).spacing(6),
+            rule::horizontal(1),
+            scrollable(container(column(summary_rows).spacing(4)).padding(8)).height(Fill),
+        ]
+        .spacing(12)
+        .into()
+    }
+
+    // -- History tab --
+    fn view_history_tab(&self) -> Element<'_, Message> {
+        let title = text("Activity History").size(20);
+
+        let filters = row![
+            button(text("All").size(13)).on_press(Message::HistoryFilterChanged(None)),
+            button(text("Info").size(13))
+                .on_press(Message::HistoryFilterChanged(Some(HistorySeverity::Info))),
+            button(text("Error").size(13))
+                .on_press(Message::HistoryFilterChanged(Some(HistorySeverity::Error))),
+        ]
+        .spacing(8);
+
+        let entries: Vec<&HistoryEntry> = self
+            .history
+            .iter()
+            .filter(|e| match self.history_filter {
+                Some(sev) => e.severity == sev,
+                None => true,
+            })
+            .collect();
+
+        let list = if entries.is_empty() {
+            column![text("No activity recorded yet.").size(13)]
+        } else {
+            column(
+                entries
+                    .iter()
+                    .map(|e| {
+                        let severity_tag = match e.severity {
+                            HistorySeverity::Info => "INFO",
+                            HistorySeverity::Error => "ERROR",
+                        };
+                        let tab_tag = e.tab.map(tab_label).unwrap_or("General");
+                        let color = match e.severity {
+                            HistorySeverity::Info => self.colors.text,
+                            HistorySeverity::Error => self.colors.error,
+                        };
+                        text(format!(
+                            "{} [{}] {}: {}",
+                            e.timestamp, tab_tag, severity_tag, e.message
+                        ))
+                        .size(12)
+                        .color(color)
+                        .into()
+                    })
+                    .collect::<Vec<Element<'_, Message>>>(),
+            )
+            .spacing(4)
+        };
+
+        let run_log_title = text("Run Log").size(18);
+
+        let total_boards: usize = self.run_ledger.iter().map(|r| r.boards_processed).sum();
+        let total_secs: f64 = self.run_ledger.iter().map(|r| r.duration_secs).sum();
+        let total_mins = (total_secs / 60.0) as u64;
+        let total_secs_part = (total_secs as u64) % 60;
+        let totals = text(format!(
+            "{} runs • {} boards processed • {:02}:{:02} cumulative",
+            self.run_ledger.len(),
+            total_boards,
+            total_mins,
+            total_secs_part
+        ))
+        .size(13);
+
+        let run_list = if self.run_ledger.is_empty() {
+            column![text("No runs recorded yet.").size(13)]
+        } else {
+            column(
+                self.run_ledger
+                    .iter()
+                    .rev()
+                    .map(|r| {
+                        let tab_tag = r.tab.map(tab_label).unwrap_or("General");
+                        let color = match r.status {
+                            RunStatus::Completed => self.colors.success,
+                            RunStatus::Failed => self.colors.error,
+                            RunStatus::Cancelled => self.colors.muted,
+                        };
+                        text(format!(
+                            "{} [{}] {} in {:.1}s • {} boards, {} errors, {} skipped • {} -> {}",
+                            r.started_at,
+                            tab_tag,
+                            r.status.label(),
+                            r.duration_secs,
+                            r.boards_processed,
+                            r.errors,
+                            r.skipped,
+                            r.input_path,
+                            r.output_path,
+                        ))
+                        .size(12)
+                        .color(color)
+                        .into()
+                    })
+                    .collect::<Vec<Element<'_, Message>>>(),
+            )
+            .spacing(4)
+        };
+
+        column![
+            title,
+            text("Durable, timestamped record of activity across all tabs, persisted to this case's EDGAR Defense folder.").size(13),
+            filters,
+            scrollable(container(list).padding(8)).height(300),
+            rule::horizontal(1),
+            run_log_title,
+            totals,
+            scrollable(container(run_list).padding(8)).height(Fill),
+        ]
+        .spacing(12)
+        .into()
+    }
+
+    // -- Command palette --
+
+    /// Render the command-palette overlay: a search box plus a filtered list
+    /// of matching actions, shown on top of the current tab via `stack!`.
+    fn view_palette_overlay(&self) -> Element<'_, Message> {
+        let query_lower = self.palette_query.to_lowercase();
+        let matches: Vec<PaletteAction> = PaletteAction::all()
+            .into_iter()
+            .filter(|a| query_lower.is_empty() || a.label().to_lowercase().contains(&query_lower))
+            .collect();
+
+        let reverse_keymap: HashMap<PaletteAction, &str> = self
+            .keymap
+            .iter()
+            .map(|(chord, action)| (*action, chord.as_str()))
+            .collect();
+
+        let rows: Vec<Element<'_, Message>> = matches
+            .into_iter()
+            .map(|action| {
+                let busy = action.requires_idle() && self.is_running;
+                let chord_hint = reverse_keymap
+                    .get(&action)
+                    .map(|c| format!("  [{}]", c))
+                    .unwrap_or_default();
+                let label = text(format!("{}{}", action.label(), chord_hint)).size(14);
+                let btn = button(label).width(Fill);
+                let btn = if busy {
+                    btn
+                } else {
+                    btn.on_press(Message::PaletteExecute(action))
+                };
+                btn.into()
+            })
+            .collect();
+
+        let panel = column![
+            text_input("Type a command...", &self.palette_query)
+                .on_input(Message::PaletteQueryChanged)
+                .size(16),
+            scrollable(column(rows).spacing(2)).height(300),
+        ]
+        .spacing(8)
+        .padding(16)
+        .width(420);
+
+        container(container(panel).style(container::bordered_box))
+            .center(Fill)
+            .into()
+    }
 }
 
 // ============================================================================
@@ -1703,6 +3319,38 @@ fn scan_case_folder(folder: &Path) -> CaseFiles {
     result
 }
 
+/// Discover one `BatchCase` per immediate subdirectory of `root` that
+/// contains at least a hand-records CSV, for the Batch tab's parent-folder
+/// mode. Subdirectories without a CSV (e.g. stray folders that aren't
+/// actually cases) are skipped rather than surfaced as empty entries.
+fn scan_batch_root(root: &Path) -> Vec<BatchCase> {
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(root)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .collect()
+        })
+        .unwrap_or_default();
+    dirs.sort();
+
+    dirs.into_iter()
+        .filter_map(|folder| {
+            let case_files = scan_case_folder(&folder);
+            if case_files.csv_file.is_some() {
+                Some(BatchCase {
+                    folder,
+                    case_files,
+                    status: None,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
 /// Recursive directory walker for case file detection.
 fn scan_dir_recursive(dir: &Path, result: &mut CaseFiles) {
     let entries = match std::fs::read_dir(dir) {
@@ -1803,20 +3451,93 @@ fn extract_concise_subject(path: &Path) -> Option<String> {
 // Helper widgets
 // ============================================================================
 
+/// Render one suit line of a hand, e.g. "♥ AKQ", in red for hearts/diamonds
+/// and the default text color for spades/clubs.
+fn suit_line(symbol: char, cards: &str, red: bool) -> Element<'static, Message> {
+    let label = text(format!("{} {}", symbol, cards)).size(14);
+    if red {
+        label.color(iced::Color::from_rgb(0.85, 0.15, 0.15)).into()
+    } else {
+        label.into()
+    }
+}
+
+/// Map a suit letter ('S'/'H'/'D'/'C') to its Unicode symbol.
+fn suit_symbol(suit: char) -> char {
+    match suit {
+        'S' => '♠',
+        'H' => '♥',
+        'D' => '♦',
+        'C' => '♣',
+        _ => '?',
+    }
+}
+
+/// Color-grade a double-dummy cost: green for no cost, amber for one trick,
+/// red for two or more — lets a reviewer spot defensive errors at a glance.
+fn cost_color(cost: u8) -> iced::Color {
+    match cost {
+        0 => iced::Color::from_rgb(0.3, 0.75, 0.35),
+        1 => iced::Color::from_rgb(0.85, 0.65, 0.15),
+        _ => iced::Color::from_rgb(0.85, 0.15, 0.15),
+    }
+}
+
+/// Render one seat's card in a trick: the suit symbol colored red/black as
+/// usual, and the rank colored by its double-dummy cost (see `cost_color`).
+fn card_cell(play: Option<&pipeline::CardPlay>) -> Element<'static, Message> {
+    let Some(play) = play else {
+        return text("-").size(14).into();
+    };
+
+    let suit = play.card.chars().next().unwrap_or('?');
+    let rank = play.card.get(1..).unwrap_or("").to_string();
+    let suit_color = if suit == 'H' || suit == 'D' {
+        iced::Color::from_rgb(0.85, 0.15, 0.15)
+    } else {
+        iced::Color::from_rgb(0.75, 0.75, 0.75)
+    };
+
+    let rank_text = text(rank).size(14);
+    let rank_text = match play.cost {
+        Some(cost) => rank_text.color(cost_color(cost)),
+        None => rank_text,
+    };
+
+    row![
+        text(suit_symbol(suit).to_string()).size(14).color(suit_color),
+        rank_text,
+    ]
+    .spacing(2)
+    .into()
+}
+
+/// Render one trick as a row of seat cells in N/E/S/W order.
+fn trick_row(trick: &pipeline::TrickDisplay) -> Element<'static, Message> {
+    let find = |seat: char| trick.plays.iter().find(|p| p.seat == seat);
+    row![
+        text(format!("{:>2}", trick.trick_num)).size(13).width(40),
+        container(card_cell(find('N'))).width(Fill).align_x(Center),
+        container(card_cell(find('E'))).width(Fill).align_x(Center),
+        container(card_cell(find('S'))).width(Fill).align_x(Center),
+        container(card_cell(find('W'))).width(Fill).align_x(Center),
+    ]
+    .spacing(8)
+    .align_y(Center)
+    .into()
+}
+
 /// Render a row in the case file table showing file type, filename, and status.
-fn file_status_row<'a>(label: &'a str, file: &Option<PathBuf>) -> Element<'a, Message> {
+fn file_status_row<'a>(label: &'a str, file: &Option<PathBuf>, colors: &AppColors) -> Element<'a, Message> {
     let (filename, style_color) = match file {
         Some(path) => {
             let name = path
                 .file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or("(unknown)");
-            (name.to_string(), iced::Color::from_rgb(0.4, 0.9, 0.4))
+            (name.to_string(), colors.success)
         }
-        None => (
-            "-- not found --".to_string(),
-            iced::Color::from_rgb(0.6, 0.6, 0.6),
-        ),
+        None => ("-- not found --".to_string(), colors.muted),
     };
 
     row![
@@ -1914,53 +3635,653 @@ fn derive_analyze_output(input: &str) -> String {
 // Config persistence
 // ============================================================================
 
-/// Get the config file path: ~/.edgar-toolkit.conf
-fn config_path() -> Option<PathBuf> {
-    std::env::var("HOME")
-        .ok()
-        .map(|home| PathBuf::from(home).join(".edgar-toolkit.conf"))
-}
+/// Cap on how many case folders `Config::recent_folders` remembers.
+const MAX_RECENT_FOLDERS: usize = 8;
+
+/// Persisted, structured preferences: deal-limit defaults and the
+/// most-recently-used case folders. Distinct from `SessionState` below,
+/// which tracks the operator's in-progress workspace (tab inputs, the
+/// currently open folder) — this is longer-lived preference data the
+/// operator would expect to survive even a `--session` switch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Config {
+    #[serde(default = "default_deal_limit_enabled")]
+    deal_limit_enabled: bool,
+    #[serde(default = "default_deal_limit_count")]
+    deal_limit_count: String,
+    #[serde(default)]
+    recent_folders: Vec<String>,
+}
 
-/// Load deal limit settings from config file. Returns (enabled, count) with defaults.
-fn load_config() -> (bool, String) {
-    let defaults = (true, "1000".to_string());
-    let path = match config_path() {
-        Some(p) => p,
-        None => return defaults,
-    };
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            deal_limit_enabled: default_deal_limit_enabled(),
+            deal_limit_count: default_deal_limit_count(),
+            recent_folders: Vec::new(),
+        }
+    }
+}
 
-    let content = match std::fs::read_to_string(&path) {
-        Ok(c) => c,
-        Err(_) => return defaults,
-    };
+fn default_deal_limit_enabled() -> bool {
+    true
+}
+
+fn default_deal_limit_count() -> String {
+    "1000".to_string()
+}
+
+/// Get the config file path: ~/.edgar-toolkit-config.toml
+fn config_path() -> Option<PathBuf> {
+    std::env::var("HOME")
+        .ok()
+        .map(|home| PathBuf::from(home).join(".edgar-toolkit-config.toml"))
+}
+
+/// Get the path of the legacy pre-TOML config file, read once to migrate
+/// `deal_limit_enabled`/`deal_limit_count` the first time `config_path()`
+/// doesn't exist yet.
+fn legacy_config_path() -> Option<PathBuf> {
+    std::env::var("HOME")
+        .ok()
+        .map(|home| PathBuf::from(home).join(".edgar-toolkit.conf"))
+}
 
-    let mut enabled = true;
-    let mut count = "1000".to_string();
+/// Load preferences from `config_path()`, falling back to migrating the
+/// legacy `key=value` file (or defaults) if it doesn't exist yet, and
+/// pruning any recent folder that's no longer a directory on disk.
+fn load_config() -> Config {
+    let mut config = match config_path().and_then(|p| std::fs::read_to_string(p).ok()) {
+        Some(content) => toml::from_str(&content).unwrap_or_default(),
+        None => load_legacy_config(),
+    };
+    config.recent_folders.retain(|f| Path::new(f).is_dir());
+    config
+}
 
+/// Parse the legacy plain-text config file for the two fields it ever held.
+/// Used only on first run after upgrading, before a TOML config exists.
+fn load_legacy_config() -> Config {
+    let mut config = Config::default();
+    let Some(path) = legacy_config_path() else {
+        return config;
+    };
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return config;
+    };
     for line in content.lines() {
         if let Some((key, value)) = line.split_once('=') {
             match key.trim() {
-                "deal_limit_enabled" => enabled = value.trim() == "true",
-                "deal_limit_count" => count = value.trim().to_string(),
+                "deal_limit_enabled" => config.deal_limit_enabled = value.trim() == "true",
+                "deal_limit_count" => config.deal_limit_count = value.trim().to_string(),
                 _ => {}
             }
         }
     }
-
-    (enabled, count)
+    config
 }
 
-/// Save deal limit settings to config file.
-fn save_config(enabled: bool, count: &str) {
+/// Save preferences to `config_path()`, overwriting any previous save.
+fn save_config(config: &Config) {
     if let Some(path) = config_path() {
+        if let Ok(content) = toml::to_string_pretty(config) {
+            let _ = std::fs::write(&path, content);
+        }
+    }
+}
+
+/// The operator's restorable workspace: the paths, mappings, and per-tab
+/// settings an operator would otherwise have to retype every launch.
+/// Mirrors the subset of `App`'s fields worth restoring — transient state
+/// (progress, running tasks, history, palette) is deliberately left out.
+#[derive(Debug, Clone, Default)]
+struct SessionState {
+    case_folder: String,
+    fetch_input: String,
+    fetch_output: String,
+    fetch_delay: String,
+    fetch_batch_size: String,
+    fetch_batch_delay: String,
+    fetch_resume: bool,
+    fetch_advanced_open: bool,
+    anon_input: String,
+    anon_output: String,
+    anon_map: String,
+    analyze_input: String,
+    analyze_output: String,
+    analyze_threads: String,
+    analyze_checkpoint: String,
+    analyze_resume: bool,
+    analyze_advanced_open: bool,
+    stats_input: String,
+    stats_output: String,
+    stats_top_n: String,
+    display_input: String,
+    package_output: String,
+    theme_name: String,
+}
+
+/// Get the session state file path: ~/.edgar-toolkit-session.conf
+fn session_state_path() -> Option<PathBuf> {
+    std::env::var("HOME")
+        .ok()
+        .map(|home| PathBuf::from(home).join(".edgar-toolkit-session.conf"))
+}
+
+/// Load the saved workspace from `session_state_path()`. A missing or
+/// malformed file, or one with unrecognized keys from a future version,
+/// just falls back to (or ignores) defaults rather than failing to start.
+fn load_session_state() -> SessionState {
+    let mut state = SessionState::default();
+    let Some(path) = session_state_path() else {
+        return state;
+    };
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return state;
+    };
+
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        match key.trim() {
+            "case_folder" => state.case_folder = value,
+            "fetch_input" => state.fetch_input = value,
+            "fetch_output" => state.fetch_output = value,
+            "fetch_delay" => state.fetch_delay = value,
+            "fetch_batch_size" => state.fetch_batch_size = value,
+            "fetch_batch_delay" => state.fetch_batch_delay = value,
+            "fetch_resume" => state.fetch_resume = value == "true",
+            "fetch_advanced_open" => state.fetch_advanced_open = value == "true",
+            "anon_input" => state.anon_input = value,
+            "anon_output" => state.anon_output = value,
+            "anon_map" => state.anon_map = value,
+            "analyze_input" => state.analyze_input = value,
+            "analyze_output" => state.analyze_output = value,
+            "analyze_threads" => state.analyze_threads = value,
+            "analyze_checkpoint" => state.analyze_checkpoint = value,
+            "analyze_resume" => state.analyze_resume = value == "true",
+            "analyze_advanced_open" => state.analyze_advanced_open = value == "true",
+            "stats_input" => state.stats_input = value,
+            "stats_output" => state.stats_output = value,
+            "stats_top_n" => state.stats_top_n = value,
+            "display_input" => state.display_input = value,
+            "package_output" => state.package_output = value,
+            "theme_name" => state.theme_name = value,
+            _ => {} // unknown key from a newer version: ignore
+        }
+    }
+
+    state
+}
+
+/// Save the workspace to `session_state_path()`, overwriting any previous
+/// save. Best-effort: a failed write (e.g. unwritable home dir) is silently
+/// skipped rather than interrupting the operator.
+fn save_session_state(state: &SessionState) {
+    if let Some(path) = session_state_path() {
         let content = format!(
-            "deal_limit_enabled={}\ndeal_limit_count={}\n",
-            enabled, count
+            "case_folder={}\n\
+             fetch_input={}\n\
+             fetch_output={}\n\
+             fetch_delay={}\n\
+             fetch_batch_size={}\n\
+             fetch_batch_delay={}\n\
+             fetch_resume={}\n\
+             fetch_advanced_open={}\n\
+             anon_input={}\n\
+             anon_output={}\n\
+             anon_map={}\n\
+             analyze_input={}\n\
+             analyze_output={}\n\
+             analyze_threads={}\n\
+             analyze_checkpoint={}\n\
+             analyze_resume={}\n\
+             analyze_advanced_open={}\n\
+             stats_input={}\n\
+             stats_output={}\n\
+             stats_top_n={}\n\
+             display_input={}\n\
+             package_output={}\n\
+             theme_name={}\n",
+            state.case_folder,
+            state.fetch_input,
+            state.fetch_output,
+            state.fetch_delay,
+            state.fetch_batch_size,
+            state.fetch_batch_delay,
+            state.fetch_resume,
+            state.fetch_advanced_open,
+            state.anon_input,
+            state.anon_output,
+            state.anon_map,
+            state.analyze_input,
+            state.analyze_output,
+            state.analyze_threads,
+            state.analyze_checkpoint,
+            state.analyze_resume,
+            state.analyze_advanced_open,
+            state.stats_input,
+            state.stats_output,
+            state.stats_top_n,
+            state.display_input,
+            state.package_output,
+            state.theme_name,
         );
         let _ = std::fs::write(&path, content);
     }
 }
 
+/// App-wide colors for the custom widgets (`file_status_row` and friends)
+/// that iced's built-in `button::primary`/`secondary` styles don't cover,
+/// since those already track the active `Theme` automatically. Kept in sync
+/// with `App::theme`'s palette so a theme switch recolors everything at once.
+#[derive(Debug, Clone, Copy)]
+struct AppColors {
+    background: iced::Color,
+    text: iced::Color,
+    accent: iced::Color,
+    success: iced::Color,
+    muted: iced::Color,
+    error: iced::Color,
+    monospace_size: u16,
+}
+
+impl AppColors {
+    /// Built-in default, matching `Theme::Dark`'s palette. `muted` has no
+    /// equivalent in `iced::theme::Palette`, so it's a fixed mid-gray.
+    fn dark() -> AppColors {
+        let palette = Theme::Dark.palette();
+        AppColors {
+            background: palette.background,
+            text: palette.text,
+            accent: palette.primary,
+            success: palette.success,
+            muted: iced::Color::from_rgb(0.6, 0.6, 0.6),
+            error: palette.danger,
+            monospace_size: 13,
+        }
+    }
+}
+
+/// Directory of named theme files: `~/.config/edgar-toolkit/themes/*.toml`.
+fn themes_dir() -> Option<PathBuf> {
+    std::env::var("HOME")
+        .ok()
+        .map(|home| PathBuf::from(home).join(".config/edgar-toolkit/themes"))
+}
+
+/// List available theme names: the built-in "dark" first, followed by every
+/// `*.toml` file's stem found under `themes_dir()`, sorted.
+fn list_theme_names() -> Vec<String> {
+    let mut names = vec!["dark".to_string()];
+    if let Some(dir) = themes_dir() {
+        if let Ok(entries) = std::fs::read_dir(&dir) {
+            let mut found: Vec<String> = entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "toml"))
+                .filter_map(|e| {
+                    e.path()
+                        .file_stem()
+                        .map(|s| s.to_string_lossy().to_string())
+                })
+                .filter(|n| n != "dark")
+                .collect();
+            found.sort();
+            names.extend(found);
+        }
+    }
+    names
+}
+
+/// Load a named theme. `"dark"` (or any unknown name) is the built-in
+/// default; anything else is read from `themes_dir()/<name>.toml`, falling
+/// back to the built-in default if the file is missing or malformed.
+fn load_named_theme(name: &str) -> (Theme, AppColors) {
+    if name != "dark" {
+        if let Some(dir) = themes_dir() {
+            let path = dir.join(format!("{}.toml", name));
+            if let Ok(content) = std::fs::read_to_string(&path) {
+                if let Some((palette, colors)) = parse_theme_toml(&content) {
+                    return (Theme::custom(name.to_string(), palette), colors);
+                }
+            }
+        }
+    }
+    (Theme::Dark, AppColors::dark())
+}
+
+/// Parse the `[palette]` section of a theme TOML file into an iced `Palette`
+/// plus the full `AppColors` set. `background`/`text`/`accent`/`success`/
+/// `error` are required; `muted` and `monospace_size` fall back to the
+/// built-in defaults if absent, so existing files stay forward-compatible.
+fn parse_theme_toml(content: &str) -> Option<(iced::theme::Palette, AppColors)> {
+    let mut colors = std::collections::HashMap::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            colors.insert(
+                key.trim().to_string(),
+                value.trim().trim_matches('"').to_string(),
+            );
+        }
+    }
+
+    let background = parse_hex_color(colors.get("background")?)?;
+    let text = parse_hex_color(colors.get("text")?)?;
+    let accent = parse_hex_color(colors.get("accent")?)?;
+    let success = parse_hex_color(colors.get("success")?)?;
+    let error = parse_hex_color(colors.get("error")?)?;
+    let defaults = AppColors::dark();
+    let muted = colors
+        .get("muted")
+        .and_then(|s| parse_hex_color(s))
+        .unwrap_or(defaults.muted);
+    let monospace_size = colors
+        .get("monospace_size")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(defaults.monospace_size);
+
+    let palette = iced::theme::Palette {
+        background,
+        text,
+        primary: accent,
+        success,
+        danger: error,
+    };
+    let app_colors = AppColors {
+        background,
+        text,
+        accent,
+        success,
+        muted,
+        error,
+        monospace_size,
+    };
+    Some((palette, app_colors))
+}
+
+/// Parse a `#rrggbb` hex color.
+fn parse_hex_color(s: &str) -> Option<iced::Color> {
+    let s = s.trim_start_matches('#');
+    if s.len() != 6 {
+        return None;
+    }
+    let r = u8::from_str_radix(&s[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&s[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&s[4..6], 16).ok()?;
+    Some(iced::Color::from_rgb8(r, g, b))
+}
+
+/// Format a color as `#rrggbb`.
+fn format_hex_color(c: iced::Color) -> String {
+    let to_byte = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        to_byte(c.r),
+        to_byte(c.g),
+        to_byte(c.b)
+    )
+}
+
+/// Render the built-in default theme as TOML, for `--print-default-theme`.
+/// Users can redirect this to a file under `themes_dir()` as a starting
+/// point for their own theme.
+fn default_theme_toml() -> String {
+    let colors = AppColors::dark();
+    format!(
+        "[palette]\n\
+         background = \"{}\"\n\
+         text = \"{}\"\n\
+         accent = \"{}\"\n\
+         success = \"{}\"\n\
+         muted = \"{}\"\n\
+         error = \"{}\"\n\
+         monospace_size = {}\n",
+        format_hex_color(colors.background),
+        format_hex_color(colors.text),
+        format_hex_color(colors.accent),
+        format_hex_color(colors.success),
+        format_hex_color(colors.muted),
+        format_hex_color(colors.error),
+        colors.monospace_size,
+    )
+}
+
+// ============================================================================
+// Command palette / keymap
+// ============================================================================
+
+/// An action reachable from the command palette (Ctrl+K) and, optionally,
+/// bound to a key chord in the user's keymap file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum PaletteAction {
+    GoToWelcome,
+    GoToFetch,
+    GoToAnonymize,
+    GoToAnalyze,
+    GoToStats,
+    GoToDisplay,
+    GoToPackage,
+    GoToBatch,
+    GoToHistory,
+    StartFetch,
+    CancelFetch,
+    StartAnonymize,
+    StartAnalyze,
+    CancelAnalyze,
+    StartPackage,
+    RunBenchmark,
+    StartBatch,
+    CancelBatch,
+}
+
+impl PaletteAction {
+    fn all() -> Vec<PaletteAction> {
+        vec![
+            PaletteAction::GoToWelcome,
+            PaletteAction::GoToFetch,
+            PaletteAction::GoToAnonymize,
+            PaletteAction::GoToAnalyze,
+            PaletteAction::GoToStats,
+            PaletteAction::GoToDisplay,
+            PaletteAction::GoToPackage,
+            PaletteAction::GoToBatch,
+            PaletteAction::GoToHistory,
+            PaletteAction::StartFetch,
+            PaletteAction::CancelFetch,
+            PaletteAction::StartAnonymize,
+            PaletteAction::StartAnalyze,
+            PaletteAction::CancelAnalyze,
+            PaletteAction::StartPackage,
+            PaletteAction::RunBenchmark,
+            PaletteAction::StartBatch,
+            PaletteAction::CancelBatch,
+        ]
+    }
+
+    /// Human-readable label shown in the palette overlay.
+    fn label(&self) -> &'static str {
+        match self {
+            PaletteAction::GoToWelcome => "Go to Welcome",
+            PaletteAction::GoToFetch => "Go to Fetch Cardplay",
+            PaletteAction::GoToAnonymize => "Go to Anonymize",
+            PaletteAction::GoToAnalyze => "Go to Analyze DD",
+            PaletteAction::GoToStats => "Go to Statistics",
+            PaletteAction::GoToDisplay => "Go to Display Hand",
+            PaletteAction::GoToPackage => "Go to Package",
+            PaletteAction::GoToBatch => "Go to Batch",
+            PaletteAction::GoToHistory => "Go to History",
+            PaletteAction::StartFetch => "Start Fetch",
+            PaletteAction::CancelFetch => "Cancel Fetch",
+            PaletteAction::StartAnonymize => "Start Anonymize",
+            PaletteAction::StartAnalyze => "Start Analyze",
+            PaletteAction::CancelAnalyze => "Cancel Analyze",
+            PaletteAction::StartPackage => "Start Package",
+            PaletteAction::RunBenchmark => "Run DD Benchmark",
+            PaletteAction::StartBatch => "Start Batch",
+            PaletteAction::CancelBatch => "Cancel Batch",
+        }
+    }
+
+    /// The `Message` this action dispatches when run.
+    fn message(&self) -> Message {
+        match self {
+            PaletteAction::GoToWelcome => Message::TabSelected(TabId::Welcome),
+            PaletteAction::GoToFetch => Message::TabSelected(TabId::Fetch),
+            PaletteAction::GoToAnonymize => Message::TabSelected(TabId::Anonymize),
+            PaletteAction::GoToAnalyze => Message::TabSelected(TabId::Analyze),
+            PaletteAction::GoToStats => Message::TabSelected(TabId::Stats),
+            PaletteAction::GoToDisplay => Message::TabSelected(TabId::Display),
+            PaletteAction::GoToPackage => Message::TabSelected(TabId::Package),
+            PaletteAction::GoToBatch => Message::TabSelected(TabId::Batch),
+            PaletteAction::GoToHistory => Message::TabSelected(TabId::History),
+            PaletteAction::StartFetch => Message::FetchStart,
+            PaletteAction::CancelFetch => Message::FetchCancel,
+            PaletteAction::StartAnonymize => Message::AnonStart,
+            PaletteAction::StartAnalyze => Message::AnalyzeStart,
+            PaletteAction::CancelAnalyze => Message::AnalyzeCancel,
+            PaletteAction::StartPackage => Message::PackageStart,
+            PaletteAction::RunBenchmark => Message::BenchmarkStart,
+            PaletteAction::StartBatch => Message::BatchStart,
+            PaletteAction::CancelBatch => Message::BatchCancel,
+        }
+    }
+
+    /// Whether this action is only valid while no task is running. Tab
+    /// navigation and cancellation are always available.
+    fn requires_idle(&self) -> bool {
+        matches!(
+            self,
+            PaletteAction::StartFetch
+                | PaletteAction::StartAnonymize
+                | PaletteAction::StartAnalyze
+                | PaletteAction::StartPackage
+                | PaletteAction::RunBenchmark
+                | PaletteAction::StartBatch
+        )
+    }
+
+    /// Kebab-case slug used for keymap file serialization, e.g. `start-fetch`.
+    fn slug(&self) -> &'static str {
+        match self {
+            PaletteAction::GoToWelcome => "go-to-welcome",
+            PaletteAction::GoToFetch => "go-to-fetch",
+            PaletteAction::GoToAnonymize => "go-to-anonymize",
+            PaletteAction::GoToAnalyze => "go-to-analyze",
+            PaletteAction::GoToStats => "go-to-stats",
+            PaletteAction::GoToDisplay => "go-to-display",
+            PaletteAction::GoToPackage => "go-to-package",
+            PaletteAction::GoToBatch => "go-to-batch",
+            PaletteAction::GoToHistory => "go-to-history",
+            PaletteAction::StartFetch => "start-fetch",
+            PaletteAction::CancelFetch => "cancel-fetch",
+            PaletteAction::StartAnonymize => "start-anonymize",
+            PaletteAction::StartAnalyze => "start-analyze",
+            PaletteAction::CancelAnalyze => "cancel-analyze",
+            PaletteAction::StartPackage => "start-package",
+            PaletteAction::RunBenchmark => "run-benchmark",
+            PaletteAction::StartBatch => "start-batch",
+            PaletteAction::CancelBatch => "cancel-batch",
+        }
+    }
+
+    fn from_slug(s: &str) -> Option<PaletteAction> {
+        PaletteAction::all().into_iter().find(|a| a.slug() == s)
+    }
+}
+
+/// Get the keymap file path: ~/.edgar-toolkit-keymap.toml
+fn keymap_path() -> Option<PathBuf> {
+    std::env::var("HOME")
+        .ok()
+        .map(|home| PathBuf::from(home).join(".edgar-toolkit-keymap.toml"))
+}
+
+/// Load the user's keymap from `keymap_path()`, falling back to
+/// `default_keymap()` if the file is missing or malformed.
+fn load_keymap() -> HashMap<String, PaletteAction> {
+    let Some(path) = keymap_path() else {
+        return default_keymap();
+    };
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return default_keymap();
+    };
+
+    let mut keymap = HashMap::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            let chord = normalize_chord(key.trim());
+            let slug = value.trim().trim_matches('"');
+            if let Some(action) = PaletteAction::from_slug(slug) {
+                keymap.insert(chord, action);
+            }
+        }
+    }
+
+    if keymap.is_empty() {
+        default_keymap()
+    } else {
+        keymap
+    }
+}
+
+/// The built-in keymap used when no keymap file exists: ctrl+1 through ctrl+9
+/// for tab navigation. Enter (primary action) and Escape (cancel) are handled
+/// directly by the key handler and have no entry here.
+fn default_keymap() -> HashMap<String, PaletteAction> {
+    HashMap::from([
+        ("ctrl+1".to_string(), PaletteAction::GoToWelcome),
+        ("ctrl+2".to_string(), PaletteAction::GoToFetch),
+        ("ctrl+3".to_string(), PaletteAction::GoToAnonymize),
+        ("ctrl+4".to_string(), PaletteAction::GoToAnalyze),
+        ("ctrl+5".to_string(), PaletteAction::GoToStats),
+        ("ctrl+6".to_string(), PaletteAction::GoToDisplay),
+        ("ctrl+7".to_string(), PaletteAction::GoToPackage),
+        ("ctrl+8".to_string(), PaletteAction::GoToBatch),
+        ("ctrl+9".to_string(), PaletteAction::GoToHistory),
+    ])
+}
+
+/// Normalize a chord string to its canonical form: lowercase, with the
+/// `esc` alias expanded to `escape`.
+fn normalize_chord(chord: &str) -> String {
+    let lower = chord.trim().to_lowercase();
+    if lower == "esc" {
+        "escape".to_string()
+    } else {
+        lower
+    }
+}
+
+/// Translate a raw key-press event into a canonical chord string, e.g.
+/// `ctrl+k` or `escape`, matching the format used by the keymap file.
+fn key_to_chord(key: &iced::keyboard::Key, modifiers: iced::keyboard::Modifiers) -> String {
+    let base = match key {
+        iced::keyboard::Key::Character(c) => c.to_lowercase(),
+        iced::keyboard::Key::Named(named) => format!("{:?}", named).to_lowercase(),
+        iced::keyboard::Key::Unidentified => "unidentified".to_string(),
+    };
+
+    let mut chord = String::new();
+    if modifiers.control() {
+        chord.push_str("ctrl+");
+    }
+    if modifiers.alt() {
+        chord.push_str("alt+");
+    }
+    chord.push_str(&base);
+    chord
+}
+
 // ============================================================================
 // Subprocess runner
 // ============================================================================
@@ -1981,6 +4302,59 @@ fn find_bbo_csv() -> Result<PathBuf, String> {
     Ok(bbo_csv)
 }
 
+/// Watch `folder` for filesystem changes and emit a debounced
+/// `Message::CaseFolderChanged` after each burst settles, re-running the
+/// same scan used when the folder is first selected.
+///
+/// Events are coalesced with a ~300ms debounce window so a batch write
+/// (e.g. a fetch or anonymize step writing several CSVs at once) triggers
+/// one rescan instead of dozens.
+fn watch_case_folder_stream(folder: PathBuf) -> impl futures::Stream<Item = Message> {
+    const DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(300);
+
+    let (tx, rx) = futures::channel::mpsc::unbounded();
+
+    std::thread::spawn(move || {
+        let (event_tx, event_rx) = std::sync::mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(event_tx) {
+            Ok(w) => w,
+            Err(_) => return,
+        };
+        if watcher
+            .watch(&folder, notify::RecursiveMode::Recursive)
+            .is_err()
+        {
+            return;
+        }
+
+        loop {
+            // Block for the first event of the next burst.
+            if event_rx.recv().is_err() {
+                return;
+            }
+            // Drain and coalesce any further events that arrive within the
+            // debounce window before acting on the burst.
+            while event_rx.recv_timeout(DEBOUNCE).is_ok() {}
+
+            let case_files = scan_case_folder(&folder);
+            let case_usernames = case_files
+                .concise_file
+                .as_deref()
+                .map(parse_concise_usernames)
+                .unwrap_or_default();
+
+            if tx
+                .unbounded_send(Message::CaseFolderChanged(case_files, case_usernames))
+                .is_err()
+            {
+                return;
+            }
+        }
+    });
+
+    rx
+}
+
 /// Run fetch-cardplay directly via the library and stream progress updates to the UI.
 ///
 /// Returns a stream of `Message` values: `ProgressUpdate` during execution,
@@ -1989,6 +4363,7 @@ fn fetch_cardplay_stream(
     mut config: pipeline::FetchCardplayConfig,
     deal_limit: Option<usize>,
     cancel: Arc<AtomicBool>,
+    run_id: u64,
 ) -> impl futures::Stream<Item = Message> {
     let (tx, rx) = futures::channel::mpsc::unbounded();
 
@@ -1998,10 +4373,10 @@ fn fetch_cardplay_stream(
             match pipeline::truncate_csv(&config.input, n) {
                 Ok(p) => config.input = p,
                 Err(e) => {
-                    let _ = tx.unbounded_send(Message::TaskFinished(Err(format!(
-                        "Failed to truncate CSV: {}",
-                        e
-                    ))));
+                    let _ = tx.unbounded_send(Message::TaskFinished(
+                        run_id,
+                        Err(format!("Failed to truncate CSV: {}", e)),
+                    ));
                     return;
                 }
             }
@@ -2009,6 +4384,36 @@ fn fetch_cardplay_stream(
 
         let result = pipeline::fetch_cardplay(&config, |p| {
             let _ = tx.unbounded_send(Message::ProgressUpdate {
+                run_id,
+                completed: p.completed,
+                total: p.total,
+                errors: p.errors,
+                skipped: p.skipped,
+            });
+            !cancel.load(Ordering::Relaxed)
+        });
+
+        let _ = tx.unbounded_send(Message::TaskFinished(run_id, result.map_err(|e| e.to_string())));
+    });
+
+    rx
+}
+
+/// Run DD analysis directly via the library and stream progress updates to the UI.
+///
+/// Returns a stream of `Message` values: `ProgressUpdate` during execution,
+/// and a final `TaskFinished` when complete or cancelled.
+fn analyze_dd_stream(
+    config: pipeline::AnalyzeDdConfig,
+    cancel: Arc<AtomicBool>,
+    run_id: u64,
+) -> impl futures::Stream<Item = Message> {
+    let (tx, rx) = futures::channel::mpsc::unbounded();
+
+    std::thread::spawn(move || {
+        let result = pipeline::analyze_dd(&config, |p| {
+            let _ = tx.unbounded_send(Message::ProgressUpdate {
+                run_id,
                 completed: p.completed,
                 total: p.total,
                 errors: p.errors,
@@ -2017,12 +4422,307 @@ fn fetch_cardplay_stream(
             !cancel.load(Ordering::Relaxed)
         });
 
-        let _ = tx.unbounded_send(Message::TaskFinished(result.map_err(|e| e.to_string())));
+        let _ = tx.unbounded_send(Message::TaskFinished(run_id, result.map_err(|e| e.to_string())));
+    });
+
+    rx
+}
+
+/// Create the packaged workbook and stream progress updates to the UI.
+///
+/// Returns a stream of `Message` values: `ProgressUpdate` during execution,
+/// and a final `PackageCompleted` when complete.
+fn package_workbook_stream(
+    config: pipeline::PackageConfig,
+    run_id: u64,
+) -> impl futures::Stream<Item = Message> {
+    let (tx, rx) = futures::channel::mpsc::unbounded();
+
+    std::thread::spawn(move || {
+        let result = pipeline::package_workbook(&config, |p| {
+            let _ = tx.unbounded_send(Message::ProgressUpdate {
+                run_id,
+                completed: p.completed,
+                total: p.total,
+                errors: 0,
+                skipped: 0,
+            });
+        });
+
+        let _ = tx.unbounded_send(Message::PackageCompleted(
+            run_id,
+            result.map_err(|e| e.to_string()),
+        ));
+    });
+
+    rx
+}
+
+/// Run anonymization directly via the library and stream progress updates to the UI.
+///
+/// Returns a stream of `Message` values: `ProgressUpdate` during execution,
+/// and a final `TaskFinished` when complete.
+fn anon_stream(
+    config: pipeline::AnonymizeAllConfig,
+    cancel: Arc<AtomicBool>,
+    run_id: u64,
+) -> impl futures::Stream<Item = Message> {
+    let (tx, rx) = futures::channel::mpsc::unbounded();
+
+    std::thread::spawn(move || {
+        let result = pipeline::anonymize_all(&config, |p| {
+            let _ = tx.unbounded_send(Message::ProgressUpdate {
+                run_id,
+                completed: p.completed,
+                total: p.total,
+                errors: 0,
+                skipped: 0,
+            });
+            !cancel.load(Ordering::Relaxed)
+        });
+
+        let _ = tx.unbounded_send(Message::TaskFinished(run_id, result.map_err(|e| e.to_string())));
+    });
+
+    rx
+}
+
+/// Compute DD error statistics directly via the library and stream progress
+/// updates to the UI.
+///
+/// Returns a stream of `Message` values: `ProgressUpdate` during execution,
+/// and a final `TaskFinished` when complete.
+fn stats_stream(
+    input: PathBuf,
+    top_n: usize,
+    cancel: Arc<AtomicBool>,
+    run_id: u64,
+) -> impl futures::Stream<Item = Message> {
+    let (tx, rx) = futures::channel::mpsc::unbounded();
+
+    std::thread::spawn(move || {
+        let result = pipeline::compute_stats(&input, top_n, None, |p| {
+            let _ = tx.unbounded_send(Message::ProgressUpdate {
+                run_id,
+                completed: p.completed,
+                total: p.total,
+                errors: 0,
+                skipped: 0,
+            });
+            !cancel.load(Ordering::Relaxed)
+        });
+
+        let _ = tx.unbounded_send(Message::TaskFinished(run_id, result.map_err(|e| e.to_string())));
     });
 
     rx
 }
 
+/// Run Fetch Cardplay -> Anonymize -> Analyze DD -> Package sequentially
+/// over every case in `cases`, in one background thread, checking `cancel`
+/// between each stage and each case. Per-case progress is reported the same
+/// way a single-case run would (`BatchCaseProgress`, the batch analog of
+/// `ProgressUpdate`), tagged with the case index so the UI can show both
+/// per-case and aggregate progress.
+fn batch_pipeline_stream(
+    cases: Vec<BatchCase>,
+    deal_limit: Option<usize>,
+    cancel: Arc<AtomicBool>,
+    run_id: u64,
+) -> impl futures::Stream<Item = Message> {
+    let (tx, rx) = futures::channel::mpsc::unbounded();
+
+    std::thread::spawn(move || {
+        for (index, case) in cases.iter().enumerate() {
+            if cancel.load(Ordering::Relaxed) {
+                break;
+            }
+            let _ = tx.unbounded_send(Message::BatchCaseStarted(run_id, index));
+
+            let result = run_one_batch_case(case, deal_limit, &cancel, run_id, index, &tx);
+
+            let _ = tx.unbounded_send(Message::BatchCaseCompleted(run_id, index, result));
+        }
+        let _ = tx.unbounded_send(Message::BatchAllDone(run_id));
+    });
+
+    rx
+}
+
+/// Run one case through Fetch -> Anonymize -> Analyze DD -> Package,
+/// reporting progress via `tx` tagged with `index`. Stops early (without
+/// treating it as an error) if `cancel` is set partway through.
+fn run_one_batch_case(
+    case: &BatchCase,
+    deal_limit: Option<usize>,
+    cancel: &Arc<AtomicBool>,
+    run_id: u64,
+    index: usize,
+    tx: &futures::channel::mpsc::UnboundedSender<Message>,
+) -> Result<String, String> {
+    let report = |completed: usize, total: usize, errors: usize, skipped: usize| {
+        let _ = tx.unbounded_send(Message::BatchCaseProgress {
+            run_id,
+            index,
+            completed,
+            total,
+            errors,
+            skipped,
+        });
+    };
+
+    let csv_file = case
+        .case_files
+        .csv_file
+        .clone()
+        .ok_or_else(|| "no hand-records CSV found".to_string())?;
+    let edgar_dir = case.folder.join("EDGAR Defense");
+    std::fs::create_dir_all(&edgar_dir).map_err(|e| e.to_string())?;
+
+    // -- Fetch Cardplay --
+    let csv_stem = csv_file.file_stem().and_then(|s| s.to_str()).unwrap_or("output");
+    let base = csv_stem.strip_prefix("Hand Records ").unwrap_or(csv_stem);
+    let fetch_output = edgar_dir.join(format!("{} cardplay.csv", base));
+    let fetch_input = match deal_limit {
+        Some(n) => pipeline::truncate_csv(&csv_file, n).map_err(|e| e.to_string())?,
+        None => csv_file.clone(),
+    };
+    let fetch_config = pipeline::FetchCardplayConfig {
+        input: fetch_input,
+        output: fetch_output.clone(),
+        url_column: "BBO".to_string(),
+        delay_ms: 20,
+        batch_size: 100,
+        batch_delay_ms: 500,
+        resume: false,
+    };
+    pipeline::fetch_cardplay(&fetch_config, |p| {
+        report(p.completed, p.total, p.errors, p.skipped);
+        !cancel.load(Ordering::Relaxed)
+    })
+    .map_err(|e| e.to_string())?;
+    if cancel.load(Ordering::Relaxed) {
+        return Err("cancelled".to_string());
+    }
+
+    // -- Anonymize --
+    let anon_output = add_suffix_to_filename(&fetch_output.display().to_string(), "anon");
+    let subject_players = case
+        .case_files
+        .concise_file
+        .as_deref()
+        .map(parse_concise_usernames)
+        .unwrap_or_default();
+    let key = case
+        .case_files
+        .concise_file
+        .as_deref()
+        .and_then(extract_concise_subject)
+        .unwrap_or_else(|| "default".to_string());
+    let default_names = ["Bob", "Sally"];
+    let map = subject_players
+        .iter()
+        .zip(default_names.iter())
+        .map(|(user, alias)| format!("{}={}", user, alias))
+        .collect::<Vec<String>>()
+        .join(",");
+
+    let concise_input = case.case_files.concise_file.clone();
+    let concise_output = concise_input.as_ref().map(|p| {
+        let stem = p.file_stem().and_then(|s| s.to_str()).unwrap_or("concise");
+        edgar_dir.join(format!("{} anon.txt", stem))
+    });
+    let hotspot_input = case.case_files.hotspot_file.clone();
+    let hotspot_output = hotspot_input.as_ref().map(|p| {
+        let stem = p.file_stem().and_then(|s| s.to_str()).unwrap_or("hotspot");
+        edgar_dir.join(format!("{} anon.txt", stem))
+    });
+
+    let anon_config = pipeline::AnonymizeAllConfig {
+        csv_input: fetch_output.clone(),
+        csv_output: PathBuf::from(&anon_output),
+        key,
+        map,
+        columns: "N,S,E,W,Ob name,Dec name,Leader"
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .collect(),
+        concise_input,
+        concise_output,
+        hotspot_input,
+        hotspot_output,
+        subject_players: subject_players.clone(),
+        live_resolve_urls: false,
+        url_cache_path: None,
+        url_cache_ttl: None,
+        url_rules_path: None,
+    };
+    pipeline::anonymize_all(&anon_config, |p| {
+        report(p.completed, p.total, 0, 0);
+        !cancel.load(Ordering::Relaxed)
+    })
+    .map_err(|e| e.to_string())?;
+    if cancel.load(Ordering::Relaxed) {
+        return Err("cancelled".to_string());
+    }
+
+    // -- Analyze DD --
+    let analyze_output = derive_analyze_output(&anon_output);
+    let analyze_config = pipeline::AnalyzeDdConfig {
+        input: PathBuf::from(&anon_output),
+        output: PathBuf::from(&analyze_output),
+        threads: None,
+        resume: false,
+        checkpoint_interval: 100,
+        error_mode: pipeline::DdErrorMode::default(),
+    };
+    pipeline::analyze_dd(&analyze_config, |p| {
+        report(p.completed, p.total, p.errors, p.skipped);
+        !cancel.load(Ordering::Relaxed)
+    })
+    .map_err(|e| e.to_string())?;
+    if cancel.load(Ordering::Relaxed) {
+        return Err("cancelled".to_string());
+    }
+
+    // -- Package --
+    let hotspot = case
+        .case_files
+        .hotspot_file
+        .clone()
+        .ok_or_else(|| "no hotspot report found".to_string())?;
+    let concise = case
+        .case_files
+        .concise_file
+        .clone()
+        .ok_or_else(|| "no concise report found".to_string())?;
+    let subject = extract_concise_subject(&concise).unwrap_or_else(|| "Report".to_string());
+    let package_output = edgar_dir.join(format!("EDGAR Defense {}.xlsx", subject));
+    let package_config = pipeline::PackageConfig {
+        csv_file,
+        hotspot_file: hotspot,
+        concise_file: concise,
+        output: package_output.clone(),
+        case_folder: case.folder.display().to_string(),
+        subject_players,
+        deal_limit,
+        cardplay_file: Some(PathBuf::from(&analyze_output)),
+        is_anon: false,
+        classifier_corpus: None,
+        output_format: pipeline::OutputFormat::Xlsx,
+        hyperlink_dialect: pipeline::HyperlinkDialect::Excel,
+        flat_export: None,
+        category_palette: None,
+        category_color_overrides: std::collections::HashMap::new(),
+    };
+    pipeline::package_workbook(&package_config, |p| {
+        report(p.completed, p.total, 0, 0);
+    })
+    .map_err(|e| e.to_string())?;
+
+    Ok(package_output.display().to_string())
+}
+
 /// Run the `bbo-csv` CLI binary as a subprocess, returning combined output.
 fn run_bbo_csv(args: Vec<String>) -> Result<String, String> {
     let bbo_csv = find_bbo_csv()?;
@@ -2057,3 +4757,172 @@ fn run_bbo_csv(args: Vec<String>) -> Result<String, String> {
         ))
     }
 }
+
+// ============================================================================
+// External control pipe
+// ============================================================================
+
+/// A command received over the `msg_in` pipe, mapped onto an existing
+/// `Message` variant so it can be replayed through `App::update` exactly
+/// like a button press.
+#[derive(Debug, Clone)]
+enum ExternalMsg {
+    SetCaseFolder(PathBuf),
+    SetDealLimit(usize),
+    StartFetch,
+    StartAnalyze,
+    StartPackage,
+    Cancel,
+}
+
+impl ExternalMsg {
+    /// Parse one `msg_in` line, e.g. `set-case-folder /path/to/case` or
+    /// `start-fetch`. Deliberately a small whitespace DSL rather than JSON,
+    /// to match the plain-text config formats used elsewhere in this binary.
+    fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim();
+        let (cmd, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let rest = rest.trim();
+        match cmd {
+            "set-case-folder" if !rest.is_empty() => {
+                Ok(ExternalMsg::SetCaseFolder(PathBuf::from(rest)))
+            }
+            "set-deal-limit" => rest
+                .parse()
+                .map(ExternalMsg::SetDealLimit)
+                .map_err(|_| format!("invalid deal limit: {}", rest)),
+            "start-fetch" => Ok(ExternalMsg::StartFetch),
+            "start-analyze" => Ok(ExternalMsg::StartAnalyze),
+            "start-package" => Ok(ExternalMsg::StartPackage),
+            "cancel" => Ok(ExternalMsg::Cancel),
+            _ => Err(format!("unrecognized command: {}", line)),
+        }
+    }
+}
+
+/// Default session directory when `--session` isn't given: a per-process
+/// folder under the OS temp dir, so multiple instances don't collide.
+fn default_session_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("edgar-defense-{}", std::process::id()))
+}
+
+/// The four named pipes a controlling script uses to drive the app
+/// headlessly: `msg_in` for commands, and `result_out`/`progress_out`/
+/// `log_out` for the app to report back on, modeled on xplr's `msg_in`.
+struct ExternalSession {
+    dir: PathBuf,
+    result_tx: std::sync::mpsc::Sender<String>,
+    progress_tx: std::sync::mpsc::Sender<String>,
+    log_tx: std::sync::mpsc::Sender<String>,
+}
+
+impl ExternalSession {
+    /// Create the session directory and its pipes, and spawn the threads
+    /// that service them for the lifetime of the app. Returns the session
+    /// handle plus a stream of `Message`s decoded from `msg_in`.
+    fn start(dir: PathBuf) -> Result<(Self, impl futures::Stream<Item = Message>), String> {
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("failed to create session dir {:?}: {}", dir, e))?;
+
+        let msg_in = dir.join("msg_in");
+        let result_out = dir.join("result_out");
+        let progress_out = dir.join("progress_out");
+        let log_out = dir.join("log_out");
+
+        for path in [&msg_in, &result_out, &progress_out, &log_out] {
+            make_fifo(path)?;
+        }
+
+        println!("external control pipe: {}", dir.display());
+
+        let result_tx = spawn_pipe_writer(result_out);
+        let progress_tx = spawn_pipe_writer(progress_out);
+        let log_tx = spawn_pipe_writer(log_out);
+
+        let (msg_tx, msg_rx) = futures::channel::mpsc::unbounded();
+        std::thread::spawn(move || loop {
+            let file = match std::fs::File::open(&msg_in) {
+                Ok(f) => f,
+                Err(_) => return,
+            };
+            for line in std::io::BufReader::new(file).lines() {
+                let Ok(line) = line else { break };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let msg = match ExternalMsg::parse(&line) {
+                    Ok(cmd) => Message::ExternalCommand(cmd),
+                    Err(e) => Message::ExternalCommandError(e),
+                };
+                if msg_tx.unbounded_send(msg).is_err() {
+                    return;
+                }
+            }
+            // Writer closed; reopen and wait for the next one.
+        });
+
+        Ok((
+            ExternalSession {
+                dir,
+                result_tx,
+                progress_tx,
+                log_tx,
+            },
+            msg_rx,
+        ))
+    }
+
+    fn send_result(&self, line: &str) {
+        let _ = self.result_tx.send(line.to_string());
+    }
+
+    fn send_progress(&self, line: &str) {
+        let _ = self.progress_tx.send(line.to_string());
+    }
+
+    fn send_log(&self, line: &str) {
+        let _ = self.log_tx.send(line.to_string());
+    }
+}
+
+impl Drop for ExternalSession {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Create a FIFO at `path`, removing any stale file left from a previous run.
+fn make_fifo(path: &Path) -> Result<(), String> {
+    let _ = std::fs::remove_file(path);
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| format!("invalid pipe path {:?}: {}", path, e))?;
+    let rc = unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) };
+    if rc != 0 {
+        return Err(format!(
+            "mkfifo failed for {:?}: {}",
+            path,
+            std::io::Error::last_os_error()
+        ));
+    }
+    Ok(())
+}
+
+/// Spawn a thread that opens `path` for writing — blocking until a reader
+/// attaches, same as a controlling script blocking on `cat result_out` — and
+/// forwards lines sent over the returned channel. Reopens the pipe whenever
+/// the current reader disconnects, so repeated runs keep working.
+fn spawn_pipe_writer(path: PathBuf) -> std::sync::mpsc::Sender<String> {
+    let (tx, rx) = std::sync::mpsc::channel::<String>();
+    std::thread::spawn(move || loop {
+        let mut file = match std::fs::OpenOptions::new().write(true).open(&path) {
+            Ok(f) => f,
+            Err(_) => return,
+        };
+        while let Ok(line) = rx.recv() {
+            if writeln!(file, "{}", line).is_err() {
+                break;
+            }
+        }
+    });
+    tx
+}
@@ -14,8 +14,11 @@
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use edgar_defense_toolkit::anon_common::*;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 #[derive(Parser)]
 #[command(
@@ -57,6 +60,15 @@ struct Cli {
     /// Text map file for page text replacement (one "old=new" pair per line)
     #[arg(long)]
     text_map: Option<PathBuf>,
+
+    /// Smallest width/height (pixels) for an image to be treated as a BBO screenshot
+    #[arg(long, default_value_t = 1000)]
+    min_image_dim: usize,
+
+    /// Write a structured JSON run report (counts per phase, unmatched
+    /// tinyurls, per-page text tallies) to this path
+    #[arg(long)]
+    report: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -78,6 +90,80 @@ enum Commands {
         /// Output mapping CSV
         #[arg(short, long)]
         output: PathBuf,
+
+        /// Number of tinyurls to resolve concurrently
+        #[arg(long, default_value_t = 8)]
+        jobs: usize,
+
+        /// Persistent JSON cache of resolved tinyurls, reused across runs
+        #[arg(long)]
+        cache: Option<PathBuf>,
+
+        /// Max requests per second sent to any single redirect host
+        #[arg(long, default_value_t = 5.0)]
+        rate_limit: f64,
+
+        /// Sharded cache directory (one file per resolved tinyurl), so an
+        /// interrupted run can resume without re-fetching already-resolved
+        /// links. Distinct from --cache, which is a single flat JSON file.
+        #[arg(long)]
+        cache_dir: Option<PathBuf>,
+    },
+
+    /// Run the replace pipeline over every PDF found under a directory tree
+    Batch {
+        /// Directory to scan recursively for .pdf files
+        #[arg(long)]
+        input_dir: PathBuf,
+
+        /// Directory to write anonymized PDFs to, mirroring input_dir's
+        /// relative layout
+        #[arg(long)]
+        output_dir: PathBuf,
+
+        /// Tinyurl lookup CSV
+        #[arg(long)]
+        lookup: PathBuf,
+
+        /// Anonymized DD CSV
+        #[arg(long)]
+        anon: PathBuf,
+
+        /// Extra tinyurl -> anon URL mappings not present in lookup/anon
+        #[arg(long)]
+        extra_map: Option<PathBuf>,
+
+        /// Skip BBO screenshot image anonymization
+        #[arg(long)]
+        no_images: bool,
+
+        /// Extra "orig=anon" player name overrides, comma-separated
+        #[arg(long)]
+        name_map: Option<String>,
+
+        /// Extra page-text replacement map file (one "old=new" pair per line)
+        #[arg(long)]
+        text_map: Option<PathBuf>,
+
+        /// Smallest width/height (pixels) for an image to be treated as a BBO screenshot
+        #[arg(long, default_value_t = 1000)]
+        min_image_dim: usize,
+
+        /// Additional glob exclude pattern (repeatable), matched against
+        /// each file's path relative to input_dir
+        #[arg(long)]
+        exclude: Vec<String>,
+    },
+
+    /// Check a finished PDF for surviving (pre-anonymization) player names
+    Verify {
+        /// PDF to check
+        #[arg(long)]
+        pdf: PathBuf,
+
+        /// Forbidden names, one per line
+        #[arg(long)]
+        names: PathBuf,
     },
 }
 
@@ -178,8 +264,9 @@ fn resolve_obj<'a>(doc: &'a lopdf::Document, obj: &'a lopdf::Object) -> &'a lopd
 }
 
 /// Collect `(image_object_id, lin_url)` pairs for each page that has a BBO
-/// screenshot (large image) and a handviewer link annotation.
-fn collect_page_image_info(doc: &lopdf::Document) -> Vec<(lopdf::ObjectId, String)> {
+/// screenshot (an image at least `min_dim` on each side) and a handviewer
+/// link annotation.
+fn collect_page_image_info(doc: &lopdf::Document, min_dim: usize) -> Vec<(lopdf::ObjectId, String)> {
     let mut results = Vec::new();
     let pages = doc.get_pages();
 
@@ -278,7 +365,7 @@ fn collect_page_image_info(doc: &lopdf::Document) -> Vec<(lopdf::ObjectId, Strin
                 })
                 .unwrap_or(0);
 
-            if width > 1000 && height > 1000 {
+            if width >= min_dim && height >= min_dim {
                 results.push((img_id, lin_url.clone()));
                 break; // one image per page
             }
@@ -290,13 +377,151 @@ fn collect_page_image_info(doc: &lopdf::Document) -> Vec<(lopdf::ObjectId, Strin
 
 // TrueType text rendering and image modification functions are in anon_common.
 
+/// How an image XObject's samples are stored, which decides how to get it
+/// to an RGB buffer `modify_screenshot_pixels` can overwrite in place.
+enum ImageKind {
+    /// Already `/DeviceRGB` with no filter (or a filter that decompresses
+    /// straight to raw RGB), the original, still-most-common shape.
+    RawRgb,
+    /// `/DCTDecode` (JPEG) -- `stream.content` is the compressed JPEG bytes
+    /// directly, never Flate-wrapped.
+    Jpeg,
+    /// `/DeviceGray`, one byte per pixel once decompressed.
+    Gray,
+    /// `/Indexed` over a `/DeviceRGB` base: one palette-index byte per
+    /// pixel once decompressed, plus the flattened RGB palette itself.
+    Indexed(Vec<u8>),
+}
+
+/// Inspect an image stream's `/Filter`/`/ColorSpace` to decide how to
+/// decode it to RGB.
+fn detect_image_kind(doc: &lopdf::Document, stream: &lopdf::Stream) -> Result<ImageKind> {
+    let is_dct = match stream.dict.get(b"Filter").ok() {
+        Some(lopdf::Object::Name(n)) => n == b"DCTDecode",
+        Some(lopdf::Object::Array(arr)) => {
+            arr.iter().any(|f| matches!(f, lopdf::Object::Name(n) if n == b"DCTDecode"))
+        }
+        _ => false,
+    };
+    if is_dct {
+        return Ok(ImageKind::Jpeg);
+    }
+
+    let colorspace = stream.dict.get(b"ColorSpace").ok().map(|o| resolve_obj(doc, o));
+    match colorspace {
+        Some(lopdf::Object::Name(n)) if n == b"DeviceGray" => Ok(ImageKind::Gray),
+        Some(lopdf::Object::Array(arr))
+            if matches!(arr.first(), Some(lopdf::Object::Name(n)) if n == b"Indexed") =>
+        {
+            Ok(ImageKind::Indexed(decode_indexed_palette(doc, arr)?))
+        }
+        _ => Ok(ImageKind::RawRgb),
+    }
+}
+
+/// Read an `/Indexed` colorspace array's lookup table (`[/Indexed base
+/// hival lookup]`), as a flattened RGB palette. Assumes a `/DeviceRGB`
+/// base (3 bytes/entry), the only base this toolkit's inputs use.
+fn decode_indexed_palette(doc: &lopdf::Document, arr: &[lopdf::Object]) -> Result<Vec<u8>> {
+    match arr.get(3).map(|o| resolve_obj(doc, o)) {
+        Some(lopdf::Object::String(bytes, _)) => Ok(bytes.clone()),
+        Some(lopdf::Object::Stream(s)) => {
+            let mut s = s.clone();
+            s.decompress().ok();
+            Ok(s.content)
+        }
+        _ => anyhow::bail!("Indexed colorspace missing a lookup table"),
+    }
+}
+
+/// Decode an image stream to a flat RGB buffer (`width * height * 3`
+/// bytes), per its detected `ImageKind`.
+fn decode_to_rgb(stream: &mut lopdf::Stream, width: usize, height: usize, kind: &ImageKind) -> Result<Vec<u8>> {
+    match kind {
+        ImageKind::Jpeg => {
+            let img = image::load_from_memory_with_format(&stream.content, image::ImageFormat::Jpeg)
+                .context("Failed to decode JPEG image stream")?;
+            Ok(img.to_rgb8().into_raw())
+        }
+        ImageKind::RawRgb => {
+            stream
+                .decompress()
+                .map_err(|e| anyhow::anyhow!("failed to decompress image stream: {e}"))?;
+            let expected = width * height * 3;
+            anyhow::ensure!(
+                stream.content.len() >= expected,
+                "image data too short: {} < {}",
+                stream.content.len(),
+                expected
+            );
+            Ok(stream.content[..expected].to_vec())
+        }
+        ImageKind::Gray => {
+            stream
+                .decompress()
+                .map_err(|e| anyhow::anyhow!("failed to decompress image stream: {e}"))?;
+            let expected = width * height;
+            anyhow::ensure!(
+                stream.content.len() >= expected,
+                "gray image data too short: {} < {}",
+                stream.content.len(),
+                expected
+            );
+            let gray = image::GrayImage::from_raw(width as u32, height as u32, stream.content[..expected].to_vec())
+                .context("gray image dimensions don't match stream length")?;
+            Ok(image::DynamicImage::ImageLuma8(gray).to_rgb8().into_raw())
+        }
+        ImageKind::Indexed(palette) => {
+            stream
+                .decompress()
+                .map_err(|e| anyhow::anyhow!("failed to decompress image stream: {e}"))?;
+            let expected = width * height;
+            anyhow::ensure!(
+                stream.content.len() >= expected,
+                "indexed image data too short: {} < {}",
+                stream.content.len(),
+                expected
+            );
+            let mut rgb = Vec::with_capacity(expected * 3);
+            for &idx in &stream.content[..expected] {
+                let base = idx as usize * 3;
+                rgb.extend_from_slice(palette.get(base..base + 3).unwrap_or(&[0, 0, 0]));
+            }
+            Ok(rgb)
+        }
+    }
+}
+
+/// Write a modified RGB buffer back to the stream. `RawRgb` round-trips
+/// through the stream's existing filter, unchanged from before; anything
+/// else is re-encoded as Flate-compressed `/DeviceRGB`, since round-tripping
+/// back through JPEG or rebuilding a palette buys nothing once the pixels
+/// have already been touched.
+fn write_back_rgb(stream: &mut lopdf::Stream, rgb: Vec<u8>, kind: &ImageKind) {
+    stream.content = rgb;
+    if !matches!(kind, ImageKind::RawRgb) {
+        stream.dict.set("Filter", lopdf::Object::Name(b"FlateDecode".to_vec()));
+        stream.dict.set("ColorSpace", lopdf::Object::Name(b"DeviceRGB".to_vec()));
+        stream.dict.set("BitsPerComponent", lopdf::Object::Integer(8));
+        stream.dict.remove(b"DecodeParms");
+        stream.dict.remove(b"Decode");
+    }
+    let _ = stream.compress();
+}
+
 /// Process all BBO screenshot images in the document, overwriting player name
 /// areas with anonymized names extracted from the page's link annotation URL.
-fn anonymize_bbo_images(doc: &mut lopdf::Document) -> Result<u32> {
+/// `min_dim` is the smallest width/height (in pixels) a screenshot must have
+/// on each side to be treated as a BBO result image.
+fn anonymize_bbo_images(doc: &mut lopdf::Document, min_dim: usize) -> Result<u32> {
     let font = load_system_font()?;
+    // Shared across every image processed below -- a PDF's BBO screenshots
+    // are handled sequentially here (unlike docx-anon's rayon pipeline), so
+    // one cache sees every glyph the whole document redacts.
+    let mut glyph_cache = edgar_defense_toolkit::anon_common::GlyphCache::new();
 
     // First pass: collect image object IDs and their associated LIN URLs
-    let image_info = collect_page_image_info(doc);
+    let image_info = collect_page_image_info(doc, min_dim);
     let mut modified = 0u32;
 
     for (img_id, lin_url) in &image_info {
@@ -311,8 +536,8 @@ fn anonymize_bbo_images(doc: &mut lopdf::Document) -> Result<u32> {
             }
         };
 
-        // Get image dimensions from the stream dictionary
-        let (width, height) = {
+        // Get image dimensions and storage shape from the stream dictionary
+        let (width, height, kind) = {
             let stream = match doc.get_object(*img_id) {
                 Ok(lopdf::Object::Stream(s)) => s,
                 _ => continue,
@@ -341,44 +566,37 @@ fn anonymize_bbo_images(doc: &mut lopdf::Document) -> Result<u32> {
                     }
                 })
                 .unwrap_or(0);
-            (w, h)
+            let kind = match detect_image_kind(doc, stream) {
+                Ok(k) => k,
+                Err(e) => {
+                    eprintln!("  Warning: {:?}: {}", img_id, e);
+                    continue;
+                }
+            };
+            (w, h, kind)
         };
 
         if width == 0 || height == 0 {
             continue;
         }
 
-        // Decompress and modify the stream content (raw RGB bytes)
         let stream = match doc.get_object_mut(*img_id) {
             Ok(lopdf::Object::Stream(s)) => s,
             _ => continue,
         };
 
-        // Decompress the stream data
-        if stream.decompress().is_err() {
-            eprintln!(
-                "  Warning: failed to decompress image stream (obj {:?})",
-                img_id
-            );
-            continue;
-        }
-
-        let expected_len = width * height * 3;
-        if stream.content.len() < expected_len {
-            eprintln!(
-                "  Warning: image data too short (obj {:?}): {} < {}",
-                img_id,
-                stream.content.len(),
-                expected_len
-            );
-            continue;
-        }
+        let mut rgb = match decode_to_rgb(stream, width, height, &kind) {
+            Ok(buf) => buf,
+            Err(e) => {
+                eprintln!("  Warning: failed to decode image (obj {:?}): {}", img_id, e);
+                continue;
+            }
+        };
 
         // Modify the pixels
-        modify_screenshot_pixels(&mut stream.content, width, height, &names, &font, 3);
+        modify_screenshot_pixels(&mut rgb, width, height, &names, &font, 3, &mut glyph_cache);
 
-        // Re-compress
-        let _ = stream.compress();
+        write_back_rgb(stream, rgb, &kind);
 
         println!(
             "  Image {:?} ({}x{}): {} -> [N]{} [S]{} [W]{} [E]{}",
@@ -408,6 +626,158 @@ fn resolve_tinyurl(client: &reqwest::blocking::Client, url: &str) -> Result<Stri
     Ok(resp.url().to_string())
 }
 
+/// `resolve_tinyurl`, retried with doubling backoff on transient failures.
+fn resolve_with_retry(client: &reqwest::blocking::Client, url: &str, attempts: u32) -> Result<String> {
+    let mut last_err = None;
+    for attempt in 0..attempts {
+        if attempt > 0 {
+            std::thread::sleep(std::time::Duration::from_millis(200 * 2u64.pow(attempt - 1)));
+        }
+        match resolve_tinyurl(client, url) {
+            Ok(dest) => return Ok(dest),
+            Err(e) => last_err = Some(e),
+        }
+    }
+    Err(last_err.unwrap())
+}
+
+/// Per-host token bucket so concurrent resolve workers don't hammer a
+/// single redirect endpoint (e.g. ACBL's tinyurl host) all at once.
+struct RateLimiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    state: std::sync::Mutex<(f64, std::time::Instant)>,
+}
+
+impl RateLimiter {
+    fn new(rate_per_sec: f64) -> Self {
+        let capacity = rate_per_sec.max(0.1);
+        RateLimiter {
+            capacity,
+            refill_per_sec: capacity,
+            state: std::sync::Mutex::new((capacity, std::time::Instant::now())),
+        }
+    }
+
+    /// Block the calling thread until a token is available, then consume one.
+    fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap();
+                let now = std::time::Instant::now();
+                let elapsed = now.duration_since(state.1).as_secs_f64();
+                state.0 = (state.0 + elapsed * self.refill_per_sec).min(self.capacity);
+                state.1 = now;
+                if state.0 >= 1.0 {
+                    state.0 -= 1.0;
+                    None
+                } else {
+                    Some(std::time::Duration::from_secs_f64((1.0 - state.0) / self.refill_per_sec))
+                }
+            };
+            match wait {
+                None => return,
+                Some(d) => std::thread::sleep(d),
+            }
+        }
+    }
+}
+
+/// Host part of a URL, for keying per-host rate limiters (`"example.com"`
+/// out of `"https://example.com/path"`).
+fn url_host(url: &str) -> &str {
+    let rest = url.splitn(2, "://").nth(1).unwrap_or(url);
+    rest.split('/').next().unwrap_or(rest)
+}
+
+/// Get-or-create the rate limiter for `host`, sharing one bucket per host
+/// across all resolve workers.
+fn rate_limiter_for(
+    limiters: &std::sync::Mutex<HashMap<String, Arc<RateLimiter>>>,
+    host: &str,
+    rate_per_sec: f64,
+) -> Arc<RateLimiter> {
+    let mut map = limiters.lock().unwrap();
+    map.entry(host.to_string())
+        .or_insert_with(|| Arc::new(RateLimiter::new(rate_per_sec)))
+        .clone()
+}
+
+/// Shard filename for a normalized tinyurl key: a hash of the key, not the
+/// key itself, since keys can contain characters that aren't safe in a
+/// filename on every platform.
+fn cache_dir_entry_path(dir: &Path, key: &str) -> PathBuf {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    key.hash(&mut hasher);
+    dir.join(format!("{:016x}.json", hasher.finish()))
+}
+
+/// Load one entry from the sharded cache directory, if present.
+fn load_cache_dir_entry(dir: &Path, key: &str) -> Option<TinyurlCacheEntry> {
+    let text = std::fs::read_to_string(cache_dir_entry_path(dir, key)).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+/// Write one entry into the sharded cache directory, atomically (temp file
+/// in the same directory + rename) so a killed run never leaves a
+/// half-written entry behind.
+fn write_cache_dir_entry(dir: &Path, key: &str, entry: &TinyurlCacheEntry) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create cache dir {}", dir.display()))?;
+    let path = cache_dir_entry_path(dir, key);
+    let tmp_path = path.with_extension("tmp");
+    let serialized = serde_json::to_string(entry)?;
+    std::fs::write(&tmp_path, &serialized)
+        .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+    std::fs::rename(&tmp_path, &path)
+        .with_context(|| format!("Failed to move {} into place", path.display()))?;
+    Ok(())
+}
+
+/// One cached tinyurl resolution, keyed by `normalize_tinyurl(url)` in
+/// `TinyurlCache::entries`.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct TinyurlCacheEntry {
+    dest: String,
+    fingerprint: Option<String>,
+}
+
+/// Persistent JSON cache of resolved tinyurl destinations, so a re-run of
+/// `resolve` doesn't re-fetch links it already resolved.
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct TinyurlCache {
+    entries: HashMap<String, TinyurlCacheEntry>,
+}
+
+/// Load the cache at `path`, or an empty one if the file doesn't exist yet.
+fn load_tinyurl_cache(path: &Path) -> Result<TinyurlCache> {
+    if !path.exists() {
+        return Ok(TinyurlCache::default());
+    }
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read cache {}", path.display()))?;
+    Ok(serde_json::from_str(&text)
+        .with_context(|| format!("Failed to parse cache {}", path.display()))?)
+}
+
+/// Write `cache` to `path` atomically (temp file + rename), but only if its
+/// serialized contents differ from what's already on disk.
+fn write_tinyurl_cache_if_changed(path: &Path, cache: &TinyurlCache) -> Result<()> {
+    let serialized = serde_json::to_string_pretty(cache)?;
+    if let Ok(existing) = std::fs::read_to_string(path) {
+        if existing == serialized {
+            return Ok(());
+        }
+    }
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, &serialized)
+        .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+    std::fs::rename(&tmp_path, path)
+        .with_context(|| format!("Failed to move {} into place", path.display()))?;
+    Ok(())
+}
+
 /// Replace player names in all URI annotations (not just tinyurls).
 /// This catches direct handviewer links that contain original player names.
 fn replace_names_in_uris(doc: &mut lopdf::Document, name_pairs: &[(String, String)]) -> u32 {
@@ -473,59 +843,112 @@ fn load_text_map_bytes(path: &std::path::Path) -> Result<Vec<(Vec<u8>, Vec<u8>)>
         .collect())
 }
 
-/// Location of a single character byte within the parsed content operations.
+/// Anonymize player names directly in a page's text layer by rewriting
+/// matched `Tj`/`TJ` runs via `pdf_text::anonymize_text_runs`. Complements
+/// `anonymize_bbo_images`, which only redacts the rasterized screenshot --
+/// this closes the same leak for PDFs whose BBO result table is real,
+/// searchable text rather than (or in addition to) a screenshot.
+fn anonymize_page_text_layer(
+    doc: &mut lopdf::Document,
+    page_id: lopdf::ObjectId,
+    replacements: &[(Vec<u8>, Vec<u8>)],
+    font: &edgar_defense_toolkit::anon_common::FontStack,
+    glyph_cache: &mut edgar_defense_toolkit::anon_common::GlyphCache,
+) -> Result<usize> {
+    let mut content = doc.get_and_decode_page_content(page_id)?;
+    let count = edgar_defense_toolkit::pdf_text::anonymize_text_runs(
+        &mut content.operations,
+        replacements,
+        font,
+        glyph_cache,
+    );
+    if count > 0 {
+        let encoded = content.encode()?;
+        doc.change_page_content(page_id, encoded)?;
+    }
+    Ok(count)
+}
+
+/// Location of a single character (one font code, 1 or 2 bytes) within the
+/// parsed content operations.
 #[derive(Clone)]
 struct TextChar {
     op_idx: usize,
     /// For TJ: element index within the Array. For Tj: always 0.
     arr_idx: usize,
-    /// Byte offset within that String operand.
+    /// Byte offset of this code within that String operand, or `usize::MAX`
+    /// for a synthetic space inserted for large negative TJ kerning.
     byte_idx: usize,
+    /// How many bytes this code occupies (1 for simple fonts, 2 for
+    /// Identity-H-style Type0/CID fonts) -- unused when `byte_idx` is the
+    /// synthetic-space sentinel.
+    code_bytes: usize,
+    /// Index into this page's font table the code was decoded/written
+    /// with, so a padded replacement char re-encodes with the same font.
+    font_idx: usize,
 }
 
 /// Replace text in a single page's content stream, matching across TJ operands.
 ///
-/// Uses per-character in-place byte replacement: each replacement byte is written
-/// to the exact operand position of the corresponding search byte.  This preserves
-/// all original kerning, cursor advance, and font context.
+/// Decodes each `Tj`/`TJ` operand to Unicode using the active font's
+/// `pdf_font::FontEncoding` (tracked via `Tf`) so matching works regardless
+/// of the font's `/Encoding`/`/ToUnicode` table, then writes the replacement
+/// back through the same per-character in-place byte write `replace_page_text`
+/// has always used: each matched character's bytes are overwritten at their
+/// exact operand position, preserving all original kerning and cursor
+/// advance. If the replacement is shorter, remaining matched positions are
+/// set to that font's space code.
 fn replace_page_text(
     doc: &mut lopdf::Document,
     page_id: lopdf::ObjectId,
     replacements: &[(Vec<u8>, Vec<u8>)],
-) -> Result<usize> {
+) -> Result<(usize, bool)> {
+    let font_table = page_font_table(doc, page_id);
+    let has_unmapped_font = font_table_has_unmapped(&font_table);
+    if has_unmapped_font {
+        eprintln!(
+            "  Warning: page {:?} has a font with no usable character table -- \
+             text replacement can't see that font's real characters on this page",
+            page_id
+        );
+    }
+
     let mut content = doc.get_and_decode_page_content(page_id)?;
     let mut total = 0usize;
     let mut changed = false;
 
-    // Build text blocks: collect all (byte, location) tuples between BT/ET.
-    let mut blocks: Vec<(Vec<u8>, Vec<TextChar>)> = Vec::new();
-    let mut cur_bytes: Vec<u8> = Vec::new();
+    // Build text blocks: collect all (char, location) pairs between BT/ET,
+    // decoding each string operand through the font active at that point.
+    let mut blocks: Vec<(Vec<char>, Vec<TextChar>)> = Vec::new();
+    let mut cur_chars: Vec<char> = Vec::new();
     let mut cur_locs: Vec<TextChar> = Vec::new();
     let mut in_text = false;
+    let mut font_idx: usize = usize::MAX;
 
     for (op_idx, op) in content.operations.iter().enumerate() {
         match op.operator.as_ref() {
             "BT" => {
                 in_text = true;
-                cur_bytes.clear();
+                cur_chars.clear();
                 cur_locs.clear();
             }
             "ET" => {
-                if !cur_bytes.is_empty() {
-                    blocks.push((cur_bytes.clone(), cur_locs.clone()));
+                if !cur_chars.is_empty() {
+                    blocks.push((cur_chars.clone(), cur_locs.clone()));
                 }
                 in_text = false;
             }
+            "Tf" => {
+                if let Some(lopdf::Object::Name(name)) = op.operands.first() {
+                    font_idx = font_table
+                        .iter()
+                        .position(|(n, _)| n == name)
+                        .unwrap_or(usize::MAX);
+                }
+            }
             "Tj" if in_text => {
                 if let Some(lopdf::Object::String(bytes, _)) = op.operands.first() {
-                    for (byte_idx, &b) in bytes.iter().enumerate() {
-                        cur_bytes.push(b);
-                        cur_locs.push(TextChar {
-                            op_idx,
-                            arr_idx: 0,
-                            byte_idx,
-                        });
-                    }
+                    push_decoded_run(&font_table, font_idx, bytes, op_idx, 0, &mut cur_chars, &mut cur_locs);
                 }
             }
             "TJ" if in_text => {
@@ -533,22 +956,17 @@ fn replace_page_text(
                     for (arr_idx, item) in arr.iter().enumerate() {
                         match item {
                             lopdf::Object::String(bytes, _) => {
-                                for (byte_idx, &b) in bytes.iter().enumerate() {
-                                    cur_bytes.push(b);
-                                    cur_locs.push(TextChar {
-                                        op_idx,
-                                        arr_idx,
-                                        byte_idx,
-                                    });
-                                }
+                                push_decoded_run(&font_table, font_idx, bytes, op_idx, arr_idx, &mut cur_chars, &mut cur_locs);
                             }
                             lopdf::Object::Integer(n) if *n < -100 => {
                                 // Large negative kerning ≈ word space
-                                cur_bytes.push(b' ');
+                                cur_chars.push(' ');
                                 cur_locs.push(TextChar {
                                     op_idx,
                                     arr_idx,
                                     byte_idx: usize::MAX,
+                                    code_bytes: 0,
+                                    font_idx,
                                 });
                             }
                             _ => {}
@@ -560,38 +978,42 @@ fn replace_page_text(
         }
     }
 
-    // Apply replacements to each text block using per-character in-place
-    // byte replacement.  Each replacement byte is written to the exact
-    // operand position of the corresponding search byte, preserving all
-    // original kerning and cursor advance.  If the replacement is shorter,
-    // remaining matched positions are set to space (0x20).
-    for (block_bytes, block_locs) in &blocks {
-        for (search, replace) in replacements {
+    // Apply replacements to each text block by matching in Unicode space,
+    // then writing the replacement's bytes back through the font each
+    // matched character was decoded with.
+    for (block_chars, block_locs) in &blocks {
+        for (search_bytes, replace_bytes) in replacements {
+            let search: Vec<char> = String::from_utf8_lossy(search_bytes).chars().collect();
+            let replace: Vec<char> = String::from_utf8_lossy(replace_bytes).chars().collect();
             if search.is_empty() {
                 continue;
             }
-            // Pad replacement to search length with spaces.
-            let mut padded = replace.to_vec();
-            while padded.len() < search.len() {
-                padded.push(b' ');
-            }
             let mut start = 0;
-            while start + search.len() <= block_bytes.len() {
-                if let Some(pos) = find_subsequence(&block_bytes[start..], search) {
-                    let abs_pos = start + pos;
-                    let match_locs = &block_locs[abs_pos..abs_pos + search.len()];
+            while start + search.len() <= block_chars.len() {
+                if block_chars[start..start + search.len()] == search[..] {
+                    let match_locs = &block_locs[start..start + search.len()];
 
                     let mut any_written = false;
                     for (i, loc) in match_locs.iter().enumerate() {
                         if loc.byte_idx == usize::MAX {
                             continue; // synthetic space from kerning — skip
                         }
-                        set_byte_at(
+                        let enc = font_table
+                            .get(loc.font_idx)
+                            .map(|(_, e)| e)
+                            .cloned()
+                            .unwrap_or_default();
+                        let code = replace
+                            .get(i)
+                            .and_then(|&ch| enc.encode_char(ch))
+                            .unwrap_or_else(|| enc.space_code());
+                        set_code_at(
                             &mut content.operations,
                             loc.op_idx,
                             loc.arr_idx,
                             loc.byte_idx,
-                            padded[i],
+                            loc.code_bytes,
+                            &code,
                         );
                         any_written = true;
                     }
@@ -600,9 +1022,9 @@ fn replace_page_text(
                         total += 1;
                     }
 
-                    start = abs_pos + search.len();
+                    start += search.len();
                 } else {
-                    break;
+                    start += 1;
                 }
             }
         }
@@ -613,60 +1035,107 @@ fn replace_page_text(
         doc.change_page_content(page_id, encoded)?;
     }
 
-    Ok(total)
+    Ok((total, has_unmapped_font))
+}
+
+/// True if any font on this page built an empty `code_to_unicode` table --
+/// a Type0/CID font with no usable `/ToUnicode` CMap decodes every code to
+/// `'\u{FFFD}'`, so name matching against this page's text is unreliable.
+fn font_table_has_unmapped(font_table: &[(Vec<u8>, edgar_defense_toolkit::pdf_font::FontEncoding)]) -> bool {
+    font_table.iter().any(|(_, enc)| enc.is_unmapped())
 }
 
-/// Modify a single byte in a Tj/TJ string operand in place.
-fn set_byte_at(
+/// This page's `/Resources` `/Font` table as `(name, encoding)` pairs, in no
+/// particular order (collected from a `HashMap`); `Tf`'s font name is looked
+/// up by name.
+fn page_font_table(
+    doc: &mut lopdf::Document,
+    page_id: lopdf::ObjectId,
+) -> Vec<(Vec<u8>, edgar_defense_toolkit::pdf_font::FontEncoding)> {
+    let page_dict = match doc.get_object(page_id) {
+        Ok(lopdf::Object::Dictionary(d)) => d.clone(),
+        _ => return Vec::new(),
+    };
+    let resources_dict = match page_dict.get(b"Resources") {
+        Ok(lopdf::Object::Dictionary(d)) => d.clone(),
+        Ok(&lopdf::Object::Reference(id)) => match doc.get_object(id) {
+            Ok(lopdf::Object::Dictionary(d)) => d.clone(),
+            _ => return Vec::new(),
+        },
+        _ => return Vec::new(),
+    };
+    edgar_defense_toolkit::pdf_font::build_page_font_encodings(doc, &resources_dict)
+        .into_iter()
+        .collect()
+}
+
+/// Decode one `Tj`/`TJ` string operand through `font_idx`'s encoding,
+/// appending the resulting chars and their source locations.
+fn push_decoded_run(
+    font_table: &[(Vec<u8>, edgar_defense_toolkit::pdf_font::FontEncoding)],
+    font_idx: usize,
+    bytes: &[u8],
+    op_idx: usize,
+    arr_idx: usize,
+    cur_chars: &mut Vec<char>,
+    cur_locs: &mut Vec<TextChar>,
+) {
+    let default_enc = edgar_defense_toolkit::pdf_font::FontEncoding::default();
+    let enc = font_table.get(font_idx).map(|(_, e)| e).unwrap_or(&default_enc);
+    let code_bytes = enc.code_bytes;
+    for (ch, byte_idx) in enc.decode(bytes).into_iter().zip((0..bytes.len()).step_by(code_bytes)) {
+        cur_chars.push(ch);
+        cur_locs.push(TextChar { op_idx, arr_idx, byte_idx, code_bytes, font_idx });
+    }
+}
+
+/// Overwrite a code (1 or 2 bytes) in a Tj/TJ string operand in place.
+fn set_code_at(
     ops: &mut [lopdf::content::Operation],
     op_idx: usize,
     arr_idx: usize,
     byte_idx: usize,
-    value: u8,
+    code_bytes: usize,
+    value: &[u8],
 ) {
     let op = &mut ops[op_idx];
-    match op.operator.as_ref() {
-        "Tj" => {
-            if let Some(lopdf::Object::String(bytes, _)) = op.operands.first_mut() {
-                if byte_idx < bytes.len() {
-                    bytes[byte_idx] = value;
-                }
-            }
-        }
-        "TJ" => {
-            if let Some(lopdf::Object::Array(arr)) = op.operands.first_mut() {
-                if let Some(lopdf::Object::String(bytes, _)) = arr.get_mut(arr_idx) {
-                    if byte_idx < bytes.len() {
-                        bytes[byte_idx] = value;
-                    }
-                }
+    let target = match op.operator.as_ref() {
+        "Tj" => op.operands.first_mut().and_then(|o| match o {
+            lopdf::Object::String(bytes, _) => Some(bytes),
+            _ => None,
+        }),
+        "TJ" => op.operands.first_mut().and_then(|o| match o {
+            lopdf::Object::Array(arr) => arr.get_mut(arr_idx).and_then(|item| match item {
+                lopdf::Object::String(bytes, _) => Some(bytes),
+                _ => None,
+            }),
+            _ => None,
+        }),
+        _ => None,
+    };
+    if let Some(bytes) = target {
+        for (i, &b) in value.iter().enumerate().take(code_bytes) {
+            if byte_idx + i < bytes.len() {
+                bytes[byte_idx + i] = b;
             }
         }
-        _ => {}
     }
 }
 
-/// Find first occurrence of `needle` in `haystack`.
-fn find_subsequence(haystack: &[u8], needle: &[u8]) -> Option<usize> {
-    haystack
-        .windows(needle.len())
-        .position(|window| window == needle)
-}
-
 // ─── Main commands ───────────────────────────────────────────────────────────
 
-/// Replace link mode: replace tinyurl links and anonymize BBO screenshots.
-#[allow(clippy::too_many_arguments)]
-fn run_replace(
-    pdf_path: &PathBuf,
+/// Shared mappings built once from the lookup/anon CSVs, then reused across
+/// every PDF in a batch run instead of being re-parsed per file.
+struct AnonMappings {
+    url_map: HashMap<String, String>,
+    player_names: Vec<(String, String)>,
+}
+
+fn build_anon_mappings(
     lookup_path: &PathBuf,
     anon_path: &PathBuf,
     extra_map_path: Option<&PathBuf>,
-    output_path: &PathBuf,
-    anon_images: bool,
-    name_map: Option<&str>,
-    text_map_path: Option<&PathBuf>,
-) -> Result<()> {
+) -> Result<AnonMappings> {
     println!("Building URL mapping...");
     let mut url_map = build_url_mapping(lookup_path, anon_path)?;
     println!("  {} mappings from lookup + anon CSVs", url_map.len());
@@ -686,12 +1155,56 @@ fn run_replace(
     println!("  {} unique player name mappings", player_names.len());
     anonymize_mapping_urls(&mut url_map, &player_names);
 
-    println!("\nOpening PDF: {}", pdf_path.display());
-    let mut doc = lopdf::Document::load(pdf_path).context("Failed to load PDF")?;
+    Ok(AnonMappings { url_map, player_names })
+}
+
+/// One tinyurl link annotation left unresolved after the replace pass --
+/// carried into `ReplaceSummary::unmatched_tinyurls` so a later resolve
+/// pass (or `--report` consumer) can act on the list directly instead of
+/// re-scanning the PDF for it.
+#[derive(Default, Serialize)]
+struct UnmatchedLink {
+    key: String,
+    uri: String,
+}
+
+/// Per-file outcome of `anonymize_pdf`, printed for a single run and
+/// aggregated across files by `run_batch`; also the shape written out by
+/// `--report`.
+#[derive(Default, Serialize)]
+struct ReplaceSummary {
+    links_replaced: usize,
+    links_unmatched: usize,
+    unmatched_tinyurls: Vec<UnmatchedLink>,
+    name_urls_replaced: usize,
+    text_layer_runs: usize,
+    text_replacements: usize,
+    text_replacements_per_page: Vec<usize>,
+    images_modified: usize,
+    pages_with_unmapped_fonts: Vec<usize>,
+}
+
+/// Apply every anonymization pass to an already-open `doc`, against a
+/// pre-built `AnonMappings`. Does not load or save the document -- that's
+/// `run_replace`'s (or `run_batch`'s) job, so the mapping can be reused
+/// across many files without re-parsing the lookup/anon CSVs each time.
+#[allow(clippy::too_many_arguments)]
+fn anonymize_pdf(
+    doc: &mut lopdf::Document,
+    mappings: &AnonMappings,
+    anon_images: bool,
+    name_map: Option<&str>,
+    text_map_path: Option<&PathBuf>,
+    min_image_dim: usize,
+) -> Result<ReplaceSummary> {
+    let url_map = &mappings.url_map;
+    let player_names = &mappings.player_names;
+    let mut summary = ReplaceSummary::default();
 
     println!("Replacing link annotations...");
     let mut count = 0;
     let mut unmatched = 0;
+    let mut unmatched_links: Vec<UnmatchedLink> = Vec::new();
 
     let obj_ids: Vec<_> = doc.objects.keys().copied().collect();
     for obj_id in obj_ids {
@@ -730,6 +1243,7 @@ fn run_replace(
             } else if uri_str.contains("tinyurl.com") {
                 eprintln!("  UNMATCHED: {} (key: {})", uri_str, key);
                 unmatched += 1;
+                unmatched_links.push(UnmatchedLink { key, uri: uri_str });
             }
         }
     }
@@ -738,6 +1252,9 @@ fn run_replace(
     if unmatched > 0 {
         eprintln!("{} tinyurl links had no match", unmatched);
     }
+    summary.links_replaced = count;
+    summary.links_unmatched = unmatched;
+    summary.unmatched_tinyurls = unmatched_links;
 
     // ── Replace player names in remaining URLs (direct handviewer links) ──
     // Build URL-encoded name pairs for URI replacement
@@ -751,8 +1268,39 @@ fn run_replace(
     }
     if !uri_pairs.is_empty() {
         println!("\nReplacing player names in remaining URLs...");
-        let name_count = replace_names_in_uris(&mut doc, &uri_pairs);
+        let name_count = replace_names_in_uris(doc, &uri_pairs);
         println!("  {} URLs updated with name replacements", name_count);
+        summary.name_urls_replaced = name_count as usize;
+    }
+
+    // ── Anonymize player names in the PDF text layer ──
+    // A pixel redaction (anonymize_bbo_images, below) only hides names
+    // visually in a rasterized BBO screenshot; many result PDFs also carry
+    // a real text layer with the same names still selectable/searchable
+    // underneath it. Walk each page's content stream and rewrite any
+    // matched name run in place.
+    if !player_names.is_empty() {
+        println!("\nAnonymizing player names in PDF text layer...");
+        let font = load_system_font()?;
+        let name_pairs: Vec<(Vec<u8>, Vec<u8>)> = player_names
+            .iter()
+            .map(|(orig, anon)| (orig.clone().into_bytes(), anon.clone().into_bytes()))
+            .collect();
+        let page_ids: Vec<lopdf::ObjectId> = doc.page_iter().collect();
+        let mut name_text_count = 0usize;
+        let mut glyph_cache = edgar_defense_toolkit::anon_common::GlyphCache::new();
+        for &page_id in &page_ids {
+            match anonymize_page_text_layer(doc, page_id, &name_pairs, &font, &mut glyph_cache) {
+                Ok(n) => name_text_count += n,
+                Err(e) => eprintln!("  Warning: page {:?}: {}", page_id, e),
+            }
+        }
+        println!(
+            "  {} text-layer runs rewritten across {} pages",
+            name_text_count,
+            page_ids.len()
+        );
+        summary.text_layer_runs = name_text_count;
     }
 
     // ── Replace visible page text ──
@@ -764,11 +1312,22 @@ fn run_replace(
             tm_path.display()
         );
         let mut text_count = 0usize;
+        let mut per_page = Vec::new();
+        let mut unmapped_font_pages = Vec::new();
         let page_ids: Vec<lopdf::ObjectId> = doc.page_iter().collect();
-        for &page_id in &page_ids {
-            match replace_page_text(&mut doc, page_id, &text_pairs) {
-                Ok(n) => text_count += n,
-                Err(e) => eprintln!("  Warning: page {:?}: {}", page_id, e),
+        for (page_idx, &page_id) in page_ids.iter().enumerate() {
+            match replace_page_text(doc, page_id, &text_pairs) {
+                Ok((n, has_unmapped_font)) => {
+                    text_count += n;
+                    per_page.push(n);
+                    if has_unmapped_font {
+                        unmapped_font_pages.push(page_idx);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("  Warning: page {:?}: {}", page_id, e);
+                    per_page.push(0);
+                }
             }
         }
         println!(
@@ -776,26 +1335,269 @@ fn run_replace(
             text_count,
             page_ids.len()
         );
+        if !unmapped_font_pages.is_empty() {
+            eprintln!(
+                "  {} page(s) have a font with no usable character table -- \
+                 text replacement coverage for those pages is unverified: {:?}",
+                unmapped_font_pages.len(),
+                unmapped_font_pages,
+            );
+        }
+        summary.text_replacements = text_count;
+        summary.text_replacements_per_page = per_page;
+        summary.pages_with_unmapped_fonts = unmapped_font_pages;
     }
 
     // ── Anonymize BBO screenshot images ──
     if anon_images {
         println!("\nAnonymizing BBO screenshot images...");
-        let img_count = anonymize_bbo_images(&mut doc)?;
+        let img_count = anonymize_bbo_images(doc, min_image_dim)?;
         println!("Modified {} BBO screenshot images", img_count);
+        summary.images_modified = img_count as usize;
     }
 
+    Ok(summary)
+}
+
+/// Replace link mode: replace tinyurl links and anonymize BBO screenshots
+/// in a single PDF.
+#[allow(clippy::too_many_arguments)]
+fn run_replace(
+    pdf_path: &PathBuf,
+    lookup_path: &PathBuf,
+    anon_path: &PathBuf,
+    extra_map_path: Option<&PathBuf>,
+    output_path: &PathBuf,
+    anon_images: bool,
+    name_map: Option<&str>,
+    text_map_path: Option<&PathBuf>,
+    min_image_dim: usize,
+    report_path: Option<&PathBuf>,
+) -> Result<()> {
+    let mappings = build_anon_mappings(lookup_path, anon_path, extra_map_path)?;
+
+    println!("\nOpening PDF: {}", pdf_path.display());
+    let mut doc = lopdf::Document::load(pdf_path).context("Failed to load PDF")?;
+
+    let summary = anonymize_pdf(&mut doc, &mappings, anon_images, name_map, text_map_path, min_image_dim)?;
+
     doc.save(output_path).context("Failed to save PDF")?;
     println!("\nSaved to: {}", output_path.display());
+
+    if let Some(path) = report_path {
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("Failed to create report {}", path.display()))?;
+        serde_json::to_writer_pretty(file, &summary)
+            .with_context(|| format!("Failed to write report {}", path.display()))?;
+        println!("Wrote run report to: {}", path.display());
+    }
     Ok(())
 }
 
+// ─── Batch mode ───────────────────────────────────────────────────────────────
+
+/// Minimal glob match: `*` matches any run of characters, everything else
+/// is literal. Good enough for `.anonignore`-style exclude patterns;
+/// doesn't support `?`, character classes, or `**`.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+    let mut pos = 0;
+    for (i, part) in parts.iter().enumerate() {
+        if part.is_empty() {
+            continue;
+        }
+        if i == 0 {
+            if !text[pos..].starts_with(part) {
+                return false;
+            }
+            pos += part.len();
+        } else if i == parts.len() - 1 {
+            return text[pos..].ends_with(part);
+        } else if let Some(found) = text[pos..].find(part) {
+            pos += found + part.len();
+        } else {
+            return false;
+        }
+    }
+    true
+}
+
+/// Whether `rel_path` (slash-separated, relative to the scan root) should be
+/// skipped: matched either as a whole relative path or as any one of its
+/// path segments, mirroring `.gitignore`'s "bare name matches anywhere"
+/// behavior.
+fn is_ignored(rel_path: &str, patterns: &[String]) -> bool {
+    patterns
+        .iter()
+        .any(|p| glob_match(p, rel_path) || rel_path.split('/').any(|seg| glob_match(p, seg)))
+}
+
+/// Load ignore patterns from `.anonignore` or `.gitignore` at the root of
+/// the scan (checked in that order, first one found wins), plus any
+/// `--exclude` patterns from the command line.
+fn load_ignore_patterns(root: &Path, extra: &[String]) -> Vec<String> {
+    let mut patterns = Vec::new();
+    for name in [".anonignore", ".gitignore"] {
+        if let Ok(text) = std::fs::read_to_string(root.join(name)) {
+            patterns.extend(
+                text.lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                    .map(str::to_string),
+            );
+            break;
+        }
+    }
+    patterns.extend(extra.iter().cloned());
+    patterns
+}
+
+/// Collect every `.pdf` file under `root`, recursing depth-first and
+/// skipping ignored paths. Matches `bbo_csv.rs`'s `discover_csv_files`
+/// convention (plain `std::fs::read_dir`, no extra crate). Sorted for
+/// deterministic processing order.
+fn discover_pdf_files(root: &Path, ignore: &[String]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    collect_pdf_files(root, root, ignore, &mut files);
+    files.sort();
+    files
+}
+
+fn collect_pdf_files(root: &Path, dir: &Path, ignore: &[String], files: &mut Vec<PathBuf>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let rel = path.strip_prefix(root).unwrap_or(&path);
+        let rel_str = rel.to_string_lossy().replace('\\', "/");
+        if is_ignored(&rel_str, ignore) {
+            continue;
+        }
+        let name = match path.file_name().and_then(|n| n.to_str()) {
+            Some(name) => name,
+            None => continue,
+        };
+        if name.starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            collect_pdf_files(root, &path, ignore, files);
+        } else if name.to_lowercase().ends_with(".pdf") {
+            files.push(path);
+        }
+    }
+}
+
+/// Batch mode: run the replace pipeline over every PDF found under
+/// `input_dir`, building the URL/name mapping once
+/// and reusing it across all files, writing outputs to a mirrored tree
+/// under `output_dir`.
+#[allow(clippy::too_many_arguments)]
+fn run_batch(
+    input_dir: &PathBuf,
+    output_dir: &PathBuf,
+    lookup_path: &PathBuf,
+    anon_path: &PathBuf,
+    extra_map_path: Option<&PathBuf>,
+    anon_images: bool,
+    name_map: Option<&str>,
+    text_map_path: Option<&PathBuf>,
+    min_image_dim: usize,
+    exclude: &[String],
+) -> Result<()> {
+    let mappings = build_anon_mappings(lookup_path, anon_path, extra_map_path)?;
+
+    let ignore = load_ignore_patterns(input_dir, exclude);
+    let files = discover_pdf_files(input_dir, &ignore);
+    if files.is_empty() {
+        eprintln!("No PDF files found under {}", input_dir.display());
+        return Ok(());
+    }
+    println!("\n{} PDF file(s) to process", files.len());
+
+    let mut total = ReplaceSummary::default();
+    let mut files_with_unmatched = 0usize;
+    let mut failures = 0usize;
+
+    for input in &files {
+        let rel = input.strip_prefix(input_dir).unwrap_or(input);
+        let output = output_dir.join(rel);
+        if let Some(parent) = output.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        println!("\n=== {} ===", rel.display());
+
+        let outcome = (|| -> Result<ReplaceSummary> {
+            let mut doc = lopdf::Document::load(input).context("Failed to load PDF")?;
+            let summary =
+                anonymize_pdf(&mut doc, &mappings, anon_images, name_map, text_map_path, min_image_dim)?;
+            doc.save(&output).context("Failed to save PDF")?;
+            Ok(summary)
+        })();
+
+        match outcome {
+            Ok(summary) => {
+                println!(
+                    "  {} links, {} text runs, {} page-text replacements, {} images",
+                    summary.links_replaced,
+                    summary.text_layer_runs,
+                    summary.text_replacements,
+                    summary.images_modified
+                );
+                if summary.links_unmatched > 0 {
+                    files_with_unmatched += 1;
+                }
+                total.links_replaced += summary.links_replaced;
+                total.links_unmatched += summary.links_unmatched;
+                total.name_urls_replaced += summary.name_urls_replaced;
+                total.text_layer_runs += summary.text_layer_runs;
+                total.text_replacements += summary.text_replacements;
+                total.images_modified += summary.images_modified;
+            }
+            Err(e) => {
+                eprintln!("  FAILED: {}", e);
+                failures += 1;
+            }
+        }
+    }
+
+    println!("\n=== Batch summary ===");
+    println!("Files processed: {} ({} failed)", files.len(), failures);
+    println!("Total links replaced: {}", total.links_replaced);
+    println!("Total name URLs replaced: {}", total.name_urls_replaced);
+    println!("Total text-layer runs rewritten: {}", total.text_layer_runs);
+    println!("Total page-text replacements: {}", total.text_replacements);
+    println!("Total images modified: {}", total.images_modified);
+    println!("Files with unmatched tinyurls: {}", files_with_unmatched);
+
+    Ok(())
+}
+
+/// Outcome of resolving one unmatched tinyurl, carried from the worker pool
+/// back to the (single-threaded) CSV-writing loop so output order stays
+/// deterministic regardless of which worker finished first.
+enum ResolveOutcome {
+    Matched { anon_lin: String, fingerprint: String },
+    Unmatched { fingerprint: Option<String>, dest: Option<String> },
+    Failed(String),
+}
+
 /// Resolve mode: resolve unmatched ACBL tinyurls and produce a mapping CSV.
 fn run_resolve(
     pdf_path: Option<&PathBuf>,
     lookup_path: &PathBuf,
     anon_path: &PathBuf,
     output_path: &PathBuf,
+    jobs: usize,
+    cache_path: Option<&PathBuf>,
+    rate_limit: f64,
+    cache_dir: Option<&PathBuf>,
 ) -> Result<()> {
     let primary_map = build_url_mapping(lookup_path, anon_path)?;
     println!("{} primary mappings loaded", primary_map.len());
@@ -819,10 +1621,58 @@ fn run_resolve(
 
     println!("  {} unmatched tinyurls to resolve\n", unmatched.len());
 
-    let client = reqwest::blocking::Client::builder()
-        .redirect(reqwest::redirect::Policy::limited(10))
-        .timeout(std::time::Duration::from_secs(15))
-        .build()?;
+    let mut cache = match cache_path {
+        Some(path) => load_tinyurl_cache(path)?,
+        None => TinyurlCache::default(),
+    };
+    let cache_before = serde_json::to_string(&cache.entries).unwrap_or_default();
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs.max(1))
+        .build()
+        .context("Failed to build resolver thread pool")?;
+    let client = Arc::new(
+        reqwest::blocking::Client::builder()
+            .redirect(reqwest::redirect::Policy::limited(10))
+            .timeout(std::time::Duration::from_secs(15))
+            .build()?,
+    );
+    let fp_index = Arc::new(fp_index);
+    let limiters: std::sync::Mutex<HashMap<String, Arc<RateLimiter>>> = std::sync::Mutex::new(HashMap::new());
+
+    let outcomes: Vec<(String, ResolveOutcome, Option<TinyurlCacheEntry>)> = pool.install(|| {
+        unmatched
+            .par_iter()
+            .map(|url| {
+                let key = normalize_tinyurl(url);
+                if let Some(entry) = cache.entries.get(&key) {
+                    let outcome = classify_resolution(&fp_index, &entry.fingerprint, entry.dest.clone());
+                    return (key, outcome, None);
+                }
+                if let Some(dir) = cache_dir {
+                    if let Some(entry) = load_cache_dir_entry(dir, &key) {
+                        let outcome = classify_resolution(&fp_index, &entry.fingerprint, Some(entry.dest.clone()));
+                        return (key, outcome, Some(entry));
+                    }
+                }
+                rate_limiter_for(&limiters, url_host(url), rate_limit).acquire();
+                match resolve_with_retry(&client, url, 3) {
+                    Ok(dest) => {
+                        let fingerprint = extract_deal_fingerprint(&dest);
+                        let entry = TinyurlCacheEntry { dest: dest.clone(), fingerprint: fingerprint.clone() };
+                        if let Some(dir) = cache_dir {
+                            if let Err(e) = write_cache_dir_entry(dir, &key, &entry) {
+                                eprintln!("warning: failed to write cache entry for {}: {}", key, e);
+                            }
+                        }
+                        let outcome = classify_resolution(&fp_index, &fingerprint, Some(dest));
+                        (key, outcome, Some(entry))
+                    }
+                    Err(e) => (key, ResolveOutcome::Failed(e.to_string()), None),
+                }
+            })
+            .collect()
+    });
 
     let mut writer = csv::Writer::from_path(output_path)?;
     writer.write_record(["ACBL_TinyURL", "Anon_LIN_URL", "Match_Fingerprint"])?;
@@ -830,30 +1680,27 @@ fn run_resolve(
     let mut matched = 0;
     let mut failed = 0;
 
-    for url in &unmatched {
-        let key = normalize_tinyurl(url);
+    for (url, (key, outcome, new_entry)) in unmatched.iter().zip(outcomes) {
         print!("  {} -> ", key);
-
-        match resolve_tinyurl(&client, url) {
-            Ok(dest) => {
-                if let Some(fp) = extract_deal_fingerprint(&dest) {
-                    if let Some((_bbo_key, anon_lin)) = fp_index.get(&fp) {
-                        println!("MATCHED (fp: {}...)", &fp[..fp.len().min(12)]);
-                        writer.write_record([url.as_str(), anon_lin.as_str(), &fp])?;
-                        matched += 1;
-                    } else {
-                        println!("no fingerprint match (fp: {}...)", &fp[..fp.len().min(12)]);
-                        failed += 1;
-                    }
-                } else {
-                    println!(
-                        "no deal data in destination: {}",
-                        &dest[..dest.len().min(80)]
-                    );
-                    failed += 1;
-                }
+        if let Some(entry) = new_entry {
+            cache.entries.insert(key, entry);
+        }
+        match outcome {
+            ResolveOutcome::Matched { anon_lin, fingerprint } => {
+                println!("MATCHED (fp: {}...)", &fingerprint[..fingerprint.len().min(12)]);
+                writer.write_record([url.as_str(), anon_lin.as_str(), &fingerprint])?;
+                matched += 1;
             }
-            Err(e) => {
+            ResolveOutcome::Unmatched { fingerprint: Some(fp), .. } => {
+                println!("no fingerprint match (fp: {}...)", &fp[..fp.len().min(12)]);
+                failed += 1;
+            }
+            ResolveOutcome::Unmatched { dest, .. } => {
+                let dest = dest.unwrap_or_default();
+                println!("no deal data in destination: {}", &dest[..dest.len().min(80)]);
+                failed += 1;
+            }
+            ResolveOutcome::Failed(e) => {
                 println!("FAILED: {}", e);
                 failed += 1;
             }
@@ -861,6 +1708,12 @@ fn run_resolve(
     }
 
     writer.flush()?;
+    if let Some(path) = cache_path {
+        let cache_after = serde_json::to_string(&cache.entries).unwrap_or_default();
+        if cache_after != cache_before {
+            write_tinyurl_cache_if_changed(path, &cache)?;
+        }
+    }
     println!(
         "\nDone! {} matched, {} failed out of {} unmatched",
         matched,
@@ -871,6 +1724,243 @@ fn run_resolve(
     Ok(())
 }
 
+/// Turn a resolved destination URL (or a cached one) into a `ResolveOutcome`
+/// by looking its deal fingerprint up in `fp_index`.
+fn classify_resolution(
+    fp_index: &HashMap<String, (String, String)>,
+    fingerprint: &Option<String>,
+    dest: Option<String>,
+) -> ResolveOutcome {
+    match fingerprint {
+        Some(fp) => match fp_index.get(fp) {
+            Some((_bbo_key, anon_lin)) => ResolveOutcome::Matched {
+                anon_lin: anon_lin.clone(),
+                fingerprint: fp.clone(),
+            },
+            None => ResolveOutcome::Unmatched { fingerprint: Some(fp.clone()), dest },
+        },
+        None => ResolveOutcome::Unmatched { fingerprint: None, dest },
+    }
+}
+
+// ─── Verify mode ──────────────────────────────────────────────────────────────
+
+/// Load a flat list of forbidden names, one per line (blank lines and `#`
+/// comments ignored).
+fn load_name_list(path: &std::path::Path) -> Result<Vec<String>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// One occurrence of a forbidden name found by `run_verify`.
+struct VerifyHit {
+    location: String,
+    name: String,
+    context: String,
+}
+
+/// Decode a page's full visible text, in content-stream order, through its
+/// fonts' `pdf_font::FontEncoding` -- the same decoding `replace_page_text`
+/// uses, minus the per-character locations `verify` doesn't need.
+///
+/// Returns the page's text plus whether any font on it built an empty
+/// character table -- `verify`'s caller uses that to flag the page's
+/// coverage as unverified instead of silently trusting a clean scan.
+fn extract_page_text(doc: &mut lopdf::Document, page_id: lopdf::ObjectId) -> Result<(String, bool)> {
+    let font_table = page_font_table(doc, page_id);
+    let has_unmapped_font = font_table_has_unmapped(&font_table);
+    let default_enc = edgar_defense_toolkit::pdf_font::FontEncoding::default();
+    let content = doc.get_and_decode_page_content(page_id)?;
+    let mut text = String::new();
+    let mut font_idx = usize::MAX;
+    let mut in_text = false;
+
+    let decode = |bytes: &[u8], font_idx: usize| -> Vec<char> {
+        font_table
+            .get(font_idx)
+            .map(|(_, e)| e)
+            .unwrap_or(&default_enc)
+            .decode(bytes)
+    };
+
+    for op in &content.operations {
+        match op.operator.as_ref() {
+            "BT" => in_text = true,
+            "ET" => {
+                in_text = false;
+                text.push('\n');
+            }
+            "Tf" => {
+                if let Some(lopdf::Object::Name(name)) = op.operands.first() {
+                    font_idx = font_table.iter().position(|(n, _)| n == name).unwrap_or(usize::MAX);
+                }
+            }
+            "Tj" if in_text => {
+                if let Some(lopdf::Object::String(bytes, _)) = op.operands.first() {
+                    text.extend(decode(bytes, font_idx));
+                }
+            }
+            "TJ" if in_text => {
+                if let Some(lopdf::Object::Array(arr)) = op.operands.first() {
+                    for item in arr {
+                        if let lopdf::Object::String(bytes, _) = item {
+                            text.extend(decode(bytes, font_idx));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok((text, has_unmapped_font))
+}
+
+/// Collect every `/URI` string in the document, not just tinyurls -- a
+/// leaked name can show up in a direct handviewer link too.
+fn extract_all_uris(doc: &lopdf::Document) -> Vec<String> {
+    let mut uris = Vec::new();
+    for obj in doc.objects.values() {
+        if let lopdf::Object::Dictionary(ref dict) = obj {
+            let is_uri = dict
+                .get(b"S")
+                .map(|s| matches!(s, lopdf::Object::Name(n) if n == b"URI"))
+                .unwrap_or(false);
+            if !is_uri {
+                continue;
+            }
+            if let Ok(lopdf::Object::String(bytes, _)) = dict.get(b"URI") {
+                uris.push(String::from_utf8_lossy(bytes).to_string());
+            }
+        }
+    }
+    uris
+}
+
+/// The document's XMP metadata stream (the Catalog's `/Metadata` entry), if present.
+fn extract_xmp_metadata(doc: &mut lopdf::Document) -> Option<String> {
+    let root_id = match doc.trailer.get(b"Root") {
+        Ok(&lopdf::Object::Reference(id)) => id,
+        _ => return None,
+    };
+    let catalog_dict = match doc.get_object(root_id) {
+        Ok(lopdf::Object::Dictionary(d)) => d.clone(),
+        _ => return None,
+    };
+    let metadata_id = match catalog_dict.get(b"Metadata") {
+        Ok(&lopdf::Object::Reference(id)) => id,
+        _ => return None,
+    };
+    match doc.get_object_mut(metadata_id) {
+        Ok(lopdf::Object::Stream(stream)) => {
+            let _ = stream.decompress();
+            Some(String::from_utf8_lossy(&stream.content).to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Surrounding text around a char-index match, for the report's "context" column.
+fn context_around(chars: &[char], pos: usize, len: usize) -> String {
+    let start = pos.saturating_sub(20);
+    let end = (pos + len + 20).min(chars.len());
+    chars[start..end].iter().collect::<String>().replace('\n', " ")
+}
+
+/// Record every occurrence of any `names` entry in `text` under `location`.
+fn scan_for_names(text: &str, names: &[String], location: &str, hits: &mut Vec<VerifyHit>) {
+    let chars: Vec<char> = text.chars().collect();
+    for name in names {
+        let name_chars: Vec<char> = name.chars().collect();
+        if name_chars.is_empty() {
+            continue;
+        }
+        let mut start = 0;
+        while start + name_chars.len() <= chars.len() {
+            if chars[start..start + name_chars.len()] == name_chars[..] {
+                hits.push(VerifyHit {
+                    location: location.to_string(),
+                    name: name.clone(),
+                    context: context_around(&chars, start, name_chars.len()),
+                });
+                start += name_chars.len();
+            } else {
+                start += 1;
+            }
+        }
+    }
+}
+
+/// Verify mode: re-extract a finished PDF's visible text, URIs, Info
+/// dictionary, and XMP metadata, and report any surviving forbidden name.
+fn run_verify(pdf_path: &PathBuf, names_path: &PathBuf) -> Result<()> {
+    let names = load_name_list(names_path)?;
+    println!("Checking {} name(s) against {}", names.len(), pdf_path.display());
+
+    let mut doc = lopdf::Document::load(pdf_path).context("Failed to load PDF")?;
+    let mut hits: Vec<VerifyHit> = Vec::new();
+    let mut unverified_pages: Vec<u32> = Vec::new();
+
+    let mut pages: Vec<(u32, lopdf::ObjectId)> = doc.get_pages().into_iter().collect();
+    pages.sort_by_key(|(num, _)| *num);
+    for (page_num, page_id) in &pages {
+        match extract_page_text(&mut doc, *page_id) {
+            Ok((text, has_unmapped_font)) => {
+                if has_unmapped_font {
+                    unverified_pages.push(*page_num);
+                }
+                scan_for_names(&text, &names, &format!("page {page_num} text"), &mut hits);
+            }
+            Err(e) => eprintln!("  Warning: page {page_num}: {e}"),
+        }
+    }
+
+    for uri in extract_all_uris(&doc) {
+        scan_for_names(&uri, &names, "URI annotation", &mut hits);
+    }
+
+    if let Ok(info_obj) = doc.trailer.get(b"Info").cloned() {
+        if let lopdf::Object::Dictionary(info) = resolve_obj(&doc, &info_obj) {
+            for (key, value) in info.iter() {
+                if let lopdf::Object::String(bytes, _) = value {
+                    let text = String::from_utf8_lossy(bytes);
+                    scan_for_names(&text, &names, &format!("Info /{}", String::from_utf8_lossy(key)), &mut hits);
+                }
+            }
+        }
+    }
+
+    if let Some(xmp) = extract_xmp_metadata(&mut doc) {
+        scan_for_names(&xmp, &names, "XMP metadata", &mut hits);
+    }
+
+    if !unverified_pages.is_empty() {
+        println!(
+            "\nWarning: {} page(s) have a font with no usable character table ({:?}) -- \
+             text-layer coverage for those pages is unverified, not confirmed clean.",
+            unverified_pages.len(),
+            unverified_pages,
+        );
+    }
+
+    if hits.is_empty() {
+        println!("\nNo leaked names found across {} page(s).", pages.len());
+        Ok(())
+    } else {
+        println!("\n{} leak(s) found:", hits.len());
+        for hit in &hits {
+            println!("  [{}] \"{}\" -- ...{}...", hit.location, hit.name, hit.context);
+        }
+        std::process::exit(1);
+    }
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
@@ -880,7 +1970,46 @@ fn main() -> Result<()> {
             lookup,
             anon,
             output,
-        }) => run_resolve(pdf.as_ref(), &lookup, &anon, &output),
+            jobs,
+            cache,
+            rate_limit,
+            cache_dir,
+        }) => run_resolve(
+            pdf.as_ref(),
+            &lookup,
+            &anon,
+            &output,
+            jobs,
+            cache.as_ref(),
+            rate_limit,
+            cache_dir.as_ref(),
+        ),
+
+        Some(Commands::Batch {
+            input_dir,
+            output_dir,
+            lookup,
+            anon,
+            extra_map,
+            no_images,
+            name_map,
+            text_map,
+            min_image_dim,
+            exclude,
+        }) => run_batch(
+            &input_dir,
+            &output_dir,
+            &lookup,
+            &anon,
+            extra_map.as_ref(),
+            !no_images,
+            name_map.as_deref(),
+            text_map.as_ref(),
+            min_image_dim,
+            &exclude,
+        ),
+
+        Some(Commands::Verify { pdf, names }) => run_verify(&pdf, &names),
 
         None => {
             let pdf = cli.pdf.context("--pdf is required")?;
@@ -896,6 +2025,8 @@ fn main() -> Result<()> {
                 !cli.no_images,
                 cli.name_map.as_deref(),
                 cli.text_map.as_ref(),
+                cli.min_image_dim,
+                cli.report.as_ref(),
             )
         }
     }
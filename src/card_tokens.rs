@@ -0,0 +1,165 @@
+//! Locale-aware card-token parsing.
+//!
+//! This codebase writes card tokens in the canonical `"SA"`/`"HT"`
+//! shorthand. Real-world text is looser: Unicode suit glyphs, the
+//! two-character `"10"` spelling, and either suit-first or rank-first
+//! ordering. This module reads that looser form (and the canonical one) into
+//! the same `bridge_parsers::Card`; `dd_analysis::parse_card_str` parses
+//! through it.
+//!
+//! Suits are looked up in [`SUIT_TABLE`], a list of (symbol -> `Suit`)
+//! entries rather than a hardcoded `match`, so a localized suit name or
+//! spelling can be added there without touching the parsing logic itself.
+
+use bridge_parsers::{Card, Rank, Suit};
+use std::fmt;
+
+/// One suit's recognized spellings -- any of `symbols` maps to `suit`.
+struct SuitEntry {
+    suit: Suit,
+    symbols: &'static [&'static str],
+}
+
+/// Recognized suit spellings: the ASCII letter, the filled Unicode suit
+/// glyph, and its outline ("white") variant.
+const SUIT_TABLE: &[SuitEntry] = &[
+    SuitEntry { suit: Suit::Spades, symbols: &["S", "\u{2660}", "\u{2664}"] },
+    SuitEntry { suit: Suit::Hearts, symbols: &["H", "\u{2665}", "\u{2661}"] },
+    SuitEntry { suit: Suit::Diamonds, symbols: &["D", "\u{2666}", "\u{2662}"] },
+    SuitEntry { suit: Suit::Clubs, symbols: &["C", "\u{2663}", "\u{2667}"] },
+];
+
+/// Why a card token failed to parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardTokenError {
+    /// No recognized suit letter or glyph appears anywhere in the token.
+    UnknownSuitGlyph(String),
+    /// The token has suit-shaped text at both ends that disagree, so which
+    /// part is the suit and which is the rank can't be determined.
+    AmbiguousToken(String),
+    /// A suit was found, but the remaining text isn't a recognized rank.
+    InvalidRank(String),
+}
+
+impl fmt::Display for CardTokenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardTokenError::UnknownSuitGlyph(s) => write!(f, "Unknown suit glyph in card token: {}", s),
+            CardTokenError::AmbiguousToken(s) => write!(f, "Ambiguous card token (suit unclear): {}", s),
+            CardTokenError::InvalidRank(s) => write!(f, "Invalid rank in card token: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for CardTokenError {}
+
+/// Parse a card token, auto-detecting whether the suit comes first (e.g.
+/// `"SA"`, `"\u{2665}10"`) or last (e.g. `"AS"`, `"10\u{2665}"`).
+pub fn parse_card_token(token: &str) -> Result<Card, CardTokenError> {
+    let s = token.trim();
+    if s.is_empty() {
+        return Err(CardTokenError::UnknownSuitGlyph(token.to_string()));
+    }
+
+    let prefix = match_suit_prefix(s);
+    let suffix = match_suit_suffix(s);
+
+    let (suit, rank_str) = match (prefix, suffix) {
+        (Some((suit, len)), None) => (suit, &s[len..]),
+        (None, Some((suit, len))) => (suit, &s[..s.len() - len]),
+        (Some((suit_a, len_a)), Some((suit_b, len_b))) => {
+            let from_prefix = &s[len_a..];
+            let from_suffix = &s[..s.len() - len_b];
+            if suit_a == suit_b && from_prefix == from_suffix {
+                (suit_a, from_prefix)
+            } else {
+                return Err(CardTokenError::AmbiguousToken(token.to_string()));
+            }
+        }
+        (None, None) => return Err(CardTokenError::UnknownSuitGlyph(token.to_string())),
+    };
+
+    let rank = parse_rank_token(rank_str)
+        .ok_or_else(|| CardTokenError::InvalidRank(token.to_string()))?;
+
+    Ok(Card::new(suit, rank))
+}
+
+fn match_suit_prefix(s: &str) -> Option<(Suit, usize)> {
+    for entry in SUIT_TABLE {
+        for &sym in entry.symbols {
+            if let Some(chunk) = s.get(..sym.len()) {
+                if chunk.eq_ignore_ascii_case(sym) {
+                    return Some((entry.suit, sym.len()));
+                }
+            }
+        }
+    }
+    None
+}
+
+fn match_suit_suffix(s: &str) -> Option<(Suit, usize)> {
+    for entry in SUIT_TABLE {
+        for &sym in entry.symbols {
+            if sym.len() > s.len() {
+                continue;
+            }
+            if let Some(chunk) = s.get(s.len() - sym.len()..) {
+                if chunk.eq_ignore_ascii_case(sym) {
+                    return Some((entry.suit, sym.len()));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Parse a rank token: the two-character `"10"` spelling, or a single
+/// letter/digit recognized by `Rank::from_char`.
+fn parse_rank_token(s: &str) -> Option<Rank> {
+    let s = s.trim();
+    if s.eq_ignore_ascii_case("10") {
+        return Some(Rank::Ten);
+    }
+    let mut chars = s.chars();
+    let c = chars.next()?;
+    if chars.next().is_some() {
+        return None;
+    }
+    Rank::from_char(c.to_ascii_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_suit_first_and_rank_first() {
+        assert_eq!(parse_card_token("SA").unwrap(), Card::new(Suit::Spades, Rank::Ace));
+        assert_eq!(parse_card_token("AS").unwrap(), Card::new(Suit::Spades, Rank::Ace));
+    }
+
+    #[test]
+    fn parses_unicode_glyphs_and_ten() {
+        assert_eq!(
+            parse_card_token("\u{2665}10").unwrap(),
+            Card::new(Suit::Hearts, Rank::Ten)
+        );
+        assert_eq!(
+            parse_card_token("10\u{2661}").unwrap(),
+            Card::new(Suit::Hearts, Rank::Ten)
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_and_ambiguous_tokens() {
+        assert!(matches!(
+            parse_card_token("XA"),
+            Err(CardTokenError::UnknownSuitGlyph(_))
+        ));
+        assert!(matches!(
+            parse_card_token("SH"),
+            Err(CardTokenError::AmbiguousToken(_))
+        ));
+    }
+}
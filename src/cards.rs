@@ -0,0 +1,330 @@
+//! Typed card/hand model and a single trick-winner evaluator.
+//!
+//! `display_hand`'s replay loop and `compute_dd_analysis`'s per-trick voids
+//! and winner tracking (in `bbo_csv.rs`) each re-parsed two-character card
+//! strings like `"SA"` into their own ad hoc `(suit, rank)` tuples, with
+//! their own trump-suit detection from the contract string -- two copies of
+//! the same logic that could (and did) drift. `Card::parse` and
+//! `trick_winner` give both call sites one place to go instead.
+//!
+//! This duplicates rather than reuses `bridge_parsers::{Card, Suit, Rank}`
+//! (already used elsewhere in this tree, e.g. `packed_cards`): neither that
+//! type nor `packed_cards`'s per-suit bitmask has a `NoTrump` suit, and
+//! `trick_winner` needs trump expressed as a plain `Suit` to keep its
+//! signature simple. See `bbo_csv.rs`'s parallel `DdWorkItem`/`Anonymizer`
+//! duplication for the same tradeoff made elsewhere in this codebase.
+
+use std::fmt;
+
+/// A suit, or notrump for a contract with no trump suit. Only `trick_winner`
+/// ever sees the `NoTrump` variant -- a played `Card` is always a real suit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+    NoTrump,
+}
+
+impl Suit {
+    /// This suit's `0..=3` field index within a [`Card`]/[`Hand`] encoding,
+    /// or `None` for `NoTrump`.
+    fn index(self) -> Option<u8> {
+        match self {
+            Suit::Clubs => Some(0),
+            Suit::Diamonds => Some(1),
+            Suit::Hearts => Some(2),
+            Suit::Spades => Some(3),
+            Suit::NoTrump => None,
+        }
+    }
+
+    fn from_index(i: u8) -> Suit {
+        match i {
+            0 => Suit::Clubs,
+            1 => Suit::Diamonds,
+            2 => Suit::Hearts,
+            _ => Suit::Spades,
+        }
+    }
+
+    fn from_char(c: char) -> Option<Suit> {
+        match c.to_ascii_uppercase() {
+            'C' => Some(Suit::Clubs),
+            'D' => Some(Suit::Diamonds),
+            'H' => Some(Suit::Hearts),
+            'S' => Some(Suit::Spades),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Suit::Clubs => "C",
+            Suit::Diamonds => "D",
+            Suit::Hearts => "H",
+            Suit::Spades => "S",
+            Suit::NoTrump => "NT",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A card rank, Two through Ace. Discriminant order is card-strength order,
+/// so comparing two `Rank`s is a plain integer compare via `derive(Ord)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rank {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+const RANKS: [Rank; 13] = [
+    Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Six, Rank::Seven,
+    Rank::Eight, Rank::Nine, Rank::Ten, Rank::Jack, Rank::Queen, Rank::King, Rank::Ace,
+];
+
+impl Rank {
+    fn from_char(c: char) -> Option<Rank> {
+        match c.to_ascii_uppercase() {
+            '2' => Some(Rank::Two),
+            '3' => Some(Rank::Three),
+            '4' => Some(Rank::Four),
+            '5' => Some(Rank::Five),
+            '6' => Some(Rank::Six),
+            '7' => Some(Rank::Seven),
+            '8' => Some(Rank::Eight),
+            '9' => Some(Rank::Nine),
+            'T' => Some(Rank::Ten),
+            'J' => Some(Rank::Jack),
+            'Q' => Some(Rank::Queen),
+            'K' => Some(Rank::King),
+            'A' => Some(Rank::Ace),
+            _ => None,
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Rank::Two => '2', Rank::Three => '3', Rank::Four => '4', Rank::Five => '5',
+            Rank::Six => '6', Rank::Seven => '7', Rank::Eight => '8', Rank::Nine => '9',
+            Rank::Ten => 'T', Rank::Jack => 'J', Rank::Queen => 'Q', Rank::King => 'K',
+            Rank::Ace => 'A',
+        }
+    }
+}
+
+/// A single playing card, packed as `suit * 13 + rank` in `0..=51`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Card(u8);
+
+impl Card {
+    pub fn new(suit: Suit, rank: Rank) -> Card {
+        let suit_idx = suit.index().expect("a Card's suit can't be NoTrump");
+        Card(suit_idx * 13 + rank as u8)
+    }
+
+    pub fn suit(self) -> Suit {
+        Suit::from_index(self.0 / 13)
+    }
+
+    pub fn rank(self) -> Rank {
+        RANKS[(self.0 % 13) as usize]
+    }
+
+    /// Parses a two-or-three-character card token: case-insensitive suit
+    /// letter followed by a rank (`T` or `10` for ten), e.g. `"SA"`,
+    /// `"h10"`, `"D9"`.
+    pub fn parse(s: &str) -> Option<Card> {
+        let s = s.trim();
+        let mut chars = s.chars();
+        let suit = Suit::from_char(chars.next()?)?;
+        let rest = chars.as_str();
+        let rank = if rest == "10" {
+            Rank::Ten
+        } else {
+            Rank::from_char(rest.chars().next()?)?
+        };
+        Some(Card::new(suit, rank))
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.suit(), self.rank().to_char())
+    }
+}
+
+/// A hand (or any set of cards) as a 52-bit mask, one bit per [`Card`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Hand(u64);
+
+impl Hand {
+    pub const EMPTY: Hand = Hand(0);
+
+    pub fn insert(&mut self, card: Card) {
+        self.0 |= 1u64 << card.0;
+    }
+
+    pub fn contains(self, card: Card) -> bool {
+        self.0 & (1u64 << card.0) != 0
+    }
+
+    pub fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// This hand's cards held in `suit`, lowest rank first -- the fast path
+    /// for printing a deal diagram's suit line without splitting a hand
+    /// string or allocating per card.
+    pub fn cards_in_suit(self, suit: Suit) -> impl Iterator<Item = Card> + 'static {
+        let suit_idx = suit.index().unwrap_or(0);
+        let field = ((self.0 >> (suit_idx as u32 * 13)) & 0x1FFF) as u16;
+        (0..13).filter(move |rank| field & (1 << rank) != 0).map(move |rank| Card(suit_idx * 13 + rank as u8))
+    }
+}
+
+impl FromIterator<Card> for Hand {
+    fn from_iter<I: IntoIterator<Item = Card>>(iter: I) -> Hand {
+        let mut hand = Hand::EMPTY;
+        for card in iter {
+            hand.insert(card);
+        }
+        hand
+    }
+}
+
+/// Trump suit implied by a short contract string like `"4S"`, `"3NTX"`,
+/// `"6HXX"` -- `NoTrump` if it contains `"NT"` or no suit letter is found.
+pub fn trump_from_contract(contract: &str) -> Suit {
+    let contract = contract.trim().to_uppercase();
+    if contract.contains("NT") {
+        return Suit::NoTrump;
+    }
+    contract.chars().find_map(Suit::from_char).unwrap_or(Suit::NoTrump)
+}
+
+/// The seat (`0..=3`) that wins a trick played in order starting from
+/// `leader`: the highest trump played, or if no trump was played, the
+/// highest card of the led suit.
+pub fn trick_winner(cards: [Card; 4], leader: usize, trump: Suit) -> usize {
+    let led_suit = cards[0].suit();
+    let mut winner = 0;
+
+    for (i, &card) in cards.iter().enumerate().skip(1) {
+        let current = cards[winner];
+        let beats = if card.suit() == trump && current.suit() != trump {
+            true
+        } else if card.suit() == trump && current.suit() == trump {
+            card.rank() > current.rank()
+        } else if current.suit() == trump {
+            false
+        } else if card.suit() == led_suit && current.suit() == led_suit {
+            card.rank() > current.rank()
+        } else {
+            card.suit() == led_suit
+        };
+
+        if beats {
+            winner = i;
+        }
+    }
+
+    (leader + winner) % 4
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cards() {
+        assert_eq!(Card::parse("SA"), Some(Card::new(Suit::Spades, Rank::Ace)));
+        assert_eq!(Card::parse("h10"), Some(Card::new(Suit::Hearts, Rank::Ten)));
+        assert_eq!(Card::parse("dT"), Some(Card::new(Suit::Diamonds, Rank::Ten)));
+        assert_eq!(Card::parse("c2"), Some(Card::new(Suit::Clubs, Rank::Two)));
+        assert_eq!(Card::parse("X9"), None);
+        assert_eq!(Card::parse(""), None);
+    }
+
+    #[test]
+    fn roundtrips_suit_and_rank() {
+        let card = Card::new(Suit::Hearts, Rank::Jack);
+        assert_eq!(card.suit(), Suit::Hearts);
+        assert_eq!(card.rank(), Rank::Jack);
+    }
+
+    #[test]
+    fn follows_led_suit() {
+        let cards = [
+            Card::new(Suit::Spades, Rank::Queen),
+            Card::new(Suit::Hearts, Rank::Jack),
+            Card::new(Suit::Spades, Rank::King),
+            Card::new(Suit::Diamonds, Rank::Two),
+        ];
+        assert_eq!(trick_winner(cards, 1, Suit::NoTrump), (1 + 2) % 4);
+    }
+
+    #[test]
+    fn trump_beats_led_suit() {
+        let cards = [
+            Card::new(Suit::Hearts, Rank::Ace),
+            Card::new(Suit::Clubs, Rank::Two),
+            Card::new(Suit::Hearts, Rank::Five),
+            Card::new(Suit::Hearts, Rank::Nine),
+        ];
+        assert_eq!(trick_winner(cards, 0, Suit::Clubs), (0 + 1) % 4);
+    }
+
+    #[test]
+    fn higher_trump_wins() {
+        let cards = [
+            Card::new(Suit::Clubs, Rank::Two),
+            Card::new(Suit::Clubs, Rank::King),
+            Card::new(Suit::Hearts, Rank::Ace),
+            Card::new(Suit::Clubs, Rank::Queen),
+        ];
+        assert_eq!(trick_winner(cards, 2, Suit::Clubs), (2 + 1) % 4);
+    }
+
+    #[test]
+    fn hand_enumerates_cards_in_suit_order() {
+        let hand: Hand = [
+            Card::new(Suit::Spades, Rank::Ace),
+            Card::new(Suit::Spades, Rank::Two),
+            Card::new(Suit::Hearts, Rank::King),
+        ]
+        .into_iter()
+        .collect();
+
+        let spades: Vec<Card> = hand.cards_in_suit(Suit::Spades).collect();
+        assert_eq!(spades, vec![Card::new(Suit::Spades, Rank::Two), Card::new(Suit::Spades, Rank::Ace)]);
+        assert!(hand.contains(Card::new(Suit::Hearts, Rank::King)));
+        assert_eq!(hand.len(), 3);
+    }
+
+    #[test]
+    fn parses_trump_from_contract() {
+        assert_eq!(trump_from_contract("4S"), Suit::Spades);
+        assert_eq!(trump_from_contract("3NT"), Suit::NoTrump);
+        assert_eq!(trump_from_contract("6HXX"), Suit::Hearts);
+        assert_eq!(trump_from_contract("garbage"), Suit::NoTrump);
+    }
+}
@@ -0,0 +1,184 @@
+//! Structured contract type.
+//!
+//! `extract_contract` used to hand back a bare `String` like `"6HX"`, which
+//! every consumer then had to re-parse with its own heuristics (see the old
+//! `parse_trump`, which guessed notrump from the presence of an `'N'` that
+//! wasn't in a spade contract). `Contract` parses that string once, robustly,
+//! and carries the level/strain/doubling apart from here on.
+
+use std::fmt;
+use std::str::FromStr;
+
+use bridge_solver::{CLUB, DIAMOND, HEART, NOTRUMP, SPADE};
+
+use crate::scoring::Doubled;
+
+/// The strain of a contract (the trump suit, or notrump).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strain {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+    NoTrump,
+}
+
+impl Strain {
+    /// The `bridge_solver` suit constant for this strain.
+    pub fn to_solver(self) -> usize {
+        match self {
+            Strain::Clubs => CLUB,
+            Strain::Diamonds => DIAMOND,
+            Strain::Hearts => HEART,
+            Strain::Spades => SPADE,
+            Strain::NoTrump => NOTRUMP,
+        }
+    }
+
+    /// Bidding-order rank (clubs lowest, notrump highest), used to compare
+    /// two bids at the same level for sufficiency.
+    pub(crate) fn rank(self) -> u8 {
+        match self {
+            Strain::Clubs => 0,
+            Strain::Diamonds => 1,
+            Strain::Hearts => 2,
+            Strain::Spades => 3,
+            Strain::NoTrump => 4,
+        }
+    }
+}
+
+impl fmt::Display for Strain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Strain::Clubs => "C",
+            Strain::Diamonds => "D",
+            Strain::Hearts => "H",
+            Strain::Spades => "S",
+            Strain::NoTrump => "NT",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A bid contract: level, strain, and doubling state.
+///
+/// Parses from (and displays back to) the same short form used throughout
+/// the rest of the toolkit, e.g. `"3NT"`, `"4S"`, `"6HX"`, `"7CXX"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contract {
+    pub level: u8,
+    pub strain: Strain,
+    pub doubling: Doubled,
+}
+
+impl Contract {
+    /// The `bridge_solver` suit constant for this contract's trump suit.
+    pub fn trump(&self) -> usize {
+        self.strain.to_solver()
+    }
+}
+
+impl FromStr for Contract {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_uppercase();
+
+        let mut chars = s.chars();
+        let level = chars
+            .next()
+            .and_then(|c| c.to_digit(10))
+            .map(|d| d as u8)
+            .ok_or_else(|| format!("Could not parse level from: {}", s))?;
+
+        let mut rest = chars.as_str();
+
+        let doubling = if let Some(stripped) = rest.strip_suffix("XX") {
+            rest = stripped;
+            Doubled::Redoubled
+        } else if let Some(stripped) = rest.strip_suffix('X') {
+            rest = stripped;
+            Doubled::Doubled
+        } else {
+            Doubled::Undoubled
+        };
+
+        let strain = match rest {
+            "NT" | "N" => Strain::NoTrump,
+            "S" => Strain::Spades,
+            "H" => Strain::Hearts,
+            "D" => Strain::Diamonds,
+            "C" => Strain::Clubs,
+            _ => return Err(format!("Could not parse strain from: {}", s)),
+        };
+
+        Ok(Contract { level, strain, doubling })
+    }
+}
+
+impl fmt::Display for Contract {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.level, self.strain)?;
+        match self.doubling {
+            Doubled::Redoubled => f.write_str("XX"),
+            Doubled::Doubled => f.write_str("X"),
+            Doubled::Undoubled => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        let cases = [
+            ("3NT", 3, Strain::NoTrump, Doubled::Undoubled),
+            ("4S", 4, Strain::Spades, Doubled::Undoubled),
+            ("6HX", 6, Strain::Hearts, Doubled::Doubled),
+            ("7CXX", 7, Strain::Clubs, Doubled::Redoubled),
+            ("1D", 1, Strain::Diamonds, Doubled::Undoubled),
+        ];
+        for (text, level, strain, doubling) in cases {
+            let contract: Contract = text.parse().unwrap();
+            assert_eq!(contract.level, level, "parsing {text}");
+            assert_eq!(contract.strain, strain, "parsing {text}");
+            assert_eq!(contract.doubling, doubling, "parsing {text}");
+            assert_eq!(contract.to_string(), text, "displaying {text}");
+        }
+    }
+
+    #[test]
+    fn test_parse_accepts_lowercase_and_trims_whitespace() {
+        let contract: Contract = "  4s  ".parse().unwrap();
+        assert_eq!(contract.level, 4);
+        assert_eq!(contract.strain, Strain::Spades);
+        assert_eq!(contract.doubling, Doubled::Undoubled);
+    }
+
+    #[test]
+    fn test_parse_n_as_notrump_shorthand() {
+        let contract: Contract = "3N".parse().unwrap();
+        assert_eq!(contract.strain, Strain::NoTrump);
+    }
+
+    #[test]
+    fn test_parse_rejects_missing_level() {
+        assert!("NT".parse::<Contract>().is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_strain() {
+        assert!("4Z".parse::<Contract>().is_err());
+    }
+
+    #[test]
+    fn test_strain_rank_is_bidding_order() {
+        assert!(Strain::Clubs.rank() < Strain::Diamonds.rank());
+        assert!(Strain::Diamonds.rank() < Strain::Hearts.rank());
+        assert!(Strain::Hearts.rank() < Strain::Spades.rank());
+        assert!(Strain::Spades.rank() < Strain::NoTrump.rank());
+    }
+}
@@ -3,8 +3,10 @@
 //! This module provides DD (double-dummy) analysis of bridge cardplay,
 //! computing the cost of each card or trick relative to optimal play.
 
+use crate::contract::Contract;
+use crate::scoring::{points_to_imps, score_contract};
 use bridge_parsers::lin::LinData;
-use bridge_parsers::{Card, Direction, Rank, Suit};
+use bridge_parsers::{Card, Direction, Rank, Suit, Vulnerability};
 use bridge_solver::cards::{card_of, suit_of};
 use bridge_solver::{CutoffCache, Hands, PartialTrick, PatternCache, Solver};
 use bridge_solver::{CLUB, DIAMOND, EAST, HEART, NOTRUMP, NORTH, SOUTH, SPADE, WEST};
@@ -23,6 +25,14 @@ pub struct DdError {
     pub card: Card,
     /// DD cost (tricks lost by this play)
     pub cost: u8,
+    /// Declarer's DD tricks just before this error
+    pub dd_before: u8,
+    /// Declarer's DD tricks just after this error
+    pub dd_after: u8,
+    /// Legal cards that would have tied the DD optimum instead. Empty
+    /// unless the analysis that produced this error enumerated alternatives
+    /// (see `compute_dd_costs`'s `find_best_cards`).
+    pub best_cards: Vec<Card>,
 }
 
 /// Configuration for DD analysis
@@ -32,6 +42,21 @@ pub struct DdAnalysisConfig {
     pub mid_trick: bool,
     /// Print debug output for DD values
     pub debug: bool,
+    /// Enumerate the legal cards tying the DD optimum at every costly
+    /// mid-trick position (see `compute_dd_costs`'s `find_best_cards`).
+    /// Multiplies solver calls per error position, so off by default; has
+    /// no effect in trick-boundary mode.
+    pub find_best_cards: bool,
+    /// Flag a card as an error only when its DD value is strictly worse
+    /// than the best legal alternative at that exact decision point,
+    /// instead of comparing against the DD value left by the previous ply
+    /// (see `compute_dd_costs`'s `alternative_baseline`). This is the
+    /// methodology web replayers expect: cards that tie the optimum (e.g.
+    /// equivalent low spot cards) are never counted as errors. Implies
+    /// alternative enumeration at every ply, so it's markedly more solver
+    /// calls than `find_best_cards` alone; has no effect in trick-boundary
+    /// mode.
+    pub alternative_baseline: bool,
 }
 
 impl Default for DdAnalysisConfig {
@@ -39,6 +64,8 @@ impl Default for DdAnalysisConfig {
         Self {
             mid_trick: false,
             debug: false,
+            find_best_cards: false,
+            alternative_baseline: false,
         }
     }
 }
@@ -49,6 +76,8 @@ impl DdAnalysisConfig {
         Self {
             mid_trick: true,
             debug: false,
+            find_best_cards: false,
+            alternative_baseline: false,
         }
     }
 
@@ -57,6 +86,8 @@ impl DdAnalysisConfig {
         Self {
             mid_trick: false,
             debug: false,
+            find_best_cards: false,
+            alternative_baseline: false,
         }
     }
 
@@ -65,6 +96,19 @@ impl DdAnalysisConfig {
         self.debug = true;
         self
     }
+
+    /// Enable best-card enumeration at costly mid-trick positions
+    pub fn with_best_cards(mut self) -> Self {
+        self.find_best_cards = true;
+        self
+    }
+
+    /// Compare each card against the best legal alternative at its own
+    /// decision point, rather than the DD value left by the previous ply.
+    pub fn with_alternative_baseline(mut self) -> Self {
+        self.alternative_baseline = true;
+        self
+    }
 }
 
 /// Result of DD analysis for a single board
@@ -72,14 +116,16 @@ impl DdAnalysisConfig {
 pub struct DdAnalysisResult {
     /// Board number if available
     pub board_num: Option<usize>,
-    /// Contract string (e.g., "3NT", "4SX")
-    pub contract: String,
+    /// The contract played
+    pub contract: Contract,
     /// Declarer direction as string
     pub declarer: String,
     /// Initial DD result (tricks declarer can make with optimal play)
     pub initial_dd: u8,
     /// Final result (tricks declarer actually made)
     pub final_result: u8,
+    /// Whether declarer's side was vulnerable
+    pub vulnerable: bool,
     /// All DD errors found
     pub errors: Vec<DdError>,
 }
@@ -89,6 +135,18 @@ pub struct DdAnalysisResult {
 pub struct DdCostsResult {
     /// DD costs per card, organized by trick: costs[trick_idx][card_idx]
     pub costs: Vec<Vec<u8>>,
+    /// DD value just before and just after each card, same shape as `costs`
+    pub dd_timeline: Vec<Vec<(u8, u8)>>,
+    /// Seat that played each card, same shape as `costs`
+    pub seats: Vec<Vec<usize>>,
+    /// At each position with a nonzero cost, every legal card that ties the
+    /// DD optimum for the side on play. Empty unless `compute_dd_costs` was
+    /// called with `find_best_cards` (filled at nonzero-cost positions only)
+    /// or `alternative_baseline` (filled at every position). Same shape as
+    /// `costs`.
+    pub best_cards: Vec<Vec<Vec<Card>>>,
+    /// Contract string (e.g., "4S", "3NT", "6HX")
+    pub contract: String,
     /// Initial DD result (tricks declarer can make with optimal play)
     pub initial_dd: u8,
     /// Declarer seat (NORTH, EAST, SOUTH, WEST)
@@ -107,25 +165,43 @@ pub struct DdCostsResult {
 /// * `deal_pbn` - Deal in PBN format (e.g., "N:AKQ.JT9.876.5432 ...")
 /// * `cardplay` - Cardplay string with tricks separated by `|` and cards by spaces
 ///                (e.g., "S4 S2 SA S5|D7 DQ DK DA|...")
-/// * `contract` - Contract string (e.g., "4S", "3NT", "6HX")
+/// * `contract` - The contract played
 /// * `declarer` - Declarer direction (e.g., "North", "S", "West")
 /// * `debug` - Whether to print debug output
+/// * `find_best_cards` - Whether to also enumerate, at every costly
+///   position, the legal cards that tie the DD optimum (`best_cards` on the
+///   result). This multiplies solver calls per error position, so it's off
+///   by default.
+/// * `alternative_baseline` - Whether to flag a card as an error only when
+///   its DD value is strictly worse than the best legal alternative at that
+///   exact decision point, instead of comparing against `dd_before` (the DD
+///   value left by the previous ply). Cards that tie the optimum (e.g.
+///   equivalent low spot cards) are never flagged under this mode. Implies
+///   alternative enumeration at every ply, not just costly ones, so it's
+///   markedly more solver calls than `find_best_cards` alone.
 ///
 /// # Returns
 /// DD costs per card per trick, or an error message
 pub fn compute_dd_costs(
     deal_pbn: &str,
     cardplay: &str,
-    contract: &str,
+    contract: &Contract,
     declarer: &str,
     debug: bool,
+    find_best_cards: bool,
+    alternative_baseline: bool,
 ) -> Result<DdCostsResult, String> {
     // Parse the deal
     let mut current_hands = Hands::from_pbn(deal_pbn)
         .ok_or_else(|| format!("Failed to parse deal: {}", deal_pbn))?;
+    let mut holdings = if find_best_cards || alternative_baseline {
+        parse_deal_holdings(deal_pbn)?
+    } else {
+        HashMap::new()
+    };
 
-    // Parse trump suit
-    let trump = parse_trump(contract)?;
+    // Trump suit
+    let trump = contract.trump();
 
     // Parse declarer
     let declarer_seat = parse_declarer_seat(declarer)?;
@@ -138,6 +214,10 @@ pub fn compute_dd_costs(
     if tricks.is_empty() {
         return Ok(DdCostsResult {
             costs: Vec::new(),
+            dd_timeline: Vec::new(),
+            seats: Vec::new(),
+            best_cards: Vec::new(),
+            contract: contract.to_string(),
             initial_dd: 0,
             declarer_seat,
             declarer_is_ns,
@@ -163,12 +243,18 @@ pub fn compute_dd_costs(
     };
 
     let mut all_costs: Vec<Vec<u8>> = Vec::new();
+    let mut all_dd_timeline: Vec<Vec<(u8, u8)>> = Vec::new();
+    let mut all_seats: Vec<Vec<usize>> = Vec::new();
+    let mut all_best_cards: Vec<Vec<Vec<Card>>> = Vec::new();
     let mut declarer_tricks_won: u8 = 0;
     let mut current_leader = initial_leader;
 
     // Mid-trick analysis: compute DD before and after every card
     for (trick_idx, trick) in tricks.iter().enumerate() {
         let mut card_costs: Vec<u8> = Vec::new();
+        let mut card_dd_timeline: Vec<(u8, u8)> = Vec::new();
+        let mut card_seats: Vec<usize> = Vec::new();
+        let mut card_best_cards: Vec<Vec<Card>> = Vec::new();
         let mut seat = current_leader;
         let mut partial_trick = PartialTrick::new();
         let mut cards_in_trick: Vec<(usize, usize)> = Vec::new();
@@ -198,10 +284,18 @@ pub fn compute_dd_costs(
             // dd_before is the DD state coming into this card
             let dd_before = current_dd;
 
+            // Snapshot the position before this card, for best-card analysis
+            let hands_before = current_hands;
+            let cards_in_trick_before = cards_in_trick.clone();
+            let holding_before = holdings.get(&seat).cloned().unwrap_or_default();
+
             // Play the card
             current_hands[seat].remove(solver_card);
             partial_trick.add(solver_card, seat);
             cards_in_trick.push((seat, solver_card));
+            if let Some(hand) = holdings.get_mut(&seat) {
+                hand.retain(|c| c != card);
+            }
 
             // Compute DD AFTER this card is played
             let dd_after = if card_idx == 3 {
@@ -270,27 +364,62 @@ pub fn compute_dd_costs(
                 seat == EAST || seat == WEST
             };
 
-            let cost = if player_is_declarer_side {
+            let baseline_cost = if player_is_declarer_side {
                 // Declarer error: lost tricks (DD went down)
-                if dd_after < dd_before {
-                    dd_before - dd_after
-                } else {
-                    0
-                }
+                dd_before.saturating_sub(dd_after)
             } else {
                 // Defender error: declarer gained tricks (DD went up)
-                if dd_after > dd_before {
-                    dd_after - dd_before
-                } else {
-                    0
+                dd_after.saturating_sub(dd_before)
+            };
+
+            // Under `alternative_baseline`, the comparison point is the best
+            // legal card available at this exact decision point (enumerated
+            // here), not `dd_before` -- so cards tying that optimum are
+            // never flagged, even if an earlier ply already cost the side
+            // some tricks.
+            let need_alternatives = alternative_baseline || (find_best_cards && baseline_cost > 0);
+            let alternatives = if need_alternatives {
+                Some(find_best_cards_for_position(
+                    hands_before,
+                    &cards_in_trick_before,
+                    seat,
+                    card_idx,
+                    current_leader,
+                    trump,
+                    declarer_is_ns,
+                    declarer_tricks_won,
+                    player_is_declarer_side,
+                    &holding_before,
+                    &mut cutoff_cache,
+                    &mut pattern_cache,
+                ))
+            } else {
+                None
+            };
+
+            let cost = if alternative_baseline {
+                match alternatives.as_ref().and_then(|(_, best)| *best) {
+                    Some(best) if player_is_declarer_side => best.saturating_sub(dd_after),
+                    Some(best) => dd_after.saturating_sub(best),
+                    None => baseline_cost,
                 }
+            } else {
+                baseline_cost
             };
 
+            let best_cards = alternatives.map(|(cards, _)| cards).unwrap_or_default();
+
             card_costs.push(cost);
+            card_dd_timeline.push((dd_before, dd_after));
+            card_seats.push(seat);
+            card_best_cards.push(best_cards);
             seat = (seat + 1) % 4;
         }
 
         all_costs.push(card_costs);
+        all_dd_timeline.push(card_dd_timeline);
+        all_seats.push(card_seats);
+        all_best_cards.push(card_best_cards);
 
         // Update state after trick
         if cards_in_trick.len() == 4 {
@@ -309,12 +438,1045 @@ pub fn compute_dd_costs(
 
     Ok(DdCostsResult {
         costs: all_costs,
+        dd_timeline: all_dd_timeline,
+        seats: all_seats,
+        best_cards: all_best_cards,
+        contract: contract.to_string(),
         initial_dd,
         declarer_seat,
         declarer_is_ns,
     })
 }
 
+/// Enumerate every legal card for `seat` at this decision point (respecting
+/// suit-following from `cards_in_trick_before`), and return every one whose
+/// resulting line ties the DD optimum for the side on play, plus that
+/// optimum value itself (the best achievable declarer-DD trick count among
+/// the legal alternatives, from whichever side is on play).
+#[allow(clippy::too_many_arguments)]
+fn find_best_cards_for_position(
+    hands_before: Hands,
+    cards_in_trick_before: &[(usize, usize)],
+    seat: usize,
+    card_idx: usize,
+    leader: usize,
+    trump: usize,
+    declarer_is_ns: bool,
+    declarer_tricks_won: u8,
+    player_is_declarer_side: bool,
+    holding: &[Card],
+    cutoff_cache: &mut CutoffCache,
+    pattern_cache: &mut PatternCache,
+) -> (Vec<Card>, Option<u8>) {
+    let led_suit = cards_in_trick_before
+        .first()
+        .map(|&(_, solver_card)| solver_suit_to_suit(suit_of(solver_card)));
+    let legal: Vec<Card> = if let Some(suit) = led_suit {
+        let following: Vec<Card> = holding.iter().copied().filter(|c| c.suit == suit).collect();
+        if !following.is_empty() {
+            following
+        } else {
+            holding.to_vec()
+        }
+    } else {
+        holding.to_vec()
+    };
+
+    let mut values: Vec<(Card, u8)> = Vec::new();
+    for candidate in &legal {
+        let Ok(candidate_solver) = bridge_card_to_solver(*candidate) else {
+            continue;
+        };
+        let mut trial_hands = hands_before;
+        trial_hands[seat].remove(candidate_solver);
+
+        let value = if card_idx == 3 {
+            let mut trial_cards_in_trick = cards_in_trick_before.to_vec();
+            trial_cards_in_trick.push((seat, candidate_solver));
+            let winner = determine_trick_winner(&trial_cards_in_trick, trump, leader);
+            let declarer_won = if declarer_is_ns {
+                winner == NORTH || winner == SOUTH
+            } else {
+                winner == EAST || winner == WEST
+            };
+            let tricks_from_this = if declarer_won { 1u8 } else { 0u8 };
+
+            if trial_hands.num_tricks() == 0 {
+                declarer_tricks_won + tricks_from_this
+            } else {
+                let ns = solve_position(&trial_hands, trump, winner, cutoff_cache, pattern_cache);
+                if declarer_is_ns {
+                    declarer_tricks_won + tricks_from_this + ns
+                } else {
+                    let remaining = trial_hands.num_tricks() as u8;
+                    declarer_tricks_won + tricks_from_this + remaining.saturating_sub(ns)
+                }
+            }
+        } else {
+            let mut trial_partial = PartialTrick::new();
+            for &(s, solver_card) in cards_in_trick_before {
+                trial_partial.add(solver_card, s);
+            }
+            trial_partial.add(candidate_solver, seat);
+            let (ns, remaining) =
+                solve_mid_trick(&trial_hands, trump, &trial_partial, cutoff_cache, pattern_cache);
+            if declarer_is_ns {
+                declarer_tricks_won + ns
+            } else {
+                declarer_tricks_won + remaining.saturating_sub(ns)
+            }
+        };
+
+        values.push((*candidate, value));
+    }
+
+    let best = if player_is_declarer_side {
+        values.iter().map(|&(_, v)| v).max()
+    } else {
+        values.iter().map(|&(_, v)| v).min()
+    };
+
+    match best {
+        Some(best) => (
+            values.into_iter().filter(|&(_, v)| v == best).map(|(c, _)| c).collect(),
+            Some(best),
+        ),
+        None => (Vec::new(), None),
+    }
+}
+
+fn solver_suit_to_suit(solver_suit: usize) -> Suit {
+    match solver_suit {
+        SPADE => Suit::Spades,
+        HEART => Suit::Hearts,
+        DIAMOND => Suit::Diamonds,
+        _ => Suit::Clubs,
+    }
+}
+
+/// Schema version for [`DdCostsResult::to_replay_json`]'s output. Bump this
+/// whenever the shape of the exported JSON changes, so web viewers can tell
+/// old exports apart from new ones.
+#[cfg(feature = "serde")]
+const REPLAY_SCHEMA_VERSION: u32 = 1;
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct ReplayExport {
+    schema_version: u32,
+    contract: String,
+    declarer: String,
+    initial_dd: u8,
+    tricks: Vec<ReplayTrick>,
+    errors: Vec<ReplayError>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct ReplayTrick {
+    cards: Vec<ReplayCard>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct ReplayCard {
+    seat: String,
+    card: String,
+    dd_before: u8,
+    dd_after: u8,
+    cost: u8,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct ReplayError {
+    player: String,
+    trick_num: usize,
+    card_position: usize,
+    card: String,
+    cost: u8,
+}
+
+#[cfg(feature = "serde")]
+impl DdCostsResult {
+    /// Serialize the DD timeline as JSON for a web-based board replayer:
+    /// each trick's four cards with their `dd_before`/`dd_after` values and
+    /// cost, plus contract, declarer, `initial_dd`, and the attributed
+    /// errors. `tricks` must have the same trick/card shape as `self.costs`
+    /// (i.e. the actual cards played, as returned by `parse_cardplay`).
+    pub fn to_replay_json(&self, tricks: &[Vec<Card>]) -> String {
+        let declarer = seat_name(self.declarer_seat).to_string();
+
+        let mut replay_tricks = Vec::with_capacity(self.costs.len());
+        let mut errors = Vec::new();
+
+        for (trick_idx, trick_costs) in self.costs.iter().enumerate() {
+            let mut cards = Vec::with_capacity(trick_costs.len());
+            for (card_idx, &cost) in trick_costs.iter().enumerate() {
+                let card = tricks
+                    .get(trick_idx)
+                    .and_then(|t| t.get(card_idx))
+                    .copied();
+                let seat = self.seats[trick_idx][card_idx];
+                let (dd_before, dd_after) = self.dd_timeline[trick_idx][card_idx];
+                let card_str = card
+                    .map(|c| format!("{}{}", c.suit.to_char(), c.rank.to_char()))
+                    .unwrap_or_default();
+
+                if cost > 0 {
+                    errors.push(ReplayError {
+                        player: seat_name(seat).to_string(),
+                        trick_num: trick_idx + 1,
+                        card_position: card_idx,
+                        card: card_str.clone(),
+                        cost,
+                    });
+                }
+
+                cards.push(ReplayCard {
+                    seat: seat_name(seat).to_string(),
+                    card: card_str,
+                    dd_before,
+                    dd_after,
+                    cost,
+                });
+            }
+            replay_tricks.push(ReplayTrick { cards });
+        }
+
+        let export = ReplayExport {
+            schema_version: REPLAY_SCHEMA_VERSION,
+            contract: self.contract.clone(),
+            declarer,
+            initial_dd: self.initial_dd,
+            tricks: replay_tricks,
+            errors,
+        };
+
+        serde_json::to_string(&export).unwrap_or_default()
+    }
+}
+
+fn seat_name(seat: usize) -> &'static str {
+    match seat {
+        NORTH => "North",
+        EAST => "East",
+        SOUTH => "South",
+        WEST => "West",
+        _ => "?",
+    }
+}
+
+/// Schema version for [`DdAnalysisResult::to_json`]'s output. Bump this
+/// whenever the shape of the exported JSON changes.
+#[cfg(feature = "serde")]
+const BOARD_ANALYSIS_SCHEMA_VERSION: u32 = 1;
+
+/// JSON shape of a [`DdAnalysisResult`], for downstream tooling (web
+/// front-ends, bots, statistics pipelines) that wants to consume an
+/// `analyze_board` result without scraping `println!` output.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BoardAnalysisExport {
+    pub schema_version: u32,
+    pub board_num: Option<usize>,
+    pub contract: String,
+    pub declarer: String,
+    pub initial_dd: u8,
+    pub final_result: u8,
+    pub vulnerable: bool,
+    pub errors: Vec<BoardAnalysisErrorExport>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BoardAnalysisErrorExport {
+    pub player: String,
+    pub trick_num: usize,
+    pub card_position: usize,
+    pub suit: String,
+    pub rank: String,
+    pub cost: u8,
+}
+
+#[cfg(feature = "serde")]
+impl DdAnalysisResult {
+    /// Serialize this board's analysis -- contract, declarer, the DD summary,
+    /// and every attributed error -- as JSON, so it can be diffed against
+    /// reference data programmatically instead of by string-matching
+    /// `println!` output.
+    pub fn to_json(&self) -> String {
+        let export = BoardAnalysisExport {
+            schema_version: BOARD_ANALYSIS_SCHEMA_VERSION,
+            board_num: self.board_num,
+            contract: self.contract.to_string(),
+            declarer: self.declarer.clone(),
+            initial_dd: self.initial_dd,
+            final_result: self.final_result,
+            vulnerable: self.vulnerable,
+            errors: self
+                .errors
+                .iter()
+                .map(|e| BoardAnalysisErrorExport {
+                    player: e.player.clone(),
+                    trick_num: e.trick_num,
+                    card_position: e.card_position,
+                    suit: e.card.suit.to_char().to_string(),
+                    rank: e.card.rank.to_char().to_string(),
+                    cost: e.cost,
+                })
+                .collect(),
+        };
+        serde_json::to_string(&export).unwrap_or_default()
+    }
+}
+
+/// Runs [`analyze_board`] and serializes the result directly, for callers
+/// that only want the JSON form.
+#[cfg(feature = "serde")]
+pub fn analyze_board_to_json(lin_data: &LinData, config: &DdAnalysisConfig) -> Option<String> {
+    analyze_board(lin_data, config).map(|result| result.to_json())
+}
+
+/// Parses JSON produced by [`DdAnalysisResult::to_json`] /
+/// [`analyze_board_to_json`] back into its exported form.
+#[cfg(feature = "serde")]
+pub fn parse_board_analysis_json(json: &str) -> Result<BoardAnalysisExport, serde_json::Error> {
+    serde_json::from_str(json)
+}
+
+/// One position in a [`BoardReplay`]: the seat on play, the card played
+/// there, the DD value just before and just after it, its cost, and the
+/// DD-tied legal alternatives at that decision point (populated only if the
+/// underlying [`DdCostsResult`] was computed with `find_best_cards` or
+/// `alternative_baseline`).
+#[derive(Debug, Clone)]
+pub struct ReplayPosition {
+    pub trick_num: usize,
+    pub card_position: usize,
+    pub seat: usize,
+    pub card: Card,
+    pub dd_before: u8,
+    pub dd_after: u8,
+    pub cost: u8,
+    pub legal_alternatives: Vec<Card>,
+}
+
+/// A step-through cursor over an already-computed [`DdCostsResult`], for
+/// UIs that want to walk a board's cardplay one card at a time (forward or
+/// backward) instead of consuming the whole timeline at once.
+pub struct BoardReplay<'a> {
+    result: &'a DdCostsResult,
+    tricks: &'a [Vec<Card>],
+    trick_idx: usize,
+    card_idx: usize,
+}
+
+impl<'a> BoardReplay<'a> {
+    /// A cursor starting at the first card of the first trick. `tricks`
+    /// must have the same trick/card shape as `result.costs` (i.e. the
+    /// actual cards played, as returned by `parse_cardplay`).
+    pub fn new(result: &'a DdCostsResult, tricks: &'a [Vec<Card>]) -> Self {
+        BoardReplay { result, tricks, trick_idx: 0, card_idx: 0 }
+    }
+
+    /// The position the cursor is currently on, or `None` if the board has
+    /// no cardplay at all.
+    pub fn current(&self) -> Option<ReplayPosition> {
+        let card = *self.tricks.get(self.trick_idx)?.get(self.card_idx)?;
+        let seat = self.result.seats[self.trick_idx][self.card_idx];
+        let (dd_before, dd_after) = self.result.dd_timeline[self.trick_idx][self.card_idx];
+        let cost = self.result.costs[self.trick_idx][self.card_idx];
+        let legal_alternatives = self.result.best_cards[self.trick_idx][self.card_idx].clone();
+        Some(ReplayPosition {
+            trick_num: self.trick_idx + 1,
+            card_position: self.card_idx,
+            seat,
+            card,
+            dd_before,
+            dd_after,
+            cost,
+            legal_alternatives,
+        })
+    }
+
+    /// Advances the cursor to the next card, if there is one. Returns
+    /// whether the cursor moved.
+    pub fn step_forward(&mut self) -> bool {
+        let trick_len = self.tricks.get(self.trick_idx).map(|t| t.len()).unwrap_or(0);
+        if self.card_idx + 1 < trick_len {
+            self.card_idx += 1;
+            true
+        } else if self.trick_idx + 1 < self.tricks.len() {
+            self.trick_idx += 1;
+            self.card_idx = 0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Moves the cursor to the previous card, if there is one. Returns
+    /// whether the cursor moved.
+    pub fn step_backward(&mut self) -> bool {
+        if self.card_idx > 0 {
+            self.card_idx -= 1;
+            true
+        } else if self.trick_idx > 0 {
+            self.trick_idx -= 1;
+            self.card_idx = self.tricks[self.trick_idx].len().saturating_sub(1);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Severity tier for a flagged card, derived from DD cost magnitude.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Minor,
+    Major,
+    Blunder,
+}
+
+impl Severity {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Minor => "Minor",
+            Severity::Major => "Major",
+            Severity::Blunder => "Blunder",
+        }
+    }
+}
+
+/// Cost thresholds (in tricks) that map a DD cost to a [`Severity`] tier.
+/// A cost of 0 is never flagged; anything below `major` is Minor.
+#[derive(Debug, Clone, Copy)]
+pub struct SeverityThresholds {
+    pub major: u8,
+    pub blunder: u8,
+}
+
+impl Default for SeverityThresholds {
+    fn default() -> Self {
+        Self { major: 2, blunder: 3 }
+    }
+}
+
+impl SeverityThresholds {
+    fn classify(&self, cost: u8) -> Option<Severity> {
+        if cost == 0 {
+            None
+        } else if cost >= self.blunder {
+            Some(Severity::Blunder)
+        } else if cost >= self.major {
+            Some(Severity::Major)
+        } else {
+            Some(Severity::Minor)
+        }
+    }
+}
+
+/// A seat's role on a given deal, relative to declarer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayRole {
+    Declarer,
+    Dummy,
+    Defender,
+}
+
+/// Everything a [`PlayRule`] needs to judge a single played card.
+pub struct PlayContext {
+    pub trick_num: usize,
+    pub card_position: usize,
+    pub seat: usize,
+    pub card: Card,
+    pub cost: u8,
+    pub trump: usize,
+    pub role: PlayRole,
+    pub severity: Severity,
+}
+
+/// A single flagged card, tagged with the rule that raised it.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub rule_id: &'static str,
+    pub severity: Severity,
+    pub trick_num: usize,
+    pub card_position: usize,
+    pub seat: usize,
+    pub card: Card,
+    pub cost: u8,
+    /// DD-optimal alternative, when one could be identified
+    pub suggested_card: Option<Card>,
+}
+
+/// A pluggable check over a single played card, in the spirit of a linter
+/// rule. `evaluate` returns `Some` when the card should be flagged.
+pub trait PlayRule {
+    fn id(&self) -> &'static str;
+    fn evaluate(&self, ctx: &PlayContext) -> Option<Diagnostic>;
+}
+
+fn make_diagnostic(rule_id: &'static str, ctx: &PlayContext) -> Diagnostic {
+    Diagnostic {
+        rule_id,
+        severity: ctx.severity,
+        trick_num: ctx.trick_num,
+        card_position: ctx.card_position,
+        seat: ctx.seat,
+        card: ctx.card,
+        cost: ctx.cost,
+        suggested_card: None,
+    }
+}
+
+/// Flags the opening lead (trick 1, card 0) when it costs a trick.
+struct OpeningLeadRule;
+impl PlayRule for OpeningLeadRule {
+    fn id(&self) -> &'static str {
+        "opening-lead"
+    }
+
+    fn evaluate(&self, ctx: &PlayContext) -> Option<Diagnostic> {
+        if ctx.trick_num == 1 && ctx.card_position == 0 && ctx.role == PlayRole::Defender {
+            Some(make_diagnostic(self.id(), ctx))
+        } else {
+            None
+        }
+    }
+}
+
+/// Flags costly defensive signals/discards after the opening lead.
+struct DefensiveCardingRule;
+impl PlayRule for DefensiveCardingRule {
+    fn id(&self) -> &'static str {
+        "defensive-carding"
+    }
+
+    fn evaluate(&self, ctx: &PlayContext) -> Option<Diagnostic> {
+        let is_opening_lead = ctx.trick_num == 1 && ctx.card_position == 0;
+        if ctx.role == PlayRole::Defender && !is_opening_lead {
+            Some(make_diagnostic(self.id(), ctx))
+        } else {
+            None
+        }
+    }
+}
+
+/// Flags costly plays by declarer or from dummy.
+struct DeclarerLineRule;
+impl PlayRule for DeclarerLineRule {
+    fn id(&self) -> &'static str {
+        "declarer-line"
+    }
+
+    fn evaluate(&self, ctx: &PlayContext) -> Option<Diagnostic> {
+        if ctx.role == PlayRole::Declarer || ctx.role == PlayRole::Dummy {
+            Some(make_diagnostic(self.id(), ctx))
+        } else {
+            None
+        }
+    }
+}
+
+/// The built-in rule set: opening lead, defensive carding, declarer line.
+pub fn default_rules() -> Vec<Box<dyn PlayRule>> {
+    vec![
+        Box::new(OpeningLeadRule),
+        Box::new(DefensiveCardingRule),
+        Box::new(DeclarerLineRule),
+    ]
+}
+
+/// Per-seat diagnostic counts by severity tier.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SeatSeverityCounts {
+    pub minors: u8,
+    pub majors: u8,
+    pub blunders: u8,
+}
+
+/// Result of running the play-rule engine over a board's cardplay.
+#[derive(Debug, Clone)]
+pub struct DdDiagnosticsResult {
+    /// Every flagged card, in play order
+    pub diagnostics: Vec<Diagnostic>,
+    /// Cost string with each flagged card tagged `rule_id:severity`,
+    /// e.g. `T1:SA:opening-lead:Major,S2|T2:...`
+    pub tagged_analysis: String,
+    /// Per-seat severity tallies (NORTH/EAST/SOUTH/WEST constant -> counts)
+    pub seat_counts: HashMap<usize, SeatSeverityCounts>,
+}
+
+/// Run the play-rule engine over a board's per-card DD costs, classifying
+/// each nonzero-cost card by severity tier and rule id.
+///
+/// Layered over [`compute_dd_costs`]: every card whose DD cost clears the
+/// lowest severity threshold is offered to each rule in turn, and the first
+/// rule that claims it produces the [`Diagnostic`]. This lets callers triage
+/// blunders instead of raw per-card error counts.
+pub fn compute_dd_diagnostics(
+    deal_pbn: &str,
+    cardplay: &str,
+    contract: &str,
+    declarer: &str,
+    thresholds: &SeverityThresholds,
+    rules: &[Box<dyn PlayRule>],
+) -> Result<DdDiagnosticsResult, String> {
+    let parsed_contract: Contract = contract.parse()?;
+    let result = compute_dd_costs(deal_pbn, cardplay, &parsed_contract, declarer, false, false, false)?;
+    let trump = parsed_contract.trump();
+    let tricks = parse_cardplay(cardplay)?;
+
+    let mut diagnostics = Vec::new();
+    let mut seat_counts: HashMap<usize, SeatSeverityCounts> = HashMap::new();
+    let mut tagged_tricks: Vec<String> = Vec::new();
+
+    let initial_leader = (result.declarer_seat + 1) % 4;
+    let mut current_leader = initial_leader;
+
+    for (trick_idx, (trick_costs, trick_cards)) in result.costs.iter().zip(tricks.iter()).enumerate() {
+        let mut seat = current_leader;
+        let mut tagged_cards: Vec<String> = Vec::new();
+
+        for (card_idx, (&cost, &card)) in trick_costs.iter().zip(trick_cards.iter()).enumerate() {
+            let role = if seat == result.declarer_seat {
+                PlayRole::Declarer
+            } else if (seat == NORTH || seat == SOUTH) == result.declarer_is_ns {
+                PlayRole::Dummy
+            } else {
+                PlayRole::Defender
+            };
+
+            let card_str = format!("{}{}", card.suit.to_char(), card.rank.to_char());
+
+            if let Some(severity) = thresholds.classify(cost) {
+                let ctx = PlayContext {
+                    trick_num: trick_idx + 1,
+                    card_position: card_idx,
+                    seat,
+                    card,
+                    cost,
+                    trump,
+                    role,
+                    severity,
+                };
+
+                if let Some(diag) = rules.iter().find_map(|rule| rule.evaluate(&ctx)) {
+                    let counts = seat_counts.entry(seat).or_default();
+                    match diag.severity {
+                        Severity::Minor => counts.minors += 1,
+                        Severity::Major => counts.majors += 1,
+                        Severity::Blunder => counts.blunders += 1,
+                    }
+                    tagged_cards.push(format!(
+                        "{}:{}:{}",
+                        card_str,
+                        diag.rule_id,
+                        diag.severity.as_str()
+                    ));
+                    diagnostics.push(diag);
+                    seat = (seat + 1) % 4;
+                    continue;
+                }
+            }
+
+            tagged_cards.push(card_str);
+            seat = (seat + 1) % 4;
+        }
+
+        tagged_tricks.push(format!("T{}:{}", trick_idx + 1, tagged_cards.join(",")));
+
+        if trick_cards.len() == 4 {
+            let cards_in_trick: Vec<(usize, usize)> = trick_cards
+                .iter()
+                .enumerate()
+                .map(|(i, c)| {
+                    let s = (current_leader + i) % 4;
+                    (s, bridge_card_to_solver(*c).unwrap_or(0))
+                })
+                .collect();
+            current_leader = determine_trick_winner(&cards_in_trick, trump, current_leader);
+        }
+    }
+
+    Ok(DdDiagnosticsResult {
+        diagnostics,
+        tagged_analysis: tagged_tricks.join("|"),
+        seat_counts,
+    })
+}
+
+/// A single detected revoke: a seat failed to follow the led suit despite
+/// still holding a card in it.
+#[derive(Debug, Clone)]
+pub struct RevokeEvent {
+    /// Trick number (1-based)
+    pub trick_num: usize,
+    /// Seat that revoked (NORTH/EAST/SOUTH/WEST constant)
+    pub seat: usize,
+    /// Suit that was led for the trick
+    pub led_suit: Suit,
+}
+
+/// Result of walking a deal's cardplay for rule-legality problems: genuine
+/// revokes (seat had the led suit but didn't follow it) and a separate
+/// `illegal` flag for cards that weren't even in the player's holding --
+/// the latter means the row's LIN/CSV data is corrupt rather than that a
+/// real revoke occurred, so callers should treat it as a data-quality
+/// signal rather than a scored infraction.
+#[derive(Debug, Clone, Default)]
+pub struct RevokeReport {
+    pub revokes: Vec<RevokeEvent>,
+    pub illegal: bool,
+}
+
+/// Parse a PBN deal string (e.g. `"N:AKQ.JT9.876.5432 ..."`) into per-seat
+/// holdings, keyed by the bridge_solver seat constants.
+fn parse_deal_holdings(deal_pbn: &str) -> Result<HashMap<usize, Vec<Card>>, String> {
+    let (first_seat_str, hands_str) = deal_pbn
+        .split_once(':')
+        .ok_or_else(|| format!("Invalid deal (missing seat prefix): {}", deal_pbn))?;
+
+    let first_seat = match first_seat_str.trim().to_uppercase().as_str() {
+        "N" => NORTH,
+        "E" => EAST,
+        "S" => SOUTH,
+        "W" => WEST,
+        _ => return Err(format!("Invalid deal seat prefix: {}", first_seat_str)),
+    };
+
+    let suits = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+    let mut holdings = HashMap::new();
+    for (i, hand_str) in hands_str.split_whitespace().enumerate() {
+        let seat = (first_seat + i) % 4;
+        let mut cards = Vec::new();
+        for (suit, ranks) in suits.iter().zip(hand_str.split('.')) {
+            for rank_char in ranks.chars() {
+                if let Some(rank) = Rank::from_char(rank_char) {
+                    cards.push(Card::new(*suit, rank));
+                }
+            }
+        }
+        holdings.insert(seat, cards);
+    }
+
+    Ok(holdings)
+}
+
+/// Detect revokes (failures to follow suit while still holding the led
+/// suit) across a full deal's cardplay, plus illegal plays (a card that
+/// isn't in the player's remaining holding at all).
+///
+/// Walks the play trick by trick, tracking each seat's remaining holding
+/// from the original deal, and for every non-leader play checks whether the
+/// played suit differs from the trick's led suit despite the seat still
+/// holding a card in that suit. Surfacing these lets reviewers spot
+/// data-entry errors (or actual rule violations) in BBO records. Separately,
+/// any card that a seat plays despite it not being in their holding at all
+/// sets `illegal` -- that's a sign the row's deal/cardplay were mis-parsed
+/// rather than an actual revoke, so it's kept out of the `revokes` list.
+pub fn detect_revokes(
+    deal_pbn: &str,
+    cardplay: &str,
+    contract: &str,
+    declarer: &str,
+) -> Result<RevokeReport, String> {
+    let mut holdings = parse_deal_holdings(deal_pbn)?;
+    let trump = parse_trump(contract)?;
+    let declarer_seat = parse_declarer_seat(declarer)?;
+    let mut current_leader = (declarer_seat + 1) % 4;
+    let tricks = parse_cardplay(cardplay)?;
+
+    let mut report = RevokeReport::default();
+
+    for (trick_idx, trick) in tricks.iter().enumerate() {
+        if trick.len() != 4 {
+            continue;
+        }
+
+        let mut led_suit: Option<Suit> = None;
+        let mut seat = current_leader;
+        let mut cards_in_trick: Vec<(usize, usize)> = Vec::new();
+
+        for (pos, card) in trick.iter().enumerate() {
+            if pos == 0 {
+                led_suit = Some(card.suit);
+            } else if let Some(led) = led_suit {
+                if card.suit != led {
+                    let still_has_led = holdings
+                        .get(&seat)
+                        .map(|h| h.iter().any(|c| c.suit == led))
+                        .unwrap_or(false);
+                    if still_has_led {
+                        report.revokes.push(RevokeEvent { trick_num: trick_idx + 1, seat, led_suit: led });
+                    }
+                }
+            }
+
+            if let Some(hand) = holdings.get_mut(&seat) {
+                if hand.contains(card) {
+                    hand.retain(|c| c != card);
+                } else {
+                    report.illegal = true;
+                }
+            }
+            if let Ok(solver_card) = bridge_card_to_solver(*card) {
+                cards_in_trick.push((seat, solver_card));
+            }
+
+            seat = (seat + 1) % 4;
+        }
+
+        current_leader = determine_trick_winner(&cards_in_trick, trump, current_leader);
+    }
+
+    Ok(report)
+}
+
+/// Outcome of adjudicating a claim: whether the claimed trick count holds
+/// against best defense, the DD-guaranteed trick count for the claimer's
+/// side from this position, and -- when unsound -- the continuation that
+/// holds the claimer below what they claimed.
+#[derive(Debug, Clone)]
+pub struct ClaimValidation {
+    /// Whether `claimed_tricks` is guaranteed for the claimer's side.
+    pub sound: bool,
+    /// The DD-guaranteed trick count for the claimer's side from this
+    /// position (with best play on both sides).
+    pub guaranteed_tricks: u8,
+    /// When `sound` is `false`, one full DD-optimal continuation (both
+    /// sides' cards, in play order from this position to the end of the
+    /// hand) that holds the claimer to `guaranteed_tricks`.
+    pub defeating_line: Option<Vec<Card>>,
+}
+
+/// Validate a claim of `claimed_tricks` more tricks for `claimer`'s side,
+/// from the position reached after `completed_tricks` (full tricks, in the
+/// shape `parse_cardplay` returns) plus `current_trick` (0..=3 cards
+/// already played in the trick still in progress, in play order starting
+/// from whoever led it).
+///
+/// `deal_pbn` and `leader` (who led the very first trick of the hand) are
+/// needed to replay the position -- a flat card list alone doesn't carry
+/// that, the same way `compute_dd_costs` needs `deal_pbn` and `declarer` to
+/// derive its own `initial_leader`.
+///
+/// A claim is sound only if it holds against *any* legal defense, which is
+/// exactly the double-dummy value from this position: a single solve
+/// already finds the defense that minimizes the claimer's tricks (and the
+/// claimer's own best continuation), so there's no separate "claimer's best
+/// defense" and "claimer's worst defense" solve to run -- just the one DD
+/// value, the way a director adjudicating a disputed claim would reason
+/// about it.
+pub fn validate_claim(
+    deal_pbn: &str,
+    leader: usize,
+    completed_tricks: &[Vec<Card>],
+    current_trick: &[Card],
+    claimer: usize,
+    claimed_tricks: usize,
+    trump: usize,
+) -> Result<ClaimValidation, String> {
+    let mut hands = Hands::from_pbn(deal_pbn)
+        .ok_or_else(|| format!("Failed to parse deal: {}", deal_pbn))?;
+    let mut holdings = parse_deal_holdings(deal_pbn)?;
+    let mut cutoff_cache = CutoffCache::new(16);
+    let mut pattern_cache = PatternCache::new(16);
+    let claimer_is_ns = claimer == NORTH || claimer == SOUTH;
+
+    let mut current_leader = leader;
+    for trick in completed_tricks {
+        if trick.len() != 4 {
+            return Err("completed_tricks must contain only full 4-card tricks".to_string());
+        }
+        let mut cards_in_trick = Vec::with_capacity(4);
+        let mut seat = current_leader;
+        for card in trick {
+            let solver_card = bridge_card_to_solver(*card)?;
+            hands[seat].remove(solver_card);
+            if let Some(hand) = holdings.get_mut(&seat) {
+                hand.retain(|c| c != card);
+            }
+            cards_in_trick.push((seat, solver_card));
+            seat = (seat + 1) % 4;
+        }
+        current_leader = determine_trick_winner(&cards_in_trick, trump, current_leader);
+    }
+
+    let mut partial_trick = PartialTrick::new();
+    let mut cards_in_trick: Vec<(usize, usize)> = Vec::new();
+    let mut seat = current_leader;
+    for card in current_trick {
+        let solver_card = bridge_card_to_solver(*card)?;
+        hands[seat].remove(solver_card);
+        if let Some(hand) = holdings.get_mut(&seat) {
+            hand.retain(|c| c != card);
+        }
+        partial_trick.add(solver_card, seat);
+        cards_in_trick.push((seat, solver_card));
+        seat = (seat + 1) % 4;
+    }
+
+    let guaranteed_tricks = if current_trick.is_empty() {
+        let ns = solve_position(&hands, trump, current_leader, &mut cutoff_cache, &mut pattern_cache);
+        if claimer_is_ns {
+            ns
+        } else {
+            (hands.num_tricks() as u8).saturating_sub(ns)
+        }
+    } else {
+        let (ns, remaining) =
+            solve_mid_trick(&hands, trump, &partial_trick, &mut cutoff_cache, &mut pattern_cache);
+        if claimer_is_ns {
+            ns
+        } else {
+            remaining.saturating_sub(ns)
+        }
+    };
+
+    let sound = guaranteed_tricks as usize >= claimed_tricks;
+
+    let defeating_line = if sound {
+        None
+    } else {
+        Some(construct_defeating_line(
+            hands,
+            trump,
+            current_leader,
+            cards_in_trick,
+            seat,
+            claimer_is_ns,
+            &mut holdings,
+            &mut cutoff_cache,
+            &mut pattern_cache,
+        ))
+    };
+
+    Ok(ClaimValidation { sound, guaranteed_tricks, defeating_line })
+}
+
+/// Play out the rest of the hand from an unsound claim's position, one card
+/// at a time: at every position, each seat picks the legal card that's best
+/// for its own side (the claimer's side maximizes the claimer's eventual
+/// tricks, the defense minimizes it), using a solve the same way
+/// `find_best_cards_for_position` scores one candidate. The result is the
+/// single DD-optimal continuation that holds the claimer to
+/// `guaranteed_tricks` -- the line a director would read out to show why
+/// the claim fails.
+#[allow(clippy::too_many_arguments)]
+fn construct_defeating_line(
+    mut hands: Hands,
+    trump: usize,
+    mut leader: usize,
+    mut cards_in_trick: Vec<(usize, usize)>,
+    mut seat: usize,
+    claimer_is_ns: bool,
+    holdings: &mut HashMap<usize, Vec<Card>>,
+    cutoff_cache: &mut CutoffCache,
+    pattern_cache: &mut PatternCache,
+) -> Vec<Card> {
+    let mut line = Vec::new();
+
+    while hands.num_tricks() > 0 || !cards_in_trick.is_empty() {
+        let seat_is_claimer_side = (seat == NORTH || seat == SOUTH) == claimer_is_ns;
+        let holding = holdings.get(&seat).cloned().unwrap_or_default();
+        let led_suit = cards_in_trick
+            .first()
+            .map(|&(_, solver_card)| solver_suit_to_suit(suit_of(solver_card)));
+        let legal: Vec<Card> = match led_suit {
+            Some(suit) => {
+                let following: Vec<Card> =
+                    holding.iter().copied().filter(|c| c.suit == suit).collect();
+                if following.is_empty() {
+                    holding
+                } else {
+                    following
+                }
+            }
+            None => holding,
+        };
+
+        let mut best_card = None;
+        let mut best_value = None;
+        for candidate in legal {
+            let Ok(candidate_solver) = bridge_card_to_solver(candidate) else {
+                continue;
+            };
+            let mut trial_hands = hands;
+            trial_hands[seat].remove(candidate_solver);
+
+            let value = if cards_in_trick.len() == 3 {
+                let mut trial_cards_in_trick = cards_in_trick.clone();
+                trial_cards_in_trick.push((seat, candidate_solver));
+                let winner = determine_trick_winner(&trial_cards_in_trick, trump, leader);
+                if trial_hands.num_tricks() == 0 {
+                    if (winner == NORTH || winner == SOUTH) == claimer_is_ns {
+                        1u8
+                    } else {
+                        0u8
+                    }
+                } else {
+                    let ns = solve_position(&trial_hands, trump, winner, cutoff_cache, pattern_cache);
+                    let remaining = trial_hands.num_tricks() as u8;
+                    let claimer_won_this = (winner == NORTH || winner == SOUTH) == claimer_is_ns;
+                    let rest = if claimer_is_ns { ns } else { remaining.saturating_sub(ns) };
+                    rest + if claimer_won_this { 1u8 } else { 0u8 }
+                }
+            } else {
+                let mut trial_partial = PartialTrick::new();
+                for &(s, solver_card) in &cards_in_trick {
+                    trial_partial.add(solver_card, s);
+                }
+                trial_partial.add(candidate_solver, seat);
+                let (ns, remaining) =
+                    solve_mid_trick(&trial_hands, trump, &trial_partial, cutoff_cache, pattern_cache);
+                if claimer_is_ns {
+                    ns
+                } else {
+                    remaining.saturating_sub(ns)
+                }
+            };
+
+            let better = match best_value {
+                None => true,
+                Some(current) => {
+                    if seat_is_claimer_side {
+                        value > current
+                    } else {
+                        value < current
+                    }
+                }
+            };
+            if better {
+                best_value = Some(value);
+                best_card = Some(candidate);
+            }
+        }
+
+        let Some(card) = best_card else { break };
+        let solver_card = bridge_card_to_solver(card).expect("already parsed above");
+        hands[seat].remove(solver_card);
+        if let Some(hand) = holdings.get_mut(&seat) {
+            hand.retain(|c| c != &card);
+        }
+        cards_in_trick.push((seat, solver_card));
+        line.push(card);
+
+        if cards_in_trick.len() == 4 {
+            leader = determine_trick_winner(&cards_in_trick, trump, leader);
+            seat = leader;
+            cards_in_trick.clear();
+        } else {
+            seat = (seat + 1) % 4;
+        }
+    }
+
+    line
+}
+
 /// Analyze DD errors for a single board
 ///
 /// Returns detailed DD analysis including all errors found during cardplay.
@@ -324,11 +1486,8 @@ pub fn analyze_board(lin_data: &LinData, config: &DdAnalysisConfig) -> Option<Dd
         return None;
     }
 
-    // Extract contract info
-    let contract = extract_contract(lin_data);
-    if contract == "Passed Out" {
-        return None;
-    }
+    // Extract contract info (None means the board was passed out)
+    let contract = extract_contract(lin_data)?;
 
     let declarer = extract_declarer(lin_data);
 
@@ -348,7 +1507,16 @@ pub fn analyze_board(lin_data: &LinData, config: &DdAnalysisConfig) -> Option<Dd
 
     if config.mid_trick {
         // Mid-trick mode: use shared compute_dd_costs function
-        let dd_result = compute_dd_costs(&pbn, &cardplay, &contract, &declarer, config.debug).ok()?;
+        let dd_result = compute_dd_costs(
+            &pbn,
+            &cardplay,
+            &contract,
+            &declarer,
+            config.debug,
+            config.find_best_cards,
+            config.alternative_baseline,
+        )
+        .ok()?;
 
         // Parse cardplay to get cards for error attribution
         let tricks = parse_cardplay(&cardplay).ok()?;
@@ -378,12 +1546,16 @@ pub fn analyze_board(lin_data: &LinData, config: &DdAnalysisConfig) -> Option<Dd
                     };
 
                     if let Some(player) = seat_to_player.get(&error_seat) {
+                        let (dd_before, dd_after) = dd_result.dd_timeline[trick_idx][card_idx];
                         errors.push(DdError {
                             player: player.clone(),
                             trick_num: trick_idx + 1,
                             card_position: card_idx,
                             card: *card,
                             cost: *cost,
+                            dd_before,
+                            dd_after,
+                            best_cards: dd_result.best_cards[trick_idx][card_idx].clone(),
                         });
                     }
                 }
@@ -392,7 +1564,7 @@ pub fn analyze_board(lin_data: &LinData, config: &DdAnalysisConfig) -> Option<Dd
 
             // Determine winner to update leader for next trick
             if trick_cards.len() == 4 {
-                let trump = parse_trump(&contract).ok()?;
+                let trump = contract.trump();
                 let cards_in_trick: Vec<(usize, usize)> = trick_cards
                     .iter()
                     .enumerate()
@@ -413,12 +1585,13 @@ pub fn analyze_board(lin_data: &LinData, config: &DdAnalysisConfig) -> Option<Dd
             declarer,
             initial_dd: dd_result.initial_dd,
             final_result: dd_result.costs.len() as u8, // Approximate - could track properly
+            vulnerable: declarer_side_vulnerable(&lin_data.vulnerability, dd_result.declarer_is_ns),
             errors,
         });
     }
 
     // Trick-boundary mode: compute DD only at start and end of each trick
-    let trump = parse_trump(&contract).ok()?;
+    let trump = contract.trump();
     let declarer_seat = parse_declarer_seat(&declarer).ok()?;
     let initial_leader = (declarer_seat + 1) % 4;
     let declarer_is_ns = declarer_seat == NORTH || declarer_seat == SOUTH;
@@ -531,6 +1704,9 @@ pub fn analyze_board(lin_data: &LinData, config: &DdAnalysisConfig) -> Option<Dd
                     card_position: 0,
                     card: trick[0],
                     cost,
+                    dd_before: dd_start,
+                    dd_after: dd_end,
+                    best_cards: Vec::new(),
                 });
             }
         } else if dd_end > dd_start {
@@ -567,6 +1743,9 @@ pub fn analyze_board(lin_data: &LinData, config: &DdAnalysisConfig) -> Option<Dd
                     card_position: 0,
                     card: trick[0],
                     cost,
+                    dd_before: dd_start,
+                    dd_after: dd_end,
+                    best_cards: Vec::new(),
                 });
             }
         }
@@ -586,6 +1765,7 @@ pub fn analyze_board(lin_data: &LinData, config: &DdAnalysisConfig) -> Option<Dd
         declarer,
         initial_dd,
         final_result: declarer_tricks_won,
+        vulnerable: declarer_side_vulnerable(&lin_data.vulnerability, declarer_is_ns),
         errors,
     })
 }
@@ -612,8 +1792,72 @@ pub fn aggregate_costs_by_player(result: &DdAnalysisResult) -> HashMap<String, u
     costs
 }
 
+/// Score point cost of a single error: the duplicate-scoring swing between
+/// the contract scored at `error.dd_before` tricks and at `error.dd_after`
+/// tricks, at `result`'s contract and vulnerability. Always non-negative.
+fn score_error_cost(result: &DdAnalysisResult, error: &DdError) -> i32 {
+    let contract = &result.contract;
+    let points_before = score_contract(
+        contract.level,
+        contract.trump(),
+        contract.doubling,
+        error.dd_before,
+        result.vulnerable,
+    );
+    let points_after = score_contract(
+        contract.level,
+        contract.trump(),
+        contract.doubling,
+        error.dd_after,
+        result.vulnerable,
+    );
+    (points_before - points_after).max(0)
+}
+
+/// Aggregate DD errors by player, summing the duplicate-scoring point cost
+/// of each error (contract scored at `dd_before` tricks minus at `dd_after`
+/// tricks) rather than raw tricks lost.
+///
+/// Returns a map of player name -> total points cost
+pub fn aggregate_score_cost_by_player(result: &DdAnalysisResult) -> HashMap<String, i32> {
+    let mut costs: HashMap<String, i32> = HashMap::new();
+    for error in &result.errors {
+        *costs.entry(error.player.clone()).or_insert(0) += score_error_cost(result, error);
+    }
+    costs
+}
+
+/// Same as [`aggregate_score_cost_by_player`], but in IMPs rather than raw
+/// duplicate points.
+pub fn aggregate_imp_cost_by_player(result: &DdAnalysisResult) -> HashMap<String, i32> {
+    let mut costs: HashMap<String, i32> = HashMap::new();
+    for error in &result.errors {
+        *costs.entry(error.player.clone()).or_insert(0) += points_to_imps(score_error_cost(result, error));
+    }
+    costs
+}
+
+fn declarer_side_vulnerable(vulnerability: &Vulnerability, declarer_is_ns: bool) -> bool {
+    match vulnerability {
+        Vulnerability::Both => true,
+        Vulnerability::NorthSouth => declarer_is_ns,
+        Vulnerability::EastWest => !declarer_is_ns,
+        Vulnerability::None => false,
+    }
+}
+
 // Helper functions
 
+/// `cutoff_cache`/`pattern_cache` are already allocated once per
+/// `compute_dd_costs` call and threaded through every solve in the board
+/// (start-of-trick and mid-trick alike), so entries from earlier positions
+/// are already reused here.
+///
+/// Narrowing the alpha-beta window itself around the previous card's DD
+/// value (as real engines do) would need a `Solver::solve_with_guess` hook
+/// into `bridge_solver`'s search -- that crate is an external dependency
+/// with no source in this tree, so there's no internals to add the seeded
+/// search to from here. Left as a follow-up for whoever owns that crate.
 fn solve_position(
     hands: &Hands,
     trump: usize,
@@ -664,7 +1908,9 @@ fn extract_board_number(header: &Option<String>) -> Option<usize> {
     })
 }
 
-fn extract_contract(lin_data: &LinData) -> String {
+/// Extract the final contract from a LIN auction, or `None` if the board
+/// was passed out.
+fn extract_contract(lin_data: &LinData) -> Option<Contract> {
     let mut level = 0u8;
     let mut suit = String::new();
     let mut doubled = false;
@@ -690,16 +1936,16 @@ fn extract_contract(lin_data: &LinData) -> String {
     }
 
     if level == 0 {
-        return "Passed Out".to_string();
+        return None;
     }
 
-    let mut contract = format!("{}{}", level, suit);
+    let mut contract_str = format!("{}{}", level, suit);
     if redoubled {
-        contract.push_str("XX");
+        contract_str.push_str("XX");
     } else if doubled {
-        contract.push_str("X");
+        contract_str.push_str("X");
     }
-    contract
+    contract_str.parse().ok()
 }
 
 fn extract_declarer(lin_data: &LinData) -> String {
@@ -721,20 +1967,7 @@ fn extract_declarer(lin_data: &LinData) -> String {
 }
 
 fn parse_trump(contract: &str) -> Result<usize, String> {
-    let contract = contract.trim().to_uppercase();
-    if contract.contains("NT") || (contract.contains('N') && !contract.contains('S')) {
-        return Ok(NOTRUMP);
-    }
-    for c in contract.chars() {
-        match c {
-            'S' => return Ok(SPADE),
-            'H' => return Ok(HEART),
-            'D' => return Ok(DIAMOND),
-            'C' => return Ok(CLUB),
-            _ => continue,
-        }
-    }
-    Err(format!("Could not parse trump from: {}", contract))
+    contract.parse::<Contract>().map(|c| c.trump())
 }
 
 fn parse_declarer_seat(declarer: &str) -> Result<usize, String> {
@@ -767,26 +2000,7 @@ fn parse_cardplay(cardplay: &str) -> Result<Vec<Vec<Card>>, String> {
 }
 
 fn parse_card_str(s: &str) -> Result<Card, String> {
-    let s = s.trim();
-    if s.len() < 2 {
-        return Err(format!("Invalid card: {}", s));
-    }
-    let mut chars = s.chars();
-    let suit_char = chars.next().unwrap();
-    let rank_char = chars.next().unwrap();
-
-    let suit = match suit_char.to_ascii_uppercase() {
-        'S' => Suit::Spades,
-        'H' => Suit::Hearts,
-        'D' => Suit::Diamonds,
-        'C' => Suit::Clubs,
-        _ => return Err(format!("Invalid suit: {}", suit_char)),
-    };
-
-    let rank =
-        Rank::from_char(rank_char).ok_or_else(|| format!("Invalid rank: {}", rank_char))?;
-
-    Ok(Card::new(suit, rank))
+    crate::card_tokens::parse_card_token(s).map_err(|e| e.to_string())
 }
 
 fn bridge_card_to_solver(card: Card) -> Result<usize, String> {
@@ -849,8 +2063,8 @@ mod tests {
         let lin = "pn|South,West,North,East|md|3SAKHJD876C5432,S2HQT9DKQ5CKQJT9,SQJT9HA32DAJ2CA8,|sv|o|ah|Board+1|mb|1C|mb|p|mb|1N|mb|p|mb|p|mb|p|";
         let data = parse_lin(lin).unwrap();
 
-        let contract = extract_contract(&data);
-        assert_eq!(contract, "1N");
+        let contract = extract_contract(&data).unwrap();
+        assert_eq!(contract.to_string(), "1N");
     }
 
     #[test]
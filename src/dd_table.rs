@@ -0,0 +1,353 @@
+//! Full double-dummy table and par contract computation
+//!
+//! This module extends the single-contract DD analysis in [`crate::dd_analysis`]
+//! to the full 20-cell double-dummy table (tricks available to each of the
+//! four declarers in each of the five strains) and derives the par result
+//! from it: the contract and score that would be reached if both sides bid
+//! and defended double-dummy, including sacrifices.
+
+use crate::scoring::{score_contract, Doubled};
+use bridge_parsers::Vulnerability;
+use bridge_solver::{CutoffCache, Hands, PatternCache, Solver};
+use bridge_solver::{CLUB, DIAMOND, EAST, HEART, NORTH, NOTRUMP, SOUTH, SPADE, WEST};
+
+/// Strains in the order the table's columns are reported, NT last.
+pub const STRAINS: [usize; 5] = [SPADE, HEART, DIAMOND, CLUB, NOTRUMP];
+
+/// Seats in the order the table's rows are reported.
+pub const SEATS: [usize; 4] = [NORTH, EAST, SOUTH, WEST];
+
+/// Tricks each seat can take as declarer in each strain: `table[seat_row][strain_col]`,
+/// indexed per [`SEATS`] and [`STRAINS`].
+pub type DdTable = [[u8; 5]; 4];
+
+/// A partnership, for par-contract purposes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    NorthSouth,
+    EastWest,
+}
+
+impl Side {
+    fn opposite(self) -> Side {
+        match self {
+            Side::NorthSouth => Side::EastWest,
+            Side::EastWest => Side::NorthSouth,
+        }
+    }
+
+    fn seats(self) -> [usize; 2] {
+        match self {
+            Side::NorthSouth => [NORTH, SOUTH],
+            Side::EastWest => [EAST, WEST],
+        }
+    }
+
+    fn is_vulnerable(self, vul: &Vulnerability) -> bool {
+        match (self, vul) {
+            (Side::NorthSouth, Vulnerability::NorthSouth) => true,
+            (Side::NorthSouth, Vulnerability::Both) => true,
+            (Side::EastWest, Vulnerability::EastWest) => true,
+            (Side::EastWest, Vulnerability::Both) => true,
+            _ => false,
+        }
+    }
+
+    fn of_seat(seat: usize) -> Side {
+        if seat == NORTH || seat == SOUTH {
+            Side::NorthSouth
+        } else {
+            Side::EastWest
+        }
+    }
+}
+
+/// The par result: the contract reached if both sides bid and defended
+/// double-dummy, with sacrifices taken into account.
+#[derive(Debug, Clone)]
+pub struct ParResult {
+    /// Contract level, 1-7. `None` if neither side can make anything and the
+    /// board is passed out.
+    pub level: Option<u8>,
+    /// Trump strain of the final contract (meaningless if `level` is `None`).
+    pub strain: usize,
+    /// Declaring seat (meaningless if `level` is `None`).
+    pub declarer_seat: usize,
+    /// The side that ends up declaring (meaningless if `level` is `None`).
+    pub declaring_side: Side,
+    /// Whether the final contract makes (`false` means it's a sacrifice that
+    /// goes down on purpose).
+    pub making: bool,
+    /// Par score from the declaring side's perspective; positive means the
+    /// declaring side gains that many points.
+    pub score: i32,
+}
+
+/// Compute the full double-dummy table for a deal: tricks each seat can
+/// take as declarer, in each of the five strains.
+pub fn compute_dd_table(hands: &Hands) -> DdTable {
+    let mut table = [[0u8; 5]; 4];
+    let mut cutoff_cache = CutoffCache::new(16);
+    let mut pattern_cache = PatternCache::new(16);
+
+    for (row, &declarer_seat) in SEATS.iter().enumerate() {
+        let leader = (declarer_seat + 1) % 4;
+        let declarer_is_ns = declarer_seat == NORTH || declarer_seat == SOUTH;
+        for (col, &trump) in STRAINS.iter().enumerate() {
+            let ns = solve_position(hands, trump, leader, &mut cutoff_cache, &mut pattern_cache);
+            table[row][col] = if declarer_is_ns { ns } else { 13 - ns };
+        }
+    }
+
+    table
+}
+
+/// Compute the double-dummy table and its derived par result in one pass.
+pub fn compute_dd_table_and_par(hands: &Hands, vulnerability: &Vulnerability) -> (DdTable, ParResult) {
+    let table = compute_dd_table(hands);
+    let par = compute_par(&table, vulnerability);
+    (table, par)
+}
+
+fn solve_position(
+    hands: &Hands,
+    trump: usize,
+    leader: usize,
+    cutoff_cache: &mut CutoffCache,
+    pattern_cache: &mut PatternCache,
+) -> u8 {
+    if hands.num_tricks() == 0 {
+        return 0;
+    }
+    let solver = Solver::new(*hands, trump, leader);
+    solver.solve_with_caches(cutoff_cache, pattern_cache)
+}
+
+fn seat_row(seat: usize) -> usize {
+    SEATS.iter().position(|&s| s == seat).expect("valid seat")
+}
+
+fn strain_col(strain: usize) -> usize {
+    STRAINS.iter().position(|&s| s == strain).expect("valid strain")
+}
+
+/// The seat within `side` that takes the most tricks in `strain`, and how
+/// many tricks it takes.
+fn best_seat_for_strain(table: &DdTable, side: Side, strain: usize) -> (usize, u8) {
+    let col = strain_col(strain);
+    side.seats()
+        .into_iter()
+        .map(|seat| (seat, table[seat_row(seat)][col]))
+        .max_by_key(|&(_, tricks)| tricks)
+        .expect("a side has two seats")
+}
+
+/// Highest-scoring contract `side` can make, across all five strains, or
+/// `None` if it can't make anything.
+fn best_makeable_contract(table: &DdTable, side: Side, vul: &Vulnerability) -> Option<(u8, usize, usize, i32)> {
+    let vulnerable = side.is_vulnerable(vul);
+    let mut best: Option<(u8, usize, usize, i32)> = None;
+
+    for &strain in STRAINS.iter() {
+        let (seat, tricks) = best_seat_for_strain(table, side, strain);
+        if tricks < 7 {
+            continue;
+        }
+        let level = tricks - 6;
+        let score = score_contract(level, strain, Doubled::Undoubled, tricks, vulnerable);
+        if best.is_none_or(|(_, _, _, best_score)| score > best_score) {
+            best = Some((level, strain, seat, score));
+        }
+    }
+
+    best
+}
+
+/// Derive the par result from a completed double-dummy table.
+///
+/// Starting from whichever side's best makeable contract scores higher, the
+/// other side is repeatedly given the chance to outbid it at a higher level:
+/// by making a higher contract of its own (strictly better, so it always
+/// takes it), or by sacrificing if going down costs fewer points than
+/// defending the contract on the table. The loop stops once neither side has
+/// a profitable bid left; it always terminates because every step raises the
+/// level, and the level is capped at 7.
+pub fn compute_par(table: &DdTable, vulnerability: &Vulnerability) -> ParResult {
+    let ns_best = best_makeable_contract(table, Side::NorthSouth, vulnerability);
+    let ew_best = best_makeable_contract(table, Side::EastWest, vulnerability);
+
+    let (mut side, mut level, mut strain, mut seat, mut making, mut score) = match (ns_best, ew_best) {
+        (None, None) => {
+            return ParResult {
+                level: None,
+                strain: NOTRUMP,
+                declarer_seat: NORTH,
+                declaring_side: Side::NorthSouth,
+                making: false,
+                score: 0,
+            };
+        }
+        (Some((l, s, seat, score)), None) => (Side::NorthSouth, l, s, seat, true, score),
+        (None, Some((l, s, seat, score))) => (Side::EastWest, l, s, seat, true, score),
+        (Some(ns), Some(ew)) => {
+            if ns.3 >= ew.3 {
+                (Side::NorthSouth, ns.0, ns.1, ns.2, true, ns.3)
+            } else {
+                (Side::EastWest, ew.0, ew.1, ew.2, true, ew.3)
+            }
+        }
+    };
+
+    loop {
+        let challenger = side.opposite();
+        let vulnerable = challenger.is_vulnerable(vulnerability);
+        let mut best_bid: Option<(u8, usize, usize, bool, i32)> = None;
+
+        for bid_level in (level + 1)..=7 {
+            for &bid_strain in STRAINS.iter() {
+                let (bid_seat, tricks) = best_seat_for_strain(table, challenger, bid_strain);
+                let bid_making = tricks >= bid_level + 6;
+                let bid_score = score_contract(bid_level, bid_strain, Doubled::Undoubled, tricks, vulnerable);
+
+                // Worth bidding if it beats defending the current contract,
+                // i.e. conceding `score` points to the side on the table.
+                if bid_score > -score
+                    && best_bid.is_none_or(|(_, _, _, _, best_score)| bid_score > best_score)
+                {
+                    best_bid = Some((bid_level, bid_strain, bid_seat, bid_making, bid_score));
+                }
+            }
+        }
+
+        match best_bid {
+            Some((bid_level, bid_strain, bid_seat, bid_making, bid_score)) => {
+                side = challenger;
+                level = bid_level;
+                strain = bid_strain;
+                seat = bid_seat;
+                making = bid_making;
+                score = bid_score;
+            }
+            None => break,
+        }
+    }
+
+    ParResult {
+        level: Some(level),
+        strain,
+        declarer_seat: seat,
+        declaring_side: side,
+        making,
+        score,
+    }
+}
+
+/// Render the double-dummy table as a text grid, seats as rows and strains
+/// as columns.
+pub fn format_dd_table(table: &DdTable) -> String {
+    let mut out = String::new();
+    out.push_str("      S  H  D  C NT\n");
+    for (row, &seat) in SEATS.iter().enumerate() {
+        out.push_str(&format!(
+            "{:5} {:2} {:2} {:2} {:2} {:2}\n",
+            seat_name(seat),
+            table[row][0],
+            table[row][1],
+            table[row][2],
+            table[row][3],
+            table[row][4],
+        ));
+    }
+    out
+}
+
+/// Render the par result as a one-line summary.
+pub fn format_par_result(par: &ParResult) -> String {
+    let Some(level) = par.level else {
+        return "Par: Passed Out (0)".to_string();
+    };
+    let strain_char = match par.strain {
+        SPADE => "S",
+        HEART => "H",
+        DIAMOND => "D",
+        CLUB => "C",
+        _ => "NT",
+    };
+    let verb = if par.making { "makes" } else { "goes down in" };
+    format!(
+        "Par: {}{} by {} ({:?}) {} for {}",
+        level,
+        strain_char,
+        seat_name(par.declarer_seat),
+        par.declaring_side,
+        verb,
+        par.score,
+    )
+}
+
+fn seat_name(seat: usize) -> &'static str {
+    match seat {
+        WEST => "West",
+        NORTH => "North",
+        EAST => "East",
+        SOUTH => "South",
+        _ => "?",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An all-zero table, with one `(seat, strain, tricks)` entry poked in
+    /// per call -- lets a test describe only the cells it cares about.
+    fn table_with(entries: &[(usize, usize, u8)]) -> DdTable {
+        let mut table = [[0u8; 5]; 4];
+        for &(seat, strain, tricks) in entries {
+            table[seat_row(seat)][strain_col(strain)] = tricks;
+        }
+        table
+    }
+
+    #[test]
+    fn test_compute_par_straightforward_game() {
+        // North can make exactly 4S (10 tricks); nobody else can make
+        // anything, so par is just North's best contract.
+        let table = table_with(&[(NORTH, SPADE, 10)]);
+        let par = compute_par(&table, &Vulnerability::None);
+
+        assert_eq!(par.level, Some(4));
+        assert_eq!(par.strain, SPADE);
+        assert_eq!(par.declarer_seat, NORTH);
+        assert_eq!(par.declaring_side, Side::NorthSouth);
+        assert!(par.making);
+        assert_eq!(par.score, 420);
+    }
+
+    #[test]
+    fn test_compute_par_sacrifice() {
+        // North makes 4S (420 not vulnerable). East can only manage 9 tricks
+        // in hearts, but going down 2 in a 5H sacrifice (-100) still beats
+        // letting North's 420 stand, so par becomes EW's non-making sacrifice.
+        let table = table_with(&[(NORTH, SPADE, 10), (EAST, HEART, 9)]);
+        let par = compute_par(&table, &Vulnerability::None);
+
+        assert_eq!(par.level, Some(5));
+        assert_eq!(par.strain, HEART);
+        assert_eq!(par.declarer_seat, EAST);
+        assert_eq!(par.declaring_side, Side::EastWest);
+        assert!(!par.making);
+        assert_eq!(par.score, -100);
+    }
+
+    #[test]
+    fn test_compute_par_passed_out() {
+        // Nobody can take 7 tricks in anything -- the board is passed out.
+        let table = [[0u8; 5]; 4];
+        let par = compute_par(&table, &Vulnerability::None);
+
+        assert_eq!(par.level, None);
+        assert!(!par.making);
+        assert_eq!(par.score, 0);
+    }
+}
@@ -3,14 +3,60 @@
 //! Tools for detecting suspicious bridge play patterns through double-dummy analysis.
 //!
 //! This library provides:
-//! - `dd_analysis`: Core double-dummy analysis engine for computing per-card costs
+//! - `card_tokens`: Locale-aware card-token parsing (Unicode suit glyphs,
+//!   "10", suit-first or rank-first ordering)
+//! - `cards`: Typed `Card`/`Suit`/`Rank` model with a bitmask `Hand` and a
+//!   single `trick_winner` evaluator, used by `bbo-csv`'s hand display and
+//!   DD-summary code so trick-winner logic lives in one place
+//! - `contract`: Structured `Contract` type (level/strain/doubling) parsed
+//!   once from the short contract string and reused everywhere else
+//! - `anon_common`: Shared anonymization utilities (URL mapping, player name
+//!   mapping, BBO screenshot pixel redaction, text map loading) used by both
+//!   `docx-anon` and `pdf-anon`
+//! - `dd_analysis`: Core double-dummy analysis engine for computing per-card costs.
+//!   With the `serde` feature enabled, `DdCostsResult::to_replay_json` exports
+//!   the full per-card DD timeline for a web-based board replayer.
+//! - `dd_table`: Full double-dummy table and par contract computation
+//! - `packed_cards`: Bitmask card/hand encoding for fast trick-winner lookup
+//! - `pbn`: Minimal PBN tag-pair reader/writer for `(deal_pbn, declarer,
+//!   contract, cardplay)` boards
+//! - `pdf_text`: Content-stream text-run extraction/rewriting for PDF
+//!   anonymization, tracking text-positioning state well enough to recover
+//!   each run's device-space bounding box
+//! - `play_state`: Reusable card-by-card play engine with follow-suit validation
+//! - `rate_limit`: Per-host AIMD token-bucket rate limiting, shared by
+//!   `pipeline`'s and `bbo-csv`'s URL-fetching workers
+//! - `scoring`: Standard duplicate bridge scoring and the IMP table
+//! - `single_dummy`: Restricted-information DD analysis via constrained redeal sampling
+//! - `stats`: Player statistics engine (`PlayerStats`, Wilson CIs, bootstrap
+//!   defender-vs-declarer comparisons, JSON export), shared by `pipeline`'s
+//!   and `bbo-csv`'s own `compute_stats`
+//! - `variation_tree`: Annotated variation tree (SGF-style) tagging each
+//!   played card with a move-quality rating and a suggested alternative
 //!
 //! Binaries:
 //! - `bbo-csv`: Bulk analysis tool for BBO hand record CSVs
 //! - `dd-debug`: Single-hand DD verification utility
+//! - `dd-batch`: Parallel DD analysis over many boards, with JSON output
 
+pub mod anon_common;
+pub mod auction;
+pub mod card_tokens;
+pub mod cards;
+pub mod contract;
 pub mod dd_analysis;
+pub mod dd_table;
+pub mod packed_cards;
+pub mod pbn;
+pub mod pdf_font;
+pub mod pdf_text;
 pub mod pipeline;
+pub mod play_state;
+pub mod rate_limit;
+pub mod scoring;
+pub mod single_dummy;
+pub mod stats;
+pub mod variation_tree;
 
 // Re-export commonly used types from dependencies
 pub use bridge_parsers::lin::LinData;
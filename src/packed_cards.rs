@@ -0,0 +1,145 @@
+//! Packed bit representation of cards and hands for the double-dummy inner
+//! loop.
+//!
+//! Each card is a single set bit within a 52-bit value laid out as four
+//! 13-bit suit fields (bits `0..=12` for the trump-suit-agnostic suit index
+//! `0`, `13..=25` for suit index `1`, and so on), with bit position within a
+//! field equal to rank (`0` = Two, `12` = Ace -- the same rank numbering
+//! `dd_analysis::bridge_card_to_solver` already uses). A hand is just the
+//! union of its cards' bits as a `u64` bitmask, so masking out a whole suit
+//! holding is one shift-and-AND instead of a per-card suit comparison.
+
+use bridge_parsers::{Card, Rank, Suit};
+
+/// Number of bits in one suit's field.
+const SUIT_BITS: u32 = 13;
+
+/// 13 set bits, used as the suit-0 field mask before shifting.
+const SUIT_FIELD: u64 = (1u64 << SUIT_BITS) - 1;
+
+/// The packed bit for a single card at `suit` (a `bridge_solver` suit
+/// constant, `0..=3`) and `rank` (`0` = Two, `12` = Ace).
+pub fn card_bit(suit: usize, rank: usize) -> u64 {
+    1u64 << (suit as u32 * SUIT_BITS + rank as u32)
+}
+
+/// Convert a `bridge_parsers::Card` to its packed bit.
+///
+/// Converts from the same `Card` that `dd_analysis::bridge_card_to_solver`
+/// takes (mirroring its suit/rank mapping rather than sharing it, per this
+/// codebase's convention of duplicating small per-module parsing helpers),
+/// not from the `bridge_solver::cards::card_of` index itself -- that index
+/// is opaque outside the `bridge_solver` crate, so there's no public inverse
+/// to recover a suit/rank pair from it. Every call site already has the
+/// original `Card` before it ever reaches `card_of`, so converting from
+/// there keeps this a drop-in addition rather than a new required input.
+pub fn bridge_card_to_packed(card: Card) -> u64 {
+    let suit = match card.suit {
+        Suit::Spades => 0,
+        Suit::Hearts => 1,
+        Suit::Diamonds => 2,
+        Suit::Clubs => 3,
+    };
+
+    let rank = match card.rank {
+        Rank::Ace => 12,
+        Rank::King => 11,
+        Rank::Queen => 10,
+        Rank::Jack => 9,
+        Rank::Ten => 8,
+        Rank::Nine => 7,
+        Rank::Eight => 6,
+        Rank::Seven => 5,
+        Rank::Six => 4,
+        Rank::Five => 3,
+        Rank::Four => 2,
+        Rank::Three => 1,
+        Rank::Two => 0,
+    };
+
+    card_bit(suit, rank)
+}
+
+/// The 13-bit field mask for suit index `suit` (`0..=3`). Returns `0` for
+/// anything outside that range (e.g. a notrump "suit"), so a notrump trump
+/// mask passed to [`trick_winner_packed`] never matches any played card.
+pub fn suit_mask(suit: usize) -> u64 {
+    if suit < 4 {
+        SUIT_FIELD << (suit as u32 * SUIT_BITS)
+    } else {
+        0
+    }
+}
+
+/// The suit field mask that `card`'s single bit falls within, or `0` if
+/// `card` is empty.
+fn card_suit_mask(card: u64) -> u64 {
+    if card == 0 {
+        0
+    } else {
+        SUIT_FIELD << (SUIT_BITS * (card.trailing_zeros() / SUIT_BITS))
+    }
+}
+
+/// Determine the winner of a trick from its packed cards.
+///
+/// `played` holds one packed card per play, in play order starting from
+/// `leader`. `trump_mask` is the trump suit's field mask (from
+/// [`suit_mask`]), or `0` for a notrump contract. Returns the seat
+/// (`(leader + winning position) % 4`) that won, matching
+/// `dd_analysis::determine_trick_winner`'s contract.
+///
+/// A timed comparison against `determine_trick_winner`'s per-card linear
+/// scan would belong in a `benches/` harness (criterion), but this tree has
+/// no `Cargo.toml` to declare that dev-dependency against. The tests below
+/// instead pin down correctness on led-suit and trump-ruff tricks; wiring
+/// up the actual wall-clock benchmark is left to whoever adds the manifest.
+pub fn trick_winner_packed(played: &[u64], trump_mask: u64, leader: usize) -> usize {
+    let led_suit_mask = card_suit_mask(played[0]);
+    let any_trump = played.iter().any(|card| card & trump_mask != 0);
+    let relevant_mask = if any_trump { trump_mask } else { led_suit_mask };
+
+    let mut winner_idx = 0;
+    let mut winning_bit = played[0] & relevant_mask;
+
+    for (i, card) in played.iter().enumerate().skip(1) {
+        let bit = card & relevant_mask;
+        // Highest set bit wins: fewer leading zeros is a higher rank, since
+        // bit position increases with rank within a suit field.
+        if bit != 0 && (winning_bit == 0 || bit.leading_zeros() < winning_bit.leading_zeros()) {
+            winning_bit = bit;
+            winner_idx = i;
+        }
+    }
+
+    (leader + winner_idx) % 4
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follows_led_suit() {
+        // Spades led: SK beats SQ, off-suit discards don't matter.
+        let sk = bridge_card_to_packed(Card::new(Suit::Spades, Rank::King));
+        let sq = bridge_card_to_packed(Card::new(Suit::Spades, Rank::Queen));
+        let hj = bridge_card_to_packed(Card::new(Suit::Hearts, Rank::Jack));
+        let d2 = bridge_card_to_packed(Card::new(Suit::Diamonds, Rank::Two));
+
+        let played = [sq, hj, sk, d2];
+        assert_eq!(trick_winner_packed(&played, suit_mask(4), 1), (1 + 2) % 4);
+    }
+
+    #[test]
+    fn trump_beats_led_suit() {
+        // Hearts led, clubs are trump: a low trump beats a high heart.
+        let ha = bridge_card_to_packed(Card::new(Suit::Hearts, Rank::Ace));
+        let c2 = bridge_card_to_packed(Card::new(Suit::Clubs, Rank::Two));
+        let h5 = bridge_card_to_packed(Card::new(Suit::Hearts, Rank::Five));
+        let h9 = bridge_card_to_packed(Card::new(Suit::Hearts, Rank::Nine));
+
+        let played = [ha, c2, h5, h9];
+        assert_eq!(trick_winner_packed(&played, suit_mask(3), 0), (0 + 1) % 4);
+    }
+}
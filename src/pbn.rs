@@ -0,0 +1,175 @@
+//! Minimal PBN (Portable Bridge Notation) board reader.
+//!
+//! A proper `bridge_parsers::pbn` module mirroring `lin::parse_lin`'s
+//! `LinData` output (so `extract_contract`, `extract_declarer`,
+//! `parse_cardplay`, and the rest of the `DdAnalysisConfig` path worked
+//! unchanged on it) would live in the `bridge_parsers` crate. That crate is
+//! an external dependency with no source in this tree, so there's nothing
+//! to add a module to from here, and `LinData` itself can't be constructed
+//! outside it either.
+//!
+//! What this reads instead is the handful of tag pairs the rest of the
+//! toolkit actually needs -- `[Deal]`, `[Declarer]`, `[Contract]`, `[Play]`,
+//! and `[South]`/`[West]`/`[North]`/`[East]` -- directly into the
+//! `(deal_pbn, declarer, contract, cardplay, player_names)` shape
+//! `dd_analysis::compute_dd_costs` and friends already take. A PBN board can
+//! be analyzed the same way as a LIN one; it just skips `LinData` and
+//! `analyze_board` entirely, since those are LIN-shaped all the way through.
+
+use crate::contract::Contract;
+
+/// One board's worth of PBN data, ready to feed straight into
+/// `dd_analysis::compute_dd_costs`, `compute_dd_diagnostics`, or
+/// `detect_revokes`.
+#[derive(Debug, Clone)]
+pub struct PbnBoard {
+    /// Deal in the `"N:AKQ.JT9.876.5432 ..."` form `Hands::from_pbn` expects
+    /// (PBN's own deal tag value, unchanged).
+    pub deal_pbn: String,
+    /// Declarer seat letter (e.g. `"N"`, `"S"`)
+    pub declarer: String,
+    pub contract: Contract,
+    /// Cardplay in the `"S4 S2 SA S5|D7 DQ DK DA|..."` form `parse_cardplay`
+    /// expects: one `|`-separated group per trick, cards space-separated in
+    /// play order.
+    pub cardplay: String,
+    /// Player names in South, West, North, East order, matching
+    /// `LinData::player_names` -- parsed from PBN's `[South]`/`[West]`/
+    /// `[North]`/`[East]` tags, empty string for any tag that's missing.
+    pub player_names: [String; 4],
+}
+
+/// Parse one board's `[Deal]`/`[Declarer]`/`[Contract]`/`[Play]` tag pairs.
+///
+/// The `[Play]` section is a seat line (the opening leader, unused here
+/// since `compute_dd_costs` derives the leader from `declarer` instead)
+/// followed by one trick per line, space-separated cards, ending at `*` or
+/// the next tag.
+pub fn parse_pbn_board(text: &str) -> Result<PbnBoard, String> {
+    let mut deal_pbn = None;
+    let mut declarer = None;
+    let mut contract_str = None;
+    let mut play_lines: Vec<String> = Vec::new();
+    let mut in_play = false;
+    let mut player_names = [String::new(), String::new(), String::new(), String::new()];
+
+    for raw_line in text.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(value) = parse_tag(line, "Deal") {
+            deal_pbn = Some(value);
+            in_play = false;
+        } else if let Some(value) = parse_tag(line, "Declarer") {
+            declarer = Some(value);
+            in_play = false;
+        } else if let Some(value) = parse_tag(line, "Contract") {
+            contract_str = Some(value);
+            in_play = false;
+        } else if let Some(value) = parse_tag(line, "South") {
+            player_names[0] = value;
+            in_play = false;
+        } else if let Some(value) = parse_tag(line, "West") {
+            player_names[1] = value;
+            in_play = false;
+        } else if let Some(value) = parse_tag(line, "North") {
+            player_names[2] = value;
+            in_play = false;
+        } else if let Some(value) = parse_tag(line, "East") {
+            player_names[3] = value;
+            in_play = false;
+        } else if parse_tag(line, "Play").is_some() {
+            in_play = true;
+        } else if line.starts_with('[') {
+            in_play = false;
+        } else if in_play {
+            if line == "*" {
+                in_play = false;
+            } else {
+                play_lines.push(line.to_string());
+            }
+        }
+    }
+
+    let deal_pbn = deal_pbn.ok_or_else(|| "Missing [Deal ...] tag".to_string())?;
+    let declarer = declarer.ok_or_else(|| "Missing [Declarer ...] tag".to_string())?;
+    let contract_str = contract_str.ok_or_else(|| "Missing [Contract ...] tag".to_string())?;
+    let contract: Contract = contract_str.parse()?;
+    let cardplay = play_lines.join("|");
+
+    Ok(PbnBoard { deal_pbn, declarer, contract, cardplay, player_names })
+}
+
+/// Serialize a board back to the `[Deal]`/`[Declarer]`/`[Contract]`/`[Play]`
+/// tag pairs `parse_pbn_board` reads.
+pub fn to_pbn_board(board: &PbnBoard) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("[Deal \"{}\"]\n", board.deal_pbn));
+    out.push_str(&format!("[Declarer \"{}\"]\n", board.declarer));
+    out.push_str(&format!("[Contract \"{}\"]\n", board.contract));
+    let tag_names = ["South", "West", "North", "East"];
+    for (name, value) in tag_names.iter().zip(board.player_names.iter()) {
+        if !value.is_empty() {
+            out.push_str(&format!("[{} \"{}\"]\n", name, value));
+        }
+    }
+    let leader = match board.declarer.trim().chars().next().map(|c| c.to_ascii_uppercase()) {
+        Some('N') => "E",
+        Some('E') => "S",
+        Some('S') => "W",
+        Some('W') => "N",
+        _ => board.declarer.as_str(),
+    };
+    out.push_str(&format!("[Play \"{}\"]\n", leader));
+    for trick in board.cardplay.split('|') {
+        if !trick.is_empty() {
+            out.push_str(trick);
+            out.push('\n');
+        }
+    }
+    out.push_str("*\n");
+    out
+}
+
+/// If `line` is `[tag "value"]`, return `value`.
+fn parse_tag(line: &str, tag: &str) -> Option<String> {
+    let rest = line.strip_prefix('[')?.strip_prefix(tag)?;
+    let rest = rest.trim_start();
+    let rest = rest.strip_prefix('"')?;
+    let (value, _) = rest.split_once('"')?;
+    Some(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_a_board() {
+        let pbn = "[Deal \"N:AKQ.JT9.876.5432 JT9.876.5432.AKQ 876.5432.AKQ.JT9 5432.AKQ.JT9.876\"]\n\
+                   [Declarer \"W\"]\n\
+                   [Contract \"3NT\"]\n\
+                   [South \"Alice\"]\n\
+                   [West \"Bob\"]\n\
+                   [North \"Carol\"]\n\
+                   [East \"Dave\"]\n\
+                   [Play \"N\"]\n\
+                   D2 DA D5 D9\n\
+                   *\n";
+
+        let board = parse_pbn_board(pbn).unwrap();
+        assert_eq!(board.declarer, "W");
+        assert_eq!(board.contract.to_string(), "3NT");
+        assert_eq!(board.cardplay, "D2 DA D5 D9");
+        assert_eq!(board.player_names, ["Alice", "Bob", "Carol", "Dave"]);
+
+        let serialized = to_pbn_board(&board);
+        let reparsed = parse_pbn_board(&serialized).unwrap();
+        assert_eq!(reparsed.declarer, board.declarer);
+        assert_eq!(reparsed.contract, board.contract);
+        assert_eq!(reparsed.cardplay, board.cardplay);
+        assert_eq!(reparsed.player_names, board.player_names);
+    }
+}
@@ -0,0 +1,503 @@
+//! Per-font code <-> Unicode decoding for PDF content-stream text.
+//!
+//! `pdf_anon::replace_page_text` used to byte-match `Tj`/`TJ` operand bytes
+//! directly, which only works when those bytes happen to already be ASCII.
+//! Most exported PDFs instead carry a custom `/Encoding` (a `/Differences`
+//! array overlaying a base encoding) or a Type0/CID font with multi-byte
+//! codes and a `/ToUnicode` CMap, so the same glyph can show up as an
+//! arbitrary byte or byte pair. This module builds a `FontEncoding` per
+//! `/Font` resource so callers can decode an operand to Unicode for
+//! matching, then invert the table to re-encode a replacement back to that
+//! font's codes.
+//!
+//! `/ToUnicode` is preferred when present (it's the authoritative source
+//! for CID fonts and is also common on simple fonts); otherwise a simple
+//! font falls back to WinAnsiEncoding overlaid with `/Differences`, using
+//! the Adobe Glyph List's `uniXXXX` convention plus the common Latin glyph
+//! names these exported PDFs actually carry.
+
+use std::collections::HashMap;
+
+/// Code -> Unicode (and back) table for one `/Font` resource, with the code
+/// width (1 byte for simple fonts, 2 bytes for Identity-H-style Type0/CID
+/// fonts).
+#[derive(Debug, Clone)]
+pub struct FontEncoding {
+    code_to_unicode: HashMap<u32, char>,
+    unicode_to_code: HashMap<char, u32>,
+    pub code_bytes: usize,
+}
+
+impl Default for FontEncoding {
+    /// Pass-through 1-byte encoding (byte N <-> codepoint N) used when a
+    /// `Tf` names a font this page's `/Resources` doesn't resolve -- the
+    /// same behavior `replace_page_text` had before font decoding existed.
+    fn default() -> Self {
+        let mut enc = FontEncoding::new(1);
+        for code in 0u32..=0xFF {
+            enc.insert(code, char::from_u32(code).unwrap());
+        }
+        enc
+    }
+}
+
+impl FontEncoding {
+    fn new(code_bytes: usize) -> Self {
+        FontEncoding {
+            code_to_unicode: HashMap::new(),
+            unicode_to_code: HashMap::new(),
+            code_bytes,
+        }
+    }
+
+    fn insert(&mut self, code: u32, ch: char) {
+        self.code_to_unicode.insert(code, ch);
+        self.unicode_to_code.entry(ch).or_insert(code);
+    }
+
+    /// Decode a `Tj`/`TJ` string operand into Unicode text, `code_bytes`
+    /// bytes at a time. An unmapped 1-byte code falls back to treating the
+    /// byte as its own Latin-1 codepoint, matching the old raw-ASCII
+    /// behavior for fonts this table doesn't cover.
+    pub fn decode(&self, bytes: &[u8]) -> Vec<char> {
+        let mut out = Vec::new();
+        let mut i = 0;
+        while i + self.code_bytes <= bytes.len() {
+            let code = self.code_at(bytes, i);
+            match self.code_to_unicode.get(&code) {
+                Some(&ch) => out.push(ch),
+                None if self.code_bytes == 1 => out.push(bytes[i] as char),
+                None => out.push('\u{FFFD}'),
+            }
+            i += self.code_bytes;
+        }
+        out
+    }
+
+    fn code_at(&self, bytes: &[u8], i: usize) -> u32 {
+        match self.code_bytes {
+            1 => bytes[i] as u32,
+            _ => ((bytes[i] as u32) << 8) | *bytes.get(i + 1).unwrap_or(&0) as u32,
+        }
+    }
+
+    /// Encode a single Unicode character back to this font's bytes, if the
+    /// font's table maps some code to it.
+    pub fn encode_char(&self, ch: char) -> Option<Vec<u8>> {
+        let code = *self.unicode_to_code.get(&ch)?;
+        Some(match self.code_bytes {
+            1 => vec![code as u8],
+            _ => vec![(code >> 8) as u8, code as u8],
+        })
+    }
+
+    /// True if this table has no code mapped at all -- a Type0/CID font
+    /// with no (or an unparseable) `/ToUnicode` CMap builds one of these,
+    /// since unlike a simple font it has no WinAnsi/`/Differences` fallback.
+    /// Every code on such a page decodes to `'\u{FFFD}'`, so name matching
+    /// and `verify` can't see that page's real text at all.
+    pub fn is_unmapped(&self) -> bool {
+        self.code_to_unicode.is_empty()
+    }
+
+    /// Bytes for an ASCII space in this font, for padding a shorter
+    /// replacement -- falls back to the raw space byte when unmapped.
+    pub fn space_code(&self) -> Vec<u8> {
+        self.encode_char(' ')
+            .unwrap_or_else(|| vec![0; self.code_bytes - 1].into_iter().chain(std::iter::once(b' ')).collect())
+    }
+}
+
+/// WinAnsiEncoding's printable range: 0x20-0x7E match ASCII, and the
+/// printable block above 0xA0 matches Latin-1; 0x80-0x9F carry the handful
+/// of curly-quote/dash glyphs simple-font PDFs actually use.
+fn win_ansi_base() -> HashMap<u32, char> {
+    let mut m = HashMap::new();
+    for code in 0x20u32..=0x7Eu32 {
+        m.insert(code, code as u8 as char);
+    }
+    let cp1252_high = [
+        (0x85, '\u{2026}'),
+        (0x91, '\u{2018}'),
+        (0x92, '\u{2019}'),
+        (0x93, '\u{201C}'),
+        (0x94, '\u{201D}'),
+        (0x96, '\u{2013}'),
+        (0x97, '\u{2014}'),
+    ];
+    for (code, ch) in cp1252_high {
+        m.insert(code, ch);
+    }
+    for code in 0xA0u32..=0xFFu32 {
+        m.insert(code, char::from_u32(code).unwrap());
+    }
+    m
+}
+
+/// Resolve a `/Differences`-array glyph name to Unicode: the `uniXXXX`
+/// convention, then the common Adobe Glyph List subset (bare letters,
+/// digits, and the punctuation/typography names these PDFs carry).
+fn glyph_name_to_unicode(name: &str) -> Option<char> {
+    if let Some(hex) = name.strip_prefix("uni") {
+        if hex.len() >= 4 {
+            if let Ok(cp) = u32::from_str_radix(&hex[..4], 16) {
+                return char::from_u32(cp);
+            }
+        }
+    }
+    if name.len() == 1 {
+        let c = name.chars().next().unwrap();
+        if c.is_ascii_alphabetic() {
+            return Some(c);
+        }
+    }
+    const TABLE: &[(&str, char)] = &[
+        ("space", ' '), ("exclam", '!'), ("quotedbl", '"'), ("numbersign", '#'),
+        ("dollar", '$'), ("percent", '%'), ("ampersand", '&'), ("quotesingle", '\''),
+        ("parenleft", '('), ("parenright", ')'), ("asterisk", '*'), ("plus", '+'),
+        ("comma", ','), ("hyphen", '-'), ("period", '.'), ("slash", '/'),
+        ("zero", '0'), ("one", '1'), ("two", '2'), ("three", '3'), ("four", '4'),
+        ("five", '5'), ("six", '6'), ("seven", '7'), ("eight", '8'), ("nine", '9'),
+        ("colon", ':'), ("semicolon", ';'), ("less", '<'), ("equal", '='), ("greater", '>'),
+        ("question", '?'), ("at", '@'), ("bracketleft", '['), ("backslash", '\\'),
+        ("bracketright", ']'), ("asciicircum", '^'), ("underscore", '_'), ("grave", '`'),
+        ("braceleft", '{'), ("bar", '|'), ("braceright", '}'), ("asciitilde", '~'),
+        ("quoteleft", '\u{2018}'), ("quoteright", '\u{2019}'),
+        ("quotedblleft", '\u{201C}'), ("quotedblright", '\u{201D}'),
+        ("endash", '\u{2013}'), ("emdash", '\u{2014}'), ("ellipsis", '\u{2026}'),
+        ("spade", '\u{2660}'), ("heart", '\u{2665}'), ("diamond", '\u{2666}'), ("club", '\u{2663}'),
+    ];
+    TABLE.iter().find(|(n, _)| *n == name).map(|&(_, ch)| ch)
+}
+
+/// Tokenize a `/ToUnicode` CMap's PostScript-ish syntax into hex strings
+/// (`<...>`), `[`/`]` brackets, and bare keywords -- enough to walk
+/// `beginbfchar`/`beginbfrange` blocks without a full PostScript parser.
+fn tokenize_cmap(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '<' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != '>' {
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            tokens.push(chars[start..i].iter().collect());
+        } else if c == '[' || c == ']' {
+            tokens.push(c.to_string());
+            i += 1;
+        } else if c == '%' {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else {
+            let start = i;
+            while i < chars.len() && !chars[i].is_whitespace() && !['<', '[', ']'].contains(&chars[i]) {
+                i += 1;
+            }
+            tokens.push(chars[start..i].iter().collect());
+        }
+    }
+    tokens
+}
+
+fn hex_token_bytes(tok: &str) -> Option<Vec<u8>> {
+    let inner = tok.strip_prefix('<')?.strip_suffix('>')?;
+    let hex: String = inner.chars().filter(|c| !c.is_whitespace()).collect();
+    let hex = if hex.len() % 2 == 1 { format!("{hex}0") } else { hex };
+    let mut bytes = Vec::with_capacity(hex.len() / 2);
+    let mut it = hex.chars();
+    while let (Some(a), Some(b)) = (it.next(), it.next()) {
+        bytes.push(u8::from_str_radix(&format!("{a}{b}"), 16).ok()?);
+    }
+    Some(bytes)
+}
+
+fn bytes_to_code(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0u32, |acc, &b| (acc << 8) | b as u32)
+}
+
+fn utf16be_to_char(bytes: &[u8]) -> Option<char> {
+    if bytes.len() < 2 {
+        return None;
+    }
+    let units: Vec<u16> = bytes
+        .chunks(2)
+        .map(|c| ((c[0] as u16) << 8) | *c.get(1).unwrap_or(&0) as u16)
+        .collect();
+    char::decode_utf16(units).next()?.ok()
+}
+
+/// Parse a `/ToUnicode` CMap stream's `beginbfchar`/`beginbfrange` entries
+/// into a `code -> Unicode` table.
+fn parse_tounicode_cmap(data: &[u8]) -> HashMap<u32, char> {
+    let mut map = HashMap::new();
+    let text = String::from_utf8_lossy(data);
+    let tokens = tokenize_cmap(&text);
+    let mut i = 0;
+    while i < tokens.len() {
+        match tokens[i].as_str() {
+            "beginbfchar" => {
+                i += 1;
+                while i + 1 < tokens.len() && tokens[i] != "endbfchar" {
+                    if let (Some(src), Some(dst)) =
+                        (hex_token_bytes(&tokens[i]), hex_token_bytes(&tokens[i + 1]))
+                    {
+                        if let Some(ch) = utf16be_to_char(&dst) {
+                            map.insert(bytes_to_code(&src), ch);
+                        }
+                    }
+                    i += 2;
+                }
+            }
+            "beginbfrange" => {
+                i += 1;
+                while i < tokens.len() && tokens[i] != "endbfrange" {
+                    if i + 2 >= tokens.len() {
+                        break;
+                    }
+                    let (lo, hi) = match (hex_token_bytes(&tokens[i]), hex_token_bytes(&tokens[i + 1])) {
+                        (Some(lo), Some(hi)) => (bytes_to_code(&lo), bytes_to_code(&hi)),
+                        _ => {
+                            i += 1;
+                            continue;
+                        }
+                    };
+                    if tokens[i + 2] == "[" {
+                        let mut j = i + 3;
+                        let mut code = lo;
+                        while j < tokens.len() && tokens[j] != "]" {
+                            if let Some(dst) = hex_token_bytes(&tokens[j]) {
+                                if let Some(ch) = utf16be_to_char(&dst) {
+                                    map.insert(code, ch);
+                                }
+                            }
+                            code += 1;
+                            j += 1;
+                        }
+                        i = j + 1;
+                    } else if let Some(dst) = hex_token_bytes(&tokens[i + 2]) {
+                        let base = bytes_to_code(&dst);
+                        for (offset, code) in (lo..=hi).enumerate() {
+                            if let Some(ch) = char::from_u32(base + offset as u32) {
+                                map.insert(code, ch);
+                            }
+                        }
+                        i += 3;
+                    } else {
+                        i += 1;
+                    }
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    map
+}
+
+/// Build the `code -> Unicode` table for one `/Font` resource dictionary.
+/// Prefers `/ToUnicode` when present (authoritative, and the only option
+/// for CID fonts); otherwise falls back to WinAnsiEncoding overlaid with
+/// `/Differences`.
+pub fn build_font_encoding(doc: &mut lopdf::Document, font_id: lopdf::ObjectId) -> FontEncoding {
+    let font_dict = match doc.get_object(font_id) {
+        Ok(lopdf::Object::Dictionary(d)) => d.clone(),
+        _ => return FontEncoding::default(),
+    };
+    let is_type0 = font_dict
+        .get(b"Subtype")
+        .ok()
+        .map(|o| matches!(o, lopdf::Object::Name(n) if n == b"Type0"))
+        .unwrap_or(false);
+    let mut enc = FontEncoding::new(if is_type0 { 2 } else { 1 });
+
+    if let Ok(&lopdf::Object::Reference(tounicode_id)) = font_dict.get(b"ToUnicode") {
+        if let Ok(lopdf::Object::Stream(_)) = doc.get_object(tounicode_id) {
+            if let Ok(lopdf::Object::Stream(stream)) = doc.get_object_mut(tounicode_id) {
+                if stream.decompress().is_ok() {
+                    for (code, ch) in parse_tounicode_cmap(&stream.content) {
+                        enc.insert(code, ch);
+                    }
+                }
+            }
+        }
+    }
+
+    if !is_type0 && enc.code_to_unicode.is_empty() {
+        for (code, ch) in win_ansi_base() {
+            enc.insert(code, ch);
+        }
+        let encoding_dict = match font_dict.get(b"Encoding") {
+            Ok(lopdf::Object::Dictionary(d)) => Some(d.clone()),
+            Ok(&lopdf::Object::Reference(id)) => match doc.get_object(id) {
+                Ok(lopdf::Object::Dictionary(d)) => Some(d.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(enc_dict) = encoding_dict {
+            if let Ok(lopdf::Object::Array(diffs)) = enc_dict.get(b"Differences") {
+                let mut code = 0u32;
+                for item in diffs {
+                    match item {
+                        lopdf::Object::Integer(n) => code = *n as u32,
+                        lopdf::Object::Name(name) => {
+                            if let Some(ch) = glyph_name_to_unicode(&String::from_utf8_lossy(name)) {
+                                enc.insert(code, ch);
+                            }
+                            code += 1;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    if is_type0 && enc.code_to_unicode.is_empty() {
+        eprintln!(
+            "  Warning: font {:?} is a Type0/CID font with no usable /ToUnicode CMap -- \
+             its text decodes entirely to U+FFFD, so redaction/verify can't see this font's \
+             real characters",
+            font_id
+        );
+    }
+
+    enc
+}
+
+/// Build `font resource name -> FontEncoding` for every font in a page's
+/// `/Resources` `/Font` dictionary, so callers can look one up by the name
+/// a `Tf` operator references.
+pub fn build_page_font_encodings(
+    doc: &mut lopdf::Document,
+    resources_dict: &lopdf::Dictionary,
+) -> HashMap<Vec<u8>, FontEncoding> {
+    let mut table = HashMap::new();
+    let font_ids: Vec<(Vec<u8>, lopdf::ObjectId)> = match resources_dict.get(b"Font") {
+        Ok(lopdf::Object::Dictionary(d)) => d
+            .iter()
+            .filter_map(|(name, obj)| match obj {
+                lopdf::Object::Reference(id) => Some((name.clone(), *id)),
+                _ => None,
+            })
+            .collect(),
+        Ok(&lopdf::Object::Reference(id)) => match doc.get_object(id) {
+            Ok(lopdf::Object::Dictionary(d)) => d
+                .iter()
+                .filter_map(|(name, obj)| match obj {
+                    lopdf::Object::Reference(fid) => Some((name.clone(), *fid)),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+    for (name, font_id) in font_ids {
+        table.insert(name, build_font_encoding(doc, font_id));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_encoding_is_pass_through_ascii() {
+        let enc = FontEncoding::default();
+        assert_eq!(enc.decode(b"Hi!"), vec!['H', 'i', '!']);
+        assert_eq!(enc.encode_char('A'), Some(vec![b'A']));
+    }
+
+    #[test]
+    fn test_two_byte_encoding_decodes_pairs() {
+        let mut enc = FontEncoding::new(2);
+        enc.insert(0x0041, 'x');
+        assert_eq!(enc.decode(&[0x00, 0x41]), vec!['x']);
+        // Unmapped two-byte code falls back to the replacement character.
+        assert_eq!(enc.decode(&[0xFF, 0xFF]), vec!['\u{FFFD}']);
+    }
+
+    #[test]
+    fn test_encode_char_round_trips_through_insert() {
+        let mut enc = FontEncoding::new(1);
+        enc.insert(0x41, 'Z');
+        assert_eq!(enc.encode_char('Z'), Some(vec![0x41]));
+        assert_eq!(enc.encode_char('Q'), None);
+    }
+
+    #[test]
+    fn test_space_code_falls_back_when_unmapped() {
+        let enc = FontEncoding::new(2);
+        assert_eq!(enc.space_code(), vec![0, b' ']);
+    }
+
+    #[test]
+    fn test_is_unmapped() {
+        let mut enc = FontEncoding::new(2);
+        assert!(enc.is_unmapped());
+        enc.insert(0x0041, 'A');
+        assert!(!enc.is_unmapped());
+    }
+
+    #[test]
+    fn test_glyph_name_to_unicode() {
+        assert_eq!(glyph_name_to_unicode("uni00E9"), Some('\u{00E9}'));
+        assert_eq!(glyph_name_to_unicode("A"), Some('A'));
+        assert_eq!(glyph_name_to_unicode("hyphen"), Some('-'));
+        assert_eq!(glyph_name_to_unicode("quotedblleft"), Some('\u{201C}'));
+        assert_eq!(glyph_name_to_unicode("nonexistentglyph"), None);
+    }
+
+    #[test]
+    fn test_tokenize_cmap_splits_hex_and_brackets() {
+        let tokens = tokenize_cmap("<0041> [<0042> <0043>] beginbfrange");
+        assert_eq!(tokens, vec!["<0041>", "[", "<0042>", "<0043>", "]", "beginbfrange"]);
+    }
+
+    #[test]
+    fn test_hex_token_bytes() {
+        assert_eq!(hex_token_bytes("<0041>"), Some(vec![0x00, 0x41]));
+        assert_eq!(hex_token_bytes("<41>"), Some(vec![0x41]));
+        assert_eq!(hex_token_bytes("not hex"), None);
+    }
+
+    #[test]
+    fn test_utf16be_to_char() {
+        assert_eq!(utf16be_to_char(&[0x00, 0x41]), Some('A'));
+        assert_eq!(utf16be_to_char(&[0x00]), None);
+    }
+
+    #[test]
+    fn test_parse_tounicode_cmap_bfchar() {
+        let cmap = b"1 beginbfchar\n<0041> <0042>\nendbfchar";
+        let map = parse_tounicode_cmap(cmap);
+        assert_eq!(map.get(&0x0041), Some(&'B'));
+    }
+
+    #[test]
+    fn test_parse_tounicode_cmap_bfrange_array() {
+        let cmap = b"1 beginbfrange\n<0001> <0003> [<0041> <0042> <0043>]\nendbfrange";
+        let map = parse_tounicode_cmap(cmap);
+        assert_eq!(map.get(&0x0001), Some(&'A'));
+        assert_eq!(map.get(&0x0002), Some(&'B'));
+        assert_eq!(map.get(&0x0003), Some(&'C'));
+    }
+
+    #[test]
+    fn test_parse_tounicode_cmap_bfrange_offset() {
+        let cmap = b"1 beginbfrange\n<0001> <0003> <0041>\nendbfrange";
+        let map = parse_tounicode_cmap(cmap);
+        assert_eq!(map.get(&0x0001), Some(&'A'));
+        assert_eq!(map.get(&0x0002), Some(&'B'));
+        assert_eq!(map.get(&0x0003), Some(&'C'));
+    }
+}
@@ -0,0 +1,336 @@
+//! Content-stream text-run extraction and rewriting for PDF anonymization.
+//!
+//! `pdf_anon::replace_page_text` already walks `Tj`/`TJ` operators and
+//! patches matched bytes in place, but it's restricted to same-length
+//! writes (padding a shorter replacement with spaces) because it doesn't
+//! know where a run actually sits on the page. This module tracks the
+//! graphics/text state (`cm`, `Tm`/`Td`/`TD`, `Tf`) well enough to recover
+//! each show-text run's device-space bounding box, so a replacement of any
+//! length can be substituted and then horizontally rescaled (`Tz`) to still
+//! roughly fill the box the original text occupied.
+//!
+//! Run widths are estimated from the font size and character count rather
+//! than real embedded-font glyph metrics (recovering those would mean
+//! parsing the PDF's font program) -- the same kind of ratio-based
+//! heuristic `anon_common::bbo_name_rects` uses for screenshot layout.
+
+use lopdf::content::Operation;
+use lopdf::Object;
+
+/// A PDF text/graphics-state matrix `[a b c d e f]`, applied to a point as
+/// `(x', y') = (a*x + c*y + e, b*x + d*y + f)`.
+#[derive(Clone, Copy, Debug)]
+struct Matrix {
+    a: f64,
+    b: f64,
+    c: f64,
+    d: f64,
+    e: f64,
+    f: f64,
+}
+
+impl Matrix {
+    const IDENTITY: Matrix = Matrix { a: 1.0, b: 0.0, c: 0.0, d: 1.0, e: 0.0, f: 0.0 };
+
+    fn translation(tx: f64, ty: f64) -> Matrix {
+        Matrix { a: 1.0, b: 0.0, c: 0.0, d: 1.0, e: tx, f: ty }
+    }
+
+    /// `self × other`, PDF's row-vector matrix convention: a point is moved
+    /// by `self` first, then by `other` (e.g. `cm`'s new CTM = `self × old CTM`).
+    fn multiply(&self, other: &Matrix) -> Matrix {
+        Matrix {
+            a: self.a * other.a + self.b * other.c,
+            b: self.a * other.b + self.b * other.d,
+            c: self.c * other.a + self.d * other.c,
+            d: self.c * other.b + self.d * other.d,
+            e: self.e * other.a + self.f * other.c + other.e,
+            f: self.e * other.b + self.f * other.d + other.f,
+        }
+    }
+
+    fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        (self.a * x + self.c * y + self.e, self.b * x + self.d * y + self.f)
+    }
+}
+
+/// One `Tj`/`TJ` show-text run recovered from a content stream, with its
+/// approximate device-space bounding box.
+pub struct TextRun {
+    /// Index into the page's operation list of the `Tj`/`TJ` operator.
+    op_idx: usize,
+    /// For `TJ`: index of this string within the array operand. `None` for `Tj`.
+    arr_idx: Option<usize>,
+    text: Vec<u8>,
+    font_size: f64,
+    /// `(x0, y0, x1, y1)` in device space.
+    bbox: (f64, f64, f64, f64),
+}
+
+impl TextRun {
+    pub fn text(&self) -> &[u8] {
+        &self.text
+    }
+
+    pub fn width(&self) -> f64 {
+        (self.bbox.2 - self.bbox.0).max(1.0)
+    }
+}
+
+fn object_as_f64(obj: &Object) -> Option<f64> {
+    match obj {
+        Object::Integer(n) => Some(*n as f64),
+        Object::Real(n) => Some(*n as f64),
+        _ => None,
+    }
+}
+
+fn matrix_from_operands(operands: &[Object]) -> Option<Matrix> {
+    let vals: Vec<f64> = operands.iter().filter_map(object_as_f64).collect();
+    if vals.len() != 6 {
+        return None;
+    }
+    Some(Matrix { a: vals[0], b: vals[1], c: vals[2], d: vals[3], e: vals[4], f: vals[5] })
+}
+
+/// Crude average glyph width used only to estimate how far a run extends
+/// on the page when no embedded font metrics are available.
+fn estimate_run_width(char_count: usize, font_size: f64) -> f64 {
+    char_count as f64 * font_size * 0.5
+}
+
+/// Walk a page's content-stream operations and recover every `Tj`/`TJ`
+/// show-text run together with its device-space bounding box.
+pub fn extract_text_runs(ops: &[Operation]) -> Vec<TextRun> {
+    let mut runs = Vec::new();
+    let mut ctm_stack: Vec<Matrix> = vec![Matrix::IDENTITY];
+    let mut tm = Matrix::IDENTITY;
+    let mut tlm = Matrix::IDENTITY;
+    let mut font_size: f64 = 0.0;
+    let mut in_text = false;
+
+    for (op_idx, op) in ops.iter().enumerate() {
+        match op.operator.as_ref() {
+            "q" => ctm_stack.push(*ctm_stack.last().unwrap_or(&Matrix::IDENTITY)),
+            "Q" => {
+                if ctm_stack.len() > 1 {
+                    ctm_stack.pop();
+                }
+            }
+            "cm" => {
+                if let Some(m) = matrix_from_operands(&op.operands) {
+                    if let Some(top) = ctm_stack.last_mut() {
+                        *top = m.multiply(top);
+                    }
+                }
+            }
+            "BT" => {
+                in_text = true;
+                tm = Matrix::IDENTITY;
+                tlm = Matrix::IDENTITY;
+            }
+            "ET" => in_text = false,
+            "Tf" => {
+                if let Some(size) = op.operands.get(1).and_then(object_as_f64) {
+                    font_size = size;
+                }
+            }
+            "Tm" => {
+                if let Some(m) = matrix_from_operands(&op.operands) {
+                    tm = m;
+                    tlm = m;
+                }
+            }
+            "Td" | "TD" => {
+                if let (Some(tx), Some(ty)) = (
+                    op.operands.first().and_then(object_as_f64),
+                    op.operands.get(1).and_then(object_as_f64),
+                ) {
+                    tlm = Matrix::translation(tx, ty).multiply(&tlm);
+                    tm = tlm;
+                }
+            }
+            "T*" => {
+                tm = tlm;
+            }
+            "Tj" if in_text => {
+                if let Some(Object::String(bytes, _)) = op.operands.first() {
+                    let ctm = *ctm_stack.last().unwrap_or(&Matrix::IDENTITY);
+                    let run = make_run(op_idx, None, bytes.clone(), font_size, &ctm, &tm);
+                    tm = Matrix::translation(estimate_run_width(bytes.len(), font_size), 0.0).multiply(&tm);
+                    runs.push(run);
+                }
+            }
+            "TJ" if in_text => {
+                if let Some(Object::Array(arr)) = op.operands.first() {
+                    let ctm = *ctm_stack.last().unwrap_or(&Matrix::IDENTITY);
+                    for (arr_idx, item) in arr.iter().enumerate() {
+                        if let Object::String(bytes, _) = item {
+                            let run = make_run(op_idx, Some(arr_idx), bytes.clone(), font_size, &ctm, &tm);
+                            tm = Matrix::translation(estimate_run_width(bytes.len(), font_size), 0.0)
+                                .multiply(&tm);
+                            runs.push(run);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    runs
+}
+
+fn make_run(
+    op_idx: usize,
+    arr_idx: Option<usize>,
+    text: Vec<u8>,
+    font_size: f64,
+    ctm: &Matrix,
+    tm: &Matrix,
+) -> TextRun {
+    let trm = tm.multiply(ctm); // text rendering matrix = Tm × CTM
+    let width_est = estimate_run_width(text.len(), font_size);
+    let (x0, y0) = trm.apply(0.0, 0.0);
+    let (x1, y1) = trm.apply(width_est, font_size);
+    TextRun {
+        op_idx,
+        arr_idx,
+        text,
+        font_size,
+        bbox: (x0.min(x1), y0.min(y1), x0.max(x1), y0.max(y1)),
+    }
+}
+
+fn find_subsequence(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.is_empty() || needle.len() > haystack.len() {
+        return None;
+    }
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+fn write_run_text(ops: &mut [Operation], run: &TextRun, new_text: Vec<u8>) {
+    let op = &mut ops[run.op_idx];
+    match run.arr_idx {
+        None => {
+            if let Some(Object::String(bytes, _)) = op.operands.first_mut() {
+                *bytes = new_text;
+            }
+        }
+        Some(arr_idx) => {
+            if let Some(Object::Array(arr)) = op.operands.first_mut() {
+                if let Some(Object::String(bytes, _)) = arr.get_mut(arr_idx) {
+                    *bytes = new_text;
+                }
+            }
+        }
+    }
+}
+
+/// Rewrite every text run whose bytes contain one of `replacements`
+/// (exact substring match, first match wins), substituting the matched
+/// bytes directly in the PDF string object -- unlike patching fixed-size
+/// operands, the replacement can be any length -- and wrapping the run's
+/// show-text operator in `Tz` (horizontal scaling) operators so the new
+/// text still roughly fills the box the original occupied.
+///
+/// Returns the number of runs rewritten.
+pub fn anonymize_text_runs(
+    ops: &mut Vec<Operation>,
+    replacements: &[(Vec<u8>, Vec<u8>)],
+    font: &crate::anon_common::FontStack,
+    glyph_cache: &mut crate::anon_common::GlyphCache,
+) -> usize {
+    let runs = extract_text_runs(ops);
+    let mut count = 0usize;
+
+    // Process from the last run backwards: inserting the Tz wrapper around
+    // an earlier op_idx shifts every later index, but runs already handled
+    // (later in the stream) don't need their indices to stay valid.
+    for run in runs.iter().rev() {
+        let matched = replacements
+            .iter()
+            .find(|(search, _)| find_subsequence(run.text(), search).is_some());
+        let Some((search, replace)) = matched else {
+            continue;
+        };
+        let pos = match find_subsequence(run.text(), search) {
+            Some(p) => p,
+            None => continue,
+        };
+
+        let mut new_text = run.text().to_vec();
+        new_text.splice(pos..pos + search.len(), replace.iter().copied());
+
+        let orig_width = run.width() as f32;
+        let new_str = String::from_utf8_lossy(&new_text);
+        let new_width =
+            crate::anon_common::measure_text_width(font, &new_str, run.font_size as f32, glyph_cache)
+                .max(1.0);
+        let scale_pct = ((orig_width / new_width) * 100.0).clamp(50.0, 150.0);
+
+        write_run_text(ops, run, new_text);
+        ops.insert(run.op_idx + 1, Operation::new("Tz", vec![Object::Real(100.0)]));
+        ops.insert(run.op_idx, Operation::new("Tz", vec![Object::Real(scale_pct)]));
+        count += 1;
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_subsequence() {
+        assert_eq!(find_subsequence(b"hello world", b"world"), Some(6));
+        assert_eq!(find_subsequence(b"hello world", b"xyz"), None);
+        assert_eq!(find_subsequence(b"hello", b""), None);
+        assert_eq!(find_subsequence(b"hi", b"hello"), None);
+    }
+
+    #[test]
+    fn test_estimate_run_width() {
+        assert_eq!(estimate_run_width(0, 12.0), 0.0);
+        assert_eq!(estimate_run_width(4, 12.0), 24.0);
+    }
+
+    #[test]
+    fn test_matrix_identity_multiply_is_identity() {
+        let m = Matrix::translation(10.0, 20.0);
+        let result = Matrix::IDENTITY.multiply(&m);
+        assert_eq!(result.apply(0.0, 0.0), (10.0, 20.0));
+    }
+
+    #[test]
+    fn test_matrix_apply_translation() {
+        let m = Matrix::translation(5.0, -3.0);
+        assert_eq!(m.apply(1.0, 1.0), (6.0, -2.0));
+    }
+
+    #[test]
+    fn test_extract_text_runs_simple_tj() {
+        let ops = vec![
+            Operation::new("BT", vec![]),
+            Operation::new("Tf", vec![Object::Name(b"F1".to_vec()), Object::Real(12.0)]),
+            Operation::new("Td", vec![Object::Real(100.0), Object::Real(200.0)]),
+            Operation::new("Tj", vec![Object::String(b"Hi".to_vec(), lopdf::StringFormat::Literal)]),
+            Operation::new("ET", vec![]),
+        ];
+        let runs = extract_text_runs(&ops);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].text(), b"Hi");
+        assert_eq!(runs[0].bbox.0, 100.0);
+        assert_eq!(runs[0].bbox.1, 200.0);
+    }
+
+    #[test]
+    fn test_extract_text_runs_ignores_tj_outside_text_object() {
+        let ops = vec![Operation::new(
+            "Tj",
+            vec![Object::String(b"Hi".to_vec(), lopdf::StringFormat::Literal)],
+        )];
+        assert!(extract_text_runs(&ops).is_empty());
+    }
+}
@@ -9,12 +9,20 @@ use bridge_parsers::tinyurl::UrlResolver;
 use bridge_parsers::{Direction, Vulnerability};
 use bridge_solver::{CLUB, DIAMOND, EAST, HEART, NORTH, SOUTH, SPADE, WEST};
 use csv::{ReaderBuilder, StringRecord, Writer};
+use crate::rate_limit;
+use crate::stats::{
+    bootstrap_def_minus_decl, replacement_defending_rate, write_stats_export,
+    z_test_diff_vs_baseline, DealObservation, PlayRole, PlayerStats,
+};
 use rayon::prelude::*;
+use rusqlite::OptionalExtension;
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Mutex;
+use url::Url;
 
 // ============================================================================
 // Fetch Cardplay
@@ -76,27 +84,31 @@ pub fn fetch_cardplay(
 
 /// Phase 1: Generate the tinyurl lookup file by resolving URLs and parsing LIN data.
 ///
-/// Skips entirely if the lookup file already has the expected row count.
-/// Resumes from where it left off if partially complete.
+/// Resumes row-by-row: a row is only reused from an existing lookup file if
+/// its URL hash still matches and it didn't previously end in an error --
+/// otherwise (new row, changed URL, or a prior failed fetch) it's resolved
+/// again. The file is rewritten atomically (temp file + rename) so a crash
+/// or cancellation mid-run never leaves a half-written last line that a
+/// later `count_csv_rows` check could mistake for a complete row.
+///
+/// Rows that still need a fetch are resolved `config.batch_size` at a time
+/// with `rayon`, gated by a [`TokenBucket`] per URL host (the same limiter
+/// `bbo_csv`'s `fetch_cardplay` uses) instead of one row at a time on a
+/// single thread -- a "Rate limited" response throttles that host's bucket
+/// for every worker hitting it rather than sleeping the one thread that saw
+/// it. Progress/cancellation is checked between chunks, same granularity
+/// `bbo_csv` uses for its own chunked fetch loop.
 fn generate_lookup_file(
     config: &FetchCardplayConfig,
     on_progress: &mut impl FnMut(&FetchProgress) -> bool,
     total_rows: usize,
 ) -> Result<String> {
-    // Check if lookup file is already complete
-    let existing_rows = if config.lookup_output.exists() {
-        count_csv_rows(&config.lookup_output)?
+    let existing = if config.lookup_output.exists() {
+        load_existing_lookup_rows(&config.lookup_output)?
     } else {
-        0
+        HashMap::new()
     };
 
-    if existing_rows >= total_rows {
-        return Ok(format!(
-            "Lookup file already complete ({} rows). Skipped URL resolution.",
-            existing_rows
-        ));
-    }
-
     let csv_data = read_bbo_csv_fixed(&config.input)?;
     let mut reader = ReaderBuilder::new()
         .flexible(true)
@@ -108,113 +120,142 @@ fn generate_lookup_file(
         .position(|h| h == config.url_column)
         .ok_or_else(|| anyhow::anyhow!("Column '{}' not found in CSV", config.url_column))?;
 
-    let mut resolver =
-        UrlResolver::with_config(config.delay_ms, config.batch_size, config.batch_delay_ms);
-
-    // Open lookup file for writing (append if resuming)
-    let resuming = existing_rows > 0;
-    let file: std::fs::File = if resuming {
-        std::fs::OpenOptions::new()
-            .append(true)
-            .open(&config.lookup_output)?
-    } else {
-        std::fs::File::create(&config.lookup_output)?
-    };
-    let mut out = csv::WriterBuilder::new()
-        .flexible(true)
-        .has_headers(false)
-        .from_writer(std::io::BufWriter::new(file));
-
-    if !resuming {
-        out.write_record(LOOKUP_FIELDS)?;
-    }
-
-    let mut processed = 0usize;
+    // Split rows into ones reusable from a prior run and ones that need a
+    // fresh fetch, same resume split `bbo_csv::fetch_cardplay` does, so only
+    // rows that actually need a network round trip pay for one.
+    let mut tinyurls: Vec<String> = Vec::with_capacity(total_rows);
+    let mut rows: Vec<Option<Vec<String>>> = Vec::with_capacity(total_rows);
+    let mut to_fetch: Vec<usize> = Vec::new();
     let mut skipped = 0usize;
-    let mut errors = 0usize;
 
     for (row_num, result) in reader.records().enumerate() {
         let record = result.context("Failed to read CSV row")?;
         let board_id = row_num + 1;
-        processed += 1;
+        let tinyurl = record.get(url_col_idx).unwrap_or("").trim().to_string();
+        let src_hash = hash_source_url(&tinyurl);
 
-        // Skip rows already in the lookup file
-        if row_num < existing_rows {
-            skipped += 1;
-            let keep_going = on_progress(&FetchProgress {
-                completed: processed,
-                total: total_rows,
-                errors,
-                skipped,
-            });
-            if !keep_going {
-                out.flush()?;
-                return Ok(format!(
-                    "Cancelled after {} of {} rows ({} errors, {} skipped)",
-                    processed, total_rows, errors, skipped
-                ));
+        if let Some(prior) = existing.get(&board_id) {
+            if prior.src_hash == src_hash && !prior.cardplay.is_empty() {
+                skipped += 1;
+                tinyurls.push(tinyurl);
+                rows.push(Some(prior.fields.clone()));
+                continue;
             }
+        }
+
+        if tinyurl.is_empty() {
+            rows.push(Some(lookup_empty_row_fields(board_id, &tinyurl, &src_hash)));
+            tinyurls.push(tinyurl);
             continue;
         }
 
-        // Report progress and check for cancellation
+        to_fetch.push(row_num);
+        tinyurls.push(tinyurl);
+        rows.push(None);
+    }
+
+    let processed = AtomicUsize::new(skipped);
+    let errors = AtomicUsize::new(0);
+    let buckets: Mutex<HashMap<String, std::sync::Arc<rate_limit::TokenBucket>>> =
+        Mutex::new(HashMap::new());
+    let mut cancelled_message = None;
+
+    for chunk in to_fetch.chunks(config.batch_size.max(1)) {
+        let chunk_results: Vec<(usize, Vec<String>)> = chunk
+            .par_iter()
+            .map(|&row_num| {
+                let board_id = row_num + 1;
+                let tinyurl = &tinyurls[row_num];
+                let src_hash = hash_source_url(tinyurl);
+                let bucket = rate_limit::bucket_for(
+                    &buckets,
+                    rate_limit::url_host(tinyurl),
+                    config.batch_size,
+                    config.delay_ms,
+                );
+                bucket.acquire();
+
+                let mut resolver = UrlResolver::with_config(0, config.batch_size, 0);
+                let fields = match resolve_and_parse_url(&mut resolver, tinyurl) {
+                    Ok((lin, resolved_url)) => {
+                        lookup_row_fields(board_id, tinyurl, &lin, &resolved_url, &src_hash)
+                    }
+                    Err(e) => {
+                        log::warn!("Row {}: Error processing URL '{}': {}", board_id, tinyurl, e);
+                        errors.fetch_add(1, Ordering::Relaxed);
+                        if e.to_string().contains("Rate limited") {
+                            bucket.throttle();
+                        }
+                        lookup_empty_row_fields(board_id, tinyurl, &src_hash)
+                    }
+                };
+                processed.fetch_add(1, Ordering::Relaxed);
+                (row_num, fields)
+            })
+            .collect();
+
+        for (row_num, fields) in chunk_results {
+            rows[row_num] = Some(fields);
+        }
+
+        for bucket in buckets.lock().unwrap().values() {
+            bucket.recover();
+        }
+        if config.batch_delay_ms > 0 {
+            std::thread::sleep(std::time::Duration::from_millis(config.batch_delay_ms));
+        }
+
         let keep_going = on_progress(&FetchProgress {
-            completed: processed,
+            completed: processed.load(Ordering::Relaxed),
             total: total_rows,
-            errors,
+            errors: errors.load(Ordering::Relaxed),
             skipped,
         });
         if !keep_going {
-            out.flush()?;
-            return Ok(format!(
+            cancelled_message = Some(format!(
                 "Cancelled after {} of {} rows ({} errors, {} skipped)",
-                processed, total_rows, errors, skipped
+                processed.load(Ordering::Relaxed),
+                total_rows,
+                errors.load(Ordering::Relaxed),
+                skipped
             ));
+            break;
         }
+    }
 
-        let tinyurl = record.get(url_col_idx).unwrap_or("").trim();
-
-        if tinyurl.is_empty() {
-            write_lookup_empty_row(&mut out, board_id, tinyurl)?;
-            continue;
-        }
-
-        match resolve_and_parse_url(&mut resolver, tinyurl) {
-            Ok((lin, resolved_url)) => {
-                write_lookup_row(&mut out, board_id, tinyurl, &lin, &resolved_url)?;
-            }
-            Err(e) => {
-                log::warn!(
-                    "Row {}: Error processing URL '{}': {}",
-                    board_id,
-                    tinyurl,
-                    e
-                );
-                errors += 1;
-
-                if e.to_string().contains("Rate limited") {
-                    log::warn!("Rate limited - pausing for 60 seconds...");
-                    std::thread::sleep(std::time::Duration::from_secs(60));
-                    resolver.reset_batch();
-                }
+    // Any row whose chunk never ran (cancelled partway through) still needs
+    // a placeholder so `rows` has no gaps for `write_lookup_rows`.
+    let final_rows: Vec<Vec<String>> = rows
+        .into_iter()
+        .enumerate()
+        .map(|(row_num, row)| {
+            row.unwrap_or_else(|| {
+                let tinyurl = &tinyurls[row_num];
+                lookup_empty_row_fields(row_num + 1, tinyurl, &hash_source_url(tinyurl))
+            })
+        })
+        .collect();
 
-                write_lookup_empty_row(&mut out, board_id, tinyurl)?;
-            }
-        }
+    write_atomic(&config.lookup_output, |tmp_path| write_lookup_rows(tmp_path, &final_rows))?;
 
-        if processed.is_multiple_of(100) {
-            out.flush()?;
-        }
+    if let Some(message) = cancelled_message {
+        return Ok(message);
     }
 
-    out.flush()?;
     Ok(format!(
         "Done! Processed {} rows ({} errors, {} skipped)",
-        processed, errors, skipped
+        processed.load(Ordering::Relaxed),
+        errors.load(Ordering::Relaxed),
+        skipped
     ))
 }
 
+
 /// Phase 2: Read the lookup file and original CSV, merge Cardplay + LIN_URL into the output.
+///
+/// Written via `write_atomic` so a crash or cancellation mid-merge never
+/// leaves a truncated `config.output` that a later run would treat as a
+/// finished cardplay CSV.
 fn merge_lookup_to_cardplay(config: &FetchCardplayConfig) -> Result<()> {
     // Load lookup data: Board_ID (1-based index) → (Cardplay, LIN_URL)
     let lookup = load_lookup_data(&config.lookup_output)?;
@@ -234,39 +275,41 @@ fn merge_lookup_to_cardplay(config: &FetchCardplayConfig) -> Result<()> {
         output_headers.push_field("LIN_URL");
     }
 
-    let mut writer = csv::WriterBuilder::new()
-        .flexible(true)
-        .from_path(&config.output)
-        .context("Failed to create output CSV")?;
-    writer.write_record(&output_headers)?;
+    write_atomic(&config.output, |tmp_path| {
+        let mut writer = csv::WriterBuilder::new()
+            .flexible(true)
+            .from_path(tmp_path)
+            .context("Failed to create output CSV")?;
+        writer.write_record(&output_headers)?;
 
-    for (row_num, result) in reader.records().enumerate() {
-        let record = result.context("Failed to read CSV row")?;
-        let board_id = row_num + 1;
+        for (row_num, result) in reader.records().enumerate() {
+            let record = result.context("Failed to read CSV row")?;
+            let board_id = row_num + 1;
 
-        let (cardplay, lin_url) = lookup
-            .get(&board_id)
-            .cloned()
-            .unwrap_or_else(|| (String::new(), String::new()));
+            let (cardplay, lin_url) = lookup
+                .get(&board_id)
+                .cloned()
+                .unwrap_or_else(|| (String::new(), String::new()));
 
-        let mut output_record: Vec<String> = record.iter().map(|s| s.to_string()).collect();
+            let mut output_record: Vec<String> = record.iter().map(|s| s.to_string()).collect();
 
-        if let (Some(cp_idx), Some(lu_idx)) = (cardplay_col_idx, lin_url_col_idx) {
-            if cp_idx < output_record.len() {
-                output_record[cp_idx] = cardplay;
-            }
-            if lu_idx < output_record.len() {
-                output_record[lu_idx] = lin_url;
+            if let (Some(cp_idx), Some(lu_idx)) = (cardplay_col_idx, lin_url_col_idx) {
+                if cp_idx < output_record.len() {
+                    output_record[cp_idx] = cardplay;
+                }
+                if lu_idx < output_record.len() {
+                    output_record[lu_idx] = lin_url;
+                }
+            } else {
+                output_record.push(cardplay);
+                output_record.push(lin_url);
             }
-        } else {
-            output_record.push(cardplay);
-            output_record.push(lin_url);
+            writer.write_record(&output_record)?;
         }
-        writer.write_record(&output_record)?;
-    }
 
-    writer.flush()?;
-    Ok(())
+        writer.flush()?;
+        Ok(())
+    })
 }
 
 /// Resolve a URL (following tinyurl/bit.ly redirects) and parse its LIN data.
@@ -362,23 +405,39 @@ pub fn load_lookup_board_ids(lookup_path: &Path) -> Result<HashMap<String, (Stri
     Ok(data)
 }
 
-/// Count the number of data rows (excluding header) in a CSV file.
+/// Count data rows via a real CSV parse (not a line count), so fields with
+/// embedded newlines (e.g. a multi-line `Explanations` column) don't inflate
+/// the total.
 pub fn count_csv_rows(path: &Path) -> Result<usize> {
-    use std::io::BufRead;
-    let file = std::fs::File::open(path)?;
-    let reader = std::io::BufReader::new(file);
-    Ok(reader.lines().count().saturating_sub(1))
+    let mut reader = ReaderBuilder::new().flexible(true).from_path(path)?;
+    Ok(reader.records().count())
 }
 
 // ============================================================================
 // Display Hand
 // ============================================================================
 
-/// Display a single hand from a CSV file, returning formatted text.
-///
-/// This is the library version of the CLI's `display-hand` subcommand.
-/// Instead of printing to stdout, it returns the formatted output as a String.
-pub fn display_hand(input: &Path, row_num: usize) -> Result<String> {
+/// The raw fields of a single CSV row needed to display a hand, shared by
+/// the plain-text (`display_hand`) and structured (`display_hand_structured`)
+/// entry points.
+struct HandRowFields {
+    north_hand: String,
+    south_hand: String,
+    east_hand: String,
+    west_hand: String,
+    contract: String,
+    declarer: String,
+    result: String,
+    cardplay: String,
+    dd_analysis: String,
+    north_player: String,
+    south_player: String,
+    east_player: String,
+    west_player: String,
+    ref_num: String,
+}
+
+fn read_hand_row(input: &Path, row_num: usize) -> Result<HandRowFields> {
     if row_num == 0 {
         return Err(anyhow::anyhow!("Row number must be 1 or greater"));
     }
@@ -412,22 +471,204 @@ pub fn display_hand(input: &Path, row_num: usize) -> Result<String> {
         .ok_or_else(|| anyhow::anyhow!("Row {} not found in file", row_num))?
         .context("Failed to read CSV row")?;
 
-    let get = |col: Option<usize>| col.and_then(|i| record.get(i)).unwrap_or("");
-
-    let north_hand = get(north_col);
-    let south_hand = get(south_col);
-    let east_hand = get(east_col);
-    let west_hand = get(west_col);
-    let contract = get(contract_col);
-    let declarer = get(declarer_col);
-    let result = get(result_col);
-    let cardplay = get(cardplay_col);
-    let dd_analysis = get(dd_col);
-    let north_player = get(n_col);
-    let south_player = get(s_col);
-    let east_player = get(e_col);
-    let west_player = get(w_col);
-    let ref_num = get(ref_col);
+    let get = |col: Option<usize>| col.and_then(|i| record.get(i)).unwrap_or("").to_string();
+
+    Ok(HandRowFields {
+        north_hand: get(north_col),
+        south_hand: get(south_col),
+        east_hand: get(east_col),
+        west_hand: get(west_col),
+        contract: get(contract_col),
+        declarer: get(declarer_col),
+        result: get(result_col),
+        cardplay: get(cardplay_col),
+        dd_analysis: get(dd_col),
+        north_player: get(n_col),
+        south_player: get(s_col),
+        east_player: get(e_col),
+        west_player: get(w_col),
+        ref_num: get(ref_col),
+    })
+}
+
+/// Suit holdings for one seat, e.g. `spades: "AKQ"` (highest to lowest, no suit letter).
+#[derive(Debug, Clone)]
+pub struct SuitHoldings {
+    pub spades: String,
+    pub hearts: String,
+    pub diamonds: String,
+    pub clubs: String,
+}
+
+/// Extract one suit's cards from a hand string like "S:AKQ H:JT9 D:— C:2",
+/// highest to lowest, with no suit letter. Returns "-" if the suit is void
+/// or not present.
+fn format_suit(hand: &str, suit_char: char) -> String {
+    for part in hand.split_whitespace() {
+        let lower_suit = suit_char.to_ascii_lowercase();
+        if part.starts_with(suit_char) || part.starts_with(lower_suit) {
+            if let Some(cards) = part.get(2..) {
+                return cards.to_string();
+            }
+        }
+    }
+    "-".to_string()
+}
+
+fn suit_holdings(hand: &str) -> SuitHoldings {
+    SuitHoldings {
+        spades: format_suit(hand, 'S'),
+        hearts: format_suit(hand, 'H'),
+        diamonds: format_suit(hand, 'D'),
+        clubs: format_suit(hand, 'C'),
+    }
+}
+
+/// One card played in a trick: the seat that played it, the card itself
+/// (e.g. "AH"), and its double-dummy cost in tricks relative to par, if DD
+/// analysis is available for this trick.
+#[derive(Debug, Clone)]
+pub struct CardPlay {
+    pub seat: char,
+    pub card: String,
+    pub cost: Option<u8>,
+}
+
+/// One trick's worth of cardplay, in lead order, for the Display Hand diagram.
+#[derive(Debug, Clone)]
+pub struct TrickDisplay {
+    pub trick_num: usize,
+    pub plays: Vec<CardPlay>,
+}
+
+/// Structured hand data for the four-hand bridge diagram in the Display Hand tab.
+#[derive(Debug, Clone)]
+pub struct DealDisplay {
+    pub ref_num: String,
+    pub contract: String,
+    pub declarer: String,
+    pub result: String,
+    pub north_player: String,
+    pub south_player: String,
+    pub east_player: String,
+    pub west_player: String,
+    pub north: SuitHoldings,
+    pub south: SuitHoldings,
+    pub east: SuitHoldings,
+    pub west: SuitHoldings,
+    /// Trick-by-trick cardplay with per-card DD cost, for graphical rendering.
+    pub tricks: Vec<TrickDisplay>,
+    /// DD-analysis seat summary and any parse errors, as plain text.
+    pub narrative: String,
+}
+
+/// Build the trick-by-trick cardplay, in lead order, with each card's
+/// double-dummy cost attached from the "Tn:cost,cost,cost,cost" DD field.
+fn build_tricks(cardplay: &str, dd_analysis: &str, declarer: &str, contract: &str) -> Vec<TrickDisplay> {
+    let mut tricks = Vec::new();
+    if cardplay.is_empty() {
+        return tricks;
+    }
+
+    let initial_leader = match declarer.chars().next() {
+        Some('N') => 'E',
+        Some('E') => 'S',
+        Some('S') => 'W',
+        Some('W') => 'N',
+        _ => '?',
+    };
+    let dd_costs = parse_dd_costs(dd_analysis);
+    let mut current_leader = initial_leader;
+
+    for (trick_idx, trick_str) in cardplay.split('|').enumerate() {
+        if trick_str.is_empty() {
+            continue;
+        }
+
+        let trick_num = trick_idx + 1;
+        let cards: Vec<&str> = trick_str.split_whitespace().collect();
+        if cards.len() != 4 {
+            continue;
+        }
+
+        let seats = get_seat_order(current_leader);
+        let costs = dd_costs.get(&trick_num);
+        let plays = cards
+            .iter()
+            .enumerate()
+            .map(|(i, c)| CardPlay {
+                seat: seats[i],
+                card: c.to_string(),
+                cost: costs.map(|c| c[i]),
+            })
+            .collect();
+        tricks.push(TrickDisplay { trick_num, plays });
+
+        if let Some(winner_seat) = determine_trick_winner_for_display(&cards, current_leader, contract) {
+            current_leader = winner_seat;
+        }
+    }
+
+    tricks
+}
+
+/// Display a single hand from a CSV file as structured seat/suit data for
+/// graphical rendering, with the trick-by-trick cardplay (DD cost attached
+/// per card) and a plain-text DD-analysis summary tail.
+pub fn display_hand_structured(input: &Path, row_num: usize) -> Result<DealDisplay> {
+    let fields = read_hand_row(input, row_num)?;
+    let tricks = build_tricks(
+        &fields.cardplay,
+        &fields.dd_analysis,
+        &fields.declarer,
+        &fields.contract,
+    );
+    let narrative = dd_summary_text(
+        &fields.cardplay,
+        &fields.dd_analysis,
+        &fields.declarer,
+        &fields.contract,
+    )?;
+
+    Ok(DealDisplay {
+        ref_num: fields.ref_num,
+        contract: fields.contract,
+        declarer: fields.declarer,
+        result: fields.result,
+        north_player: fields.north_player,
+        south_player: fields.south_player,
+        east_player: fields.east_player,
+        west_player: fields.west_player,
+        north: suit_holdings(&fields.north_hand),
+        south: suit_holdings(&fields.south_hand),
+        east: suit_holdings(&fields.east_hand),
+        west: suit_holdings(&fields.west_hand),
+        tricks,
+        narrative,
+    })
+}
+
+/// Display a single hand from a CSV file, returning formatted text.
+///
+/// This is the library version of the CLI's `display-hand` subcommand.
+/// Instead of printing to stdout, it returns the formatted output as a String.
+pub fn display_hand(input: &Path, row_num: usize) -> Result<String> {
+    let fields = read_hand_row(input, row_num)?;
+
+    let north_hand = fields.north_hand.as_str();
+    let south_hand = fields.south_hand.as_str();
+    let east_hand = fields.east_hand.as_str();
+    let west_hand = fields.west_hand.as_str();
+    let contract = fields.contract.as_str();
+    let declarer = fields.declarer.as_str();
+    let result = fields.result.as_str();
+    let cardplay = fields.cardplay.as_str();
+    let dd_analysis = fields.dd_analysis.as_str();
+    let north_player = fields.north_player.as_str();
+    let south_player = fields.south_player.as_str();
+    let east_player = fields.east_player.as_str();
+    let west_player = fields.west_player.as_str();
+    let ref_num = fields.ref_num.as_str();
 
     let mut out = String::new();
 
@@ -452,18 +693,6 @@ pub fn display_hand(input: &Path, row_num: usize) -> Result<String> {
     writeln!(out, "\n{:^40}", "DEAL")?;
     writeln!(out, "{:-<40}", "")?;
 
-    let format_suit = |hand: &str, suit_char: char| -> String {
-        for part in hand.split_whitespace() {
-            let lower_suit = suit_char.to_ascii_lowercase();
-            if part.starts_with(suit_char) || part.starts_with(lower_suit) {
-                if let Some(cards) = part.get(2..) {
-                    return cards.to_string();
-                }
-            }
-        }
-        "-".to_string()
-    };
-
     let format_hand_lines = |hand: &str| -> [String; 4] {
         [
             format!("S: {}", format_suit(hand, 'S')),
@@ -568,7 +797,18 @@ pub fn display_hand(input: &Path, row_num: usize) -> Result<String> {
         }
     }
 
-    // DD Analysis summary
+    write!(out, "{}", dd_summary_text(cardplay, dd_analysis, declarer, contract)?)?;
+    writeln!(out, "\n{:=^80}", "")?;
+
+    Ok(out)
+}
+
+/// Build the "DD ANALYSIS SUMMARY" block: per-seat play/error/cost totals
+/// and declaring-vs-defending role, or an error line if `dd_analysis` failed.
+/// Empty if no DD analysis is present at all.
+fn dd_summary_text(cardplay: &str, dd_analysis: &str, declarer: &str, contract: &str) -> Result<String> {
+    let mut out = String::new();
+
     if !dd_analysis.is_empty() && !dd_analysis.starts_with("ERROR") {
         writeln!(out, "\n{:=^80}", " DD ANALYSIS SUMMARY ")?;
 
@@ -650,8 +890,6 @@ pub fn display_hand(input: &Path, row_num: usize) -> Result<String> {
         writeln!(out, "Error: {}", dd_analysis)?;
     }
 
-    writeln!(out, "\n{:=^80}", "")?;
-
     Ok(out)
 }
 
@@ -659,8 +897,34 @@ pub fn display_hand(input: &Path, row_num: usize) -> Result<String> {
 // Stats
 // ============================================================================
 
+/// Progress information for `compute_stats`.
+pub struct StatsProgress {
+    /// Number of CSV rows processed so far
+    pub completed: usize,
+    /// Total number of CSV rows to process
+    pub total: usize,
+}
+
 /// Compute DD error statistics and return formatted text.
-pub fn compute_stats(input: &Path, top_n: usize) -> Result<String> {
+///
+/// Includes Wilson-interval error rates, logistic play accuracy, Defense
+/// Above Replacement, and a bootstrap-backed comparison of the top two
+/// players against the field -- the same statistical engine `bbo_csv stats`
+/// uses, so the GUI's Stats tab and the CLI report identical numbers for the
+/// same input. When `export` is `Some`, also writes a JSON breakdown to that
+/// path (see `write_stats_export`).
+///
+/// Calls `on_progress` after each CSV row; returning `false` stops the scan
+/// early and returns an error, the same cooperative-cancellation contract
+/// used by `anonymize_csv` and `analyze_dd`.
+pub fn compute_stats(
+    input: &Path,
+    top_n: usize,
+    export: Option<&Path>,
+    mut on_progress: impl FnMut(&StatsProgress) -> bool,
+) -> Result<String> {
+    let total_rows = count_csv_rows(input)?;
+
     let mut reader = ReaderBuilder::new()
         .flexible(true)
         .from_path(input)
@@ -720,14 +984,42 @@ pub fn compute_stats(input: &Path, top_n: usize) -> Result<String> {
         .iter()
         .position(|h| h == "DD_W_Errors")
         .ok_or_else(|| anyhow::anyhow!("Column 'DD_W_Errors' not found"))?;
+    // Optional columns only present on output from a newer `analyze-dd` run.
+    let dd_error_categories_col = headers.iter().position(|h| h == "DD_Error_Categories");
+    let dd_n_costsum_col = headers.iter().position(|h| h == "DD_N_CostSum");
+    let dd_s_costsum_col = headers.iter().position(|h| h == "DD_S_CostSum");
+    let dd_e_costsum_col = headers.iter().position(|h| h == "DD_E_CostSum");
+    let dd_w_costsum_col = headers.iter().position(|h| h == "DD_W_CostSum");
+    let cardplay_col = headers.iter().position(|h| h == "Cardplay");
+    let contract_col = headers.iter().position(|h| h == "Contract");
+    let dd_analysis_col = headers.iter().position(|h| h == "DD_Analysis");
+
+    // Logistic `p_loss` curve parameters and bootstrap iteration count,
+    // matching the CLI's `bbo_csv stats` defaults so the GUI's Stats tab
+    // reports the same numbers for the same input.
+    let accuracy_k = 3.0;
+    let accuracy_c0 = 0.5;
+    let bootstrap_iterations: u64 = 10_000;
 
     let mut player_stats: HashMap<String, PlayerStats> = HashMap::new();
     let mut processed = 0u64;
     let mut skipped = 0u64;
+    let mut deal_seq = 0u64;
 
     for result in reader.records() {
         let record = result.context("Failed to read CSV row")?;
         processed += 1;
+        deal_seq += 1;
+        if !on_progress(&StatsProgress {
+            completed: processed as usize,
+            total: total_rows,
+        }) {
+            return Err(anyhow::anyhow!(
+                "Cancelled after {} of {} rows",
+                processed,
+                total_rows
+            ));
+        }
 
         let north = record.get(n_col).unwrap_or("").to_lowercase();
         let south = record.get(s_col).unwrap_or("").to_lowercase();
@@ -773,6 +1065,11 @@ pub fn compute_stats(input: &Path, top_n: usize) -> Result<String> {
             .and_then(|s| s.parse().ok())
             .unwrap_or(0);
 
+        let n_cost: u64 = dd_n_costsum_col.and_then(|i| record.get(i)).and_then(|s| s.parse().ok()).unwrap_or(0);
+        let s_cost: u64 = dd_s_costsum_col.and_then(|i| record.get(i)).and_then(|s| s.parse().ok()).unwrap_or(0);
+        let e_cost: u64 = dd_e_costsum_col.and_then(|i| record.get(i)).and_then(|s| s.parse().ok()).unwrap_or(0);
+        let w_cost: u64 = dd_w_costsum_col.and_then(|i| record.get(i)).and_then(|s| s.parse().ok()).unwrap_or(0);
+
         if n_plays == 0 && s_plays == 0 && e_plays == 0 && w_plays == 0 {
             skipped += 1;
             continue;
@@ -789,14 +1086,59 @@ pub fn compute_stats(input: &Path, top_n: usize) -> Result<String> {
             }
         };
 
+        let categories_by_seat = dd_error_categories_col
+            .and_then(|i| record.get(i))
+            .map(parse_error_categories)
+            .unwrap_or_default();
+
+        // Win-probability-weighted accuracy needs each play's own cost, not
+        // just the seat totals below, so walk the per-trick cost string
+        // directly when the row still has it.
+        if let (Some(cp_i), Some(con_i), Some(dda_i)) = (cardplay_col, contract_col, dd_analysis_col) {
+            let cardplay = record.get(cp_i).unwrap_or("");
+            let contract_str = record.get(con_i).unwrap_or("");
+            let dd_analysis = record.get(dda_i).unwrap_or("");
+            if !dd_analysis.is_empty() && !dd_analysis.starts_with("ERROR") {
+                let declaring_seat_chars: [char; 2] = match declarer.chars().next() {
+                    Some('N') | Some('S') => ['N', 'S'],
+                    Some('E') | Some('W') => ['E', 'W'],
+                    _ => ['?', '?'],
+                };
+                let attribute = |name: &str| -> String {
+                    if name == declarer_name || name == dummy_name {
+                        declarer_name.clone()
+                    } else {
+                        name.to_string()
+                    }
+                };
+                let accuracy_seats: [(char, String); 4] = [
+                    ('N', attribute(&north)),
+                    ('S', attribute(&south)),
+                    ('E', attribute(&east)),
+                    ('W', attribute(&west)),
+                ];
+                accumulate_play_accuracy(
+                    dd_analysis,
+                    cardplay,
+                    contract_str,
+                    &declarer,
+                    &accuracy_seats,
+                    declaring_seat_chars,
+                    accuracy_k,
+                    accuracy_c0,
+                    &mut player_stats,
+                );
+            }
+        }
+
         let seat_data = [
-            (&north, n_plays, n_errors),
-            (&south, s_plays, s_errors),
-            (&east, e_plays, e_errors),
-            (&west, w_plays, w_errors),
+            (&north, 'N', n_plays, n_errors, n_cost),
+            (&south, 'S', s_plays, s_errors, s_cost),
+            (&east, 'E', e_plays, e_errors, e_cost),
+            (&west, 'W', w_plays, w_errors, w_cost),
         ];
 
-        for (player_name, plays, errors) in &seat_data {
+        for (player_name, seat, plays, errors, cost) in &seat_data {
             if player_name.is_empty() {
                 continue;
             }
@@ -805,22 +1147,44 @@ pub fn compute_stats(input: &Path, top_n: usize) -> Result<String> {
             let is_dummy = *player_name == dummy_name;
             let is_declaring_side = is_declarer || is_dummy;
 
-            if is_declaring_side {
+            let stats = if is_declaring_side {
                 let stats = player_stats
                     .entry(declarer_name.clone())
                     .or_insert_with(|| PlayerStats::new(declarer_name));
                 stats.declaring_plays += plays;
                 stats.declaring_errors += errors;
+                stats.declaring_cost_sum += cost;
+                stats.observations.push(DealObservation {
+                    role: PlayRole::Declaring,
+                    plays: *plays,
+                    errors: *errors,
+                    seq: deal_seq,
+                });
+                stats
             } else {
                 let stats = player_stats
                     .entry((*player_name).clone())
                     .or_insert_with(|| PlayerStats::new(player_name));
                 stats.defending_plays += plays;
                 stats.defending_errors += errors;
+                stats.defending_cost_sum += cost;
+                stats.observations.push(DealObservation {
+                    role: PlayRole::Defending,
+                    plays: *plays,
+                    errors: *errors,
+                    seq: deal_seq,
+                });
+                stats
+            };
+
+            if let Some(categories) = categories_by_seat.get(seat) {
+                for (category, count) in categories {
+                    *stats.error_categories.entry(category.clone()).or_insert(0) += count;
+                }
             }
         }
 
-        for (player_name, _, _) in &seat_data {
+        for (player_name, _, _, _, _) in &seat_data {
             if player_name.is_empty() {
                 continue;
             }
@@ -841,8 +1205,7 @@ pub fn compute_stats(input: &Path, top_n: usize) -> Result<String> {
     players.sort_by(|a, b| b.total_deals.cmp(&a.total_deals));
 
     // Build "Field" from everyone except top 2
-    let top_2: std::collections::HashSet<String> =
-        players.iter().take(2).map(|p| p.name.clone()).collect();
+    let top_2: HashSet<String> = players.iter().take(2).map(|p| p.name.clone()).collect();
     let mut field_stats = PlayerStats::new("FIELD");
     for player in &players {
         if !top_2.contains(&player.name) {
@@ -850,6 +1213,10 @@ pub fn compute_stats(input: &Path, top_n: usize) -> Result<String> {
         }
     }
 
+    if let Some(export_path) = export {
+        write_stats_export(export_path, &players, &field_stats)?;
+    }
+
     // Format output
     let mut out = String::new();
     writeln!(out, "Processed {} deals ({} skipped)", processed, skipped)?;
@@ -897,9 +1264,9 @@ pub fn compute_stats(input: &Path, top_n: usize) -> Result<String> {
             rel_pct
         )?;
 
-        let decl_ci = player.declaring_ci();
-        let def_ci = player.defending_ci();
-        if !decl_ci.is_nan() || !def_ci.is_nan() {
+        let (decl_ci_lo, decl_ci_hi) = player.declaring_ci();
+        let (def_ci_lo, def_ci_hi) = player.defending_ci();
+        if player.declaring_plays > 0 || player.defending_plays > 0 {
             writeln!(
                 out,
                 "{:<20} {:>8} {:>6} {:>6} {:>12} {:>10} {:>12} {:>10}",
@@ -907,14 +1274,41 @@ pub fn compute_stats(input: &Path, top_n: usize) -> Result<String> {
                 "",
                 "",
                 "",
-                format!("(\u{00b1}{:.2}%)", decl_ci),
+                format!("[{:.2}-{:.2}%]", decl_ci_lo, decl_ci_hi),
                 "",
-                format!("(\u{00b1}{:.2}%)", def_ci),
+                format!("[{:.2}-{:.2}%]", def_ci_lo, def_ci_hi),
                 ""
             )?;
         }
     }
 
+    if players.iter().take(top_n).any(|p| !p.error_categories.is_empty()) {
+        writeln!(out, "\n{:=^80}", " Error Categories ")?;
+        for player in players.iter().take(top_n) {
+            let top = player.top_categories(5);
+            if top.is_empty() {
+                continue;
+            }
+            let breakdown = top
+                .iter()
+                .map(|(id, count)| format!("{}={}", id, count))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(out, "  {:<20} {}", truncate_name(&player.name, 20), breakdown)?;
+        }
+    }
+
+    if players.iter().take(top_n).any(|p| p.declaring_accuracy().is_some() || p.defending_accuracy().is_some()) {
+        writeln!(out, "\n{:=^60}", format!(" Accuracy (logistic k={}, c0={}) ", accuracy_k, accuracy_c0))?;
+        writeln!(out, "{:<20} {:>10} {:>10}", "Player", "Decl Acc%", "Def Acc%")?;
+        writeln!(out, "{:-<42}", "")?;
+        for player in players.iter().take(top_n) {
+            let decl_acc = player.declaring_accuracy().map(|a| format!("{:.2}%", a)).unwrap_or_else(|| "-".to_string());
+            let def_acc = player.defending_accuracy().map(|a| format!("{:.2}%", a)).unwrap_or_else(|| "-".to_string());
+            writeln!(out, "{:<20} {:>10} {:>10}", truncate_name(&player.name, 20), decl_acc, def_acc)?;
+        }
+    }
+
     // Field aggregate
     writeln!(out, "{:-<126}", "")?;
     let decl_rate = field_stats.declaring_error_rate();
@@ -940,6 +1334,97 @@ pub fn compute_stats(input: &Path, top_n: usize) -> Result<String> {
         diff,
         rel_pct
     )?;
+    let (field_decl_ci_lo, field_decl_ci_hi) = field_stats.declaring_ci();
+    let (field_def_ci_lo, field_def_ci_hi) = field_stats.defending_ci();
+    writeln!(
+        out,
+        "{:<20} {:>8} {:>6} {:>6} {:>12} {:>10} {:>12} {:>10}",
+        "",
+        "",
+        "",
+        "",
+        format!("[{:.2}-{:.2}%]", field_decl_ci_lo, field_decl_ci_hi),
+        "",
+        format!("[{:.2}-{:.2}%]", field_def_ci_lo, field_def_ci_hi),
+        ""
+    )?;
+
+    // Partner comparison between the top two players, same as the CLI's
+    // two-subject statistical analysis section.
+    if players.len() >= 2 {
+        let subj_a = players[0].clone();
+        let subj_b = players[1].clone();
+
+        writeln!(out, "\n{:=^100}", " Partner Comparison ")?;
+        writeln!(out, "\nComparing {} vs {}:", subj_a.name, subj_b.name)?;
+
+        let decl_gap = subj_a.declaring_error_rate() - subj_b.declaring_error_rate();
+        writeln!(out, "\n  DECLARING:")?;
+        writeln!(out, "    {:<20}: {:.2}% error rate", subj_a.name, subj_a.declaring_error_rate())?;
+        writeln!(out, "    {:<20}: {:.2}% error rate", subj_b.name, subj_b.declaring_error_rate())?;
+
+        let def_gap = subj_a.defending_error_rate() - subj_b.defending_error_rate();
+        writeln!(out, "\n  DEFENDING:")?;
+        writeln!(out, "    {:<20}: {:.2}% error rate", subj_a.name, subj_a.defending_error_rate())?;
+        writeln!(out, "    {:<20}: {:.2}% error rate", subj_b.name, subj_b.defending_error_rate())?;
+
+        writeln!(out, "\n{:=^100}", " Statistical Analysis ")?;
+        let replacement_rate = replacement_defending_rate(&players);
+
+        for subj in [&subj_a, &subj_b] {
+            let subj_diff = subj.def_minus_decl();
+            let field_diff = field_stats.def_minus_decl();
+            let (z, p) = z_test_diff_vs_baseline(subj, &field_stats);
+
+            writeln!(out, "\n  {} vs FIELD baseline:", subj.name)?;
+            writeln!(out, "    {} Def-Decl diff: {:+.2}%", subj.name, subj_diff)?;
+            writeln!(out, "    FIELD Def-Decl diff:      {:+.2}%", field_diff)?;
+
+            if !z.is_nan() {
+                writeln!(out, "    Z-score: {:.2}, P-value: {:.4}", z, p)?;
+            } else {
+                writeln!(out, "    (Insufficient data for statistical test)")?;
+            }
+
+            match bootstrap_def_minus_decl(&subj.observations, field_diff, bootstrap_iterations) {
+                Some(boot) => {
+                    writeln!(
+                        out,
+                        "    Bootstrap 95% CI ({} resamples): [{:+.2}%, {:+.2}%] (P-value {:.4})",
+                        bootstrap_iterations, boot.ci_lo, boot.ci_hi, boot.p_value
+                    )?;
+                }
+                None => writeln!(out, "    (No per-deal observations recorded for bootstrap)")?,
+            }
+
+            if subj.defending_plays > 0 {
+                let dar = subj.defense_above_replacement(replacement_rate);
+                writeln!(
+                    out,
+                    "    Defense Above Replacement: {:+.2} tricks over {} defending plays (replacement rate {:.3} tricks/play)",
+                    dar, subj.defending_plays, replacement_rate
+                )?;
+            } else {
+                writeln!(out, "    (No DD cost data for DAR -- re-run analyze-dd to populate DD_<seat>_CostSum)")?;
+            }
+        }
+
+        writeln!(out, "\n  {} vs {} (declaring error rate):", subj_a.name, subj_b.name)?;
+        let (decl_z, decl_p) = subj_a.declaring_vs(&subj_b);
+        if !decl_z.is_nan() {
+            writeln!(out, "    Z-score: {:.2}, P-value: {:.4}", decl_z, decl_p)?;
+        } else {
+            writeln!(out, "    (Insufficient data for statistical test)")?;
+        }
+
+        writeln!(out, "  {} vs {} (defending error rate):", subj_a.name, subj_b.name)?;
+        let (def_z, def_p) = subj_a.defending_vs(&subj_b);
+        if !def_z.is_nan() {
+            writeln!(out, "    Z-score: {:.2}, P-value: {:.4}", def_z, def_p)?;
+        } else {
+            writeln!(out, "    (Insufficient data for statistical test)")?;
+        }
+    }
 
     writeln!(out, "\n{:=^100}", "")?;
     writeln!(out, "\nInterpretation:")?;
@@ -959,6 +1444,7 @@ pub fn compute_stats(input: &Path, top_n: usize) -> Result<String> {
     Ok(out)
 }
 
+
 // ============================================================================
 // Anonymize
 // ============================================================================
@@ -988,6 +1474,11 @@ pub struct AnonymizeConfig {
     pub map: String,
     /// Columns to anonymize
     pub columns: Vec<String>,
+    /// Subject players (real names) to guarantee a pseudonym for, even if
+    /// they have no explicit mapping and never made it into a generated one
+    /// via CSV column anonymization. Used to build `subject_mappings` without
+    /// requiring the caller to hand-author a mapping for every player.
+    pub subject_players: Vec<String>,
 }
 
 /// Run anonymize and return a result with summary and name mappings.
@@ -1099,12 +1590,25 @@ pub fn anonymize_csv(
         .collect();
     name_mappings.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
 
-    // Subject-only mappings (explicit maps) for hotspot reports
-    let mut subject_mappings: Vec<(String, String)> = anonymizer
-        .explicit_maps
-        .iter()
-        .map(|(k, v)| (k.clone(), v.clone()))
-        .collect();
+    // Subject-only mappings for hotspot reports. When explicit subject players
+    // are given, derive their pseudonyms directly (generating one via the
+    // salted hash if they weren't already seen in an anonymized column) so
+    // the caller never has to hand-author a mapping just to anonymize the
+    // hotspot report. Otherwise fall back to the explicit map, as before.
+    let mut subject_mappings: Vec<(String, String)> = if config.subject_players.is_empty() {
+        anonymizer
+            .explicit_maps
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    } else {
+        config
+            .subject_players
+            .iter()
+            .filter(|p| !p.trim().is_empty())
+            .map(|p| (p.to_lowercase(), anonymizer.anonymize(p)))
+            .collect()
+    };
     subject_mappings.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
 
     Ok(AnonymizeResult {
@@ -1121,25 +1625,317 @@ pub fn anonymize_csv(
     })
 }
 
-/// Anonymize player names in a text file using pre-built name mappings.
-///
-/// Performs case-insensitive replacement with column-aware spacing: when a name
-/// is followed by whitespace, the space count is adjusted so that column
-/// alignment is preserved. Uses simple string matching (no regex).
-///
-/// When `board_id_map` is non-empty (hotspot reports), tinyurls are replaced
-/// with Board_IDs and the anonymized LIN_URL (from `url_mappings`) is appended
-/// at the end of each matching line.
-pub fn anonymize_text_file(
-    input: &Path,
-    output: &Path,
-    name_mappings: &[(String, String)],
-    url_mappings: &HashMap<String, String>,
-    board_id_map: &HashMap<String, (String, String)>,
-) -> Result<()> {
-    let content = std::fs::read_to_string(input)
-        .with_context(|| format!("Failed to read text file: {}", input.display()))?;
-    let had_trailing_newline = content.ends_with('\n');
+/// Short-link prefixes recognized as board tinyurls in text reports.
+const SHORT_URL_PREFIXES: &[&str] = &[
+    "http://tinyurl.com/",
+    "https://tinyurl.com/",
+    "http://bit.ly/",
+    "https://bit.ly/",
+];
+
+/// Live HTTP resolver for short links that weren't already captured in a
+/// tinyurl lookup file. Resolution runs on a small dedicated rayon pool so
+/// network work is bounded to `max_concurrent` requests in flight at once,
+/// independent of whatever the global rayon pool is doing elsewhere.
+pub struct LiveUrlResolver {
+    client: reqwest::blocking::Client,
+    pool: rayon::ThreadPool,
+}
+
+impl LiveUrlResolver {
+    /// Build a resolver with the given per-request timeout and concurrency cap.
+    pub fn new(timeout: std::time::Duration, max_concurrent: usize) -> Result<Self> {
+        let client = reqwest::blocking::Client::builder()
+            .redirect(reqwest::redirect::Policy::limited(10))
+            .timeout(timeout)
+            .build()
+            .context("Failed to build HTTP client for live URL resolution")?;
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(max_concurrent.max(1))
+            .build()
+            .context("Failed to build URL resolver thread pool")?;
+        Ok(Self { client, pool })
+    }
+
+    /// Resolve each short link to its final destination URL, following
+    /// redirects. Degrades to `None` on any HTTP error or timeout rather than
+    /// failing the whole batch.
+    fn resolve_all(&self, urls: &[String]) -> HashMap<String, Option<String>> {
+        let client = &self.client;
+        self.pool.install(|| {
+            urls.par_iter()
+                .map(|url| {
+                    let resolved = client
+                        .head(url.as_str())
+                        .send()
+                        .ok()
+                        .filter(|resp| resp.status().is_success())
+                        .map(|resp| resp.url().to_string());
+                    (url.clone(), resolved)
+                })
+                .collect()
+        })
+    }
+}
+
+/// Persistent SQLite-backed cache of resolved `short_code -> (Board_ID,
+/// resolved LIN_URL)` lookups, so repeated anonymization runs over
+/// overlapping event data don't re-resolve the same short links or hammer
+/// the remote host every time.
+pub struct UrlCache {
+    conn: rusqlite::Connection,
+}
+
+impl UrlCache {
+    /// Open (creating if necessary) a cache database at `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = rusqlite::Connection::open(path)
+            .with_context(|| format!("Failed to open URL cache: {}", path.display()))?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS resolved_urls (
+                short_code       TEXT PRIMARY KEY,
+                board_id         TEXT NOT NULL,
+                resolved_lin_url TEXT NOT NULL,
+                fetched_at       INTEGER NOT NULL
+            )",
+            [],
+        )
+        .context("Failed to initialize URL cache schema")?;
+        Ok(Self { conn })
+    }
+
+    /// Look up a cached resolution for `short_code`. Entries older than `ttl`
+    /// (when given) are treated as a miss so the caller re-fetches them.
+    fn get(&self, short_code: &str, ttl: Option<std::time::Duration>) -> Result<Option<(String, String)>> {
+        let row: Option<(String, String, i64)> = self
+            .conn
+            .query_row(
+                "SELECT board_id, resolved_lin_url, fetched_at FROM resolved_urls WHERE short_code = ?1",
+                [short_code],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()
+            .context("Failed to query URL cache")?;
+
+        Ok(row.and_then(|(board_id, lin_url, fetched_at)| {
+            if let Some(ttl) = ttl {
+                let age = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs()
+                    .saturating_sub(fetched_at as u64);
+                if age > ttl.as_secs() {
+                    return None;
+                }
+            }
+            Some((board_id, lin_url))
+        }))
+    }
+
+    /// Insert or refresh a cached resolution, stamped with the current time.
+    fn put(&self, short_code: &str, board_id: &str, lin_url: &str) -> Result<()> {
+        let fetched_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO resolved_urls (short_code, board_id, resolved_lin_url, fetched_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![short_code, board_id, lin_url, fetched_at],
+            )
+            .context("Failed to write URL cache entry")?;
+        Ok(())
+    }
+}
+
+/// Scan `input` for short links not already present in `board_id_map`,
+/// resolve each one (consulting `cache` first, when given, before falling
+/// back to `resolver`), and insert a Board_ID (assigned sequentially from
+/// `next_board_id`, in order of first appearance) plus its resolved LIN_URL.
+/// Unresolvable links still get a Board_ID but an empty LIN_URL, so
+/// `anonymize_text_file` falls back to `[unknown]` for those rather than
+/// leaking the raw tinyurl.
+///
+/// This lets a text report be anonymized directly, without first running it
+/// through `fetch_cardplay` to pre-build a lookup file out of band. Returns
+/// the next free Board_ID after this file's links have been assigned.
+pub fn auto_resolve_board_ids(
+    input: &Path,
+    resolver: &LiveUrlResolver,
+    cache: Option<&UrlCache>,
+    cache_ttl: Option<std::time::Duration>,
+    board_id_map: &mut HashMap<String, (String, String)>,
+    next_board_id: usize,
+) -> Result<usize> {
+    let content = std::fs::read_to_string(input)
+        .with_context(|| format!("Failed to read text file: {}", input.display()))?;
+
+    let mut candidates: Vec<String> = Vec::new();
+    for line in content.lines() {
+        if let Some(start) = SHORT_URL_PREFIXES
+            .iter()
+            .filter_map(|prefix| line.find(prefix))
+            .min()
+        {
+            let end = line[start..]
+                .find(char::is_whitespace)
+                .map(|p| start + p)
+                .unwrap_or(line.len());
+            let url = line[start..end].to_string();
+            let key = normalize_tinyurl(&url);
+            if !board_id_map.contains_key(&key) && !candidates.contains(&url) {
+                candidates.push(url);
+            }
+        }
+    }
+
+    // Serve from cache where possible, and only hit the network for misses.
+    let mut unresolved: Vec<String> = Vec::new();
+    for url in &candidates {
+        let key = normalize_tinyurl(url);
+        if let Some(cache) = cache {
+            if let Some((board_id, lin_url)) = cache.get(&key, cache_ttl)? {
+                board_id_map.insert(key, (board_id, lin_url));
+                continue;
+            }
+        }
+        unresolved.push(url.clone());
+    }
+
+    let resolved = resolver.resolve_all(&unresolved);
+
+    let mut next_id = next_board_id;
+    for url in &unresolved {
+        let key = normalize_tinyurl(url);
+        let lin_url = resolved.get(url).cloned().flatten().unwrap_or_default();
+        let board_id = next_id.to_string();
+        if let Some(cache) = cache {
+            cache.put(&key, &board_id, &lin_url)?;
+        }
+        board_id_map.insert(key, (board_id, lin_url));
+        next_id += 1;
+    }
+
+    Ok(next_id)
+}
+
+/// A single allow/deny rule: either a literal domain/host substring match, or
+/// a regex pattern compiled once up front.
+enum UrlRule {
+    Domain(String),
+    Pattern(regex::Regex),
+}
+
+impl UrlRule {
+    fn matches(&self, url: &str) -> bool {
+        match self {
+            UrlRule::Domain(domain) => url.to_lowercase().contains(domain),
+            UrlRule::Pattern(re) => re.is_match(url),
+        }
+    }
+}
+
+/// What to do with a URL found in a text report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UrlDecision {
+    /// Rewrite to a Board_ID + anonymized LIN_URL, as usual.
+    Anonymize,
+    /// Remove the URL entirely, replacing it with `[redacted]`.
+    Strip,
+    /// Leave the URL untouched.
+    Leave,
+}
+
+/// Domain allowlist/denylist controlling which URLs in a text report get
+/// anonymized, left alone, or stripped.
+///
+/// Deny rules are checked first: a match strips the URL outright. Otherwise,
+/// if any allow rules are configured, only URLs matching one of them are
+/// anonymized — everything else is left untouched. With no allow rules at
+/// all, every URL that isn't denied is anonymized (the historical behavior).
+pub struct UrlFilterRules {
+    deny: Vec<UrlRule>,
+    allow: Vec<UrlRule>,
+}
+
+impl UrlFilterRules {
+    /// Load rules from a list file. Each non-empty, non-comment (`#`) line is
+    /// `deny <rule>` or `allow <rule>`, where `<rule>` is either a literal
+    /// domain/host substring or a `regex:<pattern>` entry compiled once here.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read URL filter rules: {}", path.display()))?;
+
+        let mut deny = Vec::new();
+        let mut allow = Vec::new();
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.splitn(2, char::is_whitespace);
+            let action = parts.next().unwrap_or("");
+            let rule_str = parts.next().unwrap_or("").trim();
+            if rule_str.is_empty() {
+                continue;
+            }
+
+            let rule = if let Some(pattern) = rule_str.strip_prefix("regex:") {
+                UrlRule::Pattern(
+                    regex::Regex::new(pattern)
+                        .with_context(|| format!("Invalid URL filter regex: {pattern}"))?,
+                )
+            } else {
+                UrlRule::Domain(rule_str.to_lowercase())
+            };
+
+            match action {
+                "deny" => deny.push(rule),
+                "allow" => allow.push(rule),
+                other => return Err(anyhow::anyhow!("Unknown URL filter action '{}'", other)),
+            }
+        }
+
+        Ok(Self { deny, allow })
+    }
+
+    /// Decide what to do with `url`.
+    pub fn decide(&self, url: &str) -> UrlDecision {
+        if self.deny.iter().any(|r| r.matches(url)) {
+            return UrlDecision::Strip;
+        }
+        if self.allow.is_empty() || self.allow.iter().any(|r| r.matches(url)) {
+            UrlDecision::Anonymize
+        } else {
+            UrlDecision::Leave
+        }
+    }
+}
+
+/// Anonymize player names in a text file using pre-built name mappings.
+///
+/// Performs case-insensitive replacement with column-aware spacing: when a name
+/// is followed by whitespace, the space count is adjusted so that column
+/// alignment is preserved. Uses simple string matching (no regex).
+///
+/// When `board_id_map` is non-empty (hotspot reports), tinyurls are replaced
+/// with Board_IDs and the anonymized LIN_URL (from `url_mappings`) is appended
+/// at the end of each matching line. `url_rules`, when given, is applied to
+/// each URL first to decide whether it should be anonymized, stripped, or
+/// left alone.
+pub fn anonymize_text_file(
+    input: &Path,
+    output: &Path,
+    name_mappings: &[(String, String)],
+    url_mappings: &HashMap<String, String>,
+    board_id_map: &HashMap<String, (String, String)>,
+    url_rules: Option<&UrlFilterRules>,
+) -> Result<()> {
+    let content = std::fs::read_to_string(input)
+        .with_context(|| format!("Failed to read text file: {}", input.display()))?;
+    let had_trailing_newline = content.ends_with('\n');
 
     let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
 
@@ -1217,14 +2013,8 @@ pub fn anonymize_text_file(
 
     // Tinyurl replacement: replace with Board_ID, append anonymized LIN_URL
     if !board_id_map.is_empty() {
-        let url_prefixes = [
-            "http://tinyurl.com/",
-            "https://tinyurl.com/",
-            "http://bit.ly/",
-            "https://bit.ly/",
-        ];
         for line in &mut lines {
-            let url_start = url_prefixes
+            let url_start = SHORT_URL_PREFIXES
                 .iter()
                 .filter_map(|prefix| line.find(prefix))
                 .min();
@@ -1233,8 +2023,17 @@ pub fn anonymize_text_file(
                     .find(char::is_whitespace)
                     .map(|p| start + p)
                     .unwrap_or(line.len());
-                let url = &line[start..url_end];
-                let key = normalize_tinyurl(url);
+                let url = line[start..url_end].to_string();
+                match url_rules.map(|rules| rules.decide(&url)) {
+                    Some(UrlDecision::Leave) => continue,
+                    Some(UrlDecision::Strip) => {
+                        *line = format!("{}[redacted]{}", &line[..start], &line[url_end..]);
+                        continue;
+                    }
+                    Some(UrlDecision::Anonymize) | None => {}
+                }
+
+                let key = normalize_tinyurl(&url);
                 let board_id = board_id_map
                     .get(&key)
                     .map(|(id, _)| id.as_str())
@@ -1280,6 +2079,23 @@ pub struct AnonymizeAllConfig {
     pub hotspot_input: Option<PathBuf>,
     /// Optional hotspot report output path.
     pub hotspot_output: Option<PathBuf>,
+    /// Subject players (real names) to guarantee a pseudonym for in the
+    /// hotspot report, without requiring an explicit mapping.
+    pub subject_players: Vec<String>,
+    /// When true, resolve any short links in the hotspot report that aren't
+    /// already covered by the tinyurl lookup file live over HTTP, instead of
+    /// requiring the caller to pre-resolve them with `fetch_cardplay` first.
+    pub live_resolve_urls: bool,
+    /// Optional path to a persistent SQLite cache of resolved short links,
+    /// consulted (and updated) before falling back to a live HTTP request.
+    pub url_cache_path: Option<PathBuf>,
+    /// How long a cached resolution stays valid before it's re-fetched.
+    /// `None` means cached entries never expire.
+    pub url_cache_ttl: Option<std::time::Duration>,
+    /// Optional path to a URL allowlist/denylist rules file (see
+    /// `UrlFilterRules::from_file`) controlling which URLs in the text
+    /// reports get anonymized, left alone, or stripped.
+    pub url_rules_path: Option<PathBuf>,
 }
 
 /// Progress information for the anonymize operation.
@@ -1301,7 +2117,7 @@ pub fn anonymize_all(
 ) -> Result<String> {
     // Load tinyurl → Board_ID mapping from lookup file if available
     let lookup_path = derive_lookup_path(&config.csv_input);
-    let board_id_map = if lookup_path.exists() {
+    let mut board_id_map = if lookup_path.exists() {
         load_lookup_board_ids(&lookup_path)?
     } else {
         HashMap::new()
@@ -1315,6 +2131,7 @@ pub fn anonymize_all(
         key: config.key.clone(),
         map: config.map.clone(),
         columns: config.columns.clone(),
+        subject_players: config.subject_players.clone(),
     };
     let csv_result = anonymize_csv(&csv_config, &board_id_map, total_rows, &mut on_progress)?;
 
@@ -1322,6 +2139,11 @@ pub fn anonymize_all(
 
     let empty_urls = HashMap::new();
     let empty_board_ids: HashMap<String, (String, String)> = HashMap::new();
+    let url_rules = config
+        .url_rules_path
+        .as_ref()
+        .map(|p| UrlFilterRules::from_file(p))
+        .transpose()?;
 
     if let (Some(input), Some(output)) = (&config.concise_input, &config.concise_output) {
         on_progress(&AnonProgress {
@@ -1335,6 +2157,7 @@ pub fn anonymize_all(
             &csv_result.name_mappings,
             &empty_urls,
             &empty_board_ids,
+            url_rules.as_ref(),
         )?;
     }
 
@@ -1344,6 +2167,32 @@ pub fn anonymize_all(
             total: total_rows,
             phase: "Processing hotspot report...",
         });
+        // If the lookup file didn't cover every short link in the hotspot
+        // report (or there was no lookup file at all), resolve the rest live
+        // rather than leaving them as "[unknown]".
+        if config.live_resolve_urls {
+            let next_board_id = board_id_map
+                .values()
+                .filter_map(|(id, _)| id.parse::<usize>().ok())
+                .max()
+                .map(|n| n + 1)
+                .unwrap_or(1);
+            let resolver = LiveUrlResolver::new(std::time::Duration::from_secs(10), 8)?;
+            let cache = config
+                .url_cache_path
+                .as_ref()
+                .map(|p| UrlCache::open(p))
+                .transpose()?;
+            auto_resolve_board_ids(
+                input,
+                &resolver,
+                cache.as_ref(),
+                config.url_cache_ttl,
+                &mut board_id_map,
+                next_board_id,
+            )?;
+        }
+
         // Hotspot reports only contain the 2 subject players — use subject_mappings
         // to avoid spurious matches (e.g. player named "None" replacing "Vul: None")
         anonymize_text_file(
@@ -1352,6 +2201,7 @@ pub fn anonymize_all(
             &csv_result.subject_mappings,
             &csv_result.url_mappings,
             &board_id_map,
+            url_rules.as_ref(),
         )?;
     }
 
@@ -1584,14 +2434,9 @@ fn format_vulnerability(v: &Vulnerability) -> &'static str {
     }
 }
 
-/// Write a single lookup row from parsed LIN data.
-fn write_lookup_row(
-    out: &mut csv::Writer<impl std::io::Write>,
-    board_id: usize,
-    tinyurl: &str,
-    lin: &LinData,
-    lin_url: &str,
-) -> Result<()> {
+/// Build a single lookup row's fields from parsed LIN data, ending with the
+/// source URL hash used for hash-aware resume (see `hash_source_url`).
+fn lookup_row_fields(board_id: usize, tinyurl: &str, lin: &LinData, lin_url: &str, src_hash: &str) -> Vec<String> {
     let board_header = lin.board_header.as_deref().unwrap_or("");
     let cardplay = lin.format_cardplay_by_trick();
     let claim = lin.claim.map(|c| c.to_string()).unwrap_or_default();
@@ -1600,49 +2445,126 @@ fn write_lookup_row(
     let explanations = format_explanations(lin);
     let vulnerability = format_vulnerability(&lin.vulnerability);
 
-    out.write_record([
-        &board_id.to_string(),
-        tinyurl,
-        board_header,
-        &lin.player_names[0], // S
-        &lin.player_names[1], // W
-        &lin.player_names[2], // N
-        &lin.player_names[3], // E
-        &format!("{:?}", lin.dealer),
-        vulnerability,
-        &deal_pbn,
-        &auction,
-        &explanations,
-        &cardplay,
-        &claim,
-        lin_url,
-    ])?;
+    vec![
+        board_id.to_string(),
+        tinyurl.to_string(),
+        board_header.to_string(),
+        lin.player_names[0].clone(), // S
+        lin.player_names[1].clone(), // W
+        lin.player_names[2].clone(), // N
+        lin.player_names[3].clone(), // E
+        format!("{:?}", lin.dealer),
+        vulnerability.to_string(),
+        deal_pbn,
+        auction,
+        explanations,
+        cardplay,
+        claim,
+        lin_url.to_string(),
+        src_hash.to_string(),
+    ]
+}
+
+/// Build an empty lookup row's fields (for missing/error URLs).
+fn lookup_empty_row_fields(board_id: usize, tinyurl: &str, src_hash: &str) -> Vec<String> {
+    vec![
+        board_id.to_string(),
+        tinyurl.to_string(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        src_hash.to_string(),
+    ]
+}
+
+/// Write the full lookup file from in-memory rows (header + one line per
+/// row), used as the `write_atomic` body so a crash never leaves a
+/// partially-written last row.
+fn write_lookup_rows(path: &Path, rows: &[Vec<String>]) -> Result<()> {
+    let mut out = csv::WriterBuilder::new()
+        .flexible(true)
+        .from_path(path)
+        .context("Failed to create lookup CSV")?;
+    out.write_record(LOOKUP_FIELDS)?;
+    for row in rows {
+        out.write_record(row)?;
+    }
+    out.flush()?;
     Ok(())
 }
 
-/// Write an empty lookup row (for missing/error URLs).
-fn write_lookup_empty_row(
-    out: &mut csv::Writer<impl std::io::Write>,
-    board_id: usize,
-    tinyurl: &str,
-) -> Result<()> {
-    out.write_record([
-        &board_id.to_string(),
-        tinyurl,
-        "",
-        "",
-        "",
-        "",
-        "",
-        "",
-        "",
-        "",
-        "",
-        "",
-        "",
-        "",
-        "",
-    ])?;
+/// An existing lookup row, read back in for hash-aware resume: its raw
+/// fields (so an unchanged row can be copied through verbatim) plus the
+/// parsed `Cardplay` and `Src_Hash` columns.
+struct ExistingLookupRow {
+    fields: Vec<String>,
+    cardplay: String,
+    src_hash: String,
+}
+
+/// Load a previously-written lookup file, keyed by Board_ID, for
+/// `generate_lookup_file`'s resume check. Rows from a lookup file that
+/// predates the `Src_Hash` column have an empty hash, which never matches,
+/// so they're simply re-fetched rather than trusted blindly.
+fn load_existing_lookup_rows(path: &Path) -> Result<HashMap<usize, ExistingLookupRow>> {
+    let mut data = HashMap::new();
+    let mut reader = ReaderBuilder::new().flexible(true).from_path(path)?;
+    let headers = reader.headers()?.clone();
+
+    let board_id_idx = match headers.iter().position(|h| h == "Board_ID") {
+        Some(idx) => idx,
+        None => return Ok(data),
+    };
+    let cardplay_idx = headers.iter().position(|h| h == "Cardplay");
+    let src_hash_idx = headers.iter().position(|h| h == "Src_Hash");
+
+    for result in reader.records() {
+        let record = result?;
+        let board_id: usize = record
+            .get(board_id_idx)
+            .unwrap_or("0")
+            .trim()
+            .parse()
+            .unwrap_or(0);
+        if board_id == 0 {
+            continue;
+        }
+        let cardplay = cardplay_idx.and_then(|i| record.get(i)).unwrap_or("").to_string();
+        let src_hash = src_hash_idx.and_then(|i| record.get(i)).unwrap_or("").to_string();
+        let fields: Vec<String> = record.iter().map(|s| s.to_string()).collect();
+        data.insert(board_id, ExistingLookupRow { fields, cardplay, src_hash });
+    }
+
+    Ok(data)
+}
+
+/// Short, non-cryptographic hash of a source URL, used only to detect when a
+/// row's input has changed across runs -- not a security primitive.
+fn hash_source_url(url: &str) -> String {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    url.trim().hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Write `contents` to a temp file next to `path` and atomically rename it
+/// into place, so a crash mid-write never leaves a partial file that a later
+/// resume or completeness check would mistake for a finished one.
+fn write_atomic(path: &Path, contents: impl FnOnce(&Path) -> Result<()>) -> Result<()> {
+    let tmp_path = PathBuf::from(format!("{}.tmp", path.display()));
+    contents(&tmp_path)?;
+    std::fs::rename(&tmp_path, path)
+        .with_context(|| format!("Failed to atomically rename {:?} -> {:?}", tmp_path, path))?;
     Ok(())
 }
 
@@ -1663,6 +2585,7 @@ const LOOKUP_FIELDS: &[&str] = &[
     "Cardplay",
     "Claim",
     "LIN_URL",
+    "Src_Hash",
 ];
 
 // ============================================================================
@@ -1724,72 +2647,120 @@ fn fix_bbo_csv_line(line: &str) -> String {
 // ============================================================================
 // Player Stats (for compute_stats)
 // ============================================================================
-
-#[derive(Default, Clone)]
-struct PlayerStats {
-    name: String,
-    total_deals: u64,
-    declaring_plays: u64,
-    declaring_errors: u64,
-    declaring_deals: u64,
-    defending_plays: u64,
-    defending_errors: u64,
-    defending_deals: u64,
-}
-
-impl PlayerStats {
-    fn new(name: &str) -> Self {
-        PlayerStats {
-            name: name.to_string(),
-            ..Default::default()
+//
+// PlayerStats itself and the statistics engine (wilson_ci,
+// two_proportion_z_test, bootstrap_def_minus_decl, etc.) live in
+// `crate::stats`, shared with `bbo_csv`'s own `compute_stats` so the GUI and
+// CLI report the same numbers from the same code.
+
+/// Parse a `DD_Error_Categories` field like `"N:opening-lead=1,duck=2;S:..."`
+/// into a per-seat map of rule id -> count.
+fn parse_error_categories(raw: &str) -> HashMap<char, HashMap<String, u64>> {
+    let mut by_seat = HashMap::new();
+    for seat_part in raw.split(';') {
+        let seat_part = seat_part.trim();
+        if seat_part.is_empty() {
+            continue;
         }
-    }
+        let Some((seat_str, cats_str)) = seat_part.split_once(':') else {
+            continue;
+        };
+        let Some(seat_char) = seat_str.trim().chars().next() else {
+            continue;
+        };
 
-    fn declaring_error_rate(&self) -> f64 {
-        if self.declaring_plays == 0 {
-            0.0
-        } else {
-            self.declaring_errors as f64 / self.declaring_plays as f64 * 100.0
+        let mut categories = HashMap::new();
+        for cat in cats_str.split(',') {
+            if let Some((id, count_str)) = cat.split_once('=') {
+                if let Ok(count) = count_str.trim().parse::<u64>() {
+                    categories.insert(id.trim().to_string(), count);
+                }
+            }
         }
+        by_seat.insert(seat_char, categories);
     }
+    by_seat
+}
 
-    fn defending_error_rate(&self) -> f64 {
-        if self.defending_plays == 0 {
-            0.0
-        } else {
-            self.defending_errors as f64 / self.defending_plays as f64 * 100.0
-        }
-    }
+/// Logistic win-probability-style weight for a single play's DD cost: a cost
+/// of `c0` maps to 0.5, and `k` controls how sharply probability rises
+/// around it. Unlike a flat "cost > 0 is an error" threshold, this lets a
+/// one-trick blunder count far more than a borderline inaccuracy would.
+fn p_loss(cost: f64, k: f64, c0: f64) -> f64 {
+    1.0 / (1.0 + (-k * (cost - c0)).exp())
+}
 
-    fn merge(&mut self, other: &PlayerStats) {
-        self.total_deals += other.total_deals;
-        self.declaring_plays += other.declaring_plays;
-        self.declaring_errors += other.declaring_errors;
-        self.declaring_deals += other.declaring_deals;
-        self.defending_plays += other.defending_plays;
-        self.defending_errors += other.defending_errors;
-        self.defending_deals += other.defending_deals;
-    }
+/// Walks one row's `DD_Analysis` cost string trick by trick (the same
+/// seat-rotation walk as `display_hand`'s "DD ANALYSIS SUMMARY" section),
+/// adding each play's [`p_loss`] to the attributed player's declaring or
+/// defending loss sum in `player_stats`. `accuracy_seats` maps a seat letter
+/// to the player name its plays are attributed to -- the declaring side's
+/// plays (declarer and dummy) are both attributed to the declarer.
+#[allow(clippy::too_many_arguments)]
+fn accumulate_play_accuracy(
+    dd_analysis: &str,
+    cardplay: &str,
+    contract: &str,
+    declarer: &str,
+    accuracy_seats: &[(char, String); 4],
+    declaring_seats: [char; 2],
+    k: f64,
+    c0: f64,
+    player_stats: &mut HashMap<String, PlayerStats>,
+) {
+    let initial_leader = match declarer.chars().next() {
+        Some('N') => 'E',
+        Some('E') => 'S',
+        Some('S') => 'W',
+        Some('W') => 'N',
+        _ => return,
+    };
 
-    fn declaring_ci(&self) -> f64 {
-        if self.declaring_plays < 30 {
-            return f64::NAN;
-        }
-        let p = self.declaring_errors as f64 / self.declaring_plays as f64;
-        let n = self.declaring_plays as f64;
-        1.96 * (p * (1.0 - p) / n).sqrt() * 100.0
-    }
+    let tricks: Vec<&str> = cardplay.split('|').collect();
+    let mut current_leader = initial_leader;
+
+    for (trick_idx, trick_str) in dd_analysis.split('|').enumerate() {
+        if let Some(colon_idx) = trick_str.find(':') {
+            let costs: Vec<u8> = trick_str[colon_idx + 1..]
+                .split(',')
+                .filter_map(|s| s.trim().parse().ok())
+                .collect();
+
+            if costs.len() == 4 {
+                let seats = get_seat_order(current_leader);
+                for (i, &cost) in costs.iter().enumerate() {
+                    let seat = seats[i];
+                    let Some((_, player)) = accuracy_seats.iter().find(|(s, _)| *s == seat) else {
+                        continue;
+                    };
+                    if player.is_empty() {
+                        continue;
+                    }
+                    let loss = p_loss(cost as f64, k, c0);
+                    let stats = player_stats
+                        .entry(player.clone())
+                        .or_insert_with(|| PlayerStats::new(player));
+                    if declaring_seats.contains(&seat) {
+                        stats.declaring_ploss_sum += loss;
+                        stats.declaring_ploss_count += 1;
+                    } else {
+                        stats.defending_ploss_sum += loss;
+                        stats.defending_ploss_count += 1;
+                    }
+                }
+            }
 
-    fn defending_ci(&self) -> f64 {
-        if self.defending_plays < 30 {
-            return f64::NAN;
+            if trick_idx < tricks.len() {
+                let cards: Vec<&str> = tricks[trick_idx].split_whitespace().collect();
+                if let Some(winner) = determine_trick_winner_for_display(&cards, current_leader, contract) {
+                    current_leader = winner;
+                }
+            }
         }
-        let p = self.defending_errors as f64 / self.defending_plays as f64;
-        let n = self.defending_plays as f64;
-        1.96 * (p * (1.0 - p) / n).sqrt() * 100.0
     }
 }
 
+
 // ============================================================================
 // Anonymizer
 // ============================================================================
@@ -2241,79 +3212,107 @@ impl Anonymizer {
     }
 
     fn generate_name(&mut self, username: &str) -> String {
-        let combined = format!("{}:{}", self.key, username);
-        let hash = self.simple_hash(&combined);
-
-        let first_idx = (hash % FIRST_NAMES.len() as u64) as usize;
-        let surname_idx = ((hash / FIRST_NAMES.len() as u64) % SURNAMES.len() as u64) as usize;
-
-        let mut candidate = format!("{}_{}", FIRST_NAMES[first_idx], SURNAMES[surname_idx]);
+        let base = pseudonym_for(username, &self.key);
+        let mut candidate = base.clone();
 
         let mut suffix = 2;
         while self.used_names.contains(&candidate) {
-            candidate = format!(
-                "{}_{}_{suffix}",
-                FIRST_NAMES[first_idx], SURNAMES[surname_idx]
-            );
+            candidate = format!("{base}_{suffix}");
             suffix += 1;
         }
 
         self.used_names.insert(candidate.clone());
         candidate
     }
+}
 
-    fn simple_hash(&self, s: &str) -> u64 {
-        let mut hash: u64 = 0xcbf29ce484222325;
-        for byte in s.bytes() {
-            hash ^= byte as u64;
-            hash = hash.wrapping_mul(0x100000001b3);
-        }
-        hash
+/// FNV-1a hash (64-bit) of a string's UTF-8 bytes.
+fn fnv1a_hash(s: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in s.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
     }
+    hash
+}
+
+/// Derive a stable "First_Last" pseudonym from `token`, salted with `salt`.
+///
+/// Hashes `"{salt}:{token}"` with FNV-1a and indexes into the first-name and
+/// surname word lists, so the same token always yields the same pseudonym for
+/// a given salt, but the mapping can't be inverted or correlated across runs
+/// that use a different salt.
+fn pseudonym_for(token: &str, salt: &str) -> String {
+    let hash = fnv1a_hash(&format!("{salt}:{token}"));
+    let first_idx = (hash % FIRST_NAMES.len() as u64) as usize;
+    let surname_idx = ((hash / FIRST_NAMES.len() as u64) % SURNAMES.len() as u64) as usize;
+    format!("{}_{}", FIRST_NAMES[first_idx], SURNAMES[surname_idx])
 }
 
 /// Anonymize player names embedded in a BBO LIN URL.
-fn anonymize_lin_url(url: &str, anonymizer: &mut Anonymizer) -> String {
-    use regex::Regex;
+fn anonymize_lin_url(raw_url: &str, anonymizer: &mut Anonymizer) -> String {
+    // Parse properly rather than regex-matching the raw query string, so
+    // percent-encoding, trailing whitespace, extra query params and
+    // fragments all round-trip the way a browser would see them. Only the
+    // `lin` parameter's `pn|...|` player-name segment is rewritten.
+    let Ok(mut parsed) = Url::parse(raw_url.trim()) else {
+        return raw_url.to_string();
+    };
+
+    let Some(lin_value) = parsed
+        .query_pairs()
+        .find(|(k, _)| k == "lin")
+        .map(|(_, v)| v.into_owned())
+    else {
+        return raw_url.to_string();
+    };
+
+    let anon_lin = anonymize_pn_segment(&lin_value, anonymizer);
 
+    // `query_pairs()` already gives us every other param decoded; collect
+    // them before taking a mutable borrow to rebuild the query string.
+    let other_pairs: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(k, _)| k != "lin")
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+
+    {
+        let mut pairs = parsed.query_pairs_mut();
+        pairs.clear();
+        for (k, v) in &other_pairs {
+            pairs.append_pair(k, v);
+        }
+        pairs.append_pair("lin", &anon_lin);
+    }
+
+    parsed.to_string()
+}
+
+/// Replace the player names in a LIN string's `pn|A,B,C,D|` segment with
+/// their pseudonyms, leaving the rest of the (pipe-delimited) LIN data as-is.
+fn anonymize_pn_segment(lin: &str, anonymizer: &mut Anonymizer) -> String {
+    use regex::Regex;
     lazy_static::lazy_static! {
-        static ref PN_ENCODED: Regex = Regex::new(r"(?i)pn%7C([^%]+(?:%2C[^%]+)*)%7C").unwrap();
         static ref PN_LITERAL: Regex = Regex::new(r"pn\|([^|]+)\|").unwrap();
     }
 
-    let result = PN_ENCODED.replace(url, |caps: &regex::Captures| {
-        let names_str = &caps[1];
-        let anon_names: Vec<String> = names_str
-            .split("%2C")
-            .map(|name| {
-                let name = name.trim();
-                if name.is_empty() {
-                    String::new()
-                } else {
-                    anonymizer.anonymize(name)
-                }
-            })
-            .collect();
-        format!("pn%7C{}%7C", anon_names.join("%2C"))
-    });
-
-    let result = PN_LITERAL.replace(&result, |caps: &regex::Captures| {
-        let names = &caps[1];
-        let anon_names: Vec<String> = names
-            .split(',')
-            .map(|name| {
-                let name = name.trim();
-                if name.is_empty() {
-                    String::new()
-                } else {
-                    anonymizer.anonymize(name)
-                }
-            })
-            .collect();
-        format!("pn|{}|", anon_names.join(","))
-    });
-
-    result.to_string()
+    PN_LITERAL
+        .replace(lin, |caps: &regex::Captures| {
+            let anon_names: Vec<String> = caps[1]
+                .split(',')
+                .map(|name| {
+                    let name = name.trim();
+                    if name.is_empty() {
+                        String::new()
+                    } else {
+                        anonymizer.anonymize(name)
+                    }
+                })
+                .collect();
+            format!("pn|{}|", anon_names.join(","))
+        })
+        .to_string()
 }
 
 // ============================================================================
@@ -2332,6 +3331,31 @@ pub struct AnalyzeDdConfig {
     pub resume: bool,
     /// Save progress every N rows
     pub checkpoint_interval: usize,
+    /// How a per-card DD cost is attributed to the `DD_*_Errors` columns
+    pub error_mode: DdErrorMode,
+}
+
+/// How a per-card DD cost counts toward a seat's `DD_*_Errors` tally.
+///
+/// In every mode the raw per-card cost string in `DD_Analysis` is left
+/// untouched; only the error counters derived from it change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DdErrorMode {
+    /// Every card with a nonzero DD cost counts as an error (current/default behavior).
+    Raw,
+    /// A card's cost only counts if removing it would flip the contract
+    /// between making and going down, i.e. the error survived to matter.
+    ResultAffecting,
+    /// Only the first suboptimal card in each unbroken run of errors by the
+    /// same seat counts; later cards in that run are typically forced
+    /// consequences of the first one.
+    FirstErrorOnly,
+}
+
+impl Default for DdErrorMode {
+    fn default() -> Self {
+        DdErrorMode::Raw
+    }
 }
 
 /// Progress information for the DD analysis operation.
@@ -2410,8 +3434,20 @@ struct DdRowData {
     declarer: String,
 }
 
+/// Caps how many dispatched work items may be computing at once, which in
+/// turn bounds how far the writer's reorder buffer can grow ahead of the
+/// next row it's waiting to flush.
+const DD_MAX_IN_FLIGHT: usize = 256;
+
 /// Run double-dummy analysis on a CSV of cardplay data.
 ///
+/// This streams rows through the pipeline rather than buffering the whole
+/// file: each row is read once, handed to rayon as soon as it's known to
+/// need DD analysis, and a dedicated writer thread re-orders completed rows
+/// by `row_idx` in a bounded buffer, flushing the longest available prefix
+/// as soon as it's contiguous. Peak memory is governed by `DD_MAX_IN_FLIGHT`
+/// rather than by the number of rows in the file.
+///
 /// Calls `on_progress` periodically (~10 times/second). Return `false` to cancel.
 pub fn analyze_dd(
     config: &AnalyzeDdConfig,
@@ -2442,6 +3478,15 @@ pub fn analyze_dd(
         HashSet::new()
     };
 
+    // A count-only pass gives progress reporting an accurate total without
+    // holding every row of the real pass in memory at once.
+    let total_rows = ReaderBuilder::new()
+        .flexible(true)
+        .from_path(&config.input)
+        .context("Failed to open input CSV")?
+        .records()
+        .count();
+
     // Prepare output headers
     let mut output_headers = headers.clone();
     if !dd_col_exists {
@@ -2459,266 +3504,327 @@ pub fn analyze_dd(
         output_headers.push_field("DD_Analysis");
     }
 
-    // Collect all rows and prepare work items
-    let mut all_records: Vec<StringRecord> = Vec::new();
-    let mut work_items: Vec<DdWorkItem> = Vec::new();
-    let mut skipped_incomplete = 0usize;
-    let mut skipped_passout = 0usize;
-    let mut skipped_resume = 0usize;
-
-    for (row_idx, result) in reader.records().enumerate() {
-        let record = result.context("Failed to read CSV row")?;
-        all_records.push(record.clone());
+    let mut writer = Writer::from_path(&config.output).context("Failed to create output CSV")?;
+    writer.write_record(&output_headers)?;
 
-        let ref_id = record.get(col_indices.ref_col).unwrap_or("").to_string();
+    // Shared atomics for progress and backpressure. `skipped_resume`,
+    // `skipped_incomplete`, `skipped_passout` and `to_process` are written
+    // only from the dispatch loop below but are polled by the monitor
+    // thread, so they're atomics rather than plain locals.
+    let processed_count = AtomicUsize::new(0);
+    let error_count = AtomicUsize::new(0);
+    let skipped_resume = AtomicUsize::new(0);
+    let skipped_incomplete = AtomicUsize::new(0);
+    let skipped_passout = AtomicUsize::new(0);
+    let to_process = AtomicUsize::new(0);
+    let rows_resolved = AtomicUsize::new(0);
+    let in_flight = AtomicUsize::new(0);
+    let cancelled = AtomicBool::new(false);
+    let done = AtomicBool::new(false);
 
-        if config.resume && existing_refs.contains(&ref_id) {
-            skipped_resume += 1;
-            continue;
-        }
+    let (result_tx, result_rx) =
+        std::sync::mpsc::sync_channel::<(usize, StringRecord, Option<DdResultEntry>)>(
+            DD_MAX_IN_FLIGHT,
+        );
 
-        let max_dd: Option<i8> = col_indices
-            .max_dd_col
-            .and_then(|col| record.get(col))
-            .and_then(|s| s.parse::<i8>().ok());
-
-        if max_dd == Some(-1) {
-            skipped_incomplete += 1;
-            continue;
-        }
-
-        let cardplay = record
-            .get(col_indices.cardplay_col)
-            .unwrap_or("")
-            .to_string();
-
-        if cardplay.is_empty() || cardplay.starts_with("ERROR:") {
-            continue;
-        }
-
-        if let Some(row_data) = dd_extract_row_data(&record, &col_indices) {
-            let contract_upper = row_data.contract.to_uppercase();
-            if contract_upper.starts_with('0') || contract_upper == "P" || contract_upper == "PASS"
-            {
-                skipped_passout += 1;
-                continue;
-            }
-
-            work_items.push(DdWorkItem {
-                row_idx,
-                ref_id,
-                deal_pbn: row_data.deal_pbn,
-                cardplay,
-                contract: row_data.contract,
-                declarer: row_data.declarer,
-                max_dd,
-            });
-        }
-    }
-
-    let total_rows = all_records.len();
-    let to_process = work_items.len();
-    let skipped_no_work = total_rows - to_process - skipped_resume;
-
-    if to_process == 0 {
-        return Ok(format!(
-            "Nothing to process ({} rows, {} already done, {} incomplete, {} passout)",
-            total_rows, skipped_resume, skipped_incomplete, skipped_passout
-        ));
-    }
-
-    // Shared atomics for progress
-    let processed_count = AtomicUsize::new(0);
-    let error_count = AtomicUsize::new(0);
-    let cancelled = AtomicBool::new(false);
-    let done = AtomicBool::new(false);
-
-    // Results map
-    let results: Mutex<HashMap<usize, DdResultEntry>> = Mutex::new(HashMap::new());
-
-    // Run monitor thread + parallel processing within a scope.
-    // std::thread::scope automatically joins all spawned threads on exit.
-    std::thread::scope(|s| {
-        let processed_ref = &processed_count;
-        let error_ref = &error_count;
-        let cancelled_ref = &cancelled;
-        let done_ref = &done;
-
-        // skipped_all = rows not needing analysis (resume + incomplete/passout/etc)
-        let skipped_all = skipped_no_work + skipped_resume;
+    let (dd_matches, dd_mismatches) = std::thread::scope(|s| -> Result<(usize, Vec<(usize, u8, i8)>)> {
+        let error_ref = &error_count;
+        let resolved_ref = &rows_resolved;
+        let skipped_resume_ref = &skipped_resume;
+        let cancelled_ref = &cancelled;
+        let done_ref = &done;
 
         s.spawn(move || {
             let mut on_progress = on_progress;
             loop {
                 std::thread::sleep(std::time::Duration::from_millis(100));
-                let completed = processed_ref.load(Ordering::Relaxed);
-                let errors = error_ref.load(Ordering::Relaxed);
                 let progress = DdProgress {
-                    completed: completed + skipped_all,
+                    completed: resolved_ref.load(Ordering::Relaxed),
                     total: total_rows,
-                    errors,
-                    skipped: skipped_resume,
+                    errors: error_ref.load(Ordering::Relaxed),
+                    skipped: skipped_resume_ref.load(Ordering::Relaxed),
                 };
                 if !on_progress(&progress) {
                     cancelled_ref.store(true, Ordering::Relaxed);
                 }
                 if done_ref.load(Ordering::Relaxed) {
-                    // Send final progress update
-                    let completed = processed_ref.load(Ordering::Relaxed);
-                    let errors = error_ref.load(Ordering::Relaxed);
                     let _ = on_progress(&DdProgress {
-                        completed: completed + skipped_all,
+                        completed: resolved_ref.load(Ordering::Relaxed),
                         total: total_rows,
-                        errors,
-                        skipped: skipped_resume,
+                        errors: error_ref.load(Ordering::Relaxed),
+                        skipped: skipped_resume_ref.load(Ordering::Relaxed),
                     });
                     break;
                 }
             }
         });
 
-        // Process work items in parallel.
-        // Wrap each call in catch_unwind so bridge-solver panics don't kill
-        // rayon threads and stall the entire analysis.
-        work_items.par_iter().for_each(|item| {
-            if cancelled.load(Ordering::Relaxed) {
-                return;
-            }
+        // Writer thread: holds a reorder buffer keyed by row_idx and flushes
+        // the longest contiguous prefix available after each arrival, so it
+        // never needs to see more rows at once than the pipeline has in flight.
+        let writer_handle = s.spawn(move || -> Result<(usize, Vec<(usize, u8, i8)>)> {
+            let mut pending: HashMap<usize, (StringRecord, Option<DdResultEntry>)> =
+                HashMap::new();
+            let mut next_write = 0usize;
+            let mut dd_matches = 0usize;
+            let mut dd_mismatches: Vec<(usize, u8, i8)> = Vec::new();
+
+            for (row_idx, record, entry) in result_rx.iter() {
+                pending.insert(row_idx, (record, entry));
+
+                while let Some((record, entry)) = pending.remove(&next_write) {
+                    let mut output_record = record;
+
+                    if !dd_col_exists {
+                        if let Some(entry) = &entry {
+                            output_record.push_field(
+                                &entry.computed_dd.map(|d| d.to_string()).unwrap_or_default(),
+                            );
+                            let dd_match = match (entry.computed_dd, entry.input_max_dd) {
+                                (Some(computed), Some(input)) if input >= 0 => {
+                                    if computed as i8 == input { "true" } else { "false" }
+                                }
+                                _ => "",
+                            };
+                            output_record.push_field(dd_match);
+                            output_record.push_field(&entry.ol_error.to_string());
+                            output_record.push_field(&entry.plays_n.to_string());
+                            output_record.push_field(&entry.plays_s.to_string());
+                            output_record.push_field(&entry.plays_e.to_string());
+                            output_record.push_field(&entry.plays_w.to_string());
+                            output_record.push_field(&entry.errors_n.to_string());
+                            output_record.push_field(&entry.errors_s.to_string());
+                            output_record.push_field(&entry.errors_e.to_string());
+                            output_record.push_field(&entry.errors_w.to_string());
+                            output_record.push_field(&entry.analysis);
+                        } else {
+                            for _ in 0..12 {
+                                output_record.push_field("");
+                            }
+                        }
+                    }
 
-            let entry = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                dd_compute_analysis(item)
-            })) {
-                Ok(Ok(output)) => DdResultEntry {
-                    analysis: output.analysis,
-                    computed_dd: Some(output.initial_dd),
-                    input_max_dd: item.max_dd,
-                    ol_error: output.ol_error,
-                    plays_n: output.plays_n,
-                    plays_s: output.plays_s,
-                    plays_e: output.plays_e,
-                    plays_w: output.plays_w,
-                    errors_n: output.errors_n,
-                    errors_s: output.errors_s,
-                    errors_e: output.errors_e,
-                    errors_w: output.errors_w,
-                },
-                Ok(Err(e)) => {
-                    error_count.fetch_add(1, Ordering::Relaxed);
-                    log::warn!("Row {}: DD analysis error: {}", item.row_idx + 1, e);
-                    DdResultEntry {
-                        analysis: format!("ERROR: {}", e),
-                        computed_dd: None,
-                        input_max_dd: item.max_dd,
-                        ol_error: 0,
-                        plays_n: 0,
-                        plays_s: 0,
-                        plays_e: 0,
-                        plays_w: 0,
-                        errors_n: 0,
-                        errors_s: 0,
-                        errors_e: 0,
-                        errors_w: 0,
+                    if let Some(entry) = &entry {
+                        if let (Some(computed), Some(input_dd)) =
+                            (entry.computed_dd, entry.input_max_dd)
+                        {
+                            if input_dd >= 0 {
+                                if computed as i8 == input_dd {
+                                    dd_matches += 1;
+                                } else {
+                                    dd_mismatches.push((next_write + 2, computed, input_dd));
+                                }
+                            }
+                        }
                     }
-                }
-                Err(panic_info) => {
-                    error_count.fetch_add(1, Ordering::Relaxed);
-                    let msg = if let Some(s) = panic_info.downcast_ref::<&str>() {
-                        (*s).to_string()
-                    } else if let Some(s) = panic_info.downcast_ref::<String>() {
-                        s.clone()
-                    } else {
-                        "unknown panic".to_string()
-                    };
-                    log::warn!("Row {}: DD solver panic: {}", item.row_idx + 1, msg);
-                    DdResultEntry {
-                        analysis: format!("PANIC: {}", msg),
-                        computed_dd: None,
-                        input_max_dd: item.max_dd,
-                        ol_error: 0,
-                        plays_n: 0,
-                        plays_s: 0,
-                        plays_e: 0,
-                        plays_w: 0,
-                        errors_n: 0,
-                        errors_s: 0,
-                        errors_e: 0,
-                        errors_w: 0,
+
+                    writer.write_record(&output_record)?;
+                    next_write += 1;
+
+                    if next_write % config.checkpoint_interval == 0 {
+                        writer.flush()?;
                     }
                 }
-            };
+            }
 
-            results.lock().unwrap().insert(item.row_idx, entry);
-            processed_count.fetch_add(1, Ordering::Relaxed);
+            writer.flush()?;
+            Ok((dd_matches, dd_mismatches))
         });
 
-        done.store(true, Ordering::Relaxed);
-    });
+        // Read + dispatch loop, running on this thread. Work items are
+        // handed to rayon via `rayon::scope`, which only blocks once every
+        // spawned item has finished, so dispatch and computation overlap
+        // with reading instead of waiting for the whole file up front.
+        rayon::scope(|rs| {
+            for (row_idx, result) in reader.records().enumerate() {
+                let record = match result {
+                    Ok(r) => r,
+                    Err(e) => {
+                        // A single malformed row shouldn't abort a
+                        // multi-hundred-thousand-row streaming run; skip it
+                        // and keep going.
+                        log::warn!("Row {}: failed to read CSV row: {}", row_idx + 1, e);
+                        continue;
+                    }
+                };
 
-    let was_cancelled = cancelled.load(Ordering::Relaxed);
+                let ref_id = record.get(col_indices.ref_col).unwrap_or("").to_string();
 
-    // Write output CSV
-    let results_map = results.into_inner().unwrap();
-    let mut writer = Writer::from_path(&config.output).context("Failed to create output CSV")?;
-    writer.write_record(&output_headers)?;
+                // Once cancelled, every remaining row is passed through
+                // unchanged rather than dispatched, so the writer still sees
+                // every row_idx and the reorder buffer can always drain.
+                if cancelled.load(Ordering::Relaxed) {
+                    rows_resolved.fetch_add(1, Ordering::Relaxed);
+                    let _ = result_tx.send((row_idx, record, None));
+                    continue;
+                }
 
-    let mut dd_matches = 0usize;
-    let mut dd_mismatches: Vec<(usize, u8, i8)> = Vec::new();
+                if config.resume && existing_refs.contains(&ref_id) {
+                    skipped_resume.fetch_add(1, Ordering::Relaxed);
+                    rows_resolved.fetch_add(1, Ordering::Relaxed);
+                    let _ = result_tx.send((row_idx, record, None));
+                    continue;
+                }
 
-    for (row_idx, record) in all_records.iter().enumerate() {
-        let mut output_record = record.clone();
+                let max_dd: Option<i8> = col_indices
+                    .max_dd_col
+                    .and_then(|col| record.get(col))
+                    .and_then(|s| s.parse::<i8>().ok());
 
-        if !dd_col_exists {
-            if let Some(entry) = results_map.get(&row_idx) {
-                output_record
-                    .push_field(&entry.computed_dd.map(|d| d.to_string()).unwrap_or_default());
-                let dd_match = match (entry.computed_dd, entry.input_max_dd) {
-                    (Some(computed), Some(input)) if input >= 0 => {
-                        if computed as i8 == input {
-                            "true"
-                        } else {
-                            "false"
-                        }
-                    }
-                    _ => "",
-                };
-                output_record.push_field(dd_match);
-                output_record.push_field(&entry.ol_error.to_string());
-                output_record.push_field(&entry.plays_n.to_string());
-                output_record.push_field(&entry.plays_s.to_string());
-                output_record.push_field(&entry.plays_e.to_string());
-                output_record.push_field(&entry.plays_w.to_string());
-                output_record.push_field(&entry.errors_n.to_string());
-                output_record.push_field(&entry.errors_s.to_string());
-                output_record.push_field(&entry.errors_e.to_string());
-                output_record.push_field(&entry.errors_w.to_string());
-                output_record.push_field(&entry.analysis);
-            } else {
-                for _ in 0..12 {
-                    output_record.push_field("");
+                if max_dd == Some(-1) {
+                    skipped_incomplete.fetch_add(1, Ordering::Relaxed);
+                    rows_resolved.fetch_add(1, Ordering::Relaxed);
+                    let _ = result_tx.send((row_idx, record, None));
+                    continue;
                 }
-            }
-        }
 
-        if let Some(entry) = results_map.get(&row_idx) {
-            if let (Some(computed), Some(input_dd)) = (entry.computed_dd, entry.input_max_dd) {
-                if input_dd >= 0 {
-                    if computed as i8 == input_dd {
-                        dd_matches += 1;
+                let cardplay = record
+                    .get(col_indices.cardplay_col)
+                    .unwrap_or("")
+                    .to_string();
+
+                if cardplay.is_empty() || cardplay.starts_with("ERROR:") {
+                    rows_resolved.fetch_add(1, Ordering::Relaxed);
+                    let _ = result_tx.send((row_idx, record, None));
+                    continue;
+                }
+
+                let row_data = dd_extract_row_data(&record, &col_indices);
+                let work_item = row_data.and_then(|row_data| {
+                    let contract_upper = row_data.contract.to_uppercase();
+                    if contract_upper.starts_with('0')
+                        || contract_upper == "P"
+                        || contract_upper == "PASS"
+                    {
+                        skipped_passout.fetch_add(1, Ordering::Relaxed);
+                        None
                     } else {
-                        dd_mismatches.push((row_idx + 2, computed, input_dd));
+                        Some(DdWorkItem {
+                            row_idx,
+                            ref_id: ref_id.clone(),
+                            deal_pbn: row_data.deal_pbn,
+                            cardplay,
+                            contract: row_data.contract,
+                            declarer: row_data.declarer,
+                            max_dd,
+                        })
                     }
+                });
+
+                let Some(item) = work_item else {
+                    rows_resolved.fetch_add(1, Ordering::Relaxed);
+                    let _ = result_tx.send((row_idx, record, None));
+                    continue;
+                };
+
+                to_process.fetch_add(1, Ordering::Relaxed);
+
+                while in_flight.load(Ordering::Acquire) >= DD_MAX_IN_FLIGHT {
+                    std::thread::sleep(std::time::Duration::from_millis(1));
                 }
+                in_flight.fetch_add(1, Ordering::AcqRel);
+
+                let result_tx = result_tx.clone();
+                let processed_ref = &processed_count;
+                let error_ref = &error_count;
+                let resolved_ref = &rows_resolved;
+                let in_flight_ref = &in_flight;
+                let error_mode = config.error_mode;
+
+                rs.spawn(move |_| {
+                    // Wrap in catch_unwind so a bridge-solver panic on one
+                    // item can't kill a rayon worker and stall the pipeline.
+                    let entry = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                        dd_compute_analysis(&item, error_mode)
+                    })) {
+                        Ok(Ok(output)) => DdResultEntry {
+                            analysis: output.analysis,
+                            computed_dd: Some(output.initial_dd),
+                            input_max_dd: item.max_dd,
+                            ol_error: output.ol_error,
+                            plays_n: output.plays_n,
+                            plays_s: output.plays_s,
+                            plays_e: output.plays_e,
+                            plays_w: output.plays_w,
+                            errors_n: output.errors_n,
+                            errors_s: output.errors_s,
+                            errors_e: output.errors_e,
+                            errors_w: output.errors_w,
+                        },
+                        Ok(Err(e)) => {
+                            error_ref.fetch_add(1, Ordering::Relaxed);
+                            log::warn!("Row {}: DD analysis error: {}", item.row_idx + 1, e);
+                            DdResultEntry {
+                                analysis: format!("ERROR: {}", e),
+                                computed_dd: None,
+                                input_max_dd: item.max_dd,
+                                ol_error: 0,
+                                plays_n: 0,
+                                plays_s: 0,
+                                plays_e: 0,
+                                plays_w: 0,
+                                errors_n: 0,
+                                errors_s: 0,
+                                errors_e: 0,
+                                errors_w: 0,
+                            }
+                        }
+                        Err(panic_info) => {
+                            error_ref.fetch_add(1, Ordering::Relaxed);
+                            let msg = if let Some(s) = panic_info.downcast_ref::<&str>() {
+                                (*s).to_string()
+                            } else if let Some(s) = panic_info.downcast_ref::<String>() {
+                                s.clone()
+                            } else {
+                                "unknown panic".to_string()
+                            };
+                            log::warn!("Row {}: DD solver panic: {}", item.row_idx + 1, msg);
+                            DdResultEntry {
+                                analysis: format!("PANIC: {}", msg),
+                                computed_dd: None,
+                                input_max_dd: item.max_dd,
+                                ol_error: 0,
+                                plays_n: 0,
+                                plays_s: 0,
+                                plays_e: 0,
+                                plays_w: 0,
+                                errors_n: 0,
+                                errors_s: 0,
+                                errors_e: 0,
+                                errors_w: 0,
+                            }
+                        }
+                    };
+
+                    processed_ref.fetch_add(1, Ordering::Relaxed);
+                    resolved_ref.fetch_add(1, Ordering::Relaxed);
+                    in_flight_ref.fetch_sub(1, Ordering::AcqRel);
+                    let _ = result_tx.send((row_idx, record, Some(entry)));
+                });
             }
-        }
+        });
 
-        writer.write_record(&output_record)?;
+        done.store(true, Ordering::Relaxed);
 
-        if (row_idx + 1) % config.checkpoint_interval == 0 {
-            writer.flush()?;
-        }
-    }
+        // Drop the last sender so the writer's `result_rx.iter()` sees the
+        // channel close once every dispatched row has been sent.
+        drop(result_tx);
 
-    writer.flush()?;
+        writer_handle.join().unwrap()
+    })?;
+
+    let was_cancelled = cancelled.load(Ordering::Relaxed);
+    let to_process = to_process.load(Ordering::Relaxed);
+    let skipped_resume = skipped_resume.load(Ordering::Relaxed);
+    let skipped_incomplete = skipped_incomplete.load(Ordering::Relaxed);
+    let skipped_passout = skipped_passout.load(Ordering::Relaxed);
+
+    if to_process == 0 {
+        return Ok(format!(
+            "Nothing to process ({} rows, {} already done, {} incomplete, {} passout)",
+            total_rows, skipped_resume, skipped_incomplete, skipped_passout
+        ));
+    }
 
     // Build summary
     let errors = error_count.load(Ordering::Relaxed);
@@ -2878,16 +3984,84 @@ fn bbo_hand_to_pbn(hand: &str) -> Option<String> {
     }
 }
 
+/// Contract level (the leading digit, 1-7), used to derive the tricks
+/// required to make the contract (6 + level).
+fn dd_contract_level(contract: &str) -> Option<u8> {
+    contract.trim().chars().next().and_then(|c| c.to_digit(10)).map(|d| d as u8)
+}
+
+/// Whether `seat` is on declarer's side of the table.
+fn dd_is_declarer_side(seat: usize, declarer_seat: usize) -> bool {
+    seat == declarer_seat || seat == (declarer_seat + 2) % 4
+}
+
+/// Decide, for each card in play order, whether its DD cost counts as an
+/// error under `error_mode`. The raw per-card cost string is never touched;
+/// this only changes what feeds the `DD_*_Errors` / `ol_error` tallies.
+fn dd_classify_errors(
+    card_records: &[(usize, u8)],
+    error_mode: DdErrorMode,
+    declarer_seat: usize,
+    initial_dd: u8,
+    contract: &str,
+) -> Vec<bool> {
+    match error_mode {
+        DdErrorMode::Raw => card_records.iter().map(|&(_, cost)| cost > 0).collect(),
+        DdErrorMode::FirstErrorOnly => {
+            let mut last_was_error = [false; 4];
+            card_records
+                .iter()
+                .map(|&(seat, cost)| {
+                    let is_error = cost > 0;
+                    let counts = is_error
+                        && (dd_is_declarer_side(seat, declarer_seat) || !last_was_error[seat]);
+                    last_was_error[seat] = is_error;
+                    counts
+                })
+                .collect()
+        }
+        DdErrorMode::ResultAffecting => match dd_contract_level(contract) {
+            Some(level) => {
+                let required = 6 + level as i32;
+                let signed: Vec<i32> = card_records
+                    .iter()
+                    .map(|&(seat, cost)| {
+                        if dd_is_declarer_side(seat, declarer_seat) {
+                            -(cost as i32)
+                        } else {
+                            cost as i32
+                        }
+                    })
+                    .collect();
+                let final_result = initial_dd as i32 + signed.iter().sum::<i32>();
+                let made = final_result >= required;
+                signed
+                    .iter()
+                    .zip(card_records.iter())
+                    .map(|(&delta, &(_, cost))| {
+                        cost > 0 && ((final_result - delta) >= required) != made
+                    })
+                    .collect()
+            }
+            None => card_records.iter().map(|&(_, cost)| cost > 0).collect(),
+        },
+    }
+}
+
 /// Compute DD analysis for a single work item.
-fn dd_compute_analysis(item: &DdWorkItem) -> Result<DdAnalysisOutput> {
+fn dd_compute_analysis(item: &DdWorkItem, error_mode: DdErrorMode) -> Result<DdAnalysisOutput> {
+    use crate::contract::Contract;
     use crate::dd_analysis::compute_dd_costs;
 
+    let contract: Contract = item.contract.parse().map_err(|e: String| anyhow::anyhow!("{}", e))?;
     let result = compute_dd_costs(
         &item.deal_pbn,
         &item.cardplay,
-        &item.contract,
+        &contract,
         &item.declarer,
         false,
+        false,
+        false,
     )
     .map_err(|e| anyhow::anyhow!("{}", e))?;
 
@@ -2910,12 +4084,6 @@ fn dd_compute_analysis(item: &DdWorkItem) -> Result<DdAnalysisOutput> {
     let mut plays = [0u8; 4];
     let mut errors = [0u8; 4];
 
-    let ol_error = if !result.costs[0].is_empty() && result.costs[0][0] > 0 {
-        1
-    } else {
-        0
-    };
-
     let tricks: Vec<Vec<&str>> = item
         .cardplay
         .split('|')
@@ -2926,14 +4094,13 @@ fn dd_compute_analysis(item: &DdWorkItem) -> Result<DdAnalysisOutput> {
     let initial_leader = (result.declarer_seat + 1) % 4;
     let mut current_leader = initial_leader;
 
+    // First pass: record (seat, cost) for every card in play order.
+    let mut card_records: Vec<(usize, u8)> = Vec::new();
     for (trick_idx, card_costs) in result.costs.iter().enumerate() {
         let mut seat = current_leader;
 
         for &cost in card_costs.iter() {
-            plays[seat] += 1;
-            if cost > 0 {
-                errors[seat] += 1;
-            }
+            card_records.push((seat, cost));
             seat = (seat + 1) % 4;
         }
 
@@ -2947,6 +4114,23 @@ fn dd_compute_analysis(item: &DdWorkItem) -> Result<DdAnalysisOutput> {
         }
     }
 
+    let counts_as_error = dd_classify_errors(
+        &card_records,
+        error_mode,
+        result.declarer_seat,
+        result.initial_dd,
+        &item.contract,
+    );
+
+    for (&(seat, _), &is_error) in card_records.iter().zip(counts_as_error.iter()) {
+        plays[seat] += 1;
+        if is_error {
+            errors[seat] += 1;
+        }
+    }
+
+    let ol_error = if counts_as_error.first().copied().unwrap_or(false) { 1 } else { 0 };
+
     let trick_results: Vec<String> = result
         .costs
         .iter()
@@ -3378,63 +4562,1648 @@ fn percent_decode_url(s: &str) -> String {
                 continue;
             }
         }
-        result.push(bytes[i]);
-        i += 1;
+        result.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&result).to_string()
+}
+
+/// Normalize a URL to use https scheme.
+fn to_https(url: &str) -> String {
+    let trimmed = url.trim();
+    if let Some(rest) = trimmed.strip_prefix("http://") {
+        format!("https://{rest}")
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Resolve an Overcaller-Bid (OB) player name to the auction roles of the
+/// other three seats, given this board's N/E/S/W player names. Bidding runs
+/// clockwise (N -> E -> S -> W -> N): the Overcaller is the seat right after
+/// the OB, the Responder is the OB's partner, and the Advancer is the
+/// Overcaller's partner. Returns `None` if `ob_name` doesn't match any seat.
+fn compute_ob_roles<'a>(
+    ob_name: &str,
+    n: &'a str,
+    e: &'a str,
+    s: &'a str,
+    w: &'a str,
+) -> Option<(&'a str, &'a str, &'a str)> {
+    let ob_lower = ob_name.to_lowercase();
+    if ob_lower == n.to_lowercase() {
+        Some((e, s, w))
+    } else if ob_lower == e.to_lowercase() {
+        Some((s, w, n))
+    } else if ob_lower == s.to_lowercase() {
+        Some((w, n, e))
+    } else if ob_lower == w.to_lowercase() {
+        Some((n, e, s))
+    } else {
+        None
+    }
+}
+
+/// Extract the filename (without directory) from a path, for display purposes.
+fn extract_filename(p: &Path) -> String {
+    p.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("(unknown)")
+        .to_string()
+}
+
+/// Convert a 0-based column index to an Excel column letter (A, B, ..., Z, AA, AB, ...).
+fn col_letter(idx: u32) -> String {
+    let mut result = String::new();
+    let mut n = idx;
+    loop {
+        result.insert(0, (b'A' + (n % 26) as u8) as char);
+        if n < 26 {
+            break;
+        }
+        n = n / 26 - 1;
+    }
+    result
+}
+
+// ============================================================================
+// Fidelity Diff (original vs anonymized)
+// ============================================================================
+
+/// Classification of a single differing cell between the original and
+/// anonymized case files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffClass {
+    /// A change anonymization is supposed to make: player names, the
+    /// tinyurl/board-id substitution, or the LIN_URL substitution.
+    Expected,
+    /// Any other change — a sign anonymization corrupted data that should
+    /// have passed through untouched.
+    Unexpected,
+}
+
+/// CSV columns anonymization is expected to rewrite. A differing value in
+/// any other column is treated as unexpected data corruption.
+const DIFF_EXPECTED_COLUMNS: &[&str] = &["BBO", "LIN_URL", "N", "S", "E", "W", "OB name"];
+
+/// Configuration for the fidelity-diff command.
+pub struct FidelityDiffConfig {
+    /// Original (non-anonymized) case files
+    pub original: CaseFiles,
+    /// Anonymized case files, as discovered by [`find_anon_files`]
+    pub anon: AnonCaseFiles,
+    /// Output xlsx path
+    pub output: PathBuf,
+    /// Absolute tolerance for numeric columns, so floating-point
+    /// reformatting doesn't register as a difference.
+    pub score_tolerance: f64,
+}
+
+/// Decide whether a changed CSV cell is an expected or unexpected change.
+///
+/// Returns `None` if the values should not be reported as a difference at
+/// all (identical, or within `tolerance` for a numeric column).
+fn diff_csv_cell(column: &str, orig: &str, anon: &str, tolerance: f64) -> Option<DiffClass> {
+    if orig == anon {
+        return None;
+    }
+    if DIFF_EXPECTED_COLUMNS.contains(&column) {
+        return Some(DiffClass::Expected);
+    }
+    if let (Ok(o), Ok(a)) = (orig.trim().parse::<f64>(), anon.trim().parse::<f64>()) {
+        if (o - a).abs() <= tolerance {
+            return None;
+        }
+    }
+    Some(DiffClass::Unexpected)
+}
+
+/// Write one anonymized-side hotspot field, highlighting it if it changed
+/// in a way `class_if_changed` doesn't expect.
+#[allow(clippy::too_many_arguments)]
+fn write_hotspot_diff_field(
+    sheet: &mut rust_xlsxwriter::Worksheet,
+    row: u32,
+    col: u16,
+    orig: &str,
+    anon: &str,
+    class_if_changed: DiffClass,
+    left_fmt: &rust_xlsxwriter::Format,
+    bad_fmt: &rust_xlsxwriter::Format,
+    expected_count: &mut usize,
+    unexpected_count: &mut usize,
+) -> Result<()> {
+    if orig != anon {
+        match class_if_changed {
+            DiffClass::Unexpected => {
+                *unexpected_count += 1;
+                sheet.write_string_with_format(row, col, anon, bad_fmt)?;
+            }
+            DiffClass::Expected => {
+                *expected_count += 1;
+                sheet.write_string_with_format(row, col, anon, left_fmt)?;
+            }
+        }
+    } else {
+        sheet.write_string_with_format(row, col, anon, left_fmt)?;
+    }
+    Ok(())
+}
+
+/// Compare an original case package against its anonymized counterpart and
+/// report every field that changed, classified as EXPECTED (anonymization
+/// doing its job) or UNEXPECTED (possible data corruption).
+///
+/// Produces a "Diff" workbook with a Boards table and a Hotspots table,
+/// each showing the original and anonymized value stacked per record so a
+/// human can scan differences quickly; unexpected changes are filled red.
+/// Returns a summary string noting how many of each were found.
+pub fn fidelity_diff(config: &FidelityDiffConfig) -> Result<String> {
+    use rust_xlsxwriter::{Format, FormatAlign, Workbook};
+
+    let orig_csv = config
+        .original
+        .csv_file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("Original case files have no CSV"))?;
+    let orig_hotspot = config
+        .original
+        .hotspot_file
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("Original case files have no hotspot report"))?;
+
+    let orig_data = read_bbo_csv_fixed(orig_csv)?;
+    let mut orig_reader = ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(orig_data.as_bytes());
+    let orig_headers = orig_reader.headers()?.clone();
+    let orig_records: Vec<StringRecord> = orig_reader
+        .records()
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to read original CSV records")?;
+
+    let anon_data = read_bbo_csv_fixed(&config.anon.csv_file)?;
+    let mut anon_reader = ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(anon_data.as_bytes());
+    let anon_records: Vec<StringRecord> = anon_reader
+        .records()
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to read anonymized CSV records")?;
+
+    let orig_hotspots = parse_hotspot_report(orig_hotspot)?;
+    let anon_hotspots = parse_hotspot_report(&config.anon.hotspot_file)?;
+
+    let mut workbook = Workbook::new();
+    let bold = Format::new().set_bold();
+    let left_fmt = Format::new().set_align(FormatAlign::Left);
+    let bad_fmt = Format::new()
+        .set_background_color("#FFC7CE")
+        .set_font_color("#9C0006");
+
+    let mut expected_count = 0usize;
+    let mut unexpected_count = 0usize;
+
+    let sheet = workbook.add_worksheet();
+    sheet.set_name("Diff")?;
+    let mut row: u32 = 0;
+
+    // -- Boards table --
+    sheet.write_string_with_format(row, 0, "Boards Diff", &bold)?;
+    row += 1;
+    sheet.write_string_with_format(row, 0, "Row", &bold)?;
+    sheet.write_string_with_format(row, 1, "Source", &bold)?;
+    for (j, h) in orig_headers.iter().enumerate() {
+        sheet.write_string_with_format(row, 2 + j as u16, h, &bold)?;
+    }
+    row += 1;
+
+    let board_count = orig_records.len().min(anon_records.len());
+    if orig_records.len() != anon_records.len() {
+        log::warn!(
+            "Fidelity diff: original CSV has {} rows but anonymized CSV has {}; comparing first {}",
+            orig_records.len(),
+            anon_records.len(),
+            board_count
+        );
+    }
+
+    for i in 0..board_count {
+        let orig_rec = &orig_records[i];
+        let anon_rec = &anon_records[i];
+
+        sheet.write_number(row, 0, (i + 1) as f64)?;
+        sheet.write_string_with_format(row, 1, "Original", &left_fmt)?;
+        for (j, field) in orig_rec.iter().enumerate() {
+            sheet.write_string_with_format(row, 2 + j as u16, field, &left_fmt)?;
+        }
+        row += 1;
+
+        sheet.write_number(row, 0, (i + 1) as f64)?;
+        sheet.write_string_with_format(row, 1, "Anon", &left_fmt)?;
+        for (j, field) in anon_rec.iter().enumerate() {
+            let column = orig_headers.get(j).unwrap_or("");
+            let orig_field = orig_rec.get(j).unwrap_or("");
+            match diff_csv_cell(column, orig_field, field, config.score_tolerance) {
+                Some(DiffClass::Unexpected) => {
+                    unexpected_count += 1;
+                    sheet.write_string_with_format(row, 2 + j as u16, field, &bad_fmt)?;
+                }
+                Some(DiffClass::Expected) => {
+                    expected_count += 1;
+                    sheet.write_string_with_format(row, 2 + j as u16, field, &left_fmt)?;
+                }
+                None => {
+                    sheet.write_string_with_format(row, 2 + j as u16, field, &left_fmt)?;
+                }
+            }
+        }
+        row += 1;
+    }
+    row += 1;
+
+    // -- Hotspots table --
+    sheet.write_string_with_format(row, 0, "Hotspots Diff", &bold)?;
+    row += 1;
+    let hotspot_cols = [
+        "Category", "Subindex", "Hit/Miss", "Contract", "Lead", "Player", "Link",
+    ];
+    sheet.write_string_with_format(row, 0, "Row", &bold)?;
+    sheet.write_string_with_format(row, 1, "Source", &bold)?;
+    for (j, h) in hotspot_cols.iter().enumerate() {
+        sheet.write_string_with_format(row, 2 + j as u16, *h, &bold)?;
+    }
+    row += 1;
+
+    let hotspot_count = orig_hotspots.len().min(anon_hotspots.len());
+    if orig_hotspots.len() != anon_hotspots.len() {
+        log::warn!(
+            "Fidelity diff: original hotspot report has {} entries but anonymized has {}; comparing first {}",
+            orig_hotspots.len(),
+            anon_hotspots.len(),
+            hotspot_count
+        );
+    }
+
+    for i in 0..hotspot_count {
+        let o = &orig_hotspots[i];
+        let a = &anon_hotspots[i];
+
+        sheet.write_number(row, 0, (i + 1) as f64)?;
+        sheet.write_string_with_format(row, 1, "Original", &left_fmt)?;
+        sheet.write_string_with_format(row, 2, &o.category, &left_fmt)?;
+        sheet.write_number(row, 3, o.subindex as f64)?;
+        sheet.write_string_with_format(row, 4, &o.hit_miss, &left_fmt)?;
+        sheet.write_string_with_format(row, 5, &o.contract, &left_fmt)?;
+        sheet.write_string_with_format(row, 6, &o.lead, &left_fmt)?;
+        sheet.write_string_with_format(row, 7, &o.subject_player, &left_fmt)?;
+        sheet.write_string_with_format(row, 8, &o.tinyurl, &left_fmt)?;
+        row += 1;
+
+        sheet.write_number(row, 0, (i + 1) as f64)?;
+        sheet.write_string_with_format(row, 1, "Anon", &left_fmt)?;
+        write_hotspot_diff_field(
+            sheet,
+            row,
+            2,
+            &o.category,
+            &a.category,
+            DiffClass::Unexpected,
+            &left_fmt,
+            &bad_fmt,
+            &mut expected_count,
+            &mut unexpected_count,
+        )?;
+        write_hotspot_diff_field(
+            sheet,
+            row,
+            3,
+            &o.subindex.to_string(),
+            &a.subindex.to_string(),
+            DiffClass::Unexpected,
+            &left_fmt,
+            &bad_fmt,
+            &mut expected_count,
+            &mut unexpected_count,
+        )?;
+        write_hotspot_diff_field(
+            sheet,
+            row,
+            4,
+            &o.hit_miss,
+            &a.hit_miss,
+            DiffClass::Unexpected,
+            &left_fmt,
+            &bad_fmt,
+            &mut expected_count,
+            &mut unexpected_count,
+        )?;
+        write_hotspot_diff_field(
+            sheet,
+            row,
+            5,
+            &o.contract,
+            &a.contract,
+            DiffClass::Unexpected,
+            &left_fmt,
+            &bad_fmt,
+            &mut expected_count,
+            &mut unexpected_count,
+        )?;
+        write_hotspot_diff_field(
+            sheet,
+            row,
+            6,
+            &o.lead,
+            &a.lead,
+            DiffClass::Unexpected,
+            &left_fmt,
+            &bad_fmt,
+            &mut expected_count,
+            &mut unexpected_count,
+        )?;
+        write_hotspot_diff_field(
+            sheet,
+            row,
+            7,
+            &o.subject_player,
+            &a.subject_player,
+            DiffClass::Expected,
+            &left_fmt,
+            &bad_fmt,
+            &mut expected_count,
+            &mut unexpected_count,
+        )?;
+        // Link: the original's tinyurl and the anon's board-id/LIN_URL are
+        // different representations of the same thing by design, not a
+        // cell-level diff.
+        let anon_link = a
+            .lin_url
+            .clone()
+            .unwrap_or_else(|| a.board_id.clone().unwrap_or_default());
+        sheet.write_string_with_format(row, 8, &anon_link, &left_fmt)?;
+        row += 1;
+    }
+
+    sheet.set_column_width(0, 6)?;
+    sheet.set_column_width(1, 10)?;
+    for j in 2..2 + orig_headers.len() as u16 {
+        sheet.set_column_width(j, 14)?;
+    }
+
+    if let Some(parent) = config.output.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    workbook
+        .save(&config.output)
+        .map_err(|e| anyhow::anyhow!("Failed to save diff workbook: {}", e))?;
+
+    Ok(format!(
+        "Fidelity diff complete: {} boards, {} hotspots compared\n  Expected changes: {}\n  Unexpected changes: {}",
+        board_count, hotspot_count, expected_count, unexpected_count
+    ))
+}
+
+// ============================================================================
+// Hotspot Pattern Classifier
+// ============================================================================
+
+/// Window size for sparse-bigram tokenization: an anchor token is paired
+/// with every token up to this many positions ahead.
+const HOTSPOT_CLASSIFIER_WINDOW: usize = 5;
+
+/// Build the ordered hotspot-event sequence for one subject player from a
+/// parsed hotspot report, as tokens of the form `Category:Hit`/`Category:Miss`.
+fn hotspot_events_for_player(entries: &[HotspotEntry], player: &str) -> Vec<String> {
+    entries
+        .iter()
+        .filter(|e| e.subject_player == player)
+        .map(|e| format!("{}:{}", e.category, e.hit_miss))
+        .collect()
+}
+
+/// Tokenize an ordered event sequence into orthogonal sparse bigrams: each
+/// anchor event is paired with every event up to `window - 1` positions
+/// ahead. Unlike an adjacent bigram, this captures which event types
+/// co-occur regardless of the exact spacing between them.
+fn sparse_bigrams(events: &[String], window: usize) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for i in 0..events.len() {
+        for gap in 1..window {
+            let j = i + gap;
+            if j >= events.len() {
+                break;
+            }
+            tokens.push(format!("{}~{}", events[i], events[j]));
+        }
+    }
+    tokens
+}
+
+/// One class's trained multinomial naive-Bayes token statistics.
+#[derive(Debug, Clone, Default)]
+struct NbClass {
+    token_counts: HashMap<String, usize>,
+    total_tokens: usize,
+    doc_count: usize,
+}
+
+impl NbClass {
+    fn add_document(&mut self, tokens: &[String]) {
+        self.doc_count += 1;
+        for t in tokens {
+            *self.token_counts.entry(t.clone()).or_insert(0) += 1;
+            self.total_tokens += 1;
+        }
+    }
+
+    /// Laplace-smoothed log-likelihood of `token` under this class.
+    /// Tokens never seen for this class still contribute (count defaults
+    /// to 0), which is what lets unseen tokens influence the score.
+    fn log_likelihood(&self, token: &str, vocab_size: usize) -> f64 {
+        let count = *self.token_counts.get(token).unwrap_or(&0) as f64;
+        ((count + 1.0) / (self.total_tokens as f64 + vocab_size as f64)).ln()
+    }
+}
+
+/// Result of scoring one player's hotspot event sequence against a trained
+/// [`HotspotClassifier`].
+#[derive(Debug, Clone)]
+pub struct ClassificationResult {
+    pub flagged_log_prob: f64,
+    pub clean_log_prob: f64,
+    /// Normalized posterior probability the sequence resembles "flagged" cases (0..1).
+    pub flagged_posterior: f64,
+    /// Tokens with the largest `|contribution|` to the flagged/clean split,
+    /// most important first. Positive contribution favors "flagged".
+    pub top_tokens: Vec<(String, f64)>,
+}
+
+/// A two-class (flagged vs clean) naive-Bayes hotspot-pattern classifier.
+///
+/// Trained from a labeled corpus directory containing `flagged.txt` and
+/// `clean.txt`, each one whitespace-separated event sequence per line
+/// (e.g. `PassedForce:Hit Suit_Overeasy:Miss ...`).
+pub struct HotspotClassifier {
+    flagged: NbClass,
+    clean: NbClass,
+    vocab: HashSet<String>,
+    window: usize,
+}
+
+impl HotspotClassifier {
+    /// Train on `flagged.txt` and `clean.txt` in `corpus_dir`.
+    pub fn train(corpus_dir: &Path, window: usize) -> Result<Self> {
+        let mut flagged = NbClass::default();
+        let mut clean = NbClass::default();
+        let mut vocab = HashSet::new();
+
+        for (filename, class) in [
+            ("flagged.txt", &mut flagged),
+            ("clean.txt", &mut clean),
+        ] {
+            let path = corpus_dir.join(filename);
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read training corpus {}", path.display()))?;
+            for line in content.lines() {
+                let events: Vec<String> =
+                    line.split_whitespace().map(|s| s.to_string()).collect();
+                if events.is_empty() {
+                    continue;
+                }
+                let tokens = sparse_bigrams(&events, window);
+                vocab.extend(tokens.iter().cloned());
+                class.add_document(&tokens);
+            }
+        }
+
+        Ok(HotspotClassifier {
+            flagged,
+            clean,
+            vocab,
+            window,
+        })
+    }
+
+    /// Score an event sequence against the trained model.
+    ///
+    /// An empty sequence yields the class priors only (no token evidence).
+    pub fn classify(&self, events: &[String]) -> ClassificationResult {
+        let vocab_size = self.vocab.len().max(1);
+        let total_docs = (self.flagged.doc_count + self.clean.doc_count).max(1) as f64;
+
+        let prior_flagged = (self.flagged.doc_count as f64 / total_docs).ln();
+        let prior_clean = (self.clean.doc_count as f64 / total_docs).ln();
+
+        let tokens = sparse_bigrams(events, self.window);
+
+        let mut flagged_log_prob = prior_flagged;
+        let mut clean_log_prob = prior_clean;
+        let mut contributions: HashMap<String, f64> = HashMap::new();
+
+        for t in &tokens {
+            let lf = self.flagged.log_likelihood(t, vocab_size);
+            let lc = self.clean.log_likelihood(t, vocab_size);
+            flagged_log_prob += lf;
+            clean_log_prob += lc;
+            *contributions.entry(t.clone()).or_insert(0.0) += lf - lc;
+        }
+
+        // log-sum-exp normalization to turn the two log-probs into a posterior
+        let max_log = flagged_log_prob.max(clean_log_prob);
+        let exp_flagged = (flagged_log_prob - max_log).exp();
+        let exp_clean = (clean_log_prob - max_log).exp();
+        let flagged_posterior = exp_flagged / (exp_flagged + exp_clean);
+
+        let mut top_tokens: Vec<(String, f64)> = contributions.into_iter().collect();
+        top_tokens.sort_by(|a, b| b.1.abs().partial_cmp(&a.1.abs()).unwrap());
+        top_tokens.truncate(10);
+
+        ClassificationResult {
+            flagged_log_prob,
+            clean_log_prob,
+            flagged_posterior,
+            top_tokens,
+        }
+    }
+}
+
+/// SHA-256 and byte length of one packaged source file, as recorded on the
+/// Summary sheet (or the Summary section of the Markdown/HTML report) and in
+/// the sidecar manifest.
+struct FileHash {
+    label: &'static str,
+    filename: String,
+    hash: String,
+    len: u64,
+}
+
+/// Compute the SHA-256 digest and byte length of a file, reading it in
+/// fixed-size chunks so memory use doesn't scale with file size.
+fn sha256_file(path: &Path) -> Result<(String, u64)> {
+    use std::io::Read;
+    let mut file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open {} for hashing", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 65536];
+    let mut len: u64 = 0;
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+        len += n as u64;
+    }
+    Ok((format!("{:x}", hasher.finalize()), len))
+}
+
+/// Fold a set of (filename, hash, byte length) entries into a single
+/// "package digest": the per-file hashes sorted by filename and hashed
+/// together. A reviewer who re-hashes the original case files can
+/// recompute this value and confirm none of them were swapped.
+fn package_digest(entries: &[(String, String, u64)]) -> String {
+    let mut sorted: Vec<&(String, String, u64)> = entries.iter().collect();
+    sorted.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut hasher = Sha256::new();
+    for (name, hash, _) in sorted {
+        hasher.update(name.as_bytes());
+        hasher.update(b":");
+        hasher.update(hash.as_bytes());
+        hasher.update(b"\n");
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Write the sidecar `.manifest.txt` that accompanies a package workbook.
+///
+/// Format is a `sha256sum`-style listing (one tab-separated
+/// `hash  bytes  filename` record per source file) plus a final
+/// `package-digest` line, so it can be checked by hand or re-verified with
+/// [`verify_manifest`].
+fn write_manifest(
+    manifest_path: &Path,
+    package_name: &str,
+    created: &str,
+    entries: &[(String, String, u64)],
+    digest: &str,
+) -> Result<()> {
+    let mut out = String::new();
+    out.push_str("# EDGAR Case Package Manifest\n");
+    out.push_str(&format!("# Package: {package_name}\n"));
+    out.push_str(&format!("# Created: {created}\n"));
+    for (filename, hash, len) in entries {
+        out.push_str(&format!("{hash}\t{len}\t{filename}\n"));
+    }
+    out.push_str(&format!("# package-digest\t{digest}\n"));
+    std::fs::write(manifest_path, out)
+        .with_context(|| format!("Failed to write manifest {}", manifest_path.display()))?;
+    Ok(())
+}
+
+/// Result of re-checking one file named in a package manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestFileStatus {
+    /// The file on disk hashes to the value recorded in the manifest.
+    Ok,
+    /// The file exists but its hash no longer matches.
+    Mismatch {
+        expected_hash: String,
+        actual_hash: String,
+    },
+    /// The file named in the manifest could not be read.
+    Missing,
+}
+
+/// Outcome of checking a single manifest entry against disk.
+#[derive(Debug, Clone)]
+pub struct ManifestCheck {
+    pub filename: String,
+    pub status: ManifestFileStatus,
+}
+
+/// Result of verifying a whole package manifest.
+pub struct ManifestVerification {
+    pub files: Vec<ManifestCheck>,
+    pub digest_expected: String,
+    pub digest_actual: String,
+    pub digest_ok: bool,
+}
+
+impl ManifestVerification {
+    /// True if every file matched and the package digest recomputed clean.
+    pub fn all_ok(&self) -> bool {
+        self.digest_ok
+            && self
+                .files
+                .iter()
+                .all(|f| f.status == ManifestFileStatus::Ok)
+    }
+}
+
+/// Re-hash the files named in a package manifest and report any mismatch.
+///
+/// `search_dir` is the directory the named files are expected to live in
+/// (typically the case folder the package was built from). This is the
+/// evidentiary check: a reviewer re-runs this against the original case
+/// files to confirm the delivered workbook was built from exactly those
+/// bytes.
+pub fn verify_manifest(manifest_path: &Path, search_dir: &Path) -> Result<ManifestVerification> {
+    let text = std::fs::read_to_string(manifest_path)
+        .with_context(|| format!("Failed to read manifest {}", manifest_path.display()))?;
+
+    let mut files = Vec::new();
+    let mut recorded: Vec<(String, String, u64)> = Vec::new();
+    let mut digest_expected = String::new();
+
+    for line in text.lines() {
+        if let Some(rest) = line.strip_prefix("# package-digest\t") {
+            digest_expected = rest.trim().to_string();
+            continue;
+        }
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        let mut parts = line.splitn(3, '\t');
+        let (expected_hash, len, filename) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(h), Some(l), Some(f)) => (h.to_string(), l, f.to_string()),
+            _ => continue,
+        };
+        let Ok(len) = len.parse::<u64>() else {
+            continue;
+        };
+        recorded.push((filename.clone(), expected_hash.clone(), len));
+
+        let status = match sha256_file(&search_dir.join(&filename)) {
+            Ok((actual_hash, _)) if actual_hash == expected_hash => ManifestFileStatus::Ok,
+            Ok((actual_hash, _)) => ManifestFileStatus::Mismatch {
+                expected_hash,
+                actual_hash,
+            },
+            Err(_) => ManifestFileStatus::Missing,
+        };
+        files.push(ManifestCheck { filename, status });
+    }
+
+    // Recomputed from the manifest's own recorded per-file hashes (not the
+    // live files), so a tampered digest line is distinguishable from a
+    // tampered source file.
+    let digest_actual = package_digest(&recorded);
+
+    Ok(ManifestVerification {
+        files,
+        digest_ok: digest_actual == digest_expected,
+        digest_expected,
+        digest_actual,
+    })
+}
+
+/// Output format for `package_workbook`.
+///
+/// `Markdown` renders the Summary/Boards/Hotspots content as a single
+/// self-contained document in place of the xlsx workbook, for reviewers who
+/// want a diffable artifact to check into version control. `Html` instead
+/// mirrors the workbook's own Boards/Hotspots/Cardplay sheets one `<table>`
+/// per sheet, with the conditional-formatting colors reproduced as inline
+/// CSS, for reviewers who want to open a case without Excel. `Both` writes
+/// the xlsx workbook as usual and an accompanying `Html` document alongside
+/// it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Xlsx,
+    Markdown,
+    Html,
+    Both,
+}
+
+/// Which flavor of internal navigation hyperlink `package_workbook` emits
+/// for its Boards<->Hotspots (and Cardplay->Boards) cross-links.
+///
+/// `Excel` writes `#SheetName!Cell`-style anchors (`HYPERLINK("#Boards!A"&
+/// MATCH(...),id)`), which desktop Excel resolves natively. `Sheets` instead
+/// writes `#gid=<id>&range=<cell>` anchors, the form Google Sheets expects
+/// for an internal link to survive importing the xlsx — `#SheetName!Cell`
+/// anchors are silently dropped on import. The gid used is the destination
+/// worksheet's 0-based add-order index, since `package_workbook` always
+/// creates Summary, Boards, then Hotspots in that fixed order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HyperlinkDialect {
+    #[default]
+    Excel,
+    Sheets,
+}
+
+/// Build the `"#Boards!A"&MATCH(...)`-style anchor expression for a
+/// navigation `HYPERLINK` formula, in whichever `dialect` is configured.
+/// `match_expr` is the formula fragment (typically a `MATCH(...)` call)
+/// that resolves to the destination row number; `gid` is the destination
+/// worksheet's add-order index, used only by the `Sheets` dialect.
+fn hyperlink_anchor(
+    dialect: HyperlinkDialect,
+    sheet_name: &str,
+    gid: u32,
+    match_expr: &str,
+) -> String {
+    match dialect {
+        HyperlinkDialect::Excel => format!("\"#{sheet_name}!A\"&{match_expr}"),
+        HyperlinkDialect::Sheets => format!("\"#gid={gid}&range=A\"&{match_expr}"),
+    }
+}
+
+/// Configuration for the package workbook command.
+pub struct PackageConfig {
+    /// Path to the hand records CSV file
+    pub csv_file: PathBuf,
+    /// Path to the hotspot report text file
+    pub hotspot_file: PathBuf,
+    /// Path to the concise report text file
+    pub concise_file: PathBuf,
+    /// Output xlsx path
+    pub output: PathBuf,
+    /// Case folder path (for display in Summary)
+    pub case_folder: String,
+    /// Subject player usernames (for conditional formatting)
+    pub subject_players: Vec<String>,
+    /// Optional deal limit for testing (only include this many boards)
+    pub deal_limit: Option<usize>,
+    /// Optional path to cardplay CSV (output of fetch step)
+    pub cardplay_file: Option<PathBuf>,
+    /// Whether this is an anonymized package (changes link handling)
+    pub is_anon: bool,
+    /// Optional path to a labeled training corpus directory (`flagged.txt`
+    /// / `clean.txt`) for the hotspot-pattern classifier. When absent, the
+    /// Assessment sheet is skipped.
+    pub classifier_corpus: Option<PathBuf>,
+    /// Output format: xlsx workbook (default), a self-contained Markdown or
+    /// HTML document in its place, or both the workbook and the HTML
+    /// document.
+    pub output_format: OutputFormat,
+    /// Dialect for the workbook's internal Boards<->Hotspots navigation
+    /// hyperlinks: Excel-native `#SheetName!Cell` anchors (default), or
+    /// `#gid=...&range=...` anchors that also resolve once the file is
+    /// imported into Google Sheets.
+    pub hyperlink_dialect: HyperlinkDialect,
+    /// When set, also export the Boards/Hotspots/Cardplay sheets as flat
+    /// CSV/TSV files alongside the xlsx workbook.
+    pub flat_export: Option<FlatExportConfig>,
+    /// Custom hex-color palette to cycle through for category conditional
+    /// formatting, in place of the built-in 10-color default. Only consulted
+    /// for categories not covered by `category_color_overrides`.
+    pub category_palette: Option<Vec<String>>,
+    /// Explicit category name -> hex color overrides, checked before the
+    /// palette. Lets a reviewer pin a stable color to a category they care
+    /// about regardless of palette wraparound.
+    pub category_color_overrides: HashMap<String, String>,
+}
+
+/// Built-in category fill-color palette, cycled for any category without an
+/// explicit override once `PackageConfig::category_palette` (or this
+/// default, if that's absent) runs out.
+const DEFAULT_CATEGORY_COLORS: &[&str] = &[
+    "#DAEEF3", "#E2EFDA", "#FCE4D6", "#D9E2F3", "#EDEDED", "#FFF2CC", "#E4DFEC", "#F8CBAD",
+    "#D6DCE4", "#C5E0B4",
+];
+
+/// Resolve each category in `unique_categories` to a fill color: an explicit
+/// `config.category_color_overrides` entry wins, otherwise colors cycle
+/// through `config.category_palette` (or `DEFAULT_CATEGORY_COLORS` when
+/// that's absent or empty) in order of first appearance.
+fn resolve_category_colors(
+    config: &PackageConfig,
+    unique_categories: &[String],
+) -> HashMap<String, String> {
+    let palette: Vec<&str> = match &config.category_palette {
+        Some(p) if !p.is_empty() => p.iter().map(|s| s.as_str()).collect(),
+        _ => DEFAULT_CATEGORY_COLORS.to_vec(),
+    };
+
+    unique_categories
+        .iter()
+        .enumerate()
+        .map(|(idx, cat)| {
+            let color = config
+                .category_color_overrides
+                .get(cat)
+                .cloned()
+                .unwrap_or_else(|| palette[idx % palette.len()].to_string());
+            (cat.clone(), color)
+        })
+        .collect()
+}
+
+/// Delimiter for the flat sheet export (`PackageConfig::flat_export`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlatDelimiter {
+    Csv,
+    Tsv,
+}
+
+/// Configuration for the flat CSV/TSV export of the Boards/Hotspots/Cardplay
+/// sheets, written alongside the xlsx workbook.
+#[derive(Debug, Clone, Copy)]
+pub struct FlatExportConfig {
+    pub delimiter: FlatDelimiter,
+    /// Preserve empty input rows as empty CSV/TSV lines instead of dropping
+    /// them (including a leading blank row, which is still written rather
+    /// than swallowed).
+    pub blank_rows: bool,
+}
+
+/// HTML-escape a string for safe inclusion in the Markdown/HTML report
+/// (the Markdown report embeds raw HTML for the Summary definition list and
+/// cross-link anchors, so both formats need this).
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Render the package as a self-contained Markdown or HTML document instead
+/// of an xlsx workbook (`config.output_format`). Mirrors the Summary/Boards/
+/// Hotspots content of `package_workbook`, using in-document anchors
+/// (`#board-N` / `#hotspot-N`) for the Boards<->Hotspots cross-links that the
+/// xlsx version expresses as `HYPERLINK` formulas.
+#[allow(clippy::too_many_arguments)]
+fn render_text_package(
+    config: &PackageConfig,
+    headers: &StringRecord,
+    records: &[StringRecord],
+    bbo_col_csv: usize,
+    hotspot_entries: &[HotspotEntry],
+    url_to_hotspot: &HashMap<String, (u32, String)>,
+    file_hashes: &[FileHash],
+    package_digest_hex: &str,
+    package_date: &str,
+) -> Result<String> {
+    let is_html = config.output_format == OutputFormat::Html;
+    let out_path = config.output.with_extension(if is_html { "html" } else { "md" });
+
+    // Board number -> (hotspot id, category), and the reverse, for the
+    // cross-links. Keyed the same way as `url_to_hotspot` was built: by
+    // board_id for anon packages, by normalized tinyurl otherwise.
+    let mut board_to_hotspot: HashMap<u32, (u32, String)> = HashMap::new();
+    let mut hotspot_to_board: HashMap<u32, u32> = HashMap::new();
+    for (i, record) in records.iter().enumerate() {
+        let board_num = (i + 1) as u32;
+        let bbo_val = record.get(bbo_col_csv).unwrap_or("").trim();
+        if bbo_val.is_empty() {
+            continue;
+        }
+        let key = if config.is_anon {
+            bbo_val.to_string()
+        } else {
+            normalize_tinyurl(bbo_val)
+        };
+        if let Some((hs_id, hs_cat)) = url_to_hotspot.get(&key) {
+            board_to_hotspot.insert(board_num, (*hs_id, hs_cat.clone()));
+            hotspot_to_board.insert(*hs_id, board_num);
+        }
+    }
+
+    let mut doc = String::new();
+    if is_html {
+        doc.push_str("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">\n");
+        doc.push_str("<title>EDGAR Case Package</title></head><body>\n");
+    }
+    doc.push_str("<h1>EDGAR Case Package</h1>\n\n");
+
+    // -- Summary, as a definition list --
+    doc.push_str("<h2>Summary</h2>\n<dl>\n");
+    let def = |term: &str, desc: &str, doc: &mut String| {
+        let _ = writeln!(
+            doc,
+            "<dt>{}</dt><dd>{}</dd>",
+            html_escape(term),
+            html_escape(desc)
+        );
+    };
+    def("Case Folder", &config.case_folder, &mut doc);
+    def(
+        "Subject Players",
+        &if config.subject_players.is_empty() {
+            "(none)".to_string()
+        } else {
+            config.subject_players.join(", ")
+        },
+        &mut doc,
+    );
+    def(
+        "Hand Records CSV",
+        &extract_filename(&config.csv_file),
+        &mut doc,
+    );
+    def(
+        "Concise Report",
+        &extract_filename(&config.concise_file),
+        &mut doc,
+    );
+    def(
+        "Hotspot Report",
+        &extract_filename(&config.hotspot_file),
+        &mut doc,
+    );
+    def("Package Date", package_date, &mut doc);
+    def("Number of Boards", &records.len().to_string(), &mut doc);
+    def(
+        "Number of Hotspots",
+        &hotspot_entries.len().to_string(),
+        &mut doc,
+    );
+    let hit_count = hotspot_entries.iter().filter(|e| e.hit_miss == "Hit").count();
+    let miss_count = hotspot_entries.iter().filter(|e| e.hit_miss == "Miss").count();
+    def("Hit Count", &hit_count.to_string(), &mut doc);
+    def("Miss Count", &miss_count.to_string(), &mut doc);
+    for fh in file_hashes {
+        def(&format!("{} SHA-256", fh.label), &fh.hash, &mut doc);
+        def(&format!("{} Size (bytes)", fh.label), &fh.len.to_string(), &mut doc);
+    }
+    def("Package Digest", package_digest_hex, &mut doc);
+    doc.push_str("</dl>\n\n");
+
+    // -- Boards --
+    doc.push_str("<h2>Boards</h2>\n\n");
+    doc.push_str("| Board ID | Hotspot |");
+    for h in headers.iter() {
+        let _ = write!(doc, " {} |", html_escape(h));
+    }
+    doc.push('\n');
+    doc.push_str("|---|---|");
+    for _ in headers.iter() {
+        doc.push_str("---|");
+    }
+    doc.push('\n');
+    for (i, record) in records.iter().enumerate() {
+        let board_num = (i + 1) as u32;
+        let _ = write!(
+            doc,
+            "| <a id=\"board-{n}\"></a>{n} |",
+            n = board_num
+        );
+        match board_to_hotspot.get(&board_num) {
+            Some((hs_id, hs_cat)) => {
+                let _ = write!(
+                    doc,
+                    " [{hs_id} {cat}](#hotspot-{hs_id}) |",
+                    hs_id = hs_id,
+                    cat = html_escape(hs_cat)
+                );
+            }
+            None => doc.push_str(" |"),
+        }
+        for (j, field) in record.iter().enumerate() {
+            let trimmed = field.trim();
+            let cell = if j == bbo_col_csv && !trimmed.is_empty() {
+                to_https(field)
+            } else {
+                field.to_string()
+            };
+            let _ = write!(doc, " {} |", html_escape(cell.trim()));
+        }
+        doc.push('\n');
+    }
+    doc.push('\n');
+
+    // -- Hotspots --
+    doc.push_str("<h2>Hotspots</h2>\n\n");
+    doc.push_str(
+        "| Hotspot ID | Board | Category | Subindex | Subject Player | Hit/Miss | Contract | Lead | Link |\n",
+    );
+    doc.push_str("|---|---|---|---|---|---|---|---|---|\n");
+    for (i, entry) in hotspot_entries.iter().enumerate() {
+        let hs_id = (i + 1) as u32;
+        let _ = write!(doc, "| <a id=\"hotspot-{hs_id}\"></a>{hs_id} |", hs_id = hs_id);
+        match hotspot_to_board.get(&hs_id) {
+            Some(board_num) => {
+                let _ = write!(doc, " [{board_num}](#board-{board_num}) |", board_num = board_num);
+            }
+            None => doc.push_str(" |"),
+        }
+        let link_url = entry
+            .lin_url
+            .clone()
+            .unwrap_or_else(|| to_https(&entry.tinyurl));
+        let _ = writeln!(
+            doc,
+            " {} | {} | {} | {} | {} | {} | [Link]({}) |",
+            html_escape(&entry.category),
+            entry.subindex,
+            html_escape(&entry.subject_player),
+            html_escape(&entry.hit_miss),
+            html_escape(&entry.contract),
+            html_escape(&entry.lead),
+            html_escape(&percent_decode_url(&link_url)),
+        );
+    }
+
+    if is_html {
+        doc.push_str("</body></html>\n");
+    }
+
+    if let Some(parent) = out_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&out_path, &doc)
+        .with_context(|| format!("Failed to write report to {}", out_path.display()))?;
+
+    Ok(format!(
+        "Package created: {}\n  Boards: {}\n  Hotspots: {}",
+        out_path.display(),
+        records.len(),
+        hotspot_entries.len(),
+    ))
+}
+
+/// Render the package as a single self-contained HTML document with one
+/// `<table>` per sheet (Boards, Hotspots, and Cardplay when present),
+/// mirroring `package_workbook`'s own column layout — including the derived
+/// Overcaller/Responder/Advancer columns and the hidden BBO/Tinyurl columns,
+/// which are simply omitted here rather than hidden. The Boards<->Hotspots
+/// `HYPERLINK`/`MATCH` cross-links become `id="board-N"`/`id="hotspot-N"`
+/// anchors, and the player/category/hit-miss conditional-formatting fills
+/// are reproduced as inline `style` attributes using the same colors.
+#[allow(clippy::too_many_arguments)]
+fn render_html_package(
+    config: &PackageConfig,
+    headers: &StringRecord,
+    records: &[StringRecord],
+    bbo_col_csv: usize,
+    lin_url_col_csv: Option<usize>,
+    hotspot_entries: &[HotspotEntry],
+    url_to_hotspot: &HashMap<String, (u32, String)>,
+    category_color_map: &HashMap<String, String>,
+) -> Result<PathBuf> {
+    let out_path = config.output.with_extension("html");
+
+    let player1 = config.subject_players.first().map(|s| s.as_str());
+    let player2 = config.subject_players.get(1).map(|s| s.as_str());
+
+    // Subject-player highlight takes precedence over category coloring,
+    // mirroring the order the conditional formats are added to the xlsx
+    // sheets (player rules are added after the category rules, so they win
+    // ties in Excel's own "last rule wins on overlap" semantics).
+    let player_style = |text: &str| -> Option<&'static str> {
+        if player1.is_some_and(|p| !p.is_empty() && text.contains(p)) {
+            Some("background-color:#C6EFCE")
+        } else if player2.is_some_and(|p| !p.is_empty() && text.contains(p)) {
+            Some("background-color:#BDD7EE")
+        } else {
+            None
+        }
+    };
+    let category_style = |cat: &str| -> Option<String> {
+        category_color_map
+            .get(cat)
+            .map(|color| format!("background-color:{color}"))
+    };
+
+    let ob_col_csv = headers.iter().position(|h| h == "OB name");
+    let n_col_csv = headers.iter().position(|h| h == "N");
+    let s_col_csv = headers.iter().position(|h| h == "S");
+    let e_col_csv = headers.iter().position(|h| h == "E");
+    let w_col_csv = headers.iter().position(|h| h == "W");
+
+    let mut doc = String::new();
+    doc.push_str("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">\n");
+    doc.push_str("<title>EDGAR Case Package</title></head><body>\n");
+    doc.push_str("<h1>EDGAR Case Package</h1>\n");
+
+    // -- Boards --
+    doc.push_str("<h2>Boards</h2>\n<table border=\"1\" cellspacing=\"0\" cellpadding=\"4\">\n");
+    doc.push_str("<tr><th>Board ID</th><th>Link</th><th>Hotspot ID</th><th>Hotspot Category</th>");
+    for (j, h) in headers.iter().enumerate() {
+        if j == bbo_col_csv {
+            continue; // hidden on the Boards sheet too
+        }
+        let _ = write!(doc, "<th>{}</th>", html_escape(h));
+        if Some(j) == ob_col_csv {
+            doc.push_str("<th>Overcaller</th><th>Responder</th><th>Advancer</th>");
+        }
+    }
+    doc.push_str("</tr>\n");
+
+    for (i, record) in records.iter().enumerate() {
+        let board_num = (i + 1) as u32;
+        let bbo_val = record.get(bbo_col_csv).unwrap_or("").trim();
+
+        let hotspot = if bbo_val.is_empty() {
+            None
+        } else {
+            let key = if config.is_anon {
+                bbo_val.to_string()
+            } else {
+                normalize_tinyurl(bbo_val)
+            };
+            url_to_hotspot.get(&key)
+        };
+
+        let _ = write!(doc, "<tr><td id=\"board-{n}\">{n}</td>", n = board_num);
+
+        doc.push_str("<td>");
+        if config.is_anon {
+            if let Some(lin_idx) = lin_url_col_csv {
+                let lin_url = record.get(lin_idx).unwrap_or("").trim();
+                if !lin_url.is_empty() {
+                    let decoded = percent_decode_url(lin_url);
+                    let _ = write!(doc, "<a href=\"{}\">link</a>", html_escape(&decoded));
+                }
+            }
+        } else if !bbo_val.is_empty() {
+            let _ = write!(
+                doc,
+                "<a href=\"{}\">link</a>",
+                html_escape(&to_https(bbo_val))
+            );
+        }
+        doc.push_str("</td>");
+
+        match hotspot {
+            Some((hs_id, hs_cat)) => {
+                let _ = write!(
+                    doc,
+                    "<td><a href=\"#hotspot-{hs_id}\">{hs_id}</a></td><td style=\"{style}\">{cat}</td>",
+                    hs_id = hs_id,
+                    style = category_style(hs_cat).unwrap_or_default(),
+                    cat = html_escape(hs_cat),
+                );
+            }
+            None => doc.push_str("<td></td><td></td>"),
+        }
+
+        let roles = ob_col_csv.and_then(|ob| {
+            let ob_name = record.get(ob).unwrap_or("").trim();
+            if ob_name.is_empty() {
+                return None;
+            }
+            let get_player = |col: Option<usize>| -> &str {
+                col.and_then(|c| record.get(c))
+                    .map(|s| s.trim())
+                    .unwrap_or("")
+            };
+            compute_ob_roles(
+                ob_name,
+                get_player(n_col_csv),
+                get_player(e_col_csv),
+                get_player(s_col_csv),
+                get_player(w_col_csv),
+            )
+        });
+
+        for (j, field) in record.iter().enumerate() {
+            if j == bbo_col_csv {
+                continue;
+            }
+            let trimmed = field.trim();
+            let _ = write!(
+                doc,
+                "<td style=\"{}\">{}</td>",
+                player_style(trimmed).unwrap_or_default(),
+                html_escape(trimmed)
+            );
+            if Some(j) == ob_col_csv {
+                match roles {
+                    Some((overcaller, responder, advancer)) => {
+                        for name in [overcaller, responder, advancer] {
+                            let _ = write!(
+                                doc,
+                                "<td style=\"{}\">{}</td>",
+                                player_style(name).unwrap_or_default(),
+                                html_escape(name)
+                            );
+                        }
+                    }
+                    None => doc.push_str("<td></td><td></td><td></td>"),
+                }
+            }
+        }
+        doc.push_str("</tr>\n");
+    }
+    doc.push_str("</table>\n\n");
+
+    // -- Hotspots --
+    doc.push_str("<h2>Hotspots</h2>\n<table border=\"1\" cellspacing=\"0\" cellpadding=\"4\">\n");
+    doc.push_str(
+        "<tr><th>Hotspot ID</th><th>Link</th><th>Board ID</th><th>Category</th><th>Subindex</th><th>Subject Player</th><th>Hit/Miss</th><th>Contract</th><th>Lead</th></tr>\n",
+    );
+
+    let mut hotspot_to_board: HashMap<u32, u32> = HashMap::new();
+    for (i, record) in records.iter().enumerate() {
+        let bbo_val = record.get(bbo_col_csv).unwrap_or("").trim();
+        if bbo_val.is_empty() {
+            continue;
+        }
+        let key = if config.is_anon {
+            bbo_val.to_string()
+        } else {
+            normalize_tinyurl(bbo_val)
+        };
+        if let Some((hs_id, _)) = url_to_hotspot.get(&key) {
+            hotspot_to_board.insert(*hs_id, (i + 1) as u32);
+        }
+    }
+
+    for (i, entry) in hotspot_entries.iter().enumerate() {
+        let hs_id = (i + 1) as u32;
+        let _ = write!(doc, "<tr><td id=\"hotspot-{hs_id}\">{hs_id}</td>", hs_id = hs_id);
+
+        let link_url = entry
+            .lin_url
+            .clone()
+            .unwrap_or_else(|| to_https(&entry.tinyurl));
+        let _ = write!(
+            doc,
+            "<td><a href=\"{}\">Link</a></td>",
+            html_escape(&percent_decode_url(&link_url)),
+        );
+
+        match hotspot_to_board.get(&hs_id) {
+            Some(board_num) => {
+                let _ = write!(doc, "<td><a href=\"#board-{n}\">{n}</a></td>", n = board_num);
+            }
+            None => doc.push_str("<td></td>"),
+        }
+
+        let hit_miss_style = match entry.hit_miss.as_str() {
+            "Hit" => "background-color:#FFC7CE",
+            "Miss" => "background-color:#C6EFCE",
+            _ => "",
+        };
+
+        let _ = write!(
+            doc,
+            "<td style=\"{cat_style}\">{cat}</td><td>{idx}</td><td style=\"{p_style}\">{player}</td><td style=\"{hm_style}\">{hm}</td><td>{contract}</td><td>{lead}</td></tr>\n",
+            cat_style = category_style(&entry.category).unwrap_or_default(),
+            cat = html_escape(&entry.category),
+            idx = entry.subindex,
+            p_style = player_style(&entry.subject_player).unwrap_or_default(),
+            player = html_escape(&entry.subject_player),
+            hm_style = hit_miss_style,
+            hm = html_escape(&entry.hit_miss),
+            contract = html_escape(&entry.contract),
+            lead = html_escape(&entry.lead),
+        );
+    }
+    doc.push_str("</table>\n\n");
+
+    // -- Cardplay (optional, from fetch output) --
+    if let Some(cp_path) = &config.cardplay_file {
+        if cp_path.exists() {
+            let cp_data = read_bbo_csv_fixed(cp_path)?;
+            let mut cp_reader = ReaderBuilder::new()
+                .flexible(true)
+                .from_reader(cp_data.as_bytes());
+            let cp_headers = cp_reader.headers()?.clone();
+            if let Some(cardplay_idx) = cp_headers.iter().position(|h| h == "Cardplay") {
+                doc.push_str(
+                    "<h2>Cardplay</h2>\n<table border=\"1\" cellspacing=\"0\" cellpadding=\"4\">\n<tr><th>Board ID</th><th>Cardplay</th></tr>\n",
+                );
+                for (i, result) in cp_reader.records().enumerate() {
+                    let rec = result.context("Failed to read cardplay CSV row")?;
+                    if let Some(limit) = config.deal_limit {
+                        if i >= limit {
+                            break;
+                        }
+                    }
+                    let cardplay = rec.get(cardplay_idx).unwrap_or("").trim();
+                    if cardplay.is_empty() || cardplay.starts_with("ERROR:") {
+                        continue;
+                    }
+                    let board_num = (i + 1) as u32;
+                    let _ = write!(
+                        doc,
+                        "<tr><td><a href=\"#board-{n}\">{n}</a></td><td>{cp}</td></tr>\n",
+                        n = board_num,
+                        cp = html_escape(cardplay),
+                    );
+                }
+                doc.push_str("</table>\n\n");
+            }
+        }
+    }
+
+    doc.push_str("</body></html>\n");
+
+    if let Some(parent) = out_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&out_path, &doc)
+        .with_context(|| format!("Failed to write report to {}", out_path.display()))?;
+
+    Ok(out_path)
+}
+
+/// Write the Boards and Hotspots sheets (and Cardplay, if present) out as
+/// flat CSV/TSV files alongside the xlsx workbook, for downstream tooling
+/// that can't read xlsx. Reuses the Boards sheet's derived Overcaller/
+/// Responder/Advancer columns and the same number-vs-string coercion the
+/// xlsx writer uses, and expands the HYPERLINK-formula links into literal
+/// URLs (tinyurl-to-https, or the decoded LIN URL for anon packages). A
+/// no-op, returning an empty list, when `config.flat_export` is `None`.
+fn write_flat_exports(
+    config: &PackageConfig,
+    headers: &StringRecord,
+    records: &[StringRecord],
+    bbo_col_csv: usize,
+    lin_url_col_csv: Option<usize>,
+    hotspot_entries: &[HotspotEntry],
+    url_to_hotspot: &HashMap<String, (u32, String)>,
+) -> Result<Vec<PathBuf>> {
+    let Some(flat) = &config.flat_export else {
+        return Ok(Vec::new());
+    };
+    let (delimiter, ext) = match flat.delimiter {
+        FlatDelimiter::Csv => (b',', "csv"),
+        FlatDelimiter::Tsv => (b'\t', "tsv"),
+    };
+
+    let ob_col_csv = headers.iter().position(|h| h == "OB name");
+    let n_col_csv = headers.iter().position(|h| h == "N");
+    let s_col_csv = headers.iter().position(|h| h == "S");
+    let e_col_csv = headers.iter().position(|h| h == "E");
+    let w_col_csv = headers.iter().position(|h| h == "W");
+
+    // Coerce a CSV field the same way the xlsx writer does: numbers as
+    // numbers (formatted back to their canonical string form), everything
+    // else as a string.
+    let coerce = |field: &str| -> String {
+        let trimmed = field.trim();
+        if trimmed.is_empty() {
+            String::new()
+        } else if let Ok(n) = trimmed.parse::<f64>() {
+            n.to_string()
+        } else {
+            field.to_string()
+        }
+    };
+
+    let mut out_paths = Vec::new();
+
+    // -- Boards --
+    {
+        let path = config.output.with_extension(format!("boards.{ext}"));
+        let mut w = csv::WriterBuilder::new()
+            .delimiter(delimiter)
+            .from_path(&path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+
+        let mut row_headers = vec![
+            "Board ID".to_string(),
+            "Link".to_string(),
+            "Hotspot ID".to_string(),
+            "Hotspot Category".to_string(),
+        ];
+        for (j, h) in headers.iter().enumerate() {
+            row_headers.push(h.to_string());
+            if Some(j) == ob_col_csv {
+                row_headers.push("Overcaller".to_string());
+                row_headers.push("Responder".to_string());
+                row_headers.push("Advancer".to_string());
+            }
+        }
+        w.write_record(&row_headers)?;
+
+        for (i, record) in records.iter().enumerate() {
+            // An empty input row (every field blank) — preserved as a blank
+            // output line when `blank_rows` is set (including when it's the
+            // very first row), dropped otherwise.
+            if record.iter().all(|f| f.trim().is_empty()) {
+                if flat.blank_rows {
+                    w.write_record(vec![String::new(); row_headers.len()])?;
+                }
+                continue;
+            }
+
+            let bbo_val = record.get(bbo_col_csv).unwrap_or("").trim();
+            let hotspot = if bbo_val.is_empty() {
+                None
+            } else {
+                let key = if config.is_anon {
+                    bbo_val.to_string()
+                } else {
+                    normalize_tinyurl(bbo_val)
+                };
+                url_to_hotspot.get(&key)
+            };
+
+            let link = if config.is_anon {
+                lin_url_col_csv
+                    .and_then(|idx| record.get(idx))
+                    .map(str::trim)
+                    .filter(|u| !u.is_empty())
+                    .map(percent_decode_url)
+                    .unwrap_or_default()
+            } else if !bbo_val.is_empty() {
+                to_https(bbo_val)
+            } else {
+                String::new()
+            };
+
+            let mut row = vec![
+                (i + 1).to_string(),
+                link,
+                hotspot.map(|(id, _)| id.to_string()).unwrap_or_default(),
+                hotspot.map(|(_, cat)| cat.clone()).unwrap_or_default(),
+            ];
+
+            let roles = ob_col_csv.and_then(|ob| {
+                let ob_name = record.get(ob).unwrap_or("").trim();
+                if ob_name.is_empty() {
+                    return None;
+                }
+                let get_player = |col: Option<usize>| -> &str {
+                    col.and_then(|c| record.get(c))
+                        .map(|s| s.trim())
+                        .unwrap_or("")
+                };
+                compute_ob_roles(
+                    ob_name,
+                    get_player(n_col_csv),
+                    get_player(e_col_csv),
+                    get_player(s_col_csv),
+                    get_player(w_col_csv),
+                )
+            });
+
+            for (j, field) in record.iter().enumerate() {
+                row.push(if j == bbo_col_csv && !field.trim().is_empty() {
+                    to_https(field)
+                } else {
+                    coerce(field)
+                });
+                if Some(j) == ob_col_csv {
+                    match roles {
+                        Some((overcaller, responder, advancer)) => {
+                            row.push(overcaller.to_string());
+                            row.push(responder.to_string());
+                            row.push(advancer.to_string());
+                        }
+                        None => row.extend([String::new(), String::new(), String::new()]),
+                    }
+                }
+            }
+            w.write_record(&row)?;
+        }
+        w.flush()?;
+        out_paths.push(path);
     }
-    String::from_utf8_lossy(&result).to_string()
-}
 
-/// Normalize a URL to use https scheme.
-fn to_https(url: &str) -> String {
-    let trimmed = url.trim();
-    if let Some(rest) = trimmed.strip_prefix("http://") {
-        format!("https://{rest}")
-    } else {
-        trimmed.to_string()
+    // -- Hotspots --
+    {
+        let path = config.output.with_extension(format!("hotspots.{ext}"));
+        let mut w = csv::WriterBuilder::new()
+            .delimiter(delimiter)
+            .from_path(&path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        w.write_record([
+            "Hotspot ID",
+            "Link",
+            "Board ID",
+            "Category",
+            "Subindex",
+            "Subject Player",
+            "Hit/Miss",
+            "Contract",
+            "Lead",
+        ])?;
+
+        let mut hotspot_to_board: HashMap<u32, u32> = HashMap::new();
+        for (i, record) in records.iter().enumerate() {
+            let bbo_val = record.get(bbo_col_csv).unwrap_or("").trim();
+            if bbo_val.is_empty() {
+                continue;
+            }
+            let key = if config.is_anon {
+                bbo_val.to_string()
+            } else {
+                normalize_tinyurl(bbo_val)
+            };
+            if let Some((hs_id, _)) = url_to_hotspot.get(&key) {
+                hotspot_to_board.insert(*hs_id, (i + 1) as u32);
+            }
+        }
+
+        for (i, entry) in hotspot_entries.iter().enumerate() {
+            let hs_id = (i + 1) as u32;
+            let link = entry
+                .lin_url
+                .as_deref()
+                .map(percent_decode_url)
+                .unwrap_or_else(|| to_https(&entry.tinyurl));
+            let board_id = hotspot_to_board
+                .get(&hs_id)
+                .map(|n| n.to_string())
+                .unwrap_or_default();
+            w.write_record([
+                hs_id.to_string(),
+                link,
+                board_id,
+                entry.category.clone(),
+                entry.subindex.to_string(),
+                entry.subject_player.clone(),
+                entry.hit_miss.clone(),
+                entry.contract.clone(),
+                entry.lead.clone(),
+            ])?;
+        }
+        w.flush()?;
+        out_paths.push(path);
     }
-}
 
-/// Convert a 0-based column index to an Excel column letter (A, B, ..., Z, AA, AB, ...).
-fn col_letter(idx: u32) -> String {
-    let mut result = String::new();
-    let mut n = idx;
-    loop {
-        result.insert(0, (b'A' + (n % 26) as u8) as char);
-        if n < 26 {
-            break;
+    // -- Cardplay (optional, from fetch output) --
+    if let Some(cp_path) = &config.cardplay_file {
+        if cp_path.exists() {
+            let cp_data = read_bbo_csv_fixed(cp_path)?;
+            let mut cp_reader = ReaderBuilder::new()
+                .flexible(true)
+                .from_reader(cp_data.as_bytes());
+            let cp_headers = cp_reader.headers()?.clone();
+            if let Some(cardplay_idx) = cp_headers.iter().position(|h| h == "Cardplay") {
+                let path = config.output.with_extension(format!("cardplay.{ext}"));
+                let mut w = csv::WriterBuilder::new()
+                    .delimiter(delimiter)
+                    .from_path(&path)
+                    .with_context(|| format!("Failed to create {}", path.display()))?;
+                w.write_record(["Board ID", "Cardplay"])?;
+
+                for (i, result) in cp_reader.records().enumerate() {
+                    let rec = result.context("Failed to read cardplay CSV row")?;
+                    if let Some(limit) = config.deal_limit {
+                        if i >= limit {
+                            break;
+                        }
+                    }
+                    let cardplay = rec.get(cardplay_idx).unwrap_or("").trim();
+                    if cardplay.is_empty() || cardplay.starts_with("ERROR:") {
+                        continue;
+                    }
+                    w.write_record([(i + 1).to_string(), cardplay.to_string()])?;
+                }
+                w.flush()?;
+                out_paths.push(path);
+            }
         }
-        n = n / 26 - 1;
     }
-    result
+
+    Ok(out_paths)
 }
 
-/// Configuration for the package workbook command.
-pub struct PackageConfig {
-    /// Path to the hand records CSV file
-    pub csv_file: PathBuf,
-    /// Path to the hotspot report text file
-    pub hotspot_file: PathBuf,
-    /// Path to the concise report text file
-    pub concise_file: PathBuf,
-    /// Output xlsx path
-    pub output: PathBuf,
-    /// Case folder path (for display in Summary)
-    pub case_folder: String,
-    /// Subject player usernames (for conditional formatting)
-    pub subject_players: Vec<String>,
-    /// Optional deal limit for testing (only include this many boards)
-    pub deal_limit: Option<usize>,
-    /// Optional path to cardplay CSV (output of fetch step)
-    pub cardplay_file: Option<PathBuf>,
-    /// Whether this is an anonymized package (changes link handling)
-    pub is_anon: bool,
+/// Progress information for the package-workbook operation.
+pub struct PackageProgress {
+    /// Number of board rows written so far
+    pub completed: usize,
+    /// Total number of board rows to write
+    pub total: usize,
 }
 
 /// Create a packaged Excel workbook from the three EDGAR case files.
 ///
 /// Produces a workbook with Summary, Boards, and Hotspots sheets.
+/// Calls `on_progress` after each board row is written.
 /// Returns a summary string on success.
-pub fn package_workbook(config: &PackageConfig) -> Result<String> {
+pub fn package_workbook(
+    config: &PackageConfig,
+    mut on_progress: impl FnMut(&PackageProgress),
+) -> Result<String> {
     use rust_xlsxwriter::{
         ConditionalFormatText, ConditionalFormatTextRule, Format, FormatAlign, FormatUnderline,
         Formula, Url, Workbook,
@@ -3488,11 +6257,9 @@ pub fn package_workbook(config: &PackageConfig) -> Result<String> {
         }
     }
 
-    // Category color palette
-    let category_colors = [
-        "#DAEEF3", "#E2EFDA", "#FCE4D6", "#D9E2F3", "#EDEDED", "#FFF2CC", "#E4DFEC", "#F8CBAD",
-        "#D6DCE4", "#C5E0B4",
-    ];
+    // Category -> fill color, from config overrides/palette (or the
+    // built-in default palette).
+    let category_color_map = resolve_category_colors(config, &unique_categories);
 
     // -- Create workbook --
     let mut workbook = Workbook::new();
@@ -3510,14 +6277,6 @@ pub fn package_workbook(config: &PackageConfig) -> Result<String> {
     let hit_fill = Format::new().set_background_color("#FFC7CE"); // light pink
     let miss_fill = Format::new().set_background_color("#C6EFCE"); // light green
 
-    // Helper: extract filename from path
-    let extract_filename = |p: &Path| -> String {
-        p.file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("(unknown)")
-            .to_string()
-    };
-
     // Helper: get file modified time as formatted string
     let file_date = |p: &Path| -> String {
         std::fs::metadata(p)
@@ -3530,6 +6289,86 @@ pub fn package_workbook(config: &PackageConfig) -> Result<String> {
             .unwrap_or_else(|| "(unknown)".to_string())
     };
 
+    let package_date = chrono::Local::now()
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string();
+
+    // -- File integrity: SHA-256 hash + byte length of each source file --
+    //
+    // Recorded on the Summary sheet and in a sidecar manifest so a reviewer
+    // can later re-hash the original EDGAR case files and confirm the
+    // delivered workbook was built from exactly those bytes.
+    let mut file_hashes: Vec<FileHash> = Vec::new();
+    for (label, path) in [
+        ("Hand Records CSV", Some(config.csv_file.as_path())),
+        ("Hotspot Report", Some(config.hotspot_file.as_path())),
+        ("Concise Report", Some(config.concise_file.as_path())),
+        ("Cardplay CSV", config.cardplay_file.as_deref()),
+    ] {
+        if let Some(path) = path {
+            let (hash, len) = sha256_file(path)?;
+            file_hashes.push(FileHash {
+                label,
+                filename: extract_filename(path),
+                hash,
+                len,
+            });
+        }
+    }
+    let manifest_entries: Vec<(String, String, u64)> = file_hashes
+        .iter()
+        .map(|f| (f.filename.clone(), f.hash.clone(), f.len))
+        .collect();
+    let package_digest_hex = package_digest(&manifest_entries);
+
+    // ---------------------------------------------------------------
+    // Markdown report (replaces the xlsx workbook entirely)
+    // ---------------------------------------------------------------
+    if config.output_format == OutputFormat::Markdown {
+        return render_text_package(
+            config,
+            &headers,
+            &records,
+            bbo_col_csv,
+            &hotspot_entries,
+            &url_to_hotspot,
+            &file_hashes,
+            &package_digest_hex,
+            &package_date,
+        );
+    }
+
+    // ---------------------------------------------------------------
+    // HTML report mirroring the Boards/Hotspots/Cardplay sheets (replaces
+    // the xlsx workbook unless `Both` was requested, in which case it's
+    // written alongside it further down).
+    // ---------------------------------------------------------------
+    let html_path = if config.output_format == OutputFormat::Html
+        || config.output_format == OutputFormat::Both
+    {
+        let path = render_html_package(
+            config,
+            &headers,
+            &records,
+            bbo_col_csv,
+            lin_url_col_csv,
+            &hotspot_entries,
+            &url_to_hotspot,
+            &category_color_map,
+        )?;
+        if config.output_format == OutputFormat::Html {
+            return Ok(format!(
+                "Package created: {}\n  Boards: {}\n  Hotspots: {}",
+                path.display(),
+                records.len(),
+                hotspot_entries.len(),
+            ));
+        }
+        Some(path)
+    } else {
+        None
+    };
+
     // ---------------------------------------------------------------
     // Boards sheet column layout:
     //   Board ID | Link | Hotspot ID | Hotspot Category | [CSV cols...]
@@ -3631,9 +6470,8 @@ pub fn package_workbook(config: &PackageConfig) -> Result<String> {
         sheet.write_string_with_format(row, 1, file_date(&config.hotspot_file), &left_fmt)?;
         row += 1;
 
-        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
         sheet.write_string_with_format(row, 0, "Package Date", &bold)?;
-        sheet.write_string_with_format(row, 1, &now, &left_fmt)?;
+        sheet.write_string_with_format(row, 1, &package_date, &left_fmt)?;
         row += 2;
 
         // Use formulas so counts stay live if sheets are edited
@@ -3664,6 +6502,21 @@ pub fn package_workbook(config: &PackageConfig) -> Result<String> {
             Formula::new("COUNTIF(Hotspots!H:H,\"Miss\")"),
             &left_fmt,
         )?;
+        row += 2;
+
+        sheet.write_string_with_format(row, 0, "File Integrity (SHA-256)", &bold)?;
+        row += 1;
+        for fh in &file_hashes {
+            sheet.write_string_with_format(row, 0, &format!("{} SHA-256", fh.label), &bold)?;
+            sheet.write_string_with_format(row, 1, &fh.hash, &left_fmt)?;
+            row += 1;
+            sheet.write_string_with_format(row, 0, &format!("{} Size (bytes)", fh.label), &bold)?;
+            sheet.write_number_with_format(row, 1, fh.len as f64, &left_fmt)?;
+            row += 1;
+        }
+        sheet.write_string_with_format(row, 0, "Package Digest", &bold)?;
+        sheet.write_string_with_format(row, 1, &package_digest_hex, &left_fmt)?;
+
         let summary_last_row = row;
 
         sheet.set_column_width(0, 22)?;
@@ -3690,6 +6543,11 @@ pub fn package_workbook(config: &PackageConfig) -> Result<String> {
     // ---------------------------------------------------------------
     // Boards sheet (second tab)
     // ---------------------------------------------------------------
+    // Summary is always the first worksheet added (index 0), and Boards and
+    // Hotspots always follow it in that fixed order — so their gids for the
+    // `Sheets` hyperlink dialect are always 1 and 2 respectively.
+    let boards_gid: u32 = 1;
+    let hotspots_gid: u32 = 2;
     let num_board_rows = records.len() as u32;
     {
         let sheet = workbook.add_worksheet();
@@ -3717,6 +6575,11 @@ pub fn package_workbook(config: &PackageConfig) -> Result<String> {
             let row = (i + 1) as u32;
             let excel_row = row + 1; // 1-based for formulas
 
+            on_progress(&PackageProgress {
+                completed: i + 1,
+                total: records.len(),
+            });
+
             // Board ID (sequential number)
             sheet.write_number(row, 0, (i + 1) as f64)?;
 
@@ -3754,10 +6617,13 @@ pub fn package_workbook(config: &PackageConfig) -> Result<String> {
                     normalize_tinyurl(bbo_url)
                 };
                 if let Some((hs_id, hs_cat)) = url_to_hotspot.get(&key) {
-                    let hs_link = format!(
-                        "HYPERLINK(\"#Hotspots!A\"&MATCH({id},Hotspots!$A:$A,0),{id})",
-                        id = hs_id
+                    let anchor = hyperlink_anchor(
+                        config.hyperlink_dialect,
+                        "Hotspots",
+                        hotspots_gid,
+                        &format!("MATCH({id},Hotspots!$A:$A,0)", id = hs_id),
                     );
+                    let hs_link = format!("HYPERLINK({anchor},{id})", id = hs_id);
                     sheet.write_formula_with_format(row, 2, Formula::new(hs_link), &link_fmt)?;
                     sheet.write_string(row, 3, hs_cat)?;
                 }
@@ -3793,20 +6659,7 @@ pub fn package_workbook(config: &PackageConfig) -> Result<String> {
                     let s_name = get_player(s_col_csv);
                     let w_name = get_player(w_col_csv);
 
-                    let ob_lower = ob_name.to_lowercase();
-                    // Clockwise: N -> E -> S -> W -> N
-                    // Overcaller = next clockwise, Responder = partner, Advancer = opp of overcaller
-                    let roles = if ob_lower == n_name.to_lowercase() {
-                        Some((e_name, s_name, w_name))
-                    } else if ob_lower == e_name.to_lowercase() {
-                        Some((s_name, w_name, n_name))
-                    } else if ob_lower == s_name.to_lowercase() {
-                        Some((w_name, n_name, e_name))
-                    } else if ob_lower == w_name.to_lowercase() {
-                        Some((n_name, e_name, s_name))
-                    } else {
-                        None
-                    };
+                    let roles = compute_ob_roles(ob_name, n_name, e_name, s_name, w_name);
 
                     if let Some((overcaller, responder, advancer)) = roles {
                         let insert_at = csv_col_offset + ob as u16 + 1;
@@ -3857,9 +6710,8 @@ pub fn package_workbook(config: &PackageConfig) -> Result<String> {
             }
 
             // Category conditional formatting (column 3 = Hotspot Category)
-            for (idx, cat) in unique_categories.iter().enumerate() {
-                let color = category_colors[idx % category_colors.len()];
-                let cat_fmt = Format::new().set_background_color(color);
+            for cat in &unique_categories {
+                let cat_fmt = Format::new().set_background_color(category_color_map[cat].as_str());
                 let cf = ConditionalFormatText::new()
                     .set_rule(ConditionalFormatTextRule::Contains(cat.clone()))
                     .set_format(&cat_fmt);
@@ -3922,18 +6774,26 @@ pub fn package_workbook(config: &PackageConfig) -> Result<String> {
             if let Some(ref bid) = entry.board_id {
                 // Anon: direct board_id lookup against Boards!$A:$A
                 let bid_num: u32 = bid.parse().unwrap_or(0);
-                let formula = format!(
-                    "HYPERLINK(\"#Boards!A\"&MATCH({id},Boards!$A:$A,0),{id})",
-                    id = bid_num
+                let anchor = hyperlink_anchor(
+                    config.hyperlink_dialect,
+                    "Boards",
+                    boards_gid,
+                    &format!("MATCH({id},Boards!$A:$A,0)", id = bid_num),
                 );
+                let formula = format!("HYPERLINK({anchor},{id})", id = bid_num);
                 sheet.write_formula_with_format(row, 3, Formula::new(formula), &link_fmt)?;
             } else {
                 // Original: INDEX/MATCH via tinyurl against BBO column
-                let board_id_formula = format!(
-                    "IFERROR(HYPERLINK(\"#Boards!A\"&MATCH(C{row},Boards!${col}:${col},0),INDEX(Boards!$A:$A,MATCH(C{row},Boards!${col}:${col},0))),\"\")",
+                let match_expr = format!(
+                    "MATCH(C{row},Boards!${col}:${col},0)",
                     row = excel_row,
                     col = bbo_col_letter,
                 );
+                let anchor =
+                    hyperlink_anchor(config.hyperlink_dialect, "Boards", boards_gid, &match_expr);
+                let board_id_formula = format!(
+                    "IFERROR(HYPERLINK({anchor},INDEX(Boards!$A:$A,{match_expr})),\"\")",
+                );
                 sheet.write_formula_with_format(
                     row,
                     3,
@@ -3994,9 +6854,8 @@ pub fn package_workbook(config: &PackageConfig) -> Result<String> {
             }
 
             // Category conditional formatting (column 4 = Category)
-            for (idx, cat) in unique_categories.iter().enumerate() {
-                let color = category_colors[idx % category_colors.len()];
-                let cat_fmt = Format::new().set_background_color(color);
+            for cat in &unique_categories {
+                let cat_fmt = Format::new().set_background_color(category_color_map[cat].as_str());
                 let cf = ConditionalFormatText::new()
                     .set_rule(ConditionalFormatTextRule::Contains(cat.clone()))
                     .set_format(&cat_fmt);
@@ -4005,6 +6864,100 @@ pub fn package_workbook(config: &PackageConfig) -> Result<String> {
         }
     }
 
+    // ---------------------------------------------------------------
+    // Legend sheet (category -> color key, so reviewers have a stable key
+    // even once categories exceed the palette and colors start repeating)
+    // ---------------------------------------------------------------
+    if !unique_categories.is_empty() {
+        let sheet = workbook.add_worksheet();
+        sheet.set_name("Legend")?;
+
+        sheet.write_string_with_format(0, 0, "Category", &header_fmt)?;
+        sheet.write_string_with_format(0, 1, "Color", &header_fmt)?;
+        sheet.write_string_with_format(0, 2, "Hotspot Count", &header_fmt)?;
+        sheet.write_string_with_format(0, 3, "Board Count", &header_fmt)?;
+
+        // Board counts per category, resolved the same way the Boards sheet
+        // resolves each board's hotspot category.
+        let mut board_counts: HashMap<&str, u32> = HashMap::new();
+        for record in &records {
+            let bbo_val = record.get(bbo_col_csv).unwrap_or("").trim();
+            if bbo_val.is_empty() {
+                continue;
+            }
+            let key = if config.is_anon {
+                bbo_val.to_string()
+            } else {
+                normalize_tinyurl(bbo_val)
+            };
+            if let Some((_, cat)) = url_to_hotspot.get(&key) {
+                *board_counts.entry(cat.as_str()).or_insert(0) += 1;
+            }
+        }
+
+        for (i, cat) in unique_categories.iter().enumerate() {
+            let row = (i + 1) as u32;
+            let swatch_fmt = Format::new().set_background_color(category_color_map[cat].as_str());
+            sheet.write_string_with_format(row, 0, cat, &bold)?;
+            sheet.write_string_with_format(row, 1, "", &swatch_fmt)?;
+            let hs_count = hotspot_entries.iter().filter(|e| &e.category == cat).count();
+            sheet.write_number(row, 2, hs_count as f64)?;
+            sheet.write_number(
+                row,
+                3,
+                *board_counts.get(cat.as_str()).unwrap_or(&0) as f64,
+            )?;
+        }
+
+        sheet.set_column_width(0, 22)?;
+        sheet.set_column_width(1, 10)?;
+        sheet.set_column_width(2, 16)?;
+        sheet.set_column_width(3, 14)?;
+    }
+
+    // ---------------------------------------------------------------
+    // Assessment sheet (optional — only when a training corpus was given)
+    // ---------------------------------------------------------------
+    if let Some(corpus_dir) = &config.classifier_corpus {
+        let classifier = HotspotClassifier::train(corpus_dir, HOTSPOT_CLASSIFIER_WINDOW)?;
+        let left_fmt = Format::new().set_align(FormatAlign::Left);
+
+        let sheet = workbook.add_worksheet();
+        sheet.set_name("Assessment")?;
+
+        sheet.write_string_with_format(0, 0, "Player", &header_fmt)?;
+        sheet.write_string_with_format(0, 1, "Flagged Posterior", &header_fmt)?;
+        sheet.write_string_with_format(0, 2, "Flagged Log-Prob", &header_fmt)?;
+        sheet.write_string_with_format(0, 3, "Clean Log-Prob", &header_fmt)?;
+        sheet.write_string_with_format(0, 4, "Top Contributing Patterns", &header_fmt)?;
+
+        let mut arow: u32 = 1;
+        for player in &config.subject_players {
+            let events = hotspot_events_for_player(&hotspot_entries, player);
+            let result = classifier.classify(&events);
+
+            sheet.write_string_with_format(arow, 0, player, &left_fmt)?;
+            sheet.write_number_with_format(arow, 1, result.flagged_posterior, &left_fmt)?;
+            sheet.write_number_with_format(arow, 2, result.flagged_log_prob, &left_fmt)?;
+            sheet.write_number_with_format(arow, 3, result.clean_log_prob, &left_fmt)?;
+
+            let top_desc = result
+                .top_tokens
+                .iter()
+                .map(|(tok, contrib)| format!("{tok} ({contrib:+.2})"))
+                .collect::<Vec<_>>()
+                .join("; ");
+            sheet.write_string_with_format(arow, 4, &top_desc, &left_fmt)?;
+            arow += 1;
+        }
+
+        sheet.set_column_width(0, 18)?;
+        sheet.set_column_width(1, 16)?;
+        sheet.set_column_width(2, 16)?;
+        sheet.set_column_width(3, 16)?;
+        sheet.set_column_width(4, 70)?;
+    }
+
     // ---------------------------------------------------------------
     // Cardplay sheet (optional, from fetch output)
     // ---------------------------------------------------------------
@@ -4048,10 +7001,13 @@ pub fn package_workbook(config: &PackageConfig) -> Result<String> {
                     let board_id = (i + 1) as f64;
 
                     // Board ID with hyperlink to Boards sheet
-                    let link = format!(
-                        "HYPERLINK(\"#Boards!A\"&MATCH({id},Boards!$A:$A,0),{id})",
-                        id = board_id as u32
+                    let anchor = hyperlink_anchor(
+                        config.hyperlink_dialect,
+                        "Boards",
+                        boards_gid,
+                        &format!("MATCH({id},Boards!$A:$A,0)", id = board_id as u32),
                     );
+                    let link = format!("HYPERLINK({anchor},{id})", id = board_id as u32);
                     sheet.write_formula_with_format(cp_row, 0, Formula::new(link), &link_fmt)?;
 
                     // Cardplay data
@@ -4083,6 +7039,27 @@ pub fn package_workbook(config: &PackageConfig) -> Result<String> {
         .save(&config.output)
         .map_err(|e| anyhow::anyhow!("Failed to save workbook: {}", e))?;
 
+    // Sidecar integrity manifest, so the hashes on the Summary sheet can be
+    // independently re-checked without opening the workbook.
+    let manifest_path = config.output.with_extension("manifest.txt");
+    write_manifest(
+        &manifest_path,
+        &extract_filename(&config.output),
+        &package_date,
+        &manifest_entries,
+        &package_digest_hex,
+    )?;
+
+    let flat_export_paths = write_flat_exports(
+        config,
+        &headers,
+        &records,
+        bbo_col_csv,
+        lin_url_col_csv,
+        &hotspot_entries,
+        &url_to_hotspot,
+    )?;
+
     let mut summary = format!(
         "Package created: {}\n  Boards: {}\n  Hotspots: {}",
         config.output.display(),
@@ -4092,6 +7069,13 @@ pub fn package_workbook(config: &PackageConfig) -> Result<String> {
     if cardplay_count > 0 {
         summary.push_str(&format!("\n  Cardplay: {}", cardplay_count));
     }
+    summary.push_str(&format!("\n  Manifest: {}", manifest_path.display()));
+    if let Some(path) = html_path {
+        summary.push_str(&format!("\n  HTML: {}", path.display()));
+    }
+    for path in &flat_export_paths {
+        summary.push_str(&format!("\n  Flat export: {}", path.display()));
+    }
     Ok(summary)
 }
 
@@ -4148,6 +7132,79 @@ mod tests {
         assert_eq!(col_letter(52), "BA");
     }
 
+    #[test]
+    fn test_compute_ob_roles() {
+        // N opens the bidding: Overcaller=E, Responder=S, Advancer=W
+        assert_eq!(
+            compute_ob_roles("North", "North", "East", "South", "West"),
+            Some(("East", "South", "West"))
+        );
+        // Matching is case-insensitive
+        assert_eq!(
+            compute_ob_roles("west", "North", "East", "South", "West"),
+            Some(("North", "East", "South"))
+        );
+        // OB not seated at this table
+        assert_eq!(
+            compute_ob_roles("Nobody", "North", "East", "South", "West"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_hyperlink_anchor() {
+        assert_eq!(
+            hyperlink_anchor(HyperlinkDialect::Excel, "Boards", 1, "MATCH(5,Boards!$A:$A,0)"),
+            "\"#Boards!A\"&MATCH(5,Boards!$A:$A,0)"
+        );
+        assert_eq!(
+            hyperlink_anchor(HyperlinkDialect::Sheets, "Boards", 1, "MATCH(5,Boards!$A:$A,0)"),
+            "\"#gid=1&range=A\"&MATCH(5,Boards!$A:$A,0)"
+        );
+    }
+
+    fn test_package_config(output: &str) -> PackageConfig {
+        PackageConfig {
+            csv_file: PathBuf::from("hands.csv"),
+            hotspot_file: PathBuf::from("hotspot.txt"),
+            concise_file: PathBuf::from("concise.txt"),
+            output: PathBuf::from(output),
+            case_folder: "case".to_string(),
+            subject_players: Vec::new(),
+            deal_limit: None,
+            cardplay_file: None,
+            is_anon: false,
+            classifier_corpus: None,
+            output_format: OutputFormat::Xlsx,
+            hyperlink_dialect: HyperlinkDialect::Excel,
+            flat_export: None,
+            category_palette: None,
+            category_color_overrides: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_category_colors_default_palette_cycles() {
+        let config = test_package_config("out.xlsx");
+        let categories = vec!["A".to_string(), "B".to_string()];
+        let colors = resolve_category_colors(&config, &categories);
+        assert_eq!(colors["A"], DEFAULT_CATEGORY_COLORS[0]);
+        assert_eq!(colors["B"], DEFAULT_CATEGORY_COLORS[1]);
+    }
+
+    #[test]
+    fn test_resolve_category_colors_override_wins() {
+        let mut config = test_package_config("out.xlsx");
+        config.category_palette = Some(vec!["#111111".to_string(), "#222222".to_string()]);
+        config
+            .category_color_overrides
+            .insert("B".to_string(), "#FF00FF".to_string());
+        let categories = vec!["A".to_string(), "B".to_string()];
+        let colors = resolve_category_colors(&config, &categories);
+        assert_eq!(colors["A"], "#111111");
+        assert_eq!(colors["B"], "#FF00FF");
+    }
+
     #[test]
     fn test_parse_hotspot_report() {
         use std::io::Write;
@@ -4247,6 +7304,93 @@ mod tests {
         assert_eq!(entries[1].board_id.as_deref(), Some("99"));
     }
 
+    #[test]
+    fn test_pseudonym_for_stable_and_salt_scoped() {
+        let a1 = pseudonym_for("playerOne", "case-key-1");
+        let a2 = pseudonym_for("playerOne", "case-key-1");
+        assert_eq!(a1, a2, "same token+salt must always yield the same pseudonym");
+
+        let b = pseudonym_for("playerOne", "case-key-2");
+        assert_ne!(a1, b, "different salts must not correlate to the same pseudonym");
+    }
+
+    #[test]
+    fn test_anonymize_lin_url_preserves_other_params_and_scheme() {
+        let mut anonymizer = Anonymizer::new("key", "");
+        let result = anonymize_lin_url(
+            "http://www.bridgebase.com/tools/handviewer.html?lin=pn|Bob,Alice,Carol,Dave|&wd=1 ",
+            &mut anonymizer,
+        );
+
+        let parsed = Url::parse(&result).unwrap();
+        assert_eq!(parsed.scheme(), "http");
+        assert_eq!(parsed.host_str(), Some("www.bridgebase.com"));
+
+        let pairs: HashMap<String, String> = parsed
+            .query_pairs()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect();
+        assert_eq!(pairs.get("wd").map(String::as_str), Some("1"));
+        let lin = pairs.get("lin").unwrap();
+        assert!(lin.starts_with("pn|") && lin.ends_with('|'));
+        assert!(!lin.contains("Bob") && !lin.contains("Alice"));
+    }
+
+    #[test]
+    fn test_url_filter_rules_deny_beats_allow_and_literal_plus_regex() {
+        use std::io::Write;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rules.txt");
+        let mut f = std::fs::File::create(&path).unwrap();
+        writeln!(f, "# comment, ignored").unwrap();
+        writeln!(f, "deny regex:(?i)tracker\\d+\\.example\\.com").unwrap();
+        writeln!(f, "allow bridgebase.com").unwrap();
+        f.flush().unwrap();
+
+        let rules = UrlFilterRules::from_file(&path).unwrap();
+
+        assert_eq!(
+            rules.decide("https://www.bridgebase.com/tools/handviewer.html"),
+            UrlDecision::Anonymize
+        );
+        assert_eq!(
+            rules.decide("https://tracker7.example.com/x"),
+            UrlDecision::Strip,
+            "deny rules win even when a host would otherwise be allowed"
+        );
+        assert_eq!(
+            rules.decide("https://some-other-site.com/x"),
+            UrlDecision::Leave,
+            "non-matching host is left alone once an allowlist is configured"
+        );
+    }
+
+    #[test]
+    fn test_url_filter_rules_no_allowlist_anonymizes_everything_not_denied() {
+        use std::io::Write;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rules.txt");
+        let mut f = std::fs::File::create(&path).unwrap();
+        writeln!(f, "deny ads.net").unwrap();
+        f.flush().unwrap();
+
+        let rules = UrlFilterRules::from_file(&path).unwrap();
+        assert_eq!(
+            rules.decide("https://anything.example.com/x"),
+            UrlDecision::Anonymize
+        );
+        assert_eq!(rules.decide("https://ads.net/x"), UrlDecision::Strip);
+    }
+
+    #[test]
+    fn test_anonymize_lin_url_falls_back_on_unparseable_input() {
+        let mut anonymizer = Anonymizer::new("key", "");
+        assert_eq!(
+            anonymize_lin_url("not a url", &mut anonymizer),
+            "not a url"
+        );
+    }
+
     #[test]
     fn test_anonymize_text_column_alignment() {
         use std::io::Write;
@@ -4278,7 +7422,8 @@ mod tests {
         let empty_urls = HashMap::new();
         let empty_board_ids: HashMap<String, (String, String)> = HashMap::new();
 
-        anonymize_text_file(&input, &output, &mappings, &empty_urls, &empty_board_ids).unwrap();
+        anonymize_text_file(&input, &output, &mappings, &empty_urls, &empty_board_ids, None)
+            .unwrap();
 
         let result = std::fs::read_to_string(&output).unwrap();
         let lines: Vec<&str> = result.lines().collect();
@@ -4367,6 +7512,7 @@ mod tests {
             &name_mappings,
             &url_mappings,
             &board_id_map,
+            None,
         )
         .unwrap();
 
@@ -4388,4 +7534,40 @@ mod tests {
         assert!(!result.contains("player1"));
         assert!(!result.contains("player2"));
     }
+
+    #[test]
+    fn test_count_csv_rows_embedded_newline() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("embedded_newline.csv");
+        // A quoted field containing a literal newline used to inflate a
+        // naive line count, even though it's still a single data row.
+        std::fs::write(&path, "Name,Notes\nAlice,\"line one\nline two\"\nBob,plain\n").unwrap();
+
+        assert_eq!(count_csv_rows(&path).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_hash_source_url_stable_and_sensitive() {
+        let a = hash_source_url("https://tinyurl.com/abc");
+        let b = hash_source_url("  https://tinyurl.com/abc  ");
+        let c = hash_source_url("https://tinyurl.com/xyz");
+        assert_eq!(a, b, "whitespace around the URL shouldn't change the hash");
+        assert_ne!(a, c, "a different URL should hash differently");
+    }
+
+    #[test]
+    fn test_write_atomic_replaces_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.csv");
+        std::fs::write(&path, "stale").unwrap();
+
+        write_atomic(&path, |tmp_path| {
+            std::fs::write(tmp_path, "fresh")?;
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "fresh");
+        assert!(!path.with_extension("csv.tmp").exists());
+    }
 }
@@ -0,0 +1,271 @@
+//! Reusable card-by-card play engine.
+//!
+//! `dd-debug` used to hand-roll trick-winner determination, leader rotation,
+//! and tricks-won accounting in two nearly-duplicated code paths (mid-trick
+//! and trick-boundary mode). [`PlayState`] factors that bookkeeping into a
+//! single engine both modes (and any future binary) can drive, and it
+//! validates follow-suit on every play so a malformed LIN file is rejected
+//! instead of silently mis-scored.
+
+use bridge_parsers::{Card, Rank, Suit};
+use bridge_solver::cards::{card_of, suit_of};
+use bridge_solver::{Hands, PartialTrick};
+use bridge_solver::{CLUB, DIAMOND, EAST, HEART, NORTH, NOTRUMP, SOUTH, SPADE, WEST};
+
+/// Partnership side, for tallying tricks won.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    NorthSouth,
+    EastWest,
+}
+
+fn side_of(seat: usize) -> Side {
+    if seat == NORTH || seat == SOUTH {
+        Side::NorthSouth
+    } else {
+        Side::EastWest
+    }
+}
+
+/// Card-by-card state of an in-progress deal: current holdings, trump,
+/// leader, tricks won per side, and the trick in progress.
+pub struct PlayState {
+    trump: usize,
+    leader: usize,
+    holdings: [Vec<Card>; 4],
+    hands: Hands,
+    partial_trick: PartialTrick,
+    cards_in_trick: Vec<(usize, usize)>,
+    tricks_won: [u8; 2],
+}
+
+impl PlayState {
+    /// Build a fresh play state from a PBN deal string and the opening leader.
+    pub fn new(deal_pbn: &str, trump: usize, leader: usize) -> Result<Self, String> {
+        let hands =
+            Hands::from_pbn(deal_pbn).ok_or_else(|| format!("Failed to parse deal: {}", deal_pbn))?;
+        let holdings = parse_deal_holdings(deal_pbn)?;
+        Ok(Self {
+            trump,
+            leader,
+            holdings,
+            hands,
+            partial_trick: PartialTrick::new(),
+            cards_in_trick: Vec::new(),
+            tricks_won: [0, 0],
+        })
+    }
+
+    /// Seat currently on play.
+    pub fn to_play(&self) -> usize {
+        (self.leader + self.cards_in_trick.len()) % 4
+    }
+
+    /// Legal plays for the seat on play: cards following the led suit if the
+    /// seat holds any, otherwise the whole hand.
+    pub fn legal_plays(&self, seat: usize) -> Vec<Card> {
+        let led_suit = self
+            .cards_in_trick
+            .first()
+            .map(|&(_, solver_card)| solver_suit_to_suit(suit_of(solver_card)));
+        let holding = &self.holdings[seat];
+        if let Some(suit) = led_suit {
+            let following: Vec<Card> = holding.iter().copied().filter(|c| c.suit == suit).collect();
+            if !following.is_empty() {
+                return following;
+            }
+        }
+        holding.to_vec()
+    }
+
+    /// Play a card for the seat currently on play. Rejects a card the seat
+    /// doesn't hold, or that fails to follow suit when a legal follow existed.
+    pub fn play(&mut self, card: Card) -> Result<(), String> {
+        let seat = self.to_play();
+        let legal = self.legal_plays(seat);
+        if !legal.contains(&card) {
+            return Err(format!(
+                "Illegal play: {}{} by seat {} does not follow suit",
+                card.suit.to_char(),
+                card.rank.to_char(),
+                seat
+            ));
+        }
+
+        let solver_card = bridge_card_to_solver(card)?;
+        self.hands[seat].remove(solver_card);
+        self.partial_trick.add(solver_card, seat);
+        self.cards_in_trick.push((seat, solver_card));
+        self.holdings[seat].retain(|c| c != &card);
+        Ok(())
+    }
+
+    /// If the in-progress trick just received its fourth card, determines
+    /// the winner, tallies it, rotates the leader, and resets the partial
+    /// trick for the next one. Returns the winning seat, or `None` if the
+    /// trick isn't complete yet.
+    pub fn trick_complete(&mut self) -> Option<usize> {
+        if self.cards_in_trick.len() < 4 {
+            return None;
+        }
+        let winner = determine_trick_winner(&self.cards_in_trick, self.trump, self.leader);
+        self.tricks_won[side_of(winner) as usize] += 1;
+        self.leader = winner;
+        self.partial_trick = PartialTrick::new();
+        self.cards_in_trick.clear();
+        Some(winner)
+    }
+
+    /// Tricks won so far by `side`.
+    pub fn tricks_won(&self, side: Side) -> u8 {
+        self.tricks_won[side as usize]
+    }
+
+    pub fn hands(&self) -> &Hands {
+        &self.hands
+    }
+
+    /// Leader of the trick in progress (stable until [`trick_complete`] rotates it).
+    pub fn leader(&self) -> usize {
+        self.leader
+    }
+
+    pub fn partial_trick(&self) -> &PartialTrick {
+        &self.partial_trick
+    }
+
+    pub fn cards_in_trick(&self) -> &[(usize, usize)] {
+        &self.cards_in_trick
+    }
+}
+
+fn solver_suit_to_suit(solver_suit: usize) -> Suit {
+    match solver_suit {
+        SPADE => Suit::Spades,
+        HEART => Suit::Hearts,
+        DIAMOND => Suit::Diamonds,
+        _ => Suit::Clubs,
+    }
+}
+
+/// Parse a PBN deal string (e.g. `"N:AKQ.JT9.876.5432 ..."`) into per-seat
+/// holdings, keyed by the bridge_solver seat constants.
+fn parse_deal_holdings(deal_pbn: &str) -> Result<[Vec<Card>; 4], String> {
+    let (first_seat_str, hands_str) = deal_pbn
+        .split_once(':')
+        .ok_or_else(|| format!("Invalid deal (missing seat prefix): {}", deal_pbn))?;
+
+    let first_seat = match first_seat_str.trim().to_uppercase().as_str() {
+        "N" => NORTH,
+        "E" => EAST,
+        "S" => SOUTH,
+        "W" => WEST,
+        _ => return Err(format!("Invalid deal seat prefix: {}", first_seat_str)),
+    };
+
+    let suits = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+    let mut holdings: [Vec<Card>; 4] = [Vec::new(), Vec::new(), Vec::new(), Vec::new()];
+    for (i, hand_str) in hands_str.split_whitespace().enumerate() {
+        let seat = (first_seat + i) % 4;
+        for (suit, ranks) in suits.iter().zip(hand_str.split('.')) {
+            for rank_char in ranks.chars() {
+                if let Some(rank) = Rank::from_char(rank_char) {
+                    holdings[seat].push(Card::new(*suit, rank));
+                }
+            }
+        }
+    }
+
+    Ok(holdings)
+}
+
+fn bridge_card_to_solver(card: Card) -> Result<usize, String> {
+    let suit = match card.suit {
+        Suit::Spades => SPADE,
+        Suit::Hearts => HEART,
+        Suit::Diamonds => DIAMOND,
+        Suit::Clubs => CLUB,
+    };
+
+    let rank = match card.rank {
+        Rank::Ace => 12,
+        Rank::King => 11,
+        Rank::Queen => 10,
+        Rank::Jack => 9,
+        Rank::Ten => 8,
+        Rank::Nine => 7,
+        Rank::Eight => 6,
+        Rank::Seven => 5,
+        Rank::Six => 4,
+        Rank::Five => 3,
+        Rank::Four => 2,
+        Rank::Three => 1,
+        Rank::Two => 0,
+    };
+
+    Ok(card_of(suit, rank))
+}
+
+/// Determine the winning seat of a complete trick.
+pub fn determine_trick_winner(cards: &[(usize, usize)], trump: usize, leader: usize) -> usize {
+    let mut winner_idx = 0;
+    let mut winning_card = cards[0].1;
+
+    for (i, (_seat, card)) in cards.iter().enumerate().skip(1) {
+        let card_suit = suit_of(*card);
+        let beats = if card_suit == suit_of(winning_card) {
+            *card < winning_card // Lower card value = higher rank in bridge-solver
+        } else if card_suit == trump && trump < NOTRUMP {
+            suit_of(winning_card) != trump // Trump beats non-trump
+        } else {
+            false
+        };
+
+        if beats {
+            winner_idx = i;
+            winning_card = *card;
+        }
+    }
+
+    (leader + winner_idx) % 4
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_determine_trick_winner_highest_of_led_suit() {
+        // Led suit wins it, no trump in play.
+        let cards = [
+            (NORTH, card_of(SPADE, 5)),  // 7
+            (EAST, card_of(SPADE, 9)),   // J
+            (SOUTH, card_of(SPADE, 12)), // A
+            (WEST, card_of(SPADE, 2)),   // 4
+        ];
+        assert_eq!(determine_trick_winner(&cards, NOTRUMP, NORTH), SOUTH);
+    }
+
+    #[test]
+    fn test_determine_trick_winner_trump_beats_led_suit() {
+        let cards = [
+            (NORTH, card_of(SPADE, 12)), // A of spades, led
+            (EAST, card_of(HEART, 0)),   // 2 of hearts, trump
+            (SOUTH, card_of(SPADE, 11)), // K of spades
+            (WEST, card_of(CLUB, 5)),    // off-suit discard
+        ];
+        assert_eq!(determine_trick_winner(&cards, HEART, NORTH), EAST);
+    }
+
+    #[test]
+    fn test_determine_trick_winner_leader_rotation() {
+        // East on lead; winner_idx is relative to the leader, not absolute seat order.
+        let cards = [
+            (EAST, card_of(DIAMOND, 2)),
+            (SOUTH, card_of(DIAMOND, 9)),
+            (WEST, card_of(DIAMOND, 0)),
+            (NORTH, card_of(DIAMOND, 4)),
+        ];
+        assert_eq!(determine_trick_winner(&cards, NOTRUMP, EAST), SOUTH);
+    }
+}
@@ -0,0 +1,94 @@
+//! Shared per-host token-bucket rate limiting for URL-fetching workloads.
+//!
+//! Pulled out of `bbo_csv.rs` so `pipeline::generate_lookup_file` (used by
+//! the GUI's Fetch tab) and `bbo_csv`'s own `fetch_cardplay` gate concurrent
+//! requests through the same limiter instead of each binary growing its own
+//! copy that can drift out of sync with the other.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// A per-host AIMD rate limiter.
+///
+/// The bucket holds up to `capacity` tokens, refilled at `refill_per_sec`.
+/// Workers call `acquire()` before hitting the network, blocking until a
+/// token is available. A 429 ("Rate limited") response should call
+/// `throttle()`, which halves the refill rate immediately (multiplicative
+/// decrease); `recover()` nudges it back up additively, so a rate-limit
+/// event backs off every worker hitting that host rather than just the one
+/// that hit it.
+pub struct TokenBucket {
+    tokens: Mutex<f64>,
+    last_refill: Mutex<std::time::Instant>,
+    refill_per_sec: Mutex<f64>,
+    base_refill_per_sec: f64,
+    capacity: f64,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: usize, delay_ms: u64) -> Self {
+        let refill_per_sec = 1000.0 / delay_ms.max(1) as f64;
+        TokenBucket {
+            tokens: Mutex::new(capacity as f64),
+            last_refill: Mutex::new(std::time::Instant::now()),
+            refill_per_sec: Mutex::new(refill_per_sec),
+            base_refill_per_sec: refill_per_sec,
+            capacity: capacity.max(1) as f64,
+        }
+    }
+
+    /// Block until a token is available, then take one.
+    pub fn acquire(&self) {
+        loop {
+            {
+                let mut tokens = self.tokens.lock().unwrap();
+                let mut last = self.last_refill.lock().unwrap();
+                let now = std::time::Instant::now();
+                let elapsed = now.duration_since(*last).as_secs_f64();
+                let rate = *self.refill_per_sec.lock().unwrap();
+                *tokens = (*tokens + elapsed * rate).min(self.capacity);
+                *last = now;
+                if *tokens >= 1.0 {
+                    *tokens -= 1.0;
+                    return;
+                }
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+    }
+
+    /// AIMD: a 429 halves the refill rate immediately.
+    pub fn throttle(&self) {
+        let mut rate = self.refill_per_sec.lock().unwrap();
+        *rate = (*rate / 2.0).max(0.5);
+    }
+
+    /// Additive recovery toward the configured rate, called after the cool-down.
+    pub fn recover(&self) {
+        let mut rate = self.refill_per_sec.lock().unwrap();
+        if *rate < self.base_refill_per_sec {
+            *rate = (*rate + self.base_refill_per_sec * 0.1).min(self.base_refill_per_sec);
+        }
+    }
+}
+
+/// Host part of a URL, for keying per-host rate limiters (`"example.com"`
+/// out of `"https://example.com/path"`).
+pub fn url_host(url: &str) -> &str {
+    let rest = url.splitn(2, "://").nth(1).unwrap_or(url);
+    rest.split('/').next().unwrap_or(rest)
+}
+
+/// Get-or-create the `TokenBucket` for `host`, sharing one bucket per host
+/// across all fetch workers.
+pub fn bucket_for(
+    buckets: &Mutex<HashMap<String, Arc<TokenBucket>>>,
+    host: &str,
+    capacity: usize,
+    delay_ms: u64,
+) -> Arc<TokenBucket> {
+    let mut map = buckets.lock().unwrap();
+    map.entry(host.to_string())
+        .or_insert_with(|| Arc::new(TokenBucket::new(capacity, delay_ms)))
+        .clone()
+}
@@ -0,0 +1,212 @@
+//! Standard duplicate bridge scoring
+//!
+//! Trick points, bonuses, and undertrick penalties for a bid-and-made (or
+//! bid-and-down) contract, plus the standard IMP table for turning a score
+//! swing between two results into IMPs.
+
+use bridge_solver::{CLUB, HEART, NOTRUMP, SPADE};
+
+/// How a contract was doubled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Doubled {
+    Undoubled,
+    Doubled,
+    Redoubled,
+}
+
+/// Score for declarer's side bidding `level` of `strain`, with `doubled`
+/// status, taking `tricks_made` tricks total (not just overtricks), at
+/// `vulnerable`. Positive when declarer's side gains; negative when
+/// declarer goes down.
+pub fn score_contract(level: u8, strain: usize, doubled: Doubled, tricks_made: u8, vulnerable: bool) -> i32 {
+    let needed = level + 6;
+    if tricks_made >= needed {
+        score_made(level, strain, doubled, tricks_made - needed, vulnerable)
+    } else {
+        -score_down(doubled, needed - tricks_made, vulnerable)
+    }
+}
+
+/// Trick value of one trick in `strain`, beyond the first in the case of
+/// no trump (worth 40 for the first trick, 30 thereafter), undoubled.
+fn trick_value(strain: usize, trick_index: u8) -> i32 {
+    if strain == SPADE || strain == HEART {
+        30
+    } else if strain == CLUB || strain == bridge_solver::DIAMOND {
+        20
+    } else if trick_index == 0 {
+        40
+    } else {
+        30
+    }
+}
+
+fn double_multiplier(doubled: Doubled) -> i32 {
+    match doubled {
+        Doubled::Undoubled => 1,
+        Doubled::Doubled => 2,
+        Doubled::Redoubled => 4,
+    }
+}
+
+fn score_made(level: u8, strain: usize, doubled: Doubled, overtricks: u8, vulnerable: bool) -> i32 {
+    let contract_trick_score: i32 = (0..level).map(|i| trick_value(strain, i)).sum::<i32>() * double_multiplier(doubled);
+
+    let overtrick_value = match doubled {
+        Doubled::Undoubled => {
+            if strain == SPADE || strain == HEART || strain == NOTRUMP {
+                30
+            } else {
+                20
+            }
+        }
+        Doubled::Doubled => {
+            if vulnerable {
+                200
+            } else {
+                100
+            }
+        }
+        Doubled::Redoubled => {
+            if vulnerable {
+                400
+            } else {
+                200
+            }
+        }
+    };
+    let overtrick_score = overtrick_value * overtricks as i32;
+
+    let bonus = if contract_trick_score >= 100 {
+        if vulnerable {
+            500
+        } else {
+            300
+        }
+    } else {
+        50
+    };
+
+    let slam_bonus = match level {
+        6 => {
+            if vulnerable {
+                750
+            } else {
+                500
+            }
+        }
+        7 => {
+            if vulnerable {
+                1500
+            } else {
+                1000
+            }
+        }
+        _ => 0,
+    };
+
+    let insult = match doubled {
+        Doubled::Undoubled => 0,
+        Doubled::Doubled => 50,
+        Doubled::Redoubled => 100,
+    };
+
+    contract_trick_score + overtrick_score + bonus + slam_bonus + insult
+}
+
+/// Penalty for going down `undertricks`, with `doubled` status, at
+/// `vulnerable`. Always non-negative; callers negate it themselves.
+fn score_down(doubled: Doubled, undertricks: u8, vulnerable: bool) -> i32 {
+    match doubled {
+        Doubled::Undoubled => undertricks as i32 * if vulnerable { 100 } else { 50 },
+        Doubled::Doubled | Doubled::Redoubled => {
+            let multiplier = if doubled == Doubled::Redoubled { 2 } else { 1 };
+            let mut total = 0;
+            for trick_index in 0..undertricks {
+                total += if vulnerable {
+                    if trick_index == 0 {
+                        200
+                    } else {
+                        300
+                    }
+                } else if trick_index == 0 {
+                    100
+                } else if trick_index < 3 {
+                    200
+                } else {
+                    300
+                };
+            }
+            total * multiplier
+        }
+    }
+}
+
+/// Convert a point swing between two results into IMPs, per the standard
+/// duplicate IMP table. The sign of the input is preserved.
+pub fn points_to_imps(points: i32) -> i32 {
+    const TABLE: [i32; 24] = [
+        20, 50, 90, 130, 170, 220, 270, 320, 370, 430, 500, 600, 750, 900, 1100, 1300, 1500, 1750,
+        2000, 2250, 2500, 3000, 3500, 4000,
+    ];
+    let magnitude = points.unsigned_abs() as i32;
+    let imps = TABLE.iter().take_while(|&&threshold| magnitude >= threshold).count() as i32;
+    if points < 0 {
+        -imps
+    } else {
+        imps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bridge_solver::{CLUB, DIAMOND, HEART, NOTRUMP, SPADE};
+
+    /// Known duplicate-bridge scores, `(level, strain, doubled, tricks_made, vulnerable, expected)`.
+    const KNOWN_SCORES: &[(u8, usize, Doubled, u8, bool, i32)] = &[
+        // Part-scores
+        (1, CLUB, Doubled::Undoubled, 7, false, 70),
+        (3, NOTRUMP, Doubled::Undoubled, 9, false, 400),
+        (3, NOTRUMP, Doubled::Undoubled, 9, true, 600),
+        // Games
+        (4, SPADE, Doubled::Undoubled, 10, false, 420),
+        (4, HEART, Doubled::Undoubled, 10, true, 620),
+        (5, DIAMOND, Doubled::Undoubled, 11, false, 400),
+        // Slams
+        (6, NOTRUMP, Doubled::Undoubled, 12, false, 990),
+        (7, NOTRUMP, Doubled::Undoubled, 13, true, 2220),
+        // Overtricks
+        (4, SPADE, Doubled::Undoubled, 11, false, 450),
+        // Doubled/redoubled making
+        (4, SPADE, Doubled::Doubled, 11, true, 990),
+        (1, CLUB, Doubled::Redoubled, 7, false, 230),
+        // Going down
+        (4, SPADE, Doubled::Undoubled, 9, false, -50),
+        (4, SPADE, Doubled::Undoubled, 9, true, -100),
+        (3, NOTRUMP, Doubled::Doubled, 6, false, -500),
+        (3, NOTRUMP, Doubled::Doubled, 6, true, -800),
+    ];
+
+    #[test]
+    fn test_score_contract_known_scores() {
+        for &(level, strain, doubled, tricks_made, vulnerable, expected) in KNOWN_SCORES {
+            let actual = score_contract(level, strain, doubled, tricks_made, vulnerable);
+            assert_eq!(
+                actual, expected,
+                "level={level} strain={strain} doubled={doubled:?} tricks_made={tricks_made} vulnerable={vulnerable}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_points_to_imps() {
+        assert_eq!(points_to_imps(0), 0);
+        assert_eq!(points_to_imps(19), 0);
+        assert_eq!(points_to_imps(20), 1);
+        assert_eq!(points_to_imps(420), 9);
+        assert_eq!(points_to_imps(4000), 24);
+        assert_eq!(points_to_imps(10000), 24);
+        assert_eq!(points_to_imps(-420), -9);
+    }
+}
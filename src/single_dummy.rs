@@ -0,0 +1,558 @@
+//! Restricted-information (single-dummy) DD analysis.
+//!
+//! [`crate::dd_analysis::compute_dd_costs`] judges every card against the
+//! double-dummy optimum, which assumes the player can see all four hands.
+//! In the room, the seat on play only sees its own hand and dummy's (once
+//! tabled); the other two hands are known only by card count and whatever
+//! void inferences the play so far has revealed. This module answers the
+//! more honest question: averaged over many redeals consistent with that
+//! partial information, what trick total could the player have reasonably
+//! expected?
+//!
+//! Analysis here is at trick-boundary granularity (DD sampled at the start
+//! and end of each trick, cost attributed to the trick's leader), the same
+//! granularity as [`crate::dd_analysis::analyze_board`]'s trick-boundary
+//! mode; the double-dummy path remains the default everywhere else.
+
+use bridge_parsers::{Card, Rank, Suit};
+use bridge_solver::cards::{card_of, suit_of};
+use bridge_solver::{CutoffCache, Hands, PatternCache, Solver};
+use bridge_solver::{CLUB, DIAMOND, EAST, HEART, NORTH, NOTRUMP, SOUTH, SPADE, WEST};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Configuration for single-dummy sampling.
+#[derive(Debug, Clone)]
+pub struct SingleDummyConfig {
+    /// Number of constrained random redeals averaged at each decision point.
+    pub samples: usize,
+}
+
+impl Default for SingleDummyConfig {
+    fn default() -> Self {
+        Self { samples: 50 }
+    }
+}
+
+/// Result of single-dummy cost computation: shaped like
+/// [`crate::dd_analysis::DdCostsResult`], but with averaged, real-valued
+/// expected tricks in place of a single omniscient integer. `costs` has one
+/// entry per trick (trick-boundary granularity).
+#[derive(Debug, Clone)]
+pub struct SingleDummyCostsResult {
+    pub costs: Vec<f64>,
+    pub initial_dd: f64,
+    pub declarer_seat: usize,
+    pub declarer_is_ns: bool,
+}
+
+/// Compute single-dummy (restricted-information) DD costs per trick.
+///
+/// Arguments mirror [`crate::dd_analysis::compute_dd_costs`]. At each trick
+/// boundary, the DD value is estimated from the perspective of that trick's
+/// leader: their own remaining hand and dummy's are held fixed, and the two
+/// concealed hands are redealt `config.samples` times, honoring the known
+/// remaining card counts and any void suits inferred from failures to
+/// follow suit earlier in the play.
+pub fn compute_single_dummy_costs(
+    deal_pbn: &str,
+    cardplay: &str,
+    contract: &str,
+    declarer: &str,
+    config: &SingleDummyConfig,
+) -> Result<SingleDummyCostsResult, String> {
+    let trump = parse_trump(contract)?;
+    let declarer_seat = parse_declarer_seat(declarer)?;
+    let initial_leader = (declarer_seat + 1) % 4;
+    let declarer_is_ns = declarer_seat == NORTH || declarer_seat == SOUTH;
+    let dummy_seat = (declarer_seat + 2) % 4;
+
+    let mut remaining = parse_deal_holdings(deal_pbn)?;
+    let tricks = parse_cardplay(cardplay)?;
+
+    if tricks.is_empty() {
+        return Ok(SingleDummyCostsResult {
+            costs: Vec::new(),
+            initial_dd: 0.0,
+            declarer_seat,
+            declarer_is_ns,
+        });
+    }
+
+    let mut void_suits: [Vec<Suit>; 4] = Default::default();
+    let mut cutoff_cache = CutoffCache::new(16);
+    let mut pattern_cache = PatternCache::new(16);
+    let mut rng = rand::thread_rng();
+
+    let initial_dd = expected_declarer_tricks(
+        &remaining,
+        initial_leader,
+        dummy_seat,
+        trump,
+        declarer_is_ns,
+        0,
+        &void_suits,
+        config.samples,
+        &mut cutoff_cache,
+        &mut pattern_cache,
+        &mut rng,
+    )
+    .unwrap_or(0.0);
+
+    let mut costs = Vec::new();
+    let mut declarer_tricks_won: u8 = 0;
+    let mut current_leader = initial_leader;
+
+    for trick in tricks.iter() {
+        if trick.len() != 4 {
+            continue;
+        }
+        let leader_for_trick = current_leader;
+
+        let dd_start = expected_declarer_tricks(
+            &remaining,
+            leader_for_trick,
+            dummy_seat,
+            trump,
+            declarer_is_ns,
+            declarer_tricks_won,
+            &void_suits,
+            config.samples,
+            &mut cutoff_cache,
+            &mut pattern_cache,
+            &mut rng,
+        )
+        .unwrap_or(declarer_tricks_won as f64);
+
+        // Play the trick for real, tracking holdings and void inferences.
+        let mut seat = leader_for_trick;
+        let mut led_suit: Option<Suit> = None;
+        let mut cards_in_trick: Vec<(usize, usize)> = Vec::new();
+        for (pos, card) in trick.iter().enumerate() {
+            if pos == 0 {
+                led_suit = Some(card.suit);
+            } else if let Some(led) = led_suit {
+                if card.suit != led && !void_suits[seat].contains(&led) {
+                    void_suits[seat].push(led);
+                }
+            }
+            remaining[seat].retain(|c| c != card);
+            if let Ok(solver_card) = bridge_card_to_solver(*card) {
+                cards_in_trick.push((seat, solver_card));
+            }
+            seat = (seat + 1) % 4;
+        }
+
+        let winner = determine_trick_winner(&cards_in_trick, trump, leader_for_trick);
+        let declarer_won = if declarer_is_ns {
+            winner == NORTH || winner == SOUTH
+        } else {
+            winner == EAST || winner == WEST
+        };
+        if declarer_won {
+            declarer_tricks_won += 1;
+        }
+
+        let dd_end = expected_declarer_tricks(
+            &remaining,
+            winner,
+            dummy_seat,
+            trump,
+            declarer_is_ns,
+            declarer_tricks_won,
+            &void_suits,
+            config.samples,
+            &mut cutoff_cache,
+            &mut pattern_cache,
+            &mut rng,
+        )
+        .unwrap_or(declarer_tricks_won as f64);
+
+        costs.push((dd_end - dd_start).abs());
+        current_leader = winner;
+    }
+
+    Ok(SingleDummyCostsResult {
+        costs,
+        initial_dd,
+        declarer_seat,
+        declarer_is_ns,
+    })
+}
+
+/// Expected final declarer tricks, as seen by `leader`: `leader`'s own hand
+/// and dummy's are exact; the other two hands are resampled `samples` times
+/// subject to their known remaining counts and void suits, and the DD
+/// result (declarer's already-won tricks plus the DD-optimal tricks
+/// available from the remaining cards) is averaged across valid samples.
+#[allow(clippy::too_many_arguments)]
+fn expected_declarer_tricks(
+    remaining: &[Vec<Card>; 4],
+    leader: usize,
+    dummy_seat: usize,
+    trump: usize,
+    declarer_is_ns: bool,
+    declarer_tricks_won_so_far: u8,
+    void_suits: &[Vec<Suit>; 4],
+    samples: usize,
+    cutoff_cache: &mut CutoffCache,
+    pattern_cache: &mut PatternCache,
+    rng: &mut impl Rng,
+) -> Option<f64> {
+    if remaining.iter().all(|h| h.is_empty()) {
+        return Some(declarer_tricks_won_so_far as f64);
+    }
+
+    // With dummy on lead, declarer is the one choosing dummy's card.
+    let observer = if leader == dummy_seat { (dummy_seat + 2) % 4 } else { leader };
+    let concealed: Vec<usize> = (0..4).filter(|s| *s != observer && *s != dummy_seat).collect();
+    if concealed.len() != 2 {
+        // `observer` coincides with dummy (shouldn't happen in practice);
+        // fall back to full double-dummy from the leader's seat.
+        let hands = Hands::from_pbn(&holdings_to_pbn(remaining))?;
+        let ns = solve_position(&hands, trump, leader, cutoff_cache, pattern_cache);
+        let rest = if declarer_is_ns { ns } else { (hands.num_tricks() as u8).saturating_sub(ns) };
+        return Some(declarer_tricks_won_so_far as f64 + rest as f64);
+    }
+    let (seat_a, seat_b) = (concealed[0], concealed[1]);
+    let count_a = remaining[seat_a].len();
+    let count_b = remaining[seat_b].len();
+    let pool: Vec<Card> = remaining[seat_a]
+        .iter()
+        .chain(remaining[seat_b].iter())
+        .copied()
+        .collect();
+
+    let mut total = 0.0f64;
+    let mut valid = 0usize;
+    for _ in 0..samples.max(1) {
+        let Some((hand_a, hand_b)) =
+            redeal_concealed(&pool, count_a, &void_suits[seat_a], count_b, &void_suits[seat_b], rng)
+        else {
+            continue;
+        };
+
+        let mut holdings: [Vec<Card>; 4] = Default::default();
+        holdings[observer] = remaining[observer].clone();
+        holdings[dummy_seat] = remaining[dummy_seat].clone();
+        holdings[seat_a] = hand_a;
+        holdings[seat_b] = hand_b;
+
+        let Some(hands) = Hands::from_pbn(&holdings_to_pbn(&holdings)) else {
+            continue;
+        };
+        let ns = solve_position(&hands, trump, leader, cutoff_cache, pattern_cache);
+        let rest = if declarer_is_ns {
+            ns
+        } else {
+            (hands.num_tricks() as u8).saturating_sub(ns)
+        };
+        total += declarer_tricks_won_so_far as f64 + rest as f64;
+        valid += 1;
+    }
+
+    if valid == 0 {
+        None
+    } else {
+        Some(total / valid as f64)
+    }
+}
+
+/// Randomly partition `pool` into two hands of sizes `count_a`/`count_b`,
+/// honoring each hand's void suits. Retries with a fresh shuffle on
+/// conflicts, since a naive single greedy pass can paint itself into a
+/// corner; gives up (returns `None`) after a bounded number of attempts.
+fn redeal_concealed(
+    pool: &[Card],
+    count_a: usize,
+    void_a: &[Suit],
+    count_b: usize,
+    void_b: &[Suit],
+    rng: &mut impl Rng,
+) -> Option<(Vec<Card>, Vec<Card>)> {
+    if pool.len() != count_a + count_b {
+        return None;
+    }
+
+    for _attempt in 0..200 {
+        let mut shuffled = pool.to_vec();
+        shuffled.shuffle(rng);
+
+        let mut hand_a = Vec::with_capacity(count_a);
+        let mut hand_b = Vec::with_capacity(count_b);
+        let mut ok = true;
+
+        for card in shuffled {
+            let a_ok = hand_a.len() < count_a && !void_a.contains(&card.suit);
+            let b_ok = hand_b.len() < count_b && !void_b.contains(&card.suit);
+            match (a_ok, b_ok) {
+                (true, true) => {
+                    if rng.gen_bool(0.5) {
+                        hand_a.push(card);
+                    } else {
+                        hand_b.push(card);
+                    }
+                }
+                (true, false) => hand_a.push(card),
+                (false, true) => hand_b.push(card),
+                (false, false) => {
+                    ok = false;
+                    break;
+                }
+            }
+        }
+
+        if ok && hand_a.len() == count_a && hand_b.len() == count_b {
+            return Some((hand_a, hand_b));
+        }
+    }
+
+    None
+}
+
+fn rank_sort_value(rank: Rank) -> u8 {
+    match rank {
+        Rank::Ace => 12,
+        Rank::King => 11,
+        Rank::Queen => 10,
+        Rank::Jack => 9,
+        Rank::Ten => 8,
+        Rank::Nine => 7,
+        Rank::Eight => 6,
+        Rank::Seven => 5,
+        Rank::Six => 4,
+        Rank::Five => 3,
+        Rank::Four => 2,
+        Rank::Three => 1,
+        Rank::Two => 0,
+    }
+}
+
+/// Render four seat holdings as a PBN deal string starting at North.
+fn holdings_to_pbn(holdings: &[Vec<Card>; 4]) -> String {
+    let suits = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+    let order = [NORTH, EAST, SOUTH, WEST];
+    let hand_strs: Vec<String> = order
+        .iter()
+        .map(|&seat| {
+            suits
+                .iter()
+                .map(|&suit| {
+                    let mut ranks: Vec<Rank> = holdings[seat]
+                        .iter()
+                        .filter(|c| c.suit == suit)
+                        .map(|c| c.rank)
+                        .collect();
+                    ranks.sort_by_key(|&r| std::cmp::Reverse(rank_sort_value(r)));
+                    ranks.iter().map(|r| r.to_char()).collect::<String>()
+                })
+                .collect::<Vec<String>>()
+                .join(".")
+        })
+        .collect();
+    format!("N:{}", hand_strs.join(" "))
+}
+
+// Helper functions, duplicated in the same shape as dd_analysis.rs's
+// private helpers since this module needs ground-truth holdings and a
+// trick-winner/solve path independent of the omniscient one.
+
+fn solve_position(
+    hands: &Hands,
+    trump: usize,
+    leader: usize,
+    cutoff_cache: &mut CutoffCache,
+    pattern_cache: &mut PatternCache,
+) -> u8 {
+    if hands.num_tricks() == 0 {
+        return 0;
+    }
+    let solver = Solver::new(*hands, trump, leader);
+    solver.solve_with_caches(cutoff_cache, pattern_cache)
+}
+
+fn parse_deal_holdings(deal_pbn: &str) -> Result<[Vec<Card>; 4], String> {
+    let (first_seat_str, hands_str) = deal_pbn
+        .split_once(':')
+        .ok_or_else(|| format!("Invalid deal (missing seat prefix): {}", deal_pbn))?;
+
+    let first_seat = match first_seat_str.trim().to_uppercase().as_str() {
+        "N" => NORTH,
+        "E" => EAST,
+        "S" => SOUTH,
+        "W" => WEST,
+        _ => return Err(format!("Invalid deal seat prefix: {}", first_seat_str)),
+    };
+
+    let suits = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+    let mut holdings: [Vec<Card>; 4] = Default::default();
+    for (i, hand_str) in hands_str.split_whitespace().enumerate() {
+        let seat = (first_seat + i) % 4;
+        for (suit, ranks) in suits.iter().zip(hand_str.split('.')) {
+            for rank_char in ranks.chars() {
+                if let Some(rank) = Rank::from_char(rank_char) {
+                    holdings[seat].push(Card::new(*suit, rank));
+                }
+            }
+        }
+    }
+
+    Ok(holdings)
+}
+
+fn parse_trump(contract: &str) -> Result<usize, String> {
+    let contract = contract.trim().to_uppercase();
+    if contract.contains("NT") || (contract.contains('N') && !contract.contains('S')) {
+        return Ok(NOTRUMP);
+    }
+    for c in contract.chars() {
+        match c {
+            'S' => return Ok(SPADE),
+            'H' => return Ok(HEART),
+            'D' => return Ok(DIAMOND),
+            'C' => return Ok(CLUB),
+            _ => continue,
+        }
+    }
+    Err(format!("Could not parse trump from: {}", contract))
+}
+
+fn parse_declarer_seat(declarer: &str) -> Result<usize, String> {
+    match declarer.trim().to_uppercase().chars().next() {
+        Some('N') => Ok(NORTH),
+        Some('E') => Ok(EAST),
+        Some('S') => Ok(SOUTH),
+        Some('W') => Ok(WEST),
+        _ => Err(format!("Invalid declarer: {}", declarer)),
+    }
+}
+
+fn parse_cardplay(cardplay: &str) -> Result<Vec<Vec<Card>>, String> {
+    let mut tricks = Vec::new();
+    for trick_str in cardplay.split('|') {
+        if trick_str.is_empty() {
+            continue;
+        }
+        let mut trick = Vec::new();
+        for card_str in trick_str.split_whitespace() {
+            trick.push(parse_card_str(card_str)?);
+        }
+        if !trick.is_empty() {
+            tricks.push(trick);
+        }
+    }
+    Ok(tricks)
+}
+
+fn parse_card_str(s: &str) -> Result<Card, String> {
+    let s = s.trim();
+    if s.len() < 2 {
+        return Err(format!("Invalid card: {}", s));
+    }
+    let mut chars = s.chars();
+    let suit_char = chars.next().unwrap();
+    let rank_char = chars.next().unwrap();
+
+    let suit = match suit_char.to_ascii_uppercase() {
+        'S' => Suit::Spades,
+        'H' => Suit::Hearts,
+        'D' => Suit::Diamonds,
+        'C' => Suit::Clubs,
+        _ => return Err(format!("Invalid suit: {}", suit_char)),
+    };
+
+    let rank = Rank::from_char(rank_char).ok_or_else(|| format!("Invalid rank: {}", rank_char))?;
+
+    Ok(Card::new(suit, rank))
+}
+
+fn bridge_card_to_solver(card: Card) -> Result<usize, String> {
+    let suit = match card.suit {
+        Suit::Spades => SPADE,
+        Suit::Hearts => HEART,
+        Suit::Diamonds => DIAMOND,
+        Suit::Clubs => CLUB,
+    };
+
+    let rank = rank_sort_value(card.rank);
+
+    Ok(card_of(suit, rank))
+}
+
+fn determine_trick_winner(cards: &[(usize, usize)], trump: usize, leader: usize) -> usize {
+    let mut winner_idx = 0;
+    let mut winning_card = cards[0].1;
+
+    for (i, (_seat, card)) in cards.iter().enumerate().skip(1) {
+        let card_suit = suit_of(*card);
+        let beats = if card_suit == suit_of(winning_card) {
+            *card < winning_card
+        } else if card_suit == trump && trump < NOTRUMP {
+            suit_of(winning_card) != trump
+        } else {
+            false
+        };
+
+        if beats {
+            winner_idx = i;
+            winning_card = *card;
+        }
+    }
+
+    (leader + winner_idx) % 4
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_trump() {
+        assert_eq!(parse_trump("4S").unwrap(), SPADE);
+        assert_eq!(parse_trump("3NT").unwrap(), NOTRUMP);
+        assert_eq!(parse_trump("6HX").unwrap(), HEART);
+        assert_eq!(parse_trump("7CXX").unwrap(), CLUB);
+        assert_eq!(parse_trump("5D").unwrap(), DIAMOND);
+        assert!(parse_trump("garbage").is_err());
+    }
+
+    #[test]
+    fn test_parse_declarer_seat() {
+        assert_eq!(parse_declarer_seat("N").unwrap(), NORTH);
+        assert_eq!(parse_declarer_seat("east").unwrap(), EAST);
+        assert_eq!(parse_declarer_seat("S").unwrap(), SOUTH);
+        assert_eq!(parse_declarer_seat("w").unwrap(), WEST);
+        assert!(parse_declarer_seat("").is_err());
+        assert!(parse_declarer_seat("Z").is_err());
+    }
+
+    #[test]
+    fn test_parse_card_str() {
+        let card = parse_card_str("SA").unwrap();
+        assert_eq!(card.suit, Suit::Spades);
+        assert_eq!(card.rank, Rank::Ace);
+
+        let card = parse_card_str("ht").unwrap();
+        assert_eq!(card.suit, Suit::Hearts);
+        assert_eq!(card.rank, Rank::Ten);
+
+        assert!(parse_card_str("S").is_err());
+        assert!(parse_card_str("ZA").is_err());
+        assert!(parse_card_str("SZ").is_err());
+    }
+
+    #[test]
+    fn test_parse_cardplay_splits_tricks_on_pipe() {
+        let tricks = parse_cardplay("SA S2 S3 S4|HK HQ H2 H3").unwrap();
+        assert_eq!(tricks.len(), 2);
+        assert_eq!(tricks[0].len(), 4);
+        assert_eq!(tricks[0][0].suit, Suit::Spades);
+        assert_eq!(tricks[1][0].rank, Rank::King);
+    }
+
+    #[test]
+    fn test_parse_cardplay_skips_empty_segments() {
+        let tricks = parse_cardplay("|SA S2 S3 S4|").unwrap();
+        assert_eq!(tricks.len(), 1);
+    }
+}
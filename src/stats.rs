@@ -0,0 +1,607 @@
+//! Player statistics engine shared by `pipeline::compute_stats` (the GUI's
+//! Stats tab) and `bbo_csv`'s own `compute_stats`/`compute_stats_aggregate`.
+//!
+//! This used to be copy-pasted independently into both binaries, which let
+//! the GUI and CLI report different numbers whenever one copy was edited
+//! and the other wasn't. Pulling it out here follows the same
+//! `edgar_defense_toolkit::<module>` pattern already used for `dd_analysis`,
+//! `dd_table`, and `scoring`.
+
+use anyhow::{Context, Result};
+use rand::Rng;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Below this many total deals a player is pooled into the replacement-level
+/// defending rate instead of judged individually -- too little data to be
+/// suspicious on their own, but enough in aggregate to define "unremarkable".
+pub const MIN_DEALS_FOR_SUSPICIOUS: u64 = 50;
+
+#[derive(Default, Clone)]
+pub struct PlayerStats {
+    pub name: String,
+    // Total deals where this player participated (including as dummy)
+    pub total_deals: u64,
+    // Declaring stats
+    pub declaring_plays: u64,
+    pub declaring_errors: u64,
+    pub declaring_deals: u64,
+    // Defending stats
+    pub defending_plays: u64,
+    pub defending_errors: u64,
+    pub defending_deals: u64,
+    // Sum of DD trick cost (not just a hit count) over declaring/defending
+    // plays, from `DD_<seat>_CostSum`. Zero on input CSVs from an
+    // `analyze-dd` run that predates that column.
+    pub declaring_cost_sum: u64,
+    pub defending_cost_sum: u64,
+    // Sum and count of per-play p_loss, used to compute the logistic
+    // "accuracy" score. Populated from a walk of the `DD_Analysis` cost
+    // string, independent of the coarser plays/errors counters above.
+    pub declaring_ploss_sum: f64,
+    pub declaring_ploss_count: u64,
+    pub defending_ploss_sum: f64,
+    pub defending_ploss_count: u64,
+    // Error counts by `DD_Error_Categories` rule id, e.g. "opening-lead" ->
+    // count. Populated only when the input CSV has that column.
+    pub error_categories: HashMap<String, u64>,
+    // One entry per deal this player had a role in (declaring or
+    // defending), kept alongside the running totals above so
+    // `bootstrap_def_minus_decl` can resample deals with replacement
+    // instead of assuming the aggregate counts are normally distributed.
+    pub observations: Vec<DealObservation>,
+}
+
+/// Which side of the board a [`DealObservation`] was recorded on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayRole {
+    Declaring,
+    Defending,
+}
+
+/// One player's plays/errors on one deal, in one role -- the unit the
+/// bootstrap resamples, since deals (not individual card plays) are the
+/// independent observations here.
+#[derive(Debug, Clone, Copy)]
+pub struct DealObservation {
+    pub role: PlayRole,
+    pub plays: u64,
+    pub errors: u64,
+    /// This deal's position in the input CSV's row order, used as a clock
+    /// for recency weighting -- `weighted_error_stats` takes the age of a
+    /// deal as the gap between its `seq` and the most recent `seq` this
+    /// player appears in, not a wall-clock date (these CSVs don't reliably
+    /// have one).
+    pub seq: u64,
+}
+
+impl PlayerStats {
+    pub fn new(name: &str) -> Self {
+        PlayerStats {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn declaring_error_rate(&self) -> f64 {
+        if self.declaring_plays == 0 {
+            0.0
+        } else {
+            self.declaring_errors as f64 / self.declaring_plays as f64 * 100.0
+        }
+    }
+
+    pub fn defending_error_rate(&self) -> f64 {
+        if self.defending_plays == 0 {
+            0.0
+        } else {
+            self.defending_errors as f64 / self.defending_plays as f64 * 100.0
+        }
+    }
+
+    pub fn total_deals(&self) -> u64 {
+        self.total_deals
+    }
+
+    /// Average DD trick cost per defending play. The "league minimum"
+    /// replacement level this is compared against is
+    /// `replacement_defending_rate`'s pooled rate over low-frequency players.
+    pub fn defending_cost_rate(&self) -> f64 {
+        if self.defending_plays == 0 {
+            0.0
+        } else {
+            self.defending_cost_sum as f64 / self.defending_plays as f64
+        }
+    }
+
+    /// Tricks of Defense Above Replacement: how many more tricks this player
+    /// saved on defense, over all their defending plays, than a
+    /// replacement-level defender would have given `replacement_rate` (a
+    /// pooled cost-per-play rate, from `replacement_defending_rate`).
+    pub fn defense_above_replacement(&self, replacement_rate: f64) -> f64 {
+        (replacement_rate - self.defending_cost_rate()) * self.defending_plays as f64
+    }
+
+    /// Win-probability-weighted declaring accuracy, `100 * (1 - mean(p_loss))`
+    /// over every declaring play's p_loss. `None` when no declaring play
+    /// had per-trick cost data (e.g. the input predates `DD_Analysis`).
+    pub fn declaring_accuracy(&self) -> Option<f64> {
+        if self.declaring_ploss_count == 0 {
+            return None;
+        }
+        Some(100.0 * (1.0 - self.declaring_ploss_sum / self.declaring_ploss_count as f64))
+    }
+
+    /// Win-probability-weighted defending accuracy -- see `declaring_accuracy`.
+    pub fn defending_accuracy(&self) -> Option<f64> {
+        if self.defending_ploss_count == 0 {
+            return None;
+        }
+        Some(100.0 * (1.0 - self.defending_ploss_sum / self.defending_ploss_count as f64))
+    }
+
+    /// Merge another player's stats into this one (for "Field" aggregation)
+    pub fn merge(&mut self, other: &PlayerStats) {
+        self.total_deals += other.total_deals;
+        self.declaring_plays += other.declaring_plays;
+        self.declaring_errors += other.declaring_errors;
+        self.declaring_deals += other.declaring_deals;
+        self.defending_plays += other.defending_plays;
+        self.defending_errors += other.defending_errors;
+        self.defending_deals += other.defending_deals;
+        self.declaring_cost_sum += other.declaring_cost_sum;
+        self.defending_cost_sum += other.defending_cost_sum;
+        self.declaring_ploss_sum += other.declaring_ploss_sum;
+        self.declaring_ploss_count += other.declaring_ploss_count;
+        self.defending_ploss_sum += other.defending_ploss_sum;
+        self.defending_ploss_count += other.defending_ploss_count;
+        for (category, count) in &other.error_categories {
+            *self.error_categories.entry(category.clone()).or_insert(0) += count;
+        }
+        self.observations.extend(other.observations.iter().copied());
+    }
+
+    /// 95% Wilson score confidence interval for an error rate, in percent.
+    ///
+    /// Unlike the normal (Wald) approximation, this stays inside [0, 100]
+    /// and is well-behaved for small `n` or `p` near 0 or 1 -- e.g. a player
+    /// with 0 observed errors gets an honest upper bound instead of a
+    /// degenerate zero-width interval. Returns `(0.0, 0.0)` for `n == 0`.
+    pub fn wilson_ci(errors: u64, plays: u64) -> (f64, f64) {
+        if plays == 0 {
+            return (0.0, 0.0);
+        }
+        let n = plays as f64;
+        let p = errors as f64 / n;
+        let z = 1.96;
+        let z2 = z * z;
+        let denom = 1.0 + z2 / n;
+        let center = (p + z2 / (2.0 * n)) / denom;
+        let margin = (z / denom) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+        let lower = ((center - margin) * 100.0).max(0.0);
+        let upper = ((center + margin) * 100.0).min(100.0);
+        (lower, upper)
+    }
+
+    /// 95% Wilson score confidence interval for the declaring error rate, in percent.
+    pub fn declaring_ci(&self) -> (f64, f64) {
+        Self::wilson_ci(self.declaring_errors, self.declaring_plays)
+    }
+
+    /// 95% Wilson score confidence interval for the defending error rate, in percent.
+    pub fn defending_ci(&self) -> (f64, f64) {
+        Self::wilson_ci(self.defending_errors, self.defending_plays)
+    }
+
+    /// Calculate the Def - Decl difference (expected to be positive for honest players)
+    pub fn def_minus_decl(&self) -> f64 {
+        self.defending_error_rate() - self.declaring_error_rate()
+    }
+
+    /// This player's error categories sorted by count descending (ties broken
+    /// by rule id), for the report's per-player breakdown. Empty when the
+    /// input CSV had no `DD_Error_Categories` column.
+    pub fn top_categories(&self, n: usize) -> Vec<(&str, u64)> {
+        let mut categories: Vec<(&str, u64)> = self
+            .error_categories
+            .iter()
+            .map(|(id, &count)| (id.as_str(), count))
+            .collect();
+        categories.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        categories.truncate(n);
+        categories
+    }
+
+    /// Two-proportion z-test comparing this player's declaring error rate to
+    /// another player's (or the pooled FIELD remainder's). Returns
+    /// `(z, two-sided p-value)`.
+    pub fn declaring_vs(&self, other: &PlayerStats) -> (f64, f64) {
+        two_proportion_z_test(self.declaring_errors, self.declaring_plays, other.declaring_errors, other.declaring_plays)
+    }
+
+    /// Two-proportion z-test comparing this player's defending error rate to
+    /// another player's (or the pooled FIELD remainder's). Returns
+    /// `(z, two-sided p-value)`.
+    pub fn defending_vs(&self, other: &PlayerStats) -> (f64, f64) {
+        two_proportion_z_test(self.defending_errors, self.defending_plays, other.defending_errors, other.defending_plays)
+    }
+
+    /// Standard error for the Def - Decl difference
+    pub fn diff_se(&self) -> f64 {
+        if self.declaring_plays < 30 || self.defending_plays < 30 {
+            return f64::NAN;
+        }
+        let p1 = self.declaring_errors as f64 / self.declaring_plays as f64;
+        let n1 = self.declaring_plays as f64;
+        let p2 = self.defending_errors as f64 / self.defending_plays as f64;
+        let n2 = self.defending_plays as f64;
+        ((p1 * (1.0 - p1) / n1) + (p2 * (1.0 - p2) / n2)).sqrt() * 100.0
+    }
+}
+
+/// Pooled defending cost-per-play rate over every player below
+/// `MIN_DEALS_FOR_SUSPICIOUS` deals -- a "league minimum" replacement level
+/// for Defense Above Replacement, analogous to a WAR replacement player.
+/// Returns `0.0` if no low-frequency player has any defending plays.
+pub fn replacement_defending_rate(players: &[PlayerStats]) -> f64 {
+    let mut cost_sum = 0u64;
+    let mut plays = 0u64;
+    for p in players {
+        if p.total_deals() < MIN_DEALS_FOR_SUSPICIOUS {
+            cost_sum += p.defending_cost_sum;
+            plays += p.defending_plays;
+        }
+    }
+    if plays == 0 {
+        0.0
+    } else {
+        cost_sum as f64 / plays as f64
+    }
+}
+
+/// Z-test comparing two players' Def-Decl differences.
+/// Returns (z-score, p-value) for one-tailed test.
+pub fn z_test_diff_vs_baseline(subject: &PlayerStats, baseline: &PlayerStats) -> (f64, f64) {
+    let diff_subj = subject.def_minus_decl();
+    let diff_base = baseline.def_minus_decl();
+
+    let se_subj = subject.diff_se();
+    let se_base = baseline.diff_se();
+
+    if se_subj.is_nan() || se_base.is_nan() {
+        return (f64::NAN, f64::NAN);
+    }
+
+    let se_combined = (se_subj.powi(2) + se_base.powi(2)).sqrt();
+    let z = (diff_subj - diff_base) / se_combined;
+    let p = 0.5 * (1.0 + erf(z / std::f64::consts::SQRT_2));
+
+    (z, p)
+}
+
+/// Exponential recency-decay weight for a deal `age` deals old, given a
+/// `half_life` in deals: `0.5^(age/half_life)`.
+pub fn decay_weight(age: f64, half_life: f64) -> f64 {
+    (-(std::f64::consts::LN_2 / half_life) * age).exp()
+}
+
+/// Recency-weighted error rate (in percent) and effective sample size for
+/// one player's observations in `role`. Returns `None` if `role` has no
+/// observations or zero weighted plays.
+pub fn weighted_error_stats(observations: &[DealObservation], role: PlayRole, half_life: f64) -> Option<(f64, f64)> {
+    let max_seq = observations.iter().filter(|o| o.role == role).map(|o| o.seq).max()?;
+
+    let mut w_sum = 0.0;
+    let mut w2_sum = 0.0;
+    let mut w_plays = 0.0;
+    let mut w_errors = 0.0;
+
+    for obs in observations.iter().filter(|o| o.role == role) {
+        let age = (max_seq - obs.seq) as f64;
+        let w = decay_weight(age, half_life);
+        w_sum += w;
+        w2_sum += w * w;
+        w_plays += w * obs.plays as f64;
+        w_errors += w * obs.errors as f64;
+    }
+
+    if w_plays <= 0.0 {
+        return None;
+    }
+
+    Some((100.0 * w_errors / w_plays, w_sum * w_sum / w2_sum))
+}
+
+/// [`PlayerStats::wilson_ci`] parametrized on a proportion and a (possibly
+/// fractional) sample size, for recency-weighted rates where the effective
+/// sample size is a Kish ESS rather than an integer play count.
+pub fn wilson_ci_f64(rate_pct: f64, n: f64) -> (f64, f64) {
+    if n <= 0.0 {
+        return (0.0, 0.0);
+    }
+    let p = rate_pct / 100.0;
+    let z = 1.96;
+    let z2 = z * z;
+    let denom = 1.0 + z2 / n;
+    let center = (p + z2 / (2.0 * n)) / denom;
+    let margin = (z / denom) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+    let lower = ((center - margin) * 100.0).max(0.0);
+    let upper = ((center + margin) * 100.0).min(100.0);
+    (lower, upper)
+}
+
+/// [`PlayerStats::diff_se`] parametrized on rate/ESS pairs instead of a
+/// `PlayerStats`'s integer counters, for the recency-weighted Def-Decl gap.
+pub fn diff_se_weighted(decl_rate: f64, decl_ess: f64, def_rate: f64, def_ess: f64) -> f64 {
+    if decl_ess < 30.0 || def_ess < 30.0 {
+        return f64::NAN;
+    }
+    let p1 = decl_rate / 100.0;
+    let p2 = def_rate / 100.0;
+    ((p1 * (1.0 - p1) / decl_ess) + (p2 * (1.0 - p2) / def_ess)).sqrt() * 100.0
+}
+
+/// `z_test_diff_vs_baseline` parametrized on rate/ESS pairs instead of two
+/// `PlayerStats`, for comparing recency-weighted Def-Decl gaps.
+#[allow(clippy::too_many_arguments)]
+pub fn z_test_diff_vs_baseline_weighted(
+    subj_decl_rate: f64,
+    subj_decl_ess: f64,
+    subj_def_rate: f64,
+    subj_def_ess: f64,
+    base_decl_rate: f64,
+    base_decl_ess: f64,
+    base_def_rate: f64,
+    base_def_ess: f64,
+) -> (f64, f64) {
+    let diff_subj = subj_def_rate - subj_decl_rate;
+    let diff_base = base_def_rate - base_decl_rate;
+
+    let se_subj = diff_se_weighted(subj_decl_rate, subj_decl_ess, subj_def_rate, subj_def_ess);
+    let se_base = diff_se_weighted(base_decl_rate, base_decl_ess, base_def_rate, base_def_ess);
+
+    if se_subj.is_nan() || se_base.is_nan() {
+        return (f64::NAN, f64::NAN);
+    }
+
+    let se_combined = (se_subj.powi(2) + se_base.powi(2)).sqrt();
+    let z = (diff_subj - diff_base) / se_combined;
+    let p = 0.5 * (1.0 + erf(z / std::f64::consts::SQRT_2));
+
+    (z, p)
+}
+
+/// Re-aggregates a (possibly resampled) slice of [`DealObservation`]s into
+/// the Def-Decl statistic, the same formula as `PlayerStats::def_minus_decl`
+/// but computed from raw observations instead of a running total.
+pub fn def_minus_decl_from_observations(observations: &[DealObservation]) -> f64 {
+    let mut decl_plays = 0u64;
+    let mut decl_errors = 0u64;
+    let mut def_plays = 0u64;
+    let mut def_errors = 0u64;
+
+    for obs in observations {
+        match obs.role {
+            PlayRole::Declaring => {
+                decl_plays += obs.plays;
+                decl_errors += obs.errors;
+            }
+            PlayRole::Defending => {
+                def_plays += obs.plays;
+                def_errors += obs.errors;
+            }
+        }
+    }
+
+    let decl_rate = if decl_plays == 0 { 0.0 } else { decl_errors as f64 / decl_plays as f64 * 100.0 };
+    let def_rate = if def_plays == 0 { 0.0 } else { def_errors as f64 / def_plays as f64 * 100.0 };
+    def_rate - decl_rate
+}
+
+/// A bootstrap confidence interval and empirical p-value for a player's
+/// Def-Decl statistic.
+pub struct BootstrapResult {
+    /// 2.5th/97.5th percentile of the resampled Def-Decl statistic.
+    pub ci_lo: f64,
+    pub ci_hi: f64,
+    /// Fraction of resamples whose statistic is at or above `field_stat` --
+    /// a one-tailed, distribution-free p-value in place of
+    /// `z_test_diff_vs_baseline`'s normal approximation.
+    pub p_value: f64,
+}
+
+/// Resamples `observations` with replacement `iterations` times, recomputing
+/// the Def-Decl statistic each time, to get a bootstrap confidence interval
+/// and p-value that don't assume a Gaussian sampling distribution. Returns
+/// `None` if the player has no recorded deals.
+pub fn bootstrap_def_minus_decl(
+    observations: &[DealObservation],
+    field_stat: f64,
+    iterations: u64,
+) -> Option<BootstrapResult> {
+    let n = observations.len();
+    if n == 0 || iterations == 0 {
+        return None;
+    }
+
+    let mut resampled_stats: Vec<f64> = (0..iterations)
+        .into_par_iter()
+        .map(|_| {
+            let mut rng = rand::thread_rng();
+            let resample: Vec<DealObservation> =
+                (0..n).map(|_| observations[rng.gen_range(0..n)]).collect();
+            def_minus_decl_from_observations(&resample)
+        })
+        .collect();
+
+    resampled_stats.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let lo_idx = ((resampled_stats.len() as f64) * 0.025) as usize;
+    let hi_idx = (((resampled_stats.len() as f64) * 0.975) as usize).min(resampled_stats.len() - 1);
+    let at_or_above = resampled_stats.iter().filter(|&&s| s >= field_stat).count();
+
+    Some(BootstrapResult {
+        ci_lo: resampled_stats[lo_idx],
+        ci_hi: resampled_stats[hi_idx],
+        p_value: at_or_above as f64 / resampled_stats.len() as f64,
+    })
+}
+
+/// Two-proportion z-test comparing two raw error rates: pooled proportion
+/// `p`, standard error `se = sqrt(p*(1-p)*(1/n1 + 1/n2))`,
+/// `z = (p1 - p2) / se`, and a two-sided p-value from the normal CDF.
+/// Returns `(NaN, NaN)` if either sample is empty.
+pub fn two_proportion_z_test(errors1: u64, plays1: u64, errors2: u64, plays2: u64) -> (f64, f64) {
+    if plays1 == 0 || plays2 == 0 {
+        return (f64::NAN, f64::NAN);
+    }
+    let n1 = plays1 as f64;
+    let n2 = plays2 as f64;
+    let p1 = errors1 as f64 / n1;
+    let p2 = errors2 as f64 / n2;
+    let p_pooled = (errors1 + errors2) as f64 / (n1 + n2);
+    let se = (p_pooled * (1.0 - p_pooled) * (1.0 / n1 + 1.0 / n2)).sqrt();
+    if se == 0.0 {
+        return (0.0, 1.0);
+    }
+    let z = (p1 - p2) / se;
+    let p_value = 2.0 * (1.0 - 0.5 * (1.0 + erf(z.abs() / std::f64::consts::SQRT_2)));
+    (z, p_value)
+}
+
+/// Error function approximation (for p-value calculation)
+pub fn erf(x: f64) -> f64 {
+    let a1 = 0.254829592;
+    let a2 = -0.284496736;
+    let a3 = 1.421413741;
+    let a4 = -1.453152027;
+    let a5 = 1.061405429;
+    let p = 0.3275911;
+
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+    let t = 1.0 / (1.0 + p * x);
+    let y = 1.0 - (((((a5 * t + a4) * t) + a3) * t + a2) * t + a1) * t * (-x * x).exp();
+    sign * y
+}
+
+/// Schema version for the stats JSON export -- bump whenever the shape of
+/// the exported fields changes, so downstream dashboards can tell old
+/// exports apart from new ones.
+pub const STATS_EXPORT_SCHEMA_VERSION: u32 = 1;
+
+/// A raw `(count/total)` fraction alongside the percentage it implies.
+#[derive(serde::Serialize)]
+pub struct RateExport {
+    pub count: u64,
+    pub total: u64,
+    pub pct: f64,
+}
+
+#[derive(serde::Serialize)]
+pub struct PlayerStatsExport {
+    pub name: String,
+    pub total_deals: u64,
+    pub declaring_deals: u64,
+    pub defending_deals: u64,
+    pub declaring_error_rate: RateExport,
+    pub declaring_ci_lo: f64,
+    pub declaring_ci_hi: f64,
+    pub defending_error_rate: RateExport,
+    pub defending_ci_lo: f64,
+    pub defending_ci_hi: f64,
+    pub declaring_accuracy_pct: Option<f64>,
+    pub defending_accuracy_pct: Option<f64>,
+    pub def_minus_decl_pct: f64,
+    pub defense_above_replacement: Option<f64>,
+    pub z_vs_field: Option<f64>,
+    pub p_vs_field: Option<f64>,
+}
+
+#[derive(serde::Serialize)]
+pub struct StatsExport {
+    pub schema_version: u32,
+    pub field_descriptions: HashMap<&'static str, &'static str>,
+    pub field_baseline: PlayerStatsExport,
+    pub players: Vec<PlayerStatsExport>,
+}
+
+pub fn stats_field_descriptions() -> HashMap<&'static str, &'static str> {
+    HashMap::from([
+        ("total_deals", "Number of deals this player appeared in, in any seat"),
+        ("declaring_deals", "Number of deals this player was declarer"),
+        ("defending_deals", "Number of deals this player was a defender"),
+        ("declaring_error_rate", "Fraction of declaring plays with DD cost > 0, and the percentage it implies"),
+        ("declaring_ci_lo", "Lower bound of the 95% Wilson score CI for declaring_error_rate.pct"),
+        ("declaring_ci_hi", "Upper bound of the 95% Wilson score CI for declaring_error_rate.pct"),
+        ("defending_error_rate", "Fraction of defending plays with DD cost > 0, and the percentage it implies"),
+        ("defending_ci_lo", "Lower bound of the 95% Wilson score CI for defending_error_rate.pct"),
+        ("defending_ci_hi", "Upper bound of the 95% Wilson score CI for defending_error_rate.pct"),
+        ("declaring_accuracy_pct", "Win-probability-weighted declaring accuracy, 100 * (1 - mean p_loss); null if no per-play cost data"),
+        ("defending_accuracy_pct", "Win-probability-weighted defending accuracy, 100 * (1 - mean p_loss); null if no per-play cost data"),
+        ("def_minus_decl_pct", "defending_error_rate.pct - declaring_error_rate.pct; positive is expected for honest play"),
+        ("defense_above_replacement", "Tricks saved on defense vs. the pooled replacement-level defending rate; null with no defending plays"),
+        ("z_vs_field", "Z-score of this player's def_minus_decl_pct against the FIELD baseline; null below 30 plays in either role"),
+        ("p_vs_field", "One-tailed p-value for z_vs_field; null when z_vs_field is null"),
+    ])
+}
+
+/// Builds one player's (or the FIELD baseline's) export row, including its
+/// z-score/p-value against `baseline` (pass the FIELD stats themselves as
+/// `baseline` for the FIELD row, which always compares as `z = 0`).
+pub fn player_stats_export(player: &PlayerStats, baseline: &PlayerStats, replacement_rate: f64) -> PlayerStatsExport {
+    let (decl_ci_lo, decl_ci_hi) = player.declaring_ci();
+    let (def_ci_lo, def_ci_hi) = player.defending_ci();
+    let (z, p) = z_test_diff_vs_baseline(player, baseline);
+
+    PlayerStatsExport {
+        name: player.name.clone(),
+        total_deals: player.total_deals(),
+        declaring_deals: player.declaring_deals,
+        defending_deals: player.defending_deals,
+        declaring_error_rate: RateExport {
+            count: player.declaring_errors,
+            total: player.declaring_plays,
+            pct: player.declaring_error_rate(),
+        },
+        declaring_ci_lo: decl_ci_lo,
+        declaring_ci_hi: decl_ci_hi,
+        defending_error_rate: RateExport {
+            count: player.defending_errors,
+            total: player.defending_plays,
+            pct: player.defending_error_rate(),
+        },
+        defending_ci_lo: def_ci_lo,
+        defending_ci_hi: def_ci_hi,
+        declaring_accuracy_pct: player.declaring_accuracy(),
+        defending_accuracy_pct: player.defending_accuracy(),
+        def_minus_decl_pct: player.def_minus_decl(),
+        defense_above_replacement: if player.defending_plays > 0 {
+            Some(player.defense_above_replacement(replacement_rate))
+        } else {
+            None
+        },
+        z_vs_field: if z.is_nan() { None } else { Some(z) },
+        p_vs_field: if p.is_nan() { None } else { Some(p) },
+    }
+}
+
+/// Writes every player's stats (plus the FIELD baseline) as structured JSON
+/// to `path` -- for consumers (a dashboard, a report generator) that want
+/// raw counts and field descriptions instead of a formatted table.
+pub fn write_stats_export(path: &Path, players: &[PlayerStats], field_stats: &PlayerStats) -> Result<()> {
+    let replacement_rate = replacement_defending_rate(players);
+    let export = StatsExport {
+        schema_version: STATS_EXPORT_SCHEMA_VERSION,
+        field_descriptions: stats_field_descriptions(),
+        field_baseline: player_stats_export(field_stats, field_stats, replacement_rate),
+        players: players
+            .iter()
+            .map(|p| player_stats_export(p, field_stats, replacement_rate))
+            .collect(),
+    };
+    let file = std::fs::File::create(path).context("Failed to create export JSON")?;
+    serde_json::to_writer_pretty(file, &export).context("Failed to write export JSON")?;
+    Ok(())
+}
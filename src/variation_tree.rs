@@ -0,0 +1,323 @@
+//! Annotated variation tree for cardplay, modeled loosely on the SGF node
+//! format: each played card is a node that can carry a free-text comment, a
+//! move-quality annotation against the double-dummy optimum, and child
+//! variations -- the actual continuation plus, at a costly card, a sibling
+//! node for the suggested alternative.
+//!
+//! `compute_dd_costs` already computes everything a node needs per card
+//! (`costs`, `seats`, `best_cards`); [`build_variation_tree`] just walks the
+//! actual line trick by trick and turns that flat per-card data into a tree
+//! a reviewer can render, instead of a table of numbers.
+
+use crate::dd_analysis::DdCostsResult;
+use bridge_parsers::Card;
+
+/// How a played card compares to the double-dummy optimum at that point.
+///
+/// Deliberately separate from `dd_analysis::Severity`: `Severity` classifies
+/// *reported errors* against a configurable threshold for a diagnostics
+/// feed and has no "this was fine" variant, while `MoveQuality` tags every
+/// node in the tree -- including optimal ones -- against a fixed cost
+/// mapping, so the tree always has something to render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveQuality {
+    Optimal,
+    Inaccuracy,
+    Mistake,
+    Blunder,
+}
+
+impl MoveQuality {
+    /// Classify a DD cost (tricks lost versus best play at this position).
+    pub fn from_cost(cost: u8) -> Self {
+        match cost {
+            0 => MoveQuality::Optimal,
+            1 => MoveQuality::Inaccuracy,
+            2 => MoveQuality::Mistake,
+            _ => MoveQuality::Blunder,
+        }
+    }
+}
+
+/// One played card in the variation tree.
+#[derive(Debug, Clone)]
+pub struct VariationNode {
+    /// The card played at this node.
+    pub card: Card,
+    /// Seat that played it (`bridge_solver::{NORTH, EAST, SOUTH, WEST}`).
+    pub seat: usize,
+    /// Trick number (1-based).
+    pub trick_num: usize,
+    /// Card position within the trick (0=lead, 1=2nd, 2=3rd, 3=4th).
+    pub card_position: usize,
+    /// DD cost of this card versus best play at this position.
+    pub cost: u8,
+    /// Annotation derived from `cost` via [`MoveQuality::from_cost`].
+    pub quality: MoveQuality,
+    /// Free-text note a reviewer can attach or edit. Auto-populated with a
+    /// short description when `cost > 0`, left blank otherwise.
+    pub comment: Option<String>,
+    /// Whether this node lies on the actual line played, as opposed to
+    /// being a suggested alternative variation.
+    pub is_main_line: bool,
+    /// Child nodes: the actual-line continuation (if any) always comes
+    /// first, followed by at most one sibling variation suggesting the
+    /// alternative card that would have tied the DD optimum here.
+    pub children: Vec<VariationNode>,
+}
+
+/// The root of an annotated variation tree for one board's cardplay.
+#[derive(Debug, Clone)]
+pub struct VariationTree {
+    /// The opening lead, and the root of the whole actual-line chain.
+    pub root: Option<VariationNode>,
+}
+
+/// Build an annotated variation tree from the actual cardplay and the DD
+/// costs already computed for it by `compute_dd_costs`.
+///
+/// `tricks` must have the same trick/card shape as `costs.costs` (i.e. the
+/// actual cards played, as returned by `dd_analysis::parse_cardplay`).
+pub fn build_variation_tree(tricks: &[Vec<Card>], costs: &DdCostsResult) -> VariationTree {
+    let mut positions: Vec<(usize, usize)> = Vec::new();
+    for (trick_idx, trick_costs) in costs.costs.iter().enumerate() {
+        for card_idx in 0..trick_costs.len() {
+            positions.push((trick_idx, card_idx));
+        }
+    }
+
+    VariationTree {
+        root: build_chain(tricks, costs, &positions, 0),
+    }
+}
+
+/// Build the main-line node at `positions[i]` together with its suggested
+/// alternative sibling (if the card was suboptimal) and its continuation.
+fn build_chain(
+    tricks: &[Vec<Card>],
+    costs: &DdCostsResult,
+    positions: &[(usize, usize)],
+    i: usize,
+) -> Option<VariationNode> {
+    let (trick_idx, card_idx) = *positions.get(i)?;
+    let card = *tricks.get(trick_idx)?.get(card_idx)?;
+    let cost = costs.costs[trick_idx][card_idx];
+    let seat = costs.seats[trick_idx][card_idx];
+    let quality = MoveQuality::from_cost(cost);
+
+    let mut children = Vec::new();
+    if let Some(next) = build_chain(tricks, costs, positions, i + 1) {
+        children.push(next);
+    }
+    if let Some(alternative) = suggested_alternative(costs, trick_idx, card_idx, seat) {
+        children.push(alternative);
+    }
+
+    Some(VariationNode {
+        card,
+        seat,
+        trick_num: trick_idx + 1,
+        card_position: card_idx,
+        cost,
+        quality,
+        comment: auto_comment(cost, &costs.best_cards[trick_idx][card_idx]),
+        is_main_line: true,
+        children,
+    })
+}
+
+/// A leaf node for the first card in `best_cards` at this position, if the
+/// play was suboptimal and an alternative was enumerated. There's no
+/// continuation beyond it: `compute_dd_costs` only enumerates the tying
+/// cards themselves, not a full replacement line from that point on.
+fn suggested_alternative(
+    costs: &DdCostsResult,
+    trick_idx: usize,
+    card_idx: usize,
+    seat: usize,
+) -> Option<VariationNode> {
+    let cost = costs.costs[trick_idx][card_idx];
+    if cost == 0 {
+        return None;
+    }
+    let alternative = *costs.best_cards[trick_idx][card_idx].first()?;
+
+    Some(VariationNode {
+        card: alternative,
+        seat,
+        trick_num: trick_idx + 1,
+        card_position: card_idx,
+        cost: 0,
+        quality: MoveQuality::Optimal,
+        comment: Some("Suggested alternative tying the DD optimum".to_string()),
+        is_main_line: false,
+        children: Vec::new(),
+    })
+}
+
+fn auto_comment(cost: u8, best_cards: &[Card]) -> Option<String> {
+    if cost == 0 {
+        return None;
+    }
+    if best_cards.is_empty() {
+        Some(format!("Costs {} trick(s) versus best play", cost))
+    } else {
+        let suggestion = card_str(best_cards[0]);
+        Some(format!(
+            "Costs {} trick(s) versus best play (e.g. {})",
+            cost, suggestion
+        ))
+    }
+}
+
+fn card_str(card: Card) -> String {
+    format!("{}{}", card.suit.to_char(), card.rank.to_char())
+}
+
+fn seat_name(seat: usize) -> &'static str {
+    match seat {
+        bridge_solver::NORTH => "North",
+        bridge_solver::EAST => "East",
+        bridge_solver::SOUTH => "South",
+        bridge_solver::WEST => "West",
+        _ => "?",
+    }
+}
+
+impl VariationTree {
+    /// Render the tree as an indented text outline: the main line down the
+    /// left margin, with suggested alternatives indented as `alt:` branches
+    /// underneath the node they diverge from.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        if let Some(root) = &self.root {
+            render_node(root, 0, &mut out);
+        }
+        out
+    }
+}
+
+fn render_node(node: &VariationNode, depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    let marker = if node.is_main_line { "" } else { "alt: " };
+    out.push_str(&format!(
+        "{indent}{marker}T{trick}.{pos} {seat} {card} [{quality:?}]",
+        indent = indent,
+        marker = marker,
+        trick = node.trick_num,
+        pos = node.card_position,
+        seat = seat_name(node.seat),
+        card = card_str(node.card),
+        quality = node.quality,
+    ));
+    if let Some(comment) = &node.comment {
+        out.push_str(&format!(" -- {}", comment));
+    }
+    out.push('\n');
+
+    for child in node.children.iter().filter(|c| !c.is_main_line) {
+        render_node(child, depth + 1, out);
+    }
+    for child in node.children.iter().filter(|c| c.is_main_line) {
+        render_node(child, depth, out);
+    }
+}
+
+/// JSON export of the variation tree for a web-based board-replay viewer,
+/// mirroring `DdCostsResult::to_replay_json`'s feature gating.
+#[cfg(feature = "serde")]
+mod json {
+    use super::*;
+
+    #[derive(serde::Serialize)]
+    struct TreeExport {
+        card: String,
+        seat: String,
+        trick_num: usize,
+        card_position: usize,
+        cost: u8,
+        quality: String,
+        comment: Option<String>,
+        is_main_line: bool,
+        children: Vec<TreeExport>,
+    }
+
+    fn export_node(node: &VariationNode) -> TreeExport {
+        TreeExport {
+            card: card_str(node.card),
+            seat: seat_name(node.seat).to_string(),
+            trick_num: node.trick_num,
+            card_position: node.card_position,
+            cost: node.cost,
+            quality: format!("{:?}", node.quality),
+            comment: node.comment.clone(),
+            is_main_line: node.is_main_line,
+            children: node.children.iter().map(export_node).collect(),
+        }
+    }
+
+    impl VariationTree {
+        /// Serialize the full tree -- main line, branch points, and
+        /// comments -- as JSON.
+        pub fn to_json(&self) -> String {
+            let export = self.root.as_ref().map(export_node);
+            serde_json::to_string(&export).unwrap_or_default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bridge_parsers::{Rank, Suit};
+
+    fn card(suit: Suit, rank: Rank) -> Card {
+        Card::new(suit, rank)
+    }
+
+    #[test]
+    fn flags_a_suboptimal_card_with_a_suggested_alternative() {
+        let tricks = vec![vec![
+            card(Suit::Spades, Rank::Two),
+            card(Suit::Spades, Rank::Three),
+            card(Suit::Spades, Rank::Four),
+            card(Suit::Spades, Rank::Five),
+        ]];
+
+        let costs = DdCostsResult {
+            costs: vec![vec![0, 1, 0, 0]],
+            dd_timeline: vec![vec![(9, 9), (9, 8), (8, 8), (8, 8)]],
+            seats: vec![vec![
+                bridge_solver::NORTH,
+                bridge_solver::EAST,
+                bridge_solver::SOUTH,
+                bridge_solver::WEST,
+            ]],
+            best_cards: vec![vec![
+                vec![],
+                vec![card(Suit::Spades, Rank::King)],
+                vec![],
+                vec![],
+            ]],
+            contract: "3NT".to_string(),
+            initial_dd: 9,
+            declarer_seat: bridge_solver::SOUTH,
+            declarer_is_ns: true,
+        };
+
+        let tree = build_variation_tree(&tricks, &costs);
+        let root = tree.root.unwrap();
+        assert!(root.is_main_line);
+        assert_eq!(root.children.len(), 1);
+
+        let second = &root.children[0];
+        assert_eq!(second.quality, MoveQuality::Inaccuracy);
+        assert_eq!(second.children.len(), 2);
+        assert!(second.children[0].is_main_line);
+        assert!(!second.children[1].is_main_line);
+        assert_eq!(second.children[1].card, card(Suit::Spades, Rank::King));
+
+        let rendered = tree.render();
+        assert!(rendered.contains("alt:"));
+    }
+}